@@ -0,0 +1,19 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Error {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    /// Reporter is not on the allowlist.
+    ReporterNotAllowed = 4,
+    ReporterAlreadyAllowed = 5,
+    RecordNotFound = 6,
+    /// Only the member a record is about may dispute it.
+    NotRecordMember = 7,
+    /// Record is already disputed, voided or upheld after a dispute.
+    RecordNotActive = 8,
+    RecordNotDisputed = 9,
+    InvalidPageSize = 10,
+}
@@ -0,0 +1,79 @@
+use ahjoor_types::credit::CreditOutcome;
+use soroban_sdk::{contractevent, Address, BytesN, Env};
+
+/// Event: Reporter added to or removed from the allowlist
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct ReporterUpdated {
+    pub reporter: Address,
+    pub allowed: bool,
+}
+
+/// Event: Group reported a contribution outcome for a member
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct OutcomeReported {
+    pub record_id: u64,
+    pub member: Address,
+    pub reporter: Address,
+    pub outcome: CreditOutcome,
+    pub score: i128,
+}
+
+/// Event: Member contested a record
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct RecordDisputed {
+    pub record_id: u64,
+    pub member: Address,
+    pub evidence_hash: BytesN<32>,
+}
+
+/// Event: Admin upheld or voided a disputed record
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct RecordDisputeResolved {
+    pub record_id: u64,
+    pub upheld: bool,
+    pub score: i128,
+}
+
+pub fn emit_reporter_updated(e: &Env, reporter: Address, allowed: bool) {
+    ReporterUpdated { reporter, allowed }.publish(e);
+}
+
+pub fn emit_outcome_reported(
+    e: &Env,
+    record_id: u64,
+    member: Address,
+    reporter: Address,
+    outcome: CreditOutcome,
+    score: i128,
+) {
+    OutcomeReported {
+        record_id,
+        member,
+        reporter,
+        outcome,
+        score,
+    }
+    .publish(e);
+}
+
+pub fn emit_record_disputed(e: &Env, record_id: u64, member: Address, evidence_hash: BytesN<32>) {
+    RecordDisputed {
+        record_id,
+        member,
+        evidence_hash,
+    }
+    .publish(e);
+}
+
+pub fn emit_record_dispute_resolved(e: &Env, record_id: u64, upheld: bool, score: i128) {
+    RecordDisputeResolved {
+        record_id,
+        upheld,
+        score,
+    }
+    .publish(e);
+}
@@ -0,0 +1,430 @@
+#![no_std]
+//! Cross-group credit bureau for rosca members. Allowlisted rosca groups
+//! report contribution outcomes here, and any group can gate membership on
+//! the resulting score.
+use ahjoor_types::credit::CreditOutcome;
+use soroban_sdk::{
+    contract, contractimpl, contracttype, panic_with_error, Address, BytesN, Env, Vec,
+};
+
+// Instance storage: admin, weights and the record counter
+const INSTANCE_LIFETIME_THRESHOLD: u32 = 100_000;
+const INSTANCE_BUMP_AMOUNT: u32 = 120_000;
+
+// Persistent storage: reporters, records, profiles and per-member indexes
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = 100_000;
+const PERSISTENT_BUMP_AMOUNT: u32 = 120_000;
+
+/// Maximum `page_size` accepted by `get_member_records`.
+pub const MAX_PAGE_SIZE: u32 = 50;
+
+/// Record ids held by one storage entry of a member's record index.
+pub const RECORD_INDEX_PAGE_SIZE: u32 = 100;
+
+mod errors;
+mod events;
+
+pub use errors::Error;
+
+/// Lifecycle of a reported outcome.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RecordStatus {
+    Active = 0,
+    /// Contested by the member and awaiting admin review. Still counted.
+    Disputed = 1,
+    /// Removed by the admin after a dispute. No longer counted.
+    Voided = 2,
+    /// Confirmed by the admin after a dispute. Counted and final.
+    Upheld = 3,
+}
+
+/// A single outcome reported by a group.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CreditRecord {
+    pub record_id: u64,
+    pub member: Address,
+    /// Rosca group that reported the outcome.
+    pub reporter: Address,
+    pub outcome: CreditOutcome,
+    pub reported_at: u64,
+    pub status: RecordStatus,
+    pub evidence_hash: Option<BytesN<32>>,
+}
+
+/// Outcome counters for a member across every reporting group, excluding
+/// voided records.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CreditProfile {
+    pub on_time_contributions: u32,
+    pub late_contributions: u32,
+    pub defaults: u32,
+    pub early_exits: u32,
+    pub groups_completed: u32,
+    /// Weighted sum of the counters above.
+    pub score: i128,
+}
+
+/// Admin-configurable weights used to compute the score. Defaults match the
+/// per-group weights in `ahjoor-rosca`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScoreWeights {
+    pub on_time_weight: i128,
+    pub late_weight: i128,
+    pub default_weight: i128,
+    pub exit_weight: i128,
+    pub completion_weight: i128,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    Admin,
+    ScoreWeights,
+    RecordCounter,
+    /// reporter → allowed
+    Reporter(Address),
+    /// record_id → CreditRecord
+    Record(u64),
+    /// member → CreditProfile
+    Profile(Address),
+    /// member → number of records about them
+    MemberRecordCount(Address),
+    /// (member, page) → up to `RECORD_INDEX_PAGE_SIZE` record ids
+    MemberRecordPage(Address, u32),
+}
+
+#[contract]
+pub struct AhjoorCreditRegistry;
+
+#[contractimpl]
+impl AhjoorCreditRegistry {
+    pub fn initialize(env: Env, admin: Address) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic_with_error!(&env, Error::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::RecordCounter, &0u64);
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    pub fn get_admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized))
+    }
+
+    // ── Reporter allowlist ────────────────────────────────────────────────
+
+    /// Admin allows a rosca group to report outcomes.
+    pub fn add_reporter(env: Env, admin: Address, reporter: Address) {
+        Self::require_admin(&env, &admin);
+        let key = DataKey::Reporter(reporter.clone());
+        if env.storage().persistent().has(&key) {
+            panic_with_error!(&env, Error::ReporterAlreadyAllowed);
+        }
+        env.storage().persistent().set(&key, &true);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        events::emit_reporter_updated(&env, reporter, true);
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Admin revokes a reporter. Records it already reported are kept.
+    pub fn remove_reporter(env: Env, admin: Address, reporter: Address) {
+        Self::require_admin(&env, &admin);
+        let key = DataKey::Reporter(reporter.clone());
+        if !env.storage().persistent().has(&key) {
+            panic_with_error!(&env, Error::ReporterNotAllowed);
+        }
+        env.storage().persistent().remove(&key);
+        events::emit_reporter_updated(&env, reporter, false);
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    pub fn is_reporter(env: Env, reporter: Address) -> bool {
+        env.storage().persistent().has(&DataKey::Reporter(reporter))
+    }
+
+    // ── Scoring ───────────────────────────────────────────────────────────
+
+    /// Admin changes the score weights. Existing profiles are rescored lazily
+    /// on their next report or dispute resolution; `get_score` always uses
+    /// the current weights.
+    pub fn set_score_weights(env: Env, admin: Address, weights: ScoreWeights) {
+        Self::require_admin(&env, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::ScoreWeights, &weights);
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    pub fn get_score_weights(env: Env) -> ScoreWeights {
+        env.storage()
+            .instance()
+            .get(&DataKey::ScoreWeights)
+            .unwrap_or(ScoreWeights {
+                on_time_weight: 10,
+                late_weight: -2,
+                default_weight: -20,
+                exit_weight: -15,
+                completion_weight: 30,
+            })
+    }
+
+    /// Allowlisted reporter records an outcome for `member`.
+    /// Returns the new record id.
+    pub fn report_outcome(
+        env: Env,
+        reporter: Address,
+        member: Address,
+        outcome: CreditOutcome,
+    ) -> u64 {
+        reporter.require_auth();
+        if !Self::is_reporter(env.clone(), reporter.clone()) {
+            panic_with_error!(&env, Error::ReporterNotAllowed);
+        }
+
+        let record_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::RecordCounter)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
+        let record = CreditRecord {
+            record_id,
+            member: member.clone(),
+            reporter: reporter.clone(),
+            outcome,
+            reported_at: env.ledger().timestamp(),
+            status: RecordStatus::Active,
+            evidence_hash: None,
+        };
+        Self::save_record(&env, &record);
+        env.storage()
+            .instance()
+            .set(&DataKey::RecordCounter, &(record_id + 1));
+
+        Self::index_record(&env, &member, record_id);
+
+        let profile = Self::apply_outcome(&env, &member, outcome, 1);
+        events::emit_outcome_reported(&env, record_id, member, reporter, outcome, profile.score);
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        record_id
+    }
+
+    /// Counters and score for `member` across every reporting group.
+    pub fn get_profile(env: Env, member: Address) -> CreditProfile {
+        let mut profile = Self::load_profile(&env, &member);
+        profile.score = Self::compute_score(&env, &profile);
+        profile
+    }
+
+    pub fn get_score(env: Env, member: Address) -> i128 {
+        Self::get_profile(env, member).score
+    }
+
+    pub fn get_record(env: Env, record_id: u64) -> CreditRecord {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Record(record_id))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::RecordNotFound))
+    }
+
+    /// Number of records reported about `member`.
+    pub fn get_member_record_count(env: Env, member: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MemberRecordCount(member))
+            .unwrap_or(0)
+    }
+
+    /// Ids of the records reported about `member`, oldest first.
+    pub fn get_member_records(env: Env, member: Address, page: u32, page_size: u32) -> Vec<u64> {
+        if page_size == 0 || page_size > MAX_PAGE_SIZE {
+            panic_with_error!(&env, Error::InvalidPageSize);
+        }
+        let count = Self::get_member_record_count(env.clone(), member.clone());
+        let start = page.saturating_mul(page_size).min(count);
+        let end = start.saturating_add(page_size).min(count);
+        let mut ids = Vec::new(&env);
+        let mut chunk: Vec<u64> = Vec::new(&env);
+        for i in start..end {
+            if i == start || i % RECORD_INDEX_PAGE_SIZE == 0 {
+                chunk = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::MemberRecordPage(
+                        member.clone(),
+                        i / RECORD_INDEX_PAGE_SIZE,
+                    ))
+                    .unwrap_or(Vec::new(&env));
+            }
+            ids.push_back(chunk.get(i % RECORD_INDEX_PAGE_SIZE).unwrap());
+        }
+        ids
+    }
+
+    // ── Disputes ──────────────────────────────────────────────────────────
+
+    /// Member contests a record about them. The record keeps counting until
+    /// the admin resolves the dispute, so disputing cannot be used to lift a
+    /// score on its own.
+    pub fn dispute_record(env: Env, member: Address, record_id: u64, evidence_hash: BytesN<32>) {
+        member.require_auth();
+        let mut record = Self::get_record(env.clone(), record_id);
+        if record.member != member {
+            panic_with_error!(&env, Error::NotRecordMember);
+        }
+        if record.status != RecordStatus::Active {
+            panic_with_error!(&env, Error::RecordNotActive);
+        }
+        record.status = RecordStatus::Disputed;
+        record.evidence_hash = Some(evidence_hash.clone());
+        Self::save_record(&env, &record);
+        events::emit_record_disputed(&env, record_id, member, evidence_hash);
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Admin rules on a disputed record. Upholding marks it `Upheld`, which
+    /// keeps it counted and cannot be disputed again; otherwise it is voided
+    /// and removed from the member's score.
+    pub fn resolve_record_dispute(env: Env, admin: Address, record_id: u64, uphold: bool) {
+        Self::require_admin(&env, &admin);
+        let mut record = Self::get_record(env.clone(), record_id);
+        if record.status != RecordStatus::Disputed {
+            panic_with_error!(&env, Error::RecordNotDisputed);
+        }
+        let profile = if uphold {
+            record.status = RecordStatus::Upheld;
+            Self::get_profile(env.clone(), record.member.clone())
+        } else {
+            record.status = RecordStatus::Voided;
+            Self::apply_outcome(&env, &record.member, record.outcome, -1)
+        };
+        Self::save_record(&env, &record);
+        events::emit_record_dispute_resolved(&env, record_id, uphold, profile.score);
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    // ── Internals ─────────────────────────────────────────────────────────
+
+    fn require_admin(env: &Env, caller: &Address) {
+        caller.require_auth();
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(env, Error::NotInitialized));
+        if *caller != admin {
+            panic_with_error!(env, Error::Unauthorized);
+        }
+    }
+
+    fn save_record(env: &Env, record: &CreditRecord) {
+        let key = DataKey::Record(record.record_id);
+        env.storage().persistent().set(&key, record);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    /// Append `record_id` to the member's paged record index.
+    fn index_record(env: &Env, member: &Address, record_id: u64) {
+        let count = Self::get_member_record_count(env.clone(), member.clone());
+        let page_key = DataKey::MemberRecordPage(member.clone(), count / RECORD_INDEX_PAGE_SIZE);
+        let mut ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&page_key)
+            .unwrap_or(Vec::new(env));
+        ids.push_back(record_id);
+        let count_key = DataKey::MemberRecordCount(member.clone());
+        env.storage().persistent().set(&page_key, &ids);
+        env.storage().persistent().set(&count_key, &(count + 1));
+        for key in [page_key, count_key] {
+            env.storage().persistent().extend_ttl(
+                &key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+        }
+    }
+
+    fn load_profile(env: &Env, member: &Address) -> CreditProfile {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Profile(member.clone()))
+            .unwrap_or(CreditProfile {
+                on_time_contributions: 0,
+                late_contributions: 0,
+                defaults: 0,
+                early_exits: 0,
+                groups_completed: 0,
+                score: 0,
+            })
+    }
+
+    fn compute_score(env: &Env, p: &CreditProfile) -> i128 {
+        let w = Self::get_score_weights(env.clone());
+        (p.on_time_contributions as i128 * w.on_time_weight)
+            + (p.late_contributions as i128 * w.late_weight)
+            + (p.defaults as i128 * w.default_weight)
+            + (p.early_exits as i128 * w.exit_weight)
+            + (p.groups_completed as i128 * w.completion_weight)
+    }
+
+    /// Adds (`delta = 1`) or removes (`delta = -1`) one outcome from the
+    /// member's counters, rescores and stores the profile.
+    fn apply_outcome(
+        env: &Env,
+        member: &Address,
+        outcome: CreditOutcome,
+        delta: i32,
+    ) -> CreditProfile {
+        let mut p = Self::load_profile(env, member);
+        let counter = match outcome {
+            CreditOutcome::OnTime => &mut p.on_time_contributions,
+            CreditOutcome::Late => &mut p.late_contributions,
+            CreditOutcome::Default => &mut p.defaults,
+            CreditOutcome::EarlyExit => &mut p.early_exits,
+            CreditOutcome::GroupCompleted => &mut p.groups_completed,
+        };
+        *counter = counter.saturating_add_signed(delta);
+        p.score = Self::compute_score(env, &p);
+
+        let key = DataKey::Profile(member.clone());
+        env.storage().persistent().set(&key, &p);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        p
+    }
+}
+
+#[cfg(test)]
+mod test;
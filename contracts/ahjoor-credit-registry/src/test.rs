@@ -0,0 +1,197 @@
+#![cfg(test)]
+use super::*;
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Env};
+
+struct RegistrySetup<'a> {
+    env: Env,
+    client: AhjoorCreditRegistryClient<'a>,
+    admin: Address,
+    reporter: Address,
+    member: Address,
+}
+
+fn setup<'a>() -> RegistrySetup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AhjoorCreditRegistry, ());
+    let client = AhjoorCreditRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let member = Address::generate(&env);
+
+    client.initialize(&admin);
+    client.add_reporter(&admin, &reporter);
+
+    RegistrySetup {
+        env,
+        client,
+        admin,
+        reporter,
+        member,
+    }
+}
+
+fn contract_error(code: impl Into<soroban_sdk::Error>) -> soroban_sdk::Error {
+    code.into()
+}
+
+#[test]
+fn test_scores_aggregate_across_reporting_groups() {
+    let s = setup();
+    let second_group = Address::generate(&s.env);
+    s.client.add_reporter(&s.admin, &second_group);
+
+    s.client
+        .report_outcome(&s.reporter, &s.member, &CreditOutcome::OnTime);
+    s.client
+        .report_outcome(&s.reporter, &s.member, &CreditOutcome::GroupCompleted);
+    s.client
+        .report_outcome(&second_group, &s.member, &CreditOutcome::Late);
+
+    let profile = s.client.get_profile(&s.member);
+    assert_eq!(profile.on_time_contributions, 1);
+    assert_eq!(profile.groups_completed, 1);
+    assert_eq!(profile.late_contributions, 1);
+    // 10 + 30 - 2 with the default weights
+    assert_eq!(s.client.get_score(&s.member), 38);
+    assert_eq!(s.client.get_member_records(&s.member, &0, &10).len(), 3);
+}
+
+#[test]
+fn test_unlisted_reporter_is_rejected() {
+    let s = setup();
+    let rogue = Address::generate(&s.env);
+
+    assert_eq!(
+        s.client
+            .try_report_outcome(&rogue, &s.member, &CreditOutcome::Default),
+        Err(Ok(contract_error(Error::ReporterNotAllowed)))
+    );
+
+    s.client.remove_reporter(&s.admin, &s.reporter);
+    assert!(!s.client.is_reporter(&s.reporter));
+    assert_eq!(
+        s.client
+            .try_report_outcome(&s.reporter, &s.member, &CreditOutcome::OnTime),
+        Err(Ok(contract_error(Error::ReporterNotAllowed)))
+    );
+}
+
+#[test]
+fn test_voided_dispute_removes_record_from_score() {
+    let s = setup();
+    s.client
+        .report_outcome(&s.reporter, &s.member, &CreditOutcome::OnTime);
+    let record_id = s
+        .client
+        .report_outcome(&s.reporter, &s.member, &CreditOutcome::Default);
+    assert_eq!(s.client.get_score(&s.member), -10);
+
+    let evidence = BytesN::from_array(&s.env, &[1u8; 32]);
+    s.client.dispute_record(&s.member, &record_id, &evidence);
+    // Still counted while under review
+    assert_eq!(s.client.get_score(&s.member), -10);
+    assert_eq!(
+        s.client.get_record(&record_id).status,
+        RecordStatus::Disputed
+    );
+
+    s.client
+        .resolve_record_dispute(&s.admin, &record_id, &false);
+    assert_eq!(s.client.get_record(&record_id).status, RecordStatus::Voided);
+    assert_eq!(s.client.get_profile(&s.member).defaults, 0);
+    assert_eq!(s.client.get_score(&s.member), 10);
+}
+
+#[test]
+fn test_upheld_dispute_keeps_record_and_blocks_redispute() {
+    let s = setup();
+    let record_id = s
+        .client
+        .report_outcome(&s.reporter, &s.member, &CreditOutcome::Late);
+    let evidence = BytesN::from_array(&s.env, &[2u8; 32]);
+
+    s.client.dispute_record(&s.member, &record_id, &evidence);
+    s.client.resolve_record_dispute(&s.admin, &record_id, &true);
+
+    assert_eq!(s.client.get_record(&record_id).status, RecordStatus::Upheld);
+    assert_eq!(s.client.get_score(&s.member), -2);
+    assert_eq!(
+        s.client
+            .try_resolve_record_dispute(&s.admin, &record_id, &false),
+        Err(Ok(contract_error(Error::RecordNotDisputed)))
+    );
+    // An upheld ruling is final
+    assert_eq!(
+        s.client
+            .try_dispute_record(&s.member, &record_id, &evidence),
+        Err(Ok(contract_error(Error::RecordNotActive)))
+    );
+}
+
+#[test]
+fn test_member_records_are_paged_across_index_entries() {
+    let s = setup();
+    let total = RECORD_INDEX_PAGE_SIZE + 5;
+    for _ in 0..total {
+        s.client
+            .report_outcome(&s.reporter, &s.member, &CreditOutcome::OnTime);
+    }
+    assert_eq!(s.client.get_member_record_count(&s.member), total);
+
+    // Page 2 of 40 starts in the first index entry and ends in the second
+    let page = s.client.get_member_records(&s.member, &2, &40);
+    assert_eq!(page.len(), 25);
+    for (i, record_id) in page.iter().enumerate() {
+        assert_eq!(record_id, 80 + i as u64);
+    }
+    assert_eq!(s.client.get_member_records(&s.member, &3, &40).len(), 0);
+    assert_eq!(
+        s.client
+            .try_get_member_records(&s.member, &0, &(MAX_PAGE_SIZE + 1)),
+        Err(Ok(contract_error(Error::InvalidPageSize)))
+    );
+}
+
+#[test]
+fn test_only_the_member_can_dispute_their_record() {
+    let s = setup();
+    let record_id = s
+        .client
+        .report_outcome(&s.reporter, &s.member, &CreditOutcome::Default);
+    let stranger = Address::generate(&s.env);
+    let evidence = BytesN::from_array(&s.env, &[3u8; 32]);
+
+    assert_eq!(
+        s.client
+            .try_dispute_record(&stranger, &record_id, &evidence),
+        Err(Ok(contract_error(Error::NotRecordMember)))
+    );
+}
+
+#[test]
+fn test_weights_apply_to_existing_profiles() {
+    let s = setup();
+    s.client
+        .report_outcome(&s.reporter, &s.member, &CreditOutcome::OnTime);
+    s.client.set_score_weights(
+        &s.admin,
+        &ScoreWeights {
+            on_time_weight: 5,
+            late_weight: -1,
+            default_weight: -50,
+            exit_weight: -10,
+            completion_weight: 20,
+        },
+    );
+    assert_eq!(s.client.get_score(&s.member), 5);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #3)")] // Unauthorized
+fn test_non_admin_cannot_manage_reporters() {
+    let s = setup();
+    let intruder = Address::generate(&s.env);
+    s.client.add_reporter(&intruder, &intruder);
+}
@@ -0,0 +1,372 @@
+use soroban_sdk::contracterror;
+
+// Escrow codes live in 1000–1999 so they never collide with payments
+// (1–999) or refund (2000–2999) codes surfaced by the same front-end.
+
+/// General escrow lifecycle, party and configuration failures. Codes 1000–1099.
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// The contract has already been initialized.
+    AlreadyInitialized = 1000,
+    /// The contract has not been initialized.
+    NotInitialized = 1001,
+    /// Caller is not the contract admin.
+    Unauthorized = 1002,
+    /// The contract is paused.
+    ContractPaused = 1003,
+    /// The contract is already paused.
+    ContractAlreadyPaused = 1004,
+    /// The contract is not paused.
+    ContractNotPaused = 1005,
+    /// No escrow exists with the given id.
+    EscrowNotFound = 1006,
+    /// The escrow is not in the `Active` state.
+    EscrowNotActive = 1007,
+    /// The escrow is not in a state that allows this action.
+    InvalidEscrowStatus = 1008,
+    /// An amount that must be positive was zero or negative.
+    InvalidAmount = 1009,
+    /// A deadline or unlock time is not in the future or is out of order.
+    InvalidDeadline = 1010,
+    /// A duration or window setting must be positive.
+    InvalidDuration = 1011,
+    /// A basis-point setting is outside its allowed range.
+    InvalidBps = 1012,
+    /// The token is not on the allowlist or whitelist.
+    TokenNotAllowed = 1013,
+    /// The escrow's minimum lock period has not elapsed.
+    EscrowStillLocked = 1014,
+    /// The seller list or its allocations are invalid.
+    InvalidSellers = 1015,
+    /// The address is not a seller on this escrow.
+    SellerNotInEscrow = 1016,
+    /// The batch contains no escrows.
+    EmptyBatch = 1017,
+    /// The batch exceeds the maximum of 10 escrows.
+    BatchTooLarge = 1018,
+    /// A fee setting exceeds its maximum or the escrow amount.
+    FeeTooHigh = 1019,
+    /// A fee is configured but no fee recipient is set.
+    FeeRecipientNotSet = 1020,
+    /// Caller is not the escrow buyer.
+    NotBuyer = 1021,
+    /// Caller is not the escrow seller.
+    NotSeller = 1022,
+    /// Caller is neither the buyer nor the seller.
+    NotEscrowParty = 1023,
+    /// Caller is neither the buyer nor the arbiter.
+    NotBuyerOrArbiter = 1024,
+    /// The oracle release condition is incomplete or malformed.
+    InvalidReleaseCondition = 1025,
+    /// The escrow has no release condition.
+    NoReleaseCondition = 1026,
+    /// The oracle release condition is not satisfied.
+    ReleaseConditionNotMet = 1027,
+    /// No oracle is configured for this check.
+    OracleNotConfigured = 1028,
+    /// The oracle returned no price.
+    OraclePriceUnavailable = 1029,
+    /// The oracle price is older than the allowed age.
+    OraclePriceStale = 1030,
+    /// The oracle returned a non-positive price.
+    InvalidOraclePrice = 1031,
+    /// The release amount exceeds the escrowed balance.
+    ReleaseExceedsBalance = 1032,
+    /// The top-up exceeds the configured maximum.
+    TopUpLimitExceeded = 1033,
+    /// No receipt exists for this escrow.
+    ReceiptNotFound = 1034,
+    /// Caller does not hold the escrow receipt.
+    NotReceiptHolder = 1035,
+    /// A milestone is in progress, so the receipt cannot move.
+    ActiveMilestoneInProgress = 1036,
+    /// The delivery proof does not match the stored hash.
+    InvalidDeliveryProof = 1037,
+    /// The escrow has no delivery proof hash.
+    NoDeliveryProof = 1038,
+    /// Delivery proofs cannot be submitted while the escrow is disputed.
+    DeliveryProofLocked = 1039,
+    /// The escrow deadline has not passed.
+    EscrowNotExpired = 1040,
+    /// Inactivity release is not enabled for this escrow.
+    InactivityReleaseDisabled = 1041,
+    /// The buyer inactivity window has not elapsed.
+    InactivityWindowOpen = 1042,
+    /// A rating must be between 1 and 5.
+    InvalidRating = 1043,
+    /// The caller has already rated this escrow.
+    RatingAlreadySubmitted = 1044,
+    /// Migration has already run for this version.
+    MigrationAlreadyCompleted = 1045,
+    /// A counter or version number overflowed.
+    ArithmeticOverflow = 1046,
+    /// No keeper pool is configured.
+    KeeperNotConfigured = 1047,
+    /// Keeper bounty and caps must be positive; the token can only change while the pool is empty.
+    InvalidKeeperConfig = 1048,
+    /// The keeper pool balance is insufficient.
+    InsufficientKeeperPool = 1049,
+}
+
+/// Dispute, verdict, arbitration panel, arbiter stake and insurance failures. Codes 1100–1199.
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DisputeError {
+    /// The escrow is not disputed.
+    EscrowNotDisputed = 1100,
+    /// No dispute record exists for this escrow.
+    DisputeNotFound = 1101,
+    /// The dispute has already been resolved.
+    DisputeAlreadyResolved = 1102,
+    /// The action is blocked while a dispute is open.
+    DisputeActive = 1103,
+    /// The disputed amount must be positive and at most the escrow amount.
+    InvalidDisputeAmount = 1104,
+    /// Caller is not the escrow arbiter.
+    NotArbiter = 1105,
+    /// The buyer share is outside its allowed range.
+    InvalidSplit = 1106,
+    /// The dispute timeout has not passed.
+    DisputeTimeoutNotReached = 1107,
+    /// The party has submitted the maximum number of evidence entries.
+    EvidenceLimitReached = 1108,
+    /// The escrow is not in the `CoolingOff` state.
+    NotInCoolingOff = 1109,
+    /// No verdict is waiting to be finalized.
+    NoPendingVerdict = 1110,
+    /// The cooling-off window has expired.
+    CoolingOffExpired = 1111,
+    /// The cooling-off window has not elapsed.
+    CoolingOffActive = 1112,
+    /// The resolution has already been flagged.
+    ResolutionAlreadyFlagged = 1113,
+    /// The resolution is flagged and awaits admin review.
+    ResolutionFlagged = 1114,
+    /// The resolution has no flag to review.
+    NoResolutionFlag = 1115,
+    /// The arbiter is already in the pool.
+    ArbiterAlreadyInPool = 1116,
+    /// The arbiter is not in the pool.
+    ArbiterNotInPool = 1117,
+    /// The arbiter pool is empty.
+    ArbiterPoolEmpty = 1118,
+    /// The pool has too few eligible arbiters.
+    NotEnoughArbiters = 1119,
+    /// The arbiter cannot be the buyer or the seller.
+    ArbiterIsParty = 1120,
+    /// The dispute is being decided by an arbitration panel.
+    UnderPanelArbitration = 1121,
+    /// An arbitration panel has already been requested.
+    PanelAlreadyRequested = 1122,
+    /// No arbitration panel exists for this escrow.
+    PanelNotFound = 1123,
+    /// The arbitration panel has already decided.
+    PanelAlreadyDecided = 1124,
+    /// The panel size must be 3, 5 or 7.
+    InvalidPanelSize = 1125,
+    /// The escrow amount is below the panel threshold.
+    BelowPanelThreshold = 1126,
+    /// Caller is not a member of the panel.
+    NotPanelMember = 1127,
+    /// The arbiter has already voted.
+    AlreadyVoted = 1128,
+    /// The panel voting window has closed.
+    VotingClosed = 1129,
+    /// The panel voting window has not elapsed.
+    VotingOpen = 1130,
+    /// Arbiter staking is not configured.
+    StakingNotConfigured = 1131,
+    /// The arbiter has no stake.
+    StakeNotFound = 1132,
+    /// The arbiter's active stake is below the minimum.
+    StakeBelowMinimum = 1133,
+    /// The existing stake is in a different token.
+    StakeTokenMismatch = 1134,
+    /// The unbonding amount is zero or exceeds the active stake.
+    InvalidUnbondingAmount = 1135,
+    /// No stake is unbonding.
+    NothingUnbonding = 1136,
+    /// The unbonding period has not elapsed.
+    StakeStillUnbonding = 1137,
+    /// No insurance token is configured.
+    InsuranceNotConfigured = 1138,
+    /// The escrow token is not covered by the insurance pool.
+    InsuranceTokenMismatch = 1139,
+    /// Insurance has already been claimed for this escrow.
+    InsuranceAlreadyClaimed = 1140,
+    /// The insurance trigger period has not been reached.
+    InsuranceTriggerNotReached = 1141,
+    /// The insurance pool has insufficient balance.
+    InsufficientInsurancePool = 1142,
+    /// The claim requires admin confirmation.
+    AdminConfirmationRequired = 1143,
+}
+
+/// Milestone, bounty, release schedule, time-lock and hashlock failures. Codes 1200–1299.
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MilestoneError {
+    /// The escrow or bounty has no milestones.
+    NoMilestones = 1200,
+    /// At least one milestone is required.
+    MilestoneRequired = 1201,
+    /// The milestone count exceeds the maximum.
+    TooManyMilestones = 1202,
+    /// The milestone index is out of range.
+    MilestoneIndexOutOfRange = 1203,
+    /// The milestone is not pending.
+    MilestoneNotPending = 1204,
+    /// The milestone is not in a status that allows this action.
+    InvalidMilestoneStatus = 1205,
+    /// The milestone definitions are invalid.
+    InvalidMilestones = 1206,
+    /// Milestone escrows change their amount by amending milestones.
+    AmendMilestonesInstead = 1207,
+    /// The previous milestone has not been verified.
+    PreviousMilestoneUnverified = 1208,
+    /// No bounty data exists for this escrow.
+    BountyNotFound = 1209,
+    /// The bounty is not open for claiming.
+    BountyNotOpen = 1210,
+    /// The bounty is not in the `Claimed` status.
+    BountyNotClaimed = 1211,
+    /// The bounty cannot be cancelled in its current state.
+    BountyNotCancellable = 1212,
+    /// The bounty claim deadline has passed.
+    ClaimDeadlinePassed = 1213,
+    /// The bounty submission deadline has passed.
+    SubmissionDeadlinePassed = 1214,
+    /// Caller is not the assigned solver.
+    NotSolver = 1215,
+    /// Caller is not the bounty creator.
+    NotBountyCreator = 1216,
+    /// No work has been submitted.
+    NoSubmission = 1217,
+    /// The maximum number of rejection rounds has been reached.
+    MaxRejectionRoundsReached = 1218,
+    /// The release schedule has no tranches.
+    EmptySchedule = 1219,
+    /// The escrow has no release schedule.
+    ScheduleNotFound = 1220,
+    /// No tranches are currently claimable.
+    NothingClaimable = 1221,
+    /// The escrow is not time-locked.
+    NotTimelocked = 1222,
+    /// The time-locked escrow has already been claimed or cancelled.
+    AlreadyClaimed = 1223,
+    /// The unlock time has not passed.
+    UnlockTimeNotReached = 1224,
+    /// The unlock time has passed, so the escrow must be claimed.
+    UnlockTimePassed = 1225,
+    /// Caller is not the beneficiary.
+    NotBeneficiary = 1226,
+    /// No HTLC escrow exists with the given id.
+    HtlcNotFound = 1227,
+    /// The HTLC timeout has passed, so it can no longer be claimed.
+    HtlcExpired = 1228,
+    /// The HTLC timeout has not passed yet.
+    HtlcNotExpired = 1229,
+    /// The preimage does not hash to the HTLC hashlock.
+    InvalidPreimage = 1230,
+}
+
+/// Amendment, cancellation, transfer, approval, veto, template, inspection, collateral, renewal, oracle set and TWAP failures. Codes 1300–1399.
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WorkflowError {
+    /// An amendment proposal is already pending.
+    AmendmentPending = 1300,
+    /// No amendment proposal exists.
+    AmendmentNotFound = 1301,
+    /// The amendment nonce does not match the pending proposal.
+    AmendmentNonceMismatch = 1302,
+    /// The amendment proposal has expired.
+    AmendmentExpired = 1303,
+    /// The amendment proposal has not expired.
+    AmendmentNotExpired = 1304,
+    /// The amendment changes nothing.
+    AmendmentNoop = 1305,
+    /// Caller is not the proposer.
+    NotProposer = 1306,
+    /// The proposer cannot accept their own proposal.
+    CannotAcceptOwnProposal = 1307,
+    /// The proposer cannot reject their own proposal.
+    CannotRejectOwnProposal = 1308,
+    /// No cancellation request exists.
+    CancellationNotFound = 1309,
+    /// The response window has not elapsed.
+    ResponseWindowOpen = 1310,
+    /// No deadline extension proposal exists.
+    ExtensionNotFound = 1311,
+    /// The deadline extension proposal has expired.
+    ExtensionExpired = 1312,
+    /// The new party must differ from the current one.
+    InvalidTransferTarget = 1313,
+    /// No pending transfer or proposal exists.
+    TransferNotFound = 1314,
+    /// No pending partial release request exists.
+    PartialReleaseNotFound = 1315,
+    /// A partial release request is already pending.
+    PartialReleasePending = 1316,
+    /// Multi-party approval is not configured for this escrow.
+    MultiPartyNotConfigured = 1317,
+    /// The approver list or threshold is invalid.
+    InvalidApprovers = 1318,
+    /// Caller is not an approver for this escrow.
+    NotApprover = 1319,
+    /// The approver has already approved this escrow.
+    AlreadyApproved = 1320,
+    /// The seller has an active veto on release.
+    SellerVetoActive = 1321,
+    /// The seller has already raised a veto.
+    SellerVetoAlreadyRaised = 1322,
+    /// No active veto exists.
+    NoActiveVeto = 1323,
+    /// The veto window has not expired.
+    VetoWindowOpen = 1324,
+    /// The veto override window has elapsed.
+    VetoWindowElapsed = 1325,
+    /// The veto override window has not elapsed.
+    VetoWindowNotElapsed = 1326,
+    /// No template exists with the given id.
+    TemplateNotFound = 1327,
+    /// The template is deactivated.
+    TemplateDeactivated = 1328,
+    /// Caller is not the template creator.
+    NotTemplateCreator = 1329,
+    /// Release is waiting for an inspection report.
+    InspectionPending = 1330,
+    /// The escrow is not awaiting inspection.
+    NotAwaitingInspection = 1331,
+    /// The escrow has no inspector.
+    NoInspector = 1332,
+    /// Caller is not the assigned inspector.
+    NotInspector = 1333,
+    /// The inspector ruling has already been appealed.
+    InspectorRulingAppealed = 1334,
+    /// The inspector score is below the high-value threshold.
+    InspectorScoreTooLow = 1335,
+    /// The escrow is not awaiting collateral.
+    NotAwaitingCollateral = 1336,
+    /// The collateral deposit window has expired.
+    CollateralWindowExpired = 1337,
+    /// Collateral health is not configured for this escrow.
+    CollateralHealthNotConfigured = 1338,
+    /// Auto-renew is not enabled for this escrow.
+    AutoRenewDisabled = 1339,
+    /// The renewal allowance is insufficient.
+    InsufficientRenewalAllowance = 1340,
+    /// Oracle set is empty, too large, has duplicates, or has out-of-range thresholds.
+    InvalidOracleSet = 1341,
+    /// Fewer oracle sources than required reported a fresh, valid price.
+    InsufficientOracleSources = 1342,
+    /// An oracle source deviates from the median beyond the configured limit.
+    OracleDeviationExceeded = 1343,
+    /// Less than `twap_window_seconds / twap_observation_count` has passed since the last TWAP reading.
+    TwapObservationTooEarly = 1344,
+    /// Not all TWAP readings have been taken within the window.
+    TwapNotReady = 1345,
+    /// An amount increase must be proposed by the seller and accepted by the buyer, who funds it.
+    TopUpNeedsBuyerAcceptance = 1346,
+}
@@ -1,4 +1,5 @@
-use soroban_sdk::{contractevent, Address, BytesN, Env, String, Symbol, Vec};
+use crate::AmendmentRecord;
+use soroban_sdk::{contractevent, Address, Bytes, BytesN, Env, String, Symbol, Vec};
 
 /// Event: Escrow created
 #[contractevent]
@@ -143,6 +144,79 @@ pub struct OracleReleaseTriggered {
     pub threshold_price: i128,
 }
 
+/// Event: Admin configured the oracle set for a pair
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct OracleSetConfigured {
+    pub base: Address,
+    pub quote: Address,
+    pub oracles: Vec<Address>,
+    pub min_sources: u32,
+    pub max_deviation_bps: u32,
+}
+
+/// Event: Price for a pair was taken as the median of its oracle set
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct OraclePriceAggregated {
+    pub base: Address,
+    pub quote: Address,
+    pub price: i128,
+    pub sources: u32,
+    pub skipped: u32,
+}
+
+/// Event: An oracle source was skipped (`missing`, `stale` or `invalid`) and the remaining sources used
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct OracleFailover {
+    pub base: Address,
+    pub quote: Address,
+    pub oracle: Address,
+    pub reason: Symbol,
+}
+
+/// Event: Price reading recorded for an escrow in TWAP mode
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct TwapObservationRecorded {
+    pub escrow_id: u32,
+    pub price: i128,
+    pub observations: u32,
+}
+
+/// Event: Funds locked in a hash time-locked escrow
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct HtlcCreated {
+    pub htlc_id: u32,
+    pub sender: Address,
+    pub receiver: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub hashlock: BytesN<32>,
+    pub timeout: u64,
+}
+
+/// Event: HTLC claimed by the receiver, revealing the preimage
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct HtlcClaimed {
+    pub htlc_id: u32,
+    pub receiver: Address,
+    pub amount: i128,
+    pub preimage: Bytes,
+}
+
+/// Event: HTLC reclaimed by the sender after the timeout
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct HtlcRefunded {
+    pub htlc_id: u32,
+    pub sender: Address,
+    pub amount: i128,
+}
+
 /// Event: Escrow refunded to buyer
 #[contractevent]
 #[derive(Clone, Debug)]
@@ -555,6 +629,103 @@ pub fn emit_oracle_release_triggered(
     .publish(e);
 }
 
+pub fn emit_oracle_set_configured(
+    e: &Env,
+    base: Address,
+    quote: Address,
+    oracles: Vec<Address>,
+    min_sources: u32,
+    max_deviation_bps: u32,
+) {
+    OracleSetConfigured {
+        base,
+        quote,
+        oracles,
+        min_sources,
+        max_deviation_bps,
+    }
+    .publish(e);
+}
+
+pub fn emit_oracle_price_aggregated(
+    e: &Env,
+    base: Address,
+    quote: Address,
+    price: i128,
+    sources: u32,
+    skipped: u32,
+) {
+    OraclePriceAggregated {
+        base,
+        quote,
+        price,
+        sources,
+        skipped,
+    }
+    .publish(e);
+}
+
+pub fn emit_oracle_failover(e: &Env, base: Address, quote: Address, oracle: Address, reason: Symbol) {
+    OracleFailover {
+        base,
+        quote,
+        oracle,
+        reason,
+    }
+    .publish(e);
+}
+
+pub fn emit_twap_observation_recorded(e: &Env, escrow_id: u32, price: i128, observations: u32) {
+    TwapObservationRecorded {
+        escrow_id,
+        price,
+        observations,
+    }
+    .publish(e);
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn emit_htlc_created(
+    e: &Env,
+    htlc_id: u32,
+    sender: Address,
+    receiver: Address,
+    token: Address,
+    amount: i128,
+    hashlock: BytesN<32>,
+    timeout: u64,
+) {
+    HtlcCreated {
+        htlc_id,
+        sender,
+        receiver,
+        token,
+        amount,
+        hashlock,
+        timeout,
+    }
+    .publish(e);
+}
+
+pub fn emit_htlc_claimed(e: &Env, htlc_id: u32, receiver: Address, amount: i128, preimage: Bytes) {
+    HtlcClaimed {
+        htlc_id,
+        receiver,
+        amount,
+        preimage,
+    }
+    .publish(e);
+}
+
+pub fn emit_htlc_refunded(e: &Env, htlc_id: u32, sender: Address, amount: i128) {
+    HtlcRefunded {
+        htlc_id,
+        sender,
+        amount,
+    }
+    .publish(e);
+}
+
 pub fn emit_escrow_refunded(e: &Env, escrow_id: u32, buyer: Address, amount: i128) {
     EscrowRefunded {
         escrow_id,
@@ -1151,10 +1322,31 @@ pub struct AmendmentSigned {
 pub struct AmendmentApplied {
     pub escrow_id: u32,
     pub nonce: u32,
+    pub version: u32,
     pub old_amount: i128,
     pub new_amount: i128,
     pub old_deadline: u64,
     pub new_deadline: u64,
+    pub old_arbiter: Address,
+    pub new_arbiter: Address,
+    pub milestones_changed: bool,
+}
+
+/// Event: Amendment proposal rejected by the counterparty
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct AmendmentRejected {
+    pub escrow_id: u32,
+    pub nonce: u32,
+    pub rejected_by: Address,
+}
+
+/// Event: Amendment proposal expired without acceptance
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct AmendmentExpired {
+    pub escrow_id: u32,
+    pub nonce: u32,
 }
 
 /// Event: Amendment proposal cancelled
@@ -1195,29 +1387,35 @@ pub fn emit_amendment_signed(e: &Env, escrow_id: u32, nonce: u32, signer: Addres
     .publish(e);
 }
 
-#[allow(clippy::too_many_arguments)]
-pub fn emit_amendment_applied(
-    e: &Env,
-    escrow_id: u32,
-    nonce: u32,
-    old_amount: i128,
-    new_amount: i128,
-    old_deadline: u64,
-    new_deadline: u64,
-    _old_metadata_hash: Option<BytesN<32>>,
-    _new_metadata_hash: Option<BytesN<32>>,
-) {
+pub fn emit_amendment_applied(e: &Env, escrow_id: u32, record: &AmendmentRecord) {
     AmendmentApplied {
+        escrow_id,
+        nonce: record.nonce,
+        version: record.version,
+        old_amount: record.old_amount,
+        new_amount: record.new_amount,
+        old_deadline: record.old_deadline,
+        new_deadline: record.new_deadline,
+        old_arbiter: record.old_arbiter.clone(),
+        new_arbiter: record.new_arbiter.clone(),
+        milestones_changed: record.milestones_changed,
+    }
+    .publish(e);
+}
+
+pub fn emit_amendment_rejected(e: &Env, escrow_id: u32, nonce: u32, rejected_by: Address) {
+    AmendmentRejected {
         escrow_id,
         nonce,
-        old_amount,
-        new_amount,
-        old_deadline,
-        new_deadline,
+        rejected_by,
     }
     .publish(e);
 }
 
+pub fn emit_amendment_expired(e: &Env, escrow_id: u32, nonce: u32) {
+    AmendmentExpired { escrow_id, nonce }.publish(e);
+}
+
 pub fn emit_amendment_cancelled(e: &Env, escrow_id: u32, nonce: u32, cancelled_by: Address) {
     AmendmentCancelled {
         escrow_id,
@@ -1833,3 +2031,245 @@ pub fn emit_bounty_milestone_verifier_replaced(
     }
     .publish(e);
 }
+
+// ── Panel Arbitration ────────────────────────────────────────────────────────
+
+/// Event: Arbitration panel convened for a disputed escrow
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct ArbitrationPanelOpened {
+    pub escrow_id: u32,
+    pub members: Vec<Address>,
+    pub voting_deadline: u64,
+}
+
+/// Event: Panel member voted a buyer split
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct PanelVoteSubmitted {
+    pub escrow_id: u32,
+    pub arbiter: Address,
+    pub buyer_bps: u32,
+}
+
+/// Event: Panel reached a verdict (median of the votes cast)
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct ArbitrationPanelDecided {
+    pub escrow_id: u32,
+    pub buyer_bps: u32,
+    pub votes_cast: u32,
+}
+
+/// Event: Panel voting window closed with no votes
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct ArbitrationPanelLapsed {
+    pub escrow_id: u32,
+}
+
+pub fn emit_arbitration_panel_opened(
+    e: &Env,
+    escrow_id: u32,
+    members: Vec<Address>,
+    voting_deadline: u64,
+) {
+    ArbitrationPanelOpened {
+        escrow_id,
+        members,
+        voting_deadline,
+    }
+    .publish(e);
+}
+
+pub fn emit_panel_vote_submitted(e: &Env, escrow_id: u32, arbiter: Address, buyer_bps: u32) {
+    PanelVoteSubmitted {
+        escrow_id,
+        arbiter,
+        buyer_bps,
+    }
+    .publish(e);
+}
+
+pub fn emit_arbitration_panel_decided(e: &Env, escrow_id: u32, buyer_bps: u32, votes_cast: u32) {
+    ArbitrationPanelDecided {
+        escrow_id,
+        buyer_bps,
+        votes_cast,
+    }
+    .publish(e);
+}
+
+pub fn emit_arbitration_panel_lapsed(e: &Env, escrow_id: u32) {
+    ArbitrationPanelLapsed { escrow_id }.publish(e);
+}
+
+// ── Arbiter Staking ──────────────────────────────────────────────────────────
+
+/// Event: Arbiter posted stake
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct ArbiterStaked {
+    pub arbiter: Address,
+    pub amount: i128,
+    pub total_stake: i128,
+}
+
+/// Event: Arbiter started unbonding stake
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct ArbiterUnbondingStarted {
+    pub arbiter: Address,
+    pub amount: i128,
+    pub available_at: u64,
+}
+
+/// Event: Arbiter withdrew unbonded stake
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct ArbiterStakeWithdrawn {
+    pub arbiter: Address,
+    pub amount: i128,
+}
+
+/// Event: Arbiter stake slashed and paid to the affected party
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct ArbiterSlashed {
+    pub arbiter: Address,
+    pub escrow_id: u32,
+    pub amount: i128,
+    pub recipient: Address,
+}
+
+/// Event: Admin overturned a flagged ruling during cooling-off
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct ResolutionOverturned {
+    pub escrow_id: u32,
+    pub original_buyer_percent: u32,
+    pub buyer_percent: u32,
+    pub flagger: Address,
+}
+
+pub fn emit_arbiter_staked(e: &Env, arbiter: Address, amount: i128, total_stake: i128) {
+    ArbiterStaked {
+        arbiter,
+        amount,
+        total_stake,
+    }
+    .publish(e);
+}
+
+pub fn emit_arbiter_unbonding_started(e: &Env, arbiter: Address, amount: i128, available_at: u64) {
+    ArbiterUnbondingStarted {
+        arbiter,
+        amount,
+        available_at,
+    }
+    .publish(e);
+}
+
+pub fn emit_arbiter_stake_withdrawn(e: &Env, arbiter: Address, amount: i128) {
+    ArbiterStakeWithdrawn { arbiter, amount }.publish(e);
+}
+
+pub fn emit_arbiter_slashed(
+    e: &Env,
+    arbiter: Address,
+    escrow_id: u32,
+    amount: i128,
+    recipient: Address,
+) {
+    ArbiterSlashed {
+        arbiter,
+        escrow_id,
+        amount,
+        recipient,
+    }
+    .publish(e);
+}
+
+pub fn emit_resolution_overturned(
+    e: &Env,
+    escrow_id: u32,
+    original_buyer_percent: u32,
+    buyer_percent: u32,
+    flagger: Address,
+) {
+    ResolutionOverturned {
+        escrow_id,
+        original_buyer_percent,
+        buyer_percent,
+        flagger,
+    }
+    .publish(e);
+}
+
+/// Event: Admin set the keeper bounty terms
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct KeeperConfigSet {
+    pub token: Address,
+    pub bounty_per_item: i128,
+    pub max_reward_per_call: i128,
+    pub max_reward_per_ledger: i128,
+}
+
+/// Event: Admin added funds to the keeper pool
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct KeeperPoolFunded {
+    pub amount: i128,
+    pub balance: i128,
+}
+
+/// Event: Admin withdrew funds from the keeper pool
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct KeeperPoolWithdrawn {
+    pub amount: i128,
+    pub balance: i128,
+}
+
+/// Event: Keeper was paid for processing maintenance items
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct KeeperRewarded {
+    pub keeper: Address,
+    pub items: u32,
+    pub amount: i128,
+}
+
+pub fn emit_keeper_config_set(
+    e: &Env,
+    token: Address,
+    bounty_per_item: i128,
+    max_reward_per_call: i128,
+    max_reward_per_ledger: i128,
+) {
+    KeeperConfigSet {
+        token,
+        bounty_per_item,
+        max_reward_per_call,
+        max_reward_per_ledger,
+    }
+    .publish(e);
+}
+
+pub fn emit_keeper_pool_funded(e: &Env, amount: i128, balance: i128) {
+    KeeperPoolFunded { amount, balance }.publish(e);
+}
+
+pub fn emit_keeper_pool_withdrawn(e: &Env, amount: i128, balance: i128) {
+    KeeperPoolWithdrawn { amount, balance }.publish(e);
+}
+
+pub fn emit_keeper_rewarded(e: &Env, keeper: Address, items: u32, amount: i128) {
+    KeeperRewarded {
+        keeper,
+        items,
+        amount,
+    }
+    .publish(e);
+}
@@ -1,5 +1,8 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, token, Address, BytesN, Env, String, Symbol, Vec};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, panic_with_error, symbol_short, token, Address, Bytes,
+    BytesN, Env, String, Symbol, Vec,
+};
 use ahjoor_token_whitelist::TokenWhitelistClient;
 
 // --- Storage TTL Constants ---
@@ -13,6 +16,8 @@ const MAX_EVIDENCE_ENTRIES_PER_PARTY: u32 = 5;
 const DEFAULT_DISPUTE_TIMEOUT_SECONDS: u64 = 7 * 24 * 60 * 60;
 const MAX_BATCH_ESCROWS: u32 = 10;
 const DEFAULT_MAX_ORACLE_AGE_SECONDS: u64 = 300;
+const MAX_ORACLE_SOURCES: u32 = 10;
+const MAX_TWAP_OBSERVATIONS: u32 = 24;
 const DEFAULT_INSURANCE_TRIGGER_DAYS: u64 = 7;
 const DEFAULT_MAX_TOPUP_MULTIPLIER: u32 = 3;
 const DEFAULT_PARTIAL_RELEASE_RESPONSE_DEADLINE: u64 = 86400; // 1 day
@@ -69,6 +74,14 @@ pub struct ReleaseCondition {
     pub threshold_price: i128,
 }
 
+/// A price reading recorded for an escrow's TWAP release condition.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TwapObservation {
+    pub price: i128,
+    pub timestamp: u64,
+}
+
 /// Conditional release based on external contract state (#318)
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -78,12 +91,7 @@ pub struct ConditionalRelease {
     pub expected_value: i128,
 }
 
-#[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct PriceData {
-    pub price: i128,
-    pub timestamp: u64,
-}
+pub use ahjoor_types::{KeeperConfig, KeeperPool, PriceData};
 
 /// Auto-renewal configuration for recurring service agreements.
 /// When provided at escrow creation, the escrow will automatically re-fund
@@ -120,6 +128,12 @@ pub struct EscrowCreateRequest {
     /// Optional auto-renewal configuration for recurring service agreements.
     pub auto_renew_max_renewals: Option<u32>,
     pub auto_renew_interval_ledgers: Option<u32>,
+    /// TWAP mode for the price release condition: release only when the
+    /// time-weighted mean price over the last `twap_window_seconds`, sampled
+    /// `twap_observation_count` times per window, meets the comparison.
+    /// 0 = spot price.
+    pub twap_observation_count: u32,
+    pub twap_window_seconds: u64,
 }
 
 #[contracttype]
@@ -186,6 +200,10 @@ pub struct EscrowExtensions {
     pub auto_renew_interval_ledgers: Option<u32>,
     /// Number of renewal cycles completed so far (incremented on each successful renewal).
     pub renewals_completed: u32,
+    pub twap_observation_count: u32,
+    pub twap_window_seconds: u64,
+    /// Most recent TWAP readings, oldest first, at most `twap_observation_count + 1`.
+    pub twap_observations: Vec<TwapObservation>,
 }
 
 #[contracttype]
@@ -216,6 +234,26 @@ pub struct TimeLockData {
     pub claimed: bool,
 }
 
+/// Hash time-locked escrow: the receiver claims with the sha256 preimage of
+/// `hashlock` before `timeout`; after it the sender can reclaim the funds.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HtlcEscrow {
+    pub id: u32,
+    pub sender: Address,
+    pub receiver: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub hashlock: BytesN<32>,
+    /// Ledger timestamp from which the sender may reclaim.
+    pub timeout: u64,
+    /// Active until claimed (Released) or reclaimed (Refunded).
+    pub status: EscrowStatus,
+    /// Preimage revealed by the receiver's claim.
+    pub preimage: Option<Bytes>,
+    pub created_at: u64,
+}
+
 /// #229: Mutual cancellation request record.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -403,6 +441,8 @@ pub enum DataKey2 {
     AmendmentNonce(u32),
     /// Admin-configurable amendment proposal expiry window in seconds
     AmendmentExpirySeconds,
+    /// Applied amendments per escrow (escrow_id → Vec<AmendmentRecord>)
+    AmendmentHistory(u32),
     /// #272: Inspector report per escrow
     InspectorReport(u32),
     /// #272: Pending inspector replacement
@@ -459,6 +499,39 @@ pub enum DataKey2 {
     BountyMilestones(u32),
     /// Set to true once add_allowed_token is called; activates internal token allowlist enforcement.
     AllowlistActivated,
+    /// Arbitration panel convened for a disputed escrow (escrow_id → ArbitrationPanel)
+    ArbitrationPanel(u32),
+    /// Admin-configurable panel voting window in seconds
+    PanelVotingWindow,
+    /// Minimum escrow amount for which a panel may be requested (0 = any amount)
+    PanelMinEscrowAmount,
+    /// Arbiter staking configuration (ArbiterStakeConfig)
+    ArbiterStakeConfig,
+    /// Stake posted by an arbiter (arbiter → ArbiterStake)
+    ArbiterStake(Address),
+    /// Arbiter fees earned per token (arbiter, token) → i128
+    ArbiterFeesEarned(Address, Address),
+    /// Overturned pending ruling awaiting finalization (escrow_id → flagging party)
+    OverturnedRuling(u32),
+    /// Keeper bounty terms → KeeperConfig
+    KeeperConfig,
+    /// Keeper pool balance and per-ledger tally → KeeperPool
+    KeeperPool,
+    /// Oracle set aggregated for a pair (base, quote) → OracleSet
+    OracleSet(Address, Address),
+}
+
+/// Overflow key enum — DataKey2 is capped at 50 variants by the soroban XDR limit.
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey3 {
+    /// Next HTLC escrow id
+    HtlcCounter,
+    /// Hash time-locked escrow (htlc_id → HtlcEscrow)
+    Htlc(u32),
+    /// Admin approval for the arbiter pool (arbiter → bool), set by `add_arbiter`
+    /// and revoked by `remove_arbiter`
+    ApprovedArbiter(Address),
 }
 
 /// #357: On-chain reputation record for an inspector.
@@ -469,6 +542,18 @@ pub struct InspectorScore {
     pub correct_rulings: u32,
 }
 
+/// Oracle sources for a base/quote pair. Prices are the median of the
+/// sources that answer with a fresh, positive price.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OracleSet {
+    pub oracles: Vec<Address>,
+    /// Fewest fresh sources a price may be built from.
+    pub min_sources: u32,
+    /// Furthest any fresh source may sit from the median, in bps.
+    pub max_deviation_bps: u32,
+}
+
 /// #353: A single time-locked tranche in a staged release schedule.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -513,12 +598,15 @@ pub struct MilestoneState {
 
 const MAX_PROTOCOL_FEE_BPS: u32 = 200; // 2%
 const MAX_ARBITER_FEE_BPS: u32 = 1_000; // 10%
+/// Panel votes within this many bps of an overturned ruling's final split are not slashed
+pub const OVERTURN_VOTE_TOLERANCE_BPS: u32 = 500; // 5%
 const DEFAULT_RESOLUTION_COOLING_OFF_SECONDS: u64 = 24 * 60 * 60; // 24 hours
 const DEFAULT_SELLER_TRANSFER_VETO_WINDOW: u32 = 100; // ledgers
 const DEFAULT_AMENDMENT_EXPIRY_SECONDS: u64 = 7 * 24 * 60 * 60; // 7 days
 const DEFAULT_MAX_BOUNTY_REJECTION_ROUNDS: u32 = 3; // #319: max times a bounty can be rejected and re-opened
 /// #366: default admin veto override window — 48 hours
 const DEFAULT_VETO_OVERRIDE_WINDOW_SECONDS: u64 = 48 * 60 * 60;
+const DEFAULT_PANEL_VOTING_WINDOW_SECONDS: u64 = 3 * 24 * 60 * 60; // 3 days
 
 // ── #319: Bounty Board for Open Competitive Work Assignment ──────────────────
 
@@ -608,6 +696,11 @@ pub struct AmendmentProposal {
     pub new_deadline: Option<u64>,
     /// New metadata hash, or None to keep current.
     pub new_metadata_hash: Option<BytesN<32>>,
+    /// Replacement for the escrow's pending milestones, or None to keep current.
+    /// Milestones that are already approved or disputed are always kept.
+    pub new_milestones: Option<Vec<Milestone>>,
+    /// New arbiter, or None to keep current.
+    pub new_arbiter: Option<Address>,
     /// Ledger timestamp when this proposal was created.
     pub proposed_at: u64,
     /// Ledger timestamp after which this proposal expires.
@@ -618,6 +711,28 @@ pub struct AmendmentProposal {
     pub seller_signed: bool,
 }
 
+/// Applied amendment, kept as a versioned history entry per escrow.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AmendmentRecord {
+    /// 1-based amendment version for this escrow.
+    pub version: u32,
+    pub nonce: u32,
+    pub proposer: Address,
+    pub accepted_by: Address,
+    pub applied_at: u64,
+    pub old_amount: i128,
+    pub new_amount: i128,
+    pub old_deadline: u64,
+    pub new_deadline: u64,
+    pub old_arbiter: Address,
+    pub new_arbiter: Address,
+    pub old_metadata_hash: Option<BytesN<32>>,
+    pub new_metadata_hash: Option<BytesN<32>>,
+    /// Whether the pending milestone list was replaced.
+    pub milestones_changed: bool,
+}
+
 /// #272: Inspector report stored on-chain.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -641,24 +756,66 @@ pub struct InspectorReplacement {
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PendingVerdict {
-    pub buyer_percent: u32,
+    /// Buyer's share in basis points (0–10_000); panel verdicts need the precision.
+    pub buyer_bps: u32,
     pub arbiter: Address,
     pub recorded_at: u64,
 }
 
-mod oracle {
-    use crate::PriceData;
-    use soroban_sdk::{contractclient, Address, Env};
+/// Admin-configured requirements for arbiters joining the pool.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArbiterStakeConfig {
+    /// Allowed token stakes are posted in
+    pub token: Address,
+    /// Active stake required to sit in the pool
+    pub min_stake: i128,
+    /// Share of total stake slashed per timeout or overturned ruling
+    pub slash_bps: u32,
+    /// Delay between unbonding and withdrawal
+    pub unbonding_secs: u64,
+}
+
+/// Stake posted by an arbiter.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArbiterStake {
+    pub token: Address,
+    /// Active stake counting towards pool membership
+    pub amount: i128,
+    /// Stake waiting out the unbonding delay (still slashable), oldest request first
+    pub unbonding: Vec<UnbondingTranche>,
+}
 
-    #[allow(dead_code)]
-    #[contractclient(name = "OracleClient")]
-    pub trait OracleInterface {
-        fn lastprice(env: Env, base: Address, quote: Address) -> Option<PriceData>;
-    }
+/// One `unbond_arbiter_stake` request, withdrawable on its own schedule.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnbondingTranche {
+    pub amount: i128,
+    pub available_at: u64,
+}
+
+/// Panel of pool arbiters voting on a disputed escrow. Each member votes a buyer split in
+/// basis points; the median of the votes cast becomes the verdict.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArbitrationPanel {
+    pub members: Vec<Address>,
+    /// (arbiter, buyer_bps) in the order votes were cast
+    pub votes: Vec<(Address, u32)>,
+    pub opened_at: u64,
+    pub voting_deadline: u64,
+    /// Median buyer split once the panel has decided
+    pub decided_bps: Option<u32>,
 }
 
+use ahjoor_types::oracle;
+
+mod errors;
 mod events;
 
+pub use errors::{DisputeError, Error, MilestoneError, WorkflowError};
+
 #[contract]
 pub struct AhjoorEscrowContract;
 
@@ -667,7 +824,7 @@ impl AhjoorEscrowContract {
     /// Initialize upgrade admin and contract versioning state.
     pub fn initialize(env: Env, admin: Address) {
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("Already initialized");
+            panic_with_error!(&env, Error::AlreadyInitialized);
         }
 
         env.storage().instance().set(&DataKey::Admin, &admin);
@@ -736,6 +893,8 @@ impl AhjoorEscrowContract {
             auto_renew_max_renewals: None,
 
             auto_renew_interval_ledgers: None,
+            twap_observation_count: 0,
+            twap_window_seconds: 0,
         };
 
         Self::create_escrow_core(&env, &buyer, request)
@@ -781,6 +940,8 @@ impl AhjoorEscrowContract {
             auto_renew_max_renewals: None,
 
             auto_renew_interval_ledgers: None,
+            twap_observation_count: 0,
+            twap_window_seconds: 0,
         };
 
         Self::create_escrow_core(&env, &buyer, request)
@@ -831,6 +992,8 @@ impl AhjoorEscrowContract {
             dispute_default_winner: None,
             auto_renew_max_renewals: Some(auto_renew_config.max_renewals),
             auto_renew_interval_ledgers: Some(auto_renew_config.renewal_interval_ledgers),
+            twap_observation_count: 0,
+            twap_window_seconds: 0,
         };
 
         Self::create_escrow_core(&env, &buyer, request)
@@ -847,10 +1010,10 @@ impl AhjoorEscrowContract {
         buyer.require_auth();
 
         if escrow_configs.is_empty() {
-            panic!("Batch must contain at least one escrow config");
+            panic_with_error!(&env, Error::EmptyBatch);
         }
         if escrow_configs.len() > MAX_BATCH_ESCROWS {
-            panic!("Batch size exceeds maximum of 10 escrows");
+            panic_with_error!(&env, Error::BatchTooLarge);
         }
 
         let mut created_ids: Vec<u32> = Vec::new(&env);
@@ -883,6 +1046,8 @@ impl AhjoorEscrowContract {
                     auto_renew_max_renewals: None,
 
                     auto_renew_interval_ledgers: None,
+                    twap_observation_count: 0,
+                    twap_window_seconds: 0,
                 },
             );
 
@@ -896,7 +1061,7 @@ impl AhjoorEscrowContract {
         events::emit_batch_escrow_created(
             &env,
             escrow_configs.len(),
-            first_id.expect("Batch contained no escrows"),
+            first_id.unwrap_or_else(|| panic_with_error!(&env, Error::EmptyBatch)),
             last_id,
         );
 
@@ -921,7 +1086,8 @@ impl AhjoorEscrowContract {
         let escrow_id = Self::create_escrow_core(&env, &buyer, request);
         if let Some(ref insp) = inspector {
             let mut escrow: Escrow = env
-                .storage().persistent().get(&DataKey::Escrow(escrow_id)).expect("Escrow not found");
+                .storage().persistent().get(&DataKey::Escrow(escrow_id))
+                .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
             escrow.extensions.inspector = Some(insp.clone());
             env.storage().persistent().set(&DataKey::Escrow(escrow_id), &escrow);
             env.storage().persistent().extend_ttl(
@@ -937,10 +1103,11 @@ impl AhjoorEscrowContract {
         Self::require_not_paused(&env);
         seller.require_auth();
         let mut escrow: Escrow = env
-            .storage().persistent().get(&DataKey::Escrow(escrow_id)).expect("Escrow not found");
-        if escrow.seller != seller { panic!("Only seller can mark complete"); }
-        if !Self::is_open_escrow_status(escrow.status) { panic!("Escrow is not active"); }
-        if escrow.extensions.inspector.is_none() { panic!("No inspector set; use release_escrow directly"); }
+            .storage().persistent().get(&DataKey::Escrow(escrow_id))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
+        if escrow.seller != seller { panic_with_error!(&env, Error::NotSeller); }
+        if !Self::is_open_escrow_status(escrow.status) { panic_with_error!(&env, Error::EscrowNotActive); }
+        if escrow.extensions.inspector.is_none() { panic_with_error!(&env, WorkflowError::NoInspector); }
         escrow.status = EscrowStatus::AwaitingInspection;
         env.storage().persistent().set(&DataKey::Escrow(escrow_id), &escrow);
         env.storage().persistent().extend_ttl(
@@ -961,12 +1128,14 @@ impl AhjoorEscrowContract {
         Self::require_not_paused(&env);
         inspector.require_auth();
         let mut escrow: Escrow = env
-            .storage().persistent().get(&DataKey::Escrow(escrow_id)).expect("Escrow not found");
+            .storage().persistent().get(&DataKey::Escrow(escrow_id))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
         if escrow.status != EscrowStatus::AwaitingInspection {
-            panic!("Escrow is not awaiting inspection");
+            panic_with_error!(&env, WorkflowError::NotAwaitingInspection);
         }
-        let stored_inspector = escrow.extensions.inspector.clone().expect("No inspector set");
-        if inspector != stored_inspector { panic!("Only the assigned inspector can submit a report"); }
+        let stored_inspector = escrow.extensions.inspector.clone()
+            .unwrap_or_else(|| panic_with_error!(&env, WorkflowError::NoInspector));
+        if inspector != stored_inspector { panic_with_error!(&env, WorkflowError::NotInspector); }
         let report = InspectorReport {
             inspector: inspector.clone(),
             approved,
@@ -991,11 +1160,12 @@ impl AhjoorEscrowContract {
         Self::require_not_paused(&env);
         caller.require_auth();
         let escrow: Escrow = env
-            .storage().persistent().get(&DataKey::Escrow(escrow_id)).expect("Escrow not found");
+            .storage().persistent().get(&DataKey::Escrow(escrow_id))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
         if caller != escrow.buyer && caller != escrow.seller {
-            panic!("Only buyer or seller can propose inspector replacement");
+            panic_with_error!(&env, Error::NotEscrowParty);
         }
-        if escrow.extensions.inspector.is_none() { panic!("No inspector set on this escrow"); }
+        if escrow.extensions.inspector.is_none() { panic_with_error!(&env, WorkflowError::NoInspector); }
         let key = DataKey2::InspectorReplacement(escrow_id);
         let mut replacement: InspectorReplacement = env
             .storage().persistent().get(&key)
@@ -1048,9 +1218,10 @@ impl AhjoorEscrowContract {
     ) {
         admin.require_auth();
         let stored_admin: Address = env
-            .storage().instance().get(&DataKey::Admin).expect("Not initialized");
-        if admin != stored_admin { panic!("Only admin can set inspector score threshold"); }
-        if min_score_bps > 10_000 { panic!("min_score_bps must be <= 10000"); }
+            .storage().instance().get(&DataKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
+        if admin != stored_admin { panic_with_error!(&env, Error::Unauthorized); }
+        if min_score_bps > 10_000 { panic_with_error!(&env, Error::InvalidBps); }
         env.storage().instance().set(&DataKey2::MinInspectorScoreBps, &min_score_bps);
         env.storage().instance().set(&DataKey2::InspectorScoreValueThreshold, &value_threshold);
         env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -1080,13 +1251,16 @@ impl AhjoorEscrowContract {
         Self::require_not_paused(&env);
         admin.require_auth();
         let stored_admin: Address = env
-            .storage().instance().get(&DataKey::Admin).expect("Not initialized");
-        if admin != stored_admin { panic!("Only admin can appeal inspector ruling"); }
+            .storage().instance().get(&DataKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
+        if admin != stored_admin { panic_with_error!(&env, Error::Unauthorized); }
 
         // Ensure an inspector was involved
         let escrow: Escrow = env
-            .storage().persistent().get(&DataKey::Escrow(escrow_id)).expect("Escrow not found");
-        let inspector = escrow.extensions.inspector.clone().expect("No inspector on this escrow");
+            .storage().persistent().get(&DataKey::Escrow(escrow_id))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
+        let inspector = escrow.extensions.inspector.clone()
+            .unwrap_or_else(|| panic_with_error!(&env, WorkflowError::NoInspector));
 
         // Prevent double-appeal
         let already_appealed: bool = env
@@ -1094,7 +1268,7 @@ impl AhjoorEscrowContract {
             .persistent()
             .get(&DataKey2::InspectorRulingAppealed(escrow_id))
             .unwrap_or(false);
-        if already_appealed { panic!("Inspector ruling already appealed for this escrow"); }
+        if already_appealed { panic_with_error!(&env, WorkflowError::InspectorRulingAppealed); }
 
         // Decrement correct_rulings (floor at 0)
         let key = DataKey2::InspectorScore(inspector.clone());
@@ -1174,7 +1348,7 @@ impl AhjoorEscrowContract {
 
         let accuracy_bps = (score.correct_rulings * 10_000) / score.total_rulings;
         if accuracy_bps < min_score_bps {
-            panic!("Inspector score below minimum threshold for high-value escrow");
+            panic_with_error!(env, WorkflowError::InspectorScoreTooLow);
         }
     }
 
@@ -1199,27 +1373,30 @@ impl AhjoorEscrowContract {
             dispute_default_winner,
             auto_renew_max_renewals,
             auto_renew_interval_ledgers,
+            twap_observation_count,
+            twap_window_seconds,
         } = request;
 
         if amount <= 0 {
-            panic!("Escrow amount must be positive");
+            panic_with_error!(env, Error::InvalidAmount);
         }
 
         if deadline <= env.ledger().timestamp() {
-            panic!("Deadline must be in the future");
+            panic_with_error!(env, Error::InvalidDeadline);
         }
 
         if let Some(lock_until) = min_lock_until {
             if deadline <= lock_until {
-                panic!("Deadline must be after min_lock_until");
+                panic_with_error!(env, Error::InvalidDeadline);
             }
         }
 
         if let Some(fee_bps) = arbiter_fee_bps {
             if fee_bps > MAX_ARBITER_FEE_BPS {
-                panic!("Arbiter fee exceeds maximum of 1000 bps");
+                panic_with_error!(env, Error::FeeTooHigh);
             }
         }
+        Self::require_arbiter_staked(env, &arbiter);
 
         let has_any_release_condition = release_base.is_some()
             || release_quote.is_some()
@@ -1230,12 +1407,12 @@ impl AhjoorEscrowContract {
             && release_comparison.is_some()
             && release_threshold_price.is_some();
         if has_any_release_condition && !has_full_release_condition {
-            panic!("Incomplete release condition");
+            panic_with_error!(env, Error::InvalidReleaseCondition);
         }
 
         if let Some(threshold) = release_threshold_price {
             if threshold <= 0 {
-                panic!("Release condition threshold must be positive");
+                panic_with_error!(env, Error::InvalidReleaseCondition);
             }
         }
 
@@ -1243,10 +1420,19 @@ impl AhjoorEscrowContract {
             if comparison != ORACLE_COMPARISON_LESS_OR_EQUAL
                 && comparison != ORACLE_COMPARISON_GREATER_OR_EQUAL
             {
-                panic!("Invalid release comparison");
+                panic_with_error!(env, Error::InvalidReleaseCondition);
             }
         }
 
+        // TWAP mode needs a price condition and readings at least a second apart
+        if (twap_observation_count > 0 || twap_window_seconds > 0)
+            && (!has_full_release_condition
+                || !(2..=MAX_TWAP_OBSERVATIONS).contains(&twap_observation_count)
+                || twap_window_seconds < twap_observation_count as u64)
+        {
+            panic_with_error!(env, Error::InvalidReleaseCondition);
+        }
+
         // Token whitelist validation
         Self::require_token_allowed(&env, &token);
 
@@ -1258,7 +1444,7 @@ impl AhjoorEscrowContract {
             v
         } else {
             if sellers.len() > 5 {
-                panic!("Maximum 5 sellers allowed");
+                panic_with_error!(env, Error::InvalidSellers);
             }
             let mut total_bps: u32 = 0;
             for i in 0..sellers.len() {
@@ -1266,7 +1452,7 @@ impl AhjoorEscrowContract {
                 total_bps += bps;
             }
             if total_bps != 10_000 {
-                panic!("Seller allocations must sum to 10000 bps");
+                panic_with_error!(env, Error::InvalidSellers);
             }
             sellers
         };
@@ -1321,6 +1507,9 @@ impl AhjoorEscrowContract {
                 auto_renew_max_renewals,
                 auto_renew_interval_ledgers,
                 renewals_completed: 0,
+                twap_observation_count,
+                twap_window_seconds,
+                twap_observations: Vec::new(env),
             },
             top_up_history: Vec::new(env),
             top_up_acknowledged: true,
@@ -1424,7 +1613,7 @@ impl AhjoorEscrowContract {
         dispute_timeout_seconds: u64,
     ) -> u32 {
         if dispute_timeout_seconds == 0 {
-            panic!("dispute_timeout_seconds must be positive");
+            panic_with_error!(&env, Error::InvalidDuration);
         }
 
         let auto_renew = renewal_count > 0;
@@ -1446,7 +1635,7 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
         escrow.extensions.dispute_timeout_seconds = Some(dispute_timeout_seconds);
 
         env.storage()
@@ -1470,18 +1659,18 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::EscrowReceipt(receipt_id))
-            .expect("Receipt not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::ReceiptNotFound));
 
         if caller != receipt.holder {
-            panic!("Only current holder can transfer receipt");
+            panic_with_error!(&env, Error::NotReceiptHolder);
         }
 
         // Block transfers for milestone escrows or pending partial releases
         if env.storage().persistent().has(&DataKey::EscrowMilestones(receipt.escrow_id)) {
-            panic!("ActiveMilestoneInProgress");
+            panic_with_error!(&env, Error::ActiveMilestoneInProgress);
         }
         if env.storage().persistent().has(&DataKey::PendingPartialRelease(receipt.escrow_id)) {
-            panic!("ActiveMilestoneInProgress");
+            panic_with_error!(&env, Error::ActiveMilestoneInProgress);
         }
 
         let old = receipt.holder.clone();
@@ -1512,23 +1701,23 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         let renewal_source = escrow.clone();
 
         // #272: Block release if inspection is pending
         if escrow.status == EscrowStatus::AwaitingInspection {
-            panic!("InspectionPending: inspector must submit report before release");
+            panic_with_error!(&env, WorkflowError::InspectionPending);
         }
 
         if escrow.status == EscrowStatus::InspectionPassed {
             // Allow release from InspectionPassed — fall through
         } else if !Self::is_open_escrow_status(escrow.status) {
-            panic!("Escrow is not active");
+            panic_with_error!(&env, Error::EscrowNotActive);
         }
 
         if caller != escrow.buyer && caller != escrow.arbiter {
-            panic!("Only buyer or arbiter can release escrow");
+            panic_with_error!(&env, Error::NotBuyerOrArbiter);
         }
 
         // #366: Block release if seller has raised an active veto
@@ -1537,7 +1726,7 @@ impl AhjoorEscrowContract {
             .persistent()
             .get(&DataKey2::VetoTimestamp(escrow_id));
         if veto_ts.is_some() {
-            panic!("SellerVetoActive: seller has blocked fund release");
+            panic_with_error!(&env, WorkflowError::SellerVetoActive);
         }
 
         Self::require_unlocked(&env, &escrow);
@@ -1599,7 +1788,8 @@ impl AhjoorEscrowContract {
 
     /// Trigger conditional release by verifying an on-chain oracle condition (#318).
     /// The escrow must have a `ConditionalRelease` condition set via `set_conditional_release`.
-    /// Callable by anyone; panics if the condition is not yet met.
+    /// Callable by anyone; panics if the condition is not yet met. Escrows in
+    /// TWAP mode are checked against their time-weighted mean price.
     pub fn trigger_conditional_release(env: Env, caller: Address, escrow_id: u32) {
         Self::require_not_paused(&env);
         caller.require_auth();
@@ -1608,23 +1798,27 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey2::ConditionalReleaseCondition(escrow_id))
-            .expect("No conditional release set for this escrow");
-
-        // Use the stored oracle address as the price feed source
-        let oracle_addr: Address = env
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NoReleaseCondition));
+        let escrow: Escrow = env
             .storage()
-            .instance()
-            .get(&DataKey::OracleAddress)
-            .expect("Oracle not configured");
+            .persistent()
+            .get(&DataKey::Escrow(escrow_id))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
-        let oracle_client = oracle::OracleClient::new(&env, &oracle_addr);
-        let condition_value: i128 = oracle_client
-            .lastprice(&condition.oracle_contract, &condition.oracle_contract)
-            .map(|pd| pd.price)
-            .unwrap_or(0);
+        // The pair's oracle set median when configured, else the stored oracle
+        let condition_value = if escrow.extensions.twap_observation_count > 0 {
+            Self::twap_price(&env, &escrow)
+        } else {
+            Self::get_oracle_price(
+                &env,
+                &condition.oracle_contract,
+                &condition.oracle_contract,
+            )
+            .price
+        };
 
         if condition_value < condition.expected_value {
-            panic!("Condition not met");
+            panic_with_error!(&env, Error::ReleaseConditionNotMet);
         }
 
         events::emit_conditional_release_triggered(
@@ -1658,10 +1852,10 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if caller != escrow.buyer && caller != escrow.seller {
-            panic!("Only buyer or seller can waive condition");
+            panic_with_error!(&env, Error::NotEscrowParty);
         }
 
         // Check if condition exists
@@ -1671,7 +1865,7 @@ impl AhjoorEscrowContract {
             .get::<_, ConditionalRelease>(&DataKey2::ConditionalReleaseCondition(escrow_id))
             .is_none()
         {
-            panic!("No conditional release set for this escrow");
+            panic_with_error!(&env, Error::NoReleaseCondition);
         }
 
         // Track signatures
@@ -1732,10 +1926,10 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if party != escrow.buyer && party != escrow.seller {
-            panic!("Only buyer or seller can submit evidence");
+            panic_with_error!(&env, Error::NotEscrowParty);
         }
 
         // #150: Track buyer activity
@@ -1751,7 +1945,7 @@ impl AhjoorEscrowContract {
             .unwrap_or(Vec::new(&env));
 
         if entries.len() >= MAX_EVIDENCE_ENTRIES_PER_PARTY {
-            panic!("Maximum evidence entries reached for this party");
+            panic_with_error!(&env, DisputeError::EvidenceLimitReached);
         }
 
         entries.push_back(EvidenceSubmission {
@@ -1776,7 +1970,7 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         let mut all: Vec<(Address, Vec<EvidenceSubmission>)> = Vec::new(&env);
 
@@ -1812,14 +2006,14 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if buyer != escrow.buyer {
-            panic!("Only buyer can set renewal allowance");
+            panic_with_error!(&env, Error::NotBuyer);
         }
 
         if !escrow.extensions.auto_renew {
-            panic!("Auto-renew is not enabled for this escrow");
+            panic_with_error!(&env, WorkflowError::AutoRenewDisabled);
         }
 
         let total_amount = escrow.amount * total_renewals as i128;
@@ -1851,10 +2045,10 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if buyer != escrow.buyer {
-            panic!("Only buyer can cancel auto-renew");
+            panic_with_error!(&env, Error::NotBuyer);
         }
 
         escrow.extensions.auto_renew = false;
@@ -1877,14 +2071,14 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if buyer != escrow.buyer {
-            panic!("Only buyer can cancel auto-renewal");
+            panic_with_error!(&env, Error::NotBuyer);
         }
 
         if escrow.extensions.auto_renew_max_renewals.is_none() {
-            panic!("No AutoRenewConfig set on this escrow");
+            panic_with_error!(&env, WorkflowError::AutoRenewDisabled);
         }
 
         env.storage()
@@ -1921,24 +2115,24 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if !Self::is_open_escrow_status(escrow.status) {
-            panic!("Escrow is not active");
+            panic_with_error!(&env, Error::EscrowNotActive);
         }
 
         if caller != escrow.buyer && caller != escrow.arbiter {
-            panic!("Only buyer or arbiter can release escrow");
+            panic_with_error!(&env, Error::NotBuyerOrArbiter);
         }
 
         Self::require_unlocked(&env, &escrow);
 
         if release_amount <= 0 {
-            panic!("Release amount must be positive");
+            panic_with_error!(&env, Error::InvalidAmount);
         }
 
         if release_amount > escrow.amount {
-            panic!("Release amount exceeds escrow balance");
+            panic_with_error!(&env, Error::ReleaseExceedsBalance);
         }
 
         let client = token::Client::new(&env, &escrow.token);
@@ -1992,20 +2186,20 @@ impl AhjoorEscrowContract {
         buyer.require_auth();
 
         if milestones.is_empty() {
-            panic!("At least one milestone required");
+            panic_with_error!(&env, MilestoneError::MilestoneRequired);
         }
         if milestones.len() > MAX_MILESTONES {
-            panic!("Too many milestones");
+            panic_with_error!(&env, MilestoneError::TooManyMilestones);
         }
 
         let mut total_amount: i128 = 0;
         for i in 0..milestones.len() {
             let m = milestones.get(i).unwrap();
             if m.amount <= 0 {
-                panic!("Milestone amount must be positive");
+                panic_with_error!(&env, Error::InvalidAmount);
             }
             if m.status != MilestoneStatus::Pending {
-                panic!("New milestones must start as Pending");
+                panic_with_error!(&env, MilestoneError::InvalidMilestones);
             }
             total_amount += m.amount;
         }
@@ -2031,6 +2225,8 @@ impl AhjoorEscrowContract {
             auto_renew_max_renewals: None,
 
             auto_renew_interval_ledgers: None,
+            twap_observation_count: 0,
+            twap_window_seconds: 0,
         };
         let escrow_id = Self::create_escrow_core(&env, &buyer, request);
 
@@ -2064,32 +2260,32 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if escrow.status == EscrowStatus::Released
             || escrow.status == EscrowStatus::Refunded
             || escrow.status == EscrowStatus::Resolved
         {
-            panic!("Escrow already terminal");
+            panic_with_error!(&env, Error::InvalidEscrowStatus);
         }
 
         if caller != escrow.buyer && caller != escrow.arbiter {
-            panic!("Only buyer or arbiter can approve milestones");
+            panic_with_error!(&env, Error::NotBuyerOrArbiter);
         }
 
         let mut milestones: Vec<Milestone> = env
             .storage()
             .persistent()
             .get(&DataKey::EscrowMilestones(escrow_id))
-            .expect("Escrow has no milestones");
+            .unwrap_or_else(|| panic_with_error!(&env, MilestoneError::NoMilestones));
 
         if milestone_index >= milestones.len() {
-            panic!("Milestone index out of range");
+            panic_with_error!(&env, MilestoneError::MilestoneIndexOutOfRange);
         }
 
         let mut milestone = milestones.get(milestone_index).unwrap();
         if milestone.status != MilestoneStatus::Pending {
-            panic!("Milestone not pending");
+            panic_with_error!(&env, MilestoneError::MilestoneNotPending);
         }
 
         let client = token::Client::new(&env, &escrow.token);
@@ -2147,7 +2343,7 @@ impl AhjoorEscrowContract {
         env.storage()
             .persistent()
             .get(&DataKey::EscrowMilestones(escrow_id))
-            .expect("Escrow has no milestones")
+            .unwrap_or_else(|| panic_with_error!(&env, MilestoneError::NoMilestones))
     }
 
     /// Dispute an escrow. Can be called by buyer or seller.
@@ -2167,18 +2363,18 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if !Self::is_open_escrow_status(escrow.status) {
-            panic!("Escrow is not active");
+            panic_with_error!(&env, Error::EscrowNotActive);
         }
 
         if caller != escrow.buyer && caller != escrow.seller {
-            panic!("Only buyer or seller can dispute escrow");
+            panic_with_error!(&env, Error::NotEscrowParty);
         }
 
         if dispute_amount <= 0 || dispute_amount > escrow.amount {
-            panic!("dispute_amount must be > 0 and <= escrow amount");
+            panic_with_error!(&env, DisputeError::InvalidDisputeAmount);
         }
 
         // #150: Track buyer activity
@@ -2253,7 +2449,8 @@ impl AhjoorEscrowContract {
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
     }
 
-    /// Resolve a dispute. Only arbiter can call this.
+    /// Resolve a dispute. Only the escrow's arbiter can call this, and only while holding the
+    /// minimum stake when staking is configured.
     /// `buyer_percent` is 0–100; seller receives the remainder.
     /// Use 100 for full buyer win, 0 for full seller win, or any value in between for a split.
     /// If a cooling-off window is configured, the escrow enters CoolingOff state and funds
@@ -2263,23 +2460,28 @@ impl AhjoorEscrowContract {
         arbiter.require_auth();
 
         if buyer_percent > 100 {
-            panic!("buyer_percent must be between 0 and 100");
+            panic_with_error!(&env, DisputeError::InvalidSplit);
         }
 
         let mut escrow: Escrow = env
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if escrow.status != EscrowStatus::Disputed
             && escrow.status != EscrowStatus::PartiallyDisputed
         {
-            panic!("Escrow is not disputed");
+            panic_with_error!(&env, DisputeError::EscrowNotDisputed);
         }
 
         if arbiter != escrow.arbiter {
-            panic!("Only arbiter can resolve dispute");
+            panic_with_error!(&env, DisputeError::NotArbiter);
+        }
+        Self::require_arbiter_staked(&env, &arbiter);
+
+        if env.storage().persistent().has(&DataKey2::ArbitrationPanel(escrow_id)) {
+            panic_with_error!(&env, DisputeError::UnderPanelArbitration);
         }
 
         let cooling_off_seconds: u64 = env
@@ -2291,7 +2493,7 @@ impl AhjoorEscrowContract {
         if cooling_off_seconds > 0 {
             // Enter cooling-off state — record verdict, do NOT move funds yet
             let verdict = PendingVerdict {
-                buyer_percent,
+                buyer_bps: buyer_percent * 100,
                 arbiter: arbiter.clone(),
                 recorded_at: env.ledger().timestamp(),
             };
@@ -2323,7 +2525,7 @@ impl AhjoorEscrowContract {
         }
 
         // No cooling-off configured — execute immediately (legacy path)
-        Self::execute_verdict(&env, escrow_id, escrow, buyer_percent, arbiter);
+        Self::execute_verdict(&env, escrow_id, escrow, buyer_percent * 100, arbiter);
 
         env.storage()
             .instance()
@@ -2341,21 +2543,21 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if escrow.status != EscrowStatus::CoolingOff {
-            panic!("Escrow is not in cooling-off state");
+            panic_with_error!(&env, DisputeError::NotInCoolingOff);
         }
 
         let verdict: PendingVerdict = env
             .storage()
             .persistent()
             .get(&DataKey2::PendingVerdict(escrow_id))
-            .expect("No pending verdict");
+            .unwrap_or_else(|| panic_with_error!(&env, DisputeError::NoPendingVerdict));
 
         // Only buyer or seller may flag
         if caller != escrow.buyer && caller != escrow.seller {
-            panic!("Only buyer or seller can flag a resolution error");
+            panic_with_error!(&env, Error::NotEscrowParty);
         }
 
         // Enforce cooling-off window has not expired
@@ -2366,12 +2568,12 @@ impl AhjoorEscrowContract {
             .unwrap_or(0);
         let now = env.ledger().timestamp();
         if now > verdict.recorded_at + cooling_off_seconds {
-            panic!("Cooling-off window has expired");
+            panic_with_error!(&env, DisputeError::CoolingOffExpired);
         }
 
         // Prevent duplicate flags
         if env.storage().persistent().has(&DataKey2::ResolutionFlag(escrow_id)) {
-            panic!("Resolution already flagged");
+            panic_with_error!(&env, DisputeError::ResolutionAlreadyFlagged);
         }
 
         env.storage()
@@ -2399,17 +2601,17 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if escrow.status != EscrowStatus::CoolingOff {
-            panic!("Escrow is not in cooling-off state");
+            panic_with_error!(&env, DisputeError::NotInCoolingOff);
         }
 
         let verdict: PendingVerdict = env
             .storage()
             .persistent()
             .get(&DataKey2::PendingVerdict(escrow_id))
-            .expect("No pending verdict");
+            .unwrap_or_else(|| panic_with_error!(&env, DisputeError::NoPendingVerdict));
 
         let cooling_off_seconds: u64 = env
             .storage()
@@ -2419,21 +2621,66 @@ impl AhjoorEscrowContract {
 
         let now = env.ledger().timestamp();
         if now <= verdict.recorded_at + cooling_off_seconds {
-            panic!("Cooling-off window has not elapsed");
+            panic_with_error!(&env, DisputeError::CoolingOffActive);
         }
 
         // Ensure no unresolved flag is blocking release
         if env.storage().persistent().has(&DataKey2::ResolutionFlag(escrow_id)) {
-            panic!("Resolution is flagged; admin must review before finalization");
+            panic_with_error!(&env, DisputeError::ResolutionFlagged);
         }
 
-        let buyer_percent = verdict.buyer_percent;
+        let buyer_bps = verdict.buyer_bps;
         let arbiter = verdict.arbiter.clone();
 
         // Clean up verdict
         env.storage().persistent().remove(&DataKey2::PendingVerdict(escrow_id));
 
-        Self::execute_verdict(&env, escrow_id, escrow, buyer_percent, arbiter);
+        let panel: Option<ArbitrationPanel> = env
+            .storage()
+            .persistent()
+            .get(&DataKey2::ArbitrationPanel(escrow_id));
+        let overturned_for: Option<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey2::OverturnedRuling(escrow_id));
+
+        if let Some(affected) = overturned_for {
+            env.storage()
+                .persistent()
+                .remove(&DataKey2::OverturnedRuling(escrow_id));
+            // Whoever made the overturned ruling is slashed and forfeits the arbiter fee;
+            // panelists whose vote was close to the final split are spared
+            let mut rulers: Vec<Address> = Vec::new(&env);
+            match panel {
+                Some(panel) if panel.decided_bps.is_some() => {
+                    for (voter, vote_bps) in panel.votes.iter() {
+                        if vote_bps.abs_diff(buyer_bps) > OVERTURN_VOTE_TOLERANCE_BPS {
+                            rulers.push_back(voter);
+                        }
+                    }
+                }
+                _ => rulers.push_back(arbiter.clone()),
+            }
+            for ruler in rulers.iter() {
+                Self::slash_arbiter(&env, &ruler, escrow_id, &affected);
+            }
+            Self::settle_verdict(
+                &env,
+                escrow_id,
+                escrow,
+                buyer_bps,
+                arbiter,
+                Vec::new(&env),
+                1,
+            );
+        } else {
+            match panel {
+                Some(panel) if panel.decided_bps.is_some() => {
+                    Self::settle_panel_verdict(&env, escrow_id, escrow, &panel)
+                }
+                _ => Self::execute_verdict(&env, escrow_id, escrow, buyer_bps, arbiter),
+            }
+        }
 
         env.storage()
             .instance()
@@ -2448,13 +2695,13 @@ impl AhjoorEscrowContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         if admin != stored_admin {
-            panic!("Only admin can clear resolution flags");
+            panic_with_error!(&env, Error::Unauthorized);
         }
 
         if !env.storage().persistent().has(&DataKey2::ResolutionFlag(escrow_id)) {
-            panic!("No flag to clear");
+            panic_with_error!(&env, DisputeError::NoResolutionFlag);
         }
 
         env.storage().persistent().remove(&DataKey2::ResolutionFlag(escrow_id));
@@ -2464,6 +2711,61 @@ impl AhjoorEscrowContract {
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
     }
 
+    /// Admin upholds a resolution flag and overturns the pending ruling with a corrected
+    /// `buyer_percent`. When the ruling is finalized the original arbiter(s) receive no fee
+    /// and are slashed, with the slashed stake paid to the party that raised the flag.
+    pub fn overturn_resolution(env: Env, admin: Address, escrow_id: u32, buyer_percent: u32) {
+        Self::require_not_paused(&env);
+        Self::require_admin(&env, &admin);
+
+        if buyer_percent > 100 {
+            panic_with_error!(&env, DisputeError::InvalidSplit);
+        }
+
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(escrow_id))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
+        if escrow.status != EscrowStatus::CoolingOff {
+            panic_with_error!(&env, DisputeError::NotInCoolingOff);
+        }
+
+        let (flagger, _reason_hash): (Address, BytesN<32>) = env
+            .storage()
+            .persistent()
+            .get(&DataKey2::ResolutionFlag(escrow_id))
+            .unwrap_or_else(|| panic_with_error!(&env, DisputeError::NoResolutionFlag));
+        let mut verdict: PendingVerdict = env
+            .storage()
+            .persistent()
+            .get(&DataKey2::PendingVerdict(escrow_id))
+            .unwrap_or_else(|| panic_with_error!(&env, DisputeError::NoPendingVerdict));
+
+        let original_percent = verdict.buyer_bps / 100;
+        verdict.buyer_bps = buyer_percent * 100;
+        env.storage()
+            .persistent()
+            .set(&DataKey2::PendingVerdict(escrow_id), &verdict);
+        env.storage()
+            .persistent()
+            .remove(&DataKey2::ResolutionFlag(escrow_id));
+        env.storage()
+            .persistent()
+            .set(&DataKey2::OverturnedRuling(escrow_id), &flagger);
+        env.storage().persistent().extend_ttl(
+            &DataKey2::OverturnedRuling(escrow_id),
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        events::emit_resolution_overturned(&env, escrow_id, original_percent, buyer_percent, flagger);
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
     /// Admin sets the global cooling-off window in seconds (0 = disabled).
     pub fn set_resolution_cooloff_secs(env: Env, admin: Address, seconds: u64) {
         Self::require_not_paused(&env);
@@ -2472,9 +2774,9 @@ impl AhjoorEscrowContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         if admin != stored_admin {
-            panic!("Only admin can configure cooling-off period");
+            panic_with_error!(&env, Error::Unauthorized);
         }
 
         env.storage()
@@ -2485,114 +2787,444 @@ impl AhjoorEscrowContract {
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
     }
 
-    /// Internal: execute a verdict (transfer funds per buyer_percent split).
-    fn execute_verdict(env: &Env, escrow_id: u32, mut escrow: Escrow, buyer_percent: u32, arbiter: Address) {
-        let client = token::Client::new(env, &escrow.token);
+    // ── Panel Arbitration ────────────────────────────────────────────────────
 
-        let arbiter_fee_bps = Self::effective_arbiter_fee_bps(env, &escrow);
-        let arbiter_fee = (escrow.amount * arbiter_fee_bps as i128) / 10_000;
+    /// Admin configures panel arbitration: the voting window in seconds and the minimum
+    /// escrow amount for which a party may request a panel (0 = any amount).
+    pub fn set_panel_config(env: Env, admin: Address, voting_window_secs: u64, min_escrow_amount: i128) {
+        Self::require_admin(&env, &admin);
+        if voting_window_secs == 0 {
+            panic_with_error!(&env, Error::InvalidDuration);
+        }
+        if min_escrow_amount < 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey2::PanelVotingWindow, &voting_window_secs);
+        env.storage()
+            .instance()
+            .set(&DataKey2::PanelMinEscrowAmount, &min_escrow_amount);
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Buyer or seller of a disputed escrow opts into panel arbitration instead of the
+    /// single assigned arbiter. `size` (3, 5 or 7) arbiters are drawn at random from the
+    /// arbiter pool, skipping the escrow's own parties and any arbiter below the minimum stake.
+    pub fn request_arbitration_panel(env: Env, caller: Address, escrow_id: u32, size: u32) -> Vec<Address> {
+        Self::require_not_paused(&env);
+        caller.require_auth();
 
-        if arbiter_fee > 0 {
-            client.transfer(&env.current_contract_address(), &arbiter, &arbiter_fee);
-            events::emit_arbiter_fee_paid(env, escrow_id, arbiter.clone(), arbiter_fee);
+        if size != 3 && size != 5 && size != 7 {
+            panic_with_error!(&env, DisputeError::InvalidPanelSize);
         }
 
-        let fee_bps: u32 = env
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(escrow_id))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
+
+        if escrow.status != EscrowStatus::Disputed
+            && escrow.status != EscrowStatus::PartiallyDisputed
+        {
+            panic_with_error!(&env, DisputeError::EscrowNotDisputed);
+        }
+        if caller != escrow.buyer && caller != escrow.seller {
+            panic_with_error!(&env, Error::NotEscrowParty);
+        }
+        if env.storage().persistent().has(&DataKey2::ArbitrationPanel(escrow_id)) {
+            panic_with_error!(&env, DisputeError::PanelAlreadyRequested);
+        }
+        let min_amount: i128 = env
             .storage()
             .instance()
-            .get(&DataKey::ProtocolFeeBps)
+            .get(&DataKey2::PanelMinEscrowAmount)
             .unwrap_or(0);
-        let protocol_fee = (escrow.amount * fee_bps as i128) / 10_000;
+        if escrow.amount < min_amount {
+            panic_with_error!(&env, DisputeError::BelowPanelThreshold);
+        }
 
-        if protocol_fee > 0 {
-            let fee_recipient: Address = env
-                .storage()
-                .instance()
-                .get(&DataKey::FeeRecipient)
-                .expect("FeeRecipient not set");
-            client.transfer(
-                &env.current_contract_address(),
-                &fee_recipient,
-                &protocol_fee,
-            );
-            events::emit_protocol_fee_paid(env, escrow_id, protocol_fee, fee_recipient);
+        let pool: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::ArbiterPool)
+            .unwrap_or(Vec::new(&env));
+        let min_stake = env
+            .storage()
+            .instance()
+            .get::<DataKey2, ArbiterStakeConfig>(&DataKey2::ArbiterStakeConfig)
+            .map(|config| config.min_stake)
+            .unwrap_or(0);
+        let mut candidates: Vec<Address> = Vec::new(&env);
+        for candidate in pool.iter() {
+            if candidate != escrow.buyer
+                && candidate != escrow.seller
+                && Self::active_arbiter_stake(&env, &candidate) >= min_stake
+            {
+                candidates.push_back(candidate);
+            }
+        }
+        if candidates.len() < size {
+            panic_with_error!(&env, DisputeError::NotEnoughArbiters);
         }
+        // Drawn from the host PRNG so parties cannot predict or steer the panel
+        // the way they could a round-robin position.
+        env.prng().shuffle(&mut candidates);
+        let members = candidates.slice(0..size);
 
-        let distributable = escrow.amount - protocol_fee - arbiter_fee;
+        let window: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey2::PanelVotingWindow)
+            .unwrap_or(DEFAULT_PANEL_VOTING_WINDOW_SECONDS);
+        let now = env.ledger().timestamp();
+        let panel = ArbitrationPanel {
+            members: members.clone(),
+            votes: Vec::new(&env),
+            opened_at: now,
+            voting_deadline: now + window,
+            decided_bps: None,
+        };
+        Self::save_panel(&env, escrow_id, &panel);
 
-        if distributable < 0 {
-            panic!("Fee configuration exceeds escrow amount");
-        }
+        events::emit_arbitration_panel_opened(&env, escrow_id, members.clone(), panel.voting_deadline);
 
-        let seller_percent = 100 - buyer_percent;
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 
-        let buyer_amount = (distributable * buyer_percent as i128) / 100;
-        let seller_amount = distributable - buyer_amount;
+        members
+    }
 
-        if buyer_amount > 0 {
-            client.transfer(
-                &env.current_contract_address(),
-                &escrow.buyer,
-                &buyer_amount,
-            );
-        }
-        if seller_amount > 0 {
-            client.transfer(
-                &env.current_contract_address(),
-                &escrow.seller,
-                &seller_amount,
-            );
+    /// A panel member votes the buyer's share in basis points (0–10_000). Once a majority
+    /// of the panel has voted, the median vote becomes the verdict.
+    pub fn submit_panel_vote(env: Env, arbiter: Address, escrow_id: u32, buyer_bps: u32) {
+        Self::require_not_paused(&env);
+        arbiter.require_auth();
+
+        if buyer_bps > 10_000 {
+            panic_with_error!(&env, DisputeError::InvalidSplit);
         }
 
-        // #237: Handle seller collateral
-        let collateral: i128 = env
-            .storage()
-            .persistent()
-            .get(&DataKey::SellerCollateral(escrow_id))
-            .unwrap_or(0);
-        if collateral > 0 {
-            if buyer_percent > 0 {
-                // Buyer-favour: forfeit collateral_forfeit_bps to buyer, return remainder to seller
-                let forfeit = (collateral * escrow.extensions.collateral_forfeit_bps as i128) / 10_000;
-                let returned = collateral - forfeit;
-                if forfeit > 0 {
-                    client.transfer(&env.current_contract_address(), &escrow.buyer, &forfeit);
-                    events::emit_collateral_forfeited(env, escrow_id, forfeit, escrow.buyer.clone());
-                }
-                if returned > 0 {
-                    client.transfer(&env.current_contract_address(), &escrow.seller, &returned);
-                    events::emit_collateral_returned(env, escrow_id, escrow.seller.clone(), returned);
-                }
-            } else {
-                // Seller-favour: return full collateral
-                client.transfer(&env.current_contract_address(), &escrow.seller, &collateral);
-                events::emit_collateral_returned(env, escrow_id, escrow.seller.clone(), collateral);
+        let mut panel = Self::open_panel(&env, escrow_id);
+        if env.ledger().timestamp() > panel.voting_deadline {
+            panic_with_error!(&env, DisputeError::VotingClosed);
+        }
+        if !panel.members.contains(&arbiter) {
+            panic_with_error!(&env, DisputeError::NotPanelMember);
+        }
+        for (voter, _) in panel.votes.iter() {
+            if voter == arbiter {
+                panic_with_error!(&env, DisputeError::AlreadyVoted);
             }
-            env.storage().persistent().remove(&DataKey::SellerCollateral(escrow_id));
         }
 
-        escrow.status = if buyer_percent == 100 {
-            EscrowStatus::Refunded
-        } else if buyer_percent == 0 {
-            EscrowStatus::Released
+        panel.votes.push_back((arbiter.clone(), buyer_bps));
+        events::emit_panel_vote_submitted(&env, escrow_id, arbiter, buyer_bps);
+
+        if panel.votes.len() * 2 > panel.members.len() {
+            Self::decide_panel(&env, escrow_id, panel);
         } else {
-            EscrowStatus::Resolved
-        };
+            Self::save_panel(&env, escrow_id, &panel);
+        }
 
         env.storage()
-            .persistent()
-            .set(&DataKey::Escrow(escrow_id), &escrow);
-        env.storage().persistent().extend_ttl(
-            &DataKey::Escrow(escrow_id),
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
 
-        if let Some(mut dispute) = env
-            .storage()
-            .persistent()
-            .get::<DataKey, Dispute>(&DataKey::Dispute(escrow_id))
-        {
+    /// Close a panel whose voting window has elapsed without a majority. Anyone can call.
+    /// The median of the votes cast becomes the verdict; if nobody voted the panel lapses
+    /// and the dispute falls back to the single arbiter with a fresh dispute timeout.
+    pub fn close_arbitration_panel(env: Env, escrow_id: u32) {
+        Self::require_not_paused(&env);
+
+        let panel = Self::open_panel(&env, escrow_id);
+        if env.ledger().timestamp() <= panel.voting_deadline {
+            panic_with_error!(&env, DisputeError::VotingOpen);
+        }
+        Self::close_panel(&env, escrow_id, panel);
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    pub fn get_arbitration_panel(env: Env, escrow_id: u32) -> Option<ArbitrationPanel> {
+        env.storage()
+            .persistent()
+            .get(&DataKey2::ArbitrationPanel(escrow_id))
+    }
+
+    /// Internal: load a panel that is still collecting votes.
+    fn open_panel(env: &Env, escrow_id: u32) -> ArbitrationPanel {
+        let panel: ArbitrationPanel = env
+            .storage()
+            .persistent()
+            .get(&DataKey2::ArbitrationPanel(escrow_id))
+            .unwrap_or_else(|| panic_with_error!(env, DisputeError::PanelNotFound));
+        if panel.decided_bps.is_some() {
+            panic_with_error!(env, DisputeError::PanelAlreadyDecided);
+        }
+        panel
+    }
+
+    /// Internal: close a panel past its voting window. Returns true if the votes cast
+    /// decided it, false if nobody voted and it lapsed. A lapsed panel hands the dispute
+    /// back to the single arbiter, who was locked out meanwhile, so the dispute timeout
+    /// restarts from now.
+    fn close_panel(env: &Env, escrow_id: u32, panel: ArbitrationPanel) -> bool {
+        if panel.votes.is_empty() {
+            env.storage()
+                .persistent()
+                .remove(&DataKey2::ArbitrationPanel(escrow_id));
+            let now = env.ledger().timestamp();
+            env.storage()
+                .persistent()
+                .set(&DataKey::DisputeDeadlineStart(escrow_id), &now);
+            env.storage().persistent().extend_ttl(
+                &DataKey::DisputeDeadlineStart(escrow_id),
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+            events::emit_arbitration_panel_lapsed(env, escrow_id);
+            false
+        } else {
+            Self::decide_panel(env, escrow_id, panel);
+            true
+        }
+    }
+
+    fn save_panel(env: &Env, escrow_id: u32, panel: &ArbitrationPanel) {
+        env.storage()
+            .persistent()
+            .set(&DataKey2::ArbitrationPanel(escrow_id), panel);
+        env.storage().persistent().extend_ttl(
+            &DataKey2::ArbitrationPanel(escrow_id),
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    /// Internal: record the median vote as the panel verdict, then either enter cooling-off
+    /// (via the usual `PendingVerdict`) or settle immediately.
+    fn decide_panel(env: &Env, escrow_id: u32, mut panel: ArbitrationPanel) {
+        let mut sorted: Vec<u32> = Vec::new(env);
+        for (_, bps) in panel.votes.iter() {
+            let mut i = 0;
+            while i < sorted.len() && sorted.get(i).unwrap() < bps {
+                i += 1;
+            }
+            sorted.insert(i, bps);
+        }
+        // Even vote counts take the mean of the two middle votes
+        let mid = sorted.len() / 2;
+        let median = if sorted.len() % 2 == 1 {
+            sorted.get(mid).unwrap()
+        } else {
+            (sorted.get(mid - 1).unwrap() + sorted.get(mid).unwrap()) / 2
+        };
+        panel.decided_bps = Some(median);
+        Self::save_panel(env, escrow_id, &panel);
+
+        events::emit_arbitration_panel_decided(env, escrow_id, median, panel.votes.len());
+
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(escrow_id))
+            .unwrap_or_else(|| panic_with_error!(env, Error::EscrowNotFound));
+
+        let cooling_off_seconds: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey2::ResolutionCoolingOffSeconds)
+            .unwrap_or(0);
+
+        if cooling_off_seconds > 0 {
+            let verdict = PendingVerdict {
+                buyer_bps: median,
+                arbiter: escrow.arbiter.clone(),
+                recorded_at: env.ledger().timestamp(),
+            };
+            env.storage()
+                .persistent()
+                .set(&DataKey2::PendingVerdict(escrow_id), &verdict);
+            env.storage().persistent().extend_ttl(
+                &DataKey2::PendingVerdict(escrow_id),
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+
+            escrow.status = EscrowStatus::CoolingOff;
+            env.storage()
+                .persistent()
+                .set(&DataKey::Escrow(escrow_id), &escrow);
+            env.storage().persistent().extend_ttl(
+                &DataKey::Escrow(escrow_id),
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+
+            events::emit_resolution_cooling_off(env, escrow_id, verdict.buyer_bps / 100, verdict.arbiter, env.ledger().timestamp() + cooling_off_seconds);
+            return;
+        }
+
+        Self::settle_panel_verdict(env, escrow_id, escrow, &panel);
+    }
+
+    /// Internal: settle a decided panel. Only arbiters who voted receive their share of
+    /// the arbiter fee; non-voters' shares go back into the split.
+    fn settle_panel_verdict(env: &Env, escrow_id: u32, escrow: Escrow, panel: &ArbitrationPanel) {
+        let mut voters: Vec<Address> = Vec::new(env);
+        for (voter, _) in panel.votes.iter() {
+            voters.push_back(voter);
+        }
+        let arbiter = escrow.arbiter.clone();
+        Self::settle_verdict(
+            env,
+            escrow_id,
+            escrow,
+            panel.decided_bps.unwrap(),
+            arbiter,
+            voters,
+            panel.members.len(),
+        );
+    }
+
+    /// Internal: execute a single arbiter's verdict (transfer funds per `buyer_bps` split).
+    fn execute_verdict(env: &Env, escrow_id: u32, escrow: Escrow, buyer_bps: u32, arbiter: Address) {
+        let fee_payees = Vec::from_array(env, [arbiter.clone()]);
+        Self::settle_verdict(env, escrow_id, escrow, buyer_bps, arbiter, fee_payees, 1);
+    }
+
+    /// Internal: settle a verdict at `buyer_bps` (0–10_000). The arbiter fee is split into
+    /// `fee_shares` equal shares and one share is paid to each of `fee_payees`; unpaid shares
+    /// stay in the escrow and are distributed with the rest of the funds.
+    fn settle_verdict(
+        env: &Env,
+        escrow_id: u32,
+        mut escrow: Escrow,
+        buyer_bps: u32,
+        arbiter: Address,
+        fee_payees: Vec<Address>,
+        fee_shares: u32,
+    ) {
+        let client = token::Client::new(env, &escrow.token);
+
+        let arbiter_fee_bps = Self::effective_arbiter_fee_bps(env, &escrow);
+        let fee_share = (escrow.amount * arbiter_fee_bps as i128) / 10_000 / fee_shares as i128;
+        let mut arbiter_fee: i128 = 0;
+
+        if fee_share > 0 {
+            for payee in fee_payees.iter() {
+                client.transfer(&env.current_contract_address(), &payee, &fee_share);
+                Self::record_arbiter_fee(env, &payee, &escrow.token, fee_share);
+                events::emit_arbiter_fee_paid(env, escrow_id, payee, fee_share);
+                arbiter_fee += fee_share;
+            }
+        }
+
+        let fee_bps: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ProtocolFeeBps)
+            .unwrap_or(0);
+        let protocol_fee = (escrow.amount * fee_bps as i128) / 10_000;
+
+        if protocol_fee > 0 {
+            let fee_recipient: Address = env
+                .storage()
+                .instance()
+                .get(&DataKey::FeeRecipient)
+                .unwrap_or_else(|| panic_with_error!(env, Error::FeeRecipientNotSet));
+            client.transfer(
+                &env.current_contract_address(),
+                &fee_recipient,
+                &protocol_fee,
+            );
+            events::emit_protocol_fee_paid(env, escrow_id, protocol_fee, fee_recipient);
+        }
+
+        let distributable = escrow.amount - protocol_fee - arbiter_fee;
+
+        if distributable < 0 {
+            panic_with_error!(env, Error::FeeTooHigh);
+        }
+
+        let buyer_percent = buyer_bps / 100;
+        let seller_percent = 100 - buyer_percent;
+
+        let buyer_amount = (distributable * buyer_bps as i128) / 10_000;
+        let seller_amount = distributable - buyer_amount;
+
+        if buyer_amount > 0 {
+            client.transfer(
+                &env.current_contract_address(),
+                &escrow.buyer,
+                &buyer_amount,
+            );
+        }
+        if seller_amount > 0 {
+            client.transfer(
+                &env.current_contract_address(),
+                &escrow.seller,
+                &seller_amount,
+            );
+        }
+
+        // #237: Handle seller collateral
+        let collateral: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SellerCollateral(escrow_id))
+            .unwrap_or(0);
+        if collateral > 0 {
+            if buyer_bps > 0 {
+                // Buyer-favour: forfeit collateral_forfeit_bps to buyer, return remainder to seller
+                let forfeit = (collateral * escrow.extensions.collateral_forfeit_bps as i128) / 10_000;
+                let returned = collateral - forfeit;
+                if forfeit > 0 {
+                    client.transfer(&env.current_contract_address(), &escrow.buyer, &forfeit);
+                    events::emit_collateral_forfeited(env, escrow_id, forfeit, escrow.buyer.clone());
+                }
+                if returned > 0 {
+                    client.transfer(&env.current_contract_address(), &escrow.seller, &returned);
+                    events::emit_collateral_returned(env, escrow_id, escrow.seller.clone(), returned);
+                }
+            } else {
+                // Seller-favour: return full collateral
+                client.transfer(&env.current_contract_address(), &escrow.seller, &collateral);
+                events::emit_collateral_returned(env, escrow_id, escrow.seller.clone(), collateral);
+            }
+            env.storage().persistent().remove(&DataKey::SellerCollateral(escrow_id));
+        }
+
+        escrow.status = if buyer_bps == 10_000 {
+            EscrowStatus::Refunded
+        } else if buyer_bps == 0 {
+            EscrowStatus::Released
+        } else {
+            EscrowStatus::Resolved
+        };
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(escrow_id), &escrow);
+        env.storage().persistent().extend_ttl(
+            &DataKey::Escrow(escrow_id),
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        if let Some(mut dispute) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, Dispute>(&DataKey::Dispute(escrow_id))
+        {
             dispute.resolved = true;
             env.storage()
                 .persistent()
@@ -2608,7 +3240,7 @@ impl AhjoorEscrowContract {
             seller_amount,
             arbiter.clone(),
         );
-        let release_to_seller = buyer_percent == 0;
+        let release_to_seller = buyer_bps == 0;
         events::emit_dispute_resolved(env, escrow_id, release_to_seller, arbiter.clone());
         events::emit_resolution_finalized(env, escrow_id, buyer_percent, arbiter);
 
@@ -2625,7 +3257,7 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if escrow.status != EscrowStatus::Disputed && escrow.status != EscrowStatus::PartiallyDisputed {
             return false;
@@ -2635,7 +3267,7 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Dispute(escrow_id))
-            .expect("Dispute not found");
+            .unwrap_or_else(|| panic_with_error!(&env, DisputeError::DisputeNotFound));
 
         if dispute.resolved {
             return false;
@@ -2661,7 +3293,7 @@ impl AhjoorEscrowContract {
     pub fn update_default_dispute_timeout(env: Env, admin: Address, timeout_seconds: u64) {
         Self::require_admin(&env, &admin);
         if timeout_seconds == 0 {
-            panic!("Timeout must be positive");
+            panic_with_error!(&env, Error::InvalidDuration);
         }
 
         env.storage()
@@ -2701,7 +3333,7 @@ impl AhjoorEscrowContract {
     pub fn update_max_topup_multiplier(env: Env, admin: Address, multiplier: u32) {
         Self::require_admin(&env, &admin);
         if multiplier == 0 {
-            panic!("Multiplier must be positive");
+            panic_with_error!(&env, Error::InvalidBps);
         }
 
         env.storage()
@@ -2723,7 +3355,7 @@ impl AhjoorEscrowContract {
     pub fn set_partial_release_deadline(env: Env, admin: Address, deadline: u64) {
         Self::require_admin(&env, &admin);
         if deadline == 0 {
-            panic!("Deadline must be positive");
+            panic_with_error!(&env, Error::InvalidDuration);
         }
 
         env.storage()
@@ -2742,15 +3374,15 @@ impl AhjoorEscrowContract {
     }
 
     /// Enforce dispute timeout: release funds to default winner if arbiter fails to resolve within deadline.
-    /// Can be called by anyone after the deadline has passed.
-    pub fn enforce_dispute_timeout(env: Env, escrow_id: u32) {
+    /// Can be called by anyone after the deadline has passed; `keeper` receives the keeper bounty.
+    pub fn enforce_dispute_timeout(env: Env, keeper: Address, escrow_id: u32) {
         Self::require_not_paused(&env);
 
         let mut escrow: Escrow = env
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         // Check if there's a dispute record first
         let dispute: Option<Dispute> = env
@@ -2760,17 +3392,35 @@ impl AhjoorEscrowContract {
 
         let dispute = match dispute {
             Some(d) => d,
-            None => panic!("Escrow is not disputed"),
+            None => panic_with_error!(&env, DisputeError::EscrowNotDisputed),
         };
 
         if dispute.resolved {
-            panic!("Dispute already resolved");
+            panic_with_error!(&env, DisputeError::DisputeAlreadyResolved);
         }
 
         if escrow.status != EscrowStatus::Disputed
             && escrow.status != EscrowStatus::PartiallyDisputed
         {
-            panic!("Escrow is not disputed");
+            panic_with_error!(&env, DisputeError::EscrowNotDisputed);
+        }
+
+        // A panel still inside its voting window keeps the dispute. A stalled one is
+        // closed here: its votes decide the dispute, or it lapses and the arbiter gets a
+        // fresh timeout window before anyone can be slashed for it.
+        if let Some(panel) = env
+            .storage()
+            .persistent()
+            .get::<DataKey2, ArbitrationPanel>(&DataKey2::ArbitrationPanel(escrow_id))
+        {
+            if env.ledger().timestamp() <= panel.voting_deadline {
+                panic_with_error!(&env, DisputeError::UnderPanelArbitration);
+            }
+            Self::close_panel(&env, escrow_id, panel);
+            env.storage()
+                .instance()
+                .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+            return;
         }
 
         // Get dispute deadline start timestamp
@@ -2778,7 +3428,7 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::DisputeDeadlineStart(escrow_id))
-            .expect("Dispute deadline start not found");
+            .unwrap_or_else(|| panic_with_error!(&env, DisputeError::DisputeNotFound));
 
         // Determine effective timeout
         let default_timeout: u64 = env
@@ -2792,7 +3442,7 @@ impl AhjoorEscrowContract {
         let now = env.ledger().timestamp();
         let elapsed = now.saturating_sub(deadline_start);
         if elapsed < effective_timeout {
-            panic!("Dispute timeout deadline has not passed yet");
+            panic_with_error!(&env, DisputeError::DisputeTimeoutNotReached);
         }
 
         // Determine default winner
@@ -2853,6 +3503,14 @@ impl AhjoorEscrowContract {
         // Burn any receipt in either case
         Self::burn_receipt_if_exists(&env, escrow_id);
 
+        // The party that lost by default never got a ruling; they receive the slashed stake
+        let affected = if release_to_seller {
+            escrow.buyer.clone()
+        } else {
+            escrow.seller.clone()
+        };
+        Self::slash_arbiter(&env, &escrow.arbiter, escrow_id, &affected);
+
         env.storage()
             .persistent()
             .set(&DataKey::Escrow(escrow_id), &escrow);
@@ -2881,6 +3539,7 @@ impl AhjoorEscrowContract {
             escrow.arbiter,
             arbiter_timeout_count + 1,
         );
+        Self::pay_keeper_reward(&env, &keeper, 1);
 
         env.storage()
             .instance()
@@ -2900,7 +3559,7 @@ impl AhjoorEscrowContract {
     pub fn set_default_arbiter_fee_bps(env: Env, admin: Address, fee_bps: u32) {
         Self::require_admin(&env, &admin);
         if fee_bps > MAX_ARBITER_FEE_BPS {
-            panic!("Arbiter fee exceeds maximum of 1000 bps");
+            panic_with_error!(&env, Error::FeeTooHigh);
         }
 
         env.storage()
@@ -2922,7 +3581,7 @@ impl AhjoorEscrowContract {
     pub fn set_oracle(env: Env, admin: Address, oracle: Address, max_oracle_age: u64) {
         Self::require_admin(&env, &admin);
         if max_oracle_age == 0 {
-            panic!("max_oracle_age must be positive");
+            panic_with_error!(&env, Error::InvalidDuration);
         }
 
         env.storage().instance().set(&DataKey::OracleAddress, &oracle);
@@ -2938,7 +3597,7 @@ impl AhjoorEscrowContract {
         env.storage()
             .instance()
             .get(&DataKey::OracleAddress)
-            .expect("Oracle not configured")
+            .unwrap_or_else(|| panic_with_error!(&env, Error::OracleNotConfigured))
     }
 
     pub fn get_max_oracle_age(env: Env) -> u64 {
@@ -2948,11 +3607,77 @@ impl AhjoorEscrowContract {
             .unwrap_or(DEFAULT_MAX_ORACLE_AGE_SECONDS)
     }
 
+    /// Configure the oracle set for a base/quote pair. Admin only.
+    ///
+    /// Once set, prices for the pair are the median of the sources reporting a
+    /// fresh price, and are rejected if fewer than `min_sources` are fresh or
+    /// any of them is more than `max_deviation_bps` from the median. Pairs
+    /// without a set keep using the single oracle from `set_oracle`.
+    pub fn set_oracle_set(
+        env: Env,
+        admin: Address,
+        base: Address,
+        quote: Address,
+        oracles: Vec<Address>,
+        min_sources: u32,
+        max_deviation_bps: u32,
+    ) {
+        Self::require_admin(&env, &admin);
+        if oracles.is_empty()
+            || oracles.len() > MAX_ORACLE_SOURCES
+            || min_sources == 0
+            || min_sources > oracles.len()
+            || max_deviation_bps == 0
+            || max_deviation_bps > 10_000
+        {
+            panic_with_error!(&env, WorkflowError::InvalidOracleSet);
+        }
+        for (i, oracle) in oracles.iter().enumerate() {
+            if oracles.first_index_of(&oracle) != Some(i as u32) {
+                panic_with_error!(&env, WorkflowError::InvalidOracleSet);
+            }
+        }
+
+        let set = OracleSet {
+            oracles: oracles.clone(),
+            min_sources,
+            max_deviation_bps,
+        };
+        env.storage()
+            .instance()
+            .set(&DataKey2::OracleSet(base.clone(), quote.clone()), &set);
+        events::emit_oracle_set_configured(
+            &env,
+            base,
+            quote,
+            oracles,
+            min_sources,
+            max_deviation_bps,
+        );
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Remove the oracle set for a pair, returning it to the single oracle. Admin only.
+    pub fn remove_oracle_set(env: Env, admin: Address, base: Address, quote: Address) {
+        Self::require_admin(&env, &admin);
+        env.storage()
+            .instance()
+            .remove(&DataKey2::OracleSet(base, quote));
+    }
+
+    pub fn get_oracle_set(env: Env, base: Address, quote: Address) -> Option<OracleSet> {
+        env.storage()
+            .instance()
+            .get(&DataKey2::OracleSet(base, quote))
+    }
+
     /// Configure insurance token and trigger window in days. Admin only.
     pub fn set_insurance_config(env: Env, admin: Address, token: Address, trigger_days: u64) {
         Self::require_admin(&env, &admin);
         if trigger_days == 0 {
-            panic!("insurance_trigger_days must be positive");
+            panic_with_error!(&env, Error::InvalidDuration);
         }
 
         // Token whitelist validation
@@ -2980,14 +3705,14 @@ impl AhjoorEscrowContract {
         contributor.require_auth();
 
         if amount <= 0 {
-            panic!("Insurance contribution must be positive");
+            panic_with_error!(&env, Error::InvalidAmount);
         }
 
         let token: Address = env
             .storage()
             .instance()
             .get(&DataKey::InsuranceToken)
-            .expect("Insurance token not configured");
+            .unwrap_or_else(|| panic_with_error!(&env, DisputeError::InsuranceNotConfigured));
 
         let client = token::Client::new(&env, &token);
         client.transfer(&contributor, &env.current_contract_address(), &amount);
@@ -3033,25 +3758,25 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if claimant != escrow.buyer && claimant != escrow.seller {
-            panic!("Only buyer or seller can claim insurance");
+            panic_with_error!(&env, Error::NotEscrowParty);
         }
 
         if escrow.status != EscrowStatus::Disputed
             && escrow.status != EscrowStatus::PartiallyDisputed
         {
-            panic!("Escrow is not disputed");
+            panic_with_error!(&env, DisputeError::EscrowNotDisputed);
         }
 
         let dispute: Dispute = env
             .storage()
             .persistent()
             .get(&DataKey::Dispute(escrow_id))
-            .expect("Dispute not found");
+            .unwrap_or_else(|| panic_with_error!(&env, DisputeError::DisputeNotFound));
         if dispute.resolved {
-            panic!("Dispute already resolved");
+            panic_with_error!(&env, DisputeError::DisputeAlreadyResolved);
         }
 
         if env
@@ -3060,7 +3785,7 @@ impl AhjoorEscrowContract {
             .get::<DataKey, bool>(&DataKey::InsuranceClaimed(escrow_id))
             .unwrap_or(false)
         {
-            panic!("Insurance already claimed");
+            panic_with_error!(&env, DisputeError::InsuranceAlreadyClaimed);
         }
 
         let confirmed: bool = env
@@ -3069,7 +3794,7 @@ impl AhjoorEscrowContract {
             .get(&DataKey::InsuranceAdminConfirmed(escrow_id))
             .unwrap_or(false);
         if !confirmed {
-            panic!("Admin confirmation required");
+            panic_with_error!(&env, DisputeError::AdminConfirmationRequired);
         }
 
         let trigger_days: u64 = env
@@ -3080,16 +3805,16 @@ impl AhjoorEscrowContract {
         let trigger_seconds = trigger_days.saturating_mul(24 * 60 * 60);
         let elapsed = env.ledger().timestamp().saturating_sub(dispute.created_at);
         if elapsed < trigger_seconds {
-            panic!("Insurance trigger period not reached");
+            panic_with_error!(&env, DisputeError::InsuranceTriggerNotReached);
         }
 
         let insurance_token: Address = env
             .storage()
             .instance()
             .get(&DataKey::InsuranceToken)
-            .expect("Insurance token not configured");
+            .unwrap_or_else(|| panic_with_error!(&env, DisputeError::InsuranceNotConfigured));
         if insurance_token != escrow.token {
-            panic!("Escrow token not covered by insurance pool");
+            panic_with_error!(&env, DisputeError::InsuranceTokenMismatch);
         }
 
         let max_claim = escrow.amount / 2;
@@ -3105,7 +3830,7 @@ impl AhjoorEscrowContract {
         };
 
         if claim_amount <= 0 {
-            panic!("Insurance pool has insufficient balance");
+            panic_with_error!(&env, DisputeError::InsufficientInsurancePool);
         }
 
         let token_client = token::Client::new(&env, &insurance_token);
@@ -3134,7 +3859,7 @@ impl AhjoorEscrowContract {
     pub fn update_protocol_fee(env: Env, admin: Address, fee_bps: u32, fee_recipient: Address) {
         Self::require_admin(&env, &admin);
         if fee_bps > MAX_PROTOCOL_FEE_BPS {
-            panic!("Fee exceeds maximum of 200 bps");
+            panic_with_error!(&env, Error::FeeTooHigh);
         }
         env.storage()
             .instance()
@@ -3159,6 +3884,8 @@ impl AhjoorEscrowContract {
     }
 
     /// Anyone may trigger release when the escrow's oracle condition is met.
+    /// In TWAP mode the condition is checked against the time-weighted mean of the
+    /// recorded readings rather than the current price.
     pub fn check_and_release_escrow(env: Env, escrow_id: u32) {
         Self::require_not_paused(&env);
 
@@ -3166,40 +3893,44 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if !Self::is_open_escrow_status(escrow.status) {
-            panic!("Escrow is not active");
+            panic_with_error!(&env, Error::EscrowNotActive);
         }
 
         let base = escrow
             .extensions
             .release_base
             .clone()
-            .expect("No release condition set");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NoReleaseCondition));
         let quote = escrow
             .extensions
             .release_quote
             .clone()
-            .expect("No release condition set");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NoReleaseCondition));
         let comparison = escrow
             .extensions
             .release_comparison
-            .expect("No release condition set");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NoReleaseCondition));
         let threshold_price = escrow
             .extensions
             .release_threshold_price
-            .expect("No release condition set");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NoReleaseCondition));
 
-        let price_data = Self::get_oracle_price(&env, &base, &quote);
+        let price = if escrow.extensions.twap_observation_count > 0 {
+            Self::twap_price(&env, &escrow)
+        } else {
+            Self::get_oracle_price(&env, &base, &quote).price
+        };
         let condition_met = match comparison {
-            ORACLE_COMPARISON_LESS_OR_EQUAL => price_data.price <= threshold_price,
-            ORACLE_COMPARISON_GREATER_OR_EQUAL => price_data.price >= threshold_price,
-            _ => panic!("Invalid release comparison"),
+            ORACLE_COMPARISON_LESS_OR_EQUAL => price <= threshold_price,
+            ORACLE_COMPARISON_GREATER_OR_EQUAL => price >= threshold_price,
+            _ => panic_with_error!(&env, Error::InvalidReleaseCondition),
         };
 
         if !condition_met {
-            panic!("Release condition not met");
+            panic_with_error!(&env, Error::ReleaseConditionNotMet);
         }
 
         Self::transfer_to_sellers(&env, &escrow, escrow.amount, escrow_id);
@@ -3217,7 +3948,7 @@ impl AhjoorEscrowContract {
         events::emit_oracle_release_triggered(
             &env,
             escrow_id,
-            price_data.price,
+            price,
             base,
             quote,
             comparison,
@@ -3228,27 +3959,87 @@ impl AhjoorEscrowContract {
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
     }
 
-    /// Auto-release expired escrow (past deadline, undisputed). Can be called by buyer.
-    pub fn auto_release_expired(env: Env, escrow_id: u32) {
+    /// Record a price reading for an escrow in TWAP mode. Callable by anyone
+    /// once `twap_window_seconds / twap_observation_count` has passed since
+    /// the previous reading; `keeper` receives the keeper bounty. Only the
+    /// most recent `twap_observation_count + 1` readings are kept, enough to
+    /// cover the whole window.
+    pub fn record_twap_observation(env: Env, keeper: Address, escrow_id: u32) {
+        Self::require_not_paused(&env);
+
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(escrow_id))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
+
+        if !Self::is_open_escrow_status(escrow.status) {
+            panic_with_error!(&env, Error::EscrowNotActive);
+        }
+        let count = escrow.extensions.twap_observation_count;
+        if count == 0 {
+            panic_with_error!(&env, Error::NoReleaseCondition);
+        }
+
+        let now = env.ledger().timestamp();
+        let interval = escrow.extensions.twap_window_seconds / count as u64;
+        if let Some(last) = escrow.extensions.twap_observations.last() {
+            if now < last.timestamp + interval {
+                panic_with_error!(&env, WorkflowError::TwapObservationTooEarly);
+            }
+        }
+
+        // Both are set whenever TWAP mode is
+        let base = escrow.extensions.release_base.clone().unwrap();
+        let quote = escrow.extensions.release_quote.clone().unwrap();
+        let price = Self::get_oracle_price(&env, &base, &quote).price;
+
+        let readings = &mut escrow.extensions.twap_observations;
+        readings.push_back(TwapObservation {
+            price,
+            timestamp: now,
+        });
+        if readings.len() > count + 1 {
+            readings.pop_front();
+        }
+        let recorded = readings.len();
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(escrow_id), &escrow);
+        env.storage().persistent().extend_ttl(
+            &DataKey::Escrow(escrow_id),
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        events::emit_twap_observation_recorded(&env, escrow_id, price, recorded);
+        Self::pay_keeper_reward(&env, &keeper, 1);
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Auto-release expired escrow (past deadline, undisputed) back to the buyer.
+    /// Can be called by anyone; `keeper` receives the keeper bounty.
+    pub fn auto_release_expired(env: Env, keeper: Address, escrow_id: u32) {
         Self::require_not_paused(&env);
         let mut escrow: Escrow = env
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if !Self::is_open_escrow_status(escrow.status) {
-            panic!("Escrow is not active");
+            panic_with_error!(&env, Error::EscrowNotActive);
         }
 
         if env.ledger().timestamp() <= escrow.deadline {
-            panic!("Escrow has not expired yet");
+            panic_with_error!(&env, Error::EscrowNotExpired);
         }
 
         Self::require_unlocked(&env, &escrow);
 
-        escrow.buyer.require_auth();
-
         let client = token::Client::new(&env, &escrow.token);
         client.transfer(
             &env.current_contract_address(),
@@ -3270,6 +4061,7 @@ impl AhjoorEscrowContract {
         );
 
         events::emit_escrow_refunded(&env, escrow_id, escrow.buyer, escrow.amount);
+        Self::pay_keeper_reward(&env, &keeper, 1);
 
         env.storage()
             .instance()
@@ -3290,19 +4082,19 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if caller != escrow.buyer && caller != escrow.seller {
-            panic!("Only buyer or seller can propose deadline extension");
+            panic_with_error!(&env, Error::NotEscrowParty);
         }
 
         if escrow.status == EscrowStatus::Disputed || Self::is_terminal_escrow_status(escrow.status)
         {
-            panic!("Cannot extend deadline while escrow is disputed");
+            panic_with_error!(&env, DisputeError::DisputeActive);
         }
 
         if new_deadline <= escrow.deadline {
-            panic!("New deadline must be greater than current deadline");
+            panic_with_error!(&env, Error::InvalidDeadline);
         }
 
         let proposal = DeadlineProposal {
@@ -3313,98 +4105,460 @@ impl AhjoorEscrowContract {
 
         env.storage()
             .persistent()
-            .set(&DataKey::DeadlineProposal(escrow_id), &proposal);
+            .set(&DataKey::DeadlineProposal(escrow_id), &proposal);
+        env.storage().persistent().extend_ttl(
+            &DataKey::DeadlineProposal(escrow_id),
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        events::emit_deadline_extension_proposed(
+            &env,
+            escrow_id,
+            caller,
+            proposal.new_deadline,
+            proposal.proposed_at,
+        );
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Accept a pending deadline extension proposed by the counterparty.
+    pub fn accept_deadline_extension(env: Env, caller: Address, escrow_id: u32) {
+        caller.require_auth();
+
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(escrow_id))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
+
+        if caller != escrow.buyer && caller != escrow.seller {
+            panic_with_error!(&env, Error::NotEscrowParty);
+        }
+
+        if escrow.status == EscrowStatus::Disputed || Self::is_terminal_escrow_status(escrow.status)
+        {
+            panic_with_error!(&env, DisputeError::DisputeActive);
+        }
+
+        let proposal: DeadlineProposal = env
+            .storage()
+            .persistent()
+            .get(&DataKey::DeadlineProposal(escrow_id))
+            .unwrap_or_else(|| panic_with_error!(&env, WorkflowError::ExtensionNotFound));
+
+        if caller == proposal.proposer {
+            panic_with_error!(&env, WorkflowError::CannotAcceptOwnProposal);
+        }
+
+        let now = env.ledger().timestamp();
+        if now > proposal.proposed_at + DEADLINE_EXTENSION_PROPOSAL_WINDOW {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::DeadlineProposal(escrow_id));
+            panic_with_error!(&env, WorkflowError::ExtensionExpired);
+        }
+
+        if proposal.new_deadline <= escrow.deadline {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::DeadlineProposal(escrow_id));
+            panic_with_error!(&env, Error::InvalidDeadline);
+        }
+
+        let old_deadline = escrow.deadline;
+        escrow.deadline = proposal.new_deadline;
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(escrow_id), &escrow);
+        env.storage().persistent().extend_ttl(
+            &DataKey::Escrow(escrow_id),
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        env.storage()
+            .persistent()
+            .remove(&DataKey::DeadlineProposal(escrow_id));
+
+        events::emit_deadline_extended(&env, escrow_id, old_deadline, escrow.deadline);
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Admin sets how long amendment proposals stay open (seconds).
+    pub fn set_amendment_expiry(env: Env, admin: Address, expiry_seconds: u64) {
+        Self::require_admin(&env, &admin);
+        if expiry_seconds == 0 {
+            panic_with_error!(&env, Error::InvalidDuration);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey2::AmendmentExpirySeconds, &expiry_seconds);
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Propose a mutual amendment to an active escrow's amount, deadline,
+    /// metadata, pending milestones and/or arbiter. Only buyer or seller may
+    /// propose; the counterparty accepts or rejects. Proposing replaces any
+    /// expired proposal but not a live one. Returns the proposal nonce.
+    ///
+    /// An amount increase can only be proposed by the seller, since the buyer
+    /// funds it when accepting.
+    ///
+    /// For milestone escrows the amount follows the milestone list, so an
+    /// amount change must come with `new_milestones`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn propose_amendment(
+        env: Env,
+        proposer: Address,
+        escrow_id: u32,
+        new_amount: Option<i128>,
+        new_deadline: Option<u64>,
+        new_metadata_hash: Option<BytesN<32>>,
+        new_milestones: Option<Vec<Milestone>>,
+        new_arbiter: Option<Address>,
+    ) -> u32 {
+        Self::require_not_paused(&env);
+        proposer.require_auth();
+
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(escrow_id))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
+
+        if proposer != escrow.buyer && proposer != escrow.seller {
+            panic_with_error!(&env, Error::NotEscrowParty);
+        }
+        if escrow.status != EscrowStatus::Active {
+            panic_with_error!(&env, Error::EscrowNotActive);
+        }
+        if new_amount.is_none()
+            && new_deadline.is_none()
+            && new_metadata_hash.is_none()
+            && new_milestones.is_none()
+            && new_arbiter.is_none()
+        {
+            panic_with_error!(&env, WorkflowError::AmendmentNoop);
+        }
+
+        let now = env.ledger().timestamp();
+        if let Some(existing) = env
+            .storage()
+            .persistent()
+            .get::<DataKey2, AmendmentProposal>(&DataKey2::AmendmentProposal(escrow_id))
+        {
+            if now <= existing.expires_at {
+                panic_with_error!(&env, WorkflowError::AmendmentPending);
+            }
+        }
+
+        let proposal_template = AmendmentProposal {
+            nonce: 0,
+            proposer: proposer.clone(),
+            new_amount,
+            new_deadline,
+            new_metadata_hash,
+            new_milestones,
+            new_arbiter,
+            proposed_at: now,
+            expires_at: 0,
+            buyer_signed: proposer == escrow.buyer,
+            seller_signed: proposer == escrow.seller,
+        };
+        // Validate the terms up front; they are re-checked when applied.
+        let (new_total, _) = Self::amended_terms(&env, escrow_id, &escrow, &proposal_template);
+        // The buyer funds a top-up with their own acceptance, so only the seller
+        // can propose one.
+        if new_total > escrow.amount && proposer == escrow.buyer {
+            panic_with_error!(&env, WorkflowError::TopUpNeedsBuyerAcceptance);
+        }
+
+        let nonce: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey2::AmendmentNonce(escrow_id))
+            .unwrap_or(0)
+            + 1;
+        env.storage()
+            .persistent()
+            .set(&DataKey2::AmendmentNonce(escrow_id), &nonce);
+        env.storage().persistent().extend_ttl(
+            &DataKey2::AmendmentNonce(escrow_id),
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        let expiry_seconds: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey2::AmendmentExpirySeconds)
+            .unwrap_or(DEFAULT_AMENDMENT_EXPIRY_SECONDS);
+        let proposal = AmendmentProposal {
+            nonce,
+            expires_at: now + expiry_seconds,
+            ..proposal_template
+        };
+
+        env.storage()
+            .persistent()
+            .set(&DataKey2::AmendmentProposal(escrow_id), &proposal);
+        env.storage().persistent().extend_ttl(
+            &DataKey2::AmendmentProposal(escrow_id),
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        events::emit_amendment_proposed(
+            &env,
+            escrow_id,
+            nonce,
+            proposer,
+            proposal.new_amount,
+            proposal.new_deadline,
+            proposal.new_metadata_hash,
+            proposal.expires_at,
+        );
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        nonce
+    }
+
+    /// Counterparty accepts a pending amendment, which is applied immediately.
+    /// A higher amount must be accepted by the buyer and pulls the difference,
+    /// plus the protocol fee on it, from them; a lower amount refunds the
+    /// difference to the buyer. Returns the new amendment version.
+    pub fn accept_amendment(env: Env, caller: Address, escrow_id: u32, nonce: u32) -> u32 {
+        Self::require_not_paused(&env);
+        caller.require_auth();
+
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(escrow_id))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
+        let mut proposal = Self::pending_amendment(&env, escrow_id, nonce);
+
+        if caller != escrow.buyer && caller != escrow.seller {
+            panic_with_error!(&env, Error::NotEscrowParty);
+        }
+        if caller == proposal.proposer {
+            panic_with_error!(&env, WorkflowError::CannotAcceptOwnProposal);
+        }
+        if env.ledger().timestamp() > proposal.expires_at {
+            panic_with_error!(&env, WorkflowError::AmendmentExpired);
+        }
+        if escrow.status != EscrowStatus::Active {
+            panic_with_error!(&env, Error::EscrowNotActive);
+        }
+
+        if caller == escrow.buyer {
+            proposal.buyer_signed = true;
+        } else {
+            proposal.seller_signed = true;
+        }
+        events::emit_amendment_signed(&env, escrow_id, nonce, caller.clone());
+
+        let (new_amount, new_milestones) =
+            Self::amended_terms(&env, escrow_id, &escrow, &proposal);
+
+        let client = token::Client::new(&env, &escrow.token);
+        if new_amount > escrow.amount {
+            if caller != escrow.buyer {
+                panic_with_error!(&env, WorkflowError::TopUpNeedsBuyerAcceptance);
+            }
+            let top_up = new_amount - escrow.amount;
+            client.transfer(&escrow.buyer, env.current_contract_address(), &top_up);
+
+            // The protocol fee is charged on the top-up on top of the escrowed amount.
+            let (fee_bps, fee_recipient) = Self::get_protocol_fee(env.clone());
+            let protocol_fee = (top_up * fee_bps as i128) / 10_000;
+            if protocol_fee > 0 {
+                let fee_recipient = fee_recipient
+                    .unwrap_or_else(|| panic_with_error!(&env, Error::FeeRecipientNotSet));
+                client.transfer(&escrow.buyer, &fee_recipient, &protocol_fee);
+                events::emit_protocol_fee_paid(&env, escrow_id, protocol_fee, fee_recipient);
+            }
+        } else if new_amount < escrow.amount {
+            client.transfer(
+                &env.current_contract_address(),
+                &escrow.buyer,
+                &(escrow.amount - new_amount),
+            );
+        }
+
+        if let Some(milestones) = new_milestones {
+            env.storage()
+                .persistent()
+                .set(&DataKey::EscrowMilestones(escrow_id), &milestones);
+            env.storage().persistent().extend_ttl(
+                &DataKey::EscrowMilestones(escrow_id),
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+        }
+
+        let mut history: Vec<AmendmentRecord> = env
+            .storage()
+            .persistent()
+            .get(&DataKey2::AmendmentHistory(escrow_id))
+            .unwrap_or(Vec::new(&env));
+        let record = AmendmentRecord {
+            version: history.len() + 1,
+            nonce,
+            proposer: proposal.proposer.clone(),
+            accepted_by: caller,
+            applied_at: env.ledger().timestamp(),
+            old_amount: escrow.amount,
+            new_amount,
+            old_deadline: escrow.deadline,
+            new_deadline: proposal.new_deadline.unwrap_or(escrow.deadline),
+            old_arbiter: escrow.arbiter.clone(),
+            new_arbiter: proposal.new_arbiter.clone().unwrap_or(escrow.arbiter.clone()),
+            old_metadata_hash: escrow.metadata_hash.clone(),
+            new_metadata_hash: proposal
+                .new_metadata_hash
+                .clone()
+                .or(escrow.metadata_hash.clone()),
+            milestones_changed: proposal.new_milestones.is_some(),
+        };
+
+        escrow.amount = record.new_amount;
+        escrow.deadline = record.new_deadline;
+        escrow.arbiter = record.new_arbiter.clone();
+        escrow.metadata_hash = record.new_metadata_hash.clone();
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(escrow_id), &escrow);
+        env.storage().persistent().extend_ttl(
+            &DataKey::Escrow(escrow_id),
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        history.push_back(record.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey2::AmendmentHistory(escrow_id), &history);
         env.storage().persistent().extend_ttl(
-            &DataKey::DeadlineProposal(escrow_id),
+            &DataKey2::AmendmentHistory(escrow_id),
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        env.storage()
+            .persistent()
+            .remove(&DataKey2::AmendmentProposal(escrow_id));
 
-        events::emit_deadline_extension_proposed(
-            &env,
-            escrow_id,
-            caller,
-            proposal.new_deadline,
-            proposal.proposed_at,
-        );
+        events::emit_amendment_applied(&env, escrow_id, &record);
 
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        record.version
     }
 
-    /// Accept a pending deadline extension proposed by the counterparty.
-    pub fn accept_deadline_extension(env: Env, caller: Address, escrow_id: u32) {
+    /// Counterparty rejects a pending amendment.
+    pub fn reject_amendment(env: Env, caller: Address, escrow_id: u32, nonce: u32) {
         caller.require_auth();
 
-        let mut escrow: Escrow = env
+        let escrow: Escrow = env
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
+        let proposal = Self::pending_amendment(&env, escrow_id, nonce);
 
         if caller != escrow.buyer && caller != escrow.seller {
-            panic!("Only buyer or seller can accept deadline extension");
+            panic_with_error!(&env, Error::NotEscrowParty);
         }
-
-        if escrow.status == EscrowStatus::Disputed || Self::is_terminal_escrow_status(escrow.status)
-        {
-            panic!("Cannot extend deadline while escrow is disputed");
+        if caller == proposal.proposer {
+            panic_with_error!(&env, WorkflowError::CannotRejectOwnProposal);
         }
 
-        let proposal: DeadlineProposal = env
-            .storage()
+        env.storage()
             .persistent()
-            .get(&DataKey::DeadlineProposal(escrow_id))
-            .expect("No deadline extension proposal found");
+            .remove(&DataKey2::AmendmentProposal(escrow_id));
+        events::emit_amendment_rejected(&env, escrow_id, nonce, caller);
 
-        if caller == proposal.proposer {
-            panic!("Proposer cannot accept their own deadline extension");
-        }
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
 
-        let now = env.ledger().timestamp();
-        if now > proposal.proposed_at + DEADLINE_EXTENSION_PROPOSAL_WINDOW {
-            env.storage()
-                .persistent()
-                .remove(&DataKey::DeadlineProposal(escrow_id));
-            panic!("Deadline extension proposal has expired");
-        }
+    /// Proposer withdraws their pending amendment.
+    pub fn cancel_amendment(env: Env, caller: Address, escrow_id: u32, nonce: u32) {
+        caller.require_auth();
 
-        if proposal.new_deadline <= escrow.deadline {
-            env.storage()
-                .persistent()
-                .remove(&DataKey::DeadlineProposal(escrow_id));
-            panic!("New deadline must be greater than current deadline");
+        let proposal = Self::pending_amendment(&env, escrow_id, nonce);
+        if caller != proposal.proposer {
+            panic_with_error!(&env, WorkflowError::NotProposer);
         }
 
-        let old_deadline = escrow.deadline;
-        escrow.deadline = proposal.new_deadline;
-
         env.storage()
             .persistent()
-            .set(&DataKey::Escrow(escrow_id), &escrow);
-        env.storage().persistent().extend_ttl(
-            &DataKey::Escrow(escrow_id),
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
+            .remove(&DataKey2::AmendmentProposal(escrow_id));
+        events::emit_amendment_cancelled(&env, escrow_id, nonce, caller);
+
         env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Clear an amendment proposal whose acceptance window has passed.
+    /// Callable by anyone.
+    pub fn expire_amendment(env: Env, escrow_id: u32) {
+        let proposal: AmendmentProposal = env
+            .storage()
             .persistent()
-            .remove(&DataKey::DeadlineProposal(escrow_id));
+            .get(&DataKey2::AmendmentProposal(escrow_id))
+            .unwrap_or_else(|| panic_with_error!(&env, WorkflowError::AmendmentNotFound));
+        if env.ledger().timestamp() <= proposal.expires_at {
+            panic_with_error!(&env, WorkflowError::AmendmentNotExpired);
+        }
 
-        events::emit_deadline_extended(&env, escrow_id, old_deadline, escrow.deadline);
+        env.storage()
+            .persistent()
+            .remove(&DataKey2::AmendmentProposal(escrow_id));
+        events::emit_amendment_expired(&env, escrow_id, proposal.nonce);
 
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
     }
 
+    /// Get the pending amendment proposal for an escrow, if any.
+    pub fn get_amendment_proposal(env: Env, escrow_id: u32) -> Option<AmendmentProposal> {
+        env.storage()
+            .persistent()
+            .get(&DataKey2::AmendmentProposal(escrow_id))
+    }
+
+    /// Get every amendment applied to an escrow, oldest first.
+    pub fn get_amendment_history(env: Env, escrow_id: u32) -> Vec<AmendmentRecord> {
+        env.storage()
+            .persistent()
+            .get(&DataKey2::AmendmentHistory(escrow_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
     /// Get escrow details
     pub fn get_escrow(env: Env, escrow_id: u32) -> Escrow {
         env.storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found")
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound))
     }
 
     /// Get list of sellers for an escrow (#317)
@@ -3413,7 +4567,7 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
         escrow.sellers
     }
 
@@ -3426,19 +4580,19 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if buyer != escrow.buyer {
-            panic!("Only buyer can top up escrow");
+            panic_with_error!(&env, Error::NotBuyer);
         }
 
         // Check status is Active or AwaitingInspection
         if escrow.status != EscrowStatus::Active && escrow.status != EscrowStatus::AwaitingInspection {
-            panic!("Escrow is not active or awaiting inspection");
+            panic_with_error!(&env, Error::InvalidEscrowStatus);
         }
 
         if additional_amount <= 0 {
-            panic!("Additional amount must be positive");
+            panic_with_error!(&env, Error::InvalidAmount);
         }
 
         let max_topup_multiplier: u32 = env
@@ -3450,7 +4604,7 @@ impl AhjoorEscrowContract {
         let new_total = escrow.amount + additional_amount;
 
         if new_total > max_total {
-            panic!("Top-up limit exceeded");
+            panic_with_error!(&env, Error::TopUpLimitExceeded);
         }
 
         // Transfer tokens
@@ -3494,10 +4648,10 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if seller != escrow.seller {
-            panic!("Only seller can acknowledge top-up");
+            panic_with_error!(&env, Error::NotSeller);
         }
 
         escrow.top_up_acknowledged = true;
@@ -3535,27 +4689,27 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if seller != escrow.seller {
-            panic!("Only seller can request partial release");
+            panic_with_error!(&env, Error::NotSeller);
         }
 
         if escrow.status != EscrowStatus::Active {
-            panic!("Partial release only allowed on active escrow");
+            panic_with_error!(&env, Error::EscrowNotActive);
         }
 
         if amount <= 0 {
-            panic!("Partial release amount must be positive");
+            panic_with_error!(&env, Error::InvalidAmount);
         }
 
         if amount > escrow.amount {
-            panic!("Partial release amount cannot exceed escrow amount");
+            panic_with_error!(&env, Error::ReleaseExceedsBalance);
         }
 
         // Check if there's already a pending request
         if env.storage().persistent().has(&DataKey::PendingPartialRelease(escrow_id)) {
-            panic!("Request already pending");
+            panic_with_error!(&env, WorkflowError::PartialReleasePending);
         }
 
         // Get next request ID
@@ -3615,10 +4769,10 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if buyer != escrow.buyer {
-            panic!("Only buyer can approve partial release");
+            panic_with_error!(&env, Error::NotBuyer);
         }
 
         // Get pending request
@@ -3626,10 +4780,10 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::PendingPartialRelease(escrow_id))
-            .expect("No pending partial release request");
+            .unwrap_or_else(|| panic_with_error!(&env, WorkflowError::PartialReleaseNotFound));
 
         if request.request_id != request_id {
-            panic!("Invalid request ID");
+            panic_with_error!(&env, WorkflowError::PartialReleaseNotFound);
         }
 
         // Transfer funds
@@ -3668,14 +4822,14 @@ impl AhjoorEscrowContract {
         seller.require_auth();
 
         if seller == delegate {
-            panic!("Delegate must be different from seller");
+            panic_with_error!(&env, WorkflowError::InvalidTransferTarget);
         }
 
         let escrow: Escrow = env
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         // Verify seller is part of this escrow
         let mut is_seller = false;
@@ -3686,12 +4840,12 @@ impl AhjoorEscrowContract {
             }
         }
         if !is_seller {
-            panic!("Seller is not part of this escrow");
+            panic_with_error!(&env, Error::SellerNotInEscrow);
         }
 
         // Only allow delegation before release
         if !Self::is_open_escrow_status(escrow.status) {
-            panic!("Can only delegate before escrow is released");
+            panic_with_error!(&env, Error::InvalidEscrowStatus);
         }
 
         env.storage()
@@ -3719,10 +4873,10 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if buyer != escrow.buyer {
-            panic!("Only buyer can reject partial release");
+            panic_with_error!(&env, Error::NotBuyer);
         }
 
         // Get pending request
@@ -3730,10 +4884,10 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::PendingPartialRelease(escrow_id))
-            .expect("No pending partial release request");
+            .unwrap_or_else(|| panic_with_error!(&env, WorkflowError::PartialReleaseNotFound));
 
         if request.request_id != request_id {
-            panic!("Invalid request ID");
+            panic_with_error!(&env, WorkflowError::PartialReleaseNotFound);
         }
 
         // Remove pending request
@@ -3754,10 +4908,10 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if seller != escrow.seller {
-            panic!("Only seller can escalate partial release");
+            panic_with_error!(&env, Error::NotSeller);
         }
 
         // Get pending request
@@ -3765,12 +4919,12 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::PendingPartialRelease(escrow_id))
-            .expect("No pending partial release request");
+            .unwrap_or_else(|| panic_with_error!(&env, WorkflowError::PartialReleaseNotFound));
 
         // Check if deadline passed
         let now = env.ledger().timestamp();
         if now < request.response_deadline {
-            panic!("Response deadline not yet passed");
+            panic_with_error!(&env, WorkflowError::ResponseWindowOpen);
         }
 
         // Remove pending request
@@ -3821,21 +4975,21 @@ impl AhjoorEscrowContract {
         current_buyer.require_auth();
 
         if current_buyer == new_buyer {
-            panic!("New buyer must be different from current buyer");
+            panic_with_error!(&env, WorkflowError::InvalidTransferTarget);
         }
 
         let mut escrow: Escrow = env
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if escrow.status != EscrowStatus::Active {
-            panic!("Buyer transfer only allowed for active escrows");
+            panic_with_error!(&env, Error::EscrowNotActive);
         }
 
         if escrow.buyer != current_buyer {
-            panic!("Only current buyer can transfer buyer role");
+            panic_with_error!(&env, Error::NotBuyer);
         }
 
         let old_buyer = escrow.buyer.clone();
@@ -3870,10 +5024,10 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if caller != escrow.buyer && caller != escrow.seller {
-            panic!("Only buyer or seller can update metadata");
+            panic_with_error!(&env, Error::NotEscrowParty);
         }
 
         // #150: Track buyer activity
@@ -3914,7 +5068,7 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
         escrow.metadata_hash
     }
 
@@ -3923,7 +5077,7 @@ impl AhjoorEscrowContract {
         env.storage()
             .persistent()
             .get(&DataKey::Dispute(escrow_id))
-            .expect("No dispute found for this escrow")
+            .unwrap_or_else(|| panic_with_error!(&env, DisputeError::DisputeNotFound))
     }
 
     /// Get escrow counter
@@ -3942,15 +5096,16 @@ impl AhjoorEscrowContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         if admin != stored_admin {
-            panic!("Only admin can upgrade contract");
+            panic_with_error!(&env, Error::Unauthorized);
         }
 
         let old_version = Self::get_or_init_version(&env);
         env.deployer().update_current_contract_wasm(new_wasm_hash);
 
-        let new_version = old_version.checked_add(1).expect("Version overflow");
+        let new_version = old_version.checked_add(1)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::ArithmeticOverflow));
         env.storage()
             .instance()
             .set(&DataKey::ContractVersion, &new_version);
@@ -3970,9 +5125,9 @@ impl AhjoorEscrowContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         if admin != stored_admin {
-            panic!("Only admin can migrate contract");
+            panic_with_error!(&env, Error::Unauthorized);
         }
 
         let version = Self::get_or_init_version(&env);
@@ -3982,7 +5137,7 @@ impl AhjoorEscrowContract {
             .get(&DataKey::MigrationCompleted(version))
             .unwrap_or(false)
         {
-            panic!("Migration already completed for this version");
+            panic_with_error!(&env, Error::MigrationAlreadyCompleted);
         }
 
         env.storage()
@@ -4014,7 +5169,7 @@ impl AhjoorEscrowContract {
     ) -> u32 {
         Self::require_not_paused(&env);
         buyer.require_auth();
-        if unlock_at <= env.ledger().timestamp() { panic!("unlock_at must be in the future"); }
+        if unlock_at <= env.ledger().timestamp() { panic_with_error!(&env, Error::InvalidDeadline); }
         let request = EscrowCreateRequest {
             seller: beneficiary.clone(),
             arbiter,
@@ -4036,6 +5191,8 @@ impl AhjoorEscrowContract {
             auto_renew_max_renewals: None,
 
             auto_renew_interval_ledgers: None,
+            twap_observation_count: 0,
+            twap_window_seconds: 0,
         };
         let escrow_id = Self::create_escrow_core(&env, &buyer, request);
         let lock_data = TimeLockData { unlock_at, beneficiary: beneficiary.clone(), claimed: false };
@@ -4048,12 +5205,14 @@ impl AhjoorEscrowContract {
     pub fn claim_timelocked(env: Env, beneficiary: Address, escrow_id: u32) {
         Self::require_not_paused(&env);
         beneficiary.require_auth();
-        let mut lock_data: TimeLockData = env.storage().persistent().get(&DataKey::TimeLockData(escrow_id)).expect("Not a time-locked escrow");
-        if lock_data.claimed { panic!("Already claimed"); }
-        if beneficiary != lock_data.beneficiary { panic!("Only beneficiary can claim"); }
-        if env.ledger().timestamp() < lock_data.unlock_at { panic!("Unlock time has not passed"); }
-        let escrow: Escrow = env.storage().persistent().get(&DataKey::Escrow(escrow_id)).expect("Escrow not found");
-        if escrow.status != EscrowStatus::Active { panic!("Escrow not active"); }
+        let mut lock_data: TimeLockData = env.storage().persistent().get(&DataKey::TimeLockData(escrow_id))
+            .unwrap_or_else(|| panic_with_error!(&env, MilestoneError::NotTimelocked));
+        if lock_data.claimed { panic_with_error!(&env, MilestoneError::AlreadyClaimed); }
+        if beneficiary != lock_data.beneficiary { panic_with_error!(&env, MilestoneError::NotBeneficiary); }
+        if env.ledger().timestamp() < lock_data.unlock_at { panic_with_error!(&env, MilestoneError::UnlockTimeNotReached); }
+        let escrow: Escrow = env.storage().persistent().get(&DataKey::Escrow(escrow_id))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
+        if escrow.status != EscrowStatus::Active { panic_with_error!(&env, Error::EscrowNotActive); }
         let client = token::Client::new(&env, &escrow.token);
         client.transfer(&env.current_contract_address(), &beneficiary, &escrow.amount);
         let mut e = escrow.clone();
@@ -4069,13 +5228,15 @@ impl AhjoorEscrowContract {
     pub fn cancel_timelocked(env: Env, buyer: Address, escrow_id: u32) {
         Self::require_not_paused(&env);
         buyer.require_auth();
-        let lock_data: TimeLockData = env.storage().persistent().get(&DataKey::TimeLockData(escrow_id)).expect("Not a time-locked escrow");
-        if lock_data.claimed { panic!("Already claimed"); }
-        if env.ledger().timestamp() >= lock_data.unlock_at { panic!("Past unlock time; use claim_timelocked"); }
-        let mut escrow: Escrow = env.storage().persistent().get(&DataKey::Escrow(escrow_id)).expect("Escrow not found");
-        if escrow.buyer != buyer { panic!("Only buyer can cancel"); }
-        if escrow.status == EscrowStatus::Disputed || escrow.status == EscrowStatus::PartiallyDisputed { panic!("Dispute active"); }
-        if escrow.status != EscrowStatus::Active { panic!("Escrow not active"); }
+        let lock_data: TimeLockData = env.storage().persistent().get(&DataKey::TimeLockData(escrow_id))
+            .unwrap_or_else(|| panic_with_error!(&env, MilestoneError::NotTimelocked));
+        if lock_data.claimed { panic_with_error!(&env, MilestoneError::AlreadyClaimed); }
+        if env.ledger().timestamp() >= lock_data.unlock_at { panic_with_error!(&env, MilestoneError::UnlockTimePassed); }
+        let mut escrow: Escrow = env.storage().persistent().get(&DataKey::Escrow(escrow_id))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
+        if escrow.buyer != buyer { panic_with_error!(&env, Error::NotBuyer); }
+        if escrow.status == EscrowStatus::Disputed || escrow.status == EscrowStatus::PartiallyDisputed { panic_with_error!(&env, DisputeError::DisputeActive); }
+        if escrow.status != EscrowStatus::Active { panic_with_error!(&env, Error::EscrowNotActive); }
         let client = token::Client::new(&env, &escrow.token);
         client.transfer(&env.current_contract_address(), &buyer, &escrow.amount);
         escrow.status = EscrowStatus::Refunded;
@@ -4089,6 +5250,149 @@ impl AhjoorEscrowContract {
         env.storage().persistent().get(&DataKey::TimeLockData(escrow_id))
     }
 
+    // ─── HTLC Escrow ─────────────────────────────────────────────────────────
+
+    /// Lock `amount` for `receiver` behind `hashlock`, a sha256 hash. The
+    /// receiver claims with the preimage before `timeout` (a ledger
+    /// timestamp); from `timeout` on the sender can reclaim. No arbiter is
+    /// involved, so the same hashlock can secure the other leg of a swap on
+    /// another chain or in another asset.
+    pub fn create_htlc_escrow(
+        env: Env,
+        sender: Address,
+        receiver: Address,
+        token: Address,
+        amount: i128,
+        hashlock: BytesN<32>,
+        timeout: u64,
+    ) -> u32 {
+        Self::require_not_paused(&env);
+        sender.require_auth();
+        if amount <= 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+        let now = env.ledger().timestamp();
+        if timeout <= now {
+            panic_with_error!(&env, Error::InvalidDeadline);
+        }
+        Self::require_token_allowed(&env, &token);
+
+        token::Client::new(&env, &token).transfer(
+            &sender,
+            env.current_contract_address(),
+            &amount,
+        );
+
+        let htlc_id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey3::HtlcCounter)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey3::HtlcCounter, &(htlc_id + 1));
+        let htlc = HtlcEscrow {
+            id: htlc_id,
+            sender: sender.clone(),
+            receiver: receiver.clone(),
+            token: token.clone(),
+            amount,
+            hashlock: hashlock.clone(),
+            timeout,
+            status: EscrowStatus::Active,
+            preimage: None,
+            created_at: now,
+        };
+        Self::save_htlc(&env, &htlc);
+
+        events::emit_htlc_created(
+            &env,
+            htlc_id,
+            sender,
+            receiver,
+            token,
+            amount,
+            hashlock,
+            timeout,
+        );
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        htlc_id
+    }
+
+    /// Receiver claims the funds by revealing the preimage of the hashlock
+    /// before the timeout. The preimage is stored and emitted so the
+    /// counterparty can use it on the other leg. Allowed while the contract
+    /// is paused, so a pause cannot run the receiver past the timeout.
+    pub fn claim_htlc(env: Env, receiver: Address, htlc_id: u32, preimage: Bytes) {
+        receiver.require_auth();
+        let mut htlc = Self::get_htlc(env.clone(), htlc_id);
+        if htlc.receiver != receiver {
+            panic_with_error!(&env, MilestoneError::NotBeneficiary);
+        }
+        if htlc.status != EscrowStatus::Active {
+            panic_with_error!(&env, MilestoneError::AlreadyClaimed);
+        }
+        if env.ledger().timestamp() >= htlc.timeout {
+            panic_with_error!(&env, MilestoneError::HtlcExpired);
+        }
+        let computed: BytesN<32> = env.crypto().sha256(&preimage).into();
+        if computed != htlc.hashlock {
+            panic_with_error!(&env, MilestoneError::InvalidPreimage);
+        }
+
+        token::Client::new(&env, &htlc.token).transfer(
+            &env.current_contract_address(),
+            &receiver,
+            &htlc.amount,
+        );
+        htlc.status = EscrowStatus::Released;
+        htlc.preimage = Some(preimage.clone());
+        Self::save_htlc(&env, &htlc);
+
+        events::emit_htlc_claimed(&env, htlc_id, receiver, htlc.amount, preimage);
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Sender reclaims an unclaimed HTLC once the timeout has passed. Allowed
+    /// while the contract is paused, like `claim_htlc`.
+    pub fn refund_htlc(env: Env, sender: Address, htlc_id: u32) {
+        sender.require_auth();
+        let mut htlc = Self::get_htlc(env.clone(), htlc_id);
+        if htlc.sender != sender {
+            panic_with_error!(&env, Error::NotBuyer);
+        }
+        if htlc.status != EscrowStatus::Active {
+            panic_with_error!(&env, MilestoneError::AlreadyClaimed);
+        }
+        if env.ledger().timestamp() < htlc.timeout {
+            panic_with_error!(&env, MilestoneError::HtlcNotExpired);
+        }
+
+        token::Client::new(&env, &htlc.token).transfer(
+            &env.current_contract_address(),
+            &sender,
+            &htlc.amount,
+        );
+        htlc.status = EscrowStatus::Refunded;
+        Self::save_htlc(&env, &htlc);
+
+        events::emit_htlc_refunded(&env, htlc_id, sender, htlc.amount);
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    pub fn get_htlc(env: Env, htlc_id: u32) -> HtlcEscrow {
+        env.storage()
+            .persistent()
+            .get(&DataKey3::Htlc(htlc_id))
+            .unwrap_or_else(|| panic_with_error!(&env, MilestoneError::HtlcNotFound))
+    }
+
     // --- Token Whitelist Integration ---
 
     /// Set the token whitelist contract address (admin only)
@@ -4100,9 +5404,9 @@ impl AhjoorEscrowContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         if admin != stored_admin {
-            panic!("Only admin can set token whitelist contract");
+            panic_with_error!(&env, Error::Unauthorized);
         }
 
         env.storage()
@@ -4139,7 +5443,7 @@ impl AhjoorEscrowContract {
         Self::require_or_bootstrap_admin(&env, &admin);
 
         if Self::is_paused(env.clone()) {
-            panic!("Contract already paused");
+            panic_with_error!(&env, Error::ContractAlreadyPaused);
         }
 
         env.storage().instance().set(&DataKey::Paused, &true);
@@ -4152,7 +5456,7 @@ impl AhjoorEscrowContract {
         Self::require_admin(&env, &admin);
 
         if !Self::is_paused(env.clone()) {
-            panic!("Contract is not paused");
+            panic_with_error!(&env, Error::ContractNotPaused);
         }
 
         env.storage().instance().set(&DataKey::Paused, &false);
@@ -4216,10 +5520,10 @@ impl AhjoorEscrowContract {
             .get(&DataKey::AllowedToken(config.token.clone()))
             .unwrap_or(false);
         if !is_allowed {
-            panic!("TokenNotAllowed");
+            panic_with_error!(&env, Error::TokenNotAllowed);
         }
         if config.deadline_duration == 0 {
-            panic!("deadline_duration must be positive");
+            panic_with_error!(&env, Error::InvalidDuration);
         }
 
         let mut counter: u32 = env
@@ -4272,13 +5576,13 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Template(template_id))
-            .expect("Template not found");
+            .unwrap_or_else(|| panic_with_error!(&env, WorkflowError::TemplateNotFound));
 
         if !template.active {
-            panic!("Template is deactivated");
+            panic_with_error!(&env, WorkflowError::TemplateDeactivated);
         }
         if amount <= 0 {
-            panic!("Escrow amount must be positive");
+            panic_with_error!(&env, Error::InvalidAmount);
         }
 
         let deadline = env.ledger().timestamp() + template.config.deadline_duration;
@@ -4325,6 +5629,9 @@ impl AhjoorEscrowContract {
 
                 auto_renew_interval_ledgers: None,
                 renewals_completed: 0,
+                twap_observation_count: 0,
+                twap_window_seconds: 0,
+                twap_observations: Vec::new(&env),
             },
             top_up_history: Vec::new(&env),
             top_up_acknowledged: true,
@@ -4358,8 +5665,10 @@ impl AhjoorEscrowContract {
         escrow_id
     }
     /// Add an arbiter to the pool. Admin only.
+    /// The approval lets the arbiter rejoin by restaking after falling below `min_stake`.
     pub fn add_arbiter(env: Env, admin: Address, arbiter: Address) {
         Self::require_admin(&env, &admin);
+        Self::require_arbiter_staked(&env, &arbiter);
         let mut pool: Vec<Address> = env
             .storage()
             .instance()
@@ -4367,13 +5676,14 @@ impl AhjoorEscrowContract {
             .unwrap_or(Vec::new(&env));
         for i in 0..pool.len() {
             if pool.get(i).unwrap() == arbiter {
-                panic!("Arbiter already in pool");
+                panic_with_error!(&env, DisputeError::ArbiterAlreadyInPool);
             }
         }
         pool.push_back(arbiter.clone());
         env.storage()
             .instance()
             .set(&DataKey::ArbiterPool, &pool);
+        Self::set_arbiter_approved(&env, &arbiter, true);
         events::emit_arbiter_pool_updated(&env, arbiter, true);
         env.storage()
             .instance()
@@ -4381,41 +5691,17 @@ impl AhjoorEscrowContract {
     }
 
     /// Remove an arbiter from the pool. Admin only.
+    /// Revokes the arbiter's approval, so restaking does not bring them back.
     /// Active escrows with this arbiter are flagged via ArbiterNeedsReplacement.
     pub fn remove_arbiter(env: Env, admin: Address, arbiter: Address, escrow_ids: Vec<u32>) {
         Self::require_admin(&env, &admin);
-        let pool: Vec<Address> = env
-            .storage()
-            .instance()
-            .get(&DataKey::ArbiterPool)
-            .expect("Arbiter pool is empty");
-        let mut found = false;
-        let mut new_pool: Vec<Address> = Vec::new(&env);
-        for i in 0..pool.len() {
-            let a = pool.get(i).unwrap();
-            if a == arbiter {
-                found = true;
-            } else {
-                new_pool.push_back(a);
-            }
-        }
-        if !found {
-            panic!("Arbiter not in pool");
+        if !env.storage().instance().has(&DataKey::ArbiterPool) {
+            panic_with_error!(&env, DisputeError::ArbiterPoolEmpty);
         }
-        // Reset index if it would go out of bounds
-        let next_idx: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::NextArbiterIndex)
-            .unwrap_or(0);
-        if new_pool.is_empty() || next_idx >= new_pool.len() {
-            env.storage()
-                .instance()
-                .set(&DataKey::NextArbiterIndex, &0u32);
+        if !Self::remove_from_arbiter_pool(&env, &arbiter) {
+            panic_with_error!(&env, DisputeError::ArbiterNotInPool);
         }
-        env.storage()
-            .instance()
-            .set(&DataKey::ArbiterPool, &new_pool);
+        Self::set_arbiter_approved(&env, &arbiter, false);
         // Flag active escrows that used this arbiter
         for i in 0..escrow_ids.len() {
             let eid = escrow_ids.get(i).unwrap();
@@ -4431,10 +5717,217 @@ impl AhjoorEscrowContract {
                 }
             }
         }
-        events::emit_arbiter_pool_updated(&env, arbiter, false);
+        events::emit_arbiter_pool_updated(&env, arbiter, false);
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Arbiters currently eligible for pool assignment.
+    pub fn get_arbiter_pool(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::ArbiterPool)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    // ── Arbiter Staking ──────────────────────────────────────────────────────
+
+    /// Admin configures arbiter staking. Once set, joining the pool requires at least
+    /// `min_stake` of `token` (an allowed token); `slash_bps` of an arbiter's stake is
+    /// slashed per timeout or overturned ruling, and withdrawals unbond for `unbonding_secs`.
+    pub fn set_arbiter_stake_config(
+        env: Env,
+        admin: Address,
+        token: Address,
+        min_stake: i128,
+        slash_bps: u32,
+        unbonding_secs: u64,
+    ) {
+        Self::require_admin(&env, &admin);
+        let is_allowed = env
+            .storage()
+            .instance()
+            .get(&DataKey::AllowedToken(token.clone()))
+            .unwrap_or(false);
+        if !is_allowed {
+            panic_with_error!(&env, Error::TokenNotAllowed);
+        }
+        if min_stake <= 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+        if slash_bps > 10_000 {
+            panic_with_error!(&env, Error::InvalidBps);
+        }
+        let config = ArbiterStakeConfig {
+            token,
+            min_stake,
+            slash_bps,
+            unbonding_secs,
+        };
+        env.storage()
+            .instance()
+            .set(&DataKey2::ArbiterStakeConfig, &config);
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    pub fn get_arbiter_stake_config(env: Env) -> Option<ArbiterStakeConfig> {
+        env.storage().instance().get(&DataKey2::ArbiterStakeConfig)
+    }
+
+    /// Arbiter posts stake in the configured token. Reaching `min_stake` puts an
+    /// admin-approved arbiter (back) in the pool. Returns the arbiter's active stake.
+    pub fn stake_as_arbiter(env: Env, arbiter: Address, amount: i128) -> i128 {
+        Self::require_not_paused(&env);
+        arbiter.require_auth();
+
+        if amount <= 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+        let config: ArbiterStakeConfig = env
+            .storage()
+            .instance()
+            .get(&DataKey2::ArbiterStakeConfig)
+            .unwrap_or_else(|| panic_with_error!(&env, DisputeError::StakingNotConfigured));
+
+        let mut stake = Self::arbiter_stake(&env, &arbiter).unwrap_or(ArbiterStake {
+            token: config.token.clone(),
+            amount: 0,
+            unbonding: Vec::new(&env),
+        });
+        if stake.token != config.token {
+            if stake.amount > 0 || !stake.unbonding.is_empty() {
+                panic_with_error!(&env, DisputeError::StakeTokenMismatch);
+            }
+            stake.token = config.token.clone();
+        }
+
+        token::Client::new(&env, &config.token).transfer(
+            &arbiter,
+            env.current_contract_address(),
+            &amount,
+        );
+        stake.amount += amount;
+        Self::save_arbiter_stake(&env, &arbiter, &stake);
+
+        let mut pool: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::ArbiterPool)
+            .unwrap_or(Vec::new(&env));
+        if stake.amount >= config.min_stake
+            && Self::is_arbiter_approved(&env, &arbiter)
+            && !pool.contains(&arbiter)
+        {
+            pool.push_back(arbiter.clone());
+            env.storage().instance().set(&DataKey::ArbiterPool, &pool);
+            events::emit_arbiter_pool_updated(&env, arbiter.clone(), true);
+        }
+
+        events::emit_arbiter_staked(&env, arbiter, amount, stake.amount);
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        stake.amount
+    }
+
+    /// Arbiter starts unbonding `amount` of their stake as a new tranche with its own
+    /// unbonding delay. Unbonding stake can still be slashed until it is withdrawn.
+    /// Dropping below `min_stake` removes the arbiter from the pool. Returns the
+    /// timestamp from which the tranche can be withdrawn.
+    pub fn unbond_arbiter_stake(env: Env, arbiter: Address, amount: i128) -> u64 {
+        Self::require_not_paused(&env);
+        arbiter.require_auth();
+
+        let config: ArbiterStakeConfig = env
+            .storage()
+            .instance()
+            .get(&DataKey2::ArbiterStakeConfig)
+            .unwrap_or_else(|| panic_with_error!(&env, DisputeError::StakingNotConfigured));
+        let mut stake = Self::arbiter_stake(&env, &arbiter)
+            .unwrap_or_else(|| panic_with_error!(&env, DisputeError::StakeNotFound));
+        if amount <= 0 || amount > stake.amount {
+            panic_with_error!(&env, DisputeError::InvalidUnbondingAmount);
+        }
+
+        let available_at = env.ledger().timestamp() + config.unbonding_secs;
+        stake.amount -= amount;
+        stake.unbonding.push_back(UnbondingTranche {
+            amount,
+            available_at,
+        });
+        Self::save_arbiter_stake(&env, &arbiter, &stake);
+
+        if stake.amount < config.min_stake && Self::remove_from_arbiter_pool(&env, &arbiter) {
+            events::emit_arbiter_pool_updated(&env, arbiter.clone(), false);
+        }
+
+        events::emit_arbiter_unbonding_started(&env, arbiter, amount, available_at);
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        available_at
+    }
+
+    /// Withdraw every unbonding tranche whose delay has elapsed; later tranches keep
+    /// unbonding. Returns the amount withdrawn.
+    pub fn withdraw_arbiter_stake(env: Env, arbiter: Address) -> i128 {
+        Self::require_not_paused(&env);
+        arbiter.require_auth();
+
+        let mut stake = Self::arbiter_stake(&env, &arbiter)
+            .unwrap_or_else(|| panic_with_error!(&env, DisputeError::StakeNotFound));
+        if stake.unbonding.is_empty() {
+            panic_with_error!(&env, DisputeError::NothingUnbonding);
+        }
+
+        let now = env.ledger().timestamp();
+        let mut amount: i128 = 0;
+        let mut pending: Vec<UnbondingTranche> = Vec::new(&env);
+        for tranche in stake.unbonding.iter() {
+            if tranche.available_at <= now {
+                amount += tranche.amount;
+            } else {
+                pending.push_back(tranche);
+            }
+        }
+        if amount == 0 {
+            panic_with_error!(&env, DisputeError::StakeStillUnbonding);
+        }
+
+        stake.unbonding = pending;
+        Self::save_arbiter_stake(&env, &arbiter, &stake);
+        token::Client::new(&env, &stake.token).transfer(
+            &env.current_contract_address(),
+            &arbiter,
+            &amount,
+        );
+
+        events::emit_arbiter_stake_withdrawn(&env, arbiter, amount);
+
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        amount
+    }
+
+    pub fn get_arbiter_stake(env: Env, arbiter: Address) -> Option<ArbiterStake> {
+        Self::arbiter_stake(&env, &arbiter)
+    }
+
+    /// Total arbiter fees `arbiter` has earned in `token` from resolved disputes.
+    pub fn get_arbiter_fees_earned(env: Env, arbiter: Address, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey2::ArbiterFeesEarned(arbiter, token))
+            .unwrap_or(0)
     }
 
     /// Create an escrow with the next arbiter from the pool (round-robin).
@@ -4450,10 +5943,10 @@ impl AhjoorEscrowContract {
         buyer.require_auth();
 
         if amount <= 0 {
-            panic!("Escrow amount must be positive");
+            panic_with_error!(&env, Error::InvalidAmount);
         }
         if deadline <= env.ledger().timestamp() {
-            panic!("Deadline must be in the future");
+            panic_with_error!(&env, Error::InvalidDeadline);
         }
         let is_allowed = env
             .storage()
@@ -4461,7 +5954,7 @@ impl AhjoorEscrowContract {
             .get(&DataKey::AllowedToken(token.clone()))
             .unwrap_or(false);
         if !is_allowed {
-            panic!("TokenNotAllowed");
+            panic_with_error!(&env, Error::TokenNotAllowed);
         }
 
         let pool: Vec<Address> = env
@@ -4470,7 +5963,7 @@ impl AhjoorEscrowContract {
             .get(&DataKey::ArbiterPool)
             .unwrap_or(Vec::new(&env));
         if pool.is_empty() {
-            panic!("Arbiter pool is empty");
+            panic_with_error!(&env, DisputeError::ArbiterPoolEmpty);
         }
 
         let idx: u32 = env
@@ -4479,6 +5972,8 @@ impl AhjoorEscrowContract {
             .get(&DataKey::NextArbiterIndex)
             .unwrap_or(0);
         let arbiter = pool.get(idx % pool.len()).unwrap();
+        // Arbiters added before staking was configured may still be in the pool
+        Self::require_arbiter_staked(&env, &arbiter);
         let next_idx = (idx + 1) % pool.len();
         env.storage()
             .instance()
@@ -4526,6 +6021,9 @@ impl AhjoorEscrowContract {
 
                 auto_renew_interval_ledgers: None,
                 renewals_completed: 0,
+                twap_observation_count: 0,
+                twap_window_seconds: 0,
+                twap_observations: Vec::new(&env),
             },
             top_up_history: Vec::new(&env),
             top_up_acknowledged: true,
@@ -4565,13 +6063,13 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Template(template_id))
-            .expect("Template not found");
+            .unwrap_or_else(|| panic_with_error!(&env, WorkflowError::TemplateNotFound));
 
         if template.creator != creator {
-            panic!("Only template creator can update");
+            panic_with_error!(&env, WorkflowError::NotTemplateCreator);
         }
         if !template.active {
-            panic!("Template is deactivated");
+            panic_with_error!(&env, WorkflowError::TemplateDeactivated);
         }
 
         let is_allowed = env
@@ -4580,10 +6078,10 @@ impl AhjoorEscrowContract {
             .get(&DataKey::AllowedToken(new_config.token.clone()))
             .unwrap_or(false);
         if !is_allowed {
-            panic!("TokenNotAllowed");
+            panic_with_error!(&env, Error::TokenNotAllowed);
         }
         if new_config.deadline_duration == 0 {
-            panic!("deadline_duration must be positive");
+            panic_with_error!(&env, Error::InvalidDuration);
         }
 
         template.config = new_config;
@@ -4611,13 +6109,13 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Template(template_id))
-            .expect("Template not found");
+            .unwrap_or_else(|| panic_with_error!(&env, WorkflowError::TemplateNotFound));
 
         if template.creator != creator {
-            panic!("Only template creator can deactivate");
+            panic_with_error!(&env, WorkflowError::NotTemplateCreator);
         }
         if !template.active {
-            panic!("Template already deactivated");
+            panic_with_error!(&env, WorkflowError::TemplateDeactivated);
         }
 
         template.active = false;
@@ -4637,7 +6135,7 @@ impl AhjoorEscrowContract {
         env.storage()
             .persistent()
             .get(&DataKey::Template(template_id))
-            .expect("Template not found")
+            .unwrap_or_else(|| panic_with_error!(&env, WorkflowError::TemplateNotFound))
     }
 
     // -------------------------------------------------------------------------
@@ -4655,18 +6153,18 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if escrow.extensions.buyer_inactivity_secs == 0 {
-            panic!("Inactivity release is not enabled for this escrow");
+            panic_with_error!(&env, Error::InactivityReleaseDisabled);
         }
 
         if !Self::is_open_escrow_status(escrow.status) {
-            panic!("Escrow is not active");
+            panic_with_error!(&env, Error::EscrowNotActive);
         }
 
         if seller != escrow.seller {
-            panic!("Only the escrow seller can claim inactivity release");
+            panic_with_error!(&env, Error::NotSeller);
         }
 
         let last_action: u64 = env
@@ -4679,7 +6177,7 @@ impl AhjoorEscrowContract {
         let inactivity_seconds = now.saturating_sub(last_action);
 
         if inactivity_seconds < escrow.extensions.buyer_inactivity_secs {
-            panic!("Buyer inactivity window has not elapsed");
+            panic_with_error!(&env, Error::InactivityWindowOpen);
         }
 
         let client = token::Client::new(&env, &escrow.token);
@@ -4716,7 +6214,7 @@ impl AhjoorEscrowContract {
         Self::require_not_paused(&env);
         Self::require_or_bootstrap_admin(&env, &admin);
         if penalty_bps > 10_000 {
-            panic!("Penalty cannot exceed 10000 bps");
+            panic_with_error!(&env, Error::InvalidBps);
         }
         env.storage()
             .instance()
@@ -4731,7 +6229,7 @@ impl AhjoorEscrowContract {
         Self::require_not_paused(&env);
         Self::require_or_bootstrap_admin(&env, &admin);
         if window_seconds == 0 {
-            panic!("Response window must be positive");
+            panic_with_error!(&env, Error::InvalidDuration);
         }
         env.storage()
             .instance()
@@ -4750,14 +6248,14 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if !Self::is_open_escrow_status(escrow.status) {
-            panic!("Escrow is not active");
+            panic_with_error!(&env, Error::EscrowNotActive);
         }
 
         if caller != escrow.buyer && caller != escrow.seller {
-            panic!("Only buyer or seller can request cancellation");
+            panic_with_error!(&env, Error::NotEscrowParty);
         }
 
         let window: u64 = env
@@ -4813,17 +6311,17 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if escrow.status != EscrowStatus::CancellationPending {
-            panic!("No pending cancellation for this escrow");
+            panic_with_error!(&env, WorkflowError::CancellationNotFound);
         }
 
         let request: CancellationRequest = env
             .storage()
             .persistent()
             .get(&DataKey::CancellationRequest(escrow_id))
-            .expect("Cancellation request not found");
+            .unwrap_or_else(|| panic_with_error!(&env, WorkflowError::CancellationNotFound));
 
         let now = env.ledger().timestamp();
 
@@ -4847,10 +6345,10 @@ impl AhjoorEscrowContract {
 
         // Caller must be the counterparty (not the initiator)
         if caller == request.initiator {
-            panic!("Initiator cannot accept their own cancellation request");
+            panic_with_error!(&env, WorkflowError::CannotAcceptOwnProposal);
         }
         if caller != escrow.buyer && caller != escrow.seller {
-            panic!("Only buyer or seller can accept cancellation");
+            panic_with_error!(&env, Error::NotEscrowParty);
         }
 
         let penalty_bps: u32 = env
@@ -4931,23 +6429,23 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if escrow.status != EscrowStatus::CancellationPending {
-            panic!("No pending cancellation for this escrow");
+            panic_with_error!(&env, WorkflowError::CancellationNotFound);
         }
 
         let request: CancellationRequest = env
             .storage()
             .persistent()
             .get(&DataKey::CancellationRequest(escrow_id))
-            .expect("Cancellation request not found");
+            .unwrap_or_else(|| panic_with_error!(&env, WorkflowError::CancellationNotFound));
 
         if caller == request.initiator {
-            panic!("Initiator cannot reject their own cancellation request");
+            panic_with_error!(&env, WorkflowError::CannotRejectOwnProposal);
         }
         if caller != escrow.buyer && caller != escrow.seller {
-            panic!("Only buyer or seller can reject cancellation");
+            panic_with_error!(&env, Error::NotEscrowParty);
         }
 
         escrow.status = EscrowStatus::Active;
@@ -4977,20 +6475,20 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if escrow.status != EscrowStatus::CancellationPending {
-            panic!("No pending cancellation for this escrow");
+            panic_with_error!(&env, WorkflowError::CancellationNotFound);
         }
 
         let request: CancellationRequest = env
             .storage()
             .persistent()
             .get(&DataKey::CancellationRequest(escrow_id))
-            .expect("Cancellation request not found");
+            .unwrap_or_else(|| panic_with_error!(&env, WorkflowError::CancellationNotFound));
 
         if env.ledger().timestamp() <= request.expires_at {
-            panic!("Response window has not elapsed");
+            panic_with_error!(&env, WorkflowError::ResponseWindowOpen);
         }
 
         escrow.status = EscrowStatus::Active;
@@ -5030,15 +6528,15 @@ impl AhjoorEscrowContract {
         buyer.require_auth();
 
         if amount <= 0 {
-            panic!("Bounty amount must be positive");
+            panic_with_error!(&env, Error::InvalidAmount);
         }
 
         let current_time = env.ledger().timestamp();
         if claim_deadline_ledger <= current_time {
-            panic!("Claim deadline must be in the future");
+            panic_with_error!(&env, Error::InvalidDeadline);
         }
         if submission_deadline_ledger <= claim_deadline_ledger {
-            panic!("Submission deadline must be after claim deadline");
+            panic_with_error!(&env, Error::InvalidDeadline);
         }
 
         // Check token whitelist if configured
@@ -5049,7 +6547,7 @@ impl AhjoorEscrowContract {
         {
             let whitelist_client = TokenWhitelistClient::new(&env, &whitelist_addr);
             if !whitelist_client.is_whitelisted(&token) {
-                panic!("Token not whitelisted");
+                panic_with_error!(&env, Error::TokenNotAllowed);
             }
         }
 
@@ -5104,6 +6602,9 @@ impl AhjoorEscrowContract {
 
                 auto_renew_interval_ledgers: None,
                 renewals_completed: 0,
+                twap_observation_count: 0,
+                twap_window_seconds: 0,
+                twap_observations: Vec::new(&env),
             },
             top_up_history: Vec::new(&env),
             top_up_acknowledged: false,
@@ -5163,21 +6664,21 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if escrow.status != EscrowStatus::BountyUnclaimed {
-            panic!("Bounty is not available for claiming");
+            panic_with_error!(&env, MilestoneError::BountyNotOpen);
         }
 
         let mut bounty_data: BountyData = env
             .storage()
             .persistent()
             .get(&DataKey2::BountyData(escrow_id))
-            .expect("Bounty data not found");
+            .unwrap_or_else(|| panic_with_error!(&env, MilestoneError::BountyNotFound));
 
         let current_time = env.ledger().timestamp();
         if current_time > bounty_data.claim_deadline_ledger {
-            panic!("Claim deadline has passed");
+            panic_with_error!(&env, MilestoneError::ClaimDeadlinePassed);
         }
 
         // Update escrow with solver as seller
@@ -5224,25 +6725,25 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if escrow.status != EscrowStatus::BountyClaimed {
-            panic!("Bounty is not in claimed status");
+            panic_with_error!(&env, MilestoneError::BountyNotClaimed);
         }
 
         if escrow.seller != solver {
-            panic!("Only the assigned solver can submit work");
+            panic_with_error!(&env, MilestoneError::NotSolver);
         }
 
         let mut bounty_data: BountyData = env
             .storage()
             .persistent()
             .get(&DataKey2::BountyData(escrow_id))
-            .expect("Bounty data not found");
+            .unwrap_or_else(|| panic_with_error!(&env, MilestoneError::BountyNotFound));
 
         let current_time = env.ledger().timestamp();
         if current_time > bounty_data.submission_deadline_ledger {
-            panic!("Submission deadline has passed");
+            panic_with_error!(&env, MilestoneError::SubmissionDeadlinePassed);
         }
 
         // Store submission hash
@@ -5272,24 +6773,24 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if escrow.buyer != buyer {
-            panic!("Only buyer can approve submission");
+            panic_with_error!(&env, Error::NotBuyer);
         }
 
         if escrow.status != EscrowStatus::BountyClaimed {
-            panic!("Bounty is not in claimed status");
+            panic_with_error!(&env, MilestoneError::BountyNotClaimed);
         }
 
         let bounty_data: BountyData = env
             .storage()
             .persistent()
             .get(&DataKey2::BountyData(escrow_id))
-            .expect("Bounty data not found");
+            .unwrap_or_else(|| panic_with_error!(&env, MilestoneError::BountyNotFound));
 
         if bounty_data.submission_hash.is_none() {
-            panic!("No submission has been made");
+            panic_with_error!(&env, MilestoneError::NoSubmission);
         }
 
         let solver = escrow.seller.clone();
@@ -5326,21 +6827,21 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if escrow.buyer != buyer {
-            panic!("Only buyer can reject submission");
+            panic_with_error!(&env, Error::NotBuyer);
         }
 
         if escrow.status != EscrowStatus::BountyClaimed {
-            panic!("Bounty is not in claimed status");
+            panic_with_error!(&env, MilestoneError::BountyNotClaimed);
         }
 
         let mut bounty_data: BountyData = env
             .storage()
             .persistent()
             .get(&DataKey2::BountyData(escrow_id))
-            .expect("Bounty data not found");
+            .unwrap_or_else(|| panic_with_error!(&env, MilestoneError::BountyNotFound));
 
         let max_rejections: u32 = env
             .storage()
@@ -5349,7 +6850,7 @@ impl AhjoorEscrowContract {
             .unwrap_or(DEFAULT_MAX_BOUNTY_REJECTION_ROUNDS);
 
         if bounty_data.rejection_count >= max_rejections {
-            panic!("Maximum rejection rounds reached");
+            panic_with_error!(&env, MilestoneError::MaxRejectionRoundsReached);
         }
 
         let rejected_solver = escrow.seller.clone();
@@ -5395,17 +6896,17 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if escrow.buyer != buyer {
-            panic!("Only buyer can cancel bounty");
+            panic_with_error!(&env, Error::NotBuyer);
         }
 
         let bounty_data: BountyData = env
             .storage()
             .persistent()
             .get(&DataKey2::BountyData(escrow_id))
-            .expect("Bounty data not found");
+            .unwrap_or_else(|| panic_with_error!(&env, MilestoneError::BountyNotFound));
 
         let current_time = env.ledger().timestamp();
 
@@ -5417,7 +6918,7 @@ impl AhjoorEscrowContract {
         {
             // Allow cancellation if claim deadline passed (edge case)
         } else {
-            panic!("Cannot cancel bounty in current state");
+            panic_with_error!(&env, MilestoneError::BountyNotCancellable);
         }
 
         let amount = escrow.amount;
@@ -5453,9 +6954,9 @@ impl AhjoorEscrowContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         if admin != stored_admin {
-            panic!("Only admin can set max bounty rejection rounds");
+            panic_with_error!(&env, Error::Unauthorized);
         }
 
         env.storage()
@@ -5493,22 +6994,22 @@ impl AhjoorEscrowContract {
         buyer.require_auth();
 
         if milestones.is_empty() {
-            panic!("Bounty must have at least one milestone");
+            panic_with_error!(&env, MilestoneError::MilestoneRequired);
         }
 
         let current_time = env.ledger().timestamp();
         if claim_deadline_ledger <= current_time {
-            panic!("Claim deadline must be in the future");
+            panic_with_error!(&env, Error::InvalidDeadline);
         }
         if submission_deadline_ledger <= claim_deadline_ledger {
-            panic!("Submission deadline must be after claim deadline");
+            panic_with_error!(&env, Error::InvalidDeadline);
         }
 
         // Validate milestone amounts and compute the total escrow amount.
         let mut total_amount: i128 = 0;
         for m in milestones.iter() {
             if m.amount <= 0 {
-                panic!("Milestone amount must be positive");
+                panic_with_error!(&env, Error::InvalidAmount);
             }
             total_amount += m.amount;
         }
@@ -5521,7 +7022,7 @@ impl AhjoorEscrowContract {
         {
             let whitelist_client = TokenWhitelistClient::new(&env, &whitelist_addr);
             if !whitelist_client.is_whitelisted(&token) {
-                panic!("Token not whitelisted");
+                panic_with_error!(&env, Error::TokenNotAllowed);
             }
         }
 
@@ -5578,6 +7079,9 @@ impl AhjoorEscrowContract {
 
                 auto_renew_interval_ledgers: None,
                 renewals_completed: 0,
+                twap_observation_count: 0,
+                twap_window_seconds: 0,
+                twap_observations: Vec::new(&env),
             },
             top_up_history: Vec::new(&env),
             top_up_acknowledged: false,
@@ -5658,42 +7162,42 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if escrow.status != EscrowStatus::BountyClaimed {
-            panic!("Bounty is not in claimed status");
+            panic_with_error!(&env, MilestoneError::BountyNotClaimed);
         }
         if escrow.seller != solver {
-            panic!("Only the assigned solver can submit milestones");
+            panic_with_error!(&env, MilestoneError::NotSolver);
         }
 
         let current_time = env.ledger().timestamp();
         if current_time > escrow.deadline {
-            panic!("Submission deadline has passed");
+            panic_with_error!(&env, MilestoneError::SubmissionDeadlinePassed);
         }
 
         let mut states: Vec<BountyMilestone> = env
             .storage()
             .persistent()
             .get(&DataKey2::BountyMilestones(escrow_id))
-            .expect("No milestones for this bounty");
+            .unwrap_or_else(|| panic_with_error!(&env, MilestoneError::NoMilestones));
 
         if index >= states.len() {
-            panic!("Milestone index out of range");
+            panic_with_error!(&env, MilestoneError::MilestoneIndexOutOfRange);
         }
 
         // Enforce strict ordering: all earlier milestones must already be paid.
         let mut i: u32 = 0;
         while i < index {
             if states.get(i).unwrap().status != BountyMilestoneStatus::Paid {
-                panic!("Previous milestone not yet verified");
+                panic_with_error!(&env, MilestoneError::PreviousMilestoneUnverified);
             }
             i += 1;
         }
 
         let mut milestone = states.get(index).unwrap();
         if milestone.status != BountyMilestoneStatus::Pending {
-            panic!("Milestone is not awaiting submission");
+            panic_with_error!(&env, MilestoneError::InvalidMilestoneStatus);
         }
 
         milestone.status = BountyMilestoneStatus::Submitted;
@@ -5727,20 +7231,20 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if escrow.status != EscrowStatus::BountyClaimed {
-            panic!("Bounty is not in claimed status");
+            panic_with_error!(&env, MilestoneError::BountyNotClaimed);
         }
 
         let mut states: Vec<BountyMilestone> = env
             .storage()
             .persistent()
             .get(&DataKey2::BountyMilestones(escrow_id))
-            .expect("No milestones for this bounty");
+            .unwrap_or_else(|| panic_with_error!(&env, MilestoneError::NoMilestones));
 
         if index >= states.len() {
-            panic!("Milestone index out of range");
+            panic_with_error!(&env, MilestoneError::MilestoneIndexOutOfRange);
         }
 
         let mut milestone = states.get(index).unwrap();
@@ -5749,7 +7253,7 @@ impl AhjoorEscrowContract {
         milestone.verifier.require_auth();
 
         if milestone.status != BountyMilestoneStatus::Submitted {
-            panic!("Milestone is not awaiting verification");
+            panic_with_error!(&env, MilestoneError::InvalidMilestoneStatus);
         }
 
         // Mark verified, then release the tranche to the solver.
@@ -5819,25 +7323,25 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if escrow.buyer != buyer {
-            panic!("Only the bounty creator can replace a verifier");
+            panic_with_error!(&env, MilestoneError::NotBountyCreator);
         }
 
         let mut states: Vec<BountyMilestone> = env
             .storage()
             .persistent()
             .get(&DataKey2::BountyMilestones(escrow_id))
-            .expect("No milestones for this bounty");
+            .unwrap_or_else(|| panic_with_error!(&env, MilestoneError::NoMilestones));
 
         if index >= states.len() {
-            panic!("Milestone index out of range");
+            panic_with_error!(&env, MilestoneError::MilestoneIndexOutOfRange);
         }
 
         let mut milestone = states.get(index).unwrap();
         if milestone.status != BountyMilestoneStatus::Pending {
-            panic!("Verifier can only be replaced before the milestone is submitted");
+            panic_with_error!(&env, MilestoneError::InvalidMilestoneStatus);
         }
 
         let old_verifier = milestone.verifier.clone();
@@ -5871,7 +7375,7 @@ impl AhjoorEscrowContract {
         env.storage()
             .persistent()
             .get(&DataKey2::BountyMilestones(escrow_id))
-            .expect("No milestones for this bounty")
+            .unwrap_or_else(|| panic_with_error!(&env, MilestoneError::NoMilestones))
     }
 
     // ── #361: Collateral Top-Up Mechanism ────────────────────────────────────
@@ -5893,15 +7397,15 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
         if escrow.buyer != buyer {
-            panic!("Only buyer can configure collateral health");
+            panic_with_error!(&env, Error::NotBuyer);
         }
         if !Self::is_open_escrow_status(escrow.status) {
-            panic!("Escrow is not active");
+            panic_with_error!(&env, Error::EscrowNotActive);
         }
         if min_collateral_ratio_bps == 0 || min_collateral_ratio_bps > 10_000 {
-            panic!("min_collateral_ratio_bps must be 1-10000");
+            panic_with_error!(&env, Error::InvalidBps);
         }
         env.storage().persistent().set(&DataKey2::CollateralMinRatioBps(escrow_id), &min_collateral_ratio_bps);
         env.storage().persistent().extend_ttl(&DataKey2::CollateralMinRatioBps(escrow_id), PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
@@ -5919,19 +7423,20 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         let min_ratio_bps: u32 = env
             .storage()
             .persistent()
             .get(&DataKey2::CollateralMinRatioBps(escrow_id))
-            .expect("Collateral health not configured for this escrow");
+            
+            .unwrap_or_else(|| panic_with_error!(&env, WorkflowError::CollateralHealthNotConfigured));
 
         let oracle_addr: Address = env
             .storage()
             .persistent()
             .get(&DataKey2::CollateralOracle(escrow_id))
-            .expect("Collateral oracle not configured");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::OracleNotConfigured));
 
         let collateral: i128 = env
             .storage()
@@ -5939,18 +7444,13 @@ impl AhjoorEscrowContract {
             .get(&DataKey::SellerCollateral(escrow_id))
             .unwrap_or(0);
 
-        // Query oracle for collateral token price relative to escrow token
-        let oracle_client = oracle::OracleClient::new(&env, &oracle_addr);
-        let price_data = oracle_client
-            .lastprice(&escrow.token, &escrow.token)
-            .unwrap_or(PriceData { price: 10_000_000, timestamp: env.ledger().timestamp() });
+        // Collateral token price relative to escrow token: the pair's oracle
+        // set median when configured, else the escrow's collateral oracle
+        let price_data =
+            Self::get_oracle_price_via(&env, Some(oracle_addr), &escrow.token, &escrow.token);
 
         // current_ratio_bps = (collateral * price * 10000) / (escrow.amount * 10^7)
-        let collateral_value = if price_data.price > 0 {
-            (collateral * price_data.price) / 10_000_000
-        } else {
-            collateral
-        };
+        let collateral_value = (collateral * price_data.price) / 10_000_000;
         let current_ratio_bps = if escrow.amount > 0 {
             ((collateral_value * 10_000) / escrow.amount) as u32
         } else {
@@ -5983,19 +7483,19 @@ impl AhjoorEscrowContract {
         provider.require_auth();
 
         if amount <= 0 {
-            panic!("Top-up amount must be positive");
+            panic_with_error!(&env, Error::InvalidAmount);
         }
 
         let mut escrow: Escrow = env
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if escrow.status != EscrowStatus::UnderCollateralized
             && !Self::is_open_escrow_status(escrow.status)
         {
-            panic!("Escrow is not active or under-collateralized");
+            panic_with_error!(&env, Error::InvalidEscrowStatus);
         }
 
         let client = token::Client::new(&env, &escrow.token);
@@ -6053,23 +7553,23 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if escrow.buyer != buyer {
-            panic!("Only buyer can configure multi-party approval");
+            panic_with_error!(&env, Error::NotBuyer);
         }
 
         if !Self::is_open_escrow_status(escrow.status) {
-            panic!("Escrow is not active");
+            panic_with_error!(&env, Error::EscrowNotActive);
         }
 
         let count = approvers.len();
         if count < 2 || count > 10 {
-            panic!("Approvers count must be between 2 and 10");
+            panic_with_error!(&env, WorkflowError::InvalidApprovers);
         }
 
         if threshold == 0 || threshold > count {
-            panic!("Threshold must be between 1 and approvers count");
+            panic_with_error!(&env, WorkflowError::InvalidApprovers);
         }
 
         env.storage()
@@ -6116,17 +7616,17 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
 
         if !Self::is_open_escrow_status(escrow.status) {
-            panic!("Escrow is not active");
+            panic_with_error!(&env, Error::EscrowNotActive);
         }
 
         let approvers: Vec<Address> = env
             .storage()
             .persistent()
             .get(&DataKey2::MultiPartyApprovers(escrow_id))
-            .expect("Multi-party approval not configured for this escrow");
+            .unwrap_or_else(|| panic_with_error!(&env, WorkflowError::MultiPartyNotConfigured));
 
         let mut is_authorized = false;
         for a in approvers.iter() {
@@ -6136,7 +7636,7 @@ impl AhjoorEscrowContract {
             }
         }
         if !is_authorized {
-            panic!("Caller is not an authorized approver for this escrow");
+            panic_with_error!(&env, WorkflowError::NotApprover);
         }
 
         let mut approvals: Vec<Address> = env
@@ -6147,7 +7647,7 @@ impl AhjoorEscrowContract {
 
         for a in approvals.iter() {
             if a == approver {
-                panic!("Approver has already approved this escrow");
+                panic_with_error!(&env, WorkflowError::AlreadyApproved);
             }
         }
 
@@ -6167,7 +7667,7 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey2::MultiPartyThreshold(escrow_id))
-            .expect("Threshold not configured");
+            .unwrap_or_else(|| panic_with_error!(&env, WorkflowError::MultiPartyNotConfigured));
 
         events::emit_multi_party_approval(&env, escrow_id, approver, approvals_count, threshold);
 
@@ -6229,8 +7729,8 @@ impl AhjoorEscrowContract {
     /// `schedule`, where each `ReleaseTranche` specifies an `unlock_at` timestamp
     /// and an `amount`. The sum of all tranche amounts must equal the total held.
     ///
-    /// Beneficiary (seller) calls `claim_scheduled_release` after each tranche's
-    /// `unlock_at` to receive that tranche's funds.
+    /// Anyone may call `claim_scheduled_release` after each tranche's
+    /// `unlock_at` to pay that tranche's funds to the beneficiary (seller).
     pub fn create_escrow_with_schedule(
         env: Env,
         buyer: Address,
@@ -6244,7 +7744,7 @@ impl AhjoorEscrowContract {
         buyer.require_auth();
 
         if schedule.is_empty() {
-            panic!("Release schedule must contain at least one tranche");
+            panic_with_error!(&env, MilestoneError::EmptySchedule);
         }
 
         let now = env.ledger().timestamp();
@@ -6252,10 +7752,10 @@ impl AhjoorEscrowContract {
         for i in 0..schedule.len() {
             let tranche = schedule.get(i).unwrap();
             if tranche.amount <= 0 {
-                panic!("Each tranche amount must be positive");
+                panic_with_error!(&env, Error::InvalidAmount);
             }
             if tranche.unlock_at <= now {
-                panic!("Each tranche unlock_at must be in the future");
+                panic_with_error!(&env, Error::InvalidDeadline);
             }
             total += tranche.amount;
         }
@@ -6281,6 +7781,8 @@ impl AhjoorEscrowContract {
             auto_renew_max_renewals: None,
 
             auto_renew_interval_ledgers: None,
+            twap_observation_count: 0,
+            twap_window_seconds: 0,
         };
 
         let escrow_id = Self::create_escrow_core(&env, &buyer, request);
@@ -6311,33 +7813,30 @@ impl AhjoorEscrowContract {
 
     /// Claim the next available tranche(s) from a staged release schedule.
     ///
-    /// Callable by the beneficiary (seller). Iterates from the current
+    /// Callable by anyone; tranches always go to the beneficiary (seller) and
+    /// `keeper` receives the keeper bounty per tranche. Iterates from the current
     /// `ReleasedIndex` and transfers every tranche whose `unlock_at` has elapsed.
     /// Skips tranches that have already been claimed. Emits
     /// `ScheduledReleaseExecuted` for each tranche transferred.
-    pub fn claim_scheduled_release(env: Env, beneficiary: Address, escrow_id: u32) {
+    pub fn claim_scheduled_release(env: Env, keeper: Address, escrow_id: u32) {
         Self::require_not_paused(&env);
-        beneficiary.require_auth();
 
         let escrow: Escrow = env
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
-
-        if escrow.seller != beneficiary {
-            panic!("Only the beneficiary (seller) can claim scheduled releases");
-        }
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
+        let beneficiary = escrow.seller.clone();
 
         if !Self::is_open_escrow_status(escrow.status) && escrow.status != EscrowStatus::PartiallyReleased {
-            panic!("Escrow is not in a claimable state");
+            panic_with_error!(&env, Error::InvalidEscrowStatus);
         }
 
         let schedule: Vec<ReleaseTranche> = env
             .storage()
             .persistent()
             .get(&DataKey2::ReleaseSchedule(escrow_id))
-            .expect("No release schedule found for this escrow");
+            .unwrap_or_else(|| panic_with_error!(&env, MilestoneError::ScheduleNotFound));
 
         let mut released_index: u32 = env
             .storage()
@@ -6347,7 +7846,7 @@ impl AhjoorEscrowContract {
 
         let now = env.ledger().timestamp();
         let token_client = token::Client::new(&env, &escrow.token);
-        let mut claimed_any = false;
+        let mut claimed: u32 = 0;
         let total_tranches = schedule.len();
 
         loop {
@@ -6375,11 +7874,11 @@ impl AhjoorEscrowContract {
             );
 
             released_index += 1;
-            claimed_any = true;
+            claimed += 1;
         }
 
-        if !claimed_any {
-            panic!("No tranches are currently claimable");
+        if claimed == 0 {
+            panic_with_error!(&env, MilestoneError::NothingClaimable);
         }
 
         env.storage()
@@ -6416,6 +7915,7 @@ impl AhjoorEscrowContract {
                 PERSISTENT_BUMP_AMOUNT,
             );
         }
+        Self::pay_keeper_reward(&env, &keeper, claimed);
 
         env.storage()
             .instance()
@@ -6438,6 +7938,128 @@ impl AhjoorEscrowContract {
             .unwrap_or(0)
     }
 
+    // --- Keeper Rewards ---
+
+    /// Admin sets the bounty paid to callers of the permissionless maintenance
+    /// calls (`auto_release_expired`, `enforce_dispute_timeout`,
+    /// `claim_scheduled_release`). The pool token can only change while the
+    /// pool is empty.
+    pub fn set_keeper_config(
+        env: Env,
+        admin: Address,
+        token: Address,
+        bounty_per_item: i128,
+        max_reward_per_call: i128,
+        max_reward_per_ledger: i128,
+    ) {
+        Self::require_admin(&env, &admin);
+        if bounty_per_item <= 0 || max_reward_per_call <= 0 || max_reward_per_ledger <= 0 {
+            panic_with_error!(&env, Error::InvalidKeeperConfig);
+        }
+        if let Some(current) = Self::get_keeper_config(env.clone()) {
+            if current.token != token && Self::get_keeper_pool(env.clone()).balance > 0 {
+                panic_with_error!(&env, Error::InvalidKeeperConfig);
+            }
+        }
+        Self::require_token_allowed(&env, &token);
+
+        let config = KeeperConfig {
+            token: token.clone(),
+            bounty_per_item,
+            max_reward_per_call,
+            max_reward_per_ledger,
+        };
+        env.storage().instance().set(&DataKey2::KeeperConfig, &config);
+        events::emit_keeper_config_set(
+            &env,
+            token,
+            bounty_per_item,
+            max_reward_per_call,
+            max_reward_per_ledger,
+        );
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Get the keeper bounty terms, if a keeper pool is configured.
+    pub fn get_keeper_config(env: Env) -> Option<KeeperConfig> {
+        env.storage().instance().get(&DataKey2::KeeperConfig)
+    }
+
+    /// Get the keeper pool balance and the current ledger's payout tally.
+    pub fn get_keeper_pool(env: Env) -> KeeperPool {
+        ahjoor_types::keeper::keeper_pool(&env, &DataKey2::KeeperPool)
+    }
+
+    /// Admin tops up the keeper pool in the configured keeper token.
+    pub fn fund_keeper_pool(env: Env, admin: Address, amount: i128) {
+        Self::require_admin(&env, &admin);
+        if amount <= 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+        let config = Self::get_keeper_config(env.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, Error::KeeperNotConfigured));
+
+        token::Client::new(&env, &config.token).transfer(
+            &admin,
+            env.current_contract_address(),
+            &amount,
+        );
+        let mut pool = Self::get_keeper_pool(env.clone());
+        pool.balance += amount;
+        env.storage().instance().set(&DataKey2::KeeperPool, &pool);
+        events::emit_keeper_pool_funded(&env, amount, pool.balance);
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Admin withdraws unspent funds from the keeper pool.
+    pub fn withdraw_keeper_pool(env: Env, admin: Address, amount: i128) {
+        Self::require_admin(&env, &admin);
+        if amount <= 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+        let config = Self::get_keeper_config(env.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, Error::KeeperNotConfigured));
+        let mut pool = Self::get_keeper_pool(env.clone());
+        if amount > pool.balance {
+            panic_with_error!(&env, Error::InsufficientKeeperPool);
+        }
+
+        pool.balance -= amount;
+        env.storage().instance().set(&DataKey2::KeeperPool, &pool);
+        token::Client::new(&env, &config.token).transfer(
+            &env.current_contract_address(),
+            &admin,
+            &amount,
+        );
+        events::emit_keeper_pool_withdrawn(&env, amount, pool.balance);
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Pay `keeper` the bounty for `items` processed by a maintenance call
+    /// from the keeper pool. Returns the amount paid.
+    fn pay_keeper_reward(env: &Env, keeper: &Address, items: u32) -> i128 {
+        let reward = ahjoor_types::keeper::pay_keeper_reward(
+            env,
+            &DataKey2::KeeperConfig,
+            &DataKey2::KeeperPool,
+            keeper,
+            items,
+        );
+        if reward > 0 {
+            events::emit_keeper_rewarded(env, keeper.clone(), items, reward);
+        }
+        reward
+    }
+
     // --- Internal Helpers ---
 
     fn require_not_paused(env: &Env) {
@@ -6447,7 +8069,7 @@ impl AhjoorEscrowContract {
             .get(&DataKey::Paused)
             .unwrap_or(false)
         {
-            panic!("Contract is paused");
+            panic_with_error!(env, Error::ContractPaused);
         }
     }
 
@@ -6459,7 +8081,7 @@ impl AhjoorEscrowContract {
             .get::<DataKey, Address>(&DataKey::Admin)
         {
             if stored_admin != *admin {
-                panic!("Only admin can pause contract");
+                panic_with_error!(env, Error::Unauthorized);
             }
         } else {
             env.storage().instance().set(&DataKey::Admin, admin);
@@ -6472,9 +8094,9 @@ impl AhjoorEscrowContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Admin not set");
+            .unwrap_or_else(|| panic_with_error!(env, Error::NotInitialized));
         if stored_admin != *admin {
-            panic!("Only admin can resume contract");
+            panic_with_error!(env, Error::Unauthorized);
         }
     }
 
@@ -6487,7 +8109,7 @@ impl AhjoorEscrowContract {
         {
             let client = TokenWhitelistClient::new(env, &whitelist_contract);
             if !client.is_token_allowed_for_contract(&env.current_contract_address(), token) {
-                panic!("TokenNotAllowed");
+                panic_with_error!(env, Error::TokenNotAllowed);
             }
         } else {
             // Fall back to internal allowlist if it has been activated
@@ -6503,7 +8125,7 @@ impl AhjoorEscrowContract {
                     .get(&DataKey::AllowedToken(token.clone()))
                     .unwrap_or(false);
                 if !is_allowed {
-                    panic!("TokenNotAllowed");
+                    panic_with_error!(env, Error::TokenNotAllowed);
                 }
             }
             // If allowlist not activated → allow all tokens (backward compatibility)
@@ -6513,7 +8135,7 @@ impl AhjoorEscrowContract {
     fn require_unlocked(env: &Env, escrow: &Escrow) {
         if let Some(lock_until) = escrow.extensions.min_lock_until {
             if env.ledger().timestamp() < lock_until {
-                panic!("EscrowStillLocked");
+                panic_with_error!(env, Error::EscrowStillLocked);
             }
         }
     }
@@ -6567,11 +8189,28 @@ impl AhjoorEscrowContract {
     }
 
     fn get_oracle_price(env: &Env, base: &Address, quote: &Address) -> PriceData {
-        let oracle_addr: Address = env
+        let oracle_addr: Option<Address> = env.storage().instance().get(&DataKey::OracleAddress);
+        Self::get_oracle_price_via(env, oracle_addr, base, quote)
+    }
+
+    /// Fresh, positive price for a pair: the oracle set's median when one is
+    /// configured, otherwise the price reported by `oracle_addr`.
+    fn get_oracle_price_via(
+        env: &Env,
+        oracle_addr: Option<Address>,
+        base: &Address,
+        quote: &Address,
+    ) -> PriceData {
+        let oracle_set: Option<OracleSet> = env
             .storage()
             .instance()
-            .get(&DataKey::OracleAddress)
-            .expect("Oracle not configured");
+            .get(&DataKey2::OracleSet(base.clone(), quote.clone()));
+        if let Some(set) = oracle_set {
+            return Self::get_aggregated_oracle_price(env, base, quote, &set);
+        }
+
+        let oracle_addr =
+            oracle_addr.unwrap_or_else(|| panic_with_error!(env, Error::OracleNotConfigured));
         let max_oracle_age: u64 = env
             .storage()
             .instance()
@@ -6581,19 +8220,283 @@ impl AhjoorEscrowContract {
         let oracle_client = oracle::OracleClient::new(env, &oracle_addr);
         let price_data = oracle_client
             .lastprice(base, quote)
-            .expect("Oracle price unavailable");
+            .unwrap_or_else(|| panic_with_error!(env, Error::OraclePriceUnavailable));
 
         let age = env.ledger().timestamp().saturating_sub(price_data.timestamp);
         if age > max_oracle_age {
-            panic!("Oracle price is stale");
+            panic_with_error!(env, Error::OraclePriceStale);
         }
         if price_data.price <= 0 {
-            panic!("Invalid oracle price");
+            panic_with_error!(env, Error::InvalidOraclePrice);
         }
 
         price_data
     }
 
+    /// Time-weighted mean of an escrow's TWAP readings over the last
+    /// `twap_window_seconds`: each reading counts for as long as it was the
+    /// latest one. Panics unless the readings cover the whole window and the
+    /// newest is at most one sampling interval old.
+    fn twap_price(env: &Env, escrow: &Escrow) -> i128 {
+        let readings = &escrow.extensions.twap_observations;
+        let window = escrow.extensions.twap_window_seconds;
+        let interval = window / escrow.extensions.twap_observation_count as u64;
+        let now = env.ledger().timestamp();
+        let window_start = now.saturating_sub(window);
+        let (Some(oldest), Some(newest)) = (readings.first(), readings.last()) else {
+            panic_with_error!(env, WorkflowError::TwapNotReady);
+        };
+        if oldest.timestamp > window_start || now - newest.timestamp > interval {
+            panic_with_error!(env, WorkflowError::TwapNotReady);
+        }
+
+        let mut total = 0i128;
+        for i in 0..readings.len() {
+            let reading = readings.get_unchecked(i);
+            let held_until = match readings.get(i + 1) {
+                Some(next) => next.timestamp,
+                None => now,
+            };
+            let held_from = reading.timestamp.max(window_start);
+            if held_until > held_from {
+                total += reading.price * (held_until - held_from) as i128;
+            }
+        }
+        total / window as i128
+    }
+
+    /// Median of the fresh, positive prices reported by the pair's oracle set.
+    /// Sources that fail, report nothing, report a stale price or a
+    /// non-positive one are skipped with an `OracleFailover` event. The
+    /// returned timestamp is that of the oldest source used.
+    fn get_aggregated_oracle_price(
+        env: &Env,
+        base: &Address,
+        quote: &Address,
+        set: &OracleSet,
+    ) -> PriceData {
+        let max_oracle_age: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MaxOracleAge)
+            .unwrap_or(DEFAULT_MAX_ORACLE_AGE_SECONDS);
+        let now = env.ledger().timestamp();
+
+        // Kept sorted ascending as prices come in
+        let mut prices: Vec<i128> = Vec::new(env);
+        let mut oldest = now;
+        for oracle_addr in set.oracles.iter() {
+            let oracle_client = oracle::OracleClient::new(env, &oracle_addr);
+            let reason = match oracle_client.try_lastprice(base, quote) {
+                Ok(Ok(Some(data))) if data.price <= 0 => Some(symbol_short!("invalid")),
+                Ok(Ok(Some(data))) if now.saturating_sub(data.timestamp) > max_oracle_age => {
+                    Some(symbol_short!("stale"))
+                }
+                Ok(Ok(Some(data))) => {
+                    let mut at = prices.len();
+                    while at > 0 && prices.get_unchecked(at - 1) > data.price {
+                        at -= 1;
+                    }
+                    prices.insert(at, data.price);
+                    oldest = oldest.min(data.timestamp);
+                    None
+                }
+                _ => Some(symbol_short!("missing")),
+            };
+            if let Some(reason) = reason {
+                events::emit_oracle_failover(
+                    env,
+                    base.clone(),
+                    quote.clone(),
+                    oracle_addr,
+                    reason,
+                );
+            }
+        }
+
+        let sources = prices.len();
+        if sources == 0 || sources < set.min_sources {
+            panic_with_error!(env, WorkflowError::InsufficientOracleSources);
+        }
+        let mid = sources / 2;
+        let median = if sources % 2 == 1 {
+            prices.get_unchecked(mid)
+        } else {
+            (prices.get_unchecked(mid - 1) + prices.get_unchecked(mid)) / 2
+        };
+
+        // Prices are sorted, so the extremes are the furthest from the median
+        let spread = (median - prices.get_unchecked(0))
+            .max(prices.get_unchecked(sources - 1) - median);
+        if spread * 10_000 > median * set.max_deviation_bps as i128 {
+            panic_with_error!(env, WorkflowError::OracleDeviationExceeded);
+        }
+
+        events::emit_oracle_price_aggregated(
+            env,
+            base.clone(),
+            quote.clone(),
+            median,
+            sources,
+            set.oracles.len() - sources,
+        );
+        PriceData {
+            price: median,
+            timestamp: oldest,
+        }
+    }
+
+    /// Remove `arbiter` from the pool, keeping the round-robin index in bounds.
+    /// Returns false if the arbiter was not in the pool.
+    fn remove_from_arbiter_pool(env: &Env, arbiter: &Address) -> bool {
+        let pool: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::ArbiterPool)
+            .unwrap_or(Vec::new(env));
+        let mut found = false;
+        let mut new_pool: Vec<Address> = Vec::new(env);
+        for i in 0..pool.len() {
+            let a = pool.get(i).unwrap();
+            if a == *arbiter {
+                found = true;
+            } else {
+                new_pool.push_back(a);
+            }
+        }
+        if !found {
+            return false;
+        }
+        // Reset index if it would go out of bounds
+        let next_idx: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextArbiterIndex)
+            .unwrap_or(0);
+        if new_pool.is_empty() || next_idx >= new_pool.len() {
+            env.storage()
+                .instance()
+                .set(&DataKey::NextArbiterIndex, &0u32);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::ArbiterPool, &new_pool);
+        true
+    }
+
+    fn is_arbiter_approved(env: &Env, arbiter: &Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey3::ApprovedArbiter(arbiter.clone()))
+            .unwrap_or(false)
+    }
+
+    fn set_arbiter_approved(env: &Env, arbiter: &Address, approved: bool) {
+        let key = DataKey3::ApprovedArbiter(arbiter.clone());
+        if approved {
+            env.storage().persistent().set(&key, &true);
+            env.storage().persistent().extend_ttl(
+                &key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+    }
+
+    fn arbiter_stake(env: &Env, arbiter: &Address) -> Option<ArbiterStake> {
+        env.storage()
+            .persistent()
+            .get(&DataKey2::ArbiterStake(arbiter.clone()))
+    }
+
+    fn active_arbiter_stake(env: &Env, arbiter: &Address) -> i128 {
+        Self::arbiter_stake(env, arbiter).map(|s| s.amount).unwrap_or(0)
+    }
+
+    /// Internal: panics unless `arbiter` holds the minimum stake, when staking is configured.
+    fn require_arbiter_staked(env: &Env, arbiter: &Address) {
+        if let Some(config) = env
+            .storage()
+            .instance()
+            .get::<DataKey2, ArbiterStakeConfig>(&DataKey2::ArbiterStakeConfig)
+        {
+            if Self::active_arbiter_stake(env, arbiter) < config.min_stake {
+                panic_with_error!(env, DisputeError::StakeBelowMinimum);
+            }
+        }
+    }
+
+    fn save_arbiter_stake(env: &Env, arbiter: &Address, stake: &ArbiterStake) {
+        let key = DataKey2::ArbiterStake(arbiter.clone());
+        env.storage().persistent().set(&key, stake);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn record_arbiter_fee(env: &Env, arbiter: &Address, token: &Address, fee: i128) {
+        let key = DataKey2::ArbiterFeesEarned(arbiter.clone(), token.clone());
+        let earned: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage().persistent().set(&key, &(earned + fee));
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    /// Slash `slash_bps` of an arbiter's stake (active first, then unbonding) and pay it to
+    /// `recipient`. No-op when staking is not configured or the arbiter has no stake.
+    fn slash_arbiter(env: &Env, arbiter: &Address, escrow_id: u32, recipient: &Address) {
+        let config: ArbiterStakeConfig =
+            match env.storage().instance().get(&DataKey2::ArbiterStakeConfig) {
+                Some(config) => config,
+                None => return,
+            };
+        let mut stake = match Self::arbiter_stake(env, arbiter) {
+            Some(stake) => stake,
+            None => return,
+        };
+        let mut total = stake.amount;
+        for tranche in stake.unbonding.iter() {
+            total += tranche.amount;
+        }
+        let slashed = (total * config.slash_bps as i128) / 10_000;
+        if slashed <= 0 {
+            return;
+        }
+
+        // Active stake is slashed first, then the most recently unbonded tranches
+        let from_active = slashed.min(stake.amount);
+        stake.amount -= from_active;
+        let mut remaining = slashed - from_active;
+        while remaining > 0 {
+            let mut tranche = stake.unbonding.pop_back().unwrap();
+            let taken = remaining.min(tranche.amount);
+            tranche.amount -= taken;
+            remaining -= taken;
+            if tranche.amount > 0 {
+                stake.unbonding.push_back(tranche);
+            }
+        }
+        Self::save_arbiter_stake(env, arbiter, &stake);
+
+        token::Client::new(env, &stake.token).transfer(
+            &env.current_contract_address(),
+            recipient,
+            &slashed,
+        );
+        if stake.amount < config.min_stake && Self::remove_from_arbiter_pool(env, arbiter) {
+            events::emit_arbiter_pool_updated(env, arbiter.clone(), false);
+        }
+
+        events::emit_arbiter_slashed(env, arbiter.clone(), escrow_id, slashed, recipient.clone());
+    }
+
     fn effective_arbiter_fee_bps(env: &Env, escrow: &Escrow) -> u32 {
         escrow.extensions.arbiter_fee_bps.unwrap_or(
             env.storage()
@@ -6603,6 +8506,92 @@ impl AhjoorEscrowContract {
         )
     }
 
+    fn pending_amendment(env: &Env, escrow_id: u32, nonce: u32) -> AmendmentProposal {
+        let proposal: AmendmentProposal = env
+            .storage()
+            .persistent()
+            .get(&DataKey2::AmendmentProposal(escrow_id))
+            .unwrap_or_else(|| panic_with_error!(env, WorkflowError::AmendmentNotFound));
+        if proposal.nonce != nonce {
+            panic_with_error!(env, WorkflowError::AmendmentNonceMismatch);
+        }
+        proposal
+    }
+
+    /// Validates an amendment against the escrow's current state and returns
+    /// the resulting amount plus, when milestones change, the full milestone
+    /// list (kept non-pending milestones followed by the new pending ones).
+    fn amended_terms(
+        env: &Env,
+        escrow_id: u32,
+        escrow: &Escrow,
+        proposal: &AmendmentProposal,
+    ) -> (i128, Option<Vec<Milestone>>) {
+        if let Some(deadline) = proposal.new_deadline {
+            if deadline <= env.ledger().timestamp() {
+                panic_with_error!(env, Error::InvalidDeadline);
+            }
+        }
+        if let Some(arbiter) = &proposal.new_arbiter {
+            if *arbiter == escrow.buyer || *arbiter == escrow.seller {
+                panic_with_error!(env, DisputeError::ArbiterIsParty);
+            }
+            Self::require_arbiter_staked(env, arbiter);
+        }
+
+        let current: Option<Vec<Milestone>> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowMilestones(escrow_id));
+        let Some(new_pending) = &proposal.new_milestones else {
+            if current.is_some() && proposal.new_amount.is_some() {
+                panic_with_error!(env, MilestoneError::AmendMilestonesInstead);
+            }
+            let amount = proposal.new_amount.unwrap_or(escrow.amount);
+            if amount <= 0 {
+                panic_with_error!(env, Error::InvalidAmount);
+            }
+            return (amount, None);
+        };
+
+        let current = current
+            .unwrap_or_else(|| panic_with_error!(env, MilestoneError::NoMilestones));
+        let mut milestones = Vec::new(env);
+        let mut total: i128 = 0;
+        for m in current.iter() {
+            if m.status != MilestoneStatus::Pending {
+                total += m.amount;
+                milestones.push_back(m);
+            }
+        }
+        for m in new_pending.iter() {
+            if m.amount <= 0 {
+                panic_with_error!(env, Error::InvalidAmount);
+            }
+            if m.status != MilestoneStatus::Pending {
+                panic_with_error!(env, MilestoneError::InvalidMilestones);
+            }
+            // Milestones are identified by their description hash.
+            if milestones
+                .iter()
+                .any(|kept| kept.description_hash == m.description_hash)
+            {
+                panic_with_error!(env, MilestoneError::InvalidMilestones);
+            }
+            total += m.amount;
+            milestones.push_back(m);
+        }
+        if milestones.is_empty() || milestones.len() > MAX_MILESTONES {
+            panic_with_error!(env, MilestoneError::MilestoneRequired);
+        }
+        if let Some(amount) = proposal.new_amount {
+            if amount != total {
+                panic_with_error!(env, MilestoneError::InvalidMilestones);
+            }
+        }
+        (total, Some(milestones))
+    }
+
     fn is_open_escrow_status(status: EscrowStatus) -> bool {
         matches!(
             status,
@@ -6617,6 +8606,14 @@ impl AhjoorEscrowContract {
         )
     }
 
+    fn save_htlc(env: &Env, htlc: &HtlcEscrow) {
+        let key = DataKey3::Htlc(htlc.id);
+        env.storage().persistent().set(&key, htlc);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    }
+
     fn next_escrow_id(env: &Env) -> u32 {
         let mut counter: u32 = env
             .storage()
@@ -6749,6 +8746,9 @@ impl AhjoorEscrowContract {
                     auto_renew_max_renewals: Some(max_renewals),
                     auto_renew_interval_ledgers: source.extensions.auto_renew_interval_ledgers,
                     renewals_completed: renewal_index,
+                    twap_observation_count: source.extensions.twap_observation_count,
+                    twap_window_seconds: source.extensions.twap_window_seconds,
+                    twap_observations: Vec::new(env),
                 },
                 top_up_history: Vec::new(env),
                 top_up_acknowledged: false,
@@ -6811,12 +8811,12 @@ impl AhjoorEscrowContract {
             .get(&DataKey::RenewalAllowance(old_escrow_id))
             .unwrap_or(0);
         if allowance == 0 {
-            panic!("Insufficient renewal allowance");
+            panic_with_error!(env, WorkflowError::InsufficientRenewalAllowance);
         }
 
         let duration = source.deadline.saturating_sub(source.created_at);
         if duration == 0 {
-            panic!("Escrow renewal duration must be positive");
+            panic_with_error!(env, Error::InvalidDuration);
         }
 
         let client = token::Client::new(env, &source.token);
@@ -6871,6 +8871,9 @@ impl AhjoorEscrowContract {
 
                 auto_renew_interval_ledgers: None,
                 renewals_completed: 0,
+                twap_observation_count: 0,
+                twap_window_seconds: 0,
+                twap_observations: Vec::new(env),
             },
             top_up_history: Vec::new(&env),
             top_up_acknowledged: false,
@@ -6954,9 +8957,9 @@ impl AhjoorEscrowContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         if admin != stored_admin {
-            panic!("Only admin can set max top-up bps");
+            panic_with_error!(&env, Error::Unauthorized);
         }
         env.storage().instance().set(&DataKey::MaxTopUpBps, &max_top_up_bps);
         env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -6985,10 +8988,10 @@ impl AhjoorEscrowContract {
         Self::require_not_paused(&env);
         buyer.require_auth();
         if collateral_forfeit_bps > 10_000 {
-            panic!("collateral_forfeit_bps cannot exceed 10000");
+            panic_with_error!(&env, Error::InvalidBps);
         }
         if sellers.is_empty() {
-            panic!("At least one seller required");
+            panic_with_error!(&env, Error::InvalidSellers);
         }
         let primary_seller = sellers.get(0).unwrap().0;
         let escrow_id = Self::create_escrow_core(
@@ -7015,6 +9018,8 @@ impl AhjoorEscrowContract {
                 auto_renew_max_renewals: None,
 
                 auto_renew_interval_ledgers: None,
+                twap_observation_count: 0,
+                twap_window_seconds: 0,
             },
         );
         if required_collateral_bps > 0 {
@@ -7022,7 +9027,7 @@ impl AhjoorEscrowContract {
                 .storage()
                 .persistent()
                 .get(&DataKey::Escrow(escrow_id))
-                .expect("Escrow not found");
+                .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
             let collateral_amount = (amount as u128 * required_collateral_bps as u128 / 10_000) as i128;
             let deposit_deadline = env.ledger().timestamp() + collateral_deposit_window;
             escrow.status = EscrowStatus::AwaitingCollateral;
@@ -7053,16 +9058,16 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
         if escrow.seller != seller {
-            panic!("Only seller can deposit collateral");
+            panic_with_error!(&env, Error::NotSeller);
         }
         if escrow.status != EscrowStatus::AwaitingCollateral {
-            panic!("Escrow is not awaiting collateral");
+            panic_with_error!(&env, WorkflowError::NotAwaitingCollateral);
         }
         let now = env.ledger().timestamp();
         if now > escrow.extensions.collateral_deposit_deadline {
-            panic!("Collateral deposit window has expired");
+            panic_with_error!(&env, WorkflowError::CollateralWindowExpired);
         }
         let collateral = escrow.extensions.collateral_amount;
         let client = token::Client::new(&env, &escrow.token);
@@ -7104,26 +9109,26 @@ impl AhjoorEscrowContract {
     ) {
         rater.require_auth();
         if rating < 1 || rating > 5 {
-            panic!("Rating must be between 1 and 5");
+            panic_with_error!(&env, Error::InvalidRating);
         }
         let escrow: Escrow = env
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
         if escrow.status != EscrowStatus::Released && escrow.status != EscrowStatus::Resolved {
-            panic!("Rating only allowed after escrow is Released or Resolved");
+            panic_with_error!(&env, Error::InvalidEscrowStatus);
         }
         let ratee = if rater == escrow.buyer {
             escrow.seller.clone()
         } else if rater == escrow.seller {
             escrow.buyer.clone()
         } else {
-            panic!("Only buyer or seller can submit a rating");
+            panic_with_error!(&env, Error::NotEscrowParty);
         };
         let rating_key = DataKey2::RatingSubmitted(escrow_id, rater.clone());
         if env.storage().persistent().has(&rating_key) {
-            panic!("Rating already submitted for this escrow");
+            panic_with_error!(&env, Error::RatingAlreadySubmitted);
         }
         env.storage().persistent().set(&rating_key, &true);
         env.storage().persistent().extend_ttl(
@@ -7162,25 +9167,25 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
         if escrow.seller != seller {
-            panic!("Only seller can submit delivery proof");
+            panic_with_error!(&env, Error::NotSeller);
         }
         if escrow.status == EscrowStatus::Disputed {
-            panic!("Proof submission locked: escrow is under dispute");
+            panic_with_error!(&env, Error::DeliveryProofLocked);
         }
         if !Self::is_open_escrow_status(escrow.status) {
-            panic!("Escrow is not active");
+            panic_with_error!(&env, Error::EscrowNotActive);
         }
         let expected_hash = escrow
             .extensions
             .delivery_proof_hash
             .clone()
-            .expect("No delivery proof hash set on this escrow");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NoDeliveryProof));
         let proof_bytes = soroban_sdk::Bytes::from(proof.clone());
         let computed: BytesN<32> = env.crypto().sha256(&proof_bytes).into();
         if computed != expected_hash {
-            panic!("InvalidDeliveryProof");
+            panic_with_error!(&env, Error::InvalidDeliveryProof);
         }
         let total = escrow.amount;
         Self::transfer_to_sellers(&env, &escrow, total, escrow_id);
@@ -7206,18 +9211,18 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
         if escrow.buyer != buyer {
-            panic!("Only the buyer can approve");
+            panic_with_error!(&env, Error::NotBuyer);
         }
         if escrow.status != EscrowStatus::AwaitingBuyerVetoDecision {
-            panic!("No pending seller transfer");
+            panic_with_error!(&env, WorkflowError::TransferNotFound);
         }
         let proposal: SellerTransferProposal = env
             .storage()
             .persistent()
             .get(&DataKey2::SellerTransferProposal(escrow_id))
-            .expect("Proposal not found");
+            .unwrap_or_else(|| panic_with_error!(&env, WorkflowError::TransferNotFound));
         escrow.seller = proposal.new_seller.clone();
         escrow.status = EscrowStatus::Active;
         env.storage()
@@ -7244,12 +9249,12 @@ impl AhjoorEscrowContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         if admin != stored_admin {
-            panic!("Only admin can set veto override window");
+            panic_with_error!(&env, Error::Unauthorized);
         }
         if window_seconds == 0 {
-            panic!("window_seconds must be positive");
+            panic_with_error!(&env, Error::InvalidDuration);
         }
         env.storage()
             .persistent()
@@ -7268,12 +9273,12 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
         if escrow.seller != seller {
-            panic!("Only the escrow seller can raise a veto");
+            panic_with_error!(&env, Error::NotSeller);
         }
         if !Self::is_open_escrow_status(escrow.status) {
-            panic!("Escrow is not active");
+            panic_with_error!(&env, Error::EscrowNotActive);
         }
         if env
             .storage()
@@ -7281,7 +9286,7 @@ impl AhjoorEscrowContract {
             .get::<_, u64>(&DataKey2::VetoTimestamp(escrow_id))
             .is_some()
         {
-            panic!("SellerVetoAlreadyRaised");
+            panic_with_error!(&env, WorkflowError::SellerVetoAlreadyRaised);
         }
         let now = env.ledger().timestamp();
         env.storage()
@@ -7306,15 +9311,15 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
         if escrow.seller != seller {
-            panic!("Only the escrow seller can cancel a veto");
+            panic_with_error!(&env, Error::NotSeller);
         }
         let veto_ts: u64 = env
             .storage()
             .persistent()
             .get(&DataKey2::VetoTimestamp(escrow_id))
-            .expect("No active veto to cancel");
+            .unwrap_or_else(|| panic_with_error!(&env, WorkflowError::NoActiveVeto));
         let window: u64 = env
             .storage()
             .persistent()
@@ -7322,7 +9327,7 @@ impl AhjoorEscrowContract {
             .unwrap_or(DEFAULT_VETO_OVERRIDE_WINDOW_SECONDS);
         let now = env.ledger().timestamp();
         if now > veto_ts.saturating_add(window) {
-            panic!("VetoWindowElapsed: override window has passed; only admin can act now");
+            panic_with_error!(&env, WorkflowError::VetoWindowElapsed);
         }
         env.storage()
             .persistent()
@@ -7342,24 +9347,24 @@ impl AhjoorEscrowContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         if admin != stored_admin {
-            panic!("Only admin can override a seller veto");
+            panic_with_error!(&env, Error::Unauthorized);
         }
         let mut escrow: Escrow = env
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
         // Block if an active dispute exists
         if escrow.status == EscrowStatus::Disputed || escrow.status == EscrowStatus::PartiallyDisputed {
-            panic!("ActiveDisputeExists: resolve the dispute before overriding veto");
+            panic_with_error!(&env, DisputeError::DisputeActive);
         }
         let veto_ts: u64 = env
             .storage()
             .persistent()
             .get(&DataKey2::VetoTimestamp(escrow_id))
-            .expect("No active veto to override");
+            .unwrap_or_else(|| panic_with_error!(&env, WorkflowError::NoActiveVeto));
         let window: u64 = env
             .storage()
             .persistent()
@@ -7367,7 +9372,7 @@ impl AhjoorEscrowContract {
             .unwrap_or(DEFAULT_VETO_OVERRIDE_WINDOW_SECONDS);
         let now = env.ledger().timestamp();
         if now <= veto_ts.saturating_add(window) {
-            panic!("VetoWindowNotElapsed: override window has not yet passed");
+            panic_with_error!(&env, WorkflowError::VetoWindowNotElapsed);
         }
         let elapsed = now.saturating_sub(veto_ts);
         // Store reason hash immutably
@@ -7408,17 +9413,17 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
         if escrow.status != EscrowStatus::AwaitingBuyerVetoDecision {
-            panic!("No pending seller transfer");
+            panic_with_error!(&env, WorkflowError::TransferNotFound);
         }
         let proposal: SellerTransferProposal = env
             .storage()
             .persistent()
             .get(&DataKey2::SellerTransferProposal(escrow_id))
-            .expect("Proposal not found");
+            .unwrap_or_else(|| panic_with_error!(&env, WorkflowError::TransferNotFound));
         if env.ledger().sequence() <= proposal.veto_deadline {
-            panic!("Veto window has not expired yet");
+            panic_with_error!(&env, WorkflowError::VetoWindowOpen);
         }
         escrow.seller = proposal.new_seller.clone();
         escrow.status = EscrowStatus::Active;
@@ -7445,12 +9450,12 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
         if escrow.seller != seller {
-            panic!("Only the current seller can initiate a transfer");
+            panic_with_error!(&env, Error::NotSeller);
         }
         if escrow.status != EscrowStatus::Active {
-            panic!("Escrow must be active to transfer seller role");
+            panic_with_error!(&env, Error::EscrowNotActive);
         }
         let veto_window: u32 = env
             .storage()
@@ -7494,12 +9499,12 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
         if escrow.buyer != buyer {
-            panic!("Only the buyer can veto");
+            panic_with_error!(&env, Error::NotBuyer);
         }
         if escrow.status != EscrowStatus::AwaitingBuyerVetoDecision {
-            panic!("No pending seller transfer");
+            panic_with_error!(&env, WorkflowError::TransferNotFound);
         }
         let amount = escrow.amount;
         let token_client = token::Client::new(&env, &escrow.token);
@@ -7527,9 +9532,9 @@ impl AhjoorEscrowContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         if admin != stored_admin {
-            panic!("Only admin can set the veto window");
+            panic_with_error!(&env, Error::Unauthorized);
         }
         env.storage()
             .instance()
@@ -7578,10 +9583,10 @@ impl AhjoorEscrowContract {
         buyer.require_auth();
 
         if milestones.is_empty() {
-            panic!("At least one milestone required");
+            panic_with_error!(&env, MilestoneError::MilestoneRequired);
         }
         if milestones.len() > MAX_MILESTONES {
-            panic!("Too many milestones");
+            panic_with_error!(&env, MilestoneError::TooManyMilestones);
         }
 
         // Validate release_bps sum == 10 000
@@ -7589,15 +9594,15 @@ impl AhjoorEscrowContract {
         for i in 0..milestones.len() {
             let m = milestones.get(i).unwrap();
             if m.release_bps == 0 {
-                panic!("release_bps must be positive for each milestone");
+                panic_with_error!(&env, MilestoneError::InvalidMilestones);
             }
             total_bps = total_bps.saturating_add(m.release_bps);
         }
         if total_bps != 10_000 {
-            panic!("Milestone release_bps must sum to 10 000");
+            panic_with_error!(&env, MilestoneError::InvalidMilestones);
         }
         if amount <= 0 {
-            panic!("Escrow amount must be positive");
+            panic_with_error!(&env, Error::InvalidAmount);
         }
 
         let request = EscrowCreateRequest {
@@ -7621,6 +9626,8 @@ impl AhjoorEscrowContract {
             auto_renew_max_renewals: None,
 
             auto_renew_interval_ledgers: None,
+            twap_observation_count: 0,
+            twap_window_seconds: 0,
         };
         let escrow_id = Self::create_escrow_core(&env, &buyer, request);
 
@@ -7668,28 +9675,28 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
         if escrow.seller != seller {
-            panic!("Only the escrow seller may submit milestones");
+            panic_with_error!(&env, Error::NotSeller);
         }
         if escrow.status != EscrowStatus::Active {
-            panic!("Escrow is not active");
+            panic_with_error!(&env, Error::EscrowNotActive);
         }
 
         let mut states: Vec<MilestoneState> = env
             .storage()
             .persistent()
             .get(&DataKey2::EscrowMilestonesV2(escrow_id))
-            .expect("No BPS milestones for this escrow");
+            .unwrap_or_else(|| panic_with_error!(&env, MilestoneError::NoMilestones));
 
         if milestone_index >= states.len() {
-            panic!("Milestone index out of range");
+            panic_with_error!(&env, MilestoneError::MilestoneIndexOutOfRange);
         }
         let mut state = states.get(milestone_index).unwrap();
         if state.status != MilestoneStateStatus::Pending
             && state.status != MilestoneStateStatus::Rejected
         {
-            panic!("Milestone must be Pending or Rejected to submit");
+            panic_with_error!(&env, MilestoneError::InvalidMilestoneStatus);
         }
 
         state.status = MilestoneStateStatus::Submitted;
@@ -7727,26 +9734,26 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
         if caller != escrow.buyer && caller != escrow.arbiter {
-            panic!("Only buyer or arbiter can approve milestones");
+            panic_with_error!(&env, Error::NotBuyerOrArbiter);
         }
         if escrow.status != EscrowStatus::Active {
-            panic!("Escrow is not active");
+            panic_with_error!(&env, Error::EscrowNotActive);
         }
 
         let mut states: Vec<MilestoneState> = env
             .storage()
             .persistent()
             .get(&DataKey2::EscrowMilestonesV2(escrow_id))
-            .expect("No BPS milestones for this escrow");
+            .unwrap_or_else(|| panic_with_error!(&env, MilestoneError::NoMilestones));
 
         if milestone_index >= states.len() {
-            panic!("Milestone index out of range");
+            panic_with_error!(&env, MilestoneError::MilestoneIndexOutOfRange);
         }
         let mut state = states.get(milestone_index).unwrap();
         if state.status != MilestoneStateStatus::Submitted {
-            panic!("Milestone must be Submitted before approval");
+            panic_with_error!(&env, MilestoneError::InvalidMilestoneStatus);
         }
 
         // Calculate release amount using bps; final milestone gets remainder
@@ -7828,26 +9835,26 @@ impl AhjoorEscrowContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("Escrow not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EscrowNotFound));
         if escrow.buyer != buyer {
-            panic!("Only the escrow buyer may reject milestones");
+            panic_with_error!(&env, Error::NotBuyer);
         }
         if escrow.status != EscrowStatus::Active {
-            panic!("Escrow is not active");
+            panic_with_error!(&env, Error::EscrowNotActive);
         }
 
         let mut states: Vec<MilestoneState> = env
             .storage()
             .persistent()
             .get(&DataKey2::EscrowMilestonesV2(escrow_id))
-            .expect("No BPS milestones for this escrow");
+            .unwrap_or_else(|| panic_with_error!(&env, MilestoneError::NoMilestones));
 
         if milestone_index >= states.len() {
-            panic!("Milestone index out of range");
+            panic_with_error!(&env, MilestoneError::MilestoneIndexOutOfRange);
         }
         let mut state = states.get(milestone_index).unwrap();
         if state.status != MilestoneStateStatus::Submitted {
-            panic!("Only a Submitted milestone can be rejected");
+            panic_with_error!(&env, MilestoneError::InvalidMilestoneStatus);
         }
 
         state.status = MilestoneStateStatus::Rejected;
@@ -7874,7 +9881,7 @@ impl AhjoorEscrowContract {
         env.storage()
             .persistent()
             .get(&DataKey2::EscrowMilestonesV2(escrow_id))
-            .expect("No BPS milestones for this escrow")
+            .unwrap_or_else(|| panic_with_error!(&env, MilestoneError::NoMilestones))
     }
 
 }
@@ -7902,3 +9909,19 @@ mod test_milestone_bps;
 mod test_bounty_board;
 #[cfg(test)]
 mod test_bounty_milestone;
+#[cfg(test)]
+mod test_amendment;
+#[cfg(test)]
+mod test_arbitration_panel;
+#[cfg(test)]
+mod test_arbiter_staking;
+#[cfg(test)]
+mod test_keeper;
+#[cfg(test)]
+mod test_oracle_set;
+#[cfg(test)]
+mod test_twap;
+#[cfg(test)]
+mod test_htlc;
+#[cfg(test)]
+mod test_errors;
@@ -106,7 +106,7 @@ fn test_receipt_transfer_and_release_to_new_holder() {
 }
 
 #[test]
-#[should_panic(expected = "Only current holder can transfer receipt")]
+#[should_panic(expected = "Error(Contract, #1035)")] // NotReceiptHolder
 fn test_non_holder_transfer_rejected() {
     let s = setup();
 
@@ -126,7 +126,7 @@ fn test_non_holder_transfer_rejected() {
 }
 
 #[test]
-#[should_panic(expected = "ActiveMilestoneInProgress")]
+#[should_panic(expected = "Error(Contract, #1036)")] // ActiveMilestoneInProgress
 fn test_mid_milestone_transfer_rejection() {
     let s = setup();
     let buyer = Address::generate(&s.env);
@@ -162,7 +162,7 @@ fn test_burn_on_cancel() {
 }
 
 #[test]
-#[should_panic(expected = "Escrow amount must be positive")]
+#[should_panic(expected = "Error(Contract, #1009)")] // InvalidAmount
 fn test_create_escrow_zero_amount_panics() {
     let s = setup();
 
@@ -177,7 +177,7 @@ fn test_create_escrow_zero_amount_panics() {
 }
 
 #[test]
-#[should_panic(expected = "Deadline must be in the future")]
+#[should_panic(expected = "Error(Contract, #1010)")] // InvalidDeadline
 fn test_create_escrow_past_deadline_panics() {
     let s = setup();
 
@@ -318,7 +318,7 @@ fn test_partial_release_over_release_attempt_rejected() {
 }
 
 #[test]
-#[should_panic(expected = "Only buyer or arbiter can release escrow")]
+#[should_panic(expected = "Error(Contract, #1024)")] // NotBuyerOrArbiter
 fn test_release_escrow_by_seller_panics() {
     let s = setup();
 
@@ -336,7 +336,7 @@ fn test_release_escrow_by_seller_panics() {
 }
 
 #[test]
-#[should_panic(expected = "Escrow is not active")]
+#[should_panic(expected = "Error(Contract, #1007)")] // EscrowNotActive
 fn test_release_escrow_already_released_panics() {
     let s = setup();
 
@@ -412,7 +412,7 @@ fn test_dispute_escrow_by_seller() {
 }
 
 #[test]
-#[should_panic(expected = "Only buyer or seller can dispute escrow")]
+#[should_panic(expected = "Error(Contract, #1023)")] // NotEscrowParty
 fn test_dispute_escrow_by_arbiter_panics() {
     let s = setup();
 
@@ -561,7 +561,7 @@ fn test_escalation_emits_event_with_effective_timeout() {
 }
 
 #[test]
-#[should_panic(expected = "dispute_timeout_seconds must be positive")]
+#[should_panic(expected = "Error(Contract, #1011)")] // InvalidDuration
 fn test_create_escrow_with_zero_dispute_timeout_panics() {
     let s = setup();
 
@@ -586,7 +586,7 @@ fn test_create_escrow_with_zero_dispute_timeout_panics() {
 }
 
 #[test]
-#[should_panic(expected = "Timeout must be positive")]
+#[should_panic(expected = "Error(Contract, #1011)")] // InvalidDuration
 fn test_update_default_dispute_timeout_zero_panics() {
     let s = setup();
     s.client
@@ -822,7 +822,7 @@ fn test_resolve_dispute_to_buyer() {
 }
 
 #[test]
-#[should_panic(expected = "Only arbiter can resolve dispute")]
+#[should_panic(expected = "Error(Contract, #1105)")] // NotArbiter
 fn test_resolve_dispute_by_buyer_panics() {
     let s = setup();
 
@@ -956,7 +956,7 @@ fn test_resolve_dispute_0_100_is_full_seller_win() {
 }
 
 #[test]
-#[should_panic(expected = "buyer_percent must be between 0 and 100")]
+#[should_panic(expected = "Error(Contract, #1106)")] // InvalidSplit
 fn test_resolve_dispute_invalid_percent_panics() {
     let s = setup();
     let buyer = Address::generate(&s.env);
@@ -1157,7 +1157,7 @@ fn test_auto_release_expired() {
     // Advance time past deadline
     s.env.ledger().set_timestamp(deadline + 1);
 
-    s.client.auto_release_expired(&escrow_id);
+    s.client.auto_release_expired(&Address::generate(&s.env), &escrow_id);
 
     let escrow = s.client.get_escrow(&escrow_id);
     assert_eq!(escrow.status, EscrowStatus::Refunded);
@@ -1166,7 +1166,7 @@ fn test_auto_release_expired() {
 }
 
 #[test]
-#[should_panic(expected = "Escrow has not expired yet")]
+#[should_panic(expected = "Error(Contract, #1040)")] // EscrowNotExpired
 fn test_auto_release_not_expired_panics() {
     let s = setup();
 
@@ -1180,11 +1180,11 @@ fn test_auto_release_not_expired_panics() {
         s.client
             .create_escrow(&buyer, &seller, &arbiter, &250, &s.token_addr, &deadline, &None, &Vec::new(&s.env), &false, &0u32);
 
-    s.client.auto_release_expired(&escrow_id);
+    s.client.auto_release_expired(&Address::generate(&s.env), &escrow_id);
 }
 
 #[test]
-#[should_panic(expected = "Escrow is not active")]
+#[should_panic(expected = "Error(Contract, #1007)")] // EscrowNotActive
 fn test_auto_release_disputed_panics() {
     let s = setup();
 
@@ -1208,7 +1208,7 @@ fn test_auto_release_disputed_panics() {
     // Advance time past deadline
     s.env.ledger().set_timestamp(deadline + 1);
 
-    s.client.auto_release_expired(&escrow_id);
+    s.client.auto_release_expired(&Address::generate(&s.env), &escrow_id);
 }
 
 // ===========================================================================
@@ -1809,7 +1809,7 @@ fn test_non_admin_cannot_add_or_remove_allowed_token() {
 }
 
 #[test]
-#[should_panic(expected = "TokenNotAllowed")]
+#[should_panic(expected = "Error(Contract, #1013)")] // TokenNotAllowed
 fn test_create_escrow_with_disallowed_token_panics_token_not_allowed() {
     let s = setup();
     let unallowed_token = Address::generate(&s.env);
@@ -1971,7 +1971,7 @@ fn test_update_metadata_multiple_times_only_latest_stored() {
 }
 
 #[test]
-#[should_panic(expected = "Only buyer or seller can update metadata")]
+#[should_panic(expected = "Error(Contract, #1023)")] // NotEscrowParty
 fn test_update_metadata_unauthorized_panics() {
     let s = setup();
     let buyer = Address::generate(&s.env);
@@ -2146,7 +2146,7 @@ fn test_release_multi_party_dust_goes_to_first_seller() {
 }
 
 #[test]
-#[should_panic(expected = "Seller allocations must sum to 10000 bps")]
+#[should_panic(expected = "Error(Contract, #1015)")] // InvalidSellers
 fn test_create_multi_party_escrow_invalid_bps_panics() {
     let s = setup();
     let buyer = Address::generate(&s.env);
@@ -2175,7 +2175,7 @@ fn test_create_multi_party_escrow_invalid_bps_panics() {
 }
 
 #[test]
-#[should_panic(expected = "Maximum 5 sellers allowed")]
+#[should_panic(expected = "Error(Contract, #1015)")] // InvalidSellers
 fn test_create_multi_party_escrow_too_many_sellers_panics() {
     let s = setup();
     let buyer = Address::generate(&s.env);
@@ -3025,7 +3025,9 @@ fn test_arbiter_fee_deducted_from_loser_seller_wins() {
         dispute_default_winner: None,
         auto_renew_max_renewals: None,
 
-        auto_renew_interval_ledgers: None,    };
+        auto_renew_interval_ledgers: None,
+        twap_observation_count: 0,
+        twap_window_seconds: 0,    };
     let escrow_id = s.client.create_escrow_v2(&buyer, &request);
 
     s.client.dispute_escrow(&buyer, &escrow_id, &String::from_str(&s.env, "dispute"), &1000);
@@ -3068,7 +3070,9 @@ fn test_arbiter_fee_deducted_from_loser_buyer_wins() {
         dispute_default_winner: None,
         auto_renew_max_renewals: None,
 
-        auto_renew_interval_ledgers: None,    };
+        auto_renew_interval_ledgers: None,
+        twap_observation_count: 0,
+        twap_window_seconds: 0,    };
     let escrow_id = s.client.create_escrow_v2(&buyer, &request);
 
     s.client.dispute_escrow(&buyer, &escrow_id, &String::from_str(&s.env, "dispute"), &1000);
@@ -3111,7 +3115,9 @@ fn test_arbiter_fee_zero_is_valid() {
         dispute_default_winner: None,
         auto_renew_max_renewals: None,
 
-        auto_renew_interval_ledgers: None,    };
+        auto_renew_interval_ledgers: None,
+        twap_observation_count: 0,
+        twap_window_seconds: 0,    };
     let escrow_id = s.client.create_escrow_v2(&buyer, &request);
 
     s.client.dispute_escrow(&buyer, &escrow_id, &String::from_str(&s.env, "dispute"), &1000);
@@ -3151,7 +3157,9 @@ fn test_arbiter_fee_cap_enforced_at_creation() {
         dispute_default_winner: None,
         auto_renew_max_renewals: None,
 
-        auto_renew_interval_ledgers: None,    };
+        auto_renew_interval_ledgers: None,
+        twap_observation_count: 0,
+        twap_window_seconds: 0,    };
     let result = s.client.try_create_escrow_v2(&buyer, &request);
     assert!(result.is_err());
 }
@@ -3212,7 +3220,9 @@ fn test_arbiter_fee_emits_event() {
         dispute_default_winner: None,
         auto_renew_max_renewals: None,
 
-        auto_renew_interval_ledgers: None,    };
+        auto_renew_interval_ledgers: None,
+        twap_observation_count: 0,
+        twap_window_seconds: 0,    };
     let escrow_id = s.client.create_escrow_v2(&buyer, &request);
 
     s.client.dispute_escrow(&buyer, &escrow_id, &String::from_str(&s.env, "dispute"), &1000);
@@ -3261,7 +3271,9 @@ fn test_release_before_min_lock_until_panics() {
             dispute_default_winner: None,
             auto_renew_max_renewals: None,
 
-            auto_renew_interval_ledgers: None,    };
+            auto_renew_interval_ledgers: None,
+            twap_observation_count: 0,
+            twap_window_seconds: 0,    };
     let escrow_id = s.client.create_escrow_v2(&buyer, &request);
 
     // Still before lock_until
@@ -3303,7 +3315,9 @@ fn test_release_at_min_lock_until_succeeds() {
             dispute_default_winner: None,
             auto_renew_max_renewals: None,
 
-            auto_renew_interval_ledgers: None,    };
+            auto_renew_interval_ledgers: None,
+            twap_observation_count: 0,
+            twap_window_seconds: 0,    };
     let escrow_id = s.client.create_escrow_v2(&buyer, &request);
 
     // Exactly at lock_until
@@ -3348,7 +3362,9 @@ fn test_release_after_min_lock_until_succeeds() {
             dispute_default_winner: None,
             auto_renew_max_renewals: None,
 
-            auto_renew_interval_ledgers: None,    };
+            auto_renew_interval_ledgers: None,
+            twap_observation_count: 0,
+            twap_window_seconds: 0,    };
     let escrow_id = s.client.create_escrow_v2(&buyer, &request);
 
     s.env.ledger().set_timestamp(2500);
@@ -3390,7 +3406,9 @@ fn test_dispute_not_blocked_by_lock() {
             dispute_default_winner: None,
             auto_renew_max_renewals: None,
 
-            auto_renew_interval_ledgers: None,    };
+            auto_renew_interval_ledgers: None,
+            twap_observation_count: 0,
+            twap_window_seconds: 0,    };
     let escrow_id = s.client.create_escrow_v2(&buyer, &request);
 
     // Still locked, but dispute should work
@@ -3434,7 +3452,9 @@ fn test_lock_and_deadline_independently_configurable() {
             dispute_default_winner: None,
             auto_renew_max_renewals: None,
 
-            auto_renew_interval_ledgers: None,    };
+            auto_renew_interval_ledgers: None,
+            twap_observation_count: 0,
+            twap_window_seconds: 0,    };
     let escrow_id = s.client.create_escrow_v2(&buyer, &request);
 
     let escrow = s.client.get_escrow(&escrow_id);
@@ -3475,7 +3495,9 @@ fn test_time_locked_escrow_emits_event() {
             dispute_default_winner: None,
             auto_renew_max_renewals: None,
 
-            auto_renew_interval_ledgers: None,    };
+            auto_renew_interval_ledgers: None,
+            twap_observation_count: 0,
+            twap_window_seconds: 0,    };
     s.client.create_escrow_v2(&buyer, &request);
 
     let events = s.env.events().all();
@@ -3515,7 +3537,9 @@ fn test_deadline_must_be_after_lock_until() {
             dispute_default_winner: None,
             auto_renew_max_renewals: None,
 
-            auto_renew_interval_ledgers: None,    };
+            auto_renew_interval_ledgers: None,
+            twap_observation_count: 0,
+            twap_window_seconds: 0,    };
     let result = s.client.try_create_escrow_v2(&buyer, &request);
     assert!(result.is_err());
 }
@@ -3642,7 +3666,9 @@ fn test_oracle_release_triggers_when_price_below_threshold() {
             dispute_default_winner: None,
             auto_renew_max_renewals: None,
 
-            auto_renew_interval_ledgers: None,    };
+            auto_renew_interval_ledgers: None,
+            twap_observation_count: 0,
+            twap_window_seconds: 0,    };
     let escrow_id = s.client.create_escrow_v2(&buyer, &request);
 
     // Price = 400 <= 500 → condition met
@@ -3686,7 +3712,9 @@ fn test_oracle_release_does_not_trigger_when_price_above_threshold() {
             dispute_default_winner: None,
             auto_renew_max_renewals: None,
 
-            auto_renew_interval_ledgers: None,    };
+            auto_renew_interval_ledgers: None,
+            twap_observation_count: 0,
+            twap_window_seconds: 0,    };
     let escrow_id = s.client.create_escrow_v2(&buyer, &request);
 
     // Price = 600 > 500 → condition NOT met
@@ -3730,7 +3758,9 @@ fn test_oracle_release_triggers_when_price_at_threshold() {
             dispute_default_winner: None,
             auto_renew_max_renewals: None,
 
-            auto_renew_interval_ledgers: None,    };
+            auto_renew_interval_ledgers: None,
+            twap_observation_count: 0,
+            twap_window_seconds: 0,    };
     let escrow_id = s.client.create_escrow_v2(&buyer, &request);
 
     // Price exactly at threshold = 500 → condition met (<=)
@@ -3773,7 +3803,9 @@ fn test_oracle_release_greater_or_equal_condition() {
             dispute_default_winner: None,
             auto_renew_max_renewals: None,
 
-            auto_renew_interval_ledgers: None,    };
+            auto_renew_interval_ledgers: None,
+            twap_observation_count: 0,
+            twap_window_seconds: 0,    };
     let escrow_id = s.client.create_escrow_v2(&buyer, &request);
 
     // Price = 1200 >= 1000 → condition met
@@ -3816,7 +3848,9 @@ fn test_oracle_stale_price_blocks_release() {
             dispute_default_winner: None,
             auto_renew_max_renewals: None,
 
-            auto_renew_interval_ledgers: None,    };
+            auto_renew_interval_ledgers: None,
+            twap_observation_count: 0,
+            twap_window_seconds: 0,    };
     let escrow_id = s.client.create_escrow_v2(&buyer, &request);
 
     // Set price at ts=0, advance ledger to ts=500 → age=500 > max_oracle_age(300)
@@ -3862,7 +3896,9 @@ fn test_manual_release_works_regardless_of_oracle_condition() {
             dispute_default_winner: None,
             auto_renew_max_renewals: None,
 
-            auto_renew_interval_ledgers: None,    };
+            auto_renew_interval_ledgers: None,
+            twap_observation_count: 0,
+            twap_window_seconds: 0,    };
     let escrow_id = s.client.create_escrow_v2(&buyer, &request);
 
     // Manual release by buyer still works regardless of oracle
@@ -3905,7 +3941,9 @@ fn test_oracle_release_emits_event() {
             dispute_default_winner: None,
             auto_renew_max_renewals: None,
 
-            auto_renew_interval_ledgers: None,    };
+            auto_renew_interval_ledgers: None,
+            twap_observation_count: 0,
+            twap_window_seconds: 0,    };
     let escrow_id = s.client.create_escrow_v2(&buyer, &request);
 
     set_escrow_oracle_price(&s, 300, 200);
@@ -4013,7 +4051,7 @@ fn test_milestone_full_release_transitions_to_released() {
 }
 
 #[test]
-#[should_panic(expected = "Milestone not pending")]
+#[should_panic(expected = "Error(Contract, #1204)")] // MilestoneNotPending
 fn test_double_approve_milestone_panics() {
     let s = setup();
     let buyer = Address::generate(&s.env);
@@ -4036,7 +4074,7 @@ fn test_double_approve_milestone_panics() {
 }
 
 #[test]
-#[should_panic(expected = "Only buyer or arbiter can approve milestones")]
+#[should_panic(expected = "Error(Contract, #1024)")] // NotBuyerOrArbiter
 fn test_seller_cannot_approve_own_milestone() {
     let s = setup();
     let buyer = Address::generate(&s.env);
@@ -4058,7 +4096,7 @@ fn test_seller_cannot_approve_own_milestone() {
 }
 
 #[test]
-#[should_panic(expected = "Milestone amount must be positive")]
+#[should_panic(expected = "Error(Contract, #1009)")] // InvalidAmount
 fn test_zero_milestone_amount_rejected() {
     let s = setup();
     let buyer = Address::generate(&s.env);
@@ -4078,7 +4116,7 @@ fn test_zero_milestone_amount_rejected() {
 }
 
 #[test]
-#[should_panic(expected = "At least one milestone required")]
+#[should_panic(expected = "Error(Contract, #1201)")] // MilestoneRequired
 fn test_empty_milestones_rejected() {
     let s = setup();
     let buyer = Address::generate(&s.env);
@@ -4098,7 +4136,7 @@ fn test_empty_milestones_rejected() {
 }
 
 #[test]
-#[should_panic(expected = "Milestone index out of range")]
+#[should_panic(expected = "Error(Contract, #1203)")] // MilestoneIndexOutOfRange
 fn test_milestone_out_of_range_rejected() {
     let s = setup();
     let buyer = Address::generate(&s.env);
@@ -4204,7 +4242,7 @@ fn test_multiple_top_ups() {
 }
 
 #[test]
-#[should_panic(expected = "Top-up limit exceeded")]
+#[should_panic(expected = "Error(Contract, #1033)")] // TopUpLimitExceeded
 fn test_top_up_limit_exceeded() {
     let s = setup();
     let buyer = Address::generate(&s.env);
@@ -4231,7 +4269,7 @@ fn test_top_up_limit_exceeded() {
 }
 
 #[test]
-#[should_panic(expected = "Escrow is not active or awaiting inspection")]
+#[should_panic(expected = "Error(Contract, #1008)")] // InvalidEscrowStatus
 fn test_top_up_after_release_rejected() {
     let s = setup();
     let buyer = Address::generate(&s.env);
@@ -4399,7 +4437,7 @@ fn test_partial_release_reject() {
 }
 
 #[test]
-#[should_panic(expected = "Request already pending")]
+#[should_panic(expected = "Error(Contract, #1316)")] // PartialReleasePending
 fn test_duplicate_partial_release() {
     let s = setup();
     let buyer = Address::generate(&s.env);
@@ -4467,7 +4505,7 @@ fn test_partial_release_escalate() {
 }
 
 #[test]
-#[should_panic(expected = "Partial release only allowed on active escrow")]
+#[should_panic(expected = "Error(Contract, #1007)")] // EscrowNotActive
 fn test_partial_release_non_active() {
     let s = setup();
     let buyer = Address::generate(&s.env);
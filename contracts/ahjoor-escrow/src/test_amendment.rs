@@ -0,0 +1,390 @@
+#![cfg(test)]
+use super::*;
+use soroban_sdk::token::Client as TokenClient;
+use soroban_sdk::token::StellarAssetClient as TokenAdminClient;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger, MockAuth, MockAuthInvoke},
+    vec, Address, BytesN, Env, IntoVal, Vec,
+};
+
+struct AmendmentSetup<'a> {
+    env: Env,
+    client: AhjoorEscrowContractClient<'a>,
+    admin: Address,
+    buyer: Address,
+    seller: Address,
+    arbiter: Address,
+    token_addr: Address,
+    token_client: TokenClient<'a>,
+}
+
+fn setup_amendment<'a>() -> AmendmentSetup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AhjoorEscrowContract, ());
+    let client = AhjoorEscrowContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let token_addr = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    let token_client = TokenClient::new(&env, &token_addr);
+    TokenAdminClient::new(&env, &token_addr).mint(&buyer, &1000);
+
+    client.initialize(&admin);
+    client.add_allowed_token(&admin, &token_addr);
+
+    AmendmentSetup {
+        env,
+        client,
+        admin,
+        buyer,
+        seller,
+        arbiter,
+        token_addr,
+        token_client,
+    }
+}
+
+fn create_plain_escrow(s: &AmendmentSetup) -> u32 {
+    let deadline = s.env.ledger().timestamp() + 100_000;
+    s.client.create_escrow(
+        &s.buyer,
+        &s.seller,
+        &s.arbiter,
+        &500,
+        &s.token_addr,
+        &deadline,
+        &None,
+        &Vec::new(&s.env),
+        &false,
+        &0u32,
+    )
+}
+
+fn milestone(env: &Env, tag: u8, amount: i128) -> Milestone {
+    Milestone {
+        description_hash: BytesN::from_array(env, &[tag; 32]),
+        amount,
+        status: MilestoneStatus::Pending,
+    }
+}
+
+#[test]
+fn test_accepted_increase_pulls_top_up_and_records_version() {
+    let s = setup_amendment();
+    let escrow_id = create_plain_escrow(&s);
+    let new_deadline = s.env.ledger().timestamp() + 200_000;
+
+    let nonce = s.client.propose_amendment(
+        &s.seller,
+        &escrow_id,
+        &Some(700),
+        &Some(new_deadline),
+        &None,
+        &None,
+        &None,
+    );
+    let version = s.client.accept_amendment(&s.buyer, &escrow_id, &nonce);
+    assert_eq!(version, 1);
+
+    let escrow = s.client.get_escrow(&escrow_id);
+    assert_eq!(escrow.amount, 700);
+    assert_eq!(escrow.deadline, new_deadline);
+    assert_eq!(s.token_client.balance(&s.buyer), 300);
+    assert_eq!(s.token_client.balance(&s.client.address), 700);
+
+    let history = s.client.get_amendment_history(&escrow_id);
+    assert_eq!(history.len(), 1);
+    let record = history.get(0).unwrap();
+    assert_eq!(record.old_amount, 500);
+    assert_eq!(record.new_amount, 700);
+    assert_eq!(record.proposer, s.seller);
+    assert_eq!(record.accepted_by, s.buyer);
+    assert!(s.client.get_amendment_proposal(&escrow_id).is_none());
+}
+
+#[test]
+fn test_accepted_decrease_refunds_buyer_and_swaps_arbiter() {
+    let s = setup_amendment();
+    let escrow_id = create_plain_escrow(&s);
+    let new_arbiter = Address::generate(&s.env);
+
+    let nonce = s.client.propose_amendment(
+        &s.buyer,
+        &escrow_id,
+        &Some(350),
+        &None,
+        &None,
+        &None,
+        &Some(new_arbiter.clone()),
+    );
+    s.client.accept_amendment(&s.seller, &escrow_id, &nonce);
+
+    let escrow = s.client.get_escrow(&escrow_id);
+    assert_eq!(escrow.amount, 350);
+    assert_eq!(escrow.arbiter, new_arbiter);
+    assert_eq!(s.token_client.balance(&s.buyer), 650);
+    assert_eq!(s.token_client.balance(&s.client.address), 350);
+
+    // A second amendment gets the next version
+    let nonce = s.client.propose_amendment(
+        &s.seller,
+        &escrow_id,
+        &None,
+        &None,
+        &Some(BytesN::from_array(&s.env, &[9u8; 32])),
+        &None,
+        &None,
+    );
+    assert_eq!(s.client.accept_amendment(&s.buyer, &escrow_id, &nonce), 2);
+    assert_eq!(s.client.get_amendment_history(&escrow_id).len(), 2);
+}
+
+#[test]
+fn test_milestone_amendment_keeps_approved_and_replaces_pending() {
+    let s = setup_amendment();
+    let deadline = s.env.ledger().timestamp() + 100_000;
+    let escrow_id = s.client.create_milestone_escrow(
+        &s.buyer,
+        &s.seller,
+        &s.arbiter,
+        &s.token_addr,
+        &deadline,
+        &vec![&s.env, milestone(&s.env, 1, 200), milestone(&s.env, 2, 300)],
+    );
+    s.client.approve_milestone(&s.buyer, &escrow_id, &0);
+
+    let nonce = s.client.propose_amendment(
+        &s.seller,
+        &escrow_id,
+        &None,
+        &None,
+        &None,
+        &Some(vec![
+            &s.env,
+            milestone(&s.env, 3, 150),
+            milestone(&s.env, 4, 250),
+        ]),
+        &None,
+    );
+    s.client.accept_amendment(&s.buyer, &escrow_id, &nonce);
+
+    let milestones = s.client.get_milestones(&escrow_id);
+    assert_eq!(milestones.len(), 3);
+    assert_eq!(milestones.get(0).unwrap().status, MilestoneStatus::Approved);
+    assert_eq!(milestones.get(1).unwrap().amount, 150);
+    assert_eq!(s.client.get_escrow(&escrow_id).amount, 600);
+    // 500 deposited at creation plus the 100 top-up
+    assert_eq!(s.token_client.balance(&s.buyer), 400);
+    assert!(s.client.get_amendment_history(&escrow_id).get(0).unwrap().milestones_changed);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1207)")] // AmendMilestonesInstead
+fn test_milestone_escrow_rejects_bare_amount_change() {
+    let s = setup_amendment();
+    let deadline = s.env.ledger().timestamp() + 100_000;
+    let escrow_id = s.client.create_milestone_escrow(
+        &s.buyer,
+        &s.seller,
+        &s.arbiter,
+        &s.token_addr,
+        &deadline,
+        &vec![&s.env, milestone(&s.env, 1, 500)],
+    );
+
+    s.client
+        .propose_amendment(&s.buyer, &escrow_id, &Some(600), &None, &None, &None, &None);
+}
+
+#[test]
+fn test_rejected_amendment_leaves_escrow_untouched() {
+    let s = setup_amendment();
+    let escrow_id = create_plain_escrow(&s);
+
+    let nonce =
+        s.client
+            .propose_amendment(&s.buyer, &escrow_id, &Some(100), &None, &None, &None, &None);
+    s.client.reject_amendment(&s.seller, &escrow_id, &nonce);
+
+    assert!(s.client.get_amendment_proposal(&escrow_id).is_none());
+    assert_eq!(s.client.get_escrow(&escrow_id).amount, 500);
+    assert_eq!(s.client.get_amendment_history(&escrow_id).len(), 0);
+    assert!(s
+        .client
+        .try_accept_amendment(&s.seller, &escrow_id, &nonce)
+        .is_err());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1307)")] // CannotAcceptOwnProposal
+fn test_proposer_cannot_accept_own_amendment() {
+    let s = setup_amendment();
+    let escrow_id = create_plain_escrow(&s);
+
+    let nonce =
+        s.client
+            .propose_amendment(&s.buyer, &escrow_id, &Some(100), &None, &None, &None, &None);
+    s.client.accept_amendment(&s.buyer, &escrow_id, &nonce);
+}
+
+#[test]
+fn test_expired_amendment_cannot_be_accepted_and_can_be_cleared() {
+    let s = setup_amendment();
+    let escrow_id = create_plain_escrow(&s);
+    s.client.set_amendment_expiry(&s.admin, &3_600);
+
+    let nonce =
+        s.client
+            .propose_amendment(&s.buyer, &escrow_id, &Some(100), &None, &None, &None, &None);
+    // A live proposal blocks a new one
+    assert!(s
+        .client
+        .try_propose_amendment(&s.seller, &escrow_id, &Some(200), &None, &None, &None, &None)
+        .is_err());
+    assert!(s.client.try_expire_amendment(&escrow_id).is_err());
+
+    s.env.ledger().with_mut(|l| l.timestamp += 3_601);
+    assert!(s
+        .client
+        .try_accept_amendment(&s.seller, &escrow_id, &nonce)
+        .is_err());
+
+    s.client.expire_amendment(&escrow_id);
+    assert!(s.client.get_amendment_proposal(&escrow_id).is_none());
+
+    let next =
+        s.client
+            .propose_amendment(&s.seller, &escrow_id, &Some(200), &None, &None, &None, &None);
+    assert_eq!(next, nonce + 1);
+}
+
+#[test]
+fn test_top_up_pays_protocol_fee() {
+    let s = setup_amendment();
+    let escrow_id = create_plain_escrow(&s);
+    let fee_recipient = Address::generate(&s.env);
+    s.client.update_protocol_fee(&s.admin, &200, &fee_recipient);
+
+    let nonce =
+        s.client
+            .propose_amendment(&s.seller, &escrow_id, &Some(700), &None, &None, &None, &None);
+    s.client.accept_amendment(&s.buyer, &escrow_id, &nonce);
+
+    // 2% of the 200 top-up goes to the fee recipient; the escrow holds the full 700
+    assert_eq!(s.token_client.balance(&fee_recipient), 4);
+    assert_eq!(s.token_client.balance(&s.buyer), 296);
+    assert_eq!(s.token_client.balance(&s.client.address), 700);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1133)")] // StakeBelowMinimum
+fn test_new_arbiter_must_meet_minimum_stake() {
+    let s = setup_amendment();
+    let escrow_id = create_plain_escrow(&s);
+    s.client.set_arbiter_stake_config(&s.admin, &s.token_addr, &100, &0, &3_600);
+
+    let new_arbiter = Address::generate(&s.env);
+    s.client.propose_amendment(
+        &s.buyer,
+        &escrow_id,
+        &None,
+        &None,
+        &None,
+        &None,
+        &Some(new_arbiter),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1206)")] // InvalidMilestones
+fn test_milestone_amendment_rejects_duplicate_milestones() {
+    let s = setup_amendment();
+    let deadline = s.env.ledger().timestamp() + 100_000;
+    let escrow_id = s.client.create_milestone_escrow(
+        &s.buyer,
+        &s.seller,
+        &s.arbiter,
+        &s.token_addr,
+        &deadline,
+        &vec![&s.env, milestone(&s.env, 1, 200), milestone(&s.env, 2, 300)],
+    );
+    s.client.approve_milestone(&s.buyer, &escrow_id, &0);
+
+    // Milestone 1 is already approved and kept, so a new pending milestone 1 collides
+    s.client.propose_amendment(
+        &s.seller,
+        &escrow_id,
+        &None,
+        &None,
+        &None,
+        &Some(vec![&s.env, milestone(&s.env, 1, 300)]),
+        &None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1346)")] // TopUpNeedsBuyerAcceptance
+fn test_buyer_cannot_propose_amount_increase() {
+    let s = setup_amendment();
+    let escrow_id = create_plain_escrow(&s);
+
+    s.client
+        .propose_amendment(&s.buyer, &escrow_id, &Some(700), &None, &None, &None, &None);
+}
+
+#[test]
+fn test_seller_accepts_decrease_with_only_seller_auth() {
+    let s = setup_amendment();
+    let escrow_id = create_plain_escrow(&s);
+    let nonce =
+        s.client
+            .propose_amendment(&s.buyer, &escrow_id, &Some(300), &None, &None, &None, &None);
+
+    // Only the seller signs; the refund comes out of the contract's own balance
+    s.env.mock_auths(&[MockAuth {
+        address: &s.seller,
+        invoke: &MockAuthInvoke {
+            contract: &s.client.address,
+            fn_name: "accept_amendment",
+            args: (&s.seller, escrow_id, nonce).into_val(&s.env),
+            sub_invokes: &[],
+        },
+    }]);
+    s.client.accept_amendment(&s.seller, &escrow_id, &nonce);
+
+    assert_eq!(s.client.get_escrow(&escrow_id).amount, 300);
+    assert_eq!(s.token_client.balance(&s.buyer), 700);
+}
+
+#[test]
+fn test_buyer_funds_increase_with_own_auth() {
+    let s = setup_amendment();
+    let escrow_id = create_plain_escrow(&s);
+    let nonce =
+        s.client
+            .propose_amendment(&s.seller, &escrow_id, &Some(700), &None, &None, &None, &None);
+
+    // The buyer's acceptance covers the top-up transfer it triggers
+    s.env.mock_auths(&[MockAuth {
+        address: &s.buyer,
+        invoke: &MockAuthInvoke {
+            contract: &s.client.address,
+            fn_name: "accept_amendment",
+            args: (&s.buyer, escrow_id, nonce).into_val(&s.env),
+            sub_invokes: &[MockAuthInvoke {
+                contract: &s.token_addr,
+                fn_name: "transfer",
+                args: (&s.buyer, &s.client.address, 200i128).into_val(&s.env),
+                sub_invokes: &[],
+            }],
+        },
+    }]);
+    s.client.accept_amendment(&s.buyer, &escrow_id, &nonce);
+
+    assert_eq!(s.client.get_escrow(&escrow_id).amount, 700);
+    assert_eq!(s.token_client.balance(&s.client.address), 700);
+}
@@ -0,0 +1,308 @@
+#![cfg(test)]
+use super::*;
+use soroban_sdk::token::Client as TokenClient;
+use soroban_sdk::token::StellarAssetClient as TokenAdminClient;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, BytesN, Env, String, Vec,
+};
+
+const UNBONDING_SECS: u64 = 1_000;
+
+struct StakingSetup<'a> {
+    env: Env,
+    client: AhjoorEscrowContractClient<'a>,
+    admin: Address,
+    buyer: Address,
+    seller: Address,
+    arbiter: Address,
+    token_addr: Address,
+    token_client: TokenClient<'a>,
+}
+
+/// Staking requires 500 of the escrow token, slashes 20% and unbonds over 1_000 seconds.
+/// The arbiter holds 1_000 tokens, the buyer 1_000.
+fn setup_staking<'a>() -> StakingSetup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AhjoorEscrowContract, ());
+    let client = AhjoorEscrowContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let token_addr = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    let token_client = TokenClient::new(&env, &token_addr);
+    let token_admin = TokenAdminClient::new(&env, &token_addr);
+    token_admin.mint(&buyer, &1_000);
+    token_admin.mint(&arbiter, &1_000);
+
+    client.initialize(&admin);
+    client.add_allowed_token(&admin, &token_addr);
+    client.set_default_arbiter_fee_bps(&admin, &1_000);
+    client.set_arbiter_stake_config(&admin, &token_addr, &500, &2_000, &UNBONDING_SECS);
+
+    StakingSetup {
+        env,
+        client,
+        admin,
+        buyer,
+        seller,
+        arbiter,
+        token_addr,
+        token_client,
+    }
+}
+
+/// Stake `amount` and have the admin approve the arbiter into the pool.
+fn join_pool(s: &StakingSetup, amount: i128) {
+    s.client.stake_as_arbiter(&s.arbiter, &amount);
+    s.client.add_arbiter(&s.admin, &s.arbiter);
+}
+
+fn disputed_pool_escrow(s: &StakingSetup) -> u32 {
+    let deadline = s.env.ledger().timestamp() + 10_000;
+    let escrow_id = s.client.create_escrow_with_pool_arbiter(
+        &s.buyer,
+        &s.seller,
+        &1_000,
+        &s.token_addr,
+        &deadline,
+    );
+    s.client.dispute_escrow(
+        &s.buyer,
+        &escrow_id,
+        &String::from_str(&s.env, "not delivered"),
+        &1_000,
+    );
+    escrow_id
+}
+
+#[test]
+fn test_staking_alone_does_not_join_pool() {
+    let s = setup_staking();
+
+    assert_eq!(s.client.stake_as_arbiter(&s.arbiter, &600), 600);
+    assert!(!s.client.get_arbiter_pool().contains(&s.arbiter));
+    assert_eq!(s.token_client.balance(&s.arbiter), 400);
+
+    s.client.add_arbiter(&s.admin, &s.arbiter);
+    assert!(s.client.get_arbiter_pool().contains(&s.arbiter));
+}
+
+#[test]
+fn test_approved_arbiter_rejoins_pool_on_restake() {
+    let s = setup_staking();
+    join_pool(&s, 500);
+
+    s.client.unbond_arbiter_stake(&s.arbiter, &100);
+    assert!(!s.client.get_arbiter_pool().contains(&s.arbiter));
+
+    s.client.stake_as_arbiter(&s.arbiter, &300);
+    assert!(s.client.get_arbiter_pool().contains(&s.arbiter));
+    assert_eq!(s.client.get_arbiter_pool().len(), 1);
+}
+
+#[test]
+fn test_removed_arbiter_cannot_restake_into_pool() {
+    let s = setup_staking();
+    join_pool(&s, 500);
+
+    s.client.remove_arbiter(&s.admin, &s.arbiter, &Vec::new(&s.env));
+    s.client.stake_as_arbiter(&s.arbiter, &100);
+    assert!(!s.client.get_arbiter_pool().contains(&s.arbiter));
+
+    // Only a fresh admin approval brings them back
+    s.client.add_arbiter(&s.admin, &s.arbiter);
+    assert!(s.client.get_arbiter_pool().contains(&s.arbiter));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1133)")] // StakeBelowMinimum
+fn test_admin_cannot_add_unstaked_arbiter() {
+    let s = setup_staking();
+    s.client.add_arbiter(&s.admin, &Address::generate(&s.env));
+}
+
+#[test]
+fn test_resolved_disputes_accrue_arbiter_fees() {
+    let s = setup_staking();
+    join_pool(&s, 500);
+    let escrow_id = disputed_pool_escrow(&s);
+
+    s.client.resolve_dispute(&s.arbiter, &escrow_id, &100);
+
+    assert_eq!(
+        s.client.get_arbiter_fees_earned(&s.arbiter, &s.token_addr),
+        100
+    );
+    assert_eq!(s.token_client.balance(&s.arbiter), 600);
+}
+
+#[test]
+fn test_dispute_timeout_slashes_stake_to_losing_party() {
+    let s = setup_staking();
+    join_pool(&s, 1_000);
+    let escrow_id = disputed_pool_escrow(&s);
+
+    s.env
+        .ledger()
+        .with_mut(|l| l.timestamp += DEFAULT_DISPUTE_TIMEOUT_SECONDS + 1);
+    s.client
+        .enforce_dispute_timeout(&Address::generate(&s.env), &escrow_id);
+
+    // Buyer wins by default; the seller never got a ruling and receives 20% of the stake
+    assert_eq!(s.token_client.balance(&s.buyer), 1_000);
+    assert_eq!(s.token_client.balance(&s.seller), 200);
+    assert_eq!(s.client.get_arbiter_stake(&s.arbiter).unwrap().amount, 800);
+    assert!(s.client.get_arbiter_pool().contains(&s.arbiter));
+}
+
+#[test]
+fn test_overturned_ruling_slashes_arbiter_and_forfeits_fee() {
+    let s = setup_staking();
+    s.client.set_resolution_cooloff_secs(&s.admin, &3_600);
+    join_pool(&s, 500);
+    let escrow_id = disputed_pool_escrow(&s);
+
+    s.client.resolve_dispute(&s.arbiter, &escrow_id, &0);
+    s.client
+        .flag_resolution_error(&s.buyer, &escrow_id, &BytesN::from_array(&s.env, &[1u8; 32]));
+    s.client.overturn_resolution(&s.admin, &escrow_id, &100);
+
+    s.env.ledger().with_mut(|l| l.timestamp += 3_601);
+    s.client.finalize_resolution(&escrow_id);
+
+    assert_eq!(s.client.get_escrow(&escrow_id).status, EscrowStatus::Refunded);
+    // Full refund with no arbiter fee, plus 20% of the 500 stake
+    assert_eq!(s.token_client.balance(&s.buyer), 1_100);
+    assert_eq!(s.client.get_arbiter_fees_earned(&s.arbiter, &s.token_addr), 0);
+    // Stake fell below the minimum, so the arbiter leaves the pool
+    assert_eq!(s.client.get_arbiter_stake(&s.arbiter).unwrap().amount, 400);
+    assert!(!s.client.get_arbiter_pool().contains(&s.arbiter));
+}
+
+#[test]
+fn test_unbonding_delay_gates_withdrawal() {
+    let s = setup_staking();
+    s.client.stake_as_arbiter(&s.arbiter, &800);
+
+    let available_at = s.client.unbond_arbiter_stake(&s.arbiter, &400);
+    assert_eq!(available_at, s.env.ledger().timestamp() + UNBONDING_SECS);
+    assert!(!s.client.get_arbiter_pool().contains(&s.arbiter));
+    assert!(s.client.try_withdraw_arbiter_stake(&s.arbiter).is_err());
+
+    s.env.ledger().with_mut(|l| l.timestamp = available_at);
+    assert_eq!(s.client.withdraw_arbiter_stake(&s.arbiter), 400);
+    assert_eq!(s.token_client.balance(&s.arbiter), 600);
+
+    let stake = s.client.get_arbiter_stake(&s.arbiter).unwrap();
+    assert_eq!(stake.amount, 400);
+    assert!(stake.unbonding.is_empty());
+}
+
+#[test]
+fn test_unbonding_stake_remains_slashable() {
+    let s = setup_staking();
+    join_pool(&s, 1_000);
+    let escrow_id = disputed_pool_escrow(&s);
+    s.client.unbond_arbiter_stake(&s.arbiter, &1_000);
+
+    s.env
+        .ledger()
+        .with_mut(|l| l.timestamp += DEFAULT_DISPUTE_TIMEOUT_SECONDS + 1);
+    s.client
+        .enforce_dispute_timeout(&Address::generate(&s.env), &escrow_id);
+
+    let stake = s.client.get_arbiter_stake(&s.arbiter).unwrap();
+    assert_eq!(stake.unbonding.get(0).unwrap().amount, 800);
+    assert_eq!(s.client.withdraw_arbiter_stake(&s.arbiter), 800);
+}
+
+#[test]
+fn test_each_unbonding_request_keeps_its_own_delay() {
+    let s = setup_staking();
+    s.client.stake_as_arbiter(&s.arbiter, &800);
+
+    let first = s.client.unbond_arbiter_stake(&s.arbiter, &300);
+    s.env.ledger().with_mut(|l| l.timestamp += UNBONDING_SECS / 2);
+    let second = s.client.unbond_arbiter_stake(&s.arbiter, &200);
+    assert!(second > first);
+
+    // The second request does not push back the first
+    s.env.ledger().with_mut(|l| l.timestamp = first);
+    assert_eq!(s.client.withdraw_arbiter_stake(&s.arbiter), 300);
+    assert_eq!(
+        s.client.try_withdraw_arbiter_stake(&s.arbiter),
+        Err(Ok(soroban_sdk::Error::from(DisputeError::StakeStillUnbonding)))
+    );
+
+    s.env.ledger().with_mut(|l| l.timestamp = second);
+    assert_eq!(s.client.withdraw_arbiter_stake(&s.arbiter), 200);
+    assert_eq!(s.token_client.balance(&s.arbiter), 700);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1133)")] // StakeBelowMinimum
+fn test_unstaked_arbiter_cannot_be_assigned() {
+    let s = setup_staking();
+    let deadline = s.env.ledger().timestamp() + 10_000;
+    s.client.create_escrow(
+        &s.buyer,
+        &s.seller,
+        &s.arbiter,
+        &1_000,
+        &s.token_addr,
+        &deadline,
+        &None,
+        &Vec::new(&s.env),
+        &false,
+        &0u32,
+    );
+}
+
+#[test]
+fn test_arbiter_below_minimum_stake_cannot_resolve() {
+    let s = setup_staking();
+    join_pool(&s, 500);
+    let escrow_id = disputed_pool_escrow(&s);
+
+    // Dropping below the minimum takes the arbiter off disputes it was already assigned
+    s.client.unbond_arbiter_stake(&s.arbiter, &100);
+    assert_eq!(
+        s.client.try_resolve_dispute(&s.arbiter, &escrow_id, &100),
+        Err(Ok(soroban_sdk::Error::from(DisputeError::StakeBelowMinimum)))
+    );
+}
+
+#[test]
+fn test_overturned_panel_slashes_only_voters_far_from_final_split() {
+    let s = setup_staking();
+    s.client.set_resolution_cooloff_secs(&s.admin, &3_600);
+    let token_admin = TokenAdminClient::new(&s.env, &s.token_addr);
+    for _ in 0..4 {
+        let arbiter = Address::generate(&s.env);
+        token_admin.mint(&arbiter, &500);
+        s.client.stake_as_arbiter(&arbiter, &500);
+        s.client.add_arbiter(&s.admin, &arbiter);
+    }
+    let escrow_id = disputed_pool_escrow(&s);
+    let members = s.client.request_arbitration_panel(&s.buyer, &escrow_id, &3);
+    let (wide, close) = (members.get(0).unwrap(), members.get(1).unwrap());
+
+    // Median of 0 and 4_800 is a 24% buyer split, overturned to 50%
+    s.client.submit_panel_vote(&wide, &escrow_id, &0);
+    s.client.submit_panel_vote(&close, &escrow_id, &4_800);
+    s.client
+        .flag_resolution_error(&s.buyer, &escrow_id, &BytesN::from_array(&s.env, &[1u8; 32]));
+    s.client.overturn_resolution(&s.admin, &escrow_id, &50);
+    s.env.ledger().with_mut(|l| l.timestamp += 3_601);
+    s.client.finalize_resolution(&escrow_id);
+
+    // 4_800 is within the tolerance of 5_000; only the 0 vote is slashed
+    assert_eq!(s.client.get_arbiter_stake(&wide).unwrap().amount, 400);
+    assert_eq!(s.client.get_arbiter_stake(&close).unwrap().amount, 500);
+    assert_eq!(s.token_client.balance(&s.buyer), 600);
+}
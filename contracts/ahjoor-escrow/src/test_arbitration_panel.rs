@@ -0,0 +1,274 @@
+#![cfg(test)]
+use super::*;
+use soroban_sdk::token::Client as TokenClient;
+use soroban_sdk::token::StellarAssetClient as TokenAdminClient;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env, String, Vec,
+};
+
+struct PanelSetup<'a> {
+    env: Env,
+    client: AhjoorEscrowContractClient<'a>,
+    admin: Address,
+    buyer: Address,
+    seller: Address,
+    pool: Vec<Address>,
+    token_client: TokenClient<'a>,
+    escrow_id: u32,
+}
+
+/// Disputed 1_000-token escrow with a 10% arbiter fee and a pool of seven arbiters.
+fn setup_panel<'a>() -> PanelSetup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AhjoorEscrowContract, ());
+    let client = AhjoorEscrowContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let token_addr = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    let token_client = TokenClient::new(&env, &token_addr);
+    TokenAdminClient::new(&env, &token_addr).mint(&buyer, &1_000);
+
+    client.initialize(&admin);
+    client.add_allowed_token(&admin, &token_addr);
+    client.set_default_arbiter_fee_bps(&admin, &1_000);
+
+    let mut pool = Vec::new(&env);
+    for _ in 0..7 {
+        let arbiter = Address::generate(&env);
+        client.add_arbiter(&admin, &arbiter);
+        pool.push_back(arbiter);
+    }
+
+    let deadline = env.ledger().timestamp() + 10_000;
+    let escrow_id = client.create_escrow_with_pool_arbiter(&buyer, &seller, &1_000, &token_addr, &deadline);
+    client.dispute_escrow(&buyer, &escrow_id, &String::from_str(&env, "not delivered"), &1_000);
+
+    PanelSetup {
+        env,
+        client,
+        admin,
+        buyer,
+        seller,
+        pool,
+        token_client,
+        escrow_id,
+    }
+}
+
+#[test]
+fn test_majority_vote_executes_median_and_pays_only_voters() {
+    let s = setup_panel();
+    let members = s.client.request_arbitration_panel(&s.buyer, &s.escrow_id, &5);
+    assert_eq!(members.len(), 5);
+
+    s.client.submit_panel_vote(&members.get(0).unwrap(), &s.escrow_id, &9_000);
+    s.client.submit_panel_vote(&members.get(1).unwrap(), &s.escrow_id, &6_000);
+    assert_eq!(s.client.get_escrow(&s.escrow_id).status, EscrowStatus::Disputed);
+    // Third of five votes is a majority
+    s.client.submit_panel_vote(&members.get(2).unwrap(), &s.escrow_id, &7_000);
+
+    let panel = s.client.get_arbitration_panel(&s.escrow_id).unwrap();
+    assert_eq!(panel.decided_bps, Some(7_000));
+    assert_eq!(s.client.get_escrow(&s.escrow_id).status, EscrowStatus::Resolved);
+
+    // 100 fee split five ways; the two non-voters forfeit their 20 each
+    for i in 0..3 {
+        assert_eq!(s.token_client.balance(&members.get(i).unwrap()), 20);
+    }
+    assert_eq!(s.token_client.balance(&members.get(3).unwrap()), 0);
+    assert_eq!(s.token_client.balance(&members.get(4).unwrap()), 0);
+    // 940 distributed 70/30
+    assert_eq!(s.token_client.balance(&s.buyer), 658);
+    assert_eq!(s.token_client.balance(&s.seller), 282);
+}
+
+#[test]
+fn test_closed_window_uses_median_of_votes_cast() {
+    let s = setup_panel();
+    let members = s.client.request_arbitration_panel(&s.seller, &s.escrow_id, &5);
+
+    s.client.submit_panel_vote(&members.get(0).unwrap(), &s.escrow_id, &4_000);
+    s.client.submit_panel_vote(&members.get(1).unwrap(), &s.escrow_id, &6_000);
+    assert!(s.client.try_close_arbitration_panel(&s.escrow_id).is_err());
+
+    let deadline = s.client.get_arbitration_panel(&s.escrow_id).unwrap().voting_deadline;
+    s.env.ledger().with_mut(|l| l.timestamp = deadline + 1);
+    assert!(s
+        .client
+        .try_submit_panel_vote(&members.get(2).unwrap(), &s.escrow_id, &10_000)
+        .is_err());
+
+    s.client.close_arbitration_panel(&s.escrow_id);
+    assert_eq!(
+        s.client.get_arbitration_panel(&s.escrow_id).unwrap().decided_bps,
+        Some(5_000)
+    );
+    // 1_000 - 2 * 20 fee shares, split evenly
+    assert_eq!(s.token_client.balance(&s.buyer), 480);
+    assert_eq!(s.token_client.balance(&s.seller), 480);
+}
+
+#[test]
+fn test_panel_verdict_respects_cooling_off() {
+    let s = setup_panel();
+    s.client.set_resolution_cooloff_secs(&s.admin, &3_600);
+    let members = s.client.request_arbitration_panel(&s.buyer, &s.escrow_id, &3);
+
+    s.client.submit_panel_vote(&members.get(0).unwrap(), &s.escrow_id, &10_000);
+    s.client.submit_panel_vote(&members.get(1).unwrap(), &s.escrow_id, &10_000);
+    assert_eq!(s.client.get_escrow(&s.escrow_id).status, EscrowStatus::CoolingOff);
+    assert_eq!(s.token_client.balance(&s.buyer), 0);
+
+    s.env.ledger().with_mut(|l| l.timestamp += 3_601);
+    s.client.finalize_resolution(&s.escrow_id);
+
+    assert_eq!(s.client.get_escrow(&s.escrow_id).status, EscrowStatus::Refunded);
+    // 100 fee split three ways, 33 to each voter
+    assert_eq!(s.token_client.balance(&members.get(0).unwrap()), 33);
+    assert_eq!(s.token_client.balance(&members.get(2).unwrap()), 0);
+    assert_eq!(s.token_client.balance(&s.buyer), 934);
+}
+
+#[test]
+fn test_panel_is_drawn_from_pool_without_the_parties() {
+    let s = setup_panel();
+    // Make the buyer a pool arbiter too; they must never sit on their own panel
+    s.client.add_arbiter(&s.admin, &s.buyer);
+
+    let members = s.client.request_arbitration_panel(&s.buyer, &s.escrow_id, &7);
+    assert_eq!(members.len(), 7);
+    assert!(!members.contains(&s.buyer));
+    for member in members.iter() {
+        assert!(s.pool.contains(&member));
+    }
+    assert!(s
+        .client
+        .try_request_arbitration_panel(&s.seller, &s.escrow_id, &3)
+        .is_err());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1125)")] // InvalidPanelSize
+fn test_even_panel_size_is_rejected() {
+    let s = setup_panel();
+    s.client.request_arbitration_panel(&s.buyer, &s.escrow_id, &4);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1126)")] // BelowPanelThreshold
+fn test_panel_is_limited_to_high_value_escrows() {
+    let s = setup_panel();
+    s.client.set_panel_config(&s.admin, &86_400, &5_000);
+    s.client.request_arbitration_panel(&s.buyer, &s.escrow_id, &3);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1127)")] // NotPanelMember
+fn test_outsider_cannot_vote() {
+    let s = setup_panel();
+    s.client.request_arbitration_panel(&s.buyer, &s.escrow_id, &3);
+    s.client.submit_panel_vote(&Address::generate(&s.env), &s.escrow_id, &5_000);
+}
+
+#[test]
+fn test_single_arbiter_is_locked_out_until_panel_lapses() {
+    let s = setup_panel();
+    let arbiter = s.client.get_escrow(&s.escrow_id).arbiter;
+    s.client.request_arbitration_panel(&s.buyer, &s.escrow_id, &3);
+    assert!(s
+        .client
+        .try_resolve_dispute(&arbiter, &s.escrow_id, &50)
+        .is_err());
+
+    let deadline = s.client.get_arbitration_panel(&s.escrow_id).unwrap().voting_deadline;
+    s.env.ledger().with_mut(|l| l.timestamp = deadline + 1);
+    s.client.close_arbitration_panel(&s.escrow_id);
+    assert!(s.client.get_arbitration_panel(&s.escrow_id).is_none());
+
+    s.client.resolve_dispute(&arbiter, &s.escrow_id, &50);
+    assert_eq!(s.client.get_escrow(&s.escrow_id).status, EscrowStatus::Resolved);
+}
+
+#[test]
+fn test_cooling_off_keeps_the_median_in_basis_points() {
+    let s = setup_panel();
+    s.client.set_resolution_cooloff_secs(&s.admin, &3_600);
+    let members = s.client.request_arbitration_panel(&s.buyer, &s.escrow_id, &3);
+
+    s.client.submit_panel_vote(&members.get(0).unwrap(), &s.escrow_id, &3_333);
+    s.client.submit_panel_vote(&members.get(1).unwrap(), &s.escrow_id, &3_333);
+    s.env.ledger().with_mut(|l| l.timestamp += 3_601);
+    s.client.finalize_resolution(&s.escrow_id);
+
+    // 934 distributed at 33.33%, not rounded down to 33%
+    assert_eq!(s.token_client.balance(&s.buyer), 311);
+    assert_eq!(s.token_client.balance(&s.seller), 623);
+}
+
+#[test]
+fn test_dispute_timeout_decides_a_stalled_panel() {
+    let s = setup_panel();
+    let members = s.client.request_arbitration_panel(&s.buyer, &s.escrow_id, &5);
+    s.client.submit_panel_vote(&members.get(0).unwrap(), &s.escrow_id, &8_000);
+
+    let keeper = Address::generate(&s.env);
+    assert_eq!(
+        s.client.try_enforce_dispute_timeout(&keeper, &s.escrow_id),
+        Err(Ok(soroban_sdk::Error::from(DisputeError::UnderPanelArbitration)))
+    );
+
+    let deadline = s.client.get_arbitration_panel(&s.escrow_id).unwrap().voting_deadline;
+    s.env.ledger().with_mut(|l| l.timestamp = deadline + 1);
+    s.client.enforce_dispute_timeout(&keeper, &s.escrow_id);
+
+    assert_eq!(
+        s.client.get_arbitration_panel(&s.escrow_id).unwrap().decided_bps,
+        Some(8_000)
+    );
+    assert_eq!(s.client.get_escrow(&s.escrow_id).status, EscrowStatus::Resolved);
+}
+
+#[test]
+fn test_lapsed_panel_restarts_the_dispute_timeout() {
+    let s = setup_panel();
+    s.client.request_arbitration_panel(&s.buyer, &s.escrow_id, &3);
+
+    // The arbiter was locked out while the panel sat, so the lapse only hands the
+    // dispute back with a fresh timeout
+    s.env.ledger().with_mut(|l| l.timestamp += DEFAULT_DISPUTE_TIMEOUT_SECONDS + 1);
+    let keeper = Address::generate(&s.env);
+    s.client.enforce_dispute_timeout(&keeper, &s.escrow_id);
+    assert!(s.client.get_arbitration_panel(&s.escrow_id).is_none());
+    assert_eq!(s.client.get_escrow(&s.escrow_id).status, EscrowStatus::Disputed);
+    assert_eq!(
+        s.client.try_enforce_dispute_timeout(&keeper, &s.escrow_id),
+        Err(Ok(soroban_sdk::Error::from(DisputeError::DisputeTimeoutNotReached)))
+    );
+
+    s.env.ledger().with_mut(|l| l.timestamp += DEFAULT_DISPUTE_TIMEOUT_SECONDS);
+    s.client.enforce_dispute_timeout(&keeper, &s.escrow_id);
+    assert_eq!(s.client.get_escrow(&s.escrow_id).status, EscrowStatus::Refunded);
+    assert_eq!(s.token_client.balance(&s.buyer), 1_000);
+}
+
+#[test]
+fn test_closing_a_lapsed_panel_restarts_the_dispute_timeout() {
+    let s = setup_panel();
+    s.client.request_arbitration_panel(&s.buyer, &s.escrow_id, &3);
+
+    s.env.ledger().with_mut(|l| l.timestamp += DEFAULT_DISPUTE_TIMEOUT_SECONDS + 1);
+    s.client.close_arbitration_panel(&s.escrow_id);
+    assert_eq!(
+        s.client.try_enforce_dispute_timeout(&Address::generate(&s.env), &s.escrow_id),
+        Err(Ok(soroban_sdk::Error::from(DisputeError::DisputeTimeoutNotReached)))
+    );
+
+    let arbiter = s.client.get_escrow(&s.escrow_id).arbiter;
+    s.client.resolve_dispute(&arbiter, &s.escrow_id, &50);
+    assert_eq!(s.client.get_escrow(&s.escrow_id).status, EscrowStatus::Resolved);
+}
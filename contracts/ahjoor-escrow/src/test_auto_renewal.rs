@@ -349,7 +349,7 @@ fn test_get_renewal_history_ordered() {
 
 /// cancel_auto_renewal panics if caller is not the buyer.
 #[test]
-#[should_panic(expected = "Only buyer can cancel auto-renewal")]
+#[should_panic(expected = "Error(Contract, #1021)")] // NotBuyer
 fn test_cancel_auto_renewal_non_buyer_panics() {
     let s = setup();
     let buyer = Address::generate(&s.env);
@@ -375,7 +375,7 @@ fn test_cancel_auto_renewal_non_buyer_panics() {
 
 /// cancel_auto_renewal panics if no AutoRenewConfig is set.
 #[test]
-#[should_panic(expected = "No AutoRenewConfig set on this escrow")]
+#[should_panic(expected = "Error(Contract, #1339)")] // AutoRenewDisabled
 fn test_cancel_auto_renewal_no_config_panics() {
     let s = setup();
     let buyer = Address::generate(&s.env);
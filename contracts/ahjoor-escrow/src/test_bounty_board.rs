@@ -70,7 +70,7 @@ fn test_create_bounty_success() {
 }
 
 #[test]
-#[should_panic(expected = "Bounty amount must be positive")]
+#[should_panic(expected = "Error(Contract, #1009)")] // InvalidAmount
 fn test_create_bounty_zero_amount() {
     let env = Env::default();
     env.mock_all_auths();
@@ -100,7 +100,7 @@ fn test_create_bounty_zero_amount() {
 }
 
 #[test]
-#[should_panic(expected = "Claim deadline must be in the future")]
+#[should_panic(expected = "Error(Contract, #1010)")] // InvalidDeadline
 fn test_create_bounty_past_claim_deadline() {
     let env = Env::default();
     env.mock_all_auths();
@@ -132,7 +132,7 @@ fn test_create_bounty_past_claim_deadline() {
 }
 
 #[test]
-#[should_panic(expected = "Submission deadline must be after claim deadline")]
+#[should_panic(expected = "Error(Contract, #1010)")] // InvalidDeadline
 fn test_create_bounty_invalid_deadlines() {
     let env = Env::default();
     env.mock_all_auths();
@@ -203,7 +203,7 @@ fn test_claim_bounty_success() {
 }
 
 #[test]
-#[should_panic(expected = "Bounty is not available for claiming")]
+#[should_panic(expected = "Error(Contract, #1210)")] // BountyNotOpen
 fn test_claim_bounty_duplicate() {
     let env = Env::default();
     env.mock_all_auths();
@@ -239,7 +239,7 @@ fn test_claim_bounty_duplicate() {
 }
 
 #[test]
-#[should_panic(expected = "Claim deadline has passed")]
+#[should_panic(expected = "Error(Contract, #1213)")] // ClaimDeadlinePassed
 fn test_claim_bounty_after_deadline() {
     let env = Env::default();
     env.mock_all_auths();
@@ -314,7 +314,7 @@ fn test_submit_bounty_work_success() {
 }
 
 #[test]
-#[should_panic(expected = "Only the assigned solver can submit work")]
+#[should_panic(expected = "Error(Contract, #1215)")] // NotSolver
 fn test_submit_bounty_work_wrong_solver() {
     let env = Env::default();
     env.mock_all_auths();
@@ -352,7 +352,7 @@ fn test_submit_bounty_work_wrong_solver() {
 }
 
 #[test]
-#[should_panic(expected = "Submission deadline has passed")]
+#[should_panic(expected = "Error(Contract, #1214)")] // SubmissionDeadlinePassed
 fn test_submit_bounty_work_after_deadline() {
     let env = Env::default();
     env.mock_all_auths();
@@ -436,7 +436,7 @@ fn test_approve_bounty_submission_success() {
 }
 
 #[test]
-#[should_panic(expected = "No submission has been made")]
+#[should_panic(expected = "Error(Contract, #1217)")] // NoSubmission
 fn test_approve_bounty_without_submission() {
     let env = Env::default();
     env.mock_all_auths();
@@ -525,7 +525,7 @@ fn test_reject_bounty_submission_and_reclaim() {
 }
 
 #[test]
-#[should_panic(expected = "Maximum rejection rounds reached")]
+#[should_panic(expected = "Error(Contract, #1218)")] // MaxRejectionRoundsReached
 fn test_reject_bounty_max_rejections() {
     let env = Env::default();
     env.mock_all_auths();
@@ -610,7 +610,7 @@ fn test_cancel_bounty_unclaimed() {
 }
 
 #[test]
-#[should_panic(expected = "Cannot cancel bounty in current state")]
+#[should_panic(expected = "Error(Contract, #1212)")] // BountyNotCancellable
 fn test_cancel_bounty_claimed() {
     let env = Env::default();
     env.mock_all_auths();
@@ -155,7 +155,7 @@ fn test_sequential_verification_releases_tranches_then_settles() {
 }
 
 #[test]
-#[should_panic(expected = "Previous milestone not yet verified")]
+#[should_panic(expected = "Error(Contract, #1208)")] // PreviousMilestoneUnverified
 fn test_out_of_order_submission_is_rejected() {
     let hx = setup();
     let id = create_two_milestone_bounty(&hx);
@@ -166,7 +166,7 @@ fn test_out_of_order_submission_is_rejected() {
 }
 
 #[test]
-#[should_panic(expected = "Milestone is not awaiting verification")]
+#[should_panic(expected = "Error(Contract, #1205)")] // InvalidMilestoneStatus
 fn test_verify_before_submit_is_rejected() {
     let hx = setup();
     let id = create_two_milestone_bounty(&hx);
@@ -203,7 +203,7 @@ fn test_replace_verifier_before_submission_then_flow_works() {
 }
 
 #[test]
-#[should_panic(expected = "Verifier can only be replaced before the milestone is submitted")]
+#[should_panic(expected = "Error(Contract, #1205)")] // InvalidMilestoneStatus
 fn test_replace_verifier_after_submission_is_rejected() {
     let hx = setup();
     let id = create_two_milestone_bounty(&hx);
@@ -103,7 +103,7 @@ fn test_flagged_then_reviewed() {
 // Test: flag after window expires is rejected
 // ---------------------------------------------------------------------------
 #[test]
-#[should_panic(expected = "Cooling-off window has expired")]
+#[should_panic(expected = "Error(Contract, #1111)")] // CoolingOffExpired
 fn test_flag_after_window_expired() {
     let (env, client, admin, buyer, _seller, arbiter, _token_addr, _token_client, _) = setup_cooling_off();
     let escrow_id = 0u32;
@@ -123,7 +123,7 @@ fn test_flag_after_window_expired() {
 // Test: finalize before window expires is rejected
 // ---------------------------------------------------------------------------
 #[test]
-#[should_panic(expected = "Cooling-off window has not elapsed")]
+#[should_panic(expected = "Error(Contract, #1112)")] // CoolingOffActive
 fn test_finalize_before_window_elapsed() {
     let (env, client, admin, _buyer, _seller, arbiter, _token_addr, _token_client, _) = setup_cooling_off();
     let escrow_id = 0u32;
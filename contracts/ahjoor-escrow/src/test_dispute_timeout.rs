@@ -108,7 +108,7 @@ fn test_enforce_dispute_timeout_buyer_default() {
     });
 
     // Anyone can enforce timeout
-    client.enforce_dispute_timeout(&escrow_id);
+    client.enforce_dispute_timeout(&Address::generate(&env), &escrow_id);
 
     // Verify funds released to buyer (default winner)
     let escrow = client.get_escrow(&escrow_id);
@@ -153,7 +153,7 @@ fn test_enforce_dispute_timeout_seller_default() {
     });
 
     // Enforce timeout
-    client.enforce_dispute_timeout(&escrow_id);
+    client.enforce_dispute_timeout(&Address::generate(&env), &escrow_id);
 
     // Verify funds released to seller (default winner)
     let escrow = client.get_escrow(&escrow_id);
@@ -165,7 +165,7 @@ fn test_enforce_dispute_timeout_seller_default() {
 }
 
 #[test]
-#[should_panic(expected = "Dispute timeout deadline has not passed yet")]
+#[should_panic(expected = "Error(Contract, #1107)")] // DisputeTimeoutNotReached
 fn test_enforce_timeout_before_deadline() {
     let (env, _admin, buyer, seller, arbiter, token, client) = setup_test_env();
 
@@ -191,11 +191,11 @@ fn test_enforce_timeout_before_deadline() {
         li.timestamp += 3 * 24 * 60 * 60; // 3 days
     });
 
-    client.enforce_dispute_timeout(&escrow_id);
+    client.enforce_dispute_timeout(&Address::generate(&env), &escrow_id);
 }
 
 #[test]
-#[should_panic(expected = "Escrow is not disputed")]
+#[should_panic(expected = "Error(Contract, #1100)")] // EscrowNotDisputed
 fn test_enforce_timeout_on_non_disputed_escrow() {
     let (env, _admin, buyer, seller, arbiter, token, client) = setup_test_env();
 
@@ -214,11 +214,11 @@ fn test_enforce_timeout_on_non_disputed_escrow() {
     );
 
     // Try to enforce timeout without dispute
-    client.enforce_dispute_timeout(&escrow_id);
+    client.enforce_dispute_timeout(&Address::generate(&env), &escrow_id);
 }
 
 #[test]
-#[should_panic(expected = "Dispute already resolved")]
+#[should_panic(expected = "Error(Contract, #1102)")] // DisputeAlreadyResolved
 fn test_enforce_timeout_on_resolved_dispute() {
     let (env, _admin, buyer, seller, arbiter, token, client) = setup_test_env();
 
@@ -246,7 +246,7 @@ fn test_enforce_timeout_on_resolved_dispute() {
     });
 
     // Try to enforce timeout on already resolved dispute
-    client.enforce_dispute_timeout(&escrow_id);
+    client.enforce_dispute_timeout(&Address::generate(&env), &escrow_id);
 }
 
 #[test]
@@ -277,7 +277,7 @@ fn test_per_escrow_timeout_override() {
     });
 
     // Enforce timeout
-    client.enforce_dispute_timeout(&escrow_id);
+    client.enforce_dispute_timeout(&Address::generate(&env), &escrow_id);
 
     // Verify timeout was enforced
     let escrow = client.get_escrow(&escrow_id);
@@ -305,7 +305,7 @@ fn test_arbiter_timeout_counter_increments() {
     env.ledger().with_mut(|li| {
         li.timestamp += 8 * 24 * 60 * 60;
     });
-    client.enforce_dispute_timeout(&escrow_id1);
+    client.enforce_dispute_timeout(&Address::generate(&env), &escrow_id1);
 
     // Verify counter is 1
     let timeout_count = client.get_arbiter_timeout_count(&arbiter);
@@ -328,7 +328,7 @@ fn test_arbiter_timeout_counter_increments() {
     env.ledger().with_mut(|li| {
         li.timestamp += 8 * 24 * 60 * 60;
     });
-    client.enforce_dispute_timeout(&escrow_id2);
+    client.enforce_dispute_timeout(&Address::generate(&env), &escrow_id2);
 
     // Verify counter is 2
     let timeout_count = client.get_arbiter_timeout_count(&arbiter);
@@ -370,7 +370,7 @@ fn test_partial_dispute_timeout() {
     });
 
     // Enforce timeout
-    client.enforce_dispute_timeout(&escrow_id);
+    client.enforce_dispute_timeout(&Address::generate(&env), &escrow_id);
 
     // Verify disputed portion released to buyer (default winner)
     let escrow = client.get_escrow(&escrow_id);
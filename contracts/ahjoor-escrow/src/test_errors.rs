@@ -0,0 +1,83 @@
+#![cfg(test)]
+use super::*;
+use soroban_sdk::token::StellarAssetClient as TokenAdminClient;
+use soroban_sdk::{testutils::Address as _, Address, Env, String, Vec};
+
+fn setup_errors<'a>() -> (Env, AhjoorEscrowContractClient<'a>, Address, Address, Address, Address, u32) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AhjoorEscrowContract, ());
+    let client = AhjoorEscrowContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    TokenAdminClient::new(&env, &token).mint(&buyer, &10_000);
+
+    client.initialize(&admin);
+    client.add_allowed_token(&admin, &token);
+    let deadline = env.ledger().timestamp() + 10_000;
+    let escrow_id = client.create_escrow(
+        &buyer, &seller, &arbiter, &1_000, &token, &deadline,
+        &None, &Vec::new(&env), &false, &0u32,
+    );
+
+    (env, client, admin, buyer, seller, arbiter, escrow_id)
+}
+
+fn contract_error(code: impl Into<soroban_sdk::Error>) -> soroban_sdk::Error {
+    code.into()
+}
+
+#[test]
+fn test_each_error_family_maps_to_its_own_code() {
+    let (env, client, _admin, buyer, seller, _arbiter, escrow_id) = setup_errors();
+
+    assert_eq!(
+        client.try_get_escrow(&99),
+        Err(Ok(contract_error(Error::EscrowNotFound)))
+    );
+    assert_eq!(
+        client.try_release_escrow(&seller, &escrow_id),
+        Err(Ok(contract_error(Error::NotBuyerOrArbiter)))
+    );
+    assert_eq!(
+        client.try_approve_milestone(&buyer, &escrow_id, &0),
+        Err(Ok(contract_error(MilestoneError::NoMilestones)))
+    );
+    assert_eq!(
+        client.try_accept_amendment(&seller, &escrow_id, &0),
+        Err(Ok(contract_error(WorkflowError::AmendmentNotFound)))
+    );
+
+    client.dispute_escrow(&buyer, &escrow_id, &String::from_str(&env, "late"), &1_000);
+    assert_eq!(
+        client.try_resolve_dispute(&buyer, &escrow_id, &50),
+        Err(Ok(contract_error(DisputeError::NotArbiter)))
+    );
+}
+
+#[test]
+fn test_admin_checks_raise_unauthorized() {
+    let (env, client, admin, _buyer, _seller, _arbiter, _escrow_id) = setup_errors();
+    let intruder = Address::generate(&env);
+
+    assert_eq!(
+        client.try_resume_contract(&intruder),
+        Err(Ok(contract_error(Error::Unauthorized)))
+    );
+    assert_eq!(
+        client.try_resume_contract(&admin),
+        Err(Ok(contract_error(Error::ContractNotPaused)))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1000)")] // AlreadyInitialized
+fn test_double_initialize_panics_with_typed_error() {
+    let (_env, client, admin, _buyer, _seller, _arbiter, _escrow_id) = setup_errors();
+    client.initialize(&admin);
+}
@@ -0,0 +1,167 @@
+#![cfg(test)]
+use super::*;
+use soroban_sdk::token::Client as TokenClient;
+use soroban_sdk::token::StellarAssetClient as TokenAdminClient;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Bytes, BytesN, Env, String,
+};
+
+struct HtlcSetup<'a> {
+    env: Env,
+    client: AhjoorEscrowContractClient<'a>,
+    admin: Address,
+    sender: Address,
+    receiver: Address,
+    token_addr: Address,
+    token_client: TokenClient<'a>,
+    preimage: Bytes,
+    hashlock: BytesN<32>,
+}
+
+fn setup_htlc<'a>() -> HtlcSetup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let contract_id = env.register(AhjoorEscrowContract, ());
+    let client = AhjoorEscrowContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let sender = Address::generate(&env);
+    let receiver = Address::generate(&env);
+    let token_addr = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let token_client = TokenClient::new(&env, &token_addr);
+    TokenAdminClient::new(&env, &token_addr).mint(&sender, &1_000);
+
+    client.initialize(&admin);
+    client.add_allowed_token(&admin, &token_addr);
+
+    let preimage = Bytes::from_slice(&env, b"cross-chain swap secret");
+    let hashlock: BytesN<32> = env.crypto().sha256(&preimage).into();
+
+    HtlcSetup {
+        env,
+        client,
+        admin,
+        sender,
+        receiver,
+        token_addr,
+        token_client,
+        preimage,
+        hashlock,
+    }
+}
+
+fn create(s: &HtlcSetup) -> u32 {
+    s.client.create_htlc_escrow(
+        &s.sender,
+        &s.receiver,
+        &s.token_addr,
+        &600,
+        &s.hashlock,
+        &2_000,
+    )
+}
+
+#[test]
+fn test_receiver_claims_with_preimage_which_is_recorded() {
+    let s = setup_htlc();
+    let htlc_id = create(&s);
+    assert_eq!(s.token_client.balance(&s.client.address), 600);
+
+    let wrong = Bytes::from_slice(&s.env, b"guess");
+    assert_eq!(
+        s.client.try_claim_htlc(&s.receiver, &htlc_id, &wrong),
+        Err(Ok(soroban_sdk::Error::from(
+            MilestoneError::InvalidPreimage
+        )))
+    );
+
+    s.client.claim_htlc(&s.receiver, &htlc_id, &s.preimage);
+    assert_eq!(s.token_client.balance(&s.receiver), 600);
+    let htlc = s.client.get_htlc(&htlc_id);
+    assert_eq!(htlc.status, EscrowStatus::Released);
+    assert_eq!(htlc.preimage, Some(s.preimage.clone()));
+
+    assert_eq!(
+        s.client.try_claim_htlc(&s.receiver, &htlc_id, &s.preimage),
+        Err(Ok(soroban_sdk::Error::from(MilestoneError::AlreadyClaimed)))
+    );
+}
+
+#[test]
+fn test_sender_reclaims_only_after_timeout() {
+    let s = setup_htlc();
+    let htlc_id = create(&s);
+
+    assert_eq!(
+        s.client.try_refund_htlc(&s.sender, &htlc_id),
+        Err(Ok(soroban_sdk::Error::from(MilestoneError::HtlcNotExpired)))
+    );
+
+    s.env.ledger().set_timestamp(2_000);
+    assert_eq!(
+        s.client.try_claim_htlc(&s.receiver, &htlc_id, &s.preimage),
+        Err(Ok(soroban_sdk::Error::from(MilestoneError::HtlcExpired)))
+    );
+    s.client.refund_htlc(&s.sender, &htlc_id);
+    assert_eq!(s.token_client.balance(&s.sender), 1_000);
+    assert_eq!(s.client.get_htlc(&htlc_id).status, EscrowStatus::Refunded);
+}
+
+#[test]
+fn test_claim_and_refund_are_allowed_while_paused() {
+    let s = setup_htlc();
+    TokenAdminClient::new(&s.env, &s.token_addr).mint(&s.sender, &200);
+    let claimed = create(&s);
+    let refunded = create(&s);
+    s.client
+        .pause_contract(&s.admin, &String::from_str(&s.env, "incident"));
+
+    s.client.claim_htlc(&s.receiver, &claimed, &s.preimage);
+    assert_eq!(s.token_client.balance(&s.receiver), 600);
+
+    s.env.ledger().set_timestamp(2_000);
+    s.client.refund_htlc(&s.sender, &refunded);
+    assert_eq!(s.token_client.balance(&s.sender), 1_200 - 600);
+}
+
+#[test]
+fn test_htlc_validation() {
+    let s = setup_htlc();
+    assert_eq!(
+        s.client.try_create_htlc_escrow(
+            &s.sender,
+            &s.receiver,
+            &s.token_addr,
+            &600,
+            &s.hashlock,
+            &1_000
+        ),
+        Err(Ok(soroban_sdk::Error::from(Error::InvalidDeadline)))
+    );
+    assert_eq!(
+        s.client.try_create_htlc_escrow(
+            &s.sender,
+            &s.receiver,
+            &s.token_addr,
+            &0,
+            &s.hashlock,
+            &2_000
+        ),
+        Err(Ok(soroban_sdk::Error::from(Error::InvalidAmount)))
+    );
+
+    let htlc_id = create(&s);
+    assert_eq!(
+        s.client.try_claim_htlc(&s.sender, &htlc_id, &s.preimage),
+        Err(Ok(soroban_sdk::Error::from(MilestoneError::NotBeneficiary)))
+    );
+    assert_eq!(
+        s.client.try_get_htlc(&(htlc_id + 1)),
+        Err(Ok(soroban_sdk::Error::from(MilestoneError::HtlcNotFound)))
+    );
+}
@@ -109,6 +109,8 @@ fn make_request(env: &soroban_sdk::Env, seller: &Address, arbiter: &Address, tok
         auto_renew_max_renewals: None,
 
         auto_renew_interval_ledgers: None,
+        twap_observation_count: 0,
+        twap_window_seconds: 0,
     }
 }
 
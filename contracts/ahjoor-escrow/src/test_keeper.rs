@@ -0,0 +1,169 @@
+#![cfg(test)]
+use super::*;
+use soroban_sdk::token::Client as TokenClient;
+use soroban_sdk::token::StellarAssetClient as TokenAdminClient;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, Env,
+};
+
+struct KeeperSetup<'a> {
+    env: Env,
+    client: AhjoorEscrowContractClient<'a>,
+    admin: Address,
+    buyer: Address,
+    seller: Address,
+    arbiter: Address,
+    keeper: Address,
+    token_addr: Address,
+    token_client: TokenClient<'a>,
+}
+
+/// Bounty 10 per item, at most 15 per call and 20 per ledger, pool of 100.
+fn setup_keeper<'a>() -> KeeperSetup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AhjoorEscrowContract, ());
+    let client = AhjoorEscrowContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    let token_addr = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let token_client = TokenClient::new(&env, &token_addr);
+    let token_admin = TokenAdminClient::new(&env, &token_addr);
+    token_admin.mint(&buyer, &1_000);
+    token_admin.mint(&admin, &100);
+
+    client.initialize(&admin);
+    client.add_allowed_token(&admin, &token_addr);
+    client.set_keeper_config(&admin, &token_addr, &10, &15, &20);
+    client.fund_keeper_pool(&admin, &100);
+
+    KeeperSetup {
+        env,
+        client,
+        admin,
+        buyer,
+        seller,
+        arbiter,
+        keeper,
+        token_addr,
+        token_client,
+    }
+}
+
+fn expired_escrow(s: &KeeperSetup) -> u32 {
+    let deadline = s.env.ledger().timestamp() + 1_000;
+    let escrow_id = s.client.create_escrow(
+        &s.buyer,
+        &s.seller,
+        &s.arbiter,
+        &100,
+        &s.token_addr,
+        &deadline,
+        &None,
+        &Vec::new(&s.env),
+        &false,
+        &0u32,
+    );
+    s.env.ledger().set_timestamp(deadline + 1);
+    escrow_id
+}
+
+#[test]
+fn test_auto_release_pays_keeper_once() {
+    let s = setup_keeper();
+    let escrow_id = expired_escrow(&s);
+
+    s.client.auto_release_expired(&s.keeper, &escrow_id);
+    assert_eq!(s.token_client.balance(&s.keeper), 10);
+    assert_eq!(s.token_client.balance(&s.buyer), 1_000);
+
+    s.env.ledger().with_mut(|l| l.sequence_number += 1);
+    assert_eq!(
+        s.client.try_auto_release_expired(&s.keeper, &escrow_id),
+        Err(Ok(soroban_sdk::Error::from(Error::EscrowNotActive)))
+    );
+    assert_eq!(s.token_client.balance(&s.keeper), 10);
+    assert_eq!(s.client.get_keeper_pool().balance, 90);
+}
+
+#[test]
+fn test_scheduled_release_pays_per_tranche_capped_per_call() {
+    let s = setup_keeper();
+    let now = s.env.ledger().timestamp();
+    let schedule = vec![
+        &s.env,
+        ReleaseTranche {
+            unlock_at: now + 100,
+            amount: 40,
+        },
+        ReleaseTranche {
+            unlock_at: now + 200,
+            amount: 60,
+        },
+    ];
+    let escrow_id = s.client.create_escrow_with_schedule(
+        &s.buyer,
+        &s.seller,
+        &s.arbiter,
+        &s.token_addr,
+        &(now + 1_000),
+        &schedule,
+    );
+
+    s.env.ledger().set_timestamp(now + 200);
+    s.client.claim_scheduled_release(&s.keeper, &escrow_id);
+
+    // Both tranches go to the seller; 2 × 10 is capped at 15 per call
+    assert_eq!(s.token_client.balance(&s.seller), 100);
+    assert_eq!(s.token_client.balance(&s.keeper), 15);
+}
+
+#[test]
+fn test_keeper_rewards_capped_per_ledger() {
+    let s = setup_keeper();
+    let first = expired_escrow(&s);
+    let second = expired_escrow(&s);
+    let third = expired_escrow(&s);
+
+    s.client.auto_release_expired(&s.keeper, &first);
+    s.client.auto_release_expired(&s.keeper, &second);
+    s.client.auto_release_expired(&s.keeper, &third);
+    assert_eq!(s.token_client.balance(&s.keeper), 20);
+
+    let fourth = expired_escrow(&s);
+    s.env.ledger().with_mut(|l| l.sequence_number += 1);
+    s.client.auto_release_expired(&s.keeper, &fourth);
+    assert_eq!(s.token_client.balance(&s.keeper), 30);
+}
+
+#[test]
+fn test_keeper_pool_requires_config() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(AhjoorEscrowContract, ());
+    let client = AhjoorEscrowContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    assert_eq!(
+        client.try_fund_keeper_pool(&admin, &100),
+        Err(Ok(soroban_sdk::Error::from(Error::KeeperNotConfigured)))
+    );
+    assert_eq!(client.get_keeper_config(), None);
+
+    let s = setup_keeper();
+    s.client.withdraw_keeper_pool(&s.admin, &100);
+    assert_eq!(s.token_client.balance(&s.admin), 100);
+    assert_eq!(
+        s.client.try_withdraw_keeper_pool(&s.admin, &1),
+        Err(Ok(soroban_sdk::Error::from(Error::InsufficientKeeperPool)))
+    );
+}
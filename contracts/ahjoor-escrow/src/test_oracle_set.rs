@@ -0,0 +1,263 @@
+#![cfg(test)]
+use super::*;
+use soroban_sdk::token::Client as TokenClient;
+use soroban_sdk::token::StellarAssetClient as TokenAdminClient;
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    vec, Address, Env, IntoVal,
+};
+
+pub(crate) mod set_mock_oracle {
+    use crate::PriceData;
+    use soroban_sdk::{contract, contractimpl, contracttype, Address, Env};
+
+    #[contracttype]
+    enum OracleKey {
+        Price,
+    }
+
+    #[contract]
+    pub struct SetMockOracle;
+
+    #[contractimpl]
+    impl SetMockOracle {
+        pub fn set_price(env: Env, price: i128, timestamp: u64) {
+            env.storage()
+                .instance()
+                .set(&OracleKey::Price, &PriceData { price, timestamp });
+        }
+
+        pub fn lastprice(env: Env, _base: Address, _quote: Address) -> Option<PriceData> {
+            env.storage().instance().get(&OracleKey::Price)
+        }
+    }
+}
+
+use set_mock_oracle::{SetMockOracle, SetMockOracleClient};
+
+struct OracleSetSetup<'a> {
+    env: Env,
+    client: AhjoorEscrowContractClient<'a>,
+    admin: Address,
+    buyer: Address,
+    seller: Address,
+    token_addr: Address,
+    token_client: TokenClient<'a>,
+    oracles: Vec<Address>,
+    base: Address,
+    quote: Address,
+}
+
+/// Three oracles for (base, quote), max age 300s, the ledger at t = 1_000.
+fn setup_oracle_set<'a>() -> OracleSetSetup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let contract_id = env.register(AhjoorEscrowContract, ());
+    let client = AhjoorEscrowContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let token_addr = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let token_client = TokenClient::new(&env, &token_addr);
+    TokenAdminClient::new(&env, &token_addr).mint(&buyer, &1_000);
+
+    let oracles = vec![
+        &env,
+        env.register(SetMockOracle, ()),
+        env.register(SetMockOracle, ()),
+        env.register(SetMockOracle, ()),
+    ];
+    let base = Address::generate(&env);
+    let quote = Address::generate(&env);
+
+    client.initialize(&admin);
+    client.add_allowed_token(&admin, &token_addr);
+    client.set_oracle(&admin, &oracles.get(0).unwrap(), &300u64);
+
+    OracleSetSetup {
+        env,
+        client,
+        admin,
+        buyer,
+        seller,
+        token_addr,
+        token_client,
+        oracles,
+        base,
+        quote,
+    }
+}
+
+fn set_price(s: &OracleSetSetup, index: u32, price: i128, timestamp: u64) {
+    SetMockOracleClient::new(&s.env, &s.oracles.get(index).unwrap()).set_price(&price, &timestamp);
+}
+
+/// Escrow released once the pair's price is at or below 500.
+fn create_conditional_escrow(s: &OracleSetSetup) -> u32 {
+    let request = EscrowCreateRequest {
+        seller: s.seller.clone(),
+        arbiter: Address::generate(&s.env),
+        amount: 1_000,
+        token: s.token_addr.clone(),
+        deadline: 50_000,
+        metadata_hash: None,
+        sellers: Vec::new(&s.env),
+        auto_renew: false,
+        renewal_count: 0,
+        buyer_inactivity_secs: 0,
+        min_lock_until: None,
+        release_base: Some(s.base.clone()),
+        release_quote: Some(s.quote.clone()),
+        release_comparison: Some(0u32),
+        release_threshold_price: Some(500),
+        arbiter_fee_bps: None,
+        dispute_default_winner: None,
+        auto_renew_max_renewals: None,
+        auto_renew_interval_ledgers: None,
+        twap_observation_count: 0,
+        twap_window_seconds: 0,
+    };
+    s.client.create_escrow_v2(&s.buyer, &request)
+}
+
+fn count_events(env: &Env, name: &str) -> usize {
+    env.events()
+        .all()
+        .iter()
+        .filter(|e| e.1 == (Symbol::new(env, name),).into_val(env))
+        .count()
+}
+
+#[test]
+fn test_single_manipulated_feed_cannot_release() {
+    let s = setup_oracle_set();
+    s.client
+        .set_oracle_set(&s.admin, &s.base, &s.quote, &s.oracles, &2, &500);
+    let escrow_id = create_conditional_escrow(&s);
+
+    // The single-oracle path would have released on the first feed alone
+    set_price(&s, 0, 100, 1_000);
+    set_price(&s, 1, 800, 1_000);
+    set_price(&s, 2, 810, 1_000);
+    assert_eq!(
+        s.client.try_check_and_release_escrow(&escrow_id),
+        Err(Ok(soroban_sdk::Error::from(
+            WorkflowError::OracleDeviationExceeded
+        )))
+    );
+    assert_eq!(s.client.get_escrow(&escrow_id).status, EscrowStatus::Active);
+
+    set_price(&s, 0, 400, 1_000);
+    set_price(&s, 1, 405, 1_000);
+    set_price(&s, 2, 410, 1_000);
+    s.client.check_and_release_escrow(&escrow_id);
+    assert_eq!(count_events(&s.env, "oracle_price_aggregated"), 1);
+    assert_eq!(count_events(&s.env, "oracle_failover"), 0);
+    assert_eq!(s.token_client.balance(&s.seller), 1_000);
+}
+
+#[test]
+fn test_failover_skips_stale_and_missing_sources() {
+    let s = setup_oracle_set();
+    s.client
+        .set_oracle_set(&s.admin, &s.base, &s.quote, &s.oracles, &2, &500);
+    let escrow_id = create_conditional_escrow(&s);
+
+    // Source 2 never reports; source 0 is stale
+    set_price(&s, 0, 100, 600);
+    set_price(&s, 1, 450, 1_000);
+    assert_eq!(
+        s.client.try_check_and_release_escrow(&escrow_id),
+        Err(Ok(soroban_sdk::Error::from(
+            WorkflowError::InsufficientOracleSources
+        )))
+    );
+
+    s.client
+        .set_oracle_set(&s.admin, &s.base, &s.quote, &s.oracles, &1, &500);
+    s.client.check_and_release_escrow(&escrow_id);
+    assert_eq!(count_events(&s.env, "oracle_failover"), 2);
+    assert_eq!(count_events(&s.env, "oracle_price_aggregated"), 1);
+    assert_eq!(
+        s.client.get_escrow(&escrow_id).status,
+        EscrowStatus::Released
+    );
+}
+
+#[test]
+fn test_median_of_even_source_count_and_set_validation() {
+    let s = setup_oracle_set();
+    let two = vec![&s.env, s.oracles.get(0).unwrap(), s.oracles.get(1).unwrap()];
+    s.client
+        .set_oracle_set(&s.admin, &s.base, &s.quote, &two, &2, &1_000);
+    let escrow_id = create_conditional_escrow(&s);
+
+    // Median of 480 and 540 is 510, above the threshold
+    set_price(&s, 0, 480, 1_000);
+    set_price(&s, 1, 540, 1_000);
+    assert_eq!(
+        s.client.try_check_and_release_escrow(&escrow_id),
+        Err(Ok(soroban_sdk::Error::from(Error::ReleaseConditionNotMet)))
+    );
+
+    // Removing the set falls back to the single configured oracle
+    s.client.remove_oracle_set(&s.admin, &s.base, &s.quote);
+    assert_eq!(s.client.get_oracle_set(&s.base, &s.quote), None);
+    s.client.check_and_release_escrow(&escrow_id);
+    assert_eq!(
+        s.client.get_escrow(&escrow_id).status,
+        EscrowStatus::Released
+    );
+
+    let duplicated = vec![&s.env, s.oracles.get(0).unwrap(), s.oracles.get(0).unwrap()];
+    for (oracles, min_sources, max_deviation_bps) in [
+        (duplicated, 1u32, 500u32),
+        (two.clone(), 3, 500),
+        (two.clone(), 0, 500),
+        (two, 1, 0),
+    ] {
+        assert_eq!(
+            s.client.try_set_oracle_set(
+                &s.admin,
+                &s.base,
+                &s.quote,
+                &oracles,
+                &min_sources,
+                &max_deviation_bps
+            ),
+            Err(Ok(soroban_sdk::Error::from(
+                WorkflowError::InvalidOracleSet
+            )))
+        );
+    }
+}
+
+#[test]
+fn test_collateral_health_uses_oracle_set_median() {
+    let s = setup_oracle_set();
+    let escrow_id = create_conditional_escrow(&s);
+    s.client
+        .set_collateral_health_config(&s.buyer, &escrow_id, &5_000, &s.oracles.get(0).unwrap());
+    s.client
+        .set_oracle_set(&s.admin, &s.token_addr, &s.token_addr, &s.oracles, &2, &500);
+
+    // The escrow's own collateral oracle is an outlier
+    set_price(&s, 0, 100_000_000, 1_000);
+    set_price(&s, 1, 10_000_000, 1_000);
+    set_price(&s, 2, 10_000_000, 1_000);
+    assert_eq!(
+        s.client.try_check_collateral_health(&escrow_id),
+        Err(Ok(soroban_sdk::Error::from(
+            WorkflowError::OracleDeviationExceeded
+        )))
+    );
+
+    set_price(&s, 0, 10_000_000, 1_000);
+    assert_eq!(s.client.check_collateral_health(&escrow_id), 0);
+    assert_eq!(count_events(&s.env, "oracle_price_aggregated"), 1);
+}
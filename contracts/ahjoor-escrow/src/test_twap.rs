@@ -0,0 +1,229 @@
+#![cfg(test)]
+use super::*;
+use crate::test_oracle_set::set_mock_oracle::{SetMockOracle, SetMockOracleClient};
+use soroban_sdk::token::Client as TokenClient;
+use soroban_sdk::token::StellarAssetClient as TokenAdminClient;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+
+struct TwapSetup<'a> {
+    env: Env,
+    client: AhjoorEscrowContractClient<'a>,
+    buyer: Address,
+    seller: Address,
+    keeper: Address,
+    token_addr: Address,
+    token_client: TokenClient<'a>,
+    oracle: SetMockOracleClient<'a>,
+    base: Address,
+    quote: Address,
+}
+
+fn setup_twap<'a>() -> TwapSetup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let contract_id = env.register(AhjoorEscrowContract, ());
+    let client = AhjoorEscrowContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    let token_addr = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let token_client = TokenClient::new(&env, &token_addr);
+    TokenAdminClient::new(&env, &token_addr).mint(&buyer, &1_000);
+
+    let oracle_addr = env.register(SetMockOracle, ());
+    let oracle = SetMockOracleClient::new(&env, &oracle_addr);
+    let base = Address::generate(&env);
+    let quote = Address::generate(&env);
+
+    client.initialize(&admin);
+    client.add_allowed_token(&admin, &token_addr);
+    client.set_oracle(&admin, &oracle_addr, &300u64);
+
+    TwapSetup {
+        env,
+        client,
+        buyer,
+        seller,
+        keeper,
+        token_addr,
+        token_client,
+        oracle,
+        base,
+        quote,
+    }
+}
+
+/// Releases when the price is at or below 500, averaged as configured.
+fn twap_request(
+    s: &TwapSetup,
+    with_condition: bool,
+    count: u32,
+    window: u64,
+) -> EscrowCreateRequest {
+    EscrowCreateRequest {
+        seller: s.seller.clone(),
+        arbiter: Address::generate(&s.env),
+        amount: 1_000,
+        token: s.token_addr.clone(),
+        deadline: 50_000,
+        metadata_hash: None,
+        sellers: Vec::new(&s.env),
+        auto_renew: false,
+        renewal_count: 0,
+        buyer_inactivity_secs: 0,
+        min_lock_until: None,
+        release_base: with_condition.then(|| s.base.clone()),
+        release_quote: with_condition.then(|| s.quote.clone()),
+        release_comparison: with_condition.then_some(0u32),
+        release_threshold_price: with_condition.then_some(500),
+        arbiter_fee_bps: None,
+        dispute_default_winner: None,
+        auto_renew_max_renewals: None,
+        auto_renew_interval_ledgers: None,
+        twap_observation_count: count,
+        twap_window_seconds: window,
+    }
+}
+
+/// Sets the oracle price at `timestamp`, moves the ledger there and records it.
+fn observe(s: &TwapSetup, escrow_id: u32, price: i128, timestamp: u64) {
+    s.env.ledger().set_timestamp(timestamp);
+    s.oracle.set_price(&price, &timestamp);
+    s.client.record_twap_observation(&s.keeper, &escrow_id);
+}
+
+#[test]
+fn test_brief_spike_does_not_release_until_average_holds() {
+    let s = setup_twap();
+    // 3 readings over 300s: one every 100s
+    let escrow_id = s
+        .client
+        .create_escrow_v2(&s.buyer, &twap_request(&s, true, 3, 300));
+
+    observe(&s, escrow_id, 400, 1_000);
+    assert_eq!(
+        s.client.try_check_and_release_escrow(&escrow_id),
+        Err(Ok(soroban_sdk::Error::from(WorkflowError::TwapNotReady)))
+    );
+    s.env.ledger().set_timestamp(1_050);
+    assert_eq!(
+        s.client.try_record_twap_observation(&s.keeper, &escrow_id),
+        Err(Ok(soroban_sdk::Error::from(
+            WorkflowError::TwapObservationTooEarly
+        )))
+    );
+
+    // Readings from 1_000 only cover the window once it starts at 1_000
+    observe(&s, escrow_id, 900, 1_100);
+    observe(&s, escrow_id, 400, 1_200);
+    assert_eq!(
+        s.client.try_check_and_release_escrow(&escrow_id),
+        Err(Ok(soroban_sdk::Error::from(WorkflowError::TwapNotReady)))
+    );
+
+    // The spot price is back at 400, but the 900 reading keeps the mean at 566
+    observe(&s, escrow_id, 400, 1_300);
+    assert_eq!(
+        s.client.try_check_and_release_escrow(&escrow_id),
+        Err(Ok(soroban_sdk::Error::from(Error::ReleaseConditionNotMet)))
+    );
+    observe(&s, escrow_id, 400, 1_400);
+    assert_eq!(
+        s.client.try_check_and_release_escrow(&escrow_id),
+        Err(Ok(soroban_sdk::Error::from(Error::ReleaseConditionNotMet)))
+    );
+
+    observe(&s, escrow_id, 400, 1_500);
+    let readings = s.client.get_escrow(&escrow_id).extensions.twap_observations;
+    assert_eq!(readings.len(), 4);
+    assert_eq!(readings.get(0).unwrap().timestamp, 1_200);
+
+    s.client.check_and_release_escrow(&escrow_id);
+    assert_eq!(s.token_client.balance(&s.seller), 1_000);
+}
+
+#[test]
+fn test_readings_are_weighted_by_how_long_they_held() {
+    let s = setup_twap();
+    let escrow_id = s
+        .client
+        .create_escrow_v2(&s.buyer, &twap_request(&s, true, 3, 300));
+
+    // The last three readings average 366, but 900 held for 200 of the 300s
+    observe(&s, escrow_id, 100, 1_000);
+    observe(&s, escrow_id, 100, 1_100);
+    observe(&s, escrow_id, 900, 1_200);
+    observe(&s, escrow_id, 100, 1_400);
+    assert_eq!(
+        s.client.try_check_and_release_escrow(&escrow_id),
+        Err(Ok(soroban_sdk::Error::from(Error::ReleaseConditionNotMet)))
+    );
+
+    observe(&s, escrow_id, 100, 1_500);
+    assert_eq!(
+        s.client.try_check_and_release_escrow(&escrow_id),
+        Err(Ok(soroban_sdk::Error::from(Error::ReleaseConditionNotMet)))
+    );
+
+    // Once it counts for only 100s the mean is (900*100 + 100*200) / 300 = 366
+    observe(&s, escrow_id, 100, 1_600);
+    s.client.check_and_release_escrow(&escrow_id);
+    assert_eq!(s.token_client.balance(&s.seller), 1_000);
+}
+
+#[test]
+fn test_readings_older_than_window_do_not_count() {
+    let s = setup_twap();
+    let escrow_id = s
+        .client
+        .create_escrow_v2(&s.buyer, &twap_request(&s, true, 3, 300));
+
+    observe(&s, escrow_id, 400, 1_000);
+    observe(&s, escrow_id, 400, 1_100);
+    observe(&s, escrow_id, 400, 1_200);
+    s.env.ledger().set_timestamp(1_301);
+    assert_eq!(
+        s.client.try_check_and_release_escrow(&escrow_id),
+        Err(Ok(soroban_sdk::Error::from(WorkflowError::TwapNotReady)))
+    );
+
+    observe(&s, escrow_id, 400, 1_301);
+    s.client.check_and_release_escrow(&escrow_id);
+    assert_eq!(
+        s.client.get_escrow(&escrow_id).status,
+        EscrowStatus::Released
+    );
+}
+
+#[test]
+fn test_twap_mode_validation() {
+    let s = setup_twap();
+    for request in [
+        twap_request(&s, true, 1, 300),
+        twap_request(&s, true, 3, 2),
+        twap_request(&s, true, 0, 300),
+        twap_request(&s, false, 3, 300),
+    ] {
+        assert_eq!(
+            s.client.try_create_escrow_v2(&s.buyer, &request),
+            Err(Ok(soroban_sdk::Error::from(Error::InvalidReleaseCondition)))
+        );
+    }
+
+    let spot = s
+        .client
+        .create_escrow_v2(&s.buyer, &twap_request(&s, true, 0, 0));
+    assert_eq!(
+        s.client.try_record_twap_observation(&s.keeper, &spot),
+        Err(Ok(soroban_sdk::Error::from(Error::NoReleaseCondition)))
+    );
+}
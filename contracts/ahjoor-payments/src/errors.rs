@@ -0,0 +1,259 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Error {
+    RateLimitExceeded = 1,
+    SubscriptionPaused = 2,
+    OracleConditionNotMet = 3,
+    /// Subscription's trial period has not elapsed; charging is deferred (#133)
+    SubscriptionInTrial = 4,
+    MerchantVolumeCapped = 20,
+    TokenNotAllowed = 5,
+    DuplicateExternalId = 6,
+    MultisigNotRequired = 7,
+    AlreadyApproved = 8,
+    NotASigner = 9,
+    VoucherExpired = 10,
+    VoucherExhausted = 11,
+    VoucherRevoked = 12,
+    VoucherNotFound = 13,
+    WithdrawalRateLimitExceeded = 14,
+    /// Referred merchant already has a merchant record (#242)
+    ReferralAlreadyExists = 15,
+    /// No pending commission to claim (#242)
+    NoCommissionToClaim = 16,
+    /// Slippage tolerance exceeded on dynamic payment settlement (#246)
+    SlippageExceeded = 21,
+    /// Oracle address is not on the admin whitelist (#246)
+    OracleNotWhitelisted = 22,
+    /// Dynamic payment has expired (#246)
+    DynamicPaymentExpired = 17,
+    /// Customer cumulative spend would exceed the merchant-configured cap (#235)
+    CustomerSpendLimitExceeded = 23,
+    /// Capture attempted after the authorized capture deadline ledger.
+    CapturePastDeadline = 24,
+    /// Tip supplied on a payment that does not have tipping_enabled (#265)
+    TippingNotEnabled = 18,
+    /// Tip amount exceeds the admin-configured maximum tip bps of the base amount (#265)
+    TipExceedsMaxBps = 19,
+    /// Evidence submission window has closed (#308)
+    EvidenceWindowClosed = 25,
+    /// Evidence submission limit reached for this party (#308)
+    EvidenceLimitReached = 26,
+    /// Cooling-off period has expired (#309)
+    CoolingOffExpired = 27,
+    /// Payment not in cooling-off status (#309)
+    NotInCoolingOff = 28,
+    /// Cooling-off period exceeds maximum allowed (#309)
+    CoolingOffExceedsMax = 29,
+    /// KYB verification required but merchant not verified (#310)
+    KYBVerificationRequired = 33,
+    /// retry_failed_debit called before back-off interval has elapsed (#329)
+    RetryNotDue = 34,
+    /// Failed debit record not found (#329)
+    DebitRecordNotFound = 35,
+    /// Debit record is already abandoned; no further retries (#329)
+    DebitAlreadyAbandoned = 36,
+    /// Debit record already succeeded; no retry needed (#329)
+    DebitAlreadySucceeded = 37,
+    /// Payment is not in a pending state and cannot be extended
+    InvalidPaymentStatus = 38,
+    /// Maximum number of extensions reached for this payment
+    MaxExtensionsReached = 39,
+    /// Additional ledgers exceed the maximum allowed per extension
+    MaxExtensionLedgersExceeded = 40,
+    /// Subscription pause count exceeded (#327)
+    PauseCountExceeded = 30,
+    /// Unauthorized to pause subscription (#327)
+    UnauthorizedPause = 31,
+    /// Merchant refund reserve is below the configured minimum (#334)
+    InsufficientMerchantReserve = 32,
+    /// Customer is blocked by merchant
+    CustomerBlocked = 50,
+    AlreadyInitialized = 41,
+    NotInitialized = 42,
+    Unauthorized = 43,
+    ContractPaused = 44,
+    PaymentNotFound = 45,
+    MerchantNotApproved = 46,
+    InvalidAmount = 47,
+    InvalidPageSize = 48,
+    ArithmeticOverflow = 49,
+}
+
+/// Payment lifecycle, dispute, batch and oracle failures. Codes 51–99.
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PaymentError {
+    NotPaymentCustomer = 51,
+    NotPaymentMerchant = 52,
+    PaymentNotPending = 53,
+    PaymentNotAuthorized = 54,
+    PaymentNotDisputed = 55,
+    PaymentNotCompleted = 56,
+    PaymentNotScheduled = 57,
+    PaymentNotPendingApproval = 58,
+    PaymentNotDisputable = 59,
+    PaymentNotExpirable = 60,
+    PaymentNotRefundable = 61,
+    PaymentExpired = 62,
+    PaymentNotExpired = 63,
+    NoExpirySet = 64,
+    NoCaptureDeadline = 65,
+    ScheduleNotReached = 66,
+    ScheduledPaymentDue = 67,
+    PaymentAlreadySettled = 68,
+    RefundExceedsBalance = 69,
+    ApprovalStateNotFound = 70,
+    ApprovalWindowOpen = 71,
+    MultisigPolicyNotFound = 72,
+    EmptyBatch = 73,
+    BatchTooLarge = 74,
+    MixedBatchTokens = 75,
+    DisputeNotFound = 76,
+    EvidenceNotFound = 77,
+    NotDisputeParty = 78,
+    CoolingOffConfigNotFound = 79,
+    ReceiptNotFound = 80,
+    ExternalIdNotFound = 81,
+    InvalidSplitRecipients = 82,
+    InvalidMetadata = 83,
+    ReferenceTooLong = 84,
+    TooManyTags = 85,
+    InvalidInvoice = 86,
+    OracleNotConfigured = 87,
+    OraclePriceUnavailable = 88,
+    OraclePriceStale = 89,
+    InvalidOraclePrice = 90,
+    SlippageOutOfBounds = 91,
+    SwapRouterNotConfigured = 92,
+    FeeRecipientNotConfigured = 93,
+    DeadlineInPast = 94,
+    ExpiryBelowMinimum = 95,
+    ExpiryAboveMaximum = 96,
+    InsufficientOracleSources = 97,
+    OracleDeviationExceeded = 98,
+}
+
+/// Subscription, recurring schedule, recurring invoice, installment plan, tip split, stream, subscription plan, metered billing and dunning failures. Codes 101–149.
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SubscriptionError {
+    SubscriptionNotFound = 101,
+    NotSubscriber = 102,
+    NotSubscriptionParty = 103,
+    SubscriptionCancelled = 104,
+    SubscriptionAlreadyPaused = 105,
+    SubscriptionNotPaused = 106,
+    InvalidSubscriptionState = 107,
+    IntervalNotElapsed = 108,
+    MaxChargesReached = 109,
+    InvalidInterval = 110,
+    RecurringScheduleNotFound = 111,
+    RecurringScheduleInactive = 112,
+    NotSchedulePayer = 113,
+    RecurringInvoiceNotFound = 114,
+    RecurringInvoiceCancelled = 115,
+    NotInvoiceParty = 116,
+    PaymentPlanNotFound = 117,
+    PaymentPlanNotActive = 118,
+    NotPlanCustomer = 119,
+    InstallmentNotDue = 120,
+    PlanNotInDefault = 121,
+    InvalidPlanTerms = 122,
+    InsufficientPlanPool = 123,
+    NotTipSplitMerchant = 124,
+    InvalidTipSplit = 125,
+    StreamNotFound = 126,
+    InvalidStream = 127,
+    NotStreamParty = 128,
+    StreamNotActive = 129,
+    NothingToWithdraw = 130,
+    SubscriptionPlanNotFound = 131,
+    SubscriptionPlanInactive = 132,
+    InvalidSubscriptionPlan = 133,
+    InvalidPlanChange = 134,
+    NotMeteredSubscription = 135,
+    InvalidMeteredPricing = 136,
+    NotUsageReporter = 137,
+    UsageRecordLimitReached = 138,
+    InvalidDunningConfig = 139,
+    SubscriptionPastDue = 140,
+    UsageCapNotSet = 141,
+    PaymentPlanExpired = 142,
+}
+
+/// Merchant standing, appeal, collateral, consent, voucher and administrative failures. Codes 151–199.
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MerchantError {
+    MerchantSuspended = 151,
+    MerchantBanned = 152,
+    MerchantNotBanned = 153,
+    AppealNotFound = 154,
+    AppealAlreadyExists = 155,
+    AppealNotPending = 156,
+    AppealCooldownActive = 157,
+    AppealNotInCoolingOff = 158,
+    ReinstatementCoolingOffActive = 159,
+    CollateralBelowMinimum = 160,
+    InsufficientCollateral = 161,
+    CollateralTokenNotConfigured = 162,
+    ConsentNotFound = 163,
+    ConsentExpired = 164,
+    ConsentRevoked = 165,
+    ConsentAlreadySigned = 166,
+    NotConsentCustomer = 167,
+    NotConsentRevoker = 168,
+    InvalidTermsVersion = 169,
+    InvalidNotificationKey = 170,
+    ReferrerNotApproved = 171,
+    UnblockRequestNotFound = 172,
+    NoAdminTransferProposed = 173,
+    MigrationAlreadyCompleted = 174,
+    VoucherAlreadyExists = 175,
+    NotVoucherIssuer = 176,
+    InvalidDiscount = 177,
+    WithdrawalQueueEmpty = 178,
+    NotInWithdrawalQueue = 179,
+    InsufficientLoyaltyPoints = 180,
+    LoyaltyProgramNotFound = 181,
+    InvalidLoyaltyProgram = 182,
+    CoalitionNotFound = 183,
+    NotCoalitionMember = 184,
+    NotCoalitionOwner = 185,
+    CoalitionInviteNotFound = 186,
+    AlreadyInCoalition = 187,
+    CoalitionFull = 188,
+    CoalitionTokenMismatch = 189,
+    CoalitionDebtOutstanding = 190,
+    NoCoalitionDebt = 191,
+    LoyaltyRateFrozen = 192,
+    InsufficientCoalitionReserve = 193,
+}
+
+/// Invalid admin configuration. Codes 401–449.
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ConfigError {
+    ContractAlreadyPaused = 401,
+    ContractNotPaused = 402,
+    FeeTooHigh = 403,
+    InvalidFeeTiers = 404,
+    InvalidWindow = 405,
+    InvalidDuration = 406,
+    InvalidRateLimitConfig = 407,
+    InvalidSlippageConfig = 408,
+    InvalidExtensionConfig = 409,
+    InvalidRetryConfig = 410,
+    InvalidMultisigPolicy = 411,
+    InvalidExpiryBounds = 412,
+    InvalidTipBps = 413,
+    InvalidBatchSize = 414,
+    InvalidCollateralConfig = 415,
+    KeeperNotConfigured = 416,
+    InvalidKeeperConfig = 417,
+    InsufficientKeeperPool = 418,
+    InvalidOracleSet = 419,
+}
@@ -1,5 +1,7 @@
+use crate::multi_token_invoice::InvoiceStatus;
+use crate::pre_approved_spending::AuditAction;
 use crate::{PaymentStatus, SplitTransfer};
-use soroban_sdk::{contractevent, Address, BytesN, Env, String, Symbol, Vec};
+use soroban_sdk::{contractevent, panic_with_error, Address, BytesN, Env, String, Symbol, Vec};
 
 /// Event: Consent record created for zero-amount agreement signing (#307)
 #[contractevent]
@@ -509,7 +511,7 @@ pub fn emit_payment_expired(
         .storage()
         .persistent()
         .get(&crate::DataKey::Payment(payment_id))
-        .expect("Payment not found");
+        .unwrap_or_else(|| panic_with_error!(e, crate::Error::PaymentNotFound));
 
     // Get notification key for the merchant
     let notification_key = e
@@ -634,7 +636,7 @@ pub fn emit_payment_disputed(e: &Env, payment_id: u32, customer: Address, reason
         .storage()
         .persistent()
         .get(&crate::DataKey::Payment(payment_id))
-        .expect("Payment not found");
+        .unwrap_or_else(|| panic_with_error!(e, crate::Error::PaymentNotFound));
 
     // Get notification key for the merchant
     let notification_key = e
@@ -1392,6 +1394,264 @@ pub fn emit_points_expired(e: &Env, customer: Address, points_expired: i128) {
     .publish(e);
 }
 
+/// Event: Merchant loyalty program created or updated
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct LoyaltyProgramConfigured {
+    pub merchant: Address,
+    pub points_per_unit: u32,
+    pub redemption_rate_bps: u32,
+    pub tiers: u32,
+}
+
+/// Event: Points earned in a merchant program
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct ProgramPointsAccrued {
+    pub merchant: Address,
+    pub customer: Address,
+    pub payment_id: u32,
+    pub points_earned: i128,
+    pub tier: u32,
+}
+
+/// Event: Customer's tier in a merchant program changed
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct LoyaltyTierChanged {
+    pub merchant: Address,
+    pub customer: Address,
+    pub old_tier: u32,
+    pub new_tier: u32,
+}
+
+/// Event: Merchant program points redeemed, possibly at another coalition member
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct ProgramPointsRedeemed {
+    pub issuer: Address,
+    pub merchant: Address,
+    pub customer: Address,
+    pub payment_id: u32,
+    pub points_used: i128,
+    pub discount_applied: i128,
+}
+
+/// Event: Expired point lots dropped from a merchant program
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct ProgramPointsExpired {
+    pub merchant: Address,
+    pub customer: Address,
+    pub points_expired: i128,
+}
+
+/// Event: Loyalty coalition created
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct CoalitionCreated {
+    pub coalition_id: u32,
+    pub owner: Address,
+    pub token: Address,
+}
+
+/// Event: Merchant joined or left a loyalty coalition
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct CoalitionMembershipChanged {
+    pub coalition_id: u32,
+    pub merchant: Address,
+    pub joined: bool,
+}
+
+/// Event: Issuer owes a coalition member for a cross-merchant redemption
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct CoalitionDebtRecorded {
+    pub coalition_id: u32,
+    pub debtor: Address,
+    pub creditor: Address,
+    pub amount: i128,
+    pub outstanding: i128,
+}
+
+/// Event: Coalition debt paid to the creditor from the debtor's reserve
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct CoalitionDebtSettled {
+    pub coalition_id: u32,
+    pub debtor: Address,
+    pub creditor: Address,
+    pub amount: i128,
+}
+
+/// Event: Member reserve funded (positive) or withdrawn (negative)
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct CoalitionReserveChanged {
+    pub coalition_id: u32,
+    pub merchant: Address,
+    pub delta: i128,
+    pub reserve: i128,
+}
+
+pub fn emit_loyalty_program_configured(
+    e: &Env,
+    merchant: Address,
+    points_per_unit: u32,
+    redemption_rate_bps: u32,
+    tiers: u32,
+) {
+    LoyaltyProgramConfigured {
+        merchant,
+        points_per_unit,
+        redemption_rate_bps,
+        tiers,
+    }
+    .publish(e);
+}
+
+pub fn emit_program_points_accrued(
+    e: &Env,
+    merchant: Address,
+    customer: Address,
+    payment_id: u32,
+    points_earned: i128,
+    tier: u32,
+) {
+    ProgramPointsAccrued {
+        merchant,
+        customer,
+        payment_id,
+        points_earned,
+        tier,
+    }
+    .publish(e);
+}
+
+pub fn emit_loyalty_tier_changed(
+    e: &Env,
+    merchant: Address,
+    customer: Address,
+    old_tier: u32,
+    new_tier: u32,
+) {
+    LoyaltyTierChanged {
+        merchant,
+        customer,
+        old_tier,
+        new_tier,
+    }
+    .publish(e);
+}
+
+pub fn emit_program_points_redeemed(
+    e: &Env,
+    issuer: Address,
+    merchant: Address,
+    customer: Address,
+    payment_id: u32,
+    points_used: i128,
+    discount_applied: i128,
+) {
+    ProgramPointsRedeemed {
+        issuer,
+        merchant,
+        customer,
+        payment_id,
+        points_used,
+        discount_applied,
+    }
+    .publish(e);
+}
+
+pub fn emit_program_points_expired(
+    e: &Env,
+    merchant: Address,
+    customer: Address,
+    points_expired: i128,
+) {
+    ProgramPointsExpired {
+        merchant,
+        customer,
+        points_expired,
+    }
+    .publish(e);
+}
+
+pub fn emit_coalition_created(e: &Env, coalition_id: u32, owner: Address, token: Address) {
+    CoalitionCreated {
+        coalition_id,
+        owner,
+        token,
+    }
+    .publish(e);
+}
+
+pub fn emit_coalition_membership_changed(
+    e: &Env,
+    coalition_id: u32,
+    merchant: Address,
+    joined: bool,
+) {
+    CoalitionMembershipChanged {
+        coalition_id,
+        merchant,
+        joined,
+    }
+    .publish(e);
+}
+
+pub fn emit_coalition_debt_recorded(
+    e: &Env,
+    coalition_id: u32,
+    debtor: Address,
+    creditor: Address,
+    amount: i128,
+    outstanding: i128,
+) {
+    CoalitionDebtRecorded {
+        coalition_id,
+        debtor,
+        creditor,
+        amount,
+        outstanding,
+    }
+    .publish(e);
+}
+
+pub fn emit_coalition_debt_settled(
+    e: &Env,
+    coalition_id: u32,
+    debtor: Address,
+    creditor: Address,
+    amount: i128,
+) {
+    CoalitionDebtSettled {
+        coalition_id,
+        debtor,
+        creditor,
+        amount,
+    }
+    .publish(e);
+}
+
+pub fn emit_coalition_reserve_changed(
+    e: &Env,
+    coalition_id: u32,
+    merchant: Address,
+    delta: i128,
+    reserve: i128,
+) {
+    CoalitionReserveChanged {
+        coalition_id,
+        merchant,
+        delta,
+        reserve,
+    }
+    .publish(e);
+}
+
 // #242: Merchant Referral Commission Events
 /// Event: Referral registered
 #[contractevent]
@@ -1969,3 +2229,906 @@ pub fn emit_tier_fee_applied(
 ) {
     TierFeeApplied { merchant, tier_fee_bps, fee_collected, volume_30d }.publish(e);
 }
+
+// ── #354: Multi-Token Invoices ───────────────────────────────────────────────
+
+/// Event: Multi-token invoice issued by a merchant
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct MultiTokenInvoiceCreated {
+    pub invoice_id: u32,
+    pub merchant: Address,
+    pub customer: Address,
+    pub total_amount: i128,
+    pub base_currency: Address,
+    pub preferred_settlement_token: Address,
+}
+
+/// Event: Payment credited against a multi-token invoice
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct InvoicePaymentReceived {
+    pub invoice_id: u32,
+    pub payment_id: u32,
+    pub payer: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub amount_in_base: i128,
+    pub status: InvoiceStatus,
+}
+
+/// Event: Invoice paid in a non-base token priced through the invoice oracle
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct CrossTokenSettlement {
+    pub invoice_id: u32,
+    pub paid_token: Address,
+    pub paid_amount: i128,
+    pub invoiced_token: Address,
+    pub invoiced_amount: i128,
+    /// Oracle price used (scaled by 10^6)
+    pub oracle_price: i128,
+}
+
+/// Event: Batch of fully-paid invoices paid out in the preferred settlement token
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct MultiTokenInvoicesSettled {
+    pub batch_id: u32,
+    pub merchant: Address,
+    pub settlement_token: Address,
+    pub total_settlement_amount: i128,
+    pub invoice_ids: Vec<u32>,
+}
+
+/// Event: Invoice cancelled and every received payment returned to its payer
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct MultiTokenInvoiceCancelled {
+    pub invoice_id: u32,
+    pub payments_refunded: u32,
+    pub refunded_in_base: i128,
+}
+
+pub fn emit_multi_token_invoice_created(
+    e: &Env,
+    invoice_id: u32,
+    merchant: Address,
+    customer: Address,
+    total_amount: i128,
+    base_currency: Address,
+    preferred_settlement_token: Address,
+) {
+    MultiTokenInvoiceCreated {
+        invoice_id,
+        merchant,
+        customer,
+        total_amount,
+        base_currency,
+        preferred_settlement_token,
+    }
+    .publish(e);
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn emit_invoice_payment_received(
+    e: &Env,
+    invoice_id: u32,
+    payment_id: u32,
+    payer: Address,
+    token: Address,
+    amount: i128,
+    amount_in_base: i128,
+    status: InvoiceStatus,
+) {
+    InvoicePaymentReceived {
+        invoice_id,
+        payment_id,
+        payer,
+        token,
+        amount,
+        amount_in_base,
+        status,
+    }
+    .publish(e);
+}
+
+pub fn emit_cross_token_settlement(
+    e: &Env,
+    invoice_id: u32,
+    paid_token: Address,
+    paid_amount: i128,
+    invoiced_token: Address,
+    invoiced_amount: i128,
+    oracle_price: i128,
+) {
+    CrossTokenSettlement {
+        invoice_id,
+        paid_token,
+        paid_amount,
+        invoiced_token,
+        invoiced_amount,
+        oracle_price,
+    }
+    .publish(e);
+}
+
+pub fn emit_multi_token_invoices_settled(
+    e: &Env,
+    batch_id: u32,
+    merchant: Address,
+    settlement_token: Address,
+    total_settlement_amount: i128,
+    invoice_ids: Vec<u32>,
+) {
+    MultiTokenInvoicesSettled {
+        batch_id,
+        merchant,
+        settlement_token,
+        total_settlement_amount,
+        invoice_ids,
+    }
+    .publish(e);
+}
+
+pub fn emit_multi_token_invoice_cancelled(
+    e: &Env,
+    invoice_id: u32,
+    payments_refunded: u32,
+    refunded_in_base: i128,
+) {
+    MultiTokenInvoiceCancelled {
+        invoice_id,
+        payments_refunded,
+        refunded_in_base,
+    }
+    .publish(e);
+}
+
+// ── Pre-Approved Spending Allowances ─────────────────────────────────────────
+
+/// Event: Customer granted a merchant a pre-approved spending allowance
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct AllowanceCreated {
+    pub allowance_id: u32,
+    pub customer: Address,
+    pub merchant: Address,
+    pub token: Address,
+    pub total_amount: i128,
+    pub expires_at: u64,
+}
+
+/// Event: Merchant pulled funds against an allowance
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct AllowanceSpent {
+    pub allowance_id: u32,
+    pub tx_id: u32,
+    pub merchant: Address,
+    pub amount: i128,
+    pub remaining: i128,
+}
+
+/// Event: Allowance paused, resumed, revoked or had its limits changed
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct AllowanceUpdated {
+    pub allowance_id: u32,
+    pub action: AuditAction,
+    pub actor: Address,
+}
+
+pub fn emit_allowance_created(
+    e: &Env,
+    allowance_id: u32,
+    customer: Address,
+    merchant: Address,
+    token: Address,
+    total_amount: i128,
+    expires_at: u64,
+) {
+    AllowanceCreated {
+        allowance_id,
+        customer,
+        merchant,
+        token,
+        total_amount,
+        expires_at,
+    }
+    .publish(e);
+}
+
+pub fn emit_allowance_spent(
+    e: &Env,
+    allowance_id: u32,
+    tx_id: u32,
+    merchant: Address,
+    amount: i128,
+    remaining: i128,
+) {
+    AllowanceSpent {
+        allowance_id,
+        tx_id,
+        merchant,
+        amount,
+        remaining,
+    }
+    .publish(e);
+}
+
+pub fn emit_allowance_updated(e: &Env, allowance_id: u32, action: AuditAction, actor: Address) {
+    AllowanceUpdated {
+        allowance_id,
+        action,
+        actor,
+    }
+    .publish(e);
+}
+
+// ── Installment Plans (BNPL) ─────────────────────────────────────────────────
+
+/// Event: Customer opened an installment plan
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct PaymentPlanCreated {
+    pub plan_id: u32,
+    pub customer: Address,
+    pub merchant: Address,
+    pub total_amount: i128,
+    pub num_installments: u32,
+    pub upfront_payout: bool,
+}
+
+/// Event: Installment pulled from the customer, with any late fee
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct InstallmentCollected {
+    pub plan_id: u32,
+    pub installment_number: u32,
+    pub amount: i128,
+    pub late_fee: i128,
+    /// 0 once the final installment has been collected.
+    pub next_due_ledger: u64,
+}
+
+/// Event: All installments paid, on schedule or by early payoff
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct PaymentPlanCompleted {
+    pub plan_id: u32,
+    pub early_payoff: bool,
+}
+
+/// Event: Installment left unpaid past the plan's default delay
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct PaymentPlanDefaulted {
+    pub plan_id: u32,
+    pub missed_installment: u32,
+    pub outstanding: i128,
+}
+
+pub fn emit_payment_plan_created(
+    e: &Env,
+    plan_id: u32,
+    customer: Address,
+    merchant: Address,
+    total_amount: i128,
+    num_installments: u32,
+    upfront_payout: bool,
+) {
+    PaymentPlanCreated {
+        plan_id,
+        customer,
+        merchant,
+        total_amount,
+        num_installments,
+        upfront_payout,
+    }
+    .publish(e);
+}
+
+pub fn emit_installment_collected(
+    e: &Env,
+    plan_id: u32,
+    installment_number: u32,
+    amount: i128,
+    late_fee: i128,
+    next_due_ledger: u64,
+) {
+    InstallmentCollected {
+        plan_id,
+        installment_number,
+        amount,
+        late_fee,
+        next_due_ledger,
+    }
+    .publish(e);
+}
+
+pub fn emit_payment_plan_completed(e: &Env, plan_id: u32, early_payoff: bool) {
+    PaymentPlanCompleted {
+        plan_id,
+        early_payoff,
+    }
+    .publish(e);
+}
+
+pub fn emit_payment_plan_defaulted(e: &Env, plan_id: u32, missed_installment: u32, outstanding: i128) {
+    PaymentPlanDefaulted {
+        plan_id,
+        missed_installment,
+        outstanding,
+    }
+    .publish(e);
+}
+
+/// Event: Admin set the keeper bounty terms
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct KeeperConfigSet {
+    pub token: Address,
+    pub bounty_per_item: i128,
+    pub max_reward_per_call: i128,
+    pub max_reward_per_ledger: i128,
+}
+
+/// Event: Admin added funds to the keeper pool
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct KeeperPoolFunded {
+    pub amount: i128,
+    pub balance: i128,
+}
+
+/// Event: Admin withdrew funds from the keeper pool
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct KeeperPoolWithdrawn {
+    pub amount: i128,
+    pub balance: i128,
+}
+
+/// Event: Keeper was paid for processing maintenance items
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct KeeperRewarded {
+    pub keeper: Address,
+    pub items: u32,
+    pub amount: i128,
+}
+
+/// Event: Admin configured the oracle set for a pair
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct OracleSetConfigured {
+    pub base: Address,
+    pub quote: Address,
+    pub oracles: Vec<Address>,
+    pub min_sources: u32,
+    pub max_deviation_bps: u32,
+}
+
+/// Event: Price for a pair was taken as the median of its oracle set
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct OraclePriceAggregated {
+    pub base: Address,
+    pub quote: Address,
+    pub price: i128,
+    pub sources: u32,
+    pub skipped: u32,
+}
+
+/// Event: An oracle source was skipped (`missing`, `stale` or `invalid`) and the remaining sources used
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct OracleFailover {
+    pub base: Address,
+    pub quote: Address,
+    pub oracle: Address,
+    pub reason: Symbol,
+}
+
+pub fn emit_keeper_config_set(
+    e: &Env,
+    token: Address,
+    bounty_per_item: i128,
+    max_reward_per_call: i128,
+    max_reward_per_ledger: i128,
+) {
+    KeeperConfigSet {
+        token,
+        bounty_per_item,
+        max_reward_per_call,
+        max_reward_per_ledger,
+    }
+    .publish(e);
+}
+
+pub fn emit_keeper_pool_funded(e: &Env, amount: i128, balance: i128) {
+    KeeperPoolFunded { amount, balance }.publish(e);
+}
+
+pub fn emit_keeper_pool_withdrawn(e: &Env, amount: i128, balance: i128) {
+    KeeperPoolWithdrawn { amount, balance }.publish(e);
+}
+
+pub fn emit_keeper_rewarded(e: &Env, keeper: Address, items: u32, amount: i128) {
+    KeeperRewarded {
+        keeper,
+        items,
+        amount,
+    }
+    .publish(e);
+}
+
+pub fn emit_oracle_set_configured(
+    e: &Env,
+    base: Address,
+    quote: Address,
+    oracles: Vec<Address>,
+    min_sources: u32,
+    max_deviation_bps: u32,
+) {
+    OracleSetConfigured {
+        base,
+        quote,
+        oracles,
+        min_sources,
+        max_deviation_bps,
+    }
+    .publish(e);
+}
+
+pub fn emit_oracle_price_aggregated(
+    e: &Env,
+    base: Address,
+    quote: Address,
+    price: i128,
+    sources: u32,
+    skipped: u32,
+) {
+    OraclePriceAggregated {
+        base,
+        quote,
+        price,
+        sources,
+        skipped,
+    }
+    .publish(e);
+}
+
+pub fn emit_oracle_failover(e: &Env, base: Address, quote: Address, oracle: Address, reason: Symbol) {
+    OracleFailover {
+        base,
+        quote,
+        oracle,
+        reason,
+    }
+    .publish(e);
+}
+
+// ── Payment Streams ──────────────────────────────────────────────────────────
+
+/// Event: Stream created and funded
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct StreamCreated {
+    pub stream_id: u32,
+    pub sender: Address,
+    pub recipient: Address,
+    pub token: Address,
+    pub deposit: i128,
+    pub rate_per_second: i128,
+}
+
+/// Event: Sender added funds to a stream
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct StreamToppedUp {
+    pub stream_id: u32,
+    pub amount: i128,
+    pub deposited: i128,
+}
+
+/// Event: Streamed funds paid to the recipient
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct StreamWithdrawn {
+    pub stream_id: u32,
+    pub recipient: Address,
+    pub amount: i128,
+    pub fee: i128,
+}
+
+/// Event: Stream cancelled and settled pro rata
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct StreamCancelled {
+    pub stream_id: u32,
+    pub cancelled_by: Address,
+    pub recipient_amount: i128,
+    pub sender_refund: i128,
+}
+
+/// Event: Recipient role moved to a new address
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct StreamRecipientChanged {
+    pub stream_id: u32,
+    pub old_recipient: Address,
+    pub new_recipient: Address,
+}
+
+pub fn emit_stream_created(
+    e: &Env,
+    stream_id: u32,
+    sender: Address,
+    recipient: Address,
+    token: Address,
+    deposit: i128,
+    rate_per_second: i128,
+) {
+    StreamCreated {
+        stream_id,
+        sender,
+        recipient,
+        token,
+        deposit,
+        rate_per_second,
+    }
+    .publish(e);
+}
+
+pub fn emit_stream_topped_up(e: &Env, stream_id: u32, amount: i128, deposited: i128) {
+    StreamToppedUp {
+        stream_id,
+        amount,
+        deposited,
+    }
+    .publish(e);
+}
+
+pub fn emit_stream_withdrawn(e: &Env, stream_id: u32, recipient: Address, amount: i128, fee: i128) {
+    StreamWithdrawn {
+        stream_id,
+        recipient,
+        amount,
+        fee,
+    }
+    .publish(e);
+}
+
+pub fn emit_stream_cancelled(
+    e: &Env,
+    stream_id: u32,
+    cancelled_by: Address,
+    recipient_amount: i128,
+    sender_refund: i128,
+) {
+    StreamCancelled {
+        stream_id,
+        cancelled_by,
+        recipient_amount,
+        sender_refund,
+    }
+    .publish(e);
+}
+
+pub fn emit_stream_recipient_changed(
+    e: &Env,
+    stream_id: u32,
+    old_recipient: Address,
+    new_recipient: Address,
+) {
+    StreamRecipientChanged {
+        stream_id,
+        old_recipient,
+        new_recipient,
+    }
+    .publish(e);
+}
+
+// ── Subscription Plan Catalog ────────────────────────────────────────────────
+
+/// Event: Merchant defined a subscription plan
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct SubscriptionPlanCreated {
+    pub plan_id: u32,
+    pub merchant: Address,
+    pub price: i128,
+    pub interval_seconds: u64,
+}
+
+/// Event: Plan price change scheduled; notice for existing subscribers
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct PlanPriceChangeScheduled {
+    pub plan_id: u32,
+    pub old_price: i128,
+    pub new_price: i128,
+    pub effective_at: u64,
+}
+
+/// Event: Subscription moved between plans with a prorated charge or credit
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct SubscriptionPlanChanged {
+    pub subscription_id: u32,
+    pub old_plan_id: u32,
+    pub new_plan_id: u32,
+    pub charged: i128,
+    /// Credit held against future charges after the change.
+    pub credit: i128,
+}
+
+pub fn emit_subscription_plan_created(
+    e: &Env,
+    plan_id: u32,
+    merchant: Address,
+    price: i128,
+    interval_seconds: u64,
+) {
+    SubscriptionPlanCreated {
+        plan_id,
+        merchant,
+        price,
+        interval_seconds,
+    }
+    .publish(e);
+}
+
+pub fn emit_plan_price_change_scheduled(
+    e: &Env,
+    plan_id: u32,
+    old_price: i128,
+    new_price: i128,
+    effective_at: u64,
+) {
+    PlanPriceChangeScheduled {
+        plan_id,
+        old_price,
+        new_price,
+        effective_at,
+    }
+    .publish(e);
+}
+
+pub fn emit_subscription_plan_changed(
+    e: &Env,
+    subscription_id: u32,
+    old_plan_id: u32,
+    new_plan_id: u32,
+    charged: i128,
+    credit: i128,
+) {
+    SubscriptionPlanChanged {
+        subscription_id,
+        old_plan_id,
+        new_plan_id,
+        charged,
+        credit,
+    }
+    .publish(e);
+}
+
+// ── Metered Subscriptions ────────────────────────────────────────────────────
+
+/// Event: Merchant set or replaced a subscription's usage price table
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct MeteredPricingConfigured {
+    pub subscription_id: u32,
+    pub merchant: Address,
+    pub tier_count: u32,
+    /// First billing period priced with the table.
+    pub effective_period: u32,
+}
+
+/// Event: Subscriber changed the ceiling on a period's metered charge
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct UsageCapSet {
+    pub subscription_id: u32,
+    pub max_per_period: i128,
+}
+
+/// Event: Usage recorded against a subscription's open period
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct UsageReported {
+    pub subscription_id: u32,
+    pub period: u32,
+    pub record_id: u32,
+    pub quantity: i128,
+    pub reported_by: Address,
+}
+
+/// Event: Metered period closed and priced at charge time
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct UsagePeriodCharged {
+    pub subscription_id: u32,
+    pub period: u32,
+    pub usage: i128,
+    pub priced: i128,
+    pub charged: i128,
+}
+
+pub fn emit_metered_pricing_configured(
+    e: &Env,
+    subscription_id: u32,
+    merchant: Address,
+    tier_count: u32,
+    effective_period: u32,
+) {
+    MeteredPricingConfigured {
+        subscription_id,
+        merchant,
+        tier_count,
+        effective_period,
+    }
+    .publish(e);
+}
+
+pub fn emit_usage_cap_set(e: &Env, subscription_id: u32, max_per_period: i128) {
+    UsageCapSet {
+        subscription_id,
+        max_per_period,
+    }
+    .publish(e);
+}
+
+pub fn emit_usage_reported(
+    e: &Env,
+    subscription_id: u32,
+    period: u32,
+    record_id: u32,
+    quantity: i128,
+    reported_by: Address,
+) {
+    UsageReported {
+        subscription_id,
+        period,
+        record_id,
+        quantity,
+        reported_by,
+    }
+    .publish(e);
+}
+
+pub fn emit_usage_period_charged(
+    e: &Env,
+    subscription_id: u32,
+    period: u32,
+    usage: i128,
+    priced: i128,
+    charged: i128,
+) {
+    UsagePeriodCharged {
+        subscription_id,
+        period,
+        usage,
+        priced,
+        charged,
+    }
+    .publish(e);
+}
+
+// ── Subscription Dunning ─────────────────────────────────────────────────────
+
+/// Event: Merchant set its retry ladder for failed subscription charges (0 retries = off)
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct DunningConfigured {
+    pub merchant: Address,
+    pub retries: u32,
+    pub grace_period_seconds: u64,
+}
+
+/// Event: Subscription charge failed; drives payment reminders off-chain
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct SubscriptionPastDue {
+    pub subscription_id: u32,
+    pub subscriber: Address,
+    pub merchant: Address,
+    pub amount_due: i128,
+    /// Failed attempts so far, including the original charge.
+    pub failed_attempts: u32,
+    pub next_retry_at: u64,
+    pub grace_ends_at: u64,
+}
+
+/// Event: Past-due subscription charged successfully on a retry
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct SubscriptionRecovered {
+    pub subscription_id: u32,
+    pub amount: i128,
+    /// Retries that failed before this one succeeded.
+    pub failed_retries: u32,
+}
+
+/// Event: Subscription cancelled after its last retry failed
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct SubscriptionDunningCancelled {
+    pub subscription_id: u32,
+    pub subscriber: Address,
+    pub merchant: Address,
+    pub amount_due: i128,
+    pub failed_attempts: u32,
+}
+
+pub fn emit_dunning_configured(
+    e: &Env,
+    merchant: Address,
+    retries: u32,
+    grace_period_seconds: u64,
+) {
+    DunningConfigured {
+        merchant,
+        retries,
+        grace_period_seconds,
+    }
+    .publish(e);
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn emit_subscription_past_due(
+    e: &Env,
+    subscription_id: u32,
+    subscriber: Address,
+    merchant: Address,
+    amount_due: i128,
+    failed_attempts: u32,
+    next_retry_at: u64,
+    grace_ends_at: u64,
+) {
+    SubscriptionPastDue {
+        subscription_id,
+        subscriber,
+        merchant,
+        amount_due,
+        failed_attempts,
+        next_retry_at,
+        grace_ends_at,
+    }
+    .publish(e);
+}
+
+pub fn emit_subscription_recovered(
+    e: &Env,
+    subscription_id: u32,
+    amount: i128,
+    failed_retries: u32,
+) {
+    SubscriptionRecovered {
+        subscription_id,
+        amount,
+        failed_retries,
+    }
+    .publish(e);
+}
+
+pub fn emit_subscription_dunning_cancelled(
+    e: &Env,
+    subscription_id: u32,
+    subscriber: Address,
+    merchant: Address,
+    amount_due: i128,
+    failed_attempts: u32,
+) {
+    SubscriptionDunningCancelled {
+        subscription_id,
+        subscriber,
+        merchant,
+        amount_due,
+        failed_attempts,
+    }
+    .publish(e);
+}
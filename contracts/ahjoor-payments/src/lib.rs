@@ -2,8 +2,8 @@
 use ahjoor_token_whitelist::TokenWhitelistClient;
 use soroban_sdk::xdr::ToXdr;
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, panic_with_error, token, Address, Bytes,
-    BytesN, Env, Map, String, Symbol, Vec,
+    contract, contractimpl, contracttype, panic_with_error, symbol_short, token, vec, Address,
+    Bytes, BytesN, Env, Map, String, Symbol, Vec,
 };
 
 pub mod multi_token_invoice;
@@ -20,41 +20,10 @@ const MAX_METADATA_KEY_LEN: u32 = 32;
 /// Maximum length (bytes) for merchant notification key.
 const MAX_NOTIFICATION_KEY_LEN: u32 = 128;
 
-// ---------------------------------------------------------------------------
-// Reflector-compatible oracle interface.
-// lastprice(base, quote) returns Option<PriceData> where price is scaled by
-// 10^decimals(). We call it via a generated client.
-// ---------------------------------------------------------------------------
-#[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct PriceData {
-    /// Price scaled by 10^7 (Reflector standard precision)
-    pub price: i128,
-    /// Ledger timestamp of the price update
-    pub timestamp: u64,
-}
-
-/// Minimal oracle client — only the method we need.
-mod oracle {
-    use crate::PriceData;
-    use soroban_sdk::{contractclient, Address, Env};
-
-    #[allow(dead_code)]
-    #[contractclient(name = "OracleClient")]
-    pub trait OracleInterface {
-        fn lastprice(env: Env, base: Address, quote: Address) -> Option<PriceData>;
-    }
-}
-
-mod refund_reserve {
-    use soroban_sdk::{contractclient, Address, Env};
-
-    #[allow(dead_code)]
-    #[contractclient(name = "RefundReserveClient")]
-    pub trait RefundReserveInterface {
-        fn check_merchant_reserve(env: Env, merchant: Address) -> bool;
-    }
-}
+// Cross-contract types (payment schema, Reflector oracle and refund-reserve
+// clients) live in `ahjoor-types` so every contract decodes the same XDR.
+pub use ahjoor_types::{KeeperConfig, KeeperPool, Payment, PaymentStatus, PriceData, SplitRecipient};
+use ahjoor_types::{oracle, refund_reserve};
 
 // --- Storage TTL Constants ---
 // Instance storage: counters and config (shared TTL with contract instance)
@@ -89,6 +58,8 @@ const DEFAULT_MIN_SLIPPAGE_BPS: u32 = 0;
 const DEFAULT_MAX_SLIPPAGE_BPS: u32 = 10_000;
 /// Reflector oracle price precision: prices are scaled by 10^7
 const ORACLE_PRICE_PRECISION: i128 = 10_000_000;
+/// Maximum number of oracles in a pair's oracle set
+const MAX_ORACLE_SOURCES: u32 = 10;
 /// Ledger sequences per weekly bucket (~7 days at 5s/ledger = 120_960 ledgers)
 const LEDGER_BUCKET_SIZE: u32 = 120_960;
 /// Maximum protocol fee: 500 bps = 5%
@@ -119,78 +90,8 @@ const DEFAULT_MAX_EXTENSION_LEDGERS: u32 = 30 * 24 * 60 * 60 / 5;
 /// Default maximum number of extensions per payment
 const DEFAULT_MAX_EXTENSIONS: u32 = 3;
 
-#[contracterror]
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub enum Error {
-    RateLimitExceeded = 1,
-    SubscriptionPaused = 2,
-    OracleConditionNotMet = 3,
-    /// Subscription's trial period has not elapsed; charging is deferred (#133)
-    SubscriptionInTrial = 4,
-    MerchantVolumeCapped = 20,
-    TokenNotAllowed = 5,
-    DuplicateExternalId = 6,
-    MultisigNotRequired = 7,
-    AlreadyApproved = 8,
-    NotASigner = 9,
-    VoucherExpired = 10,
-    VoucherExhausted = 11,
-    VoucherRevoked = 12,
-    VoucherNotFound = 13,
-    WithdrawalRateLimitExceeded = 14,
-    /// Referred merchant already has a merchant record (#242)
-    ReferralAlreadyExists = 15,
-    /// No pending commission to claim (#242)
-    NoCommissionToClaim = 16,
-    /// Slippage tolerance exceeded on dynamic payment settlement (#246)
-    SlippageExceeded = 21,
-    /// Oracle address is not on the admin whitelist (#246)
-    OracleNotWhitelisted = 22,
-    /// Dynamic payment has expired (#246)
-    DynamicPaymentExpired = 17,
-    /// Customer cumulative spend would exceed the merchant-configured cap (#235)
-    CustomerSpendLimitExceeded = 23,
-    /// Capture attempted after the authorized capture deadline ledger.
-    CapturePastDeadline = 24,
-    /// Tip supplied on a payment that does not have tipping_enabled (#265)
-    TippingNotEnabled = 18,
-    /// Tip amount exceeds the admin-configured maximum tip bps of the base amount (#265)
-    TipExceedsMaxBps = 19,
-    /// Evidence submission window has closed (#308)
-    EvidenceWindowClosed = 25,
-    /// Evidence submission limit reached for this party (#308)
-    EvidenceLimitReached = 26,
-    /// Cooling-off period has expired (#309)
-    CoolingOffExpired = 27,
-    /// Payment not in cooling-off status (#309)
-    NotInCoolingOff = 28,
-    /// Cooling-off period exceeds maximum allowed (#309)
-    CoolingOffExceedsMax = 29,
-    /// KYB verification required but merchant not verified (#310)
-    KYBVerificationRequired = 33,
-    /// retry_failed_debit called before back-off interval has elapsed (#329)
-    RetryNotDue = 34,
-    /// Failed debit record not found (#329)
-    DebitRecordNotFound = 35,
-    /// Debit record is already abandoned; no further retries (#329)
-    DebitAlreadyAbandoned = 36,
-    /// Debit record already succeeded; no retry needed (#329)
-    DebitAlreadySucceeded = 37,
-    /// Payment is not in a pending state and cannot be extended
-    InvalidPaymentStatus = 38,
-    /// Maximum number of extensions reached for this payment
-    MaxExtensionsReached = 39,
-    /// Additional ledgers exceed the maximum allowed per extension
-    MaxExtensionLedgersExceeded = 40,
-    /// Subscription pause count exceeded (#327)
-    PauseCountExceeded = 30,
-    /// Unauthorized to pause subscription (#327)
-    UnauthorizedPause = 31,
-    /// Merchant refund reserve is below the configured minimum (#334)
-    InsufficientMerchantReserve = 32,
-    /// Customer is blocked by merchant
-    CustomerBlocked = 50,
-}
+mod errors;
+pub use errors::{ConfigError, Error, MerchantError, PaymentError, SubscriptionError};
 
 /// Per-merchant withdrawal rate limit config (#231).
 #[contracttype]
@@ -331,31 +232,6 @@ pub struct OracleCondition {
     pub direction: OracleDirection,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-#[contracttype]
-pub enum PaymentStatus {
-    Pending = 0,
-    Completed = 1,
-    Refunded = 2,
-    Disputed = 3,
-    Expired = 4,
-    Authorized = 5,
-    ScheduledPending = 6,
-    /// Awaiting M-of-N multi-sig approval before proceeding.
-    PendingApproval = 7,
-    /// Payment completed; in cooling-off window before final settlement (#309)
-    CoolingOff = 8,
-    /// Payment cancelled during cooling-off period (#309)
-    CancelledInCoolingOff = 9,
-}
-
-#[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct SplitRecipient {
-    pub recipient: Address,
-    pub bps: u32,
-}
-
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct SplitTransfer {
@@ -389,44 +265,6 @@ pub struct InvoiceData {
     pub currency_label: Symbol,
 }
 
-#[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct Payment {
-    pub id: u32,
-    pub customer: Address,
-    pub merchant: Address,
-    pub amount: i128,
-    pub token: Address,
-    pub status: PaymentStatus,
-    pub created_at: u64,
-    /// Ledger timestamp after which the payment can be expired. 0 = no expiry.
-    pub expires_at: u64,
-    /// Cumulative amount refunded via partial refunds.
-    pub refunded_amount: i128,
-    /// Optional merchant reference string (max 64 bytes) for off-chain reconciliation.
-    pub reference: Option<String>,
-    /// Optional key-value metadata (max 5 keys, each max 32 bytes).
-    pub metadata: Option<Map<String, String>>,
-    /// Optional recipient split definitions (must sum to 10,000 bps).
-    pub split_recipients: Option<Vec<SplitRecipient>>,
-    /// Optional execution timestamp for scheduled payments. 0 = immediate.
-    pub execute_after: u64,
-    /// Optional payment category for on-chain segmentation (#122)
-    pub category: Option<Symbol>,
-    /// Optional tags (max 3) immutable after creation (#122)
-    pub tags: Option<Vec<Symbol>>,
-    /// Ledger sequence after which an authorized payment can no longer be captured. 0 = not authorized.
-    pub capture_deadline: u64,
-    // Optional oracle price condition required for completion (#125)
-    // pub release_condition: Option<OracleCondition>,
-    /// Optional off-chain order correlation key (hash of merchant order ID).
-    pub external_id: Option<BytesN<32>>,
-    /// Whether this payment accepts a customer tip at settlement time (#265).
-    pub tipping_enabled: bool,
-    /// Number of times this payment's expiry has been extended.
-    pub extension_count: u32,
-}
-
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct BlockEntry {
@@ -545,6 +383,13 @@ const DEFAULT_MAX_PAYMENT_EXPIRY: u64 = 30 * 24 * 60 * 60;
 /// Maximum allowed `page_size` for `get_customer_payments_page` (#132).
 pub const MAX_CUSTOMER_PAYMENTS_PAGE_SIZE: u32 = 50;
 
+/// Default installment grace period: ~1 day of ledgers.
+const DEFAULT_PLAN_GRACE_LEDGERS: u32 = 17_280;
+/// Default late fee on an overdue installment: 5%.
+const DEFAULT_PLAN_LATE_FEE_BPS: u32 = 500;
+/// Default delay after a missed due date before a plan can be defaulted: ~7 days.
+const DEFAULT_PLAN_DEFAULT_AFTER_LEDGERS: u32 = 120_960;
+
 /// Max pauses per subscription before denial (#327)
 const MAX_SUBSCRIPTION_PAUSES: u32 = 3;
 
@@ -581,8 +426,136 @@ pub struct Subscription {
     pub pause_authority: PauseAuthority,
     /// Count of times this subscription has been paused (#327)
     pub pause_count: u32,
+    /// Catalog plan the subscription follows (0 = custom amount and interval)
+    pub plan_id: u32,
+    /// Credit from plan downgrades, applied to the next charges
+    pub credit: i128,
+    /// When the first charge of the current dunning run failed (0 = in good standing)
+    pub past_due_since: u64,
+    /// Retries that have failed in the current dunning run
+    pub dunning_attempts: u32,
+    /// Earliest time the past-due charge may be retried
+    pub next_retry_at: u64,
+    /// Service stays active until this time while past due
+    pub grace_ends_at: u64,
+    /// Retry offsets of the merchant's ladder when the current dunning run began
+    pub dunning_ladder: Vec<u64>,
+}
+
+/// Billing standing of a subscription, derived from its state.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SubscriptionStatus {
+    Active = 0,
+    Paused = 1,
+    /// A charge failed and is being retried; service continues during the grace period.
+    PastDue = 2,
+    /// Past due beyond the grace period; retries continue until the ladder runs out.
+    Unpaid = 3,
+    Cancelled = 4,
+}
+
+/// Merchant's dunning policy for subscriptions whose charge fails.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DunningConfig {
+    /// Retry times in seconds after the first failure, ascending. The
+    /// subscription is cancelled when the last retry fails.
+    pub retry_offsets: Vec<u64>,
+    pub grace_period_seconds: u64,
+}
+
+const MAX_DUNNING_RETRIES: u32 = 10;
+
+/// Merchant-defined subscription product. Subscriptions on a plan are
+/// charged the plan's current price.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SubscriptionPlan {
+    pub plan_id: u32,
+    pub merchant: Address,
+    pub token: Address,
+    pub price: i128,
+    pub interval_seconds: u64,
+    /// Trial given to new subscribers before the first charge (0 = none).
+    pub trial_seconds: u64,
+    /// Charges before a subscription on the plan ends (0 = unlimited).
+    pub max_charges: u32,
+    /// Inactive plans take no new subscribers.
+    pub active: bool,
+    /// Price that replaces `price` at `pending_price_at`.
+    pub pending_price: i128,
+    /// When the scheduled price change applies (0 = none scheduled).
+    pub pending_price_at: u64,
+}
+
+/// Least notice a merchant must give before a plan price change applies.
+const PLAN_PRICE_NOTICE_SECONDS: u64 = 7 * 24 * 60 * 60;
+
+/// How usage is priced against a metered price table.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MeteredPricingMode {
+    /// Each unit is priced by the tier it falls in.
+    Tiered = 0,
+    /// Every unit is priced by the tier the period's total falls in.
+    Volume = 1,
+}
+
+/// One band of a metered price table.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UsagePriceTier {
+    /// Last unit in the band (0 = unbounded; last tier only).
+    pub up_to: i128,
+    pub unit_price: i128,
+}
+
+/// Usage-based pricing for a subscription, charged in arrears for the usage
+/// reported since the previous charge.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MeteredPricing {
+    pub mode: MeteredPricingMode,
+    /// Bands in ascending `up_to` order.
+    pub tiers: Vec<UsagePriceTier>,
+    /// Subscriber-set ceiling on a period's charge. Usage is only accepted once set.
+    pub max_per_period: i128,
+    /// When metering began; the first period ends one interval later.
+    pub started_at: u64,
+    /// Replacement table, priced from `pending_from_period` on (empty = none).
+    pub pending_tiers: Vec<UsagePriceTier>,
+    pub pending_mode: MeteredPricingMode,
+    pub pending_from_period: u32,
+}
+
+/// Usage reported against a subscription's open period.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UsageRecord {
+    pub record_id: u32,
+    pub quantity: i128,
+    pub reported_by: Address,
+    pub reported_at: u64,
 }
 
+/// Usage and charge for one billing period of a metered subscription.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UsagePeriod {
+    pub period: u32,
+    pub usage: i128,
+    /// Price of the usage before the subscriber's cap.
+    pub priced: i128,
+    /// Amount charged after the cap (for an open period, what would be charged now).
+    pub charged: i128,
+    /// When the period was charged (0 = still open).
+    pub charged_at: u64,
+}
+
+const MAX_USAGE_PRICE_TIERS: u32 = 10;
+const MAX_USAGE_RECORDS_PER_PERIOD: u32 = 200;
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct RateLimitConfig {
@@ -615,7 +588,12 @@ pub struct DynamicPayment {
     pub expiry: u64,
 }
 
-/// Installment payment plan record.
+/// Installment payment plan record (buy-now-pay-later).
+///
+/// The financed amount (`total_amount - down_payment`) is split into
+/// `num_installments` equal installments pulled from the customer's token
+/// allowance every `interval_ledgers`; the final installment absorbs any
+/// rounding remainder.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PaymentPlan {
@@ -643,6 +621,21 @@ pub struct PaymentPlan {
     pub installments_paid: u32,
     /// Status of the plan.
     pub status: PlanStatus,
+    /// Amount paid up front at plan creation.
+    pub down_payment: i128,
+    /// Principal paid so far, including the down payment.
+    pub amount_paid: i128,
+    /// Late fees collected so far.
+    pub late_fees_paid: i128,
+    /// Ledgers after a due date during which an installment incurs no late fee.
+    pub grace_period_ledgers: u32,
+    /// Late fee in bps of the overdue installment, charged once grace lapses.
+    pub late_fee_bps: u32,
+    /// Ledgers after a due date after which the plan can be defaulted.
+    pub default_after_ledgers: u32,
+    /// True when the merchant was paid the financed amount up front from
+    /// their plan pool; installments then replenish that pool.
+    pub upfront_payout: bool,
 }
 
 /// Status of an installment plan.
@@ -653,8 +646,137 @@ pub enum PlanStatus {
     Paused = 1,
     Completed = 2,
     Expired = 3,
+    /// An installment stayed unpaid past `default_after_ledgers`.
+    Defaulted = 4,
+}
+
+/// Merchant-configured late-payment terms applied to new payment plans.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlanTerms {
+    pub grace_period_ledgers: u32,
+    pub late_fee_bps: u32,
+    pub default_after_ledgers: u32,
+}
+
+/// One outstanding installment in a payment plan schedule.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlanInstallment {
+    /// 1-based installment number.
+    pub installment_number: u32,
+    pub due_ledger: u64,
+    pub amount: i128,
+}
+
+/// Oracle sources for a base/quote pair. Prices are the median of the
+/// sources that answer with a fresh, positive price.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OracleSet {
+    pub oracles: Vec<Address>,
+    /// Fewest fresh sources a price may be built from.
+    pub min_sources: u32,
+    /// Furthest any fresh source may sit from the median, in bps.
+    pub max_deviation_bps: u32,
+}
+
+/// Status tier of a merchant loyalty program, reached by rolling spend.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoyaltyTier {
+    /// Rolling spend with the merchant at which the tier starts.
+    pub min_spend: i128,
+    /// Multiplier on the base earn rate, in bps (10_000 = 1x).
+    pub multiplier_bps: u32,
+}
+
+/// A merchant's own loyalty program.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoyaltyProgram {
+    pub merchant: Address,
+    /// Points earned per 1_000_000 units paid, before the tier multiplier.
+    pub points_per_unit: u32,
+    /// Discount in basis points of one token unit per point redeemed.
+    pub redemption_rate_bps: u32,
+    /// Lowest amount a payment to this merchant may be discounted to.
+    pub min_payment_floor: i128,
+    /// Ledgers a lot lives after the period it was earned in (0 = never expires).
+    pub lot_expiry_ledgers: u32,
+    /// Ledgers of spend counted towards a customer's tier.
+    pub tier_window_ledgers: u32,
+    /// Tiers in ascending `min_spend` order.
+    pub tiers: Vec<LoyaltyTier>,
+    /// Coalition the program belongs to (0 = none).
+    pub coalition_id: u32,
+}
+
+/// Points earned at one program that expire together.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PointLot {
+    pub points: i128,
+    /// Ledger from which the lot is expired (0 = never).
+    pub expires_at_ledger: u32,
+}
+
+/// Spend recorded with a merchant in one slice of the tier window.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpendBucket {
+    pub start_ledger: u32,
+    pub amount: i128,
+}
+
+/// Merchants whose loyalty points can be redeemed at one another. The
+/// issuing merchant owes the redeeming merchant the discount it granted.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoyaltyCoalition {
+    pub id: u32,
+    /// Member who may invite new merchants.
+    pub owner: Address,
+    /// Token cross-merchant redemptions and settlements are made in.
+    pub token: Address,
+    pub members: Vec<Address>,
+}
+
+/// A customer's standing in one merchant loyalty program.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoyaltyProgramBalance {
+    pub merchant: Address,
+    /// Unexpired points across all lots.
+    pub points: i128,
+    /// Tier reached, counted from 1 (0 = base rate).
+    pub tier: u32,
+    pub rolling_spend: i128,
+    /// Ledger the next lot expires at (0 = nothing expiring).
+    pub next_expiry_ledger: u32,
+    /// Points in the lots expiring at `next_expiry_ledger`.
+    pub expiring_points: i128,
+    pub coalition_id: u32,
 }
 
+/// Per-program breakdown of a customer's loyalty points.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoyaltyBalance {
+    /// Points in the platform-wide program set by `configure_loyalty`.
+    pub platform_points: i128,
+    pub programs: Vec<LoyaltyProgramBalance>,
+}
+
+/// Slices a lot expiry period or tier window is divided into. Points earned
+/// in the same slice share a lot, so a customer holds at most this many live
+/// lots per program.
+const LOYALTY_WINDOW_SLICES: u32 = 12;
+const MAX_LOYALTY_TIERS: u32 = 10;
+const MAX_COALITION_MEMBERS: u32 = 20;
+/// A point is never worth more than one unit of the payment token.
+const MAX_REDEMPTION_RATE_BPS: u32 = 10_000;
+
 /// #351: On-chain recurring payment schedule.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -675,6 +797,42 @@ pub struct RecurringPaymentSchedule {
     pub active: bool,
 }
 
+/// Lifecycle of a payment stream.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum StreamStatus {
+    Active = 0,
+    /// Settled pro rata by the sender or recipient.
+    Cancelled = 1,
+    /// Reached its end time and fully withdrawn.
+    Completed = 2,
+}
+
+/// Continuous per-second token stream funded up front by the sender.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentStream {
+    pub stream_id: u32,
+    pub sender: Address,
+    pub recipient: Address,
+    pub token: Address,
+    /// Tokens streamed per second from `start_time`.
+    pub rate_per_second: i128,
+    pub start_time: u64,
+    /// Amount already streamed at `start_time`; set when a dry open-ended
+    /// stream is topped up and accrual restarts.
+    pub streamed_at_start: i128,
+    /// Nothing is streamed before the cliff; accrual then counts from `start_time`.
+    pub cliff_time: u64,
+    /// Time streaming stops (0 = runs until the deposit is streamed).
+    pub end_time: u64,
+    /// Total deposited, including top-ups.
+    pub deposited: i128,
+    /// Gross amount paid out to recipients, fees included.
+    pub withdrawn: i128,
+    pub status: StreamStatus,
+}
+
 /// Per-merchant volume cap configuration (#131)
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -981,6 +1139,58 @@ pub enum DataKey3 {
     NotificationKeyHistory(Address),
     /// #377: notification key rotation config
     NotificationKeyRotationConfig,
+    /// Instance: counter for installment payment plans
+    PaymentPlanCounter,
+    /// Persistent: late-payment terms for a merchant's payment plans
+    PlanTerms(Address),
+    /// Persistent: merchant-funded upfront payout pool (merchant, token) → i128
+    PlanPool(Address, Address),
+    /// Instance: keeper bounty terms → KeeperConfig
+    KeeperConfig,
+    /// Instance: keeper pool balance and per-ledger tally → KeeperPool
+    KeeperPool,
+    /// Instance: oracle set aggregated for a pair (base, quote) → OracleSet
+    OracleSet(Address, Address),
+    /// Persistent: merchant loyalty program (merchant) → LoyaltyProgram
+    LoyaltyProgram(Address),
+    /// Persistent: live point lots (customer, merchant) → Vec<PointLot>
+    PointLots(Address, Address),
+    /// Persistent: rolling spend slices (customer, merchant) → Vec<SpendBucket>
+    LoyaltySpend(Address, Address),
+    /// Persistent: programs a customer has earned in (customer) → Vec<Address>
+    CustomerPrograms(Address),
+    /// Instance: last coalition id issued
+    CoalitionCounter,
+    /// Persistent: coalition record (coalition_id) → LoyaltyCoalition
+    Coalition(u32),
+    /// Persistent: pending invite (coalition_id, merchant) → bool
+    CoalitionInvite(u32, Address),
+    /// Persistent: unsettled amount (coalition_id, debtor, creditor) → i128
+    CoalitionDebt(u32, Address, Address),
+    /// Persistent: total unsettled amount owed by a member (coalition_id, debtor) → i128
+    CoalitionOwed(u32, Address),
+    /// Persistent: coalition token a member holds for redemptions at other members (coalition_id, merchant) → i128
+    CoalitionReserve(u32, Address),
+    /// Instance: last stream id issued
+    StreamCounter,
+    /// Persistent: payment stream record (stream_id) → PaymentStream
+    Stream(u32),
+    /// Instance: last subscription plan id issued
+    SubscriptionPlanCounter,
+    /// Persistent: subscription plan record (plan_id) → SubscriptionPlan
+    SubscriptionPlan(u32),
+    /// Persistent: subscriptions created on or moved to a plan (plan_id) → Vec<u32>
+    PlanSubscribers(u32),
+    /// Persistent: usage-based pricing (subscription_id) → MeteredPricing
+    MeteredPricing(u32),
+    /// Persistent: address allowed to report usage for a merchant (merchant, reporter) → bool
+    UsageReporter(Address, Address),
+    /// Persistent: usage reported in a period (subscription_id, period) → Vec<UsageRecord>
+    UsageRecords(u32, u32),
+    /// Persistent: charged period summary (subscription_id, period) → UsagePeriod
+    UsagePeriod(u32, u32),
+    /// Persistent: merchant's retry ladder for failed subscription charges → DunningConfig
+    DunningConfig(Address),
 }
 
 mod events;
@@ -1068,11 +1278,11 @@ impl AhjoorPaymentsContract {
     /// fee_bps: Protocol fee in basis points (max 500 = 5%)
     pub fn initialize(env: Env, admin: Address, fee_recipient: Address, fee_bps: u32) {
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("Already initialized");
+            panic_with_error!(&env, Error::AlreadyInitialized);
         }
 
         if fee_bps > MAX_FEE_BPS {
-            panic!("Fee cannot exceed 500 bps (5%)");
+            panic_with_error!(&env, ConfigError::FeeTooHigh);
         }
 
         // Instance: config and counters
@@ -1268,13 +1478,13 @@ impl AhjoorPaymentsContract {
         Self::enforce_rate_limit(&env, &customer, 1);
 
         if amount <= 0 {
-            panic!("Payment amount must be positive");
+            panic_with_error!(&env, Error::InvalidAmount);
         }
 
         // Validate optional reference and metadata (#67)
         Self::validate_reference(&env, &reference);
         Self::validate_metadata(&env, &metadata);
-        Self::validate_split_recipients(&split_recipients);
+        Self::validate_split_recipients(&env, &split_recipients);
 
         // Token whitelist validation
         Self::require_token_allowed(&env, &token);
@@ -1293,7 +1503,7 @@ impl AhjoorPaymentsContract {
                 .storage()
                 .persistent()
                 .get(&DataKey2::CustomerBlockEntry(merchant.clone(), customer.clone()))
-                .expect("block entry");
+                .unwrap_or_else(|| panic_with_error!(&env, Error::CustomerBlocked));
             let now_ledger = env.ledger().sequence();
             let max_age: u32 = env
                 .storage()
@@ -1448,10 +1658,10 @@ impl AhjoorPaymentsContract {
     pub fn resolve_unblock_request(env: Env, admin: Address, request_id: u32, approved: bool) {
         Self::require_not_paused(&env);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).expect("Not initialized");
-        if admin != stored_admin { panic!("Only admin"); }
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
+        if admin != stored_admin { panic_with_error!(&env, Error::Unauthorized); }
 
-        let req: UnblockRequest = env.storage().persistent().get(&DataKey2::UnblockRequest(request_id)).expect("Request not found");
+        let req: UnblockRequest = env.storage().persistent().get(&DataKey2::UnblockRequest(request_id)).unwrap_or_else(|| panic_with_error!(&env, MerchantError::UnblockRequestNotFound));
         if approved {
             env.storage().persistent().remove(&DataKey2::CustomerBlockEntry(req.merchant.clone(), req.customer.clone()));
             events::emit_customer_unblocked(&env, req.merchant.clone(), req.customer.clone(), admin);
@@ -1479,7 +1689,7 @@ impl AhjoorPaymentsContract {
 
         let batch_len = payments.len();
         if batch_len == 0 {
-            panic!("Batch cannot be empty");
+            panic_with_error!(&env, PaymentError::EmptyBatch);
         }
         Self::enforce_rate_limit(&env, &customer, batch_len);
 
@@ -1490,7 +1700,7 @@ impl AhjoorPaymentsContract {
             .unwrap_or(DEFAULT_MAX_BATCH_SIZE);
 
         if batch_len > max_batch_size {
-            panic!("Batch size exceeds maximum allowed");
+            panic_with_error!(&env, PaymentError::BatchTooLarge);
         }
 
         let mut payment_ids = Vec::new(&env);
@@ -1505,7 +1715,7 @@ impl AhjoorPaymentsContract {
 
         for request in payments.iter() {
             if request.amount <= 0 {
-                panic!("Payment amount must be positive");
+                panic_with_error!(&env, Error::InvalidAmount);
             }
 
             Self::validate_reference(&env, &request.reference);
@@ -1590,17 +1800,19 @@ impl AhjoorPaymentsContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         admin.require_auth();
 
         Self::complete_payment_internal(&env, payment_id, false);
     }
 
-    /// Execute a scheduled payment once execute_after has passed. Callable by anyone.
-    pub fn execute_scheduled_payment(env: Env, payment_id: u32) {
+    /// Execute a scheduled payment once execute_after has passed. Callable by anyone;
+    /// `keeper` receives the keeper bounty if a keeper pool is configured.
+    pub fn execute_scheduled_payment(env: Env, keeper: Address, payment_id: u32) {
         Self::require_not_paused(&env);
         Self::complete_payment_internal(&env, payment_id, true);
         events::emit_scheduled_payment_executed(&env, payment_id);
+        Self::pay_keeper_reward(&env, &keeper, 1);
     }
 
     /// Customer can cancel a scheduled payment before execution and get refunded.
@@ -1612,16 +1824,16 @@ impl AhjoorPaymentsContract {
             .storage()
             .persistent()
             .get(&DataKey::Payment(payment_id))
-            .expect("Payment not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::PaymentNotFound));
 
         if payment.customer != customer {
-            panic!("Only payment customer can cancel scheduled payment");
+            panic_with_error!(&env, PaymentError::NotPaymentCustomer);
         }
         if payment.status != PaymentStatus::ScheduledPending {
-            panic!("Payment is not scheduled");
+            panic_with_error!(&env, PaymentError::PaymentNotScheduled);
         }
         if env.ledger().timestamp() >= payment.execute_after {
-            panic!("Scheduled payment is ready to execute");
+            panic_with_error!(&env, PaymentError::ScheduledPaymentDue);
         }
 
         let client = token::Client::new(&env, &payment.token);
@@ -1661,24 +1873,24 @@ impl AhjoorPaymentsContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         if admin != stored_admin {
-            panic!("Only admin can settle merchant payments");
+            panic_with_error!(&env, Error::Unauthorized);
         }
 
         let batch_size = payment_ids.len();
         if batch_size == 0 {
-            panic!("Settlement batch cannot be empty");
+            panic_with_error!(&env, PaymentError::EmptyBatch);
         }
         if batch_size > MAX_SETTLEMENT_BATCH_SIZE {
-            panic!("Settlement batch size exceeds maximum allowed");
+            panic_with_error!(&env, PaymentError::BatchTooLarge);
         }
 
         let first_payment: Payment = env
             .storage()
             .persistent()
             .get(&DataKey::Payment(payment_ids.get(0).unwrap()))
-            .expect("Payment not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::PaymentNotFound));
         let settlement_token = first_payment.token.clone();
 
         let mut total_amount: i128 = 0;
@@ -1687,16 +1899,16 @@ impl AhjoorPaymentsContract {
                 .storage()
                 .persistent()
                 .get(&DataKey::Payment(payment_id))
-                .expect("Payment not found");
+                .unwrap_or_else(|| panic_with_error!(&env, Error::PaymentNotFound));
 
             if payment.status != PaymentStatus::Completed {
-                panic!("Payment is not completed");
+                panic_with_error!(&env, PaymentError::PaymentNotCompleted);
             }
             if payment.merchant != merchant {
-                panic!("Payment does not belong to merchant");
+                panic_with_error!(&env, PaymentError::NotPaymentMerchant);
             }
             if payment.token != settlement_token {
-                panic!("All payments in batch must have same token");
+                panic_with_error!(&env, PaymentError::MixedBatchTokens);
             }
             let settled: bool = env
                 .storage()
@@ -1704,12 +1916,12 @@ impl AhjoorPaymentsContract {
                 .get(&DataKey::Settled(payment_id))
                 .unwrap_or(false);
             if settled {
-                panic!("Payment already settled");
+                panic_with_error!(&env, PaymentError::PaymentAlreadySettled);
             }
 
             total_amount = total_amount
                 .checked_add(payment.amount)
-                .expect("Settlement amount overflow");
+                .unwrap_or_else(|| panic_with_error!(&env, Error::ArithmeticOverflow));
         }
 
         // #367: Load 30-day rolling settlement volume and determine fee tier
@@ -1732,7 +1944,7 @@ impl AhjoorPaymentsContract {
         let fee_collected = (total_amount * tier_fee_bps as i128) / 10_000;
         let net_amount = total_amount
             .checked_sub(fee_collected)
-            .expect("Settlement fee exceeds amount");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::ArithmeticOverflow));
 
         // #231: Enforce withdrawal rate limit
         Self::check_and_update_withdrawal_rate_limit(&env, &merchant, net_amount);
@@ -1809,14 +2021,14 @@ impl AhjoorPaymentsContract {
             .storage()
             .persistent()
             .get(&DataKey::Payment(payment_id))
-            .expect("Payment not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::PaymentNotFound));
 
         if payment.customer != customer {
-            panic!("Only the payment customer can dispute");
+            panic_with_error!(&env, PaymentError::NotPaymentCustomer);
         }
 
         if payment.status != PaymentStatus::Pending && payment.status != PaymentStatus::Authorized {
-            panic!("Only pending or authorized payments can be disputed");
+            panic_with_error!(&env, PaymentError::PaymentNotDisputable);
         }
 
         let old_status = payment.status;
@@ -1862,17 +2074,17 @@ impl AhjoorPaymentsContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         admin.require_auth();
 
         let mut payment: Payment = env
             .storage()
             .persistent()
             .get(&DataKey::Payment(payment_id))
-            .expect("Payment not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::PaymentNotFound));
 
         if payment.status != PaymentStatus::Disputed {
-            panic!("Payment is not disputed");
+            panic_with_error!(&env, PaymentError::PaymentNotDisputed);
         }
 
         let client = token::Client::new(&env, &payment.token);
@@ -1994,7 +2206,7 @@ impl AhjoorPaymentsContract {
             .storage()
             .persistent()
             .get(&DataKey::Payment(payment_id))
-            .expect("Payment not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::PaymentNotFound));
 
         if payment.status != PaymentStatus::Disputed {
             panic_with_error!(&env, Error::EvidenceWindowClosed);
@@ -2004,7 +2216,7 @@ impl AhjoorPaymentsContract {
         let is_customer = payment.customer == caller;
         let is_merchant = payment.merchant == caller;
         if !is_customer && !is_merchant {
-            panic!("Only customer or merchant can submit evidence");
+            panic_with_error!(&env, PaymentError::NotDisputeParty);
         }
 
         // Get or create evidence record
@@ -2074,14 +2286,14 @@ impl AhjoorPaymentsContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         admin.require_auth();
 
         let mut evidence_record: DisputeEvidenceRecord = env
             .storage()
             .persistent()
             .get(&DataKey2::DisputeEvidence(payment_id))
-            .expect("No evidence record found");
+            .unwrap_or_else(|| panic_with_error!(&env, PaymentError::EvidenceNotFound));
 
         evidence_record.evidence_window_close_ledger = env.ledger().sequence();
 
@@ -2106,7 +2318,7 @@ impl AhjoorPaymentsContract {
         env.storage()
             .persistent()
             .get(&DataKey2::DisputeEvidence(payment_id))
-            .expect("No evidence record found")
+            .unwrap_or_else(|| panic_with_error!(&env, PaymentError::EvidenceNotFound))
     }
 
     /// Resolve dispute with evidence hash (#308)
@@ -2121,14 +2333,14 @@ impl AhjoorPaymentsContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         admin.require_auth();
 
         let mut evidence_record: DisputeEvidenceRecord = env
             .storage()
             .persistent()
             .get(&DataKey2::DisputeEvidence(payment_id))
-            .expect("No evidence record found");
+            .unwrap_or_else(|| panic_with_error!(&env, PaymentError::EvidenceNotFound));
 
         evidence_record.resolution_note_hash = Some(resolution_note_hash);
 
@@ -2192,10 +2404,10 @@ impl AhjoorPaymentsContract {
             .storage()
             .persistent()
             .get(&DataKey::Payment(payment_id))
-            .expect("Payment not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::PaymentNotFound));
 
         if payment.customer != customer {
-            panic!("Only the payment customer can cancel");
+            panic_with_error!(&env, PaymentError::NotPaymentCustomer);
         }
 
         if payment.status != PaymentStatus::CoolingOff {
@@ -2206,7 +2418,7 @@ impl AhjoorPaymentsContract {
             .storage()
             .persistent()
             .get(&DataKey2::CoolingOffConfig(payment_id))
-            .expect("No cooling-off config found");
+            .unwrap_or_else(|| panic_with_error!(&env, PaymentError::CoolingOffConfigNotFound));
 
         if env.ledger().sequence() >= cooling_off_config.cooling_off_expiry_ledger {
             panic_with_error!(&env, Error::CoolingOffExpired);
@@ -2304,7 +2516,7 @@ impl AhjoorPaymentsContract {
             .storage()
             .persistent()
             .get(&DataKey::Payment(payment_id))
-            .expect("Payment not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::PaymentNotFound));
 
         if payment.status != PaymentStatus::Disputed {
             return false;
@@ -2314,7 +2526,7 @@ impl AhjoorPaymentsContract {
             .storage()
             .temporary()
             .get(&DataKey::Dispute(payment_id))
-            .expect("Dispute not found");
+            .unwrap_or_else(|| panic_with_error!(&env, PaymentError::DisputeNotFound));
 
         if dispute.resolved {
             return false;
@@ -2345,11 +2557,11 @@ impl AhjoorPaymentsContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         admin.require_auth();
 
         if max_oracle_age == 0 {
-            panic!("max_oracle_age must be positive");
+            panic_with_error!(&env, ConfigError::InvalidDuration);
         }
 
         env.storage()
@@ -2393,7 +2605,7 @@ impl AhjoorPaymentsContract {
     ) -> u32 {
         Self::require_not_paused(&env);
         if amount_usdc <= 0 {
-            panic!("Payment amount must be positive");
+            panic_with_error!(&env, Error::InvalidAmount);
         }
 
         // Resolve slippage: use provided value or fall back to global default
@@ -2401,10 +2613,10 @@ impl AhjoorPaymentsContract {
         let slippage_bps = match slippage_tolerance_bps {
             Some(bps) => {
                 if bps < slippage_cfg.min_bps {
-                    panic!("slippage_bps below minimum allowed");
+                    panic_with_error!(&env, PaymentError::SlippageOutOfBounds);
                 }
                 if bps > slippage_cfg.max_bps {
-                    panic!("slippage_bps exceeds maximum allowed");
+                    panic_with_error!(&env, PaymentError::SlippageOutOfBounds);
                 }
                 bps
             }
@@ -2415,7 +2627,7 @@ impl AhjoorPaymentsContract {
             .storage()
             .instance()
             .get(&DataKey::UsdcToken)
-            .expect("Oracle not configured");
+            .unwrap_or_else(|| panic_with_error!(&env, PaymentError::OracleNotConfigured));
 
         // --- Fallback: direct USDC payment, no oracle needed ---
         if payment_token == usdc_token {
@@ -2488,41 +2700,16 @@ impl AhjoorPaymentsContract {
         Self::require_token_allowed(&env, &payment_token);
         Self::require_merchant_approved(&env, &merchant);
 
-        let oracle_addr: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::OracleAddress)
-            .expect("Oracle not configured");
-        let max_oracle_age: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::MaxOracleAge)
-            .expect("Oracle not configured");
-
         // --- Query oracle: price of payment_token denominated in USDC ---
         // Oracle returns price scaled by ORACLE_PRICE_PRECISION (10^7).
-        let oracle_client = oracle::OracleClient::new(&env, &oracle_addr);
-        let price_data: PriceData = oracle_client
-            .lastprice(&payment_token, &usdc_token)
-            .expect("Oracle price unavailable");
-
-        // --- Freshness check ---
-        let current_ts = env.ledger().timestamp();
-        let age = current_ts.saturating_sub(price_data.timestamp);
-        if age > max_oracle_age {
-            panic!("Oracle price is stale");
-        }
-
-        if price_data.price <= 0 {
-            panic!("Invalid oracle price");
-        }
+        let price_data = Self::get_oracle_price(&env, &payment_token, &usdc_token);
 
         // --- Calculate required payment_token amount ---
         // price = payment_token per USDC, scaled by 10^7
         // required = amount_usdc * 10^7 / price
         let required_token_amount = (amount_usdc * ORACLE_PRICE_PRECISION) / price_data.price;
         if required_token_amount <= 0 {
-            panic!("Computed token amount is zero");
+            panic_with_error!(&env, Error::InvalidAmount);
         }
 
         // --- Slippage check ---
@@ -2538,7 +2725,7 @@ impl AhjoorPaymentsContract {
         };
         let deviation_bps = (deviation * 10_000) / amount_usdc;
         if deviation_bps > slippage_bps as i128 {
-            panic!("Slippage tolerance exceeded");
+            panic_with_error!(&env, Error::SlippageExceeded);
         }
 
         // --- Transfer payment_token from customer to contract (escrow) ---
@@ -2622,21 +2809,223 @@ impl AhjoorPaymentsContract {
         env.storage()
             .instance()
             .get(&DataKey::OracleAddress)
-            .expect("Oracle not configured")
+            .unwrap_or_else(|| panic_with_error!(&env, PaymentError::OracleNotConfigured))
     }
 
     pub fn get_usdc_token(env: Env) -> Address {
         env.storage()
             .instance()
             .get(&DataKey::UsdcToken)
-            .expect("Oracle not configured")
+            .unwrap_or_else(|| panic_with_error!(&env, PaymentError::OracleNotConfigured))
     }
 
     pub fn get_max_oracle_age(env: Env) -> u64 {
         env.storage()
             .instance()
             .get(&DataKey::MaxOracleAge)
-            .expect("Oracle not configured")
+            .unwrap_or_else(|| panic_with_error!(&env, PaymentError::OracleNotConfigured))
+    }
+
+    /// Admin configures the oracle set for a base/quote pair.
+    ///
+    /// Once set, prices for the pair are the median of the sources reporting a
+    /// fresh price, and are rejected if fewer than `min_sources` are fresh or
+    /// any of them is more than `max_deviation_bps` from the median. Pairs
+    /// without a set keep using the single oracle from `set_oracle`.
+    pub fn set_oracle_set(
+        env: Env,
+        admin: Address,
+        base: Address,
+        quote: Address,
+        oracles: Vec<Address>,
+        min_sources: u32,
+        max_deviation_bps: u32,
+    ) {
+        Self::require_admin(&env, &admin);
+        if oracles.is_empty()
+            || oracles.len() > MAX_ORACLE_SOURCES
+            || min_sources == 0
+            || min_sources > oracles.len()
+            || max_deviation_bps == 0
+            || max_deviation_bps > 10_000
+        {
+            panic_with_error!(&env, ConfigError::InvalidOracleSet);
+        }
+        for (i, oracle) in oracles.iter().enumerate() {
+            if oracles.first_index_of(&oracle) != Some(i as u32) {
+                panic_with_error!(&env, ConfigError::InvalidOracleSet);
+            }
+        }
+
+        let set = OracleSet {
+            oracles: oracles.clone(),
+            min_sources,
+            max_deviation_bps,
+        };
+        env.storage()
+            .instance()
+            .set(&DataKey3::OracleSet(base.clone(), quote.clone()), &set);
+        events::emit_oracle_set_configured(
+            &env,
+            base,
+            quote,
+            oracles,
+            min_sources,
+            max_deviation_bps,
+        );
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Admin removes the oracle set for a pair, returning it to the single oracle.
+    pub fn remove_oracle_set(env: Env, admin: Address, base: Address, quote: Address) {
+        Self::require_admin(&env, &admin);
+        env.storage()
+            .instance()
+            .remove(&DataKey3::OracleSet(base, quote));
+    }
+
+    pub fn get_oracle_set(env: Env, base: Address, quote: Address) -> Option<OracleSet> {
+        env.storage()
+            .instance()
+            .get(&DataKey3::OracleSet(base, quote))
+    }
+
+    /// Internal: fresh, positive price for a pair — the oracle set's median
+    /// when one is configured, otherwise the single configured oracle's price.
+    fn get_oracle_price(env: &Env, base: &Address, quote: &Address) -> PriceData {
+        let max_oracle_age: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MaxOracleAge)
+            .unwrap_or_else(|| panic_with_error!(env, PaymentError::OracleNotConfigured));
+        let oracle_set: Option<OracleSet> = env
+            .storage()
+            .instance()
+            .get(&DataKey3::OracleSet(base.clone(), quote.clone()));
+        if let Some(set) = oracle_set {
+            return Self::get_aggregated_oracle_price(env, base, quote, &set, max_oracle_age);
+        }
+
+        let oracle_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::OracleAddress)
+            .unwrap_or_else(|| panic_with_error!(env, PaymentError::OracleNotConfigured));
+        let oracle_client = oracle::OracleClient::new(env, &oracle_addr);
+        let price_data: PriceData = oracle_client
+            .lastprice(base, quote)
+            .unwrap_or_else(|| panic_with_error!(env, PaymentError::OraclePriceUnavailable));
+
+        // --- Freshness check ---
+        let age = env.ledger().timestamp().saturating_sub(price_data.timestamp);
+        if age > max_oracle_age {
+            panic_with_error!(env, PaymentError::OraclePriceStale);
+        }
+        if price_data.price <= 0 {
+            panic_with_error!(env, PaymentError::InvalidOraclePrice);
+        }
+
+        price_data
+    }
+
+    /// Internal: the pair's oracle-set median rescaled to `precision`, or
+    /// `None` when no oracle set is configured for the pair.
+    pub(crate) fn get_oracle_set_price(
+        env: &Env,
+        base: &Address,
+        quote: &Address,
+        precision: i128,
+    ) -> Option<i128> {
+        let set: OracleSet = env
+            .storage()
+            .instance()
+            .get(&DataKey3::OracleSet(base.clone(), quote.clone()))?;
+        let max_oracle_age: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MaxOracleAge)
+            .unwrap_or_else(|| panic_with_error!(env, PaymentError::OracleNotConfigured));
+        let price_data = Self::get_aggregated_oracle_price(env, base, quote, &set, max_oracle_age);
+        Some(price_data.price * precision / ORACLE_PRICE_PRECISION)
+    }
+
+    /// Internal: median of the fresh, positive prices reported by the pair's
+    /// oracle set. Sources that fail, report nothing, report a stale price or
+    /// a non-positive one are skipped with an `OracleFailover` event. The
+    /// returned timestamp is that of the oldest source used.
+    fn get_aggregated_oracle_price(
+        env: &Env,
+        base: &Address,
+        quote: &Address,
+        set: &OracleSet,
+        max_oracle_age: u64,
+    ) -> PriceData {
+        let now = env.ledger().timestamp();
+
+        // Kept sorted ascending as prices come in
+        let mut prices: Vec<i128> = Vec::new(env);
+        let mut oldest = now;
+        for oracle_addr in set.oracles.iter() {
+            let oracle_client = oracle::OracleClient::new(env, &oracle_addr);
+            let reason = match oracle_client.try_lastprice(base, quote) {
+                Ok(Ok(Some(data))) if data.price <= 0 => Some(symbol_short!("invalid")),
+                Ok(Ok(Some(data))) if now.saturating_sub(data.timestamp) > max_oracle_age => {
+                    Some(symbol_short!("stale"))
+                }
+                Ok(Ok(Some(data))) => {
+                    let mut at = prices.len();
+                    while at > 0 && prices.get_unchecked(at - 1) > data.price {
+                        at -= 1;
+                    }
+                    prices.insert(at, data.price);
+                    oldest = oldest.min(data.timestamp);
+                    None
+                }
+                _ => Some(symbol_short!("missing")),
+            };
+            if let Some(reason) = reason {
+                events::emit_oracle_failover(
+                    env,
+                    base.clone(),
+                    quote.clone(),
+                    oracle_addr,
+                    reason,
+                );
+            }
+        }
+
+        let sources = prices.len();
+        if sources == 0 || sources < set.min_sources {
+            panic_with_error!(env, PaymentError::InsufficientOracleSources);
+        }
+        let mid = sources / 2;
+        let median = if sources % 2 == 1 {
+            prices.get_unchecked(mid)
+        } else {
+            (prices.get_unchecked(mid - 1) + prices.get_unchecked(mid)) / 2
+        };
+
+        // Prices are sorted, so the extremes are the furthest from the median
+        let spread = (median - prices.get_unchecked(0))
+            .max(prices.get_unchecked(sources - 1) - median);
+        if spread * 10_000 > median * set.max_deviation_bps as i128 {
+            panic_with_error!(env, PaymentError::OracleDeviationExceeded);
+        }
+
+        events::emit_oracle_price_aggregated(
+            env,
+            base.clone(),
+            quote.clone(),
+            median,
+            sources,
+            set.oracles.len() - sources,
+        );
+        PriceData {
+            price: median,
+            timestamp: oldest,
+        }
     }
 
     // --- Admin ---
@@ -2647,11 +3036,11 @@ impl AhjoorPaymentsContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         admin.require_auth();
 
         if new_size == 0 {
-            panic!("Max batch size must be at least 1");
+            panic_with_error!(&env, ConfigError::InvalidBatchSize);
         }
 
         env.storage()
@@ -2665,11 +3054,11 @@ impl AhjoorPaymentsContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         admin.require_auth();
 
         if timeout == 0 {
-            panic!("Dispute timeout must be positive");
+            panic_with_error!(&env, ConfigError::InvalidDuration);
         }
 
         env.storage()
@@ -2685,12 +3074,12 @@ impl AhjoorPaymentsContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         if admin != stored_admin {
-            panic!("Only admin can update fee");
+            panic_with_error!(&env, Error::Unauthorized);
         }
         if new_fee_bps > MAX_FEE_BPS {
-            panic!("Fee cannot exceed 500 bps (5%)");
+            panic_with_error!(&env, ConfigError::FeeTooHigh);
         }
 
         env.storage().instance().set(&DataKey::FeeBps, &new_fee_bps);
@@ -2707,9 +3096,9 @@ impl AhjoorPaymentsContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         if admin != stored_admin {
-            panic!("Only admin can update fee recipient");
+            panic_with_error!(&env, Error::Unauthorized);
         }
 
         env.storage()
@@ -2733,7 +3122,7 @@ impl AhjoorPaymentsContract {
         env.storage()
             .instance()
             .get(&DataKey::FeeRecipient)
-            .expect("Fee recipient not configured")
+            .unwrap_or_else(|| panic_with_error!(&env, PaymentError::FeeRecipientNotConfigured))
     }
 
     /// Admin updates the ascending fee tier table.
@@ -2744,12 +3133,12 @@ impl AhjoorPaymentsContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         if admin != stored_admin {
-            panic!("Only admin can update fee tiers");
+            panic_with_error!(&env, Error::Unauthorized);
         }
 
-        Self::validate_fee_tiers(&tiers);
+        Self::validate_fee_tiers(&env, &tiers);
         env.storage().instance().set(&DataKey::FeeTiers, &tiers);
         env.storage()
             .instance()
@@ -2824,15 +3213,15 @@ impl AhjoorPaymentsContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         if admin != stored_admin {
-            panic!("Only admin can update rate limit config");
+            panic_with_error!(&env, Error::Unauthorized);
         }
         if max_payments == 0 {
-            panic!("max_payments must be positive");
+            panic_with_error!(&env, ConfigError::InvalidRateLimitConfig);
         }
         if window_size_ledgers == 0 {
-            panic!("window_size_ledgers must be positive");
+            panic_with_error!(&env, ConfigError::InvalidWindow);
         }
 
         env.storage().instance().set(
@@ -2862,18 +3251,18 @@ impl AhjoorPaymentsContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         if admin != stored_admin {
-            panic!("Only admin can update slippage config");
+            panic_with_error!(&env, Error::Unauthorized);
         }
         if max_bps > 10_000 {
-            panic!("max_bps cannot exceed 10000");
+            panic_with_error!(&env, ConfigError::InvalidSlippageConfig);
         }
         if min_bps > max_bps {
-            panic!("min_bps cannot exceed max_bps");
+            panic_with_error!(&env, ConfigError::InvalidSlippageConfig);
         }
         if default_bps < min_bps || default_bps > max_bps {
-            panic!("default_bps must be within [min_bps, max_bps]");
+            panic_with_error!(&env, ConfigError::InvalidSlippageConfig);
         }
         env.storage().instance().set(
             &DataKey::SlippageConfig,
@@ -2901,15 +3290,15 @@ impl AhjoorPaymentsContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         if admin != stored_admin {
-            panic!("Only admin can update extension config");
+            panic_with_error!(&env, Error::Unauthorized);
         }
         if max_extension_ledgers == 0 {
-            panic!("max_extension_ledgers must be positive");
+            panic_with_error!(&env, ConfigError::InvalidExtensionConfig);
         }
         if max_extensions == 0 {
-            panic!("max_extensions must be positive");
+            panic_with_error!(&env, ConfigError::InvalidExtensionConfig);
         }
         env.storage()
             .instance()
@@ -2953,10 +3342,10 @@ impl AhjoorPaymentsContract {
             .storage()
             .persistent()
             .get(&DataKey::Payment(payment_id))
-            .expect("Payment not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::PaymentNotFound));
 
         if payment.merchant != merchant {
-            panic!("Only the payment merchant can extend expiry");
+            panic_with_error!(&env, PaymentError::NotPaymentMerchant);
         }
 
         if payment.status != PaymentStatus::Pending {
@@ -2994,7 +3383,7 @@ impl AhjoorPaymentsContract {
         payment.expires_at = payment
             .expires_at
             .checked_add(additional_seconds)
-            .expect("Expiry overflow");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::ArithmeticOverflow));
         payment.extension_count += 1;
 
         env.storage()
@@ -3039,15 +3428,15 @@ impl AhjoorPaymentsContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         if admin != stored_admin {
-            panic!("Only admin can set merchant volume cap");
+            panic_with_error!(&env, Error::Unauthorized);
         }
         if cap_amount < 0 {
-            panic!("cap_amount cannot be negative");
+            panic_with_error!(&env, Error::InvalidAmount);
         }
         if cap_amount > 0 && window_seconds == 0 {
-            panic!("window_seconds must be positive when cap is set");
+            panic_with_error!(&env, ConfigError::InvalidWindow);
         }
         let key = DataKey::VolumeCap(merchant.clone());
         if cap_amount == 0 {
@@ -3085,7 +3474,7 @@ impl AhjoorPaymentsContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         admin.require_auth();
 
         env.storage()
@@ -3106,14 +3495,14 @@ impl AhjoorPaymentsContract {
             .storage()
             .instance()
             .get(&DataKey::ProposedAdmin)
-            .expect("No admin transfer proposed");
+            .unwrap_or_else(|| panic_with_error!(&env, MerchantError::NoAdminTransferProposed));
         proposed_admin.require_auth();
 
         let old_admin: Address = env
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
 
         env.storage()
             .instance()
@@ -3132,7 +3521,7 @@ impl AhjoorPaymentsContract {
         env.storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized")
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized))
     }
 
     /// Upgrade this contract's WASM code. Admin only.
@@ -3143,15 +3532,17 @@ impl AhjoorPaymentsContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         if admin != stored_admin {
-            panic!("Only admin can upgrade contract");
+            panic_with_error!(&env, Error::Unauthorized);
         }
 
         let old_version = Self::get_or_init_version(&env);
         env.deployer().update_current_contract_wasm(new_wasm_hash);
 
-        let new_version = old_version.checked_add(1).expect("Version overflow");
+        let new_version = old_version
+            .checked_add(1)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::ArithmeticOverflow));
         env.storage()
             .instance()
             .set(&DataKey::ContractVersion, &new_version);
@@ -3171,9 +3562,9 @@ impl AhjoorPaymentsContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         if admin != stored_admin {
-            panic!("Only admin can migrate contract");
+            panic_with_error!(&env, Error::Unauthorized);
         }
 
         let version = Self::get_or_init_version(&env);
@@ -3183,7 +3574,7 @@ impl AhjoorPaymentsContract {
             .get(&DataKey::MigrationCompleted(version))
             .unwrap_or(false)
         {
-            panic!("Migration already completed for this version");
+            panic_with_error!(&env, MerchantError::MigrationAlreadyCompleted);
         }
 
         env.storage()
@@ -3248,7 +3639,7 @@ impl AhjoorPaymentsContract {
         env.storage()
             .persistent()
             .get(&DataKey::Payment(payment_id))
-            .expect("Payment not found")
+            .unwrap_or_else(|| panic_with_error!(&env, Error::PaymentNotFound))
     }
 
     // =========================================================================
@@ -3266,11 +3657,11 @@ impl AhjoorPaymentsContract {
             .storage()
             .persistent()
             .get(&DataKey::ExternalIdIndex(merchant, external_id))
-            .expect("No payment found for this external_id");
+            .unwrap_or_else(|| panic_with_error!(&env, PaymentError::ExternalIdNotFound));
         env.storage()
             .persistent()
             .get(&DataKey::Payment(payment_id))
-            .expect("Payment not found")
+            .unwrap_or_else(|| panic_with_error!(&env, Error::PaymentNotFound))
     }
 
     // =========================================================================
@@ -3294,16 +3685,16 @@ impl AhjoorPaymentsContract {
         merchant.require_auth();
 
         if threshold <= 0 {
-            panic!("threshold must be positive");
+            panic_with_error!(&env, ConfigError::InvalidMultisigPolicy);
         }
         if signers.is_empty() {
-            panic!("signers cannot be empty");
+            panic_with_error!(&env, ConfigError::InvalidMultisigPolicy);
         }
         if m == 0 || m > signers.len() {
-            panic!("m must be between 1 and signers.len()");
+            panic_with_error!(&env, ConfigError::InvalidMultisigPolicy);
         }
         if approval_window_seconds == 0 {
-            panic!("approval_window_seconds must be positive");
+            panic_with_error!(&env, ConfigError::InvalidDuration);
         }
 
         let policy = MultisigPolicy {
@@ -3337,17 +3728,17 @@ impl AhjoorPaymentsContract {
             .storage()
             .persistent()
             .get(&DataKey::Payment(payment_id))
-            .expect("Payment not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::PaymentNotFound));
 
         if payment.status != PaymentStatus::PendingApproval {
-            panic!("Payment is not pending approval");
+            panic_with_error!(&env, PaymentError::PaymentNotPendingApproval);
         }
 
         let policy: MultisigPolicy = env
             .storage()
             .instance()
             .get(&DataKey::MultisigPolicy(payment.merchant.clone()))
-            .expect("No multisig policy for merchant");
+            .unwrap_or_else(|| panic_with_error!(&env, PaymentError::MultisigPolicyNotFound));
 
         // Verify signer is in the policy set
         let mut is_valid_signer = false;
@@ -3366,7 +3757,7 @@ impl AhjoorPaymentsContract {
             .storage()
             .persistent()
             .get(&DataKey2::ApprovalState(payment_id))
-            .expect("Approval state not found");
+            .unwrap_or_else(|| panic_with_error!(&env, PaymentError::ApprovalStateNotFound));
 
         let now = env.ledger().timestamp();
         if now > state.created_at + policy.approval_window_seconds {
@@ -3452,27 +3843,27 @@ impl AhjoorPaymentsContract {
             .storage()
             .persistent()
             .get(&DataKey::Payment(payment_id))
-            .expect("Payment not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::PaymentNotFound));
 
         if payment.status != PaymentStatus::PendingApproval {
-            panic!("Payment is not pending approval");
+            panic_with_error!(&env, PaymentError::PaymentNotPendingApproval);
         }
 
         let policy: MultisigPolicy = env
             .storage()
             .instance()
             .get(&DataKey::MultisigPolicy(payment.merchant.clone()))
-            .expect("No multisig policy for merchant");
+            .unwrap_or_else(|| panic_with_error!(&env, PaymentError::MultisigPolicyNotFound));
 
         let state: ApprovalState = env
             .storage()
             .persistent()
             .get(&DataKey2::ApprovalState(payment_id))
-            .expect("Approval state not found");
+            .unwrap_or_else(|| panic_with_error!(&env, PaymentError::ApprovalStateNotFound));
 
         let now = env.ledger().timestamp();
         if now <= state.created_at + policy.approval_window_seconds {
-            panic!("Approval window has not expired yet");
+            panic_with_error!(&env, PaymentError::ApprovalWindowOpen);
         }
 
         let client = token::Client::new(&env, &payment.token);
@@ -3524,15 +3915,15 @@ impl AhjoorPaymentsContract {
         merchant.require_auth();
 
         if discount_type == DiscountType::Percentage && discount_value > 100 {
-            panic!("Percentage discount cannot exceed 100");
+            panic_with_error!(&env, MerchantError::InvalidDiscount);
         }
         if discount_value == 0 {
-            panic!("discount_value must be positive");
+            panic_with_error!(&env, Error::InvalidAmount);
         }
 
         let key = DataKey2::Voucher(merchant.clone(), code_hash.clone());
         if env.storage().persistent().has(&key) {
-            panic!("Voucher with this code_hash already exists");
+            panic_with_error!(&env, MerchantError::VoucherAlreadyExists);
         }
 
         let voucher = Voucher {
@@ -3577,13 +3968,13 @@ impl AhjoorPaymentsContract {
             .storage()
             .persistent()
             .get(&key)
-            .expect("Voucher not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::VoucherNotFound));
 
         if voucher.merchant != merchant {
-            panic!("Only the issuing merchant can revoke this voucher");
+            panic_with_error!(&env, MerchantError::NotVoucherIssuer);
         }
         if voucher.revoked {
-            panic!("Voucher already revoked");
+            panic_with_error!(&env, Error::VoucherRevoked);
         }
 
         voucher.revoked = true;
@@ -3606,7 +3997,7 @@ impl AhjoorPaymentsContract {
         env.storage()
             .persistent()
             .get(&DataKey2::Voucher(merchant, code_hash))
-            .expect("Voucher not found")
+            .unwrap_or_else(|| panic_with_error!(&env, Error::VoucherNotFound))
     }
 
     /// Create a payment with an optional voucher code hash for discount.
@@ -3640,7 +4031,7 @@ impl AhjoorPaymentsContract {
         Self::enforce_rate_limit(&env, &customer, 1);
 
         if amount <= 0 {
-            panic!("Payment amount must be positive");
+            panic_with_error!(&env, Error::InvalidAmount);
         }
 
         Self::validate_reference(&env, &reference);
@@ -3663,7 +4054,7 @@ impl AhjoorPaymentsContract {
                 .storage()
                 .persistent()
                 .get(&voucher_key)
-                .expect("Voucher not found");
+                .unwrap_or_else(|| panic_with_error!(&env, Error::VoucherNotFound));
 
             let now = env.ledger().timestamp();
             if voucher.revoked {
@@ -3714,7 +4105,7 @@ impl AhjoorPaymentsContract {
         };
 
         if effective_amount <= 0 {
-            panic!("Effective payment amount after discount must be positive");
+            panic_with_error!(&env, MerchantError::InvalidDiscount);
         }
 
         let client = token::Client::new(&env, &token);
@@ -3850,7 +4241,7 @@ impl AhjoorPaymentsContract {
         env.storage()
             .persistent()
             .get(&DataKey::PaymentReceipt(payment_id))
-            .expect("Receipt not found")
+            .unwrap_or_else(|| panic_with_error!(&env, PaymentError::ReceiptNotFound))
     }
 
     /// Returns true if the stored receipt hash matches `expected_hash` (#65).
@@ -3901,10 +4292,10 @@ impl AhjoorPaymentsContract {
         page_size: u32,
     ) -> CustomerPaymentsPage {
         if page_size == 0 {
-            panic!("page_size must be greater than 0");
+            panic_with_error!(&env, Error::InvalidPageSize);
         }
         if page_size > MAX_CUSTOMER_PAYMENTS_PAGE_SIZE {
-            panic!("page_size exceeds maximum of 50");
+            panic_with_error!(&env, Error::InvalidPageSize);
         }
 
         let all: Vec<u32> = env
@@ -3959,7 +4350,7 @@ impl AhjoorPaymentsContract {
             .storage()
             .persistent()
             .get(&DataKey::Payment(payment_id))
-            .expect("Payment not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::PaymentNotFound));
         payment.status == PaymentStatus::Disputed
     }
 
@@ -3967,7 +4358,7 @@ impl AhjoorPaymentsContract {
         env.storage()
             .temporary()
             .get(&DataKey::Dispute(payment_id))
-            .expect("No dispute found for this payment")
+            .unwrap_or_else(|| panic_with_error!(&env, PaymentError::DisputeNotFound))
     }
 
     pub fn get_dispute_timeout(env: Env) -> u64 {
@@ -3997,10 +4388,10 @@ impl AhjoorPaymentsContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         admin.require_auth();
         if timeout_seconds == 0 {
-            panic!("Timeout must be positive");
+            panic_with_error!(&env, ConfigError::InvalidDuration);
         }
         env.storage()
             .instance()
@@ -4035,13 +4426,13 @@ impl AhjoorPaymentsContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         admin.require_auth();
         if min_seconds == 0 {
-            panic!("min_expiry must be greater than 0");
+            panic_with_error!(&env, ConfigError::InvalidExpiryBounds);
         }
         if min_seconds > max_seconds {
-            panic!("min_expiry must be <= max_expiry");
+            panic_with_error!(&env, ConfigError::InvalidExpiryBounds);
         }
         env.storage()
             .instance()
@@ -4080,10 +4471,10 @@ impl AhjoorPaymentsContract {
             .get(&DataKey::MaxPaymentExpiry)
             .unwrap_or(DEFAULT_MAX_PAYMENT_EXPIRY);
         if expiry_seconds < min_expiry {
-            panic!("expiry_seconds is below the configured minimum");
+            panic_with_error!(env, PaymentError::ExpiryBelowMinimum);
         }
         if expiry_seconds > max_expiry {
-            panic!("expiry_seconds exceeds the configured maximum");
+            panic_with_error!(env, PaymentError::ExpiryAboveMaximum);
         }
     }
 
@@ -4095,23 +4486,23 @@ impl AhjoorPaymentsContract {
             .storage()
             .persistent()
             .get(&DataKey::Payment(payment_id))
-            .expect("Payment not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::PaymentNotFound));
 
         if payment.status != PaymentStatus::Pending && payment.status != PaymentStatus::Authorized {
-            panic!("Only pending or authorized payments can expire");
+            panic_with_error!(&env, PaymentError::PaymentNotExpirable);
         }
 
         let now = env.ledger().timestamp();
         let expired = match payment.status {
             PaymentStatus::Pending => {
                 if payment.expires_at == 0 {
-                    panic!("Payment has no expiry set");
+                    panic_with_error!(&env, PaymentError::NoExpirySet);
                 }
                 now >= payment.expires_at
             }
             PaymentStatus::Authorized => {
                 if payment.capture_deadline == 0 {
-                    panic!("Authorized payment has no capture deadline");
+                    panic_with_error!(&env, PaymentError::NoCaptureDeadline);
                 }
                 (env.ledger().sequence() as u64) > payment.capture_deadline
             }
@@ -4119,7 +4510,7 @@ impl AhjoorPaymentsContract {
         };
 
         if !expired {
-            panic!("Payment has not expired yet");
+            panic_with_error!(&env, PaymentError::PaymentNotExpired);
         }
 
         let client = token::Client::new(&env, &payment.token);
@@ -4166,26 +4557,26 @@ impl AhjoorPaymentsContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         admin.require_auth();
 
         if refund_amount <= 0 {
-            panic!("Refund amount must be positive");
+            panic_with_error!(&env, Error::InvalidAmount);
         }
 
         let mut payment: Payment = env
             .storage()
             .persistent()
             .get(&DataKey::Payment(payment_id))
-            .expect("Payment not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::PaymentNotFound));
 
         if payment.status != PaymentStatus::Disputed && payment.status != PaymentStatus::Pending {
-            panic!("Payment must be pending or disputed for partial refund");
+            panic_with_error!(&env, PaymentError::PaymentNotRefundable);
         }
 
         let remaining = payment.amount - payment.refunded_amount;
         if refund_amount > remaining {
-            panic!("Refund amount exceeds remaining balance");
+            panic_with_error!(&env, PaymentError::RefundExceedsBalance);
         }
 
         let client = token::Client::new(&env, &payment.token);
@@ -4238,7 +4629,7 @@ impl AhjoorPaymentsContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         admin.require_auth();
 
         // Enforce minimum collateral before approval (#129)
@@ -4249,7 +4640,7 @@ impl AhjoorPaymentsContract {
             .get(&DataKey::MerchantCollateral(merchant.clone()))
             .unwrap_or(0);
         if collateral < min_collateral {
-            panic!("Merchant collateral below minimum required");
+            panic_with_error!(&env, MerchantError::CollateralBelowMinimum);
         }
 
         env.storage()
@@ -4264,7 +4655,7 @@ impl AhjoorPaymentsContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         admin.require_auth();
         env.storage()
             .persistent()
@@ -4286,7 +4677,7 @@ impl AhjoorPaymentsContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         admin.require_auth();
         env.storage()
             .instance()
@@ -4309,11 +4700,11 @@ impl AhjoorPaymentsContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         admin.require_auth();
 
         if min_collateral < 0 {
-            panic!("min_collateral cannot be negative");
+            panic_with_error!(&env, ConfigError::InvalidCollateralConfig);
         }
 
         env.storage()
@@ -4337,14 +4728,16 @@ impl AhjoorPaymentsContract {
         merchant.require_auth();
 
         if amount <= 0 {
-            panic!("Deposit amount must be positive");
+            panic_with_error!(&env, Error::InvalidAmount);
         }
 
         let usdc_token: Address = env
             .storage()
             .instance()
             .get(&DataKey::UsdcToken)
-            .expect("Collateral token not configured; call set_oracle first");
+            .unwrap_or_else(|| {
+                panic_with_error!(&env, MerchantError::CollateralTokenNotConfigured)
+            });
 
         Self::require_token_allowed(&env, &usdc_token);
 
@@ -4353,7 +4746,9 @@ impl AhjoorPaymentsContract {
 
         let key = DataKey::MerchantCollateral(merchant.clone());
         let prev: i128 = env.storage().persistent().get(&key).unwrap_or(0);
-        let new_balance = prev.checked_add(amount).expect("Collateral overflow");
+        let new_balance = prev
+            .checked_add(amount)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::ArithmeticOverflow));
         env.storage().persistent().set(&key, &new_balance);
         env.storage().persistent().extend_ttl(
             &key,
@@ -4376,27 +4771,29 @@ impl AhjoorPaymentsContract {
         merchant.require_auth();
 
         if amount <= 0 {
-            panic!("Withdrawal amount must be positive");
+            panic_with_error!(&env, Error::InvalidAmount);
         }
 
         let key = DataKey::MerchantCollateral(merchant.clone());
         let current: i128 = env.storage().persistent().get(&key).unwrap_or(0);
 
         if amount > current {
-            panic!("Insufficient collateral balance");
+            panic_with_error!(&env, MerchantError::InsufficientCollateral);
         }
 
         let min_collateral = Self::get_min_collateral_internal(&env);
         let remaining = current - amount;
         if remaining < min_collateral {
-            panic!("Withdrawal would drop collateral below minimum required");
+            panic_with_error!(&env, MerchantError::CollateralBelowMinimum);
         }
 
         let usdc_token: Address = env
             .storage()
             .instance()
             .get(&DataKey::UsdcToken)
-            .expect("Collateral token not configured");
+            .unwrap_or_else(|| {
+                panic_with_error!(&env, MerchantError::CollateralTokenNotConfigured)
+            });
 
         let token_client = token::Client::new(&env, &usdc_token);
         token_client.transfer(&env.current_contract_address(), &merchant, &amount);
@@ -4433,11 +4830,11 @@ impl AhjoorPaymentsContract {
         merchant.require_auth();
 
         if key.len() > MAX_NOTIFICATION_KEY_LEN {
-            panic!("Notification key exceeds maximum length of 128 bytes");
+            panic_with_error!(&env, MerchantError::InvalidNotificationKey);
         }
 
         if key.is_empty() {
-            panic!("Notification key cannot be empty");
+            panic_with_error!(&env, MerchantError::InvalidNotificationKey);
         }
 
         let storage_key = DataKey::MerchantNotificationKey(merchant.clone());
@@ -4493,11 +4890,11 @@ impl AhjoorPaymentsContract {
         merchant.require_auth();
 
         if new_key.len() > MAX_NOTIFICATION_KEY_LEN {
-            panic!("Notification key exceeds maximum length of 128 bytes");
+            panic_with_error!(&env, MerchantError::InvalidNotificationKey);
         }
 
         if new_key.is_empty() {
-            panic!("Notification key cannot be empty");
+            panic_with_error!(&env, MerchantError::InvalidNotificationKey);
         }
 
         let now = env.ledger().timestamp();
@@ -4626,9 +5023,9 @@ impl AhjoorPaymentsContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         if admin != stored_admin {
-            panic!("Only admin can set overlap window");
+            panic_with_error!(&env, Error::Unauthorized);
         }
 
         env.storage()
@@ -4677,9 +5074,9 @@ impl AhjoorPaymentsContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         if admin != stored_admin {
-            panic!("Only admin can set swap router");
+            panic_with_error!(&env, Error::Unauthorized);
         }
 
         env.storage().instance().set(&DataKey::SwapRouter, &router);
@@ -4706,9 +5103,9 @@ impl AhjoorPaymentsContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         if admin != stored_admin {
-            panic!("Only admin can set token whitelist contract");
+            panic_with_error!(&env, Error::Unauthorized);
         }
 
         env.storage()
@@ -4786,10 +5183,10 @@ impl AhjoorPaymentsContract {
         Self::require_not_paused(&env);
         subscriber.require_auth();
         if amount <= 0 {
-            panic!("Subscription amount must be positive");
+            panic_with_error!(&env, Error::InvalidAmount);
         }
         if interval_seconds == 0 {
-            panic!("Interval must be positive");
+            panic_with_error!(&env, SubscriptionError::InvalidInterval);
         }
 
         Self::require_token_allowed(&env, &token);
@@ -4830,6 +5227,13 @@ impl AhjoorPaymentsContract {
             trial_ends_at,
             pause_authority: PauseAuthority::Subscriber, // Default to subscriber only
             pause_count: 0,
+            plan_id: 0,
+            credit: 0,
+            past_due_since: 0,
+            dunning_attempts: 0,
+            next_retry_at: 0,
+            grace_ends_at: 0,
+            dunning_ladder: Vec::new(&env),
         };
 
         env.storage()
@@ -4857,7 +5261,7 @@ impl AhjoorPaymentsContract {
             .storage()
             .persistent()
             .get(&DataKey::Subscription(subscription_id))
-            .expect("Subscription not found");
+            .unwrap_or_else(|| panic_with_error!(&env, SubscriptionError::SubscriptionNotFound));
         let now = env.ledger().timestamp();
         if sub.trial_ends_at == 0 || sub.trial_ends_at <= now {
             0
@@ -4866,41 +5270,112 @@ impl AhjoorPaymentsContract {
         }
     }
 
-    /// Charge a subscription. Callable by anyone when the interval has elapsed.
+    /// Charge a subscription. Callable by anyone when the interval has elapsed,
+    /// or for a past-due subscription once its next retry is due. Under a
+    /// merchant dunning policy a failed charge marks the subscription past due
+    /// instead of reverting, and a failed last retry cancels it.
     pub fn charge_subscription(env: Env, subscription_id: u32) {
         Self::require_not_paused(&env);
         let mut sub: Subscription = env
             .storage()
             .persistent()
             .get(&DataKey::Subscription(subscription_id))
-            .expect("Subscription not found");
+            .unwrap_or_else(|| panic_with_error!(&env, SubscriptionError::SubscriptionNotFound));
 
         if !sub.active {
-            panic!("Subscription is cancelled");
+            panic_with_error!(&env, SubscriptionError::SubscriptionCancelled);
         }
         if sub.paused {
             panic_with_error!(&env, Error::SubscriptionPaused);
         }
         if sub.max_charges > 0 && sub.charges_count >= sub.max_charges {
-            panic!("Max charges reached");
+            panic_with_error!(&env, SubscriptionError::MaxChargesReached);
         }
 
         let now = env.ledger().timestamp();
-        if sub.charges_count == 0 && sub.trial_ends_at > now {
-            panic_with_error!(&env, Error::SubscriptionInTrial);
-        }
-        if sub.last_charged_at > 0 && now < sub.last_charged_at + sub.interval_seconds {
-            panic!("Interval has not elapsed");
+        let past_due = sub.past_due_since > 0;
+        if past_due {
+            if now < sub.next_retry_at {
+                panic_with_error!(&env, Error::RetryNotDue);
+            }
+        } else {
+            if sub.charges_count == 0 && sub.trial_ends_at > now {
+                panic_with_error!(&env, Error::SubscriptionInTrial);
+            }
+            if sub.last_charged_at > 0 && now < sub.last_charged_at + sub.interval_seconds {
+                panic_with_error!(&env, SubscriptionError::IntervalNotElapsed);
+            }
+        }
+        let metered: Option<MeteredPricing> = env
+            .storage()
+            .persistent()
+            .get(&DataKey3::MeteredPricing(subscription_id));
+        if let Some(pricing) = &metered {
+            // Usage is billed in arrears, so the first metered period runs a full interval
+            if !past_due
+                && sub.last_charged_at < pricing.started_at
+                && now < pricing.started_at + sub.interval_seconds
+            {
+                panic_with_error!(&env, SubscriptionError::IntervalNotElapsed);
+            }
         }
         let trial_just_ended = sub.charges_count == 0 && sub.trial_ends_at > 0;
 
-        let client = token::Client::new(&env, &sub.token);
-        client.transfer(
-            &sub.subscriber,
-            &env.current_contract_address(),
-            &sub.amount,
-        );
-        client.transfer(&env.current_contract_address(), &sub.merchant, &sub.amount);
+        if sub.plan_id != 0 {
+            sub.amount = Self::load_subscription_plan(&env, sub.plan_id).price;
+        }
+        let usage = metered.as_ref().map(|pricing| {
+            Self::price_usage_period(&env, subscription_id, sub.charges_count, pricing)
+        });
+        if let Some(usage) = &usage {
+            sub.amount = usage.charged;
+        }
+        // Downgrade credit covers the charge first
+        let from_credit = sub.credit.min(sub.amount);
+        let due = sub.amount - from_credit;
+
+        if due > 0 {
+            let client = token::Client::new(&env, &sub.token);
+            // A run already in progress follows the ladder it started with
+            let dunning: Option<DunningConfig> = if past_due {
+                None
+            } else {
+                env.storage()
+                    .persistent()
+                    .get(&DataKey3::DunningConfig(sub.merchant.clone()))
+            };
+            if past_due || dunning.is_some() {
+                let result =
+                    client.try_transfer(&sub.subscriber, env.current_contract_address(), &due);
+                if result.is_err() {
+                    Self::mark_subscription_past_due(&env, &mut sub, dunning.as_ref(), due, now);
+                    env.storage()
+                        .persistent()
+                        .set(&DataKey::Subscription(subscription_id), &sub);
+                    env.storage().persistent().extend_ttl(
+                        &DataKey::Subscription(subscription_id),
+                        PERSISTENT_LIFETIME_THRESHOLD,
+                        PERSISTENT_BUMP_AMOUNT,
+                    );
+                    return;
+                }
+            } else {
+                client.transfer(&sub.subscriber, env.current_contract_address(), &due);
+            }
+            client.transfer(&env.current_contract_address(), &sub.merchant, &due);
+        }
+        sub.credit -= from_credit;
+        if let Some(usage) = usage {
+            Self::close_usage_period(&env, subscription_id, usage);
+        }
+        if past_due {
+            events::emit_subscription_recovered(&env, subscription_id, due, sub.dunning_attempts);
+            sub.past_due_since = 0;
+            sub.dunning_attempts = 0;
+            sub.next_retry_at = 0;
+            sub.grace_ends_at = 0;
+            sub.dunning_ladder = Vec::new(&env);
+        }
 
         sub.last_charged_at = now;
         sub.charges_count += 1;
@@ -4919,7 +5394,7 @@ impl AhjoorPaymentsContract {
             subscription_id,
             sub.subscriber,
             sub.merchant,
-            sub.amount,
+            due,
             now,
         );
         if trial_just_ended {
@@ -4940,10 +5415,10 @@ impl AhjoorPaymentsContract {
             .storage()
             .persistent()
             .get(&DataKey::Subscription(subscription_id))
-            .expect("Subscription not found");
+            .unwrap_or_else(|| panic_with_error!(&env, SubscriptionError::SubscriptionNotFound));
 
         if caller != sub.subscriber && caller != sub.merchant {
-            panic!("Only subscriber or merchant can cancel");
+            panic_with_error!(&env, SubscriptionError::NotSubscriptionParty);
         }
 
         sub.active = false;
@@ -4964,1221 +5439,1976 @@ impl AhjoorPaymentsContract {
         env.storage()
             .persistent()
             .get(&DataKey::Subscription(subscription_id))
-            .expect("Subscription not found")
+            .unwrap_or_else(|| panic_with_error!(&env, SubscriptionError::SubscriptionNotFound))
     }
 
-    // --- Subscription Pause / Resume (#124) ---
+    // --- Subscription Plan Catalog ---
 
-    /// Subscriber temporarily pauses their subscription.
-    /// Charging is blocked while paused. Only the subscriber can pause.
-    pub fn pause_subscription(env: Env, subscriber: Address, sub_id: u32) {
+    /// Merchant defines a subscription product. Returns the plan_id.
+    pub fn create_subscription_plan(
+        env: Env,
+        merchant: Address,
+        token: Address,
+        price: i128,
+        interval_seconds: u64,
+        trial_seconds: u64,
+        max_charges: u32,
+    ) -> u32 {
         Self::require_not_paused(&env);
-        subscriber.require_auth();
+        merchant.require_auth();
+        Self::require_merchant_approved(&env, &merchant);
+        Self::require_token_allowed(&env, &token);
+        if price <= 0 {
+            panic_with_error!(&env, SubscriptionError::InvalidSubscriptionPlan);
+        }
+        if interval_seconds == 0 {
+            panic_with_error!(&env, SubscriptionError::InvalidInterval);
+        }
 
-        let mut sub: Subscription = env
+        let plan_id: u32 = env
             .storage()
-            .persistent()
-            .get(&DataKey::Subscription(sub_id))
-            .expect("Subscription not found");
+            .instance()
+            .get(&DataKey3::SubscriptionPlanCounter)
+            .unwrap_or(0)
+            + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey3::SubscriptionPlanCounter, &plan_id);
+        Self::save_subscription_plan(
+            &env,
+            &SubscriptionPlan {
+                plan_id,
+                merchant: merchant.clone(),
+                token,
+                price,
+                interval_seconds,
+                trial_seconds,
+                max_charges,
+                active: true,
+                pending_price: 0,
+                pending_price_at: 0,
+            },
+        );
 
-        if sub.subscriber != subscriber {
-            panic!("Only the subscriber can pause");
+        events::emit_subscription_plan_created(&env, plan_id, merchant, price, interval_seconds);
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        plan_id
+    }
+
+    /// Merchant schedules a new plan price for every subscriber on the plan.
+    /// `effective_at` must give at least `PLAN_PRICE_NOTICE_SECONDS` notice;
+    /// a later call replaces a change that has not yet applied.
+    pub fn schedule_plan_price_change(
+        env: Env,
+        merchant: Address,
+        plan_id: u32,
+        new_price: i128,
+        effective_at: u64,
+    ) {
+        Self::require_not_paused(&env);
+        merchant.require_auth();
+
+        let mut plan = Self::load_subscription_plan(&env, plan_id);
+        if plan.merchant != merchant {
+            panic_with_error!(&env, SubscriptionError::NotSubscriptionParty);
         }
-        if !sub.active {
-            panic!("Subscription is cancelled");
+        if new_price <= 0 || effective_at < env.ledger().timestamp() + PLAN_PRICE_NOTICE_SECONDS {
+            panic_with_error!(&env, SubscriptionError::InvalidSubscriptionPlan);
         }
-        if sub.paused {
-            panic!("Subscription already paused");
+        plan.pending_price = new_price;
+        plan.pending_price_at = effective_at;
+        Self::save_subscription_plan(&env, &plan);
+
+        events::emit_plan_price_change_scheduled(&env, plan_id, plan.price, new_price, effective_at);
+    }
+
+    /// Merchant opens or closes a plan to new subscribers. Existing
+    /// subscriptions keep being charged.
+    pub fn set_subscription_plan_active(env: Env, merchant: Address, plan_id: u32, active: bool) {
+        merchant.require_auth();
+        let mut plan = Self::load_subscription_plan(&env, plan_id);
+        if plan.merchant != merchant {
+            panic_with_error!(&env, SubscriptionError::NotSubscriptionParty);
         }
+        plan.active = active;
+        Self::save_subscription_plan(&env, &plan);
+    }
 
-        let now = env.ledger().timestamp();
-        sub.paused = true;
-        sub.paused_at = now;
+    /// Subscriber signs up to a plan at its current price, starting with
+    /// the plan's trial. Returns the subscription_id.
+    pub fn subscribe_to_plan(env: Env, subscriber: Address, plan_id: u32) -> u32 {
+        let plan = Self::load_subscription_plan(&env, plan_id);
+        if !plan.active {
+            panic_with_error!(&env, SubscriptionError::SubscriptionPlanInactive);
+        }
+        let trial = if plan.trial_seconds > 0 {
+            Some(plan.trial_seconds)
+        } else {
+            None
+        };
+        let sub_id = Self::create_subscription_with_trial(
+            env.clone(),
+            subscriber,
+            plan.merchant,
+            plan.price,
+            plan.token,
+            plan.interval_seconds,
+            plan.max_charges,
+            trial,
+        );
 
+        let mut sub: Subscription = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Subscription(sub_id))
+            .unwrap();
+        sub.plan_id = plan_id;
         env.storage()
             .persistent()
             .set(&DataKey::Subscription(sub_id), &sub);
-        env.storage().persistent().extend_ttl(
-            &DataKey::Subscription(sub_id),
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
-
-        events::emit_subscription_paused(&env, sub_id, now);
-        env.storage()
-            .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::add_plan_subscriber(&env, plan_id, sub_id);
+        sub_id
     }
 
-    /// Subscriber resumes a paused subscription.
-    /// The next charge interval restarts from the resume timestamp,
-    /// so paused time does not count toward the interval.
-    pub fn resume_subscription(env: Env, subscriber: Address, sub_id: u32) {
+    /// Subscriber moves to another plan of the same merchant and token. A
+    /// paid period restarts on the new plan: the unused part of what was paid
+    /// for the current period is credited and the new price charged, leaving a
+    /// net charge or a credit against future charges. Only a net charge counts
+    /// toward `max_charges`. Credit is not refunded on cancel. Before the first
+    /// charge the plan is simply swapped.
+    pub fn change_subscription_plan(
+        env: Env,
+        subscriber: Address,
+        subscription_id: u32,
+        new_plan_id: u32,
+    ) {
         Self::require_not_paused(&env);
         subscriber.require_auth();
 
         let mut sub: Subscription = env
             .storage()
             .persistent()
-            .get(&DataKey::Subscription(sub_id))
-            .expect("Subscription not found");
-
+            .get(&DataKey::Subscription(subscription_id))
+            .unwrap_or_else(|| panic_with_error!(&env, SubscriptionError::SubscriptionNotFound));
         if sub.subscriber != subscriber {
-            panic!("Only the subscriber can resume");
+            panic_with_error!(&env, SubscriptionError::NotSubscriber);
         }
         if !sub.active {
-            panic!("Subscription is cancelled");
+            panic_with_error!(&env, SubscriptionError::SubscriptionCancelled);
         }
-        if !sub.paused {
-            panic!("Subscription is not paused");
+        if sub.paused {
+            panic_with_error!(&env, Error::SubscriptionPaused);
+        }
+        // The outstanding charge has to be settled through the dunning retries first
+        if sub.past_due_since > 0 {
+            panic_with_error!(&env, SubscriptionError::SubscriptionPastDue);
+        }
+        let new_plan = Self::load_subscription_plan(&env, new_plan_id);
+        if !new_plan.active {
+            panic_with_error!(&env, SubscriptionError::SubscriptionPlanInactive);
+        }
+        if sub.plan_id == 0
+            || sub.plan_id == new_plan_id
+            || new_plan.merchant != sub.merchant
+            || new_plan.token != sub.token
+        {
+            panic_with_error!(&env, SubscriptionError::InvalidPlanChange);
         }
+        let old_plan_id = sub.plan_id;
 
         let now = env.ledger().timestamp();
-        sub.paused = false;
-        sub.paused_at = 0;
-        // Reset last_charged_at so the next interval starts from now,
-        // ensuring paused duration does not count.
-        sub.last_charged_at = now;
+        let mut charged = 0i128;
+        if sub.last_charged_at > 0 {
+            // Credit what was actually paid for the period, not the plan's current price
+            let period_end = sub.last_charged_at + sub.interval_seconds;
+            let unused = period_end.saturating_sub(now) as i128;
+            let unused_credit = sub.amount * unused / sub.interval_seconds as i128;
+            let net = new_plan.price - unused_credit - sub.credit;
+            if net > 0 {
+                charged = net;
+                sub.credit = 0;
+                let client = token::Client::new(&env, &sub.token);
+                client.transfer(&sub.subscriber, env.current_contract_address(), &net);
+                client.transfer(&env.current_contract_address(), &sub.merchant, &net);
+                sub.charges_count += 1;
+            } else {
+                sub.credit = -net;
+            }
+            sub.last_charged_at = now;
+        }
 
+        sub.plan_id = new_plan_id;
+        sub.amount = new_plan.price;
+        sub.interval_seconds = new_plan.interval_seconds;
+        sub.max_charges = new_plan.max_charges;
         env.storage()
             .persistent()
-            .set(&DataKey::Subscription(sub_id), &sub);
+            .set(&DataKey::Subscription(subscription_id), &sub);
         env.storage().persistent().extend_ttl(
-            &DataKey::Subscription(sub_id),
+            &DataKey::Subscription(subscription_id),
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Self::add_plan_subscriber(&env, new_plan_id, subscription_id);
 
-        events::emit_subscription_resumed(&env, sub_id, now);
-        env.storage()
-            .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        events::emit_subscription_plan_changed(
+            &env,
+            subscription_id,
+            old_plan_id,
+            new_plan_id,
+            charged,
+            sub.credit,
+        );
     }
 
-    /// #327: Pause subscription with authority enforcement and pause count tracking.
-    pub fn pause_subscription_v2(env: Env, caller: Address, sub_id: u32, reason_hash: BytesN<32>) {
-        Self::require_not_paused(&env);
-        caller.require_auth();
+    /// Read a subscription plan, with any scheduled price change that is due applied.
+    pub fn get_subscription_plan(env: Env, plan_id: u32) -> SubscriptionPlan {
+        let mut plan = Self::stored_subscription_plan(&env, plan_id);
+        Self::apply_due_plan_price(&env, &mut plan);
+        plan
+    }
 
-        let mut sub: Subscription = env
+    /// Active subscriptions currently on a plan.
+    pub fn get_plan_subscribers(env: Env, plan_id: u32) -> Vec<u32> {
+        let ids: Vec<u32> = env
             .storage()
             .persistent()
-            .get(&DataKey::Subscription(sub_id))
-            .expect("Subscription not found");
-
-        // Check pause authority
-        let is_subscriber = sub.subscriber == caller;
-        let is_merchant = sub.merchant == caller;
-        match sub.pause_authority {
-            PauseAuthority::Subscriber if !is_subscriber => {
-                panic_with_error!(&env, Error::UnauthorizedPause)
-            }
-            PauseAuthority::Merchant if !is_merchant => {
-                panic_with_error!(&env, Error::UnauthorizedPause)
-            }
-            PauseAuthority::Both if !is_subscriber && !is_merchant => {
-                panic_with_error!(&env, Error::UnauthorizedPause)
+            .get(&DataKey3::PlanSubscribers(plan_id))
+            .unwrap_or(Vec::new(&env));
+        let mut current = Vec::new(&env);
+        for id in ids.iter() {
+            let sub: Option<Subscription> =
+                env.storage().persistent().get(&DataKey::Subscription(id));
+            if let Some(sub) = sub {
+                if sub.active && sub.plan_id == plan_id {
+                    current.push_back(id);
+                }
             }
-            _ => {}
         }
+        current
+    }
 
-        if !sub.active || sub.paused {
-            panic!("Invalid subscription state");
+    /// Internal: load a plan, applying and saving a scheduled price change that is due.
+    fn load_subscription_plan(env: &Env, plan_id: u32) -> SubscriptionPlan {
+        let mut plan = Self::stored_subscription_plan(env, plan_id);
+        if Self::apply_due_plan_price(env, &mut plan) {
+            Self::save_subscription_plan(env, &plan);
         }
+        plan
+    }
 
-        // Check pause count limit
-        if sub.pause_count >= MAX_SUBSCRIPTION_PAUSES {
-            panic_with_error!(&env, Error::PauseCountExceeded);
-        }
+    fn stored_subscription_plan(env: &Env, plan_id: u32) -> SubscriptionPlan {
+        env.storage()
+            .persistent()
+            .get(&DataKey3::SubscriptionPlan(plan_id))
+            .unwrap_or_else(|| panic_with_error!(env, SubscriptionError::SubscriptionPlanNotFound))
+    }
 
-        sub.paused = true;
-        sub.paused_at = env.ledger().sequence() as u64;
-        sub.pause_count += 1;
+    /// Internal: swap in a pending price once it is due. Returns whether it applied.
+    fn apply_due_plan_price(env: &Env, plan: &mut SubscriptionPlan) -> bool {
+        if plan.pending_price_at == 0 || env.ledger().timestamp() < plan.pending_price_at {
+            return false;
+        }
+        plan.price = plan.pending_price;
+        plan.pending_price = 0;
+        plan.pending_price_at = 0;
+        true
+    }
 
+    fn save_subscription_plan(env: &Env, plan: &SubscriptionPlan) {
+        let key = DataKey3::SubscriptionPlan(plan.plan_id);
+        env.storage().persistent().set(&key, plan);
         env.storage()
             .persistent()
-            .set(&DataKey::Subscription(sub_id), &sub);
-        env.storage().persistent().extend_ttl(
-            &DataKey::Subscription(sub_id),
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    }
 
-        events::emit_subscription_paused_v2(&env, sub_id, caller, env.ledger().sequence());
+    fn add_plan_subscriber(env: &Env, plan_id: u32, subscription_id: u32) {
+        let key = DataKey3::PlanSubscribers(plan_id);
+        let mut ids: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        if !ids.contains(subscription_id) {
+            ids.push_back(subscription_id);
+            env.storage().persistent().set(&key, &ids);
+        }
         env.storage()
-            .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
     }
 
-    /// #327: Resume subscription with prorated billing recalculation.
-    pub fn resume_subscription_v2(env: Env, caller: Address, sub_id: u32) -> u32 {
+    // --- Metered Subscriptions ---
+
+    /// Merchant prices a subscription by reported usage instead of a fixed
+    /// amount. Each charge bills the usage reported since the previous one,
+    /// priced against `tiers` and capped by the subscriber's `max_per_period`,
+    /// which must be set before usage is accepted. A replacement table keeps
+    /// the cap and only prices periods after the open one, so usage already
+    /// reported is never repriced. Plan subscriptions cannot be metered.
+    pub fn configure_metered_billing(
+        env: Env,
+        merchant: Address,
+        subscription_id: u32,
+        mode: MeteredPricingMode,
+        tiers: Vec<UsagePriceTier>,
+    ) {
         Self::require_not_paused(&env);
-        caller.require_auth();
+        merchant.require_auth();
 
-        let mut sub: Subscription = env
+        let sub: Subscription = env
             .storage()
             .persistent()
-            .get(&DataKey::Subscription(sub_id))
-            .expect("Subscription not found");
-
-        // Only subscriber or merchant can resume
-        let is_subscriber = sub.subscriber == caller;
-        let is_merchant = sub.merchant == caller;
-        if !is_subscriber && !is_merchant {
-            panic!("Unauthorized resume");
+            .get(&DataKey::Subscription(subscription_id))
+            .unwrap_or_else(|| panic_with_error!(&env, SubscriptionError::SubscriptionNotFound));
+        if sub.merchant != merchant {
+            panic_with_error!(&env, SubscriptionError::NotSubscriptionParty);
         }
-
-        if !sub.active || !sub.paused {
-            panic!("Invalid subscription state");
+        if !sub.active {
+            panic_with_error!(&env, SubscriptionError::SubscriptionCancelled);
         }
-
-        sub.paused = false;
-        sub.paused_at = 0;
-
-        // Prorated billing: next_due_ledger = current_ledger + interval_ledgers
-        // Convert interval_seconds to approximate ledgers (~5s per ledger)
-        let interval_ledgers = ((sub.interval_seconds + 4) / 5) as u32; // Round up
-        let next_due_ledger = env.ledger().sequence() + interval_ledgers;
-
+        if sub.plan_id != 0 || tiers.is_empty() || tiers.len() > MAX_USAGE_PRICE_TIERS {
+            panic_with_error!(&env, SubscriptionError::InvalidMeteredPricing);
+        }
+        let mut floor = 0i128;
+        for (i, tier) in tiers.iter().enumerate() {
+            let last = i as u32 == tiers.len() - 1;
+            let bounds_ok = if last {
+                tier.up_to == 0
+            } else {
+                tier.up_to > floor
+            };
+            if !bounds_ok || tier.unit_price < 0 {
+                panic_with_error!(&env, SubscriptionError::InvalidMeteredPricing);
+            }
+            floor = tier.up_to;
+        }
+
+        let key = DataKey3::MeteredPricing(subscription_id);
+        let existing: Option<MeteredPricing> = env.storage().persistent().get(&key);
+        let tier_count = tiers.len();
+        let (pricing, effective_period) = match existing {
+            Some(mut p) => {
+                p.pending_tiers = tiers;
+                p.pending_mode = mode;
+                p.pending_from_period = sub.charges_count + 1;
+                (p, sub.charges_count + 1)
+            }
+            None => (
+                MeteredPricing {
+                    mode,
+                    tiers,
+                    max_per_period: 0,
+                    started_at: env.ledger().timestamp(),
+                    pending_tiers: Vec::new(&env),
+                    pending_mode: mode,
+                    pending_from_period: 0,
+                },
+                sub.charges_count,
+            ),
+        };
+        env.storage().persistent().set(&key, &pricing);
         env.storage()
             .persistent()
-            .set(&DataKey::Subscription(sub_id), &sub);
-        env.storage().persistent().extend_ttl(
-            &DataKey::Subscription(sub_id),
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
-
-        events::emit_subscription_resumed_v2(&env, sub_id, caller, next_due_ledger);
-        env.storage()
-            .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
 
-        next_due_ledger
+        events::emit_metered_pricing_configured(
+            &env,
+            subscription_id,
+            merchant,
+            tier_count,
+            effective_period,
+        );
     }
 
-    /// #327: Admin force resume a paused subscription.
-    pub fn admin_resume_subscription(env: Env, admin: Address, sub_id: u32) -> u32 {
-        admin.require_auth();
-        Self::require_admin(&env, &admin);
+    /// Subscriber sets the most a single metered period may charge.
+    pub fn set_usage_cap(
+        env: Env,
+        subscriber: Address,
+        subscription_id: u32,
+        max_per_period: i128,
+    ) {
+        Self::require_not_paused(&env);
+        subscriber.require_auth();
 
-        let mut sub: Subscription = env
+        let sub: Subscription = env
             .storage()
             .persistent()
-            .get(&DataKey::Subscription(sub_id))
-            .expect("Subscription not found");
-
-        if !sub.active || !sub.paused {
-            panic!("Invalid subscription state");
+            .get(&DataKey::Subscription(subscription_id))
+            .unwrap_or_else(|| panic_with_error!(&env, SubscriptionError::SubscriptionNotFound));
+        if sub.subscriber != subscriber {
+            panic_with_error!(&env, SubscriptionError::NotSubscriber);
         }
-
-        sub.paused = false;
-        sub.paused_at = 0;
-
-        let interval_ledgers = ((sub.interval_seconds + 4) / 5) as u32;
-        let next_due_ledger = env.ledger().sequence() + interval_ledgers;
-
+        if max_per_period <= 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+        let mut pricing = Self::load_metered_pricing(&env, subscription_id);
+        pricing.max_per_period = max_per_period;
+        let key = DataKey3::MeteredPricing(subscription_id);
+        env.storage().persistent().set(&key, &pricing);
         env.storage()
             .persistent()
-            .set(&DataKey::Subscription(sub_id), &sub);
-        env.storage().persistent().extend_ttl(
-            &DataKey::Subscription(sub_id),
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
-
-        events::emit_subscription_resumed_v2(&env, sub_id, admin, next_due_ledger);
-        env.storage()
-            .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
 
-        next_due_ledger
+        events::emit_usage_cap_set(&env, subscription_id, max_per_period);
     }
 
-    // --- Payment Categories (#122) ---
+    /// Merchant allows or revokes an address reporting usage on its subscriptions.
+    pub fn set_usage_reporter(env: Env, merchant: Address, reporter: Address, authorized: bool) {
+        merchant.require_auth();
+        let key = DataKey3::UsageReporter(merchant, reporter);
+        if authorized {
+            env.storage().persistent().set(&key, &true);
+            env.storage().persistent().extend_ttl(
+                &key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+    }
 
-    /*
-    /// Create a payment with optional category, tags, and release condition.
-    /// Category and tags enable on-chain segmentation and analytics.
-    /// release_condition, if set, must be satisfied at completion time (#125).
-    #[allow(clippy::too_many_arguments)]
-    pub fn create_payment_with_extras(
-        env: Env,
-        customer: Address,
-        merchant: Address,
-        amount: i128,
-        token: Address,
-        category: Option<Symbol>,
-        tags: Option<Vec<Symbol>>,
-        release_condition: Option<OracleCondition>,
-    ) -> u32 {
+    /// Merchant or an authorized reporter records usage in the subscription's
+    /// open period. Returns the record id within the period.
+    pub fn report_usage(env: Env, reporter: Address, subscription_id: u32, quantity: i128) -> u32 {
         Self::require_not_paused(&env);
-        customer.require_auth();
+        reporter.require_auth();
 
-        if amount <= 0 {
-            panic!("Payment amount must be positive");
+        let sub: Subscription = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Subscription(subscription_id))
+            .unwrap_or_else(|| panic_with_error!(&env, SubscriptionError::SubscriptionNotFound));
+        if !sub.active {
+            panic_with_error!(&env, SubscriptionError::SubscriptionCancelled);
         }
-        if let Some(ref t) = tags {
-            if t.len() > MAX_TAGS {
-                panic!("Tags list cannot exceed 3 items");
-            }
+        if sub.paused {
+            panic_with_error!(&env, Error::SubscriptionPaused);
+        }
+        if Self::load_metered_pricing(&env, subscription_id).max_per_period == 0 {
+            panic_with_error!(&env, SubscriptionError::UsageCapNotSet);
+        }
+        if reporter != sub.merchant
+            && !env
+                .storage()
+                .persistent()
+                .get(&DataKey3::UsageReporter(sub.merchant.clone(), reporter.clone()))
+                .unwrap_or(false)
+        {
+            panic_with_error!(&env, SubscriptionError::NotUsageReporter);
+        }
+        if quantity <= 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
         }
 
-        Self::require_merchant_approved(&env, &merchant);
-        Self::enforce_rate_limit(&env, &customer, 1);
+        let period = sub.charges_count;
+        let key = DataKey3::UsageRecords(subscription_id, period);
+        let mut records: Vec<UsageRecord> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        if records.len() >= MAX_USAGE_RECORDS_PER_PERIOD {
+            panic_with_error!(&env, SubscriptionError::UsageRecordLimitReached);
+        }
+        let record_id = records.len() + 1;
+        records.push_back(UsageRecord {
+            record_id,
+            quantity,
+            reported_by: reporter.clone(),
+            reported_at: env.ledger().timestamp(),
+        });
+        env.storage().persistent().set(&key, &records);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
 
-        let client = token::Client::new(&env, &token);
-        client.transfer(&customer, &env.current_contract_address(), &amount);
+        events::emit_usage_reported(&env, subscription_id, period, record_id, quantity, reporter);
+        record_id
+    }
 
-        let timeout: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::PaymentTimeout)
-            .unwrap_or(DEFAULT_PAYMENT_TIMEOUT);
-        let now = env.ledger().timestamp();
+    /// Read a subscription's usage price table and cap.
+    pub fn get_metered_pricing(env: Env, subscription_id: u32) -> MeteredPricing {
+        Self::load_metered_pricing(&env, subscription_id)
+    }
 
-        let payment_id = Self::next_payment_id(&env);
-        let payment = Payment {
-            id: payment_id,
-            customer: customer.clone(),
-            merchant: merchant.clone(),
-            amount,
-            token: token.clone(),
-            status: PaymentStatus::Pending,
-            created_at: now,
-            expires_at: now + timeout,
-            refunded_amount: 0,
-            reference: None,
-            metadata: None,
-            split_recipients: None,
-            execute_after: 0,
-            category: category.clone(),
-            tags: tags.clone(),
-            capture_deadline: 0,
-            release_condition,
-        };
+    /// Usage records of a billing period. The open period is the
+    /// subscription's current `charges_count`.
+    pub fn get_usage_records(env: Env, subscription_id: u32, period: u32) -> Vec<UsageRecord> {
+        env.storage()
+            .persistent()
+            .get(&DataKey3::UsageRecords(subscription_id, period))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// What a period was charged, or for the open period what it would be
+    /// charged now under the current price table and cap.
+    pub fn get_usage_period(env: Env, subscription_id: u32, period: u32) -> UsagePeriod {
+        if let Some(closed) = env
+            .storage()
+            .persistent()
+            .get(&DataKey3::UsagePeriod(subscription_id, period))
+        {
+            return closed;
+        }
+        let pricing = Self::load_metered_pricing(&env, subscription_id);
+        Self::price_usage_period(&env, subscription_id, period, &pricing)
+    }
 
+    fn load_metered_pricing(env: &Env, subscription_id: u32) -> MeteredPricing {
         env.storage()
             .persistent()
-            .set(&DataKey::Payment(payment_id), &payment);
-        env.storage().persistent().extend_ttl(
-            &DataKey::Payment(payment_id),
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
+            .get(&DataKey3::MeteredPricing(subscription_id))
+            .unwrap_or_else(|| panic_with_error!(env, SubscriptionError::NotMeteredSubscription))
+    }
 
-        Self::add_customer_payment(&env, &customer, payment_id);
+    /// Internal: total and price a period's reported usage.
+    fn price_usage_period(
+        env: &Env,
+        subscription_id: u32,
+        period: u32,
+        pricing: &MeteredPricing,
+    ) -> UsagePeriod {
+        let records: Vec<UsageRecord> = env
+            .storage()
+            .persistent()
+            .get(&DataKey3::UsageRecords(subscription_id, period))
+            .unwrap_or(Vec::new(env));
+        let mut usage = 0i128;
+        for r in records.iter() {
+            usage = usage
+                .checked_add(r.quantity)
+                .unwrap_or_else(|| panic_with_error!(env, Error::ArithmeticOverflow));
+        }
 
-        // Index by category for analytics retrieval
-        if let Some(ref cat) = category {
-            let cat_key = DataKey::CategoryPayments(merchant.clone(), cat.clone());
-            let mut cat_ids: Vec<u32> = env
-                .storage()
-                .persistent()
-                .get(&cat_key)
-                .unwrap_or(Vec::new(&env));
-            cat_ids.push_back(payment_id);
-            env.storage().persistent().set(&cat_key, &cat_ids);
+        let (mode, tiers) =
+            if !pricing.pending_tiers.is_empty() && period >= pricing.pending_from_period {
+                (pricing.pending_mode, &pricing.pending_tiers)
+            } else {
+                (pricing.mode, &pricing.tiers)
+            };
+        let mut priced = 0i128;
+        match mode {
+            MeteredPricingMode::Tiered => {
+                let mut floor = 0i128;
+                for tier in tiers.iter() {
+                    if usage <= floor {
+                        break;
+                    }
+                    let top = if tier.up_to == 0 {
+                        usage
+                    } else {
+                        tier.up_to.min(usage)
+                    };
+                    priced = (top - floor)
+                        .checked_mul(tier.unit_price)
+                        .and_then(|v| v.checked_add(priced))
+                        .unwrap_or_else(|| panic_with_error!(env, Error::ArithmeticOverflow));
+                    floor = top;
+                }
+            }
+            MeteredPricingMode::Volume => {
+                for tier in tiers.iter() {
+                    if tier.up_to == 0 || usage <= tier.up_to {
+                        priced = usage
+                            .checked_mul(tier.unit_price)
+                            .unwrap_or_else(|| panic_with_error!(env, Error::ArithmeticOverflow));
+                        break;
+                    }
+                }
+            }
+        }
+
+        let charged = priced.min(pricing.max_per_period);
+        UsagePeriod {
+            period,
+            usage,
+            priced,
+            charged,
+            charged_at: 0,
+        }
+    }
+
+    /// Internal: keep a charged period's pricing for audit and move a
+    /// replacement table in once the next period is the one it prices.
+    fn close_usage_period(env: &Env, subscription_id: u32, mut summary: UsagePeriod) {
+        let mut pricing = Self::load_metered_pricing(env, subscription_id);
+        if !pricing.pending_tiers.is_empty() && pricing.pending_from_period <= summary.period + 1 {
+            pricing.mode = pricing.pending_mode;
+            pricing.tiers = pricing.pending_tiers.clone();
+            pricing.pending_tiers = Vec::new(env);
+            pricing.pending_from_period = 0;
+            let key = DataKey3::MeteredPricing(subscription_id);
+            env.storage().persistent().set(&key, &pricing);
             env.storage().persistent().extend_ttl(
-                &cat_key,
+                &key,
                 PERSISTENT_LIFETIME_THRESHOLD,
                 PERSISTENT_BUMP_AMOUNT,
             );
-            events::emit_payment_categorized(
-                &env,
-                payment_id,
-                merchant.clone(),
-                cat.clone(),
-                tags.clone().unwrap_or(Vec::new(&env)),
-            );
         }
 
-        Self::inc_global_created(&env);
-        Self::inc_merchant_created(&env, &merchant);
-        events::emit_payment_created(&env, payment_id, customer, merchant, amount, token);
-
+        summary.charged_at = env.ledger().timestamp();
+        let key = DataKey3::UsagePeriod(subscription_id, summary.period);
+        env.storage().persistent().set(&key, &summary);
         env.storage()
-            .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
 
-        payment_id
+        events::emit_usage_period_charged(
+            env,
+            subscription_id,
+            summary.period,
+            summary.usage,
+            summary.priced,
+            summary.charged,
+        );
     }
-    */
-    /// Return payment IDs for a merchant + category pair, paginated.
-    /// page is 0-indexed. Empty result when page exceeds available data.
-    pub fn get_payments_by_category(
+
+    // --- Subscription Dunning ---
+
+    /// Merchant sets how failed subscription charges are retried: at each
+    /// offset (seconds after the first failure) the charge may be retried,
+    /// service stays active for `grace_period_seconds`, and the subscription
+    /// is cancelled when the last retry fails. An empty ladder turns dunning
+    /// off, so failed charges revert as before. Subscriptions already past
+    /// due finish their run on the ladder they started with.
+    pub fn set_dunning_config(
         env: Env,
         merchant: Address,
-        category: Symbol,
-        page: u32,
-        page_size: u32,
-    ) -> Vec<u32> {
-        if page_size == 0 {
-            panic!("page_size must be positive");
+        retry_offsets: Vec<u64>,
+        grace_period_seconds: u64,
+    ) {
+        Self::require_not_paused(&env);
+        merchant.require_auth();
+
+        let key = DataKey3::DunningConfig(merchant.clone());
+        if retry_offsets.is_empty() {
+            env.storage().persistent().remove(&key);
+            events::emit_dunning_configured(&env, merchant, 0, grace_period_seconds);
+            return;
         }
-        let all: Vec<u32> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::CategoryPayments(merchant, category))
-            .unwrap_or(Vec::new(&env));
-        let total = all.len();
-        let start = page * page_size;
-        if start >= total {
-            return Vec::new(&env);
+        if retry_offsets.len() > MAX_DUNNING_RETRIES {
+            panic_with_error!(&env, SubscriptionError::InvalidDunningConfig);
         }
-        let end = (start + page_size).min(total);
-        let mut result = Vec::new(&env);
-        for i in start..end {
-            result.push_back(all.get(i).unwrap());
+        let mut prev = 0u64;
+        for offset in retry_offsets.iter() {
+            if offset <= prev {
+                panic_with_error!(&env, SubscriptionError::InvalidDunningConfig);
+            }
+            prev = offset;
         }
-        result
+
+        let retries = retry_offsets.len();
+        env.storage().persistent().set(
+            &key,
+            &DunningConfig {
+                retry_offsets,
+                grace_period_seconds,
+            },
+        );
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+
+        events::emit_dunning_configured(&env, merchant, retries, grace_period_seconds);
     }
 
-    /// Expire a batch of payments atomically. Admin only.
-    /// Each payment must be Pending, Disputed, or Authorized and past its expiry/capture deadline.
-    /// If any payment is ineligible the entire batch reverts.
-    /// Batch size capped at MAX_SETTLEMENT_BATCH_SIZE (50).
-    pub fn bulk_expire_payments(env: Env, admin: Address, payment_ids: Vec<u32>) {
-        Self::require_not_paused(&env);
-        admin.require_auth();
-        let stored_admin: Address = env
+    /// Read a merchant's dunning policy, if any.
+    pub fn get_dunning_config(env: Env, merchant: Address) -> Option<DunningConfig> {
+        env.storage()
+            .persistent()
+            .get(&DataKey3::DunningConfig(merchant))
+    }
+
+    /// Billing standing of a subscription. `Active` and `PastDue` mean
+    /// service should continue.
+    pub fn get_subscription_status(env: Env, subscription_id: u32) -> SubscriptionStatus {
+        let sub: Subscription = env
             .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Not initialized");
-        if admin != stored_admin {
-            panic!("Only admin can bulk expire payments");
+            .persistent()
+            .get(&DataKey::Subscription(subscription_id))
+            .unwrap_or_else(|| panic_with_error!(&env, SubscriptionError::SubscriptionNotFound));
+        if !sub.active {
+            SubscriptionStatus::Cancelled
+        } else if sub.paused {
+            SubscriptionStatus::Paused
+        } else if sub.past_due_since == 0 {
+            SubscriptionStatus::Active
+        } else if env.ledger().timestamp() < sub.grace_ends_at {
+            SubscriptionStatus::PastDue
+        } else {
+            SubscriptionStatus::Unpaid
         }
+    }
 
-        let batch_size = payment_ids.len();
-        if batch_size == 0 {
-            panic!("Batch cannot be empty");
-        }
-        if batch_size > MAX_SETTLEMENT_BATCH_SIZE {
-            panic!("Batch size exceeds maximum allowed");
+    /// Internal: record a failed charge against the dunning ladder, scheduling
+    /// the next retry or cancelling once the ladder is exhausted. `cfg` starts
+    /// a new run and is `None` for a failed retry, which keeps the ladder and
+    /// grace period the run started with.
+    fn mark_subscription_past_due(
+        env: &Env,
+        sub: &mut Subscription,
+        cfg: Option<&DunningConfig>,
+        due: i128,
+        now: u64,
+    ) {
+        match cfg {
+            Some(cfg) => {
+                sub.past_due_since = now;
+                sub.dunning_attempts = 0;
+                sub.grace_ends_at = now.saturating_add(cfg.grace_period_seconds);
+                sub.dunning_ladder = cfg.retry_offsets.clone();
+            }
+            None => sub.dunning_attempts += 1,
         }
 
-        let now = env.ledger().timestamp();
-        let now_ledger = env.ledger().sequence() as u64;
-
-        // Pass 1: validate all — any failure reverts the entire batch atomically
-        for payment_id in payment_ids.iter() {
-            let payment: Payment = env
-                .storage()
-                .persistent()
-                .get(&DataKey::Payment(payment_id))
-                .expect("Payment not found");
-
-            if payment.status != PaymentStatus::Pending
-                && payment.status != PaymentStatus::Disputed
-                && payment.status != PaymentStatus::Authorized
-            {
-                panic!("Payment is not in Pending, Disputed, or Authorized status");
+        match sub.dunning_ladder.get(sub.dunning_attempts) {
+            Some(offset) => {
+                sub.next_retry_at = sub.past_due_since.saturating_add(offset);
+                events::emit_subscription_past_due(
+                    env,
+                    sub.id,
+                    sub.subscriber.clone(),
+                    sub.merchant.clone(),
+                    due,
+                    sub.dunning_attempts + 1,
+                    sub.next_retry_at,
+                    sub.grace_ends_at,
+                );
             }
-            if payment.status == PaymentStatus::Authorized {
-                if payment.capture_deadline == 0 || now_ledger <= payment.capture_deadline {
-                    panic!("Payment has not expired yet");
-                }
-            } else {
-                let deadline = payment.expires_at;
-                if deadline == 0 || now < deadline {
-                    panic!("Payment has not expired yet");
-                }
+            None => {
+                sub.active = false;
+                sub.next_retry_at = 0;
+                events::emit_subscription_dunning_cancelled(
+                    env,
+                    sub.id,
+                    sub.subscriber.clone(),
+                    sub.merchant.clone(),
+                    due,
+                    sub.dunning_attempts + 1,
+                );
             }
         }
+    }
 
-        // Pass 2: process all refunds
-        let mut refund_total: i128 = 0;
-        for payment_id in payment_ids.iter() {
-            let mut payment: Payment = env
-                .storage()
-                .persistent()
-                .get(&DataKey::Payment(payment_id))
-                .expect("Payment not found");
+    // --- Subscription Pause / Resume (#124) ---
 
-            let refund_amount = payment.amount - payment.refunded_amount;
-            if refund_amount > 0 {
-                let client = token::Client::new(&env, &payment.token);
-                client.transfer(
-                    &env.current_contract_address(),
-                    &payment.customer,
-                    &refund_amount,
-                );
-                refund_total = refund_total
-                    .checked_add(refund_amount)
-                    .expect("Refund total overflow");
-            }
+    /// Subscriber temporarily pauses their subscription.
+    /// Charging is blocked while paused. Only the subscriber can pause.
+    pub fn pause_subscription(env: Env, subscriber: Address, sub_id: u32) {
+        Self::require_not_paused(&env);
+        subscriber.require_auth();
 
-            let old_status = payment.status;
-            payment.status = PaymentStatus::Expired;
-            env.storage()
-                .persistent()
-                .set(&DataKey::Payment(payment_id), &payment);
-            env.storage().persistent().extend_ttl(
-                &DataKey::Payment(payment_id),
-                PERSISTENT_LIFETIME_THRESHOLD,
-                PERSISTENT_BUMP_AMOUNT,
-            );
+        let mut sub: Subscription = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Subscription(sub_id))
+            .unwrap_or_else(|| panic_with_error!(&env, SubscriptionError::SubscriptionNotFound));
 
-            Self::inc_global_expired(&env);
-            events::emit_payment_expired(
-                &env,
-                payment_id,
-                payment.customer.clone(),
-                refund_amount,
-                now,
-            );
-            events::emit_payment_status_changed(
-                &env,
-                payment_id,
-                old_status,
-                PaymentStatus::Expired,
-            );
+        if sub.subscriber != subscriber {
+            panic_with_error!(&env, SubscriptionError::NotSubscriber);
         }
-
-        events::emit_bulk_expire_completed(&env, batch_size, refund_total);
-        env.storage()
-            .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-    }
-
-    pub fn pause_contract(env: Env, admin: Address, reason: String) {
-        Self::require_admin(&env, &admin);
-
-        if Self::is_paused(env.clone()) {
-            panic!("Contract already paused");
+        if !sub.active {
+            panic_with_error!(&env, SubscriptionError::SubscriptionCancelled);
         }
-
-        env.storage().instance().set(&DataKey::Paused, &true);
-        env.storage().instance().set(&DataKey::PauseReason, &reason);
-
-        events::emit_contract_paused(&env, admin, reason, env.ledger().timestamp());
-    }
-
-    pub fn resume_contract(env: Env, admin: Address) {
-        Self::require_admin(&env, &admin);
-
-        if !Self::is_paused(env.clone()) {
-            panic!("Contract is not paused");
+        if sub.paused {
+            panic_with_error!(&env, SubscriptionError::SubscriptionAlreadyPaused);
         }
 
-        env.storage().instance().set(&DataKey::Paused, &false);
-        env.storage().instance().remove(&DataKey::PauseReason);
-
-        events::emit_contract_resumed(&env, admin, env.ledger().timestamp());
-    }
+        let now = env.ledger().timestamp();
+        sub.paused = true;
+        sub.paused_at = now;
 
-    pub fn is_paused(env: Env) -> bool {
         env.storage()
-            .instance()
-            .get(&DataKey::Paused)
-            .unwrap_or(false)
-    }
+            .persistent()
+            .set(&DataKey::Subscription(sub_id), &sub);
+        env.storage().persistent().extend_ttl(
+            &DataKey::Subscription(sub_id),
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
 
-    pub fn get_pause_reason(env: Env) -> String {
+        events::emit_subscription_paused(&env, sub_id, now);
         env.storage()
             .instance()
-            .get(&DataKey::PauseReason)
-            .unwrap_or(String::from_str(&env, ""))
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
     }
 
-    // --- Merchant Withdrawal Queue (#126) ---
-
-    /// Process up to max_count entries from the merchant's withdrawal queue.
-    /// Transfers funds from contract to merchant in FIFO order.
-    /// Merchant must authorize the call.
-    /// Returns the number of payments processed.
-    pub fn process_withdrawal_queue(env: Env, merchant: Address, max_count: u32) -> u32 {
+    /// Subscriber resumes a paused subscription.
+    /// The next charge interval restarts from the resume timestamp,
+    /// so paused time does not count toward the interval.
+    pub fn resume_subscription(env: Env, subscriber: Address, sub_id: u32) {
         Self::require_not_paused(&env);
-        merchant.require_auth();
-
-        let queue = Self::get_withdrawal_queue(&env, &merchant);
-        if queue.is_empty() {
-            return 0;
-        }
-
-        let max_count = if max_count == 0 {
-            DEFAULT_MAX_BATCH_SIZE
-        } else {
-            max_count
-        };
-        let process_count = max_count.min(queue.len() as u32) as usize;
-
-        let mut processed_count = 0u32;
-        let mut remaining_queue = Vec::new(&env);
-
-        // Process the first process_count entries
-        for i in 0..process_count {
-            let (payment_id, amount) = queue.get(i as u32).unwrap();
-            processed_count += 1;
+        subscriber.require_auth();
 
-            let payment: Payment = env
-                .storage()
-                .persistent()
-                .get(&DataKey::Payment(payment_id))
-                .expect("Payment not found in queue");
-            if payment.status != PaymentStatus::Completed {
-                panic!("Payment in queue is not completed");
-            }
+        let mut sub: Subscription = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Subscription(sub_id))
+            .unwrap_or_else(|| panic_with_error!(&env, SubscriptionError::SubscriptionNotFound));
 
-            let token_client = token::Client::new(&env, &payment.token);
-            token_client.transfer(&env.current_contract_address(), &merchant, &amount);
+        if sub.subscriber != subscriber {
+            panic_with_error!(&env, SubscriptionError::NotSubscriber);
         }
-
-        // Remove processed entries from queue
-        for i in process_count..(queue.len() as usize) {
-            remaining_queue.push_back(queue.get(i as u32).unwrap());
+        if !sub.active {
+            panic_with_error!(&env, SubscriptionError::SubscriptionCancelled);
+        }
+        if !sub.paused {
+            panic_with_error!(&env, SubscriptionError::SubscriptionNotPaused);
         }
 
-        Self::set_withdrawal_queue(&env, &merchant, remaining_queue);
-        events::emit_withdrawal_processed(&env, merchant, processed_count);
+        let now = env.ledger().timestamp();
+        sub.paused = false;
+        sub.paused_at = 0;
+        // Reset last_charged_at so the next interval starts from now,
+        // ensuring paused duration does not count.
+        sub.last_charged_at = now;
 
+        env.storage()
+            .persistent()
+            .set(&DataKey::Subscription(sub_id), &sub);
+        env.storage().persistent().extend_ttl(
+            &DataKey::Subscription(sub_id),
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        events::emit_subscription_resumed(&env, sub_id, now);
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-
-        processed_count
     }
 
-    /// Move a specific payment to the front of the merchant's withdrawal queue.
-    /// Merchant must authorize the call.
-    pub fn prioritize_withdrawal(env: Env, merchant: Address, payment_id: u32) {
+    /// #327: Pause subscription with authority enforcement and pause count tracking.
+    pub fn pause_subscription_v2(env: Env, caller: Address, sub_id: u32, reason_hash: BytesN<32>) {
         Self::require_not_paused(&env);
-        merchant.require_auth();
+        caller.require_auth();
 
-        let queue = Self::get_withdrawal_queue(&env, &merchant);
-        if queue.is_empty() {
-            panic!("Withdrawal queue is empty");
-        }
+        let mut sub: Subscription = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Subscription(sub_id))
+            .unwrap_or_else(|| panic_with_error!(&env, SubscriptionError::SubscriptionNotFound));
 
-        // Find the payment in the queue
-        let mut found_index = None;
-        let mut found_amount = 0i128;
-        for i in 0..queue.len() {
-            let (pid, amount) = queue.get(i).unwrap();
-            if pid == payment_id {
-                found_index = Some(i);
-                found_amount = amount;
-                break;
+        // Check pause authority
+        let is_subscriber = sub.subscriber == caller;
+        let is_merchant = sub.merchant == caller;
+        match sub.pause_authority {
+            PauseAuthority::Subscriber if !is_subscriber => {
+                panic_with_error!(&env, Error::UnauthorizedPause)
+            }
+            PauseAuthority::Merchant if !is_merchant => {
+                panic_with_error!(&env, Error::UnauthorizedPause)
             }
+            PauseAuthority::Both if !is_subscriber && !is_merchant => {
+                panic_with_error!(&env, Error::UnauthorizedPause)
+            }
+            _ => {}
         }
 
-        if found_index.is_none() {
-            panic!("Payment not found in withdrawal queue");
+        if !sub.active || sub.paused {
+            panic_with_error!(&env, SubscriptionError::InvalidSubscriptionState);
         }
 
-        let index = found_index.unwrap();
-
-        // If already at front, do nothing
-        if index == 0 {
-            return;
+        // Check pause count limit
+        if sub.pause_count >= MAX_SUBSCRIPTION_PAUSES {
+            panic_with_error!(&env, Error::PauseCountExceeded);
         }
 
-        // Remove from current position and insert at front
-        let mut new_queue = Vec::new(&env);
-        new_queue.push_back((payment_id, found_amount));
-
-        for i in 0..queue.len() {
-            if i != index {
-                new_queue.push_back(queue.get(i).unwrap());
-            }
-        }
+        sub.paused = true;
+        sub.paused_at = env.ledger().sequence() as u64;
+        sub.pause_count += 1;
 
-        Self::set_withdrawal_queue(&env, &merchant, new_queue);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Subscription(sub_id), &sub);
+        env.storage().persistent().extend_ttl(
+            &DataKey::Subscription(sub_id),
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
 
+        events::emit_subscription_paused_v2(&env, sub_id, caller, env.ledger().sequence());
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
     }
 
-    /// Get the merchant's current withdrawal queue.
-    pub fn get_merchant_withdrawal_queue(env: Env, merchant: Address) -> Vec<(u32, i128)> {
-        Self::get_withdrawal_queue(&env, &merchant)
-    }
-
-    // --- Internal Helpers ---
+    /// #327: Resume subscription with prorated billing recalculation.
+    pub fn resume_subscription_v2(env: Env, caller: Address, sub_id: u32) -> u32 {
+        Self::require_not_paused(&env);
+        caller.require_auth();
 
-    fn require_not_paused(env: &Env) {
-        if env
+        let mut sub: Subscription = env
             .storage()
-            .instance()
-            .get(&DataKey::Paused)
-            .unwrap_or(false)
-        {
-            panic!("Contract is paused");
-        }
-    }
-
-    // -------------------------------------------------------------------------
-    // #231: Merchant Withdrawal Rate Limiting
-    // -------------------------------------------------------------------------
+            .persistent()
+            .get(&DataKey::Subscription(sub_id))
+            .unwrap_or_else(|| panic_with_error!(&env, SubscriptionError::SubscriptionNotFound));
 
-    /// Admin sets global default withdrawal window and cap.
-    pub fn set_withdrawal_window(env: Env, admin: Address, window_seconds: u64, cap: i128) {
-        Self::require_not_paused(&env);
-        admin.require_auth();
-        let stored_admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Not initialized");
-        if admin != stored_admin {
-            panic!("Only admin can set withdrawal window");
-        }
-        if window_seconds == 0 {
-            panic!("Window must be positive");
-        }
-        if cap <= 0 {
-            panic!("Cap must be positive");
+        // Only subscriber or merchant can resume
+        let is_subscriber = sub.subscriber == caller;
+        let is_merchant = sub.merchant == caller;
+        if !is_subscriber && !is_merchant {
+            panic_with_error!(&env, SubscriptionError::NotSubscriptionParty);
         }
-        env.storage()
-            .instance()
-            .set(&DataKey2::WithdrawalWindowSeconds, &window_seconds);
-        env.storage()
-            .instance()
-            .set(&DataKey2::WithdrawalWindowCap, &cap);
-        env.storage()
-            .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-    }
 
-    /// Merchant sets a personal (stricter) withdrawal limit.
-    pub fn set_withdrawal_limit(env: Env, merchant: Address, window_seconds: u64, cap: i128) {
-        Self::require_not_paused(&env);
-        merchant.require_auth();
-        if window_seconds == 0 {
-            panic!("Window must be positive");
-        }
-        if cap <= 0 {
-            panic!("Cap must be positive");
+        if !sub.active || !sub.paused {
+            panic_with_error!(&env, SubscriptionError::InvalidSubscriptionState);
         }
-        let limit = WithdrawalLimit {
-            window_seconds,
-            cap,
-        };
+
+        sub.paused = false;
+        sub.paused_at = 0;
+
+        // Prorated billing: next_due_ledger = current_ledger + interval_ledgers
+        // Convert interval_seconds to approximate ledgers (~5s per ledger)
+        let interval_ledgers = ((sub.interval_seconds + 4) / 5) as u32; // Round up
+        let next_due_ledger = env.ledger().sequence() + interval_ledgers;
+
         env.storage()
             .persistent()
-            .set(&DataKey2::MerchantWithdrawalLimit(merchant.clone()), &limit);
+            .set(&DataKey::Subscription(sub_id), &sub);
         env.storage().persistent().extend_ttl(
-            &DataKey2::MerchantWithdrawalLimit(merchant.clone()),
+            &DataKey::Subscription(sub_id),
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
-        events::emit_withdrawal_rate_limit_set(&env, merchant, window_seconds, cap);
+
+        events::emit_subscription_resumed_v2(&env, sub_id, caller, next_due_ledger);
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        next_due_ledger
     }
 
-    /// Admin overrides a merchant's withdrawal cap (emergency override).
-    pub fn override_withdrawal_limit(env: Env, admin: Address, merchant: Address, cap: i128) {
-        Self::require_not_paused(&env);
+    /// #327: Admin force resume a paused subscription.
+    pub fn admin_resume_subscription(env: Env, admin: Address, sub_id: u32) -> u32 {
         admin.require_auth();
-        let stored_admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Not initialized");
-        if admin != stored_admin {
-            panic!("Only admin can override withdrawal limit");
-        }
-        if cap <= 0 {
-            panic!("Cap must be positive");
-        }
-        // Preserve existing window or use global default
-        let window_seconds: u64 = env
+        Self::require_admin(&env, &admin);
+
+        let mut sub: Subscription = env
             .storage()
             .persistent()
-            .get::<DataKey2, WithdrawalLimit>(&DataKey2::MerchantWithdrawalLimit(merchant.clone()))
-            .map(|l| l.window_seconds)
-            .unwrap_or_else(|| {
-                env.storage()
-                    .instance()
-                    .get(&DataKey2::WithdrawalWindowSeconds)
-                    .unwrap_or(24 * 60 * 60)
-            });
-        let limit = WithdrawalLimit {
-            window_seconds,
-            cap,
-        };
+            .get(&DataKey::Subscription(sub_id))
+            .unwrap_or_else(|| panic_with_error!(&env, SubscriptionError::SubscriptionNotFound));
+
+        if !sub.active || !sub.paused {
+            panic_with_error!(&env, SubscriptionError::InvalidSubscriptionState);
+        }
+
+        sub.paused = false;
+        sub.paused_at = 0;
+
+        let interval_ledgers = ((sub.interval_seconds + 4) / 5) as u32;
+        let next_due_ledger = env.ledger().sequence() + interval_ledgers;
+
         env.storage()
             .persistent()
-            .set(&DataKey2::MerchantWithdrawalLimit(merchant.clone()), &limit);
+            .set(&DataKey::Subscription(sub_id), &sub);
         env.storage().persistent().extend_ttl(
-            &DataKey2::MerchantWithdrawalLimit(merchant.clone()),
+            &DataKey::Subscription(sub_id),
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
-        events::emit_withdrawal_rate_limit_set(&env, merchant, window_seconds, cap);
+
+        events::emit_subscription_resumed_v2(&env, sub_id, admin, next_due_ledger);
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        next_due_ledger
     }
 
-    pub fn set_default_withdrawal_limits(env: Env, admin: Address, window_seconds: u64, cap: i128) {
+    // --- Payment Categories (#122) ---
+
+    /*
+    /// Create a payment with optional category, tags, and release condition.
+    /// Category and tags enable on-chain segmentation and analytics.
+    /// release_condition, if set, must be satisfied at completion time (#125).
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_payment_with_extras(
+        env: Env,
+        customer: Address,
+        merchant: Address,
+        amount: i128,
+        token: Address,
+        category: Option<Symbol>,
+        tags: Option<Vec<Symbol>>,
+        release_condition: Option<OracleCondition>,
+    ) -> u32 {
         Self::require_not_paused(&env);
-        Self::require_admin(&env, &admin);
+        customer.require_auth();
 
-        if window_seconds == 0 {
-            panic!("Window must be positive");
-        }
-        if cap <= 0 {
-            panic!("Cap must be positive");
+        if amount <= 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
         }
-
-        env.storage()
-            .instance()
-            .set(&DataKey2::WithdrawalWindowSeconds, &window_seconds);
-        env.storage()
-            .instance()
-            .set(&DataKey2::WithdrawalWindowCap, &cap);
-
-        env.storage()
-            .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-    }
-
-    pub fn get_withdrawal_rate_limit(env: Env, merchant: Address) -> (u64, i128) {
-        if let Some(limit) = env.storage().persistent().get::<DataKey2, WithdrawalLimit>(
-            &DataKey2::MerchantWithdrawalLimit(merchant.clone()),
-        ) {
-            (limit.window_seconds, limit.cap)
-        } else {
-            let window_seconds = env.storage().instance().get::<DataKey2, u64>(&DataKey2::WithdrawalWindowSeconds).unwrap_or(86400);
-            let cap = env.storage().instance().get::<DataKey2, i128>(&DataKey2::WithdrawalWindowCap).unwrap_or(i128::MAX);
-            (window_seconds, cap)
+        if let Some(ref t) = tags {
+            if t.len() > MAX_TAGS {
+                panic_with_error!(&env, PaymentError::TooManyTags);
+            }
         }
-    }
 
-    /// Internal: check and update the rolling withdrawal window for a merchant.
-    /// Panics with WithdrawalRateLimitExceeded if the cap would be exceeded.
-    fn check_and_update_withdrawal_rate_limit(env: &Env, merchant: &Address, amount: i128) {
-        // Determine effective limit: merchant-specific > global default > no limit
-        let (window_seconds, cap) =
-            if let Some(limit) = env.storage().persistent().get::<DataKey2, WithdrawalLimit>(
-                &DataKey2::MerchantWithdrawalLimit(merchant.clone()),
-            ) {
-                (limit.window_seconds, limit.cap)
-            } else if let (Some(w), Some(c)) = (
-                env.storage()
-                    .instance()
-                    .get::<DataKey2, u64>(&DataKey2::WithdrawalWindowSeconds),
-                env.storage()
-                    .instance()
-                    .get::<DataKey2, i128>(&DataKey2::WithdrawalWindowCap),
-            ) {
-                (w, c)
-            } else {
-                // No rate limit configured — allow
-                return;
-            };
+        Self::require_merchant_approved(&env, &merchant);
+        Self::enforce_rate_limit(&env, &customer, 1);
 
-        let now = env.ledger().timestamp();
-        let state_key = DataKey2::MerchantWithdrawalWindow(merchant.clone());
+        let client = token::Client::new(&env, &token);
+        client.transfer(&customer, &env.current_contract_address(), &amount);
 
-        let mut state: WithdrawalWindowState = env
+        let timeout: u64 = env
             .storage()
-            .persistent()
-            .get(&state_key)
-            .unwrap_or(WithdrawalWindowState {
-                window_start: now,
-                withdrawn: 0,
-            });
-
-        // Reset window if it has elapsed
-        if now >= state.window_start + window_seconds {
-            state.window_start = now;
-            state.withdrawn = 0;
-        }
+            .instance()
+            .get(&DataKey::PaymentTimeout)
+            .unwrap_or(DEFAULT_PAYMENT_TIMEOUT);
+        let now = env.ledger().timestamp();
 
-        let new_total = state.withdrawn.checked_add(amount).expect("Overflow");
-        if new_total > cap {
-            events::emit_withdrawal_rate_limit_exceeded(env, merchant.clone(), new_total, cap);
-            panic_with_error!(env, Error::WithdrawalRateLimitExceeded);
-        }
+        let payment_id = Self::next_payment_id(&env);
+        let payment = Payment {
+            id: payment_id,
+            customer: customer.clone(),
+            merchant: merchant.clone(),
+            amount,
+            token: token.clone(),
+            status: PaymentStatus::Pending,
+            created_at: now,
+            expires_at: now + timeout,
+            refunded_amount: 0,
+            reference: None,
+            metadata: None,
+            split_recipients: None,
+            execute_after: 0,
+            category: category.clone(),
+            tags: tags.clone(),
+            capture_deadline: 0,
+            release_condition,
+        };
 
-        state.withdrawn = new_total;
-        env.storage().persistent().set(&state_key, &state);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Payment(payment_id), &payment);
         env.storage().persistent().extend_ttl(
-            &state_key,
+            &DataKey::Payment(payment_id),
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
-    }
 
-    fn require_admin(env: &Env, admin: &Address) {
-        admin.require_auth();
-        let stored_admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Not initialized");
-        if stored_admin != *admin {
-            panic!("Only admin can manage pause state");
-        }
-    }
+        Self::add_customer_payment(&env, &customer, payment_id);
 
-    /// Validates that a token is allowed via the whitelist contract
-    fn require_token_allowed(env: &Env, token: &Address) {
-        if let Some(whitelist_contract) = env
-            .storage()
-            .instance()
-            .get::<DataKey, Address>(&DataKey::TokenWhitelistContract)
-        {
-            let client = TokenWhitelistClient::new(env, &whitelist_contract);
-            if !client.is_token_allowed_for_contract(&env.current_contract_address(), token) {
-                panic_with_error!(env, Error::TokenNotAllowed);
-            }
+        // Index by category for analytics retrieval
+        if let Some(ref cat) = category {
+            let cat_key = DataKey::CategoryPayments(merchant.clone(), cat.clone());
+            let mut cat_ids: Vec<u32> = env
+                .storage()
+                .persistent()
+                .get(&cat_key)
+                .unwrap_or(Vec::new(&env));
+            cat_ids.push_back(payment_id);
+            env.storage().persistent().set(&cat_key, &cat_ids);
+            env.storage().persistent().extend_ttl(
+                &cat_key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+            events::emit_payment_categorized(
+                &env,
+                payment_id,
+                merchant.clone(),
+                cat.clone(),
+                tags.clone().unwrap_or(Vec::new(&env)),
+            );
         }
-    }
 
-    fn complete_payment_internal(env: &Env, payment_id: u32, scheduled_only: bool) {
-        let mut payment: Payment = env
+        Self::inc_global_created(&env);
+        Self::inc_merchant_created(&env, &merchant);
+        events::emit_payment_created(&env, payment_id, customer, merchant, amount, token);
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        payment_id
+    }
+    */
+    /// Return payment IDs for a merchant + category pair, paginated.
+    /// page is 0-indexed. Empty result when page exceeds available data.
+    pub fn get_payments_by_category(
+        env: Env,
+        merchant: Address,
+        category: Symbol,
+        page: u32,
+        page_size: u32,
+    ) -> Vec<u32> {
+        if page_size == 0 {
+            panic_with_error!(&env, Error::InvalidPageSize);
+        }
+        let all: Vec<u32> = env
             .storage()
             .persistent()
-            .get(&DataKey::Payment(payment_id))
-            .expect("Payment not found");
-
-        if scheduled_only {
-            if payment.status != PaymentStatus::ScheduledPending {
-                panic!("Payment is not scheduled");
-            }
-            if env.ledger().timestamp() < payment.execute_after {
-                panic!("Payment cannot execute before schedule");
-            }
-        } else if payment.status != PaymentStatus::Pending {
-            panic!("Payment is not pending");
+            .get(&DataKey::CategoryPayments(merchant, category))
+            .unwrap_or(Vec::new(&env));
+        let total = all.len();
+        let start = page * page_size;
+        if start >= total {
+            return Vec::new(&env);
         }
-
-        if payment.expires_at > 0 && env.ledger().timestamp() >= payment.expires_at {
-            panic!("Payment has expired");
+        let end = (start + page_size).min(total);
+        let mut result = Vec::new(&env);
+        for i in start..end {
+            result.push_back(all.get(i).unwrap());
         }
-
-        // #246: Recompute token amount at current oracle rate for dynamic payments
-        Self::settle_dynamic_payment_if_needed(env, &mut payment);
-        // #235: Check customer spend limit before finalizing
-        Self::check_and_update_customer_spend_limit(
-            env,
-            &payment.merchant,
-            &payment.customer,
-            payment.amount,
-        );
-
-        Self::finalize_payment(env, payment_id, &mut payment);
+        result
     }
 
-    /// Merchant authorizes a payment by placing customer funds into escrow immediately.
-    /// The payment starts in Authorized status and must be captured on or before
-    /// `capture_deadline_ledger`.
-    pub fn authorize_payment(
-        env: Env,
-        merchant: Address,
-        customer: Address,
-        token: Address,
-        amount: i128,
-        capture_deadline_ledger: u64,
-    ) -> u32 {
+    /// Expire a batch of payments atomically. Callable by any `keeper`, who
+    /// signs the call and receives the keeper bounty for every payment expired.
+    /// Each payment must be Pending or Authorized and past its expiry/capture
+    /// deadline; disputed payments are left to dispute resolution. Repeated
+    /// ids are expired once. If any payment is ineligible the entire batch reverts.
+    /// Batch size capped at MAX_SETTLEMENT_BATCH_SIZE (50).
+    pub fn bulk_expire_payments(env: Env, keeper: Address, payment_ids: Vec<u32>) {
         Self::require_not_paused(&env);
-        merchant.require_auth();
+        keeper.require_auth();
 
-        if amount <= 0 {
-            panic!("Payment amount must be positive");
+        if payment_ids.is_empty() {
+            panic_with_error!(&env, PaymentError::EmptyBatch);
         }
-        if capture_deadline_ledger <= (env.ledger().sequence() as u64) {
-            panic!("capture_deadline_ledger must be in the future");
+        if payment_ids.len() > MAX_SETTLEMENT_BATCH_SIZE {
+            panic_with_error!(&env, PaymentError::BatchTooLarge);
+        }
+        let mut unique_ids: Vec<u32> = Vec::new(&env);
+        for payment_id in payment_ids.iter() {
+            if !unique_ids.contains(payment_id) {
+                unique_ids.push_back(payment_id);
+            }
         }
+        let batch_size = unique_ids.len();
 
-        Self::require_token_allowed(&env, &token);
-        Self::require_merchant_approved(&env, &merchant);
+        let now = env.ledger().timestamp();
+        let now_ledger = env.ledger().sequence() as u64;
 
-        let client = token::Client::new(&env, &token);
-        client.transfer_from(
-            &env.current_contract_address(),
-            &customer,
-            &env.current_contract_address(),
-            &amount,
-        );
+        // Pass 1: validate all — any failure reverts the entire batch atomically
+        for payment_id in unique_ids.iter() {
+            Self::load_expirable_payment(&env, payment_id, now, now_ledger);
+        }
 
-        let payment_id = Self::next_payment_id(&env);
-        let payment = Payment {
-            id: payment_id,
-            customer: customer.clone(),
-            merchant: merchant.clone(),
-            amount,
-            token: token.clone(),
-            status: PaymentStatus::Authorized,
-            created_at: env.ledger().timestamp(),
-            expires_at: 0,
-            refunded_amount: 0,
-            reference: None,
-            metadata: None,
-            split_recipients: None,
-            execute_after: 0,
-            category: None,
-            tags: None,
-            capture_deadline: capture_deadline_ledger,
-            external_id: None,
-            tipping_enabled: false,
-            extension_count: 0,
-        };
+        // Pass 2: mark each payment expired, then refund it
+        let mut refund_total: i128 = 0;
+        for payment_id in unique_ids.iter() {
+            let mut payment = Self::load_expirable_payment(&env, payment_id, now, now_ledger);
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::Payment(payment_id), &payment);
-        env.storage().persistent().extend_ttl(
-            &DataKey::Payment(payment_id),
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
+            let refund_amount = payment.amount - payment.refunded_amount;
+            let old_status = payment.status;
+            payment.status = PaymentStatus::Expired;
+            env.storage()
+                .persistent()
+                .set(&DataKey::Payment(payment_id), &payment);
+            env.storage().persistent().extend_ttl(
+                &DataKey::Payment(payment_id),
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
 
-        Self::add_customer_payment(&env, &customer, payment_id);
-        Self::inc_global_created(&env);
-        Self::inc_merchant_created(&env, &merchant);
+            if refund_amount > 0 {
+                let client = token::Client::new(&env, &payment.token);
+                client.transfer(
+                    &env.current_contract_address(),
+                    &payment.customer,
+                    &refund_amount,
+                );
+                refund_total = refund_total
+                    .checked_add(refund_amount)
+                    .unwrap_or_else(|| panic_with_error!(&env, Error::ArithmeticOverflow));
+            }
 
-        events::emit_payment_authorized(
-            &env,
-            payment_id,
-            customer,
-            merchant,
-            amount,
-            capture_deadline_ledger,
-        );
+            Self::inc_global_expired(&env);
+            events::emit_payment_expired(
+                &env,
+                payment_id,
+                payment.customer.clone(),
+                refund_amount,
+                now,
+            );
+            events::emit_payment_status_changed(
+                &env,
+                payment_id,
+                old_status,
+                PaymentStatus::Expired,
+            );
+        }
 
+        events::emit_bulk_expire_completed(&env, batch_size, refund_total);
+        Self::pay_keeper_reward(&env, &keeper, batch_size);
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-
-        payment_id
     }
 
-    /// Merchant captures an authorized payment, completing it and releasing funds.
-    /// Must be called before capture_deadline.
-    pub fn capture_payment(env: Env, merchant: Address, payment_id: u32) {
-        Self::require_not_paused(&env);
-        merchant.require_auth();
-
-        let mut payment: Payment = env
+    /// Internal: load a payment that `bulk_expire_payments` may expire now.
+    fn load_expirable_payment(env: &Env, payment_id: u32, now: u64, now_ledger: u64) -> Payment {
+        let payment: Payment = env
             .storage()
             .persistent()
             .get(&DataKey::Payment(payment_id))
-            .expect("Payment not found");
+            .unwrap_or_else(|| panic_with_error!(env, Error::PaymentNotFound));
 
-        if payment.merchant != merchant {
-            panic!("Only the payment merchant can capture");
-        }
-        if payment.status != PaymentStatus::Authorized {
-            panic!("Payment is not authorized");
-        }
-        if payment.capture_deadline == 0 {
-            panic!("Authorized payment has no capture deadline");
+        match payment.status {
+            PaymentStatus::Authorized => {
+                if payment.capture_deadline == 0 || now_ledger <= payment.capture_deadline {
+                    panic_with_error!(env, PaymentError::PaymentNotExpired);
+                }
+            }
+            PaymentStatus::Pending => {
+                if payment.expires_at == 0 || now < payment.expires_at {
+                    panic_with_error!(env, PaymentError::PaymentNotExpired);
+                }
+            }
+            _ => panic_with_error!(env, PaymentError::PaymentNotExpirable),
         }
-        if (env.ledger().sequence() as u64) > payment.capture_deadline {
-            panic_with_error!(&env, Error::CapturePastDeadline);
+        payment
+    }
+
+    pub fn pause_contract(env: Env, admin: Address, reason: String) {
+        Self::require_admin(&env, &admin);
+
+        if Self::is_paused(env.clone()) {
+            panic_with_error!(&env, ConfigError::ContractAlreadyPaused);
         }
 
-        let captured_amount = payment.amount;
-        Self::finalize_payment(&env, payment_id, &mut payment);
-        events::emit_payment_captured(&env, payment_id, captured_amount);
-    }
+        env.storage().instance().set(&DataKey::Paused, &true);
+        env.storage().instance().set(&DataKey::PauseReason, &reason);
 
-    /// Customer voids an authorized payment before capture, releasing escrow back
-    /// to the customer and expiring the authorization.
-    pub fn void_authorization(env: Env, customer: Address, payment_id: u32) {
-        Self::require_not_paused(&env);
-        customer.require_auth();
+        events::emit_contract_paused(&env, admin, reason, env.ledger().timestamp());
+    }
 
-        let mut payment: Payment = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Payment(payment_id))
-            .expect("Payment not found");
+    pub fn resume_contract(env: Env, admin: Address) {
+        Self::require_admin(&env, &admin);
 
-        if payment.customer != customer {
-            panic!("Only the payment customer can void authorization");
-        }
-        if payment.status != PaymentStatus::Authorized {
-            panic!("Only authorized payments can be voided");
+        if !Self::is_paused(env.clone()) {
+            panic_with_error!(&env, ConfigError::ContractNotPaused);
         }
 
-        let refund_amount = payment.amount - payment.refunded_amount;
-        if refund_amount > 0 {
-            let client = token::Client::new(&env, &payment.token);
-            client.transfer(
-                &env.current_contract_address(),
-                &payment.customer,
-                &refund_amount,
-            );
-        }
+        env.storage().instance().set(&DataKey::Paused, &false);
+        env.storage().instance().remove(&DataKey::PauseReason);
 
-        let old_status = payment.status;
-        payment.status = PaymentStatus::Expired;
-        env.storage()
-            .persistent()
-            .set(&DataKey::Payment(payment_id), &payment);
-        env.storage().persistent().extend_ttl(
-            &DataKey::Payment(payment_id),
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
+        events::emit_contract_resumed(&env, admin, env.ledger().timestamp());
+    }
 
-        Self::inc_global_expired(&env);
-        events::emit_payment_expired(
-            &env,
-            payment_id,
-            payment.customer.clone(),
-            refund_amount,
-            env.ledger().timestamp(),
-        );
-        events::emit_payment_status_changed(&env, payment_id, old_status, PaymentStatus::Expired);
+    pub fn is_paused(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::Paused)
+            .unwrap_or(false)
+    }
 
+    pub fn get_pause_reason(env: Env) -> String {
         env.storage()
             .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+            .get(&DataKey::PauseReason)
+            .unwrap_or(String::from_str(&env, ""))
     }
 
-    /// Execute a token swap via the configured DEX router.
-    /// Returns the output amount or an error symbol.
-    /// Internal: if a DynamicPayment record exists for this payment, validate expiry.
-    /// Full oracle-rate recomputation is deferred to a future implementation.
-    fn settle_dynamic_payment_if_needed(env: &Env, payment: &mut Payment) {
-        let dyn_opt: Option<DynamicPayment> = env
-            .storage()
-            .persistent()
-            .get(&DataKey2::DynamicPayment(payment.id));
-        if let Some(dp) = dyn_opt {
-            let now = env.ledger().timestamp();
-            if dp.expiry > 0 && now > dp.expiry {
-                panic_with_error!(env, Error::DynamicPaymentExpired);
-            }
+    // --- Merchant Withdrawal Queue (#126) ---
+
+    /// Process up to max_count entries from the merchant's withdrawal queue.
+    /// Transfers funds from contract to merchant in FIFO order.
+    /// Merchant must authorize the call.
+    /// Returns the number of payments processed.
+    pub fn process_withdrawal_queue(env: Env, merchant: Address, max_count: u32) -> u32 {
+        Self::require_not_paused(&env);
+        merchant.require_auth();
+
+        let queue = Self::get_withdrawal_queue(&env, &merchant);
+        if queue.is_empty() {
+            return 0;
         }
-    }
 
-    fn execute_token_swap(
-        env: &Env,
-        payment_id: u32,
-        customer: &Address,
-        input_token: &Address,
-        output_token: &Address,
-        input_amount: i128,
-    ) -> Result<i128, Symbol> {
-        let router: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::SwapRouter)
-            .expect("Swap router not configured");
+        let max_count = if max_count == 0 {
+            DEFAULT_MAX_BATCH_SIZE
+        } else {
+            max_count
+        };
+        let process_count = max_count.min(queue.len() as u32) as usize;
 
-        // Get slippage config
-        let slippage_cfg = Self::get_slippage_config_internal(env);
-        let max_slippage_bps = slippage_cfg.max_bps;
+        let mut processed_count = 0u32;
+        let mut remaining_queue = Vec::new(&env);
 
-        // For now, we simulate a swap by checking if the router is set
-        // In a real implementation, this would call the DEX router contract
-        // to execute the swap and return the output amount
+        // Process the first process_count entries
+        for i in 0..process_count {
+            let (payment_id, amount) = queue.get(i as u32).unwrap();
+            processed_count += 1;
 
-        // Placeholder: In production, this would invoke the router contract
-        // For now, we return the input amount as if swap happened 1:1
-        // This is where you'd integrate with actual DEX contracts like Soroban AMM
+            let payment: Payment = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Payment(payment_id))
+                .unwrap_or_else(|| panic_with_error!(&env, MerchantError::NotInWithdrawalQueue));
+            if payment.status != PaymentStatus::Completed {
+                panic_with_error!(&env, PaymentError::PaymentNotCompleted);
+            }
 
-        // Simulate swap success with 1:1 rate (placeholder)
-        // Real implementation would call router.swap() and handle slippage
-        Ok(input_amount)
+            let token_client = token::Client::new(&env, &payment.token);
+            token_client.transfer(&env.current_contract_address(), &merchant, &amount);
+        }
+
+        // Remove processed entries from queue
+        for i in process_count..(queue.len() as usize) {
+            remaining_queue.push_back(queue.get(i as u32).unwrap());
+        }
+
+        Self::set_withdrawal_queue(&env, &merchant, remaining_queue);
+        events::emit_withdrawal_processed(&env, merchant, processed_count);
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        processed_count
     }
 
-    /// Shared settlement logic: checks conditions, deducts fees, distributes funds,
-    /// updates stats, emits events, and stores receipt. `payment` is mutated in-place.
-    fn finalize_payment(env: &Env, payment_id: u32, payment: &mut Payment) {
-        // Oracle price condition check (#125)
-        /*
-        if let Some(ref condition) = payment.release_condition.clone() {
-            let oracle_addr: Address = env
-                .storage()
-                .instance()
-                .get(&DataKey::OracleAddress)
-                .expect("Oracle not configured for conditional payment");
-            let usdc_token: Address = env
-                .storage()
-                .instance()
-                .get(&DataKey::UsdcToken)
-                .expect("Oracle not configured for conditional payment");
-            let max_oracle_age: u64 = env
-                .storage()
-                .instance()
-                .get(&DataKey::MaxOracleAge)
-                .expect("Oracle not configured for conditional payment");
+    /// Move a specific payment to the front of the merchant's withdrawal queue.
+    /// Merchant must authorize the call.
+    pub fn prioritize_withdrawal(env: Env, merchant: Address, payment_id: u32) {
+        Self::require_not_paused(&env);
+        merchant.require_auth();
 
-            let oracle_client = oracle::OracleClient::new(env, &oracle_addr);
-            let price_data: PriceData = oracle_client
-                .lastprice(&condition.asset, &usdc_token)
-                .expect("Oracle price unavailable");
+        let queue = Self::get_withdrawal_queue(&env, &merchant);
+        if queue.is_empty() {
+            panic_with_error!(&env, MerchantError::WithdrawalQueueEmpty);
+        }
 
-            let current_ts = env.ledger().timestamp();
-            let age = current_ts.saturating_sub(price_data.timestamp);
-            if age > max_oracle_age {
-                panic!("Oracle price is stale");
+        // Find the payment in the queue
+        let mut found_index = None;
+        let mut found_amount = 0i128;
+        for i in 0..queue.len() {
+            let (pid, amount) = queue.get(i).unwrap();
+            if pid == payment_id {
+                found_index = Some(i);
+                found_amount = amount;
+                break;
             }
+        }
 
-            let met = match condition.direction {
-                OracleDirection::Gte => price_data.price >= condition.threshold,
-                OracleDirection::Lte => price_data.price <= condition.threshold,
-            };
+        if found_index.is_none() {
+            panic_with_error!(&env, MerchantError::NotInWithdrawalQueue);
+        }
 
-            events::emit_conditional_payment_attempt(
-                env,
-                payment_id,
-                price_data.price,
-                condition.threshold,
-                met,
-            );
+        let index = found_index.unwrap();
 
-            if !met {
-                panic_with_error!(env, Error::OracleConditionNotMet);
+        // If already at front, do nothing
+        if index == 0 {
+            return;
+        }
+
+        // Remove from current position and insert at front
+        let mut new_queue = Vec::new(&env);
+        new_queue.push_back((payment_id, found_amount));
+
+        for i in 0..queue.len() {
+            if i != index {
+                new_queue.push_back(queue.get(i).unwrap());
             }
         }
-        */
 
-        // --- Volume cap check (#131) ---
-        Self::check_and_update_merchant_volume_cap(
-            env,
-            payment_id,
-            &payment.merchant,
-            payment.amount,
-        );
+        Self::set_withdrawal_queue(&env, &merchant, new_queue);
 
-        // --- Token Swap: Convert payment token to merchant's preferred token ---
-        let mut final_token = payment.token.clone();
-        let mut final_amount = payment.amount;
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
 
-        // Check if swap is needed and possible
-        let preferred_token: Option<Address> = env
+    /// Get the merchant's current withdrawal queue.
+    pub fn get_merchant_withdrawal_queue(env: Env, merchant: Address) -> Vec<(u32, i128)> {
+        Self::get_withdrawal_queue(&env, &merchant)
+    }
+
+    // --- Internal Helpers ---
+
+    fn require_not_paused(env: &Env) {
+        if env
             .storage()
             .instance()
-            .get(&DataKey::PreferredToken(payment.merchant.clone()));
-
-        if let Some(preferred) = preferred_token {
-            if payment.token != preferred {
-                // Swap is needed
-                let router: Option<Address> = env.storage().instance().get(&DataKey::SwapRouter);
+            .get(&DataKey::Paused)
+            .unwrap_or(false)
+        {
+            panic_with_error!(env, Error::ContractPaused);
+        }
+    }
 
-                if let Some(router_addr) = router {
-                    // Attempt swap
-                    let swap_result = Self::execute_token_swap(
-                        env,
-                        payment_id,
-                        &payment.customer,
-                        &payment.token,
-                        &preferred,
-                        payment.amount,
-                    );
+    // -------------------------------------------------------------------------
+    // #231: Merchant Withdrawal Rate Limiting
+    // -------------------------------------------------------------------------
 
-                    match swap_result {
-                        Ok(swapped_amount) => {
-                            final_token = preferred.clone();
-                            final_amount = swapped_amount;
-                            events::emit_payment_swapped_and_settled(
-                                env,
-                                payment_id,
-                                payment.customer.clone(),
-                                payment.merchant.clone(),
+    /// Admin sets global default withdrawal window and cap.
+    pub fn set_withdrawal_window(env: Env, admin: Address, window_seconds: u64, cap: i128) {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
+        if admin != stored_admin {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
+        if window_seconds == 0 {
+            panic_with_error!(&env, ConfigError::InvalidWindow);
+        }
+        if cap <= 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey2::WithdrawalWindowSeconds, &window_seconds);
+        env.storage()
+            .instance()
+            .set(&DataKey2::WithdrawalWindowCap, &cap);
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Merchant sets a personal (stricter) withdrawal limit.
+    pub fn set_withdrawal_limit(env: Env, merchant: Address, window_seconds: u64, cap: i128) {
+        Self::require_not_paused(&env);
+        merchant.require_auth();
+        if window_seconds == 0 {
+            panic_with_error!(&env, ConfigError::InvalidWindow);
+        }
+        if cap <= 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+        let limit = WithdrawalLimit {
+            window_seconds,
+            cap,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey2::MerchantWithdrawalLimit(merchant.clone()), &limit);
+        env.storage().persistent().extend_ttl(
+            &DataKey2::MerchantWithdrawalLimit(merchant.clone()),
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        events::emit_withdrawal_rate_limit_set(&env, merchant, window_seconds, cap);
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Admin overrides a merchant's withdrawal cap (emergency override).
+    pub fn override_withdrawal_limit(env: Env, admin: Address, merchant: Address, cap: i128) {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
+        if admin != stored_admin {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
+        if cap <= 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+        // Preserve existing window or use global default
+        let window_seconds: u64 = env
+            .storage()
+            .persistent()
+            .get::<DataKey2, WithdrawalLimit>(&DataKey2::MerchantWithdrawalLimit(merchant.clone()))
+            .map(|l| l.window_seconds)
+            .unwrap_or_else(|| {
+                env.storage()
+                    .instance()
+                    .get(&DataKey2::WithdrawalWindowSeconds)
+                    .unwrap_or(24 * 60 * 60)
+            });
+        let limit = WithdrawalLimit {
+            window_seconds,
+            cap,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey2::MerchantWithdrawalLimit(merchant.clone()), &limit);
+        env.storage().persistent().extend_ttl(
+            &DataKey2::MerchantWithdrawalLimit(merchant.clone()),
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        events::emit_withdrawal_rate_limit_set(&env, merchant, window_seconds, cap);
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    pub fn set_default_withdrawal_limits(env: Env, admin: Address, window_seconds: u64, cap: i128) {
+        Self::require_not_paused(&env);
+        Self::require_admin(&env, &admin);
+
+        if window_seconds == 0 {
+            panic_with_error!(&env, ConfigError::InvalidWindow);
+        }
+        if cap <= 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey2::WithdrawalWindowSeconds, &window_seconds);
+        env.storage()
+            .instance()
+            .set(&DataKey2::WithdrawalWindowCap, &cap);
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    pub fn get_withdrawal_rate_limit(env: Env, merchant: Address) -> (u64, i128) {
+        if let Some(limit) = env.storage().persistent().get::<DataKey2, WithdrawalLimit>(
+            &DataKey2::MerchantWithdrawalLimit(merchant.clone()),
+        ) {
+            (limit.window_seconds, limit.cap)
+        } else {
+            let window_seconds = env.storage().instance().get::<DataKey2, u64>(&DataKey2::WithdrawalWindowSeconds).unwrap_or(86400);
+            let cap = env.storage().instance().get::<DataKey2, i128>(&DataKey2::WithdrawalWindowCap).unwrap_or(i128::MAX);
+            (window_seconds, cap)
+        }
+    }
+
+    /// Internal: check and update the rolling withdrawal window for a merchant.
+    /// Panics with WithdrawalRateLimitExceeded if the cap would be exceeded.
+    fn check_and_update_withdrawal_rate_limit(env: &Env, merchant: &Address, amount: i128) {
+        // Determine effective limit: merchant-specific > global default > no limit
+        let (window_seconds, cap) =
+            if let Some(limit) = env.storage().persistent().get::<DataKey2, WithdrawalLimit>(
+                &DataKey2::MerchantWithdrawalLimit(merchant.clone()),
+            ) {
+                (limit.window_seconds, limit.cap)
+            } else if let (Some(w), Some(c)) = (
+                env.storage()
+                    .instance()
+                    .get::<DataKey2, u64>(&DataKey2::WithdrawalWindowSeconds),
+                env.storage()
+                    .instance()
+                    .get::<DataKey2, i128>(&DataKey2::WithdrawalWindowCap),
+            ) {
+                (w, c)
+            } else {
+                // No rate limit configured — allow
+                return;
+            };
+
+        let now = env.ledger().timestamp();
+        let state_key = DataKey2::MerchantWithdrawalWindow(merchant.clone());
+
+        let mut state: WithdrawalWindowState = env
+            .storage()
+            .persistent()
+            .get(&state_key)
+            .unwrap_or(WithdrawalWindowState {
+                window_start: now,
+                withdrawn: 0,
+            });
+
+        // Reset window if it has elapsed
+        if now >= state.window_start + window_seconds {
+            state.window_start = now;
+            state.withdrawn = 0;
+        }
+
+        let new_total = state.withdrawn
+            .checked_add(amount)
+            .unwrap_or_else(|| panic_with_error!(env, Error::ArithmeticOverflow));
+        if new_total > cap {
+            events::emit_withdrawal_rate_limit_exceeded(env, merchant.clone(), new_total, cap);
+            panic_with_error!(env, Error::WithdrawalRateLimitExceeded);
+        }
+
+        state.withdrawn = new_total;
+        env.storage().persistent().set(&state_key, &state);
+        env.storage().persistent().extend_ttl(
+            &state_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn require_admin(env: &Env, admin: &Address) {
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(env, Error::NotInitialized));
+        if stored_admin != *admin {
+            panic_with_error!(env, Error::Unauthorized);
+        }
+    }
+
+    /// Validates that a token is allowed via the whitelist contract
+    fn require_token_allowed(env: &Env, token: &Address) {
+        if let Some(whitelist_contract) = env
+            .storage()
+            .instance()
+            .get::<DataKey, Address>(&DataKey::TokenWhitelistContract)
+        {
+            let client = TokenWhitelistClient::new(env, &whitelist_contract);
+            if !client.is_token_allowed_for_contract(&env.current_contract_address(), token) {
+                panic_with_error!(env, Error::TokenNotAllowed);
+            }
+        }
+    }
+
+    fn complete_payment_internal(env: &Env, payment_id: u32, scheduled_only: bool) {
+        let mut payment: Payment = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Payment(payment_id))
+            .unwrap_or_else(|| panic_with_error!(env, Error::PaymentNotFound));
+
+        if scheduled_only {
+            if payment.status != PaymentStatus::ScheduledPending {
+                panic_with_error!(env, PaymentError::PaymentNotScheduled);
+            }
+            if env.ledger().timestamp() < payment.execute_after {
+                panic_with_error!(env, PaymentError::ScheduleNotReached);
+            }
+        } else if payment.status != PaymentStatus::Pending {
+            panic_with_error!(env, PaymentError::PaymentNotPending);
+        }
+
+        if payment.expires_at > 0 && env.ledger().timestamp() >= payment.expires_at {
+            panic_with_error!(env, PaymentError::PaymentExpired);
+        }
+
+        // #246: Recompute token amount at current oracle rate for dynamic payments
+        Self::settle_dynamic_payment_if_needed(env, &mut payment);
+        // #235: Check customer spend limit before finalizing
+        Self::check_and_update_customer_spend_limit(
+            env,
+            &payment.merchant,
+            &payment.customer,
+            payment.amount,
+        );
+
+        Self::finalize_payment(env, payment_id, &mut payment);
+    }
+
+    /// Merchant authorizes a payment by placing customer funds into escrow immediately.
+    /// The payment starts in Authorized status and must be captured on or before
+    /// `capture_deadline_ledger`.
+    pub fn authorize_payment(
+        env: Env,
+        merchant: Address,
+        customer: Address,
+        token: Address,
+        amount: i128,
+        capture_deadline_ledger: u64,
+    ) -> u32 {
+        Self::require_not_paused(&env);
+        merchant.require_auth();
+
+        if amount <= 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+        if capture_deadline_ledger <= (env.ledger().sequence() as u64) {
+            panic_with_error!(&env, PaymentError::DeadlineInPast);
+        }
+
+        Self::require_token_allowed(&env, &token);
+        Self::require_merchant_approved(&env, &merchant);
+
+        let client = token::Client::new(&env, &token);
+        client.transfer_from(
+            &env.current_contract_address(),
+            &customer,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        let payment_id = Self::next_payment_id(&env);
+        let payment = Payment {
+            id: payment_id,
+            customer: customer.clone(),
+            merchant: merchant.clone(),
+            amount,
+            token: token.clone(),
+            status: PaymentStatus::Authorized,
+            created_at: env.ledger().timestamp(),
+            expires_at: 0,
+            refunded_amount: 0,
+            reference: None,
+            metadata: None,
+            split_recipients: None,
+            execute_after: 0,
+            category: None,
+            tags: None,
+            capture_deadline: capture_deadline_ledger,
+            external_id: None,
+            tipping_enabled: false,
+            extension_count: 0,
+        };
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Payment(payment_id), &payment);
+        env.storage().persistent().extend_ttl(
+            &DataKey::Payment(payment_id),
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        Self::add_customer_payment(&env, &customer, payment_id);
+        Self::inc_global_created(&env);
+        Self::inc_merchant_created(&env, &merchant);
+
+        events::emit_payment_authorized(
+            &env,
+            payment_id,
+            customer,
+            merchant,
+            amount,
+            capture_deadline_ledger,
+        );
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        payment_id
+    }
+
+    /// Merchant captures an authorized payment, completing it and releasing funds.
+    /// Must be called before capture_deadline.
+    pub fn capture_payment(env: Env, merchant: Address, payment_id: u32) {
+        Self::require_not_paused(&env);
+        merchant.require_auth();
+
+        let mut payment: Payment = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Payment(payment_id))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::PaymentNotFound));
+
+        if payment.merchant != merchant {
+            panic_with_error!(&env, PaymentError::NotPaymentMerchant);
+        }
+        if payment.status != PaymentStatus::Authorized {
+            panic_with_error!(&env, PaymentError::PaymentNotAuthorized);
+        }
+        if payment.capture_deadline == 0 {
+            panic_with_error!(&env, PaymentError::NoCaptureDeadline);
+        }
+        if (env.ledger().sequence() as u64) > payment.capture_deadline {
+            panic_with_error!(&env, Error::CapturePastDeadline);
+        }
+
+        let captured_amount = payment.amount;
+        Self::finalize_payment(&env, payment_id, &mut payment);
+        events::emit_payment_captured(&env, payment_id, captured_amount);
+    }
+
+    /// Customer voids an authorized payment before capture, releasing escrow back
+    /// to the customer and expiring the authorization.
+    pub fn void_authorization(env: Env, customer: Address, payment_id: u32) {
+        Self::require_not_paused(&env);
+        customer.require_auth();
+
+        let mut payment: Payment = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Payment(payment_id))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::PaymentNotFound));
+
+        if payment.customer != customer {
+            panic_with_error!(&env, PaymentError::NotPaymentCustomer);
+        }
+        if payment.status != PaymentStatus::Authorized {
+            panic_with_error!(&env, PaymentError::PaymentNotAuthorized);
+        }
+
+        let refund_amount = payment.amount - payment.refunded_amount;
+        if refund_amount > 0 {
+            let client = token::Client::new(&env, &payment.token);
+            client.transfer(
+                &env.current_contract_address(),
+                &payment.customer,
+                &refund_amount,
+            );
+        }
+
+        let old_status = payment.status;
+        payment.status = PaymentStatus::Expired;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Payment(payment_id), &payment);
+        env.storage().persistent().extend_ttl(
+            &DataKey::Payment(payment_id),
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        Self::inc_global_expired(&env);
+        events::emit_payment_expired(
+            &env,
+            payment_id,
+            payment.customer.clone(),
+            refund_amount,
+            env.ledger().timestamp(),
+        );
+        events::emit_payment_status_changed(&env, payment_id, old_status, PaymentStatus::Expired);
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Execute a token swap via the configured DEX router.
+    /// Returns the output amount or an error symbol.
+    /// Internal: if a DynamicPayment record exists for this payment, validate expiry.
+    /// Full oracle-rate recomputation is deferred to a future implementation.
+    fn settle_dynamic_payment_if_needed(env: &Env, payment: &mut Payment) {
+        let dyn_opt: Option<DynamicPayment> = env
+            .storage()
+            .persistent()
+            .get(&DataKey2::DynamicPayment(payment.id));
+        if let Some(dp) = dyn_opt {
+            let now = env.ledger().timestamp();
+            if dp.expiry > 0 && now > dp.expiry {
+                panic_with_error!(env, Error::DynamicPaymentExpired);
+            }
+        }
+    }
+
+    fn execute_token_swap(
+        env: &Env,
+        payment_id: u32,
+        customer: &Address,
+        input_token: &Address,
+        output_token: &Address,
+        input_amount: i128,
+    ) -> Result<i128, Symbol> {
+        let router: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::SwapRouter)
+            .unwrap_or_else(|| panic_with_error!(env, PaymentError::SwapRouterNotConfigured));
+
+        // Get slippage config
+        let slippage_cfg = Self::get_slippage_config_internal(env);
+        let max_slippage_bps = slippage_cfg.max_bps;
+
+        // For now, we simulate a swap by checking if the router is set
+        // In a real implementation, this would call the DEX router contract
+        // to execute the swap and return the output amount
+
+        // Placeholder: In production, this would invoke the router contract
+        // For now, we return the input amount as if swap happened 1:1
+        // This is where you'd integrate with actual DEX contracts like Soroban AMM
+
+        // Simulate swap success with 1:1 rate (placeholder)
+        // Real implementation would call router.swap() and handle slippage
+        Ok(input_amount)
+    }
+
+    /// Shared settlement logic: checks conditions, deducts fees, distributes funds,
+    /// updates stats, emits events, and stores receipt. `payment` is mutated in-place.
+    fn finalize_payment(env: &Env, payment_id: u32, payment: &mut Payment) {
+        // Oracle price condition check (#125)
+        /*
+        if let Some(ref condition) = payment.release_condition.clone() {
+            let oracle_addr: Address = env
+                .storage()
+                .instance()
+                .get(&DataKey::OracleAddress)
+                .unwrap_or_else(|| panic_with_error!(env, PaymentError::OracleNotConfigured));
+            let usdc_token: Address = env
+                .storage()
+                .instance()
+                .get(&DataKey::UsdcToken)
+                .unwrap_or_else(|| panic_with_error!(env, PaymentError::OracleNotConfigured));
+            let max_oracle_age: u64 = env
+                .storage()
+                .instance()
+                .get(&DataKey::MaxOracleAge)
+                .unwrap_or_else(|| panic_with_error!(env, PaymentError::OracleNotConfigured));
+
+            let oracle_client = oracle::OracleClient::new(env, &oracle_addr);
+            let price_data: PriceData = oracle_client
+                .lastprice(&condition.asset, &usdc_token)
+                .unwrap_or_else(|| panic_with_error!(env, PaymentError::OraclePriceUnavailable));
+
+            let current_ts = env.ledger().timestamp();
+            let age = current_ts.saturating_sub(price_data.timestamp);
+            if age > max_oracle_age {
+                panic_with_error!(env, PaymentError::OraclePriceStale);
+            }
+
+            let met = match condition.direction {
+                OracleDirection::Gte => price_data.price >= condition.threshold,
+                OracleDirection::Lte => price_data.price <= condition.threshold,
+            };
+
+            events::emit_conditional_payment_attempt(
+                env,
+                payment_id,
+                price_data.price,
+                condition.threshold,
+                met,
+            );
+
+            if !met {
+                panic_with_error!(env, Error::OracleConditionNotMet);
+            }
+        }
+        */
+
+        // --- Volume cap check (#131) ---
+        Self::check_and_update_merchant_volume_cap(
+            env,
+            payment_id,
+            &payment.merchant,
+            payment.amount,
+        );
+
+        // --- Token Swap: Convert payment token to merchant's preferred token ---
+        let mut final_token = payment.token.clone();
+        let mut final_amount = payment.amount;
+
+        // Check if swap is needed and possible
+        let preferred_token: Option<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::PreferredToken(payment.merchant.clone()));
+
+        if let Some(preferred) = preferred_token {
+            if payment.token != preferred {
+                // Swap is needed
+                let router: Option<Address> = env.storage().instance().get(&DataKey::SwapRouter);
+
+                if let Some(router_addr) = router {
+                    // Attempt swap
+                    let swap_result = Self::execute_token_swap(
+                        env,
+                        payment_id,
+                        &payment.customer,
+                        &payment.token,
+                        &preferred,
+                        payment.amount,
+                    );
+
+                    match swap_result {
+                        Ok(swapped_amount) => {
+                            final_token = preferred.clone();
+                            final_amount = swapped_amount;
+                            events::emit_payment_swapped_and_settled(
+                                env,
+                                payment_id,
+                                payment.customer.clone(),
+                                payment.merchant.clone(),
                                 payment.token.clone(),
                                 preferred,
                                 payment.amount,
@@ -6194,3353 +7424,5312 @@ impl AhjoorPaymentsContract {
                                 &payment.amount,
                             );
 
-                            let old_status = payment.status;
-                            payment.status = PaymentStatus::Refunded;
-                            env.storage()
-                                .persistent()
-                                .set(&DataKey::Payment(payment_id), payment);
-                            env.storage().persistent().extend_ttl(
-                                &DataKey::Payment(payment_id),
-                                PERSISTENT_LIFETIME_THRESHOLD,
-                                PERSISTENT_BUMP_AMOUNT,
-                            );
+                            let old_status = payment.status;
+                            payment.status = PaymentStatus::Refunded;
+                            env.storage()
+                                .persistent()
+                                .set(&DataKey::Payment(payment_id), payment);
+                            env.storage().persistent().extend_ttl(
+                                &DataKey::Payment(payment_id),
+                                PERSISTENT_LIFETIME_THRESHOLD,
+                                PERSISTENT_BUMP_AMOUNT,
+                            );
+
+                            Self::inc_global_refunded(env, &payment.token, payment.amount);
+                            Self::inc_merchant_refunded(
+                                env,
+                                &payment.merchant,
+                                &payment.token,
+                                payment.amount,
+                            );
+                            Self::inc_merchant_failed(env, &payment.merchant); // #226
+
+                            events::emit_payment_swap_failed(
+                                env,
+                                payment_id,
+                                payment.customer.clone(),
+                                payment.token.clone(),
+                                payment.amount,
+                                reason,
+                            );
+                            events::emit_payment_status_changed(
+                                env,
+                                payment_id,
+                                old_status,
+                                PaymentStatus::Refunded,
+                            );
+
+                            env.storage()
+                                .instance()
+                                .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+                            return; // Exit early - payment refunded
+                        }
+                    }
+                } else {
+                    // No router configured - cannot swap, use original token
+                    final_token = payment.token.clone();
+                    final_amount = payment.amount;
+                }
+            }
+        }
+
+        let rolling_before = Self::rolling_merchant_volume(env, &payment.merchant);
+        let projected_volume = rolling_before + final_amount;
+        let applied_fee_bps = Self::fee_bps_for_volume(env, projected_volume);
+        let fee_amount = (final_amount * applied_fee_bps as i128) / 10_000;
+        let net_amount = final_amount - fee_amount;
+
+        let fee_recipient: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::FeeRecipient)
+            .unwrap_or_else(|| panic_with_error!(env, PaymentError::FeeRecipientNotConfigured));
+
+        let token_client = token::Client::new(env, &final_token);
+
+        if fee_amount > 0 {
+            token_client.transfer(&env.current_contract_address(), &fee_recipient, &fee_amount);
+            events::emit_fee_collected(
+                env,
+                payment_id,
+                fee_amount,
+                fee_recipient,
+                final_token.clone(),
+            );
+            // #242: Accrue referral commission on the fee collected for referred merchants
+            Self::accrue_referral_commission(env, &payment.merchant, payment_id, fee_amount);
+        }
+
+        let split_transfers = Self::distribute_net_payment(env, payment, net_amount);
+        if split_transfers.len() > 0 {
+            events::emit_payment_split_completed(env, payment_id, split_transfers);
+        }
+
+        let old_status = payment.status;
+
+        // Check if cooling-off is enabled for this payment (#309)
+        let cooling_off_config: Option<CoolingOffConfig> = env
+            .storage()
+            .persistent()
+            .get(&DataKey2::CoolingOffConfig(payment_id));
+
+        if let Some(mut config) = cooling_off_config {
+            if config.cooling_off_ledgers > 0 {
+                // Set status to CoolingOff instead of Completed
+                payment.status = PaymentStatus::CoolingOff;
+                config.cooling_off_expiry_ledger =
+                    env.ledger().sequence() + config.cooling_off_ledgers;
+                env.storage()
+                    .persistent()
+                    .set(&DataKey2::CoolingOffConfig(payment_id), &config);
+                env.storage().persistent().extend_ttl(
+                    &DataKey2::CoolingOffConfig(payment_id),
+                    PERSISTENT_LIFETIME_THRESHOLD,
+                    PERSISTENT_BUMP_AMOUNT,
+                );
+                events::emit_payment_in_cooling_off(
+                    env,
+                    payment_id,
+                    payment.customer.clone(),
+                    config.cooling_off_expiry_ledger,
+                );
+            } else {
+                payment.status = PaymentStatus::Completed;
+            }
+        } else {
+            payment.status = PaymentStatus::Completed;
+        }
+        let original_amount = payment.amount;
+        payment.amount = net_amount;
+        let completed_at = env.ledger().timestamp();
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Payment(payment_id), payment);
+        env.storage().persistent().extend_ttl(
+            &DataKey::Payment(payment_id),
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        env.storage()
+            .persistent()
+            .set(&DataKey::Settled(payment_id), &true);
+        env.storage().persistent().extend_ttl(
+            &DataKey::Settled(payment_id),
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        let receipt_hash = Self::compute_receipt_hash(
+            env,
+            payment_id,
+            &payment.customer,
+            &payment.merchant,
+            net_amount,
+            &payment.token,
+            completed_at,
+        );
+        env.storage()
+            .persistent()
+            .set(&DataKey::PaymentReceipt(payment_id), &receipt_hash);
+        env.storage().persistent().extend_ttl(
+            &DataKey::PaymentReceipt(payment_id),
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        Self::inc_global_completed(env, &payment.token, net_amount);
+        Self::inc_merchant_completed(env, &payment.merchant, &payment.token, net_amount);
+        Self::inc_volume_bucket(env, &payment.token, net_amount);
+        Self::inc_merchant_volume_bucket(env, &payment.merchant, original_amount);
+
+        // Auto-enqueue completed payment into merchant's withdrawal queue (#126)
+        Self::enqueue_withdrawal(env, &payment.merchant, payment_id, net_amount);
+
+        let rolling_after = Self::rolling_merchant_volume(env, &payment.merchant);
+        let new_tier_bps = Self::fee_bps_for_volume(env, rolling_after);
+        let tier_key = DataKey::MerchantCurrentTierBps(payment.merchant.clone());
+        let old_tier_bps: u32 = env
+            .storage()
+            .persistent()
+            .get(&tier_key)
+            .unwrap_or(Self::get_fee_bps(env.clone()));
+        if old_tier_bps != new_tier_bps {
+            env.storage().persistent().set(&tier_key, &new_tier_bps);
+            env.storage().persistent().extend_ttl(
+                &tier_key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+            events::emit_merchant_tier_updated(
+                env,
+                payment.merchant.clone(),
+                new_tier_bps,
+                rolling_after,
+            );
+        }
+
+        events::emit_payment_completed(
+            env,
+            payment_id,
+            payment.merchant.clone(),
+            net_amount,
+            completed_at,
+        );
+        events::emit_payment_status_changed(env, payment_id, old_status, PaymentStatus::Completed);
+        events::emit_payment_receipt_issued(env, payment_id, receipt_hash);
+
+        // #239: Accrue loyalty points to customer
+        Self::accrue_loyalty_points(env, payment_id, &payment.customer, payment.amount);
+        Self::accrue_program_points(
+            env,
+            payment_id,
+            &payment.merchant,
+            &payment.customer,
+            payment.amount,
+        );
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    fn distribute_net_payment(
+        env: &Env,
+        payment: &Payment,
+        net_amount: i128,
+    ) -> Vec<SplitTransfer> {
+        let token_client = token::Client::new(env, &payment.token);
+
+        if let Some(splits) = &payment.split_recipients {
+            if splits.len() == 0 {
+                token_client.transfer(
+                    &env.current_contract_address(),
+                    &payment.merchant,
+                    &net_amount,
+                );
+                return Vec::new(env);
+            }
+
+            let mut split_events = Vec::new(env);
+            let mut distributed: i128 = 0;
+            for split in splits.iter() {
+                let share = (net_amount * split.bps as i128) / 10_000;
+                if share > 0 {
+                    token_client.transfer(
+                        &env.current_contract_address(),
+                        &split.recipient,
+                        &share,
+                    );
+                }
+                distributed += share;
+                split_events.push_back(SplitTransfer {
+                    recipient: split.recipient,
+                    bps: split.bps,
+                    amount: share,
+                });
+            }
+
+            let dust_to_merchant = net_amount - distributed;
+            if dust_to_merchant > 0 {
+                token_client.transfer(
+                    &env.current_contract_address(),
+                    &payment.merchant,
+                    &dust_to_merchant,
+                );
+            }
+            return split_events;
+        }
+
+        token_client.transfer(
+            &env.current_contract_address(),
+            &payment.merchant,
+            &net_amount,
+        );
+        Vec::new(env)
+    }
+
+    fn validate_split_recipients(env: &Env, split_recipients: &Option<Vec<SplitRecipient>>) {
+        if let Some(splits) = split_recipients {
+            if splits.len() == 0 {
+                panic_with_error!(env, PaymentError::InvalidSplitRecipients);
+            }
+
+            let mut total_bps: u32 = 0;
+            for split in splits.iter() {
+                if split.bps == 0 {
+                    panic_with_error!(env, PaymentError::InvalidSplitRecipients);
+                }
+                total_bps = total_bps
+                    .checked_add(split.bps)
+                    .unwrap_or_else(|| panic_with_error!(env, Error::ArithmeticOverflow));
+            }
+            if total_bps != 10_000 {
+                panic_with_error!(env, PaymentError::InvalidSplitRecipients);
+            }
+        }
+    }
+
+    fn validate_fee_tiers(env: &Env, tiers: &Vec<FeeTier>) {
+        let mut last_min_volume: i128 = -1;
+        for tier in tiers.iter() {
+            if tier.fee_bps > MAX_FEE_BPS {
+                panic_with_error!(env, ConfigError::FeeTooHigh);
+            }
+            if tier.min_volume < 0 {
+                panic_with_error!(env, ConfigError::InvalidFeeTiers);
+            }
+            if tier.min_volume <= last_min_volume {
+                panic_with_error!(env, ConfigError::InvalidFeeTiers);
+            }
+            last_min_volume = tier.min_volume;
+        }
+    }
+
+    /// Validate invoice data and verify total matches payment amount (#128).
+    fn validate_invoice_data(env: &Env, invoice: &Option<InvoiceData>, payment_amount: i128) {
+        if let Some(inv) = invoice {
+            if inv.line_items.len() == 0 {
+                panic_with_error!(env, PaymentError::InvalidInvoice);
+            }
+            if (inv.line_items.len() as u32) > MAX_INVOICE_LINE_ITEMS {
+                panic_with_error!(env, PaymentError::InvalidInvoice);
+            }
+
+            let mut invoice_subtotal: i128 = 0;
+            for item in inv.line_items.iter() {
+                if item.quantity == 0 {
+                    panic_with_error!(env, PaymentError::InvalidInvoice);
+                }
+                if item.unit_price < 0 {
+                    panic_with_error!(env, PaymentError::InvalidInvoice);
+                }
+                let line_amount = (item.quantity as i128)
+                    .checked_mul(item.unit_price)
+                    .unwrap_or_else(|| panic_with_error!(env, Error::ArithmeticOverflow));
+                invoice_subtotal = invoice_subtotal
+                    .checked_add(line_amount)
+                    .unwrap_or_else(|| panic_with_error!(env, Error::ArithmeticOverflow));
+            }
+
+            let tax_amount = (invoice_subtotal * inv.tax_bps as i128) / 10_000;
+            let invoice_total = invoice_subtotal
+                .checked_add(tax_amount)
+                .unwrap_or_else(|| panic_with_error!(env, Error::ArithmeticOverflow));
+
+            if invoice_total != payment_amount {
+                panic_with_error!(env, PaymentError::InvalidInvoice);
+            }
+
+            let _ = env; // suppress unused warning
+        }
+    }
+
+    /// Compute SHA256 hash of serialized invoice data (#128).
+    fn compute_invoice_hash(env: &Env, invoice: &InvoiceData) -> BytesN<32> {
+        let mut preimage = Bytes::new(env);
+
+        // Serialize line items count
+        preimage.extend_from_array(&(invoice.line_items.len() as u32).to_be_bytes());
+
+        // Serialize each line item
+        for item in invoice.line_items.iter() {
+            // For Symbol, we'll use its XDR representation
+            preimage.append(&item.description.to_xdr(env));
+            preimage.extend_from_array(&item.quantity.to_be_bytes());
+            preimage.extend_from_array(&item.unit_price.to_be_bytes());
+        }
+
+        preimage.extend_from_array(&invoice.tax_bps.to_be_bytes());
+        preimage.append(&invoice.currency_label.clone().to_xdr(env));
+
+        env.crypto().sha256(&preimage).into()
+    }
+
+    fn fee_bps_for_volume(env: &Env, volume: i128) -> u32 {
+        let default_fee: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::FeeBps)
+            .unwrap_or(DEFAULT_FEE_BPS);
+        let tiers: Vec<FeeTier> = env
+            .storage()
+            .instance()
+            .get(&DataKey::FeeTiers)
+            .unwrap_or(Vec::new(env));
+
+        let mut selected = default_fee;
+        for tier in tiers.iter() {
+            if volume >= tier.min_volume {
+                selected = tier.fee_bps;
+            }
+        }
+        selected
+    }
+
+    fn rolling_merchant_volume(env: &Env, merchant: &Address) -> i128 {
+        let current_bucket = env.ledger().sequence() / LEDGER_BUCKET_SIZE;
+        let mut total = 0i128;
+        for i in 0..4u32 {
+            let bucket = current_bucket.saturating_sub(i);
+            let key = DataKey::MerchantVolumeBucket(merchant.clone(), bucket);
+            let bucket_total: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+            total += bucket_total;
+        }
+        total
+    }
+
+    fn enforce_rate_limit(env: &Env, customer: &Address, requested_payments: u32) {
+        if requested_payments == 0 {
+            return;
+        }
+
+        let cfg = Self::get_rate_limit_config_internal(env);
+        let current_ledger = env.ledger().sequence();
+        let key = DataKey::CustomerRateLimit(customer.clone());
+
+        let mut state: CustomerRateLimit =
+            env.storage()
+                .persistent()
+                .get(&key)
+                .unwrap_or(CustomerRateLimit {
+                    count: 0,
+                    window_start_ledger: current_ledger,
+                });
+
+        if current_ledger.saturating_sub(state.window_start_ledger) >= cfg.window_size_ledgers {
+            state.count = 0;
+            state.window_start_ledger = current_ledger;
+        }
+
+        let new_count = state.count.saturating_add(requested_payments);
+        if new_count > cfg.max_payments {
+            panic_with_error!(env, Error::RateLimitExceeded);
+        }
+
+        state.count = new_count;
+        env.storage().persistent().set(&key, &state);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    /// Validates merchant is approved or open mode is enabled.
+    fn require_merchant_approved(env: &Env, merchant: &Address) {
+        // Always enforce ban/suspension regardless of open mode
+        let status: MerchantStatus = env
+            .storage()
+            .persistent()
+            .get(&DataKey2::MerchantStatus(merchant.clone()))
+            .unwrap_or(MerchantStatus::Active);
+
+        match status {
+            MerchantStatus::Banned => panic_with_error!(env, MerchantError::MerchantBanned),
+            MerchantStatus::Suspended => {
+                let expiry: u64 = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey2::MerchantSuspensionExpiry(merchant.clone()))
+                    .unwrap_or(0);
+                if expiry == 0 || env.ledger().timestamp() <= expiry {
+                    panic_with_error!(env, MerchantError::MerchantSuspended);
+                }
+                // Suspension expired — fall through to allowlist check
+            }
+            MerchantStatus::Active => {}
+        }
+
+        let open_mode: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::MerchantOpenMode)
+            .unwrap_or(true); // Default: open mode (no allowlist enforcement)
+        if open_mode {
+            return;
+        }
+
+        let approved: bool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MerchantApproved(merchant.clone()))
+            .unwrap_or(false);
+        if !approved {
+            panic_with_error!(env, Error::MerchantNotApproved);
+        }
+    }
+
+    fn require_merchant_reserve(env: &Env, merchant: &Address) {
+        if let Some(addr) = env
+            .storage()
+            .instance()
+            .get::<DataKey2, Address>(&DataKey2::RefundContract)
+        {
+            if !refund_reserve::RefundReserveClient::new(env, &addr)
+                .check_merchant_reserve(merchant)
+            {
+                panic_with_error!(env, Error::InsufficientMerchantReserve);
+            }
+        }
+    }
+
+    /// Validate optional reference string: max 64 bytes (#67).
+    fn validate_reference(env: &Env, reference: &Option<String>) {
+        if let Some(r) = reference {
+            if r.len() > MAX_REFERENCE_LEN {
+                panic_with_error!(env, PaymentError::ReferenceTooLong);
+            }
+            let _ = env; // suppress unused warning
+        }
+    }
+
+    /// Validate optional metadata map: max 5 keys, each key/value max 32 bytes (#67).
+    fn validate_metadata(env: &Env, metadata: &Option<Map<String, String>>) {
+        if let Some(m) = metadata {
+            if m.len() > MAX_METADATA_KEYS {
+                panic_with_error!(env, PaymentError::InvalidMetadata);
+            }
+            for (k, v) in m.iter() {
+                if k.len() > MAX_METADATA_KEY_LEN {
+                    panic_with_error!(env, PaymentError::InvalidMetadata);
+                }
+                if v.len() > MAX_METADATA_KEY_LEN {
+                    panic_with_error!(env, PaymentError::InvalidMetadata);
+                }
+            }
+            let _ = env; // suppress unused warning
+        }
+    }
+
+    /// Compute a simple u32 hash of a reference string for use as a storage key (#67).
+    fn reference_hash(_env: &Env, reference: &String) -> u32 {
+        let bytes = reference.to_bytes();
+        let mut h: u32 = 2166136261u32;
+        for b in bytes.iter() {
+            h = h.wrapping_mul(16777619).wrapping_add(b as u32);
+        }
+        h
+    }
+
+    /// Append payment_id to the merchant+reference index (#67).
+    fn index_payment_by_reference(
+        env: &Env,
+        merchant: &Address,
+        reference: &String,
+        payment_id: u32,
+    ) {
+        let hash = Self::reference_hash(env, reference);
+        let key = DataKey::MerchantReference(merchant.clone(), hash);
+        let mut ids: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        ids.push_back(payment_id);
+        env.storage().persistent().set(&key, &ids);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    /// Compute sha256(payment_id || customer || merchant || amount || token || completed_at).
+    /// All integers encoded big-endian. Addresses encoded as their raw bytes.
+    fn compute_receipt_hash(
+        env: &Env,
+        payment_id: u32,
+        customer: &Address,
+        merchant: &Address,
+        amount: i128,
+        token: &Address,
+        completed_at: u64,
+    ) -> BytesN<32> {
+        let mut preimage = Bytes::new(env);
+        preimage.extend_from_array(&payment_id.to_be_bytes());
+        preimage.append(&customer.to_xdr(env));
+        preimage.append(&merchant.to_xdr(env));
+        preimage.extend_from_array(&amount.to_be_bytes());
+        preimage.append(&token.to_xdr(env));
+        preimage.extend_from_array(&completed_at.to_be_bytes());
+        env.crypto().sha256(&preimage).into()
+    }
+
+    // --- Stats Helpers (#70) ---
+
+    fn load_global_stats(env: &Env) -> GlobalStats {
+        env.storage()
+            .persistent()
+            .get(&DataKey::GlobalStats)
+            .unwrap_or(GlobalStats {
+                total_payments_created: 0,
+                total_payments_completed: 0,
+                total_payments_refunded: 0,
+                total_payments_expired: 0,
+                total_volume_completed: Map::new(env),
+                total_volume_refunded: Map::new(env),
+            })
+    }
+
+    fn save_global_stats(env: &Env, stats: &GlobalStats) {
+        env.storage().persistent().set(&DataKey::GlobalStats, stats);
+        env.storage().persistent().extend_ttl(
+            &DataKey::GlobalStats,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn load_merchant_stats(env: &Env, merchant: &Address) -> MerchantStats {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MerchantStats(merchant.clone()))
+            .unwrap_or(MerchantStats {
+                payments_created: 0,
+                payments_completed: 0,
+                payments_refunded: 0,
+                volume_completed: Map::new(env),
+            })
+    }
+
+    fn save_merchant_stats(env: &Env, merchant: &Address, stats: &MerchantStats) {
+        let key = DataKey::MerchantStats(merchant.clone());
+        env.storage().persistent().set(&key, stats);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn inc_global_created(env: &Env) {
+        let mut s = Self::load_global_stats(env);
+        s.total_payments_created += 1;
+        Self::save_global_stats(env, &s);
+    }
+
+    fn inc_global_completed(env: &Env, token: &Address, amount: i128) {
+        let mut s = Self::load_global_stats(env);
+        s.total_payments_completed += 1;
+        let prev = s.total_volume_completed.get(token.clone()).unwrap_or(0);
+        s.total_volume_completed.set(token.clone(), prev + amount);
+        Self::save_global_stats(env, &s);
+    }
+
+    fn inc_global_refunded(env: &Env, token: &Address, amount: i128) {
+        let mut s = Self::load_global_stats(env);
+        s.total_payments_refunded += 1;
+        let prev = s.total_volume_refunded.get(token.clone()).unwrap_or(0);
+        s.total_volume_refunded.set(token.clone(), prev + amount);
+        Self::save_global_stats(env, &s);
+    }
+
+    fn inc_global_expired(env: &Env) {
+        let mut s = Self::load_global_stats(env);
+        s.total_payments_expired += 1;
+        Self::save_global_stats(env, &s);
+    }
+
+    fn inc_merchant_created(env: &Env, merchant: &Address) {
+        let mut s = Self::load_merchant_stats(env, merchant);
+        s.payments_created += 1;
+        Self::save_merchant_stats(env, merchant, &s);
+
+        // #226: Track pending count in MerchantSummary
+        let key = DataKey2::MerchantSummary(merchant.clone());
+        let mut summary: MerchantSummary =
+            env.storage()
+                .persistent()
+                .get(&key)
+                .unwrap_or(MerchantSummary {
+                    total_volume: 0,
+                    completed_count: 0,
+                    failed_count: 0,
+                    pending_count: 0,
+                    volume_by_token: Map::new(env),
+                });
+        summary.pending_count += 1;
+        env.storage().persistent().set(&key, &summary);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn inc_merchant_completed(env: &Env, merchant: &Address, token: &Address, amount: i128) {
+        let mut s = Self::load_merchant_stats(env, merchant);
+        s.payments_completed += 1;
+        let prev = s.volume_completed.get(token.clone()).unwrap_or(0);
+        s.volume_completed.set(token.clone(), prev + amount);
+        Self::save_merchant_stats(env, merchant, &s);
+
+        // #226: Update MerchantSummary
+        let key = DataKey2::MerchantSummary(merchant.clone());
+        let mut summary: MerchantSummary =
+            env.storage()
+                .persistent()
+                .get(&key)
+                .unwrap_or(MerchantSummary {
+                    total_volume: 0,
+                    completed_count: 0,
+                    failed_count: 0,
+                    pending_count: 0,
+                    volume_by_token: Map::new(env),
+                });
+        summary.completed_count += 1;
+        if summary.pending_count > 0 {
+            summary.pending_count -= 1;
+        }
+        summary.total_volume += amount;
+        let prev_token = summary.volume_by_token.get(token.clone()).unwrap_or(0);
+        summary
+            .volume_by_token
+            .set(token.clone(), prev_token + amount);
+        env.storage().persistent().set(&key, &summary);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn inc_merchant_failed(env: &Env, merchant: &Address) {
+        let key = DataKey2::MerchantSummary(merchant.clone());
+        let mut summary: MerchantSummary =
+            env.storage()
+                .persistent()
+                .get(&key)
+                .unwrap_or(MerchantSummary {
+                    total_volume: 0,
+                    completed_count: 0,
+                    failed_count: 0,
+                    pending_count: 0,
+                    volume_by_token: Map::new(env),
+                });
+        summary.failed_count += 1;
+        if summary.pending_count > 0 {
+            summary.pending_count -= 1;
+        }
+        env.storage().persistent().set(&key, &summary);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn inc_merchant_refunded(env: &Env, merchant: &Address, token: &Address, amount: i128) {
+        let mut s = Self::load_merchant_stats(env, merchant);
+        s.payments_refunded += 1;
+        let _ = (token, amount); // volume tracked globally; merchant count only
+        Self::save_merchant_stats(env, merchant, &s);
+    }
+
+    fn inc_volume_bucket(env: &Env, token: &Address, amount: i128) {
+        let bucket = env.ledger().sequence() / LEDGER_BUCKET_SIZE;
+        let key = DataKey::VolumeBucket(token.clone(), bucket);
+        let prev: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage().persistent().set(&key, &(prev + amount));
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn inc_merchant_volume_bucket(env: &Env, merchant: &Address, amount: i128) {
+        let bucket = env.ledger().sequence() / LEDGER_BUCKET_SIZE;
+        let key = DataKey::MerchantVolumeBucket(merchant.clone(), bucket);
+        let prev: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage().persistent().set(&key, &(prev + amount));
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn next_payment_id(env: &Env) -> u32 {
+        let mut counter: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::PaymentCounter)
+            .unwrap_or(0);
+        let id = counter;
+        counter += 1;
+        // Counter stays in instance storage — bounded, config-like
+        env.storage()
+            .instance()
+            .set(&DataKey::PaymentCounter, &counter);
+        id
+    }
+
+    fn add_customer_payment(env: &Env, customer: &Address, payment_id: u32) {
+        let key = DataKey::CustomerPayments(customer.clone());
+        let mut customer_payments: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        customer_payments.push_back(payment_id);
+        // Persistent: customer index grows with payment volume
+        env.storage().persistent().set(&key, &customer_payments);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn get_or_init_version(env: &Env) -> u32 {
+        if let Some(version) = env
+            .storage()
+            .instance()
+            .get::<DataKey, u32>(&DataKey::ContractVersion)
+        {
+            version
+        } else {
+            let initial_version = 1u32;
+            env.storage()
+                .instance()
+                .set(&DataKey::ContractVersion, &initial_version);
+            initial_version
+        }
+    }
+
+    fn get_rate_limit_config_internal(env: &Env) -> RateLimitConfig {
+        env.storage()
+            .instance()
+            .get(&DataKey::RateLimitConfig)
+            .unwrap_or(RateLimitConfig {
+                max_payments: DEFAULT_RATE_LIMIT_MAX_PAYMENTS,
+                window_size_ledgers: DEFAULT_RATE_LIMIT_WINDOW_SIZE_LEDGERS,
+            })
+    }
+
+    /// Returns the configured minimum collateral, falling back to the default (#129).
+    fn get_min_collateral_internal(env: &Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MinCollateral)
+            .unwrap_or(DEFAULT_MIN_COLLATERAL)
+    }
+
+    // --- Withdrawal Queue Helpers (#126) ---
+
+    /// Enqueue a completed payment into the merchant's withdrawal queue.
+    fn enqueue_withdrawal(env: &Env, merchant: &Address, payment_id: u32, amount: i128) {
+        let key = DataKey::WithdrawalQueue(merchant.clone());
+        let mut queue: Vec<(u32, i128)> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        queue.push_back((payment_id, amount));
+        env.storage().persistent().set(&key, &queue);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        events::emit_withdrawal_queued(env, merchant.clone(), payment_id, amount);
+    }
+
+    /// Get the merchant's withdrawal queue.
+    fn get_withdrawal_queue(env: &Env, merchant: &Address) -> Vec<(u32, i128)> {
+        let key = DataKey::WithdrawalQueue(merchant.clone());
+        env.storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env))
+    }
+
+    /// Set the merchant's withdrawal queue.
+    fn set_withdrawal_queue(env: &Env, merchant: &Address, queue: Vec<(u32, i128)>) {
+        let key = DataKey::WithdrawalQueue(merchant.clone());
+        env.storage().persistent().set(&key, &queue);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    /// Returns the global slippage config, falling back to defaults (#135).
+    fn get_slippage_config_internal(env: &Env) -> SlippageConfig {
+        env.storage()
+            .instance()
+            .get(&DataKey::SlippageConfig)
+            .unwrap_or(SlippageConfig {
+                default_bps: DEFAULT_SLIPPAGE_BPS,
+                min_bps: DEFAULT_MIN_SLIPPAGE_BPS,
+                max_bps: DEFAULT_MAX_SLIPPAGE_BPS,
+            })
+    }
+
+    /// Check merchant volume cap and update window volume. Panics if cap would be exceeded (#131).
+    fn check_and_update_merchant_volume_cap(
+        env: &Env,
+        payment_id: u32,
+        merchant: &Address,
+        amount: i128,
+    ) {
+        let cap_key = DataKey::VolumeCap(merchant.clone());
+        let cap: VolumeCap = match env.storage().persistent().get(&cap_key) {
+            Some(c) => c,
+            None => return, // No cap configured — no restriction
+        };
+
+        let now = env.ledger().timestamp();
+        let bucket = now / cap.window_seconds;
+        let vol_key = DataKey::MerchantWindowVolume(merchant.clone(), bucket);
+        let current_volume: i128 = env.storage().persistent().get(&vol_key).unwrap_or(0);
+        let new_volume = current_volume
+            .checked_add(amount)
+            .unwrap_or_else(|| panic_with_error!(env, Error::ArithmeticOverflow));
+
+        if new_volume > cap.cap_amount {
+            events::emit_volume_capped(
+                env,
+                merchant.clone(),
+                payment_id,
+                current_volume,
+                cap.cap_amount,
+            );
+            panic_with_error!(env, Error::MerchantVolumeCapped);
+        }
+
+        env.storage().persistent().set(&vol_key, &new_volume);
+        env.storage().persistent().extend_ttl(
+            &vol_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    // --- Merchant Ban Management & On-Chain Appeal ---
+
+    /// Admin sets the appeal rejection cooldown in seconds.
+    pub fn set_appeal_rejection_cooldown(env: Env, admin: Address, seconds: u64) {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
+        if admin != stored_admin {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey2::AppealRejectionCooldownSeconds, &seconds);
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Admin suspends a merchant for a given duration (seconds). Payments are paused.
+    pub fn suspend_merchant(
+        env: Env,
+        admin: Address,
+        merchant: Address,
+        reason_hash: BytesN<32>,
+        duration_seconds: u64,
+    ) {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
+        if admin != stored_admin {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
+        if duration_seconds == 0 {
+            panic_with_error!(&env, ConfigError::InvalidDuration);
+        }
+
+        let expiry = env.ledger().timestamp() + duration_seconds;
+        env.storage().persistent().set(
+            &DataKey2::MerchantStatus(merchant.clone()),
+            &MerchantStatus::Suspended,
+        );
+        env.storage().persistent().set(
+            &DataKey2::MerchantSuspensionExpiry(merchant.clone()),
+            &expiry,
+        );
+        env.storage().persistent().extend_ttl(
+            &DataKey2::MerchantStatus(merchant.clone()),
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        env.storage().persistent().extend_ttl(
+            &DataKey2::MerchantSuspensionExpiry(merchant.clone()),
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        events::emit_merchant_suspended(&env, merchant, reason_hash, expiry);
 
-                            Self::inc_global_refunded(env, &payment.token, payment.amount);
-                            Self::inc_merchant_refunded(
-                                env,
-                                &payment.merchant,
-                                &payment.token,
-                                payment.amount,
-                            );
-                            Self::inc_merchant_failed(env, &payment.merchant); // #226
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
 
-                            events::emit_payment_swap_failed(
-                                env,
-                                payment_id,
-                                payment.customer.clone(),
-                                payment.token.clone(),
-                                payment.amount,
-                                reason,
-                            );
-                            events::emit_payment_status_changed(
-                                env,
-                                payment_id,
-                                old_status,
-                                PaymentStatus::Refunded,
-                            );
+    /// Admin permanently bans a merchant. Banned merchants cannot create or receive payments.
+    pub fn ban_merchant(env: Env, admin: Address, merchant: Address, reason_hash: BytesN<32>) {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
+        if admin != stored_admin {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
 
-                            env.storage()
-                                .instance()
-                                .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-                            return; // Exit early - payment refunded
-                        }
-                    }
-                } else {
-                    // No router configured - cannot swap, use original token
-                    final_token = payment.token.clone();
-                    final_amount = payment.amount;
-                }
+        env.storage().persistent().set(
+            &DataKey2::MerchantStatus(merchant.clone()),
+            &MerchantStatus::Banned,
+        );
+        env.storage()
+            .persistent()
+            .set(&DataKey::MerchantApproved(merchant.clone()), &false);
+        env.storage().persistent().extend_ttl(
+            &DataKey2::MerchantStatus(merchant.clone()),
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        events::emit_merchant_banned(&env, merchant, reason_hash);
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Admin reinstates a merchant (clears ban/suspension, restores Active status).
+    pub fn reinstate_merchant(env: Env, admin: Address, merchant: Address) {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
+        if admin != stored_admin {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
+
+        env.storage().persistent().set(
+            &DataKey2::MerchantStatus(merchant.clone()),
+            &MerchantStatus::Active,
+        );
+        env.storage()
+            .persistent()
+            .set(&DataKey::MerchantApproved(merchant.clone()), &true);
+        env.storage().persistent().extend_ttl(
+            &DataKey2::MerchantStatus(merchant.clone()),
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Banned merchant submits an appeal. Only one active appeal per merchant.
+    pub fn submit_appeal(env: Env, merchant: Address, reason_hash: BytesN<32>) {
+        Self::require_not_paused(&env);
+        merchant.require_auth();
+
+        let status: MerchantStatus = env
+            .storage()
+            .persistent()
+            .get(&DataKey2::MerchantStatus(merchant.clone()))
+            .unwrap_or(MerchantStatus::Active);
+
+        if status != MerchantStatus::Banned {
+            panic_with_error!(&env, MerchantError::MerchantNotBanned);
+        }
+
+        // Enforce one-active-appeal guard
+        let existing_opt: Option<MerchantAppeal> = env
+            .storage()
+            .persistent()
+            .get(&DataKey2::MerchantAppeal(merchant.clone()));
+        if let Some(existing) = existing_opt {
+            if existing.status == AppealStatus::Pending || existing.status == AppealStatus::ApprovedCoolingOff {
+                panic_with_error!(&env, MerchantError::AppealAlreadyExists);
             }
         }
 
-        let rolling_before = Self::rolling_merchant_volume(env, &payment.merchant);
-        let projected_volume = rolling_before + final_amount;
-        let applied_fee_bps = Self::fee_bps_for_volume(env, projected_volume);
-        let fee_amount = (final_amount * applied_fee_bps as i128) / 10_000;
-        let net_amount = final_amount - fee_amount;
+        // Enforce cooldown after rejection
+        let cooldown_until: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey2::AppealCooldownUntil(merchant.clone()))
+            .unwrap_or(0);
+        if env.ledger().timestamp() < cooldown_until {
+            panic_with_error!(&env, MerchantError::AppealCooldownActive);
+        }
 
-        let fee_recipient: Address = env
+        let appeal = MerchantAppeal {
+            merchant: merchant.clone(),
+            reason_hash: reason_hash.clone(),
+            submitted_at: env.ledger().timestamp(),
+            status: AppealStatus::Pending,
+            cooling_off_until: 0,
+        };
+
+        env.storage()
+            .persistent()
+            .set(&DataKey2::MerchantAppeal(merchant.clone()), &appeal);
+        env.storage().persistent().extend_ttl(
+            &DataKey2::MerchantAppeal(merchant.clone()),
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        events::emit_appeal_submitted(&env, merchant, reason_hash);
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Admin approves an appeal — merchant enters cooling-off period before full reinstatement.
+    pub fn approve_appeal(env: Env, admin: Address, merchant: Address) {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        let stored_admin: Address = env
             .storage()
             .instance()
-            .get(&DataKey::FeeRecipient)
-            .expect("Fee recipient not configured");
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
+        if admin != stored_admin {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
 
-        let token_client = token::Client::new(env, &final_token);
+        let mut appeal: MerchantAppeal = env
+            .storage()
+            .persistent()
+            .get(&DataKey2::MerchantAppeal(merchant.clone()))
+            .unwrap_or_else(|| panic_with_error!(&env, MerchantError::AppealNotFound));
 
-        if fee_amount > 0 {
-            token_client.transfer(&env.current_contract_address(), &fee_recipient, &fee_amount);
-            events::emit_fee_collected(
-                env,
-                payment_id,
-                fee_amount,
-                fee_recipient,
-                final_token.clone(),
-            );
-            // #242: Accrue referral commission on the fee collected for referred merchants
-            Self::accrue_referral_commission(env, &payment.merchant, payment_id, fee_amount);
+        if appeal.status != AppealStatus::Pending {
+            panic_with_error!(&env, MerchantError::AppealNotPending);
         }
 
-        let split_transfers = Self::distribute_net_payment(env, payment, net_amount);
-        if split_transfers.len() > 0 {
-            events::emit_payment_split_completed(env, payment_id, split_transfers);
-        }
+        // Set cooling-off period (default: 7 days in seconds)
+        let cooling_off_period: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey2::AppealRejectionCooldownSeconds)
+            .unwrap_or(7 * 24 * 60 * 60); // Default 7 days
+        let cooling_off_until = env.ledger().timestamp() + cooling_off_period;
 
-        let old_status = payment.status;
+        appeal.status = AppealStatus::ApprovedCoolingOff;
+        appeal.cooling_off_until = cooling_off_until;
+        env.storage()
+            .persistent()
+            .set(&DataKey2::MerchantAppeal(merchant.clone()), &appeal);
+        env.storage().persistent().extend_ttl(
+            &DataKey2::MerchantAppeal(merchant.clone()),
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
 
-        // Check if cooling-off is enabled for this payment (#309)
-        let cooling_off_config: Option<CoolingOffConfig> = env
+        events::emit_appeal_approved(&env, merchant, cooling_off_until);
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Complete reinstatement after cooling-off period has elapsed. Callable by anyone.
+    pub fn complete_reinstatement(env: Env, merchant: Address) {
+        Self::require_not_paused(&env);
+
+        let appeal: MerchantAppeal = env
             .storage()
             .persistent()
-            .get(&DataKey2::CoolingOffConfig(payment_id));
+            .get(&DataKey2::MerchantAppeal(merchant.clone()))
+            .unwrap_or_else(|| panic_with_error!(&env, MerchantError::AppealNotFound));
 
-        if let Some(mut config) = cooling_off_config {
-            if config.cooling_off_ledgers > 0 {
-                // Set status to CoolingOff instead of Completed
-                payment.status = PaymentStatus::CoolingOff;
-                config.cooling_off_expiry_ledger =
-                    env.ledger().sequence() + config.cooling_off_ledgers;
-                env.storage()
-                    .persistent()
-                    .set(&DataKey2::CoolingOffConfig(payment_id), &config);
-                env.storage().persistent().extend_ttl(
-                    &DataKey2::CoolingOffConfig(payment_id),
-                    PERSISTENT_LIFETIME_THRESHOLD,
-                    PERSISTENT_BUMP_AMOUNT,
-                );
-                events::emit_payment_in_cooling_off(
-                    env,
-                    payment_id,
-                    payment.customer.clone(),
-                    config.cooling_off_expiry_ledger,
-                );
-            } else {
-                payment.status = PaymentStatus::Completed;
-            }
-        } else {
-            payment.status = PaymentStatus::Completed;
+        if appeal.status != AppealStatus::ApprovedCoolingOff {
+            panic_with_error!(&env, MerchantError::AppealNotInCoolingOff);
         }
-        let original_amount = payment.amount;
-        payment.amount = net_amount;
-        let completed_at = env.ledger().timestamp();
 
+        let now = env.ledger().timestamp();
+        if now < appeal.cooling_off_until {
+            panic_with_error!(&env, MerchantError::ReinstatementCoolingOffActive);
+        }
+
+        // Update appeal status
+        let mut updated_appeal = appeal;
+        updated_appeal.status = AppealStatus::ApprovedReinstated;
         env.storage()
             .persistent()
-            .set(&DataKey::Payment(payment_id), payment);
-        env.storage().persistent().extend_ttl(
-            &DataKey::Payment(payment_id),
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
-        env.storage()
-            .persistent()
-            .set(&DataKey::Settled(payment_id), &true);
+            .set(&DataKey2::MerchantAppeal(merchant.clone()), &updated_appeal);
         env.storage().persistent().extend_ttl(
-            &DataKey::Settled(payment_id),
+            &DataKey2::MerchantAppeal(merchant.clone()),
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        let receipt_hash = Self::compute_receipt_hash(
-            env,
-            payment_id,
-            &payment.customer,
-            &payment.merchant,
-            net_amount,
-            &payment.token,
-            completed_at,
+        // Reinstate merchant
+        env.storage().persistent().set(
+            &DataKey2::MerchantStatus(merchant.clone()),
+            &MerchantStatus::Active,
         );
         env.storage()
             .persistent()
-            .set(&DataKey::PaymentReceipt(payment_id), &receipt_hash);
+            .set(&DataKey::MerchantApproved(merchant.clone()), &true);
         env.storage().persistent().extend_ttl(
-            &DataKey::PaymentReceipt(payment_id),
+            &DataKey2::MerchantStatus(merchant.clone()),
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        Self::inc_global_completed(env, &payment.token, net_amount);
-        Self::inc_merchant_completed(env, &payment.merchant, &payment.token, net_amount);
-        Self::inc_volume_bucket(env, &payment.token, net_amount);
-        Self::inc_merchant_volume_bucket(env, &payment.merchant, original_amount);
+        events::emit_merchant_reinstated(&env, merchant, now);
+    }
 
-        // Auto-enqueue completed payment into merchant's withdrawal queue (#126)
-        Self::enqueue_withdrawal(env, &payment.merchant, payment_id, net_amount);
+    /// Admin rejects an appeal — merchant remains banned and cooldown is applied.
+    pub fn reject_appeal(env: Env, admin: Address, merchant: Address) {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
+        if admin != stored_admin {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
 
-        let rolling_after = Self::rolling_merchant_volume(env, &payment.merchant);
-        let new_tier_bps = Self::fee_bps_for_volume(env, rolling_after);
-        let tier_key = DataKey::MerchantCurrentTierBps(payment.merchant.clone());
-        let old_tier_bps: u32 = env
+        let mut appeal: MerchantAppeal = env
             .storage()
             .persistent()
-            .get(&tier_key)
-            .unwrap_or(Self::get_fee_bps(env.clone()));
-        if old_tier_bps != new_tier_bps {
-            env.storage().persistent().set(&tier_key, &new_tier_bps);
-            env.storage().persistent().extend_ttl(
-                &tier_key,
-                PERSISTENT_LIFETIME_THRESHOLD,
-                PERSISTENT_BUMP_AMOUNT,
-            );
-            events::emit_merchant_tier_updated(
-                env,
-                payment.merchant.clone(),
-                new_tier_bps,
-                rolling_after,
-            );
+            .get(&DataKey2::MerchantAppeal(merchant.clone()))
+            .unwrap_or_else(|| panic_with_error!(&env, MerchantError::AppealNotFound));
+
+        if appeal.status != AppealStatus::Pending {
+            panic_with_error!(&env, MerchantError::AppealNotPending);
         }
 
-        events::emit_payment_completed(
-            env,
-            payment_id,
-            payment.merchant.clone(),
-            net_amount,
-            completed_at,
+        appeal.status = AppealStatus::Rejected;
+        env.storage()
+            .persistent()
+            .set(&DataKey2::MerchantAppeal(merchant.clone()), &appeal);
+
+        // Apply cooldown for next appeal
+        let cooldown_seconds: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey2::AppealRejectionCooldownSeconds)
+            .unwrap_or(30 * 24 * 60 * 60); // Default 30 days
+        let cooldown_until = env.ledger().timestamp() + cooldown_seconds;
+        env.storage()
+            .persistent()
+            .set(&DataKey2::AppealCooldownUntil(merchant.clone()), &cooldown_until);
+        env.storage().persistent().extend_ttl(
+            &DataKey2::AppealCooldownUntil(merchant.clone()),
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
         );
-        events::emit_payment_status_changed(env, payment_id, old_status, PaymentStatus::Completed);
-        events::emit_payment_receipt_issued(env, payment_id, receipt_hash);
 
-        // #239: Accrue loyalty points to customer
-        Self::accrue_loyalty_points(env, payment_id, &payment.customer, payment.amount);
+        events::emit_appeal_rejected(&env, merchant);
 
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
     }
 
-    fn distribute_net_payment(
-        env: &Env,
-        payment: &Payment,
-        net_amount: i128,
-    ) -> Vec<SplitTransfer> {
-        let token_client = token::Client::new(env, &payment.token);
-
-        if let Some(splits) = &payment.split_recipients {
-            if splits.len() == 0 {
-                token_client.transfer(
-                    &env.current_contract_address(),
-                    &payment.merchant,
-                    &net_amount,
-                );
-                return Vec::new(env);
-            }
-
-            let mut split_events = Vec::new(env);
-            let mut distributed: i128 = 0;
-            for split in splits.iter() {
-                let share = (net_amount * split.bps as i128) / 10_000;
-                if share > 0 {
-                    token_client.transfer(
-                        &env.current_contract_address(),
-                        &split.recipient,
-                        &share,
-                    );
-                }
-                distributed += share;
-                split_events.push_back(SplitTransfer {
-                    recipient: split.recipient,
-                    bps: split.bps,
-                    amount: share,
-                });
-            }
-
-            let dust_to_merchant = net_amount - distributed;
-            if dust_to_merchant > 0 {
-                token_client.transfer(
-                    &env.current_contract_address(),
-                    &payment.merchant,
-                    &dust_to_merchant,
-                );
-            }
-            return split_events;
-        }
+    /// Get the current status of a merchant.
+    pub fn get_merchant_status(env: Env, merchant: Address) -> MerchantStatus {
+        env.storage()
+            .persistent()
+            .get(&DataKey2::MerchantStatus(merchant))
+            .unwrap_or(MerchantStatus::Active)
+    }
 
-        token_client.transfer(
-            &env.current_contract_address(),
-            &payment.merchant,
-            &net_amount,
-        );
-        Vec::new(env)
+    /// Get the active appeal for a merchant, if any.
+    pub fn get_merchant_appeal(env: Env, merchant: Address) -> Option<MerchantAppeal> {
+        env.storage()
+            .persistent()
+            .get(&DataKey2::MerchantAppeal(merchant))
     }
 
-    fn validate_split_recipients(split_recipients: &Option<Vec<SplitRecipient>>) {
-        if let Some(splits) = split_recipients {
-            if splits.len() == 0 {
-                panic!("split_recipients cannot be empty");
-            }
+    // ─── #226: Merchant Revenue Dashboard ────────────────────────────────────
 
-            let mut total_bps: u32 = 0;
-            for split in splits.iter() {
-                if split.bps == 0 {
-                    panic!("split recipient bps must be positive");
-                }
-                total_bps = total_bps
-                    .checked_add(split.bps)
-                    .expect("split bps overflow");
-            }
-            if total_bps != 10_000 {
-                panic!("split_recipients must sum to 10000 bps");
-            }
-        }
+    /// Returns the full merchant revenue summary.
+    pub fn get_merchant_summary(env: Env, merchant: Address) -> MerchantSummary {
+        env.storage()
+            .persistent()
+            .get(&DataKey2::MerchantSummary(merchant))
+            .unwrap_or(MerchantSummary {
+                total_volume: 0,
+                completed_count: 0,
+                failed_count: 0,
+                pending_count: 0,
+                volume_by_token: Map::new(&env),
+            })
     }
 
-    fn validate_fee_tiers(tiers: &Vec<FeeTier>) {
-        let mut last_min_volume: i128 = -1;
-        for tier in tiers.iter() {
-            if tier.fee_bps > MAX_FEE_BPS {
-                panic!("tier fee cannot exceed 500 bps (5%)");
-            }
-            if tier.min_volume < 0 {
-                panic!("tier min_volume cannot be negative");
-            }
-            if tier.min_volume <= last_min_volume {
-                panic!("fee tiers must be strictly ascending by min_volume");
-            }
-            last_min_volume = tier.min_volume;
-        }
+    /// Returns the completed volume for a specific token for a merchant.
+    pub fn get_merchant_volume_by_token(env: Env, merchant: Address, token: Address) -> i128 {
+        let summary: MerchantSummary = env
+            .storage()
+            .persistent()
+            .get(&DataKey2::MerchantSummary(merchant))
+            .unwrap_or(MerchantSummary {
+                total_volume: 0,
+                completed_count: 0,
+                failed_count: 0,
+                pending_count: 0,
+                volume_by_token: Map::new(&env),
+            });
+        summary.volume_by_token.get(token).unwrap_or(0)
     }
 
-    /// Validate invoice data and verify total matches payment amount (#128).
-    fn validate_invoice_data(env: &Env, invoice: &Option<InvoiceData>, payment_amount: i128) {
-        if let Some(inv) = invoice {
-            if inv.line_items.len() == 0 {
-                panic!("Invoice line_items cannot be empty");
-            }
-            if (inv.line_items.len() as u32) > MAX_INVOICE_LINE_ITEMS {
-                panic!("Invoice line items exceed maximum of 20");
-            }
-
-            let mut invoice_subtotal: i128 = 0;
-            for item in inv.line_items.iter() {
-                if item.quantity == 0 {
-                    panic!("Line item quantity must be positive");
-                }
-                if item.unit_price < 0 {
-                    panic!("Line item unit_price cannot be negative");
-                }
-                let line_amount = (item.quantity as i128)
-                    .checked_mul(item.unit_price)
-                    .expect("Line item amount overflow");
-                invoice_subtotal = invoice_subtotal
-                    .checked_add(line_amount)
-                    .expect("Invoice subtotal overflow");
-            }
+    /// Returns (completed_count, failed_count, pending_count) for a merchant.
+    pub fn get_merchant_payment_counts(env: Env, merchant: Address) -> (u32, u32, u32) {
+        let summary: MerchantSummary = env
+            .storage()
+            .persistent()
+            .get(&DataKey2::MerchantSummary(merchant))
+            .unwrap_or(MerchantSummary {
+                total_volume: 0,
+                completed_count: 0,
+                failed_count: 0,
+                pending_count: 0,
+                volume_by_token: Map::new(&env),
+            });
+        (
+            summary.completed_count,
+            summary.failed_count,
+            summary.pending_count,
+        )
+    }
 
-            let tax_amount = (invoice_subtotal * inv.tax_bps as i128) / 10_000;
-            let invoice_total = invoice_subtotal
-                .checked_add(tax_amount)
-                .expect("Invoice total overflow");
+    /// Admin resets a merchant's dashboard counters.
+    pub fn reset_merchant_stats(env: Env, merchant: Address) {
+        Self::require_not_paused(&env);
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
+        admin.require_auth();
 
-            if invoice_total != payment_amount {
-                panic!("Invoice total does not match payment amount");
-            }
+        let empty = MerchantSummary {
+            total_volume: 0,
+            completed_count: 0,
+            failed_count: 0,
+            pending_count: 0,
+            volume_by_token: Map::new(&env),
+        };
+        let key = DataKey2::MerchantSummary(merchant);
+        env.storage().persistent().set(&key, &empty);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
 
-            let _ = env; // suppress unused warning
+    // ── #239: Customer Loyalty Points ─────────────────────────────────────────
+
+    /// Admin configures the platform-wide loyalty program, earned on every
+    /// payment regardless of merchant. Merchants run their own programs via
+    /// `configure_loyalty_program`.
+    /// points_per_unit: points earned per 1_000_000 units of payment token.
+    /// redemption_rate_bps: discount in basis points per 1 point redeemed.
+    /// min_payment_floor: minimum payment amount after discount.
+    /// expiry_ledgers: ledgers after which unspent points expire (0 = no expiry).
+    pub fn configure_loyalty(
+        env: Env,
+        admin: Address,
+        points_per_unit: u32,
+        redemption_rate_bps: u32,
+        min_payment_floor: i128,
+        expiry_ledgers: u32,
+    ) {
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
+        if admin != stored_admin {
+            panic_with_error!(&env, Error::Unauthorized);
         }
+        env.storage()
+            .instance()
+            .set(&DataKey2::LoyaltyPointsPerUnit, &points_per_unit);
+        env.storage()
+            .instance()
+            .set(&DataKey2::LoyaltyRedemptionRateBps, &redemption_rate_bps);
+        env.storage()
+            .instance()
+            .set(&DataKey2::LoyaltyMinPaymentFloor, &min_payment_floor);
+        env.storage()
+            .instance()
+            .set(&DataKey2::LoyaltyExpiryLedgers, &expiry_ledgers);
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
     }
 
-    /// Compute SHA256 hash of serialized invoice data (#128).
-    fn compute_invoice_hash(env: &Env, invoice: &InvoiceData) -> BytesN<32> {
-        let mut preimage = Bytes::new(env);
+    /// Customer redeems loyalty points as a discount on a pending payment.
+    /// Discount = points_to_redeem * redemption_rate_bps / 10_000.
+    /// Payment amount cannot drop below min_payment_floor.
+    /// Points are non-transferable (tied to customer address).
+    pub fn redeem_points(env: Env, customer: Address, payment_id: u32, points_to_redeem: i128) {
+        customer.require_auth();
 
-        // Serialize line items count
-        preimage.extend_from_array(&(invoice.line_items.len() as u32).to_be_bytes());
+        // Expire stale points first
+        Self::maybe_expire_points(&env, &customer);
 
-        // Serialize each line item
-        for item in invoice.line_items.iter() {
-            // For Symbol, we'll use its XDR representation
-            preimage.append(&item.description.to_xdr(env));
-            preimage.extend_from_array(&item.quantity.to_be_bytes());
-            preimage.extend_from_array(&item.unit_price.to_be_bytes());
+        let balance: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey2::LoyaltyBalance(customer.clone()))
+            .unwrap_or(0);
+        if points_to_redeem <= 0 || points_to_redeem > balance {
+            panic_with_error!(&env, MerchantError::InsufficientLoyaltyPoints);
         }
 
-        preimage.extend_from_array(&invoice.tax_bps.to_be_bytes());
-        preimage.append(&invoice.currency_label.clone().to_xdr(env));
-
-        env.crypto().sha256(&preimage).into()
-    }
-
-    fn fee_bps_for_volume(env: &Env, volume: i128) -> u32 {
-        let default_fee: u32 = env
+        let redemption_rate_bps: u32 = env
             .storage()
             .instance()
-            .get(&DataKey::FeeBps)
-            .unwrap_or(DEFAULT_FEE_BPS);
-        let tiers: Vec<FeeTier> = env
+            .get(&DataKey2::LoyaltyRedemptionRateBps)
+            .unwrap_or(0);
+        let min_floor: i128 = env
             .storage()
             .instance()
-            .get(&DataKey::FeeTiers)
-            .unwrap_or(Vec::new(env));
-
-        let mut selected = default_fee;
-        for tier in tiers.iter() {
-            if volume >= tier.min_volume {
-                selected = tier.fee_bps;
-            }
-        }
-        selected
-    }
+            .get(&DataKey2::LoyaltyMinPaymentFloor)
+            .unwrap_or(0);
 
-    fn rolling_merchant_volume(env: &Env, merchant: &Address) -> i128 {
-        let current_bucket = env.ledger().sequence() / LEDGER_BUCKET_SIZE;
-        let mut total = 0i128;
-        for i in 0..4u32 {
-            let bucket = current_bucket.saturating_sub(i);
-            let key = DataKey::MerchantVolumeBucket(merchant.clone(), bucket);
-            let bucket_total: i128 = env.storage().persistent().get(&key).unwrap_or(0);
-            total += bucket_total;
+        let mut payment: Payment = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Payment(payment_id))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::PaymentNotFound));
+        if payment.customer != customer {
+            panic_with_error!(&env, PaymentError::NotPaymentCustomer);
         }
-        total
-    }
-
-    fn enforce_rate_limit(env: &Env, customer: &Address, requested_payments: u32) {
-        if requested_payments == 0 {
-            return;
+        if payment.status != PaymentStatus::Pending {
+            panic_with_error!(&env, PaymentError::PaymentNotPending);
         }
 
-        let cfg = Self::get_rate_limit_config_internal(env);
-        let current_ledger = env.ledger().sequence();
-        let key = DataKey::CustomerRateLimit(customer.clone());
-
-        let mut state: CustomerRateLimit =
-            env.storage()
-                .persistent()
-                .get(&key)
-                .unwrap_or(CustomerRateLimit {
-                    count: 0,
-                    window_start_ledger: current_ledger,
-                });
-
-        if current_ledger.saturating_sub(state.window_start_ledger) >= cfg.window_size_ledgers {
-            state.count = 0;
-            state.window_start_ledger = current_ledger;
-        }
+        let discount = (points_to_redeem * redemption_rate_bps as i128) / 10_000;
+        let new_amount = (payment.amount - discount).max(min_floor);
+        let actual_discount = payment.amount - new_amount;
+        let actual_points_used = if actual_discount == discount {
+            points_to_redeem
+        } else {
+            // Recalculate points consumed when floor was hit
+            if redemption_rate_bps == 0 {
+                points_to_redeem
+            } else {
+                (actual_discount * 10_000) / redemption_rate_bps as i128
+            }
+        };
 
-        let new_count = state.count.saturating_add(requested_payments);
-        if new_count > cfg.max_payments {
-            panic_with_error!(env, Error::RateLimitExceeded);
-        }
+        // Burn points
+        let new_balance = balance - actual_points_used;
+        env.storage()
+            .persistent()
+            .set(&DataKey2::LoyaltyBalance(customer.clone()), &new_balance);
+        env.storage().persistent().extend_ttl(
+            &DataKey2::LoyaltyBalance(customer.clone()),
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
 
-        state.count = new_count;
-        env.storage().persistent().set(&key, &state);
+        // Apply discount to payment
+        payment.amount = new_amount;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Payment(payment_id), &payment);
         env.storage().persistent().extend_ttl(
-            &key,
+            &DataKey::Payment(payment_id),
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        events::emit_points_redeemed(
+            &env,
+            customer,
+            payment_id,
+            actual_points_used,
+            actual_discount,
+        );
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
     }
 
-    /// Validates merchant is approved or open mode is enabled.
-    fn require_merchant_approved(env: &Env, merchant: &Address) {
-        // Always enforce ban/suspension regardless of open mode
-        let status: MerchantStatus = env
-            .storage()
-            .persistent()
-            .get(&DataKey2::MerchantStatus(merchant.clone()))
-            .unwrap_or(MerchantStatus::Active);
+    // ── Merchant Loyalty Programs ─────────────────────────────────────────────
 
-        match status {
-            MerchantStatus::Banned => panic!("Merchant is banned"),
-            MerchantStatus::Suspended => {
-                let expiry: u64 = env
-                    .storage()
-                    .persistent()
-                    .get(&DataKey2::MerchantSuspensionExpiry(merchant.clone()))
-                    .unwrap_or(0);
-                if expiry == 0 || env.ledger().timestamp() <= expiry {
-                    panic!("Merchant is suspended");
-                }
-                // Suspension expired — fall through to allowlist check
+    /// Merchant creates or updates its own loyalty program, earned on every
+    /// completed payment to it alongside the platform-wide program.
+    /// Tiers must have strictly ascending, positive `min_spend` and a non-zero
+    /// multiplier, and need a non-zero `tier_window_ledgers`. Coalition
+    /// membership is kept across updates, and the redemption rate is frozen
+    /// while the program is in a coalition.
+    #[allow(clippy::too_many_arguments)]
+    pub fn configure_loyalty_program(
+        env: Env,
+        merchant: Address,
+        points_per_unit: u32,
+        redemption_rate_bps: u32,
+        min_payment_floor: i128,
+        lot_expiry_ledgers: u32,
+        tier_window_ledgers: u32,
+        tiers: Vec<LoyaltyTier>,
+    ) {
+        Self::require_not_paused(&env);
+        merchant.require_auth();
+        Self::require_merchant_approved(&env, &merchant);
+
+        if min_payment_floor < 0
+            || redemption_rate_bps > MAX_REDEMPTION_RATE_BPS
+            || tiers.len() > MAX_LOYALTY_TIERS
+            || (!tiers.is_empty() && tier_window_ledgers == 0)
+        {
+            panic_with_error!(&env, MerchantError::InvalidLoyaltyProgram);
+        }
+        let mut prev_min_spend = 0i128;
+        for tier in tiers.iter() {
+            if tier.min_spend <= prev_min_spend || tier.multiplier_bps == 0 {
+                panic_with_error!(&env, MerchantError::InvalidLoyaltyProgram);
             }
-            MerchantStatus::Active => {}
+            prev_min_spend = tier.min_spend;
         }
 
-        let open_mode: bool = env
+        let existing: Option<LoyaltyProgram> = env
             .storage()
-            .instance()
-            .get(&DataKey::MerchantOpenMode)
-            .unwrap_or(true); // Default: open mode (no allowlist enforcement)
-        if open_mode {
-            return;
+            .persistent()
+            .get(&DataKey3::LoyaltyProgram(merchant.clone()));
+        let coalition_id = existing.as_ref().map(|p| p.coalition_id).unwrap_or(0);
+        if let Some(existing) = existing {
+            if coalition_id != 0 && existing.redemption_rate_bps != redemption_rate_bps {
+                panic_with_error!(&env, MerchantError::LoyaltyRateFrozen);
+            }
         }
+        let tier_count = tiers.len();
+        let program = LoyaltyProgram {
+            merchant: merchant.clone(),
+            points_per_unit,
+            redemption_rate_bps,
+            min_payment_floor,
+            lot_expiry_ledgers,
+            tier_window_ledgers,
+            tiers,
+            coalition_id,
+        };
+        Self::save_loyalty_program(&env, &program);
+
+        events::emit_loyalty_program_configured(
+            &env,
+            merchant,
+            points_per_unit,
+            redemption_rate_bps,
+            tier_count,
+        );
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
 
-        let approved: bool = env
+    /// Returns a merchant's loyalty program.
+    pub fn get_loyalty_program(env: Env, merchant: Address) -> LoyaltyProgram {
+        Self::load_loyalty_program(&env, &merchant)
+    }
+
+    /// Customer redeems points earned in `issuer`'s program as a discount on a
+    /// pending payment. The payment may be to another member of the issuer's
+    /// coalition, in the coalition token; the discount granted is then taken
+    /// from the issuer's coalition reserve and held for that merchant. The issuer's redemption rate and the paid
+    /// merchant's floor apply. Lots closest to expiry are spent first.
+    pub fn redeem_program_points(
+        env: Env,
+        customer: Address,
+        payment_id: u32,
+        issuer: Address,
+        points_to_redeem: i128,
+    ) {
+        Self::require_not_paused(&env);
+        customer.require_auth();
+
+        let issuer_program = Self::load_loyalty_program(&env, &issuer);
+        let mut payment: Payment = env
             .storage()
             .persistent()
-            .get(&DataKey::MerchantApproved(merchant.clone()))
-            .unwrap_or(false);
-        if !approved {
-            panic!("Merchant not approved");
+            .get(&DataKey::Payment(payment_id))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::PaymentNotFound));
+        if payment.customer != customer {
+            panic_with_error!(&env, PaymentError::NotPaymentCustomer);
+        }
+        if payment.status != PaymentStatus::Pending {
+            panic_with_error!(&env, PaymentError::PaymentNotPending);
         }
-    }
 
-    fn require_merchant_reserve(env: &Env, merchant: &Address) {
-        if let Some(addr) = env
-            .storage()
-            .instance()
-            .get::<DataKey2, Address>(&DataKey2::RefundContract)
-        {
-            if !refund_reserve::RefundReserveClient::new(env, &addr)
-                .check_merchant_reserve(merchant)
+        let mut coalition_id = 0u32;
+        let min_floor = if payment.merchant == issuer {
+            issuer_program.min_payment_floor
+        } else {
+            let merchant_program: LoyaltyProgram = env
+                .storage()
+                .persistent()
+                .get(&DataKey3::LoyaltyProgram(payment.merchant.clone()))
+                .unwrap_or_else(|| panic_with_error!(&env, MerchantError::NotCoalitionMember));
+            if issuer_program.coalition_id == 0
+                || merchant_program.coalition_id != issuer_program.coalition_id
             {
-                panic_with_error!(env, Error::InsufficientMerchantReserve);
+                panic_with_error!(&env, MerchantError::NotCoalitionMember);
             }
-        }
-    }
-
-    /// Validate optional reference string: max 64 bytes (#67).
-    fn validate_reference(env: &Env, reference: &Option<String>) {
-        if let Some(r) = reference {
-            if r.len() > MAX_REFERENCE_LEN {
-                panic!("Reference exceeds maximum length of 64 bytes");
+            let coalition = Self::load_coalition(&env, issuer_program.coalition_id);
+            if payment.token != coalition.token {
+                panic_with_error!(&env, MerchantError::CoalitionTokenMismatch);
             }
-            let _ = env; // suppress unused warning
+            coalition_id = coalition.id;
+            merchant_program.min_payment_floor
+        };
+
+        let lots = Self::prune_point_lots(&env, &customer, &issuer);
+        let mut balance = 0i128;
+        for lot in lots.iter() {
+            balance += lot.points;
+        }
+        if points_to_redeem <= 0 || points_to_redeem > balance {
+            panic_with_error!(&env, MerchantError::InsufficientLoyaltyPoints);
         }
-    }
 
-    /// Validate optional metadata map: max 5 keys, each key/value max 32 bytes (#67).
-    fn validate_metadata(env: &Env, metadata: &Option<Map<String, String>>) {
-        if let Some(m) = metadata {
-            if m.len() > MAX_METADATA_KEYS {
-                panic!("Metadata exceeds maximum of 5 keys");
+        let rate = issuer_program.redemption_rate_bps as i128;
+        let discount = points_to_redeem * rate / 10_000;
+        let new_amount = (payment.amount - discount)
+            .max(min_floor)
+            .min(payment.amount);
+        let actual_discount = payment.amount - new_amount;
+        let points_used = if actual_discount == discount || rate == 0 {
+            points_to_redeem
+        } else {
+            // Floor was hit: charge only the points the granted discount needs
+            ((actual_discount * 10_000 + rate - 1) / rate).min(points_to_redeem)
+        };
+
+        let mut remaining = points_used;
+        let mut kept = Vec::new(&env);
+        for mut lot in lots.iter() {
+            if remaining > 0 {
+                let take = remaining.min(lot.points);
+                lot.points -= take;
+                remaining -= take;
             }
-            for (k, v) in m.iter() {
-                if k.len() > MAX_METADATA_KEY_LEN {
-                    panic!("Metadata key exceeds maximum length of 32 bytes");
-                }
-                if v.len() > MAX_METADATA_KEY_LEN {
-                    panic!("Metadata value exceeds maximum length of 32 bytes");
-                }
+            if lot.points > 0 {
+                kept.push_back(lot);
             }
-            let _ = env; // suppress unused warning
-        }
-    }
-
-    /// Compute a simple u32 hash of a reference string for use as a storage key (#67).
-    fn reference_hash(_env: &Env, reference: &String) -> u32 {
-        let bytes = reference.to_bytes();
-        let mut h: u32 = 2166136261u32;
-        for b in bytes.iter() {
-            h = h.wrapping_mul(16777619).wrapping_add(b as u32);
         }
-        h
-    }
+        Self::save_point_lots(&env, &customer, &issuer, &kept);
 
-    /// Append payment_id to the merchant+reference index (#67).
-    fn index_payment_by_reference(
-        env: &Env,
-        merchant: &Address,
-        reference: &String,
-        payment_id: u32,
-    ) {
-        let hash = Self::reference_hash(env, reference);
-        let key = DataKey::MerchantReference(merchant.clone(), hash);
-        let mut ids: Vec<u32> = env
-            .storage()
+        payment.amount = new_amount;
+        env.storage()
             .persistent()
-            .get(&key)
-            .unwrap_or(Vec::new(env));
-        ids.push_back(payment_id);
-        env.storage().persistent().set(&key, &ids);
+            .set(&DataKey::Payment(payment_id), &payment);
         env.storage().persistent().extend_ttl(
-            &key,
+            &DataKey::Payment(payment_id),
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
-    }
 
-    /// Compute sha256(payment_id || customer || merchant || amount || token || completed_at).
-    /// All integers encoded big-endian. Addresses encoded as their raw bytes.
-    fn compute_receipt_hash(
-        env: &Env,
-        payment_id: u32,
-        customer: &Address,
-        merchant: &Address,
-        amount: i128,
-        token: &Address,
-        completed_at: u64,
-    ) -> BytesN<32> {
-        let mut preimage = Bytes::new(env);
-        preimage.extend_from_array(&payment_id.to_be_bytes());
-        preimage.append(&customer.to_xdr(env));
-        preimage.append(&merchant.to_xdr(env));
-        preimage.extend_from_array(&amount.to_be_bytes());
-        preimage.append(&token.to_xdr(env));
-        preimage.extend_from_array(&completed_at.to_be_bytes());
-        env.crypto().sha256(&preimage).into()
+        if coalition_id != 0 && actual_discount > 0 {
+            let reserve_key = DataKey3::CoalitionReserve(coalition_id, issuer.clone());
+            let reserve: i128 = env.storage().persistent().get(&reserve_key).unwrap_or(0);
+            if reserve < actual_discount {
+                panic_with_error!(&env, MerchantError::InsufficientCoalitionReserve);
+            }
+            Self::set_coalition_reserve(&env, &reserve_key, reserve - actual_discount);
+            Self::record_coalition_debt(
+                &env,
+                coalition_id,
+                &issuer,
+                &payment.merchant,
+                actual_discount,
+            );
+        }
+        events::emit_program_points_redeemed(
+            &env,
+            issuer,
+            payment.merchant,
+            customer,
+            payment_id,
+            points_used,
+            actual_discount,
+        );
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
     }
 
-    // --- Stats Helpers (#70) ---
+    /// Merchant starts a loyalty coalition with its program and becomes its
+    /// owner. Cross-merchant redemptions and settlements are made in `token`.
+    pub fn create_loyalty_coalition(env: Env, merchant: Address, token: Address) -> u32 {
+        Self::require_not_paused(&env);
+        merchant.require_auth();
+        Self::require_token_allowed(&env, &token);
 
-    fn load_global_stats(env: &Env) -> GlobalStats {
-        env.storage()
-            .persistent()
-            .get(&DataKey::GlobalStats)
-            .unwrap_or(GlobalStats {
-                total_payments_created: 0,
-                total_payments_completed: 0,
-                total_payments_refunded: 0,
-                total_payments_expired: 0,
-                total_volume_completed: Map::new(env),
-                total_volume_refunded: Map::new(env),
-            })
-    }
+        let mut program = Self::load_loyalty_program(&env, &merchant);
+        if program.coalition_id != 0 {
+            panic_with_error!(&env, MerchantError::AlreadyInCoalition);
+        }
 
-    fn save_global_stats(env: &Env, stats: &GlobalStats) {
-        env.storage().persistent().set(&DataKey::GlobalStats, stats);
-        env.storage().persistent().extend_ttl(
-            &DataKey::GlobalStats,
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
+        let coalition_id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey3::CoalitionCounter)
+            .unwrap_or(0)
+            + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey3::CoalitionCounter, &coalition_id);
+        Self::save_coalition(
+            &env,
+            &LoyaltyCoalition {
+                id: coalition_id,
+                owner: merchant.clone(),
+                token: token.clone(),
+                members: vec![&env, merchant.clone()],
+            },
         );
+        program.coalition_id = coalition_id;
+        Self::save_loyalty_program(&env, &program);
+
+        events::emit_coalition_created(&env, coalition_id, merchant, token);
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        coalition_id
     }
 
-    fn load_merchant_stats(env: &Env, merchant: &Address) -> MerchantStats {
+    /// Coalition owner invites a merchant to join.
+    pub fn invite_to_coalition(env: Env, owner: Address, coalition_id: u32, merchant: Address) {
+        owner.require_auth();
+        let coalition = Self::load_coalition(&env, coalition_id);
+        if coalition.owner != owner {
+            panic_with_error!(&env, MerchantError::NotCoalitionOwner);
+        }
+        let key = DataKey3::CoalitionInvite(coalition_id, merchant);
+        env.storage().persistent().set(&key, &true);
         env.storage()
             .persistent()
-            .get(&DataKey::MerchantStats(merchant.clone()))
-            .unwrap_or(MerchantStats {
-                payments_created: 0,
-                payments_completed: 0,
-                payments_refunded: 0,
-                volume_completed: Map::new(env),
-            })
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
     }
 
-    fn save_merchant_stats(env: &Env, merchant: &Address, stats: &MerchantStats) {
-        let key = DataKey::MerchantStats(merchant.clone());
-        env.storage().persistent().set(&key, stats);
-        env.storage().persistent().extend_ttl(
-            &key,
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
-    }
+    /// Invited merchant joins the coalition with its loyalty program.
+    pub fn join_loyalty_coalition(env: Env, merchant: Address, coalition_id: u32) {
+        Self::require_not_paused(&env);
+        merchant.require_auth();
 
-    fn inc_global_created(env: &Env) {
-        let mut s = Self::load_global_stats(env);
-        s.total_payments_created += 1;
-        Self::save_global_stats(env, &s);
-    }
+        let mut coalition = Self::load_coalition(&env, coalition_id);
+        let invite_key = DataKey3::CoalitionInvite(coalition_id, merchant.clone());
+        if !env.storage().persistent().has(&invite_key) {
+            panic_with_error!(&env, MerchantError::CoalitionInviteNotFound);
+        }
+        let mut program = Self::load_loyalty_program(&env, &merchant);
+        if program.coalition_id != 0 {
+            panic_with_error!(&env, MerchantError::AlreadyInCoalition);
+        }
+        if coalition.members.len() >= MAX_COALITION_MEMBERS {
+            panic_with_error!(&env, MerchantError::CoalitionFull);
+        }
 
-    fn inc_global_completed(env: &Env, token: &Address, amount: i128) {
-        let mut s = Self::load_global_stats(env);
-        s.total_payments_completed += 1;
-        let prev = s.total_volume_completed.get(token.clone()).unwrap_or(0);
-        s.total_volume_completed.set(token.clone(), prev + amount);
-        Self::save_global_stats(env, &s);
-    }
+        env.storage().persistent().remove(&invite_key);
+        coalition.members.push_back(merchant.clone());
+        Self::save_coalition(&env, &coalition);
+        program.coalition_id = coalition_id;
+        Self::save_loyalty_program(&env, &program);
 
-    fn inc_global_refunded(env: &Env, token: &Address, amount: i128) {
-        let mut s = Self::load_global_stats(env);
-        s.total_payments_refunded += 1;
-        let prev = s.total_volume_refunded.get(token.clone()).unwrap_or(0);
-        s.total_volume_refunded.set(token.clone(), prev + amount);
-        Self::save_global_stats(env, &s);
+        events::emit_coalition_membership_changed(&env, coalition_id, merchant, true);
     }
 
-    fn inc_global_expired(env: &Env) {
-        let mut s = Self::load_global_stats(env);
-        s.total_payments_expired += 1;
-        Self::save_global_stats(env, &s);
+    /// Merchant leaves its coalition once it has settled everything it owes.
+    /// Debts owed to it stay settleable. If the owner leaves, ownership passes
+    /// to the longest-standing remaining member.
+    pub fn leave_loyalty_coalition(env: Env, merchant: Address) {
+        merchant.require_auth();
+
+        let mut program = Self::load_loyalty_program(&env, &merchant);
+        if program.coalition_id == 0 {
+            panic_with_error!(&env, MerchantError::NotCoalitionMember);
+        }
+        let coalition_id = program.coalition_id;
+        let owed: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey3::CoalitionOwed(coalition_id, merchant.clone()))
+            .unwrap_or(0);
+        if owed > 0 {
+            panic_with_error!(&env, MerchantError::CoalitionDebtOutstanding);
+        }
+
+        let mut coalition = Self::load_coalition(&env, coalition_id);
+        if let Some(index) = coalition.members.first_index_of(&merchant) {
+            coalition.members.remove(index);
+        }
+        if coalition.owner == merchant {
+            if let Some(next_owner) = coalition.members.first() {
+                coalition.owner = next_owner;
+            }
+        }
+        Self::save_coalition(&env, &coalition);
+        program.coalition_id = 0;
+        Self::save_loyalty_program(&env, &program);
+
+        events::emit_coalition_membership_changed(&env, coalition_id, merchant, false);
     }
 
-    fn inc_merchant_created(env: &Env, merchant: &Address) {
-        let mut s = Self::load_merchant_stats(env, merchant);
-        s.payments_created += 1;
-        Self::save_merchant_stats(env, merchant, &s);
+    /// Member deposits coalition token into its reserve, which backs
+    /// redemptions of its points at other members.
+    pub fn fund_coalition_reserve(env: Env, merchant: Address, coalition_id: u32, amount: i128) {
+        Self::require_not_paused(&env);
+        merchant.require_auth();
+        if amount <= 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+        let coalition = Self::load_coalition(&env, coalition_id);
+        if !coalition.members.contains(&merchant) {
+            panic_with_error!(&env, MerchantError::NotCoalitionMember);
+        }
 
-        // #226: Track pending count in MerchantSummary
-        let key = DataKey2::MerchantSummary(merchant.clone());
-        let mut summary: MerchantSummary =
-            env.storage()
-                .persistent()
-                .get(&key)
-                .unwrap_or(MerchantSummary {
-                    total_volume: 0,
-                    completed_count: 0,
-                    failed_count: 0,
-                    pending_count: 0,
-                    volume_by_token: Map::new(env),
-                });
-        summary.pending_count += 1;
-        env.storage().persistent().set(&key, &summary);
-        env.storage().persistent().extend_ttl(
-            &key,
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
+        token::Client::new(&env, &coalition.token).transfer(
+            &merchant,
+            env.current_contract_address(),
+            &amount,
+        );
+        let key = DataKey3::CoalitionReserve(coalition_id, merchant.clone());
+        let reserve = env.storage().persistent().get(&key).unwrap_or(0i128) + amount;
+        Self::set_coalition_reserve(&env, &key, reserve);
+
+        events::emit_coalition_reserve_changed(&env, coalition_id, merchant, amount, reserve);
+    }
+
+    /// Merchant takes back unspent reserve. Discounts already granted stay
+    /// held for the merchants that granted them.
+    pub fn withdraw_coalition_reserve(
+        env: Env,
+        merchant: Address,
+        coalition_id: u32,
+        amount: i128,
+    ) {
+        merchant.require_auth();
+        let key = DataKey3::CoalitionReserve(coalition_id, merchant.clone());
+        let reserve: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        if amount <= 0 || amount > reserve {
+            panic_with_error!(&env, MerchantError::InsufficientCoalitionReserve);
+        }
+        let coalition = Self::load_coalition(&env, coalition_id);
+
+        Self::set_coalition_reserve(&env, &key, reserve - amount);
+        token::Client::new(&env, &coalition.token).transfer(
+            &env.current_contract_address(),
+            &merchant,
+            &amount,
+        );
+
+        events::emit_coalition_reserve_changed(
+            &env,
+            coalition_id,
+            merchant,
+            -amount,
+            reserve - amount,
         );
     }
 
-    fn inc_merchant_completed(env: &Env, merchant: &Address, token: &Address, amount: i128) {
-        let mut s = Self::load_merchant_stats(env, merchant);
-        s.payments_completed += 1;
-        let prev = s.volume_completed.get(token.clone()).unwrap_or(0);
-        s.volume_completed.set(token.clone(), prev + amount);
-        Self::save_merchant_stats(env, merchant, &s);
+    /// Returns a member's unspent coalition reserve.
+    pub fn get_coalition_reserve(env: Env, coalition_id: u32, merchant: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey3::CoalitionReserve(coalition_id, merchant))
+            .unwrap_or(0)
+    }
 
-        // #226: Update MerchantSummary
-        let key = DataKey2::MerchantSummary(merchant.clone());
-        let mut summary: MerchantSummary =
-            env.storage()
-                .persistent()
-                .get(&key)
-                .unwrap_or(MerchantSummary {
-                    total_volume: 0,
-                    completed_count: 0,
-                    failed_count: 0,
-                    pending_count: 0,
-                    volume_by_token: Map::new(env),
-                });
-        summary.completed_count += 1;
-        if summary.pending_count > 0 {
-            summary.pending_count -= 1;
+    /// Pays `creditor` everything `debtor` owes it in a coalition from the
+    /// reserve held at redemption time. Callable by anyone. Returns the amount
+    /// paid.
+    pub fn settle_coalition_debt(
+        env: Env,
+        debtor: Address,
+        coalition_id: u32,
+        creditor: Address,
+    ) -> i128 {
+        Self::require_not_paused(&env);
+
+        let coalition = Self::load_coalition(&env, coalition_id);
+        let debt_key = DataKey3::CoalitionDebt(coalition_id, debtor.clone(), creditor.clone());
+        let amount: i128 = env.storage().persistent().get(&debt_key).unwrap_or(0);
+        if amount <= 0 {
+            panic_with_error!(&env, MerchantError::NoCoalitionDebt);
         }
-        summary.total_volume += amount;
-        let prev_token = summary.volume_by_token.get(token.clone()).unwrap_or(0);
-        summary
-            .volume_by_token
-            .set(token.clone(), prev_token + amount);
-        env.storage().persistent().set(&key, &summary);
-        env.storage().persistent().extend_ttl(
-            &key,
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
-    }
 
-    fn inc_merchant_failed(env: &Env, merchant: &Address) {
-        let key = DataKey2::MerchantSummary(merchant.clone());
-        let mut summary: MerchantSummary =
-            env.storage()
-                .persistent()
-                .get(&key)
-                .unwrap_or(MerchantSummary {
-                    total_volume: 0,
-                    completed_count: 0,
-                    failed_count: 0,
-                    pending_count: 0,
-                    volume_by_token: Map::new(env),
-                });
-        summary.failed_count += 1;
-        if summary.pending_count > 0 {
-            summary.pending_count -= 1;
+        env.storage().persistent().remove(&debt_key);
+        let owed_key = DataKey3::CoalitionOwed(coalition_id, debtor.clone());
+        let owed: i128 = env.storage().persistent().get(&owed_key).unwrap_or(0);
+        if owed > amount {
+            env.storage().persistent().set(&owed_key, &(owed - amount));
+        } else {
+            env.storage().persistent().remove(&owed_key);
         }
-        env.storage().persistent().set(&key, &summary);
-        env.storage().persistent().extend_ttl(
-            &key,
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
+        token::Client::new(&env, &coalition.token).transfer(
+            &env.current_contract_address(),
+            &creditor,
+            &amount,
         );
+
+        events::emit_coalition_debt_settled(&env, coalition_id, debtor, creditor, amount);
+        amount
     }
 
-    fn inc_merchant_refunded(env: &Env, merchant: &Address, token: &Address, amount: i128) {
-        let mut s = Self::load_merchant_stats(env, merchant);
-        s.payments_refunded += 1;
-        let _ = (token, amount); // volume tracked globally; merchant count only
-        Self::save_merchant_stats(env, merchant, &s);
+    /// Returns a loyalty coalition.
+    pub fn get_coalition(env: Env, coalition_id: u32) -> LoyaltyCoalition {
+        Self::load_coalition(&env, coalition_id)
     }
 
-    fn inc_volume_bucket(env: &Env, token: &Address, amount: i128) {
-        let bucket = env.ledger().sequence() / LEDGER_BUCKET_SIZE;
-        let key = DataKey::VolumeBucket(token.clone(), bucket);
-        let prev: i128 = env.storage().persistent().get(&key).unwrap_or(0);
-        env.storage().persistent().set(&key, &(prev + amount));
-        env.storage().persistent().extend_ttl(
-            &key,
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
+    /// Returns what `debtor` owes `creditor` in a coalition, unsettled.
+    pub fn get_coalition_debt(
+        env: Env,
+        coalition_id: u32,
+        debtor: Address,
+        creditor: Address,
+    ) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey3::CoalitionDebt(coalition_id, debtor, creditor))
+            .unwrap_or(0)
     }
 
-    fn inc_merchant_volume_bucket(env: &Env, merchant: &Address, amount: i128) {
-        let bucket = env.ledger().sequence() / LEDGER_BUCKET_SIZE;
-        let key = DataKey::MerchantVolumeBucket(merchant.clone(), bucket);
-        let prev: i128 = env.storage().persistent().get(&key).unwrap_or(0);
-        env.storage().persistent().set(&key, &(prev + amount));
+    // =========================================================================
+    // #242: Merchant Referral Commission Tracking
+    // =========================================================================
+    // #235: Merchant-Level Customer Spending Limits
+    // =========================================================================
+
+    /// Merchant sets a per-customer spend cap within a rolling window.
+    pub fn set_customer_spend_limit(
+        env: Env,
+        merchant: Address,
+        customer: Address,
+        amount: i128,
+        window_seconds: u64,
+    ) {
+        Self::require_not_paused(&env);
+        merchant.require_auth();
+        if amount <= 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+        if window_seconds == 0 {
+            panic_with_error!(&env, ConfigError::InvalidWindow);
+        }
+        let key = DataKey2::CustomerSpendLimit(merchant.clone(), customer.clone());
+        let limit = SpendLimit {
+            amount,
+            window_seconds,
+        };
+        env.storage().persistent().set(&key, &limit);
         env.storage().persistent().extend_ttl(
             &key,
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        events::emit_customer_spend_limit_set(&env, merchant, customer, amount, window_seconds);
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
     }
 
-    fn next_payment_id(env: &Env) -> u32 {
-        let mut counter: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::PaymentCounter)
-            .unwrap_or(0);
-        let id = counter;
-        counter += 1;
-        // Counter stays in instance storage — bounded, config-like
+    /// Merchant removes a per-customer spend cap.
+    pub fn remove_customer_spend_limit(env: Env, merchant: Address, customer: Address) {
+        Self::require_not_paused(&env);
+        merchant.require_auth();
+        let key = DataKey2::CustomerSpendLimit(merchant.clone(), customer.clone());
+        env.storage().persistent().remove(&key);
+        events::emit_customer_spend_limit_removed(&env, merchant, customer);
         env.storage()
             .instance()
-            .set(&DataKey::PaymentCounter, &counter);
-        id
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
     }
 
-    fn add_customer_payment(env: &Env, customer: &Address, payment_id: u32) {
-        let key = DataKey::CustomerPayments(customer.clone());
-        let mut customer_payments: Vec<u32> = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or(Vec::new(env));
-        customer_payments.push_back(payment_id);
-        // Persistent: customer index grows with payment volume
-        env.storage().persistent().set(&key, &customer_payments);
+    /// Merchant sets a global default spend cap applied to all customers without an individual override.
+    pub fn set_default_spend_limit(env: Env, merchant: Address, amount: i128, window_seconds: u64) {
+        Self::require_not_paused(&env);
+        merchant.require_auth();
+        if amount <= 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+        if window_seconds == 0 {
+            panic_with_error!(&env, ConfigError::InvalidWindow);
+        }
+        let key = DataKey2::DefaultSpendLimit(merchant.clone());
+        let limit = SpendLimit {
+            amount,
+            window_seconds,
+        };
+        env.storage().persistent().set(&key, &limit);
         env.storage().persistent().extend_ttl(
             &key,
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
-    }
-
-    fn get_or_init_version(env: &Env) -> u32 {
-        if let Some(version) = env
-            .storage()
+        events::emit_default_spend_limit_set(&env, merchant, amount, window_seconds);
+        env.storage()
             .instance()
-            .get::<DataKey, u32>(&DataKey::ContractVersion)
-        {
-            version
-        } else {
-            let initial_version = 1u32;
-            env.storage()
-                .instance()
-                .set(&DataKey::ContractVersion, &initial_version);
-            initial_version
-        }
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
     }
 
-    fn get_rate_limit_config_internal(env: &Env) -> RateLimitConfig {
+    /// Get the per-customer spend limit for a merchant+customer pair.
+    pub fn get_customer_spend_limit(
+        env: Env,
+        merchant: Address,
+        customer: Address,
+    ) -> Option<SpendLimit> {
         env.storage()
-            .instance()
-            .get(&DataKey::RateLimitConfig)
-            .unwrap_or(RateLimitConfig {
-                max_payments: DEFAULT_RATE_LIMIT_MAX_PAYMENTS,
-                window_size_ledgers: DEFAULT_RATE_LIMIT_WINDOW_SIZE_LEDGERS,
-            })
+            .persistent()
+            .get(&DataKey2::CustomerSpendLimit(merchant, customer))
     }
 
-    /// Returns the configured minimum collateral, falling back to the default (#129).
-    fn get_min_collateral_internal(env: &Env) -> i128 {
+    /// Get the default spend limit for a merchant.
+    pub fn get_default_spend_limit(env: Env, merchant: Address) -> Option<SpendLimit> {
         env.storage()
-            .instance()
-            .get(&DataKey::MinCollateral)
-            .unwrap_or(DEFAULT_MIN_COLLATERAL)
+            .persistent()
+            .get(&DataKey2::DefaultSpendLimit(merchant))
     }
 
-    // --- Withdrawal Queue Helpers (#126) ---
+    // ─── #358: Buyer Trust Tier System ───────────────────────────────────────
 
-    /// Enqueue a completed payment into the merchant's withdrawal queue.
-    fn enqueue_withdrawal(env: &Env, merchant: &Address, payment_id: u32, amount: i128) {
-        let key = DataKey::WithdrawalQueue(merchant.clone());
-        let mut queue: Vec<(u32, i128)> = env
+    /// Merchant assigns a trust tier to a buyer (New/Standard/Trusted/VIP).
+    /// Only the merchant may call this.
+    pub fn set_buyer_tier(
+        env: Env,
+        merchant: Address,
+        buyer: Address,
+        tier: BuyerTrustTierLevel,
+    ) {
+        Self::require_not_paused(&env);
+        merchant.require_auth();
+        let key = DataKey3::BuyerTrustTier(merchant.clone(), buyer.clone());
+        let old_tier: u32 = env
             .storage()
             .persistent()
-            .get(&key)
-            .unwrap_or(Vec::new(env));
-        queue.push_back((payment_id, amount));
-        env.storage().persistent().set(&key, &queue);
-        env.storage().persistent().extend_ttl(
-            &key,
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
-        events::emit_withdrawal_queued(env, merchant.clone(), payment_id, amount);
+            .get::<_, BuyerTrustTierLevel>(&key)
+            .map(|t| t as u32)
+            .unwrap_or(BuyerTrustTierLevel::New as u32);
+        env.storage().persistent().set(&key, &tier);
+        env.storage().persistent().extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+        events::emit_buyer_tier_updated(&env, merchant, buyer, old_tier, tier as u32);
+        env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
     }
 
-    /// Get the merchant's withdrawal queue.
-    fn get_withdrawal_queue(env: &Env, merchant: &Address) -> Vec<(u32, i128)> {
-        let key = DataKey::WithdrawalQueue(merchant.clone());
+    /// Returns the trust tier assigned to a buyer by a specific merchant.
+    pub fn get_buyer_tier(env: Env, merchant: Address, buyer: Address) -> BuyerTrustTierLevel {
         env.storage()
             .persistent()
-            .get(&key)
-            .unwrap_or(Vec::new(env))
+            .get(&DataKey3::BuyerTrustTier(merchant, buyer))
+            .unwrap_or(BuyerTrustTierLevel::New)
     }
 
-    /// Set the merchant's withdrawal queue.
-    fn set_withdrawal_queue(env: &Env, merchant: &Address, queue: Vec<(u32, i128)>) {
-        let key = DataKey::WithdrawalQueue(merchant.clone());
-        env.storage().persistent().set(&key, &queue);
-        env.storage().persistent().extend_ttl(
-            &key,
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
+    /// Merchant sets the spending limit for a specific trust tier.
+    /// tier_value: 0=New, 1=Standard, 2=Trusted, 3=VIP.
+    pub fn set_tier_spending_limit(
+        env: Env,
+        merchant: Address,
+        tier: BuyerTrustTierLevel,
+        amount: i128,
+        window_seconds: u64,
+    ) {
+        Self::require_not_paused(&env);
+        merchant.require_auth();
+        if amount <= 0 { panic_with_error!(&env, Error::InvalidAmount); }
+        if window_seconds == 0 { panic_with_error!(&env, ConfigError::InvalidWindow); }
+        let key = DataKey3::TierSpendingLimit(merchant.clone(), tier as u32);
+        let limit = SpendLimit { amount, window_seconds };
+        env.storage().persistent().set(&key, &limit);
+        env.storage().persistent().extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+        env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
     }
 
-    /// Returns the global slippage config, falling back to defaults (#135).
-    fn get_slippage_config_internal(env: &Env) -> SlippageConfig {
-        env.storage()
-            .instance()
-            .get(&DataKey::SlippageConfig)
-            .unwrap_or(SlippageConfig {
-                default_bps: DEFAULT_SLIPPAGE_BPS,
-                min_bps: DEFAULT_MIN_SLIPPAGE_BPS,
-                max_bps: DEFAULT_MAX_SLIPPAGE_BPS,
-            })
+    /// Returns the spending limit configured for a trust tier by a merchant, if any.
+    pub fn get_tier_spending_limit(
+        env: Env,
+        merchant: Address,
+        tier: BuyerTrustTierLevel,
+    ) -> Option<SpendLimit> {
+        env.storage().persistent().get(&DataKey3::TierSpendingLimit(merchant, tier as u32))
     }
 
-    /// Check merchant volume cap and update window volume. Panics if cap would be exceeded (#131).
-    fn check_and_update_merchant_volume_cap(
+    /// Check and update the customer spend window. Panics with CustomerSpendLimitExceeded if cap would be breached.
+    fn check_and_update_customer_spend_limit(
         env: &Env,
-        payment_id: u32,
         merchant: &Address,
+        customer: &Address,
         amount: i128,
     ) {
-        let cap_key = DataKey::VolumeCap(merchant.clone());
-        let cap: VolumeCap = match env.storage().persistent().get(&cap_key) {
-            Some(c) => c,
-            None => return, // No cap configured — no restriction
+        // Priority: per-customer override → tier-based limit → global default (#358)
+        let limit_opt: Option<SpendLimit> = env
+            .storage()
+            .persistent()
+            .get(&DataKey2::CustomerSpendLimit(merchant.clone(), customer.clone()))
+            .or_else(|| {
+                // #358: check tier-based limit
+                let tier: BuyerTrustTierLevel = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey3::BuyerTrustTier(merchant.clone(), customer.clone()))
+                    .unwrap_or(BuyerTrustTierLevel::New);
+                env.storage()
+                    .persistent()
+                    .get(&DataKey3::TierSpendingLimit(merchant.clone(), tier as u32))
+            })
+            .or_else(|| {
+                env.storage()
+                    .persistent()
+                    .get(&DataKey2::DefaultSpendLimit(merchant.clone()))
+            });
+
+        let limit = match limit_opt {
+            Some(l) => l,
+            None => return, // No limit configured
         };
 
+        let state_key = DataKey2::CustomerSpendWindowState(merchant.clone(), customer.clone());
         let now = env.ledger().timestamp();
-        let bucket = now / cap.window_seconds;
-        let vol_key = DataKey::MerchantWindowVolume(merchant.clone(), bucket);
-        let current_volume: i128 = env.storage().persistent().get(&vol_key).unwrap_or(0);
-        let new_volume = current_volume.checked_add(amount).expect("Volume overflow");
 
-        if new_volume > cap.cap_amount {
-            events::emit_volume_capped(
+        let mut state: CustomerSpendWindow =
+            env.storage()
+                .persistent()
+                .get(&state_key)
+                .unwrap_or(CustomerSpendWindow {
+                    window_start: now,
+                    spent: 0,
+                });
+
+        // Reset window if expired
+        if now >= state.window_start + limit.window_seconds {
+            state = CustomerSpendWindow {
+                window_start: now,
+                spent: 0,
+            };
+        }
+
+        let new_total = state.spent
+            .checked_add(amount)
+            .unwrap_or_else(|| panic_with_error!(env, Error::ArithmeticOverflow));
+        if new_total > limit.amount {
+            events::emit_customer_spend_limit_exceeded(
                 env,
                 merchant.clone(),
-                payment_id,
-                current_volume,
-                cap.cap_amount,
+                customer.clone(),
+                new_total,
+                limit.amount,
             );
-            panic_with_error!(env, Error::MerchantVolumeCapped);
+            panic_with_error!(env, Error::CustomerSpendLimitExceeded);
         }
 
-        env.storage().persistent().set(&vol_key, &new_volume);
+        state.spent = new_total;
+        env.storage().persistent().set(&state_key, &state);
         env.storage().persistent().extend_ttl(
-            &vol_key,
+            &state_key,
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
     }
 
-    // --- Merchant Ban Management & On-Chain Appeal ---
-
-    /// Admin sets the appeal rejection cooldown in seconds.
-    pub fn set_appeal_rejection_cooldown(env: Env, admin: Address, seconds: u64) {
-        Self::require_not_paused(&env);
-        admin.require_auth();
-        let stored_admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Not initialized");
-        if admin != stored_admin {
-            panic!("Only admin can configure appeal cooldown");
-        }
-        env.storage()
-            .instance()
-            .set(&DataKey2::AppealRejectionCooldownSeconds, &seconds);
-        env.storage()
-            .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-    }
+    // ─── #216: Recurring Invoice Scheduling ──────────────────────────────────
 
-    /// Admin suspends a merchant for a given duration (seconds). Payments are paused.
-    pub fn suspend_merchant(
+    /// Merchant creates a recurring invoice schedule.
+    /// `max_cycles` = 0 means unlimited.
+    pub fn create_recurring_invoice(
         env: Env,
-        admin: Address,
         merchant: Address,
-        reason_hash: BytesN<32>,
-        duration_seconds: u64,
-    ) {
+        customer: Address,
+        amount: i128,
+        token: Address,
+        interval_seconds: u64,
+        max_cycles: u32,
+        reference_hash: Option<BytesN<32>>,
+    ) -> u32 {
         Self::require_not_paused(&env);
-        admin.require_auth();
-        let stored_admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Not initialized");
-        if admin != stored_admin {
-            panic!("Only admin can suspend merchants");
+        merchant.require_auth();
+
+        if amount <= 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
         }
-        if duration_seconds == 0 {
-            panic!("Suspension duration must be positive");
+        if interval_seconds == 0 {
+            panic_with_error!(&env, SubscriptionError::InvalidInterval);
         }
 
-        let expiry = env.ledger().timestamp() + duration_seconds;
-        env.storage().persistent().set(
-            &DataKey2::MerchantStatus(merchant.clone()),
-            &MerchantStatus::Suspended,
-        );
-        env.storage().persistent().set(
-            &DataKey2::MerchantSuspensionExpiry(merchant.clone()),
-            &expiry,
-        );
-        env.storage().persistent().extend_ttl(
-            &DataKey2::MerchantStatus(merchant.clone()),
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
-        env.storage().persistent().extend_ttl(
-            &DataKey2::MerchantSuspensionExpiry(merchant.clone()),
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
-
-        events::emit_merchant_suspended(&env, merchant, reason_hash, expiry);
-
-        env.storage()
-            .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-    }
+        Self::require_token_allowed(&env, &token);
+        Self::require_merchant_approved(&env, &merchant);
 
-    /// Admin permanently bans a merchant. Banned merchants cannot create or receive payments.
-    pub fn ban_merchant(env: Env, admin: Address, merchant: Address, reason_hash: BytesN<32>) {
-        Self::require_not_paused(&env);
-        admin.require_auth();
-        let stored_admin: Address = env
+        let mut counter: u32 = env
             .storage()
             .instance()
-            .get(&DataKey::Admin)
-            .expect("Not initialized");
-        if admin != stored_admin {
-            panic!("Only admin can ban merchants");
-        }
-
-        env.storage().persistent().set(
-            &DataKey2::MerchantStatus(merchant.clone()),
-            &MerchantStatus::Banned,
-        );
-        env.storage()
-            .persistent()
-            .set(&DataKey::MerchantApproved(merchant.clone()), &false);
-        env.storage().persistent().extend_ttl(
-            &DataKey2::MerchantStatus(merchant.clone()),
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
-
-        events::emit_merchant_banned(&env, merchant, reason_hash);
-
+            .get(&DataKey2::RecurringInvoiceCounter)
+            .unwrap_or(0);
+        let invoice_id = counter;
+        counter += 1;
         env.storage()
             .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-    }
-
-    /// Admin reinstates a merchant (clears ban/suspension, restores Active status).
-    pub fn reinstate_merchant(env: Env, admin: Address, merchant: Address) {
-        Self::require_not_paused(&env);
-        admin.require_auth();
-        let stored_admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Not initialized");
-        if admin != stored_admin {
-            panic!("Only admin can reinstate merchants");
-        }
+            .set(&DataKey2::RecurringInvoiceCounter, &counter);
+
+        let now = env.ledger().timestamp();
+        let invoice = RecurringInvoice {
+            id: invoice_id,
+            merchant: merchant.clone(),
+            customer: customer.clone(),
+            amount,
+            token,
+            interval_seconds,
+            max_cycles,
+            cycles_triggered: 0,
+            next_due_at: now,
+            reference_hash,
+            active: true,
+        };
 
-        env.storage().persistent().set(
-            &DataKey2::MerchantStatus(merchant.clone()),
-            &MerchantStatus::Active,
-        );
         env.storage()
             .persistent()
-            .set(&DataKey::MerchantApproved(merchant.clone()), &true);
+            .set(&DataKey2::RecurringInvoice(invoice_id), &invoice);
         env.storage().persistent().extend_ttl(
-            &DataKey2::MerchantStatus(merchant.clone()),
+            &DataKey2::RecurringInvoice(invoice_id),
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
 
+        events::emit_recurring_invoice_created(&env, invoice_id, merchant, customer, amount);
+
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        invoice_id
     }
 
-    /// Banned merchant submits an appeal. Only one active appeal per merchant.
-    pub fn submit_appeal(env: Env, merchant: Address, reason_hash: BytesN<32>) {
+    /// Trigger the next invoice cycle, creating a standard Payment entry.
+    /// Callable by anyone (merchant, keeper, etc.) when the interval has elapsed;
+    /// `keeper` receives the keeper bounty. Returns the new payment_id.
+    pub fn trigger_invoice_cycle(env: Env, keeper: Address, invoice_id: u32) -> u32 {
         Self::require_not_paused(&env);
-        merchant.require_auth();
 
-        let status: MerchantStatus = env
+        let mut invoice: RecurringInvoice = env
             .storage()
             .persistent()
-            .get(&DataKey2::MerchantStatus(merchant.clone()))
-            .unwrap_or(MerchantStatus::Active);
+            .get(&DataKey2::RecurringInvoice(invoice_id))
+            .unwrap_or_else(|| {
+                panic_with_error!(&env, SubscriptionError::RecurringInvoiceNotFound)
+            });
 
-        if status != MerchantStatus::Banned {
-            panic!("Only banned merchants can submit an appeal");
+        if !invoice.active {
+            panic_with_error!(&env, SubscriptionError::RecurringInvoiceCancelled);
         }
 
-        // Enforce one-active-appeal guard
-        let existing_opt: Option<MerchantAppeal> = env
-            .storage()
-            .persistent()
-            .get(&DataKey2::MerchantAppeal(merchant.clone()));
-        if let Some(existing) = existing_opt {
-            if existing.status == AppealStatus::Pending || existing.status == AppealStatus::ApprovedCoolingOff {
-                panic!("An active appeal already exists");
-            }
+        let now = env.ledger().timestamp();
+        if now < invoice.next_due_at {
+            panic_with_error!(&env, SubscriptionError::IntervalNotElapsed);
         }
 
-        // Enforce cooldown after rejection
-        let cooldown_until: u64 = env
-            .storage()
-            .persistent()
-            .get(&DataKey2::AppealCooldownUntil(merchant.clone()))
-            .unwrap_or(0);
-        if env.ledger().timestamp() < cooldown_until {
-            panic!("Appeal cooldown has not elapsed");
+        if invoice.max_cycles > 0 && invoice.cycles_triggered >= invoice.max_cycles {
+            panic_with_error!(&env, SubscriptionError::MaxChargesReached);
         }
 
-        let appeal = MerchantAppeal {
-            merchant: merchant.clone(),
-            reason_hash: reason_hash.clone(),
-            submitted_at: env.ledger().timestamp(),
-            status: AppealStatus::Pending,
-            cooling_off_until: 0,
-        };
+        // Create a standard Payment entry (funds transferred from customer)
+        let payment_id = Self::create_payment_with_options(
+            env.clone(),
+            invoice.customer.clone(),
+            invoice.merchant.clone(),
+            invoice.amount,
+            invoice.token.clone(),
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+
+        invoice.cycles_triggered += 1;
+        invoice.next_due_at = now + invoice.interval_seconds;
+
+        // Auto-complete if max_cycles reached
+        if invoice.max_cycles > 0 && invoice.cycles_triggered >= invoice.max_cycles {
+            invoice.active = false;
+            events::emit_recurring_invoice_completed(&env, invoice_id);
+        }
 
         env.storage()
             .persistent()
-            .set(&DataKey2::MerchantAppeal(merchant.clone()), &appeal);
+            .set(&DataKey2::RecurringInvoice(invoice_id), &invoice);
         env.storage().persistent().extend_ttl(
-            &DataKey2::MerchantAppeal(merchant.clone()),
+            &DataKey2::RecurringInvoice(invoice_id),
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        events::emit_appeal_submitted(&env, merchant, reason_hash);
-
+        events::emit_invoice_cycle_triggered(
+            &env,
+            invoice_id,
+            payment_id,
+            invoice.cycles_triggered,
+        );
+        Self::pay_keeper_reward(&env, &keeper, 1);
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        payment_id
     }
 
-    /// Admin approves an appeal — merchant enters cooling-off period before full reinstatement.
-    pub fn approve_appeal(env: Env, admin: Address, merchant: Address) {
-        Self::require_not_paused(&env);
-        admin.require_auth();
-        let stored_admin: Address = env
+    /// Returns a customer's points per program: the platform-wide program
+    /// (after its expiry check) and every merchant program they have earned
+    /// in, excluding expired lots.
+    pub fn get_loyalty_balance(env: Env, customer: Address) -> LoyaltyBalance {
+        Self::maybe_expire_points(&env, &customer);
+        let platform_points: i128 = env
             .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Not initialized");
-        if admin != stored_admin {
-            panic!("Only admin can approve appeals");
-        }
+            .persistent()
+            .get(&DataKey2::LoyaltyBalance(customer.clone()))
+            .unwrap_or(0);
 
-        let mut appeal: MerchantAppeal = env
+        let now = env.ledger().sequence();
+        let merchants: Vec<Address> = env
             .storage()
             .persistent()
-            .get(&DataKey2::MerchantAppeal(merchant.clone()))
-            .expect("No appeal found");
+            .get(&DataKey3::CustomerPrograms(customer.clone()))
+            .unwrap_or(Vec::new(&env));
+        let mut programs = Vec::new(&env);
+        for merchant in merchants.iter() {
+            let program = Self::load_loyalty_program(&env, &merchant);
+            let mut points = 0i128;
+            let mut next_expiry_ledger = 0u32;
+            let mut expiring_points = 0i128;
+            for lot in Self::load_point_lots(&env, &customer, &merchant).iter() {
+                if lot.expires_at_ledger != 0 && now >= lot.expires_at_ledger {
+                    continue;
+                }
+                points += lot.points;
+                if lot.expires_at_ledger != 0 && next_expiry_ledger == 0 {
+                    // Lots are kept in expiry order
+                    next_expiry_ledger = lot.expires_at_ledger;
+                    expiring_points = lot.points;
+                }
+            }
+            let rolling_spend = Self::rolling_spend(&env, &program, &customer);
+            programs.push_back(LoyaltyProgramBalance {
+                merchant,
+                points,
+                tier: Self::loyalty_tier(&program, rolling_spend),
+                rolling_spend,
+                next_expiry_ledger,
+                expiring_points,
+                coalition_id: program.coalition_id,
+            });
+        }
 
-        if appeal.status != AppealStatus::Pending {
-            panic!("Appeal already resolved or not pending");
+        LoyaltyBalance {
+            platform_points,
+            programs,
         }
+    }
 
-        // Set cooling-off period (default: 7 days in seconds)
-        let cooling_off_period: u64 = env
+    /// Internal: mint points to customer after a completed payment.
+    fn accrue_loyalty_points(env: &Env, payment_id: u32, customer: &Address, payment_amount: i128) {
+        let points_per_unit: u32 = match env
             .storage()
             .instance()
-            .get(&DataKey2::AppealRejectionCooldownSeconds)
-            .unwrap_or(7 * 24 * 60 * 60); // Default 7 days
-        let cooling_off_until = env.ledger().timestamp() + cooling_off_period;
-
-        appeal.status = AppealStatus::ApprovedCoolingOff;
-        appeal.cooling_off_until = cooling_off_until;
-        env.storage()
-            .persistent()
-            .set(&DataKey2::MerchantAppeal(merchant.clone()), &appeal);
-        env.storage().persistent().extend_ttl(
-            &DataKey2::MerchantAppeal(merchant.clone()),
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
-
-        events::emit_appeal_approved(&env, merchant, cooling_off_until);
+            .get(&DataKey2::LoyaltyPointsPerUnit)
+        {
+            Some(v) => v,
+            None => return, // loyalty not configured
+        };
+        if points_per_unit == 0 {
+            return;
+        }
 
-        env.storage()
-            .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-    }
+        // Expire stale points before accruing
+        Self::maybe_expire_points(env, customer);
 
-    /// Complete reinstatement after cooling-off period has elapsed. Callable by anyone.
-    pub fn complete_reinstatement(env: Env, merchant: Address) {
-        Self::require_not_paused(&env);
+        let points_earned = payment_amount * points_per_unit as i128 / 1_000_000;
+        if points_earned <= 0 {
+            return;
+        }
 
-        let appeal: MerchantAppeal = env
+        let old_balance: i128 = env
             .storage()
             .persistent()
-            .get(&DataKey2::MerchantAppeal(merchant.clone()))
-            .expect("No appeal found");
-
-        if appeal.status != AppealStatus::ApprovedCoolingOff {
-            panic!("Appeal is not in cooling-off period");
-        }
-
-        let now = env.ledger().timestamp();
-        if now < appeal.cooling_off_until {
-            panic!("Cooling-off period has not elapsed");
-        }
+            .get(&DataKey2::LoyaltyBalance(customer.clone()))
+            .unwrap_or(0);
+        let new_balance = old_balance + points_earned;
 
-        // Update appeal status
-        let mut updated_appeal = appeal;
-        updated_appeal.status = AppealStatus::ApprovedReinstated;
         env.storage()
             .persistent()
-            .set(&DataKey2::MerchantAppeal(merchant.clone()), &updated_appeal);
+            .set(&DataKey2::LoyaltyBalance(customer.clone()), &new_balance);
         env.storage().persistent().extend_ttl(
-            &DataKey2::MerchantAppeal(merchant.clone()),
+            &DataKey2::LoyaltyBalance(customer.clone()),
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
-
-        // Reinstate merchant
         env.storage().persistent().set(
-            &DataKey2::MerchantStatus(merchant.clone()),
-            &MerchantStatus::Active,
+            &DataKey2::LoyaltyLastAccrualLedger(customer.clone()),
+            &env.ledger().sequence(),
         );
-        env.storage()
+        env.storage().persistent().extend_ttl(
+            &DataKey2::LoyaltyLastAccrualLedger(customer.clone()),
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        events::emit_points_accrued(
+            env,
+            customer.clone(),
+            payment_id,
+            points_earned,
+            new_balance,
+        );
+    }
+
+    /// Internal: record spend and mint a point lot in the merchant's program,
+    /// at the multiplier of the tier reached before this payment.
+    fn accrue_program_points(
+        env: &Env,
+        payment_id: u32,
+        merchant: &Address,
+        customer: &Address,
+        payment_amount: i128,
+    ) {
+        let program: LoyaltyProgram = match env
+            .storage()
             .persistent()
-            .set(&DataKey::MerchantApproved(merchant.clone()), &true);
+            .get(&DataKey3::LoyaltyProgram(merchant.clone()))
+        {
+            Some(p) => p,
+            None => return, // merchant runs no program
+        };
+
+        let programs_key = DataKey3::CustomerPrograms(customer.clone());
+        let mut customer_programs: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&programs_key)
+            .unwrap_or(Vec::new(env));
+        if !customer_programs.contains(merchant) {
+            customer_programs.push_back(merchant.clone());
+            env.storage().persistent().set(&programs_key, &customer_programs);
+        }
         env.storage().persistent().extend_ttl(
-            &DataKey2::MerchantStatus(merchant.clone()),
+            &programs_key,
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        events::emit_merchant_reinstated(&env, merchant, now);
+        let old_tier = Self::loyalty_tier(&program, Self::rolling_spend(env, &program, customer));
+        Self::record_loyalty_spend(env, &program, customer, payment_amount);
+        let new_tier = Self::loyalty_tier(&program, Self::rolling_spend(env, &program, customer));
+        if new_tier != old_tier {
+            events::emit_loyalty_tier_changed(
+                env,
+                merchant.clone(),
+                customer.clone(),
+                old_tier,
+                new_tier,
+            );
+        }
+
+        let multiplier_bps = if old_tier == 0 {
+            10_000
+        } else {
+            program.tiers.get(old_tier - 1).unwrap().multiplier_bps
+        };
+        let points_earned = payment_amount * program.points_per_unit as i128 * multiplier_bps as i128
+            / 10_000_000_000;
+        if points_earned <= 0 {
+            return;
+        }
+
+        let now = env.ledger().sequence();
+        let expires_at_ledger = if program.lot_expiry_ledgers == 0 {
+            0
+        } else {
+            // Points earned in the same slice share one lot
+            let slice = (program.lot_expiry_ledgers / LOYALTY_WINDOW_SLICES).max(1);
+            ((now / slice).saturating_add(1))
+                .saturating_mul(slice)
+                .saturating_add(program.lot_expiry_ledgers)
+        };
+        let order = |expires_at: u32| if expires_at == 0 { u32::MAX } else { expires_at };
+
+        let mut lots = Self::prune_point_lots(env, customer, merchant);
+        let mut insert_at = lots.len();
+        let mut merged = false;
+        for (i, lot) in lots.iter().enumerate() {
+            if lot.expires_at_ledger == expires_at_ledger {
+                lots.set(
+                    i as u32,
+                    PointLot {
+                        points: lot.points + points_earned,
+                        expires_at_ledger,
+                    },
+                );
+                merged = true;
+                break;
+            }
+            if order(lot.expires_at_ledger) > order(expires_at_ledger) {
+                insert_at = i as u32;
+                break;
+            }
+        }
+        if !merged {
+            lots.insert(
+                insert_at,
+                PointLot {
+                    points: points_earned,
+                    expires_at_ledger,
+                },
+            );
+        }
+        Self::save_point_lots(env, customer, merchant, &lots);
+
+        events::emit_program_points_accrued(
+            env,
+            merchant.clone(),
+            customer.clone(),
+            payment_id,
+            points_earned,
+            old_tier,
+        );
     }
 
-    /// Admin rejects an appeal — merchant remains banned and cooldown is applied.
-    pub fn reject_appeal(env: Env, admin: Address, merchant: Address) {
-        Self::require_not_paused(&env);
-        admin.require_auth();
-        let stored_admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Not initialized");
-        if admin != stored_admin {
-            panic!("Only admin can reject appeals");
+    /// Internal: add spend to the customer's current tier-window slice,
+    /// dropping slices that have rolled out of the window.
+    fn record_loyalty_spend(env: &Env, program: &LoyaltyProgram, customer: &Address, amount: i128) {
+        if program.tier_window_ledgers == 0 {
+            return;
         }
+        let now = env.ledger().sequence();
+        let slice = (program.tier_window_ledgers / LOYALTY_WINDOW_SLICES).max(1);
+        let start_ledger = now / slice * slice;
 
-        let mut appeal: MerchantAppeal = env
+        let key = DataKey3::LoyaltySpend(customer.clone(), program.merchant.clone());
+        let buckets: Vec<SpendBucket> = env
             .storage()
             .persistent()
-            .get(&DataKey2::MerchantAppeal(merchant.clone()))
-            .expect("No appeal found");
-
-        if appeal.status != AppealStatus::Pending {
-            panic!("Appeal already resolved or not pending");
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        let mut kept = Vec::new(env);
+        for bucket in buckets.iter() {
+            if bucket.start_ledger.saturating_add(program.tier_window_ledgers) > now {
+                kept.push_back(bucket);
+            }
         }
-
-        appeal.status = AppealStatus::Rejected;
+        match kept.last() {
+            Some(last) if last.start_ledger == start_ledger => {
+                kept.set(
+                    kept.len() - 1,
+                    SpendBucket {
+                        start_ledger,
+                        amount: last.amount + amount,
+                    },
+                );
+            }
+            _ => kept.push_back(SpendBucket {
+                start_ledger,
+                amount,
+            }),
+        }
+        env.storage().persistent().set(&key, &kept);
         env.storage()
             .persistent()
-            .set(&DataKey2::MerchantAppeal(merchant.clone()), &appeal);
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    }
 
-        // Apply cooldown for next appeal
-        let cooldown_seconds: u64 = env
+    /// Internal: customer spend with the merchant within its tier window.
+    fn rolling_spend(env: &Env, program: &LoyaltyProgram, customer: &Address) -> i128 {
+        if program.tier_window_ledgers == 0 {
+            return 0;
+        }
+        let now = env.ledger().sequence();
+        let buckets: Vec<SpendBucket> = env
             .storage()
-            .instance()
-            .get(&DataKey2::AppealRejectionCooldownSeconds)
-            .unwrap_or(30 * 24 * 60 * 60); // Default 30 days
-        let cooldown_until = env.ledger().timestamp() + cooldown_seconds;
-        env.storage()
             .persistent()
-            .set(&DataKey2::AppealCooldownUntil(merchant.clone()), &cooldown_until);
-        env.storage().persistent().extend_ttl(
-            &DataKey2::AppealCooldownUntil(merchant.clone()),
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
+            .get(&DataKey3::LoyaltySpend(customer.clone(), program.merchant.clone()))
+            .unwrap_or(Vec::new(env));
+        let mut total = 0i128;
+        for bucket in buckets.iter() {
+            if bucket.start_ledger.saturating_add(program.tier_window_ledgers) > now {
+                total += bucket.amount;
+            }
+        }
+        total
+    }
 
-        events::emit_appeal_rejected(&env, merchant);
+    /// Internal: highest tier (counted from 1) whose threshold the spend meets.
+    fn loyalty_tier(program: &LoyaltyProgram, rolling_spend: i128) -> u32 {
+        let mut tier = 0u32;
+        for t in program.tiers.iter() {
+            if rolling_spend < t.min_spend {
+                break;
+            }
+            tier += 1;
+        }
+        tier
+    }
 
-        env.storage()
-            .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    /// Internal: drop and report expired lots, returning the live ones.
+    fn prune_point_lots(env: &Env, customer: &Address, merchant: &Address) -> Vec<PointLot> {
+        let now = env.ledger().sequence();
+        let lots = Self::load_point_lots(env, customer, merchant);
+        let mut live = Vec::new(env);
+        let mut expired = 0i128;
+        for lot in lots.iter() {
+            if lot.expires_at_ledger != 0 && now >= lot.expires_at_ledger {
+                expired += lot.points;
+            } else {
+                live.push_back(lot);
+            }
+        }
+        if expired > 0 {
+            Self::save_point_lots(env, customer, merchant, &live);
+            events::emit_program_points_expired(
+                env,
+                merchant.clone(),
+                customer.clone(),
+                expired,
+            );
+        }
+        live
     }
 
-    /// Get the current status of a merchant.
-    pub fn get_merchant_status(env: Env, merchant: Address) -> MerchantStatus {
+    fn load_point_lots(env: &Env, customer: &Address, merchant: &Address) -> Vec<PointLot> {
         env.storage()
             .persistent()
-            .get(&DataKey2::MerchantStatus(merchant))
-            .unwrap_or(MerchantStatus::Active)
+            .get(&DataKey3::PointLots(customer.clone(), merchant.clone()))
+            .unwrap_or(Vec::new(env))
     }
 
-    /// Get the active appeal for a merchant, if any.
-    pub fn get_merchant_appeal(env: Env, merchant: Address) -> Option<MerchantAppeal> {
+    fn save_point_lots(env: &Env, customer: &Address, merchant: &Address, lots: &Vec<PointLot>) {
+        let key = DataKey3::PointLots(customer.clone(), merchant.clone());
+        env.storage().persistent().set(&key, lots);
         env.storage()
             .persistent()
-            .get(&DataKey2::MerchantAppeal(merchant))
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
     }
 
-    // ─── #226: Merchant Revenue Dashboard ────────────────────────────────────
+    fn load_loyalty_program(env: &Env, merchant: &Address) -> LoyaltyProgram {
+        env.storage()
+            .persistent()
+            .get(&DataKey3::LoyaltyProgram(merchant.clone()))
+            .unwrap_or_else(|| panic_with_error!(env, MerchantError::LoyaltyProgramNotFound))
+    }
 
-    /// Returns the full merchant revenue summary.
-    pub fn get_merchant_summary(env: Env, merchant: Address) -> MerchantSummary {
+    fn save_loyalty_program(env: &Env, program: &LoyaltyProgram) {
+        let key = DataKey3::LoyaltyProgram(program.merchant.clone());
+        env.storage().persistent().set(&key, program);
         env.storage()
             .persistent()
-            .get(&DataKey2::MerchantSummary(merchant))
-            .unwrap_or(MerchantSummary {
-                total_volume: 0,
-                completed_count: 0,
-                failed_count: 0,
-                pending_count: 0,
-                volume_by_token: Map::new(&env),
-            })
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
     }
 
-    /// Returns the completed volume for a specific token for a merchant.
-    pub fn get_merchant_volume_by_token(env: Env, merchant: Address, token: Address) -> i128 {
-        let summary: MerchantSummary = env
-            .storage()
+    fn load_coalition(env: &Env, coalition_id: u32) -> LoyaltyCoalition {
+        env.storage()
             .persistent()
-            .get(&DataKey2::MerchantSummary(merchant))
-            .unwrap_or(MerchantSummary {
-                total_volume: 0,
-                completed_count: 0,
-                failed_count: 0,
-                pending_count: 0,
-                volume_by_token: Map::new(&env),
-            });
-        summary.volume_by_token.get(token).unwrap_or(0)
+            .get(&DataKey3::Coalition(coalition_id))
+            .unwrap_or_else(|| panic_with_error!(env, MerchantError::CoalitionNotFound))
     }
 
-    /// Returns (completed_count, failed_count, pending_count) for a merchant.
-    pub fn get_merchant_payment_counts(env: Env, merchant: Address) -> (u32, u32, u32) {
-        let summary: MerchantSummary = env
-            .storage()
+    fn save_coalition(env: &Env, coalition: &LoyaltyCoalition) {
+        let key = DataKey3::Coalition(coalition.id);
+        env.storage().persistent().set(&key, coalition);
+        env.storage()
             .persistent()
-            .get(&DataKey2::MerchantSummary(merchant))
-            .unwrap_or(MerchantSummary {
-                total_volume: 0,
-                completed_count: 0,
-                failed_count: 0,
-                pending_count: 0,
-                volume_by_token: Map::new(&env),
-            });
-        (
-            summary.completed_count,
-            summary.failed_count,
-            summary.pending_count,
-        )
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
     }
 
-    /// Admin resets a merchant's dashboard counters.
-    pub fn reset_merchant_stats(env: Env, merchant: Address) {
-        Self::require_not_paused(&env);
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Not initialized");
-        admin.require_auth();
+    fn set_coalition_reserve(env: &Env, key: &DataKey3, amount: i128) {
+        if amount > 0 {
+            env.storage().persistent().set(key, &amount);
+            env.storage()
+                .persistent()
+                .extend_ttl(key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+        } else {
+            env.storage().persistent().remove(key);
+        }
+    }
 
-        let empty = MerchantSummary {
-            total_volume: 0,
-            completed_count: 0,
-            failed_count: 0,
-            pending_count: 0,
-            volume_by_token: Map::new(&env),
-        };
-        let key = DataKey2::MerchantSummary(merchant);
-        env.storage().persistent().set(&key, &empty);
+    /// Internal: the issuer owes the redeeming merchant for a discount it granted.
+    fn record_coalition_debt(
+        env: &Env,
+        coalition_id: u32,
+        debtor: &Address,
+        creditor: &Address,
+        amount: i128,
+    ) {
+        let debt_key = DataKey3::CoalitionDebt(coalition_id, debtor.clone(), creditor.clone());
+        let outstanding = env.storage().persistent().get(&debt_key).unwrap_or(0i128) + amount;
+        env.storage().persistent().set(&debt_key, &outstanding);
         env.storage().persistent().extend_ttl(
-            &key,
+            &debt_key,
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
-        env.storage()
-            .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let owed_key = DataKey3::CoalitionOwed(coalition_id, debtor.clone());
+        let owed = env.storage().persistent().get(&owed_key).unwrap_or(0i128) + amount;
+        env.storage().persistent().set(&owed_key, &owed);
+        env.storage().persistent().extend_ttl(
+            &owed_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        events::emit_coalition_debt_recorded(
+            env,
+            coalition_id,
+            debtor.clone(),
+            creditor.clone(),
+            amount,
+            outstanding,
+        );
     }
 
-    // ── #239: Customer Loyalty Points ─────────────────────────────────────────
+    /// Cancel a recurring invoice. Callable by merchant or customer.
+    pub fn cancel_recurring_invoice(env: Env, caller: Address, invoice_id: u32) {
+        Self::require_not_paused(&env);
+        caller.require_auth();
 
-    /// Admin configures the loyalty points system.
-    /// points_per_unit: points earned per 1_000_000 units of payment token.
-    /// redemption_rate_bps: discount in basis points per 1 point redeemed.
-    /// min_payment_floor: minimum payment amount after discount.
-    /// expiry_ledgers: ledgers after which unspent points expire (0 = no expiry).
-    pub fn configure_loyalty(
-        env: Env,
-        admin: Address,
-        points_per_unit: u32,
-        redemption_rate_bps: u32,
-        min_payment_floor: i128,
-        expiry_ledgers: u32,
-    ) {
-        admin.require_auth();
-        let stored_admin: Address = env
+        let mut invoice: RecurringInvoice = env
             .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Not initialized");
-        if admin != stored_admin {
-            panic!("Only admin");
+            .persistent()
+            .get(&DataKey2::RecurringInvoice(invoice_id))
+            .unwrap_or_else(|| {
+                panic_with_error!(&env, SubscriptionError::RecurringInvoiceNotFound)
+            });
+
+        if caller != invoice.merchant && caller != invoice.customer {
+            panic_with_error!(&env, SubscriptionError::NotInvoiceParty);
         }
+
+        if !invoice.active {
+            panic_with_error!(&env, SubscriptionError::RecurringInvoiceCancelled);
+        }
+
+        invoice.active = false;
         env.storage()
-            .instance()
-            .set(&DataKey2::LoyaltyPointsPerUnit, &points_per_unit);
-        env.storage()
-            .instance()
-            .set(&DataKey2::LoyaltyRedemptionRateBps, &redemption_rate_bps);
-        env.storage()
-            .instance()
-            .set(&DataKey2::LoyaltyMinPaymentFloor, &min_payment_floor);
-        env.storage()
-            .instance()
-            .set(&DataKey2::LoyaltyExpiryLedgers, &expiry_ledgers);
+            .persistent()
+            .set(&DataKey2::RecurringInvoice(invoice_id), &invoice);
+        env.storage().persistent().extend_ttl(
+            &DataKey2::RecurringInvoice(invoice_id),
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        events::emit_recurring_invoice_cancelled(&env, invoice_id, caller.clone());
+
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
     }
 
-    /// Customer redeems loyalty points as a discount on a pending payment.
-    /// Discount = points_to_redeem * redemption_rate_bps / 10_000.
-    /// Payment amount cannot drop below min_payment_floor.
-    /// Points are non-transferable (tied to customer address).
-    pub fn redeem_points(env: Env, customer: Address, payment_id: u32, points_to_redeem: i128) {
-        customer.require_auth();
-
-        // Expire stale points first
-        Self::maybe_expire_points(&env, &customer);
-
-        let balance: i128 = env
-            .storage()
-            .persistent()
-            .get(&DataKey2::LoyaltyBalance(customer.clone()))
-            .unwrap_or(0);
-        if points_to_redeem <= 0 || points_to_redeem > balance {
-            panic!("Insufficient loyalty points");
-        }
-
-        let redemption_rate_bps: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey2::LoyaltyRedemptionRateBps)
-            .unwrap_or(0);
-        let min_floor: i128 = env
+    /// Internal: burn expired points if expiry window has passed.
+    fn maybe_expire_points(env: &Env, customer: &Address) {
+        let expiry_ledgers: u32 = env
             .storage()
             .instance()
-            .get(&DataKey2::LoyaltyMinPaymentFloor)
+            .get(&DataKey2::LoyaltyExpiryLedgers)
             .unwrap_or(0);
-
-        let mut payment: Payment = env
+        if expiry_ledgers == 0 {
+            return;
+        }
+        let last_accrual: u32 = match env
             .storage()
             .persistent()
-            .get(&DataKey::Payment(payment_id))
-            .expect("Payment not found");
-        if payment.customer != customer {
-            panic!("Not your payment");
-        }
-        if payment.status != PaymentStatus::Pending {
-            panic!("Payment not pending");
-        }
-
-        let discount = (points_to_redeem * redemption_rate_bps as i128) / 10_000;
-        let new_amount = (payment.amount - discount).max(min_floor);
-        let actual_discount = payment.amount - new_amount;
-        let actual_points_used = if actual_discount == discount {
-            points_to_redeem
-        } else {
-            // Recalculate points consumed when floor was hit
-            if redemption_rate_bps == 0 {
-                points_to_redeem
-            } else {
-                (actual_discount * 10_000) / redemption_rate_bps as i128
+            .get(&DataKey2::LoyaltyLastAccrualLedger(customer.clone()))
+        {
+            Some(v) => v,
+            None => return,
+        };
+        let current = env.ledger().sequence();
+        if current >= last_accrual + expiry_ledgers {
+            let balance: i128 = env
+                .storage()
+                .persistent()
+                .get(&DataKey2::LoyaltyBalance(customer.clone()))
+                .unwrap_or(0);
+            if balance > 0 {
+                env.storage()
+                    .persistent()
+                    .set(&DataKey2::LoyaltyBalance(customer.clone()), &0i128);
+                events::emit_points_expired(env, customer.clone(), balance);
             }
-        };
+        }
+    }
 
-        // Burn points
-        let new_balance = balance - actual_points_used;
+    /// Get a recurring invoice by ID.
+    pub fn get_recurring_invoice(env: Env, invoice_id: u32) -> RecurringInvoice {
         env.storage()
             .persistent()
-            .set(&DataKey2::LoyaltyBalance(customer.clone()), &new_balance);
-        env.storage().persistent().extend_ttl(
-            &DataKey2::LoyaltyBalance(customer.clone()),
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
+            .get(&DataKey2::RecurringInvoice(invoice_id))
+            .unwrap_or_else(|| panic_with_error!(&env, SubscriptionError::RecurringInvoiceNotFound))
+    }
 
-        // Apply discount to payment
-        payment.amount = new_amount;
+    /// Admin sets global referral terms.
+    pub fn set_referral_config(env: Env, admin: Address, commission_bps: u32, window_ledgers: u32) {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
+        if admin != stored_admin {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
         env.storage()
-            .persistent()
-            .set(&DataKey::Payment(payment_id), &payment);
-        env.storage().persistent().extend_ttl(
-            &DataKey::Payment(payment_id),
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
-        events::emit_points_redeemed(
-            &env,
-            customer,
-            payment_id,
-            actual_points_used,
-            actual_discount,
-        );
+            .instance()
+            .set(&DataKey2::ReferralCommissionBps, &commission_bps);
+        env.storage()
+            .instance()
+            .set(&DataKey2::ReferralWindowLedgers, &window_ledgers);
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
     }
 
-    // =========================================================================
-    // #242: Merchant Referral Commission Tracking
-    // =========================================================================
-    // #235: Merchant-Level Customer Spending Limits
-    // =========================================================================
-
-    /// Merchant sets a per-customer spend cap within a rolling window.
-    pub fn set_customer_spend_limit(
-        env: Env,
-        merchant: Address,
-        customer: Address,
-        amount: i128,
-        window_seconds: u64,
-    ) {
+    /// Existing merchant registers a referral for a new merchant.
+    /// Fails if the referred address already has a merchant record.
+    pub fn register_referral(env: Env, referrer: Address, referred_merchant: Address) {
         Self::require_not_paused(&env);
-        merchant.require_auth();
-        if amount <= 0 {
-            panic!("Spend limit amount must be positive");
+        referrer.require_auth();
+
+        // Referrer must be an approved merchant
+        let referrer_approved: bool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MerchantApproved(referrer.clone()))
+            .unwrap_or(false);
+        if !referrer_approved {
+            panic_with_error!(&env, MerchantError::ReferrerNotApproved);
         }
-        if window_seconds == 0 {
-            panic!("Window seconds must be positive");
+
+        // Referred must not already have a merchant record
+        let already_exists: bool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MerchantApproved(referred_merchant.clone()))
+            .unwrap_or(false);
+        if already_exists {
+            panic_with_error!(&env, Error::ReferralAlreadyExists);
         }
-        let key = DataKey2::CustomerSpendLimit(merchant.clone(), customer.clone());
-        let limit = SpendLimit {
-            amount,
-            window_seconds,
+
+        let window_ledgers: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey2::ReferralWindowLedgers)
+            .unwrap_or(0);
+        let record = ReferralRecord {
+            referrer: referrer.clone(),
+            registered_at_ledger: env.ledger().sequence(),
+            window_ledgers,
         };
-        env.storage().persistent().set(&key, &limit);
+        let key = DataKey2::ReferralRecord(referred_merchant.clone());
+        env.storage().persistent().set(&key, &record);
         env.storage().persistent().extend_ttl(
             &key,
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
-        events::emit_customer_spend_limit_set(&env, merchant, customer, amount, window_seconds);
-        env.storage()
-            .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-    }
 
-    /// Merchant removes a per-customer spend cap.
-    pub fn remove_customer_spend_limit(env: Env, merchant: Address, customer: Address) {
-        Self::require_not_paused(&env);
-        merchant.require_auth();
-        let key = DataKey2::CustomerSpendLimit(merchant.clone(), customer.clone());
-        env.storage().persistent().remove(&key);
-        events::emit_customer_spend_limit_removed(&env, merchant, customer);
+        events::emit_referral_registered(&env, referrer, referred_merchant);
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
     }
 
-    /// Merchant sets a global default spend cap applied to all customers without an individual override.
-    pub fn set_default_spend_limit(env: Env, merchant: Address, amount: i128, window_seconds: u64) {
+    /// Referrer withdraws accumulated commission to their address.
+    pub fn claim_referral_commission(env: Env, referrer: Address, token: Address) {
         Self::require_not_paused(&env);
-        merchant.require_auth();
-        if amount <= 0 {
-            panic!("Spend limit amount must be positive");
-        }
-        if window_seconds == 0 {
-            panic!("Window seconds must be positive");
+        referrer.require_auth();
+
+        let key = DataKey2::PendingCommission(referrer.clone());
+        let pending: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        if pending <= 0 {
+            panic_with_error!(&env, Error::NoCommissionToClaim);
         }
-        let key = DataKey2::DefaultSpendLimit(merchant.clone());
-        let limit = SpendLimit {
-            amount,
-            window_seconds,
-        };
-        env.storage().persistent().set(&key, &limit);
+
+        env.storage().persistent().set(&key, &0i128);
         env.storage().persistent().extend_ttl(
             &key,
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
-        events::emit_default_spend_limit_set(&env, merchant, amount, window_seconds);
+
+        let token_client = token::Client::new(&env, &token);
+        token_client.transfer(&env.current_contract_address(), &referrer, &pending);
+
+        events::emit_commission_claimed(&env, referrer, pending);
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
     }
 
-    /// Get the per-customer spend limit for a merchant+customer pair.
-    pub fn get_customer_spend_limit(
-        env: Env,
-        merchant: Address,
-        customer: Address,
-    ) -> Option<SpendLimit> {
-        env.storage()
-            .persistent()
-            .get(&DataKey2::CustomerSpendLimit(merchant, customer))
-    }
-
-    /// Get the default spend limit for a merchant.
-    pub fn get_default_spend_limit(env: Env, merchant: Address) -> Option<SpendLimit> {
+    /// Get pending commission balance for a referrer.
+    pub fn get_pending_commission(env: Env, referrer: Address) -> i128 {
         env.storage()
             .persistent()
-            .get(&DataKey2::DefaultSpendLimit(merchant))
-    }
-
-    // ─── #358: Buyer Trust Tier System ───────────────────────────────────────
-
-    /// Merchant assigns a trust tier to a buyer (New/Standard/Trusted/VIP).
-    /// Only the merchant may call this.
-    pub fn set_buyer_tier(
-        env: Env,
-        merchant: Address,
-        buyer: Address,
-        tier: BuyerTrustTierLevel,
-    ) {
-        Self::require_not_paused(&env);
-        merchant.require_auth();
-        let key = DataKey3::BuyerTrustTier(merchant.clone(), buyer.clone());
-        let old_tier: u32 = env
-            .storage()
-            .persistent()
-            .get::<_, BuyerTrustTierLevel>(&key)
-            .map(|t| t as u32)
-            .unwrap_or(BuyerTrustTierLevel::New as u32);
-        env.storage().persistent().set(&key, &tier);
-        env.storage().persistent().extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
-        events::emit_buyer_tier_updated(&env, merchant, buyer, old_tier, tier as u32);
-        env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+            .get(&DataKey2::PendingCommission(referrer))
+            .unwrap_or(0)
     }
 
-    /// Returns the trust tier assigned to a buyer by a specific merchant.
-    pub fn get_buyer_tier(env: Env, merchant: Address, buyer: Address) -> BuyerTrustTierLevel {
+    /// Get the referral record for a referred merchant.
+    pub fn get_referral_record(env: Env, referred_merchant: Address) -> Option<ReferralRecord> {
         env.storage()
             .persistent()
-            .get(&DataKey3::BuyerTrustTier(merchant, buyer))
-            .unwrap_or(BuyerTrustTierLevel::New)
-    }
-
-    /// Merchant sets the spending limit for a specific trust tier.
-    /// tier_value: 0=New, 1=Standard, 2=Trusted, 3=VIP.
-    pub fn set_tier_spending_limit(
-        env: Env,
-        merchant: Address,
-        tier: BuyerTrustTierLevel,
-        amount: i128,
-        window_seconds: u64,
-    ) {
-        Self::require_not_paused(&env);
-        merchant.require_auth();
-        if amount <= 0 { panic!("Tier spend limit amount must be positive"); }
-        if window_seconds == 0 { panic!("Window seconds must be positive"); }
-        let key = DataKey3::TierSpendingLimit(merchant.clone(), tier as u32);
-        let limit = SpendLimit { amount, window_seconds };
-        env.storage().persistent().set(&key, &limit);
-        env.storage().persistent().extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
-        env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-    }
-
-    /// Returns the spending limit configured for a trust tier by a merchant, if any.
-    pub fn get_tier_spending_limit(
-        env: Env,
-        merchant: Address,
-        tier: BuyerTrustTierLevel,
-    ) -> Option<SpendLimit> {
-        env.storage().persistent().get(&DataKey3::TierSpendingLimit(merchant, tier as u32))
+            .get(&DataKey2::ReferralRecord(referred_merchant))
     }
 
-    /// Check and update the customer spend window. Panics with CustomerSpendLimitExceeded if cap would be breached.
-    fn check_and_update_customer_spend_limit(
+    /// Internal: accrue referral commission when a referred merchant's payment is finalized.
+    fn accrue_referral_commission(
         env: &Env,
         merchant: &Address,
-        customer: &Address,
-        amount: i128,
+        payment_id: u32,
+        fee_amount: i128,
     ) {
-        // Priority: per-customer override → tier-based limit → global default (#358)
-        let limit_opt: Option<SpendLimit> = env
-            .storage()
-            .persistent()
-            .get(&DataKey2::CustomerSpendLimit(merchant.clone(), customer.clone()))
-            .or_else(|| {
-                // #358: check tier-based limit
-                let tier: BuyerTrustTierLevel = env
-                    .storage()
-                    .persistent()
-                    .get(&DataKey3::BuyerTrustTier(merchant.clone(), customer.clone()))
-                    .unwrap_or(BuyerTrustTierLevel::New);
-                env.storage()
-                    .persistent()
-                    .get(&DataKey3::TierSpendingLimit(merchant.clone(), tier as u32))
-            })
-            .or_else(|| {
-                env.storage()
-                    .persistent()
-                    .get(&DataKey2::DefaultSpendLimit(merchant.clone()))
-            });
+        if fee_amount <= 0 {
+            return;
+        }
 
-        let limit = match limit_opt {
-            Some(l) => l,
-            None => return, // No limit configured
+        let record_opt: Option<ReferralRecord> = env
+            .storage()
+            .persistent()
+            .get(&DataKey2::ReferralRecord(merchant.clone()));
+        let record = match record_opt {
+            Some(r) => r,
+            None => return,
         };
 
-        let state_key = DataKey2::CustomerSpendWindowState(merchant.clone(), customer.clone());
-        let now = env.ledger().timestamp();
-
-        let mut state: CustomerSpendWindow =
-            env.storage()
-                .persistent()
-                .get(&state_key)
-                .unwrap_or(CustomerSpendWindow {
-                    window_start: now,
-                    spent: 0,
-                });
+        // Check window has not expired
+        let current_ledger = env.ledger().sequence();
+        if record.window_ledgers > 0
+            && current_ledger
+                > record
+                    .registered_at_ledger
+                    .saturating_add(record.window_ledgers)
+        {
+            return;
+        }
 
-        // Reset window if expired
-        if now >= state.window_start + limit.window_seconds {
-            state = CustomerSpendWindow {
-                window_start: now,
-                spent: 0,
-            };
+        let commission_bps: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey2::ReferralCommissionBps)
+            .unwrap_or(0);
+        if commission_bps == 0 {
+            return;
         }
 
-        let new_total = state.spent.checked_add(amount).expect("Spend overflow");
-        if new_total > limit.amount {
-            events::emit_customer_spend_limit_exceeded(
-                env,
-                merchant.clone(),
-                customer.clone(),
-                new_total,
-                limit.amount,
-            );
-            panic_with_error!(env, Error::CustomerSpendLimitExceeded);
+        let commission = (fee_amount * commission_bps as i128) / 10_000;
+        if commission <= 0 {
+            return;
         }
 
-        state.spent = new_total;
-        env.storage().persistent().set(&state_key, &state);
+        let pending_key = DataKey2::PendingCommission(record.referrer.clone());
+        let current: i128 = env.storage().persistent().get(&pending_key).unwrap_or(0);
+        let new_total = current.saturating_add(commission);
+        env.storage().persistent().set(&pending_key, &new_total);
         env.storage().persistent().extend_ttl(
-            &state_key,
+            &pending_key,
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+
+        events::emit_commission_accrued(
+            env,
+            record.referrer,
+            merchant.clone(),
+            payment_id,
+            commission,
+        );
     }
 
-    // ─── #216: Recurring Invoice Scheduling ──────────────────────────────────
+    // ─────────────────────────────────────────────────────────────────
+    // #265: Merchant Tip and Gratuity Support
+    // ─────────────────────────────────────────────────────────────────
 
-    /// Merchant creates a recurring invoice schedule.
-    /// `max_cycles` = 0 means unlimited.
-    pub fn create_recurring_invoice(
+    /// Create a payment with tipping enabled. Identical to `create_payment`
+    /// except `tipping_enabled` is stored on the payment record so that
+    /// `complete_payment_with_tip` may attach a gratuity at settlement.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_payment_with_tipping(
         env: Env,
-        merchant: Address,
         customer: Address,
+        merchant: Address,
         amount: i128,
         token: Address,
-        interval_seconds: u64,
-        max_cycles: u32,
-        reference_hash: Option<BytesN<32>>,
+        reference: Option<String>,
+        metadata: Option<Map<String, String>>,
+        idempotency_key: Option<BytesN<32>>,
     ) -> u32 {
-        Self::require_not_paused(&env);
-        merchant.require_auth();
-
-        if amount <= 0 {
-            panic!("Amount must be positive");
-        }
-        if interval_seconds == 0 {
-            panic!("Interval must be positive");
-        }
-
-        Self::require_token_allowed(&env, &token);
-        Self::require_merchant_approved(&env, &merchant);
-
-        let mut counter: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey2::RecurringInvoiceCounter)
-            .unwrap_or(0);
-        let invoice_id = counter;
-        counter += 1;
-        env.storage()
-            .instance()
-            .set(&DataKey2::RecurringInvoiceCounter, &counter);
-
-        let now = env.ledger().timestamp();
-        let invoice = RecurringInvoice {
-            id: invoice_id,
-            merchant: merchant.clone(),
-            customer: customer.clone(),
+        let payment_id = Self::create_payment_with_expiry(
+            env.clone(),
+            customer,
+            merchant,
             amount,
             token,
-            interval_seconds,
-            max_cycles,
-            cycles_triggered: 0,
-            next_due_at: now,
-            reference_hash,
-            active: true,
-        };
-
+            reference,
+            metadata,
+            None,
+            None,
+            idempotency_key,
+            None,
+        );
+        // Flip the tipping flag on the freshly-stored payment.
+        let mut payment: Payment = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Payment(payment_id))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::PaymentNotFound));
+        payment.tipping_enabled = true;
         env.storage()
             .persistent()
-            .set(&DataKey2::RecurringInvoice(invoice_id), &invoice);
+            .set(&DataKey::Payment(payment_id), &payment);
         env.storage().persistent().extend_ttl(
-            &DataKey2::RecurringInvoice(invoice_id),
+            &DataKey::Payment(payment_id),
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        payment_id
+    }
 
-        events::emit_recurring_invoice_created(&env, invoice_id, merchant, customer, amount);
+    /// Admin completes a payment and optionally forwards a customer-provided
+    /// gratuity directly to the merchant (fee-exempt).
+    ///
+    /// * `tip_amount == 0` behaves identically to `complete_payment`.
+    /// * `tip_amount > 0` requires `payment.tipping_enabled == true`.
+    /// * `tip_amount` must not exceed `max_tip_bps` percent of the base amount.
+    /// * The customer must auth this call (to authorise the tip pull).
+    pub fn complete_payment_with_tip(
+        env: Env,
+        payment_id: u32,
+        customer: Address,
+        tip_amount: i128,
+    ) {
+        Self::require_not_paused(&env);
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
+        admin.require_auth();
+
+        let payment: Payment = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Payment(payment_id))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::PaymentNotFound));
+
+        if tip_amount < 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+
+        if tip_amount > 0 {
+            if !payment.tipping_enabled {
+                panic_with_error!(&env, Error::TippingNotEnabled);
+            }
+            let max_tip_bps: u32 = env
+                .storage()
+                .instance()
+                .get(&DataKey2::MaxTipBps)
+                .unwrap_or(DEFAULT_MAX_TIP_BPS);
+            let max_tip = (payment.amount * max_tip_bps as i128) / 10_000;
+            if tip_amount > max_tip {
+                panic_with_error!(&env, Error::TipExceedsMaxBps);
+            }
+            // Customer must auth the tip transfer.
+            customer.require_auth();
+            let token_client = token::Client::new(&env, &payment.token);
+            
+            // Check for tip split configuration (#370)
+            let split_config: Option<soroban_sdk::Vec<TipSplitBeneficiary>> = env
+                .storage()
+                .persistent()
+                .get(&DataKey3::TipSplitConfig(payment.merchant.clone()));
+            
+            match split_config {
+                Some(beneficiaries) => {
+                    // Multi-beneficiary distribution
+                    for beneficiary in beneficiaries.iter() {
+                        let allocated_amount = (tip_amount * beneficiary.bps as i128) / 10_000;
+                        
+                        // Skip zero allocations (rounding edge case)
+                        if allocated_amount > 0 {
+                            token_client.transfer(
+                                &customer,
+                                &beneficiary.recipient,
+                                &allocated_amount
+                            );
+                            
+                            events::emit_tip_split(
+                                &env,
+                                payment_id,
+                                beneficiary.recipient.clone(),
+                                allocated_amount,
+                                payment.token.clone(),
+                            );
+                        }
+                    }
+                }
+                None => {
+                    // Fallback: single transfer to merchant (backward compatible)
+                    token_client.transfer(&customer, &payment.merchant, &tip_amount);
+                    events::emit_tip_received(
+                        &env,
+                        payment_id,
+                        payment.merchant.clone(),
+                        tip_amount,
+                        payment.token.clone(),
+                    );
+                }
+            }
+        }
 
+        Self::complete_payment_internal(&env, payment_id, false);
+    }
+
+    /// Admin sets the global maximum tip in basis points.
+    /// Must be ≤ 10 000 bps (100%).  Default is 3 000 bps (30%).
+    pub fn set_max_tip_bps(env: Env, admin: Address, max_bps: u32) {
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
+        if admin != stored_admin {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
+        if max_bps > 10_000 {
+            panic_with_error!(&env, ConfigError::InvalidTipBps);
+        }
+        env.storage().instance().set(&DataKey2::MaxTipBps, &max_bps);
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-
-        invoice_id
     }
 
-    /// Trigger the next invoice cycle, creating a standard Payment entry.
-    /// Callable by anyone (merchant, keeper, etc.) when the interval has elapsed.
-    /// Returns the new payment_id.
-    pub fn trigger_invoice_cycle(env: Env, invoice_id: u32) -> u32 {
-        Self::require_not_paused(&env);
-
-        let mut invoice: RecurringInvoice = env
+    /// Admin sets KYB verification hash for a merchant (#310)
+    pub fn set_merchant_kyb(
+        env: Env,
+        admin: Address,
+        merchant: Address,
+        kyb_hash: BytesN<32>,
+        expiry_ledger: u64,
+        jurisdiction: String,
+    ) {
+        admin.require_auth();
+        let stored_admin: Address = env
             .storage()
-            .persistent()
-            .get(&DataKey2::RecurringInvoice(invoice_id))
-            .expect("Recurring invoice not found");
-
-        if !invoice.active {
-            panic!("Recurring invoice is cancelled");
-        }
-
-        let now = env.ledger().timestamp();
-        if now < invoice.next_due_at {
-            panic!("Invoice interval has not elapsed");
-        }
-
-        if invoice.max_cycles > 0 && invoice.cycles_triggered >= invoice.max_cycles {
-            panic!("Max cycles reached");
+            .instance()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
+        if admin != stored_admin {
+            panic_with_error!(&env, Error::Unauthorized);
         }
 
-        // Create a standard Payment entry (funds transferred from customer)
-        let payment_id = Self::create_payment_with_options(
-            env.clone(),
-            invoice.customer.clone(),
-            invoice.merchant.clone(),
-            invoice.amount,
-            invoice.token.clone(),
-            None,
-            None,
-            None,
-            None,
-            None,
-        );
-
-        invoice.cycles_triggered += 1;
-        invoice.next_due_at = now + invoice.interval_seconds;
-
-        // Auto-complete if max_cycles reached
-        if invoice.max_cycles > 0 && invoice.cycles_triggered >= invoice.max_cycles {
-            invoice.active = false;
-            events::emit_recurring_invoice_completed(&env, invoice_id);
-        }
+        let kyb = MerchantKYB {
+            kyb_hash: kyb_hash.clone(),
+            expiry_ledger,
+            jurisdiction: jurisdiction.clone(),
+            revoked: false,
+        };
 
         env.storage()
             .persistent()
-            .set(&DataKey2::RecurringInvoice(invoice_id), &invoice);
+            .set(&DataKey2::MerchantKYB(merchant.clone()), &kyb);
         env.storage().persistent().extend_ttl(
-            &DataKey2::RecurringInvoice(invoice_id),
+            &DataKey2::MerchantKYB(merchant.clone()),
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        events::emit_invoice_cycle_triggered(
-            &env,
-            invoice_id,
-            payment_id,
-            invoice.cycles_triggered,
-        );
-        env.storage()
-            .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-
-        payment_id
-    }
-
-    /// Returns the loyalty points balance for a customer (after expiry check).
-    pub fn get_loyalty_balance(env: Env, customer: Address) -> i128 {
-        Self::maybe_expire_points(&env, &customer);
-        env.storage()
-            .persistent()
-            .get(&DataKey2::LoyaltyBalance(customer))
-            .unwrap_or(0)
+        events::emit_merchant_kyb_set(&env, merchant, kyb_hash, expiry_ledger, jurisdiction);
     }
 
-    /// Internal: mint points to customer after a completed payment.
-    fn accrue_loyalty_points(env: &Env, payment_id: u32, customer: &Address, payment_amount: i128) {
-        let points_per_unit: u32 = match env
+    /// Admin toggles KYB enforcement globally (#310)
+    pub fn set_kyb_enforcement(env: Env, admin: Address, enabled: bool) {
+        admin.require_auth();
+        let stored_admin: Address = env
             .storage()
             .instance()
-            .get(&DataKey2::LoyaltyPointsPerUnit)
-        {
-            Some(v) => v,
-            None => return, // loyalty not configured
-        };
-        if points_per_unit == 0 {
-            return;
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
+        if admin != stored_admin {
+            panic_with_error!(&env, Error::Unauthorized);
         }
 
-        // Expire stale points before accruing
-        Self::maybe_expire_points(env, customer);
+        env.storage()
+            .instance()
+            .set(&DataKey2::KYBEnforcementEnabled, &enabled);
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
 
-        let points_earned = payment_amount * points_per_unit as i128 / 1_000_000;
-        if points_earned <= 0 {
-            return;
+    /// Admin revokes a merchant's KYB verification (#310)
+    pub fn revoke_merchant_kyb(env: Env, admin: Address, merchant: Address) {
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
+        if admin != stored_admin {
+            panic_with_error!(&env, Error::Unauthorized);
         }
 
-        let old_balance: i128 = env
+        if let Some(mut kyb) = env
             .storage()
             .persistent()
-            .get(&DataKey2::LoyaltyBalance(customer.clone()))
-            .unwrap_or(0);
-        let new_balance = old_balance + points_earned;
+            .get::<_, MerchantKYB>(&DataKey2::MerchantKYB(merchant.clone()))
+        {
+            kyb.revoked = true;
+            env.storage()
+                .persistent()
+                .set(&DataKey2::MerchantKYB(merchant.clone()), &kyb);
+            env.storage().persistent().extend_ttl(
+                &DataKey2::MerchantKYB(merchant.clone()),
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+        }
 
-        env.storage()
-            .persistent()
-            .set(&DataKey2::LoyaltyBalance(customer.clone()), &new_balance);
-        env.storage().persistent().extend_ttl(
-            &DataKey2::LoyaltyBalance(customer.clone()),
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
-        env.storage().persistent().set(
-            &DataKey2::LoyaltyLastAccrualLedger(customer.clone()),
-            &env.ledger().sequence(),
-        );
-        env.storage().persistent().extend_ttl(
-            &DataKey2::LoyaltyLastAccrualLedger(customer.clone()),
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
-        events::emit_points_accrued(
-            env,
-            customer.clone(),
-            payment_id,
-            points_earned,
-            new_balance,
-        );
+        events::emit_merchant_kyb_revoked(&env, merchant);
     }
 
-    /// Cancel a recurring invoice. Callable by merchant or customer.
-    pub fn cancel_recurring_invoice(env: Env, caller: Address, invoice_id: u32) {
-        Self::require_not_paused(&env);
-        caller.require_auth();
+    /// Get merchant KYB status (#310)
+    pub fn get_merchant_kyb_status(env: Env, merchant: Address) -> KYBStatus {
+        let current_ledger = env.ledger().sequence() as u64;
 
-        let mut invoice: RecurringInvoice = env
+        if let Some(kyb) = env
             .storage()
             .persistent()
-            .get(&DataKey2::RecurringInvoice(invoice_id))
-            .expect("Recurring invoice not found");
-
-        if caller != invoice.merchant && caller != invoice.customer {
-            panic!("Only merchant or customer can cancel");
-        }
+            .get::<_, MerchantKYB>(&DataKey2::MerchantKYB(merchant.clone()))
+        {
+            env.storage().persistent().extend_ttl(
+                &DataKey2::MerchantKYB(merchant),
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
 
-        if !invoice.active {
-            panic!("Recurring invoice is already cancelled");
+            let verified = !kyb.revoked && current_ledger <= kyb.expiry_ledger;
+            KYBStatus {
+                verified,
+                expiry_ledger: kyb.expiry_ledger,
+                jurisdiction: kyb.jurisdiction,
+            }
+        } else {
+            KYBStatus {
+                verified: false,
+                expiry_ledger: 0,
+                jurisdiction: String::from_slice(&env, ""),
+            }
         }
+    }
 
-        invoice.active = false;
+    /// Check if KYB enforcement is enabled (#310)
+    pub fn is_kyb_enforcement_enabled(env: Env) -> bool {
         env.storage()
-            .persistent()
-            .set(&DataKey2::RecurringInvoice(invoice_id), &invoice);
-        env.storage().persistent().extend_ttl(
-            &DataKey2::RecurringInvoice(invoice_id),
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
+            .instance()
+            .get::<_, bool>(&DataKey2::KYBEnforcementEnabled)
+            .unwrap_or(false)
+    }
+    /// Returns the current maximum tip in basis points (default: 3 000).
+    pub fn get_max_tip_bps(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey2::MaxTipBps)
+            .unwrap_or(DEFAULT_MAX_TIP_BPS)
+    }
 
-        events::emit_recurring_invoice_cancelled(&env, invoice_id, caller.clone());
+    // =========================================================================
+    // Tip Split Configuration (#370)
+    // =========================================================================
 
+    /// Configure tip split allocation for a merchant.
+    /// Validates that basis points sum to exactly 10,000 and beneficiary count <= MAX_TIP_SPLIT_BENEFICIARIES.
+    pub fn set_tip_split_config(
+        env: Env,
+        merchant: Address,
+        caller: Address,
+        split_map: soroban_sdk::Vec<(Address, u32)>,
+    ) {
+        caller.require_auth();
+        
+        // Validate caller is merchant (in production, add admin override if needed)
+        if caller != merchant {
+            panic_with_error!(&env, SubscriptionError::NotTipSplitMerchant);
+        }
+        
+        // Validate non-empty
+        if split_map.is_empty() {
+            panic_with_error!(&env, SubscriptionError::InvalidTipSplit);
+        }
+        
+        // Validate max beneficiaries
+        if split_map.len() > MAX_TIP_SPLIT_BENEFICIARIES {
+            panic_with_error!(&env, SubscriptionError::InvalidTipSplit);
+        }
+        
+        // Validate total bps sum to 10,000
+        let mut total_bps: u32 = 0;
+        for entry in split_map.iter() {
+            total_bps = total_bps
+                .checked_add(entry.1)
+                .unwrap_or_else(|| panic_with_error!(&env, Error::ArithmeticOverflow));
+        }
+        if total_bps != 10_000 {
+            panic_with_error!(&env, SubscriptionError::InvalidTipSplit);
+        }
+        
+        // Convert to Vec<TipSplitBeneficiary> and store
+        let mut beneficiaries: soroban_sdk::Vec<TipSplitBeneficiary> = soroban_sdk::Vec::new(&env);
+        for entry in split_map.iter() {
+            beneficiaries.push_back(TipSplitBeneficiary {
+                recipient: entry.0,
+                bps: entry.1,
+            });
+        }
+        
         env.storage()
-            .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+            .persistent()
+            .set(&DataKey3::TipSplitConfig(merchant.clone()), &beneficiaries);
+        env.storage()
+            .persistent()
+            .extend_ttl(
+                &DataKey3::TipSplitConfig(merchant),
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
     }
 
-    /// Internal: burn expired points if expiry window has passed.
-    fn maybe_expire_points(env: &Env, customer: &Address) {
-        let expiry_ledgers: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey2::LoyaltyExpiryLedgers)
-            .unwrap_or(0);
-        if expiry_ledgers == 0 {
-            return;
-        }
-        let last_accrual: u32 = match env
+    /// Retrieve tip split configuration for a merchant.
+    /// Returns None if no configuration exists (fallback to default behavior).
+    pub fn get_tip_split_config(
+        env: Env,
+        merchant: Address,
+    ) -> Option<soroban_sdk::Vec<(Address, u32)>> {
+        let config: Option<soroban_sdk::Vec<TipSplitBeneficiary>> = env
             .storage()
             .persistent()
-            .get(&DataKey2::LoyaltyLastAccrualLedger(customer.clone()))
-        {
-            Some(v) => v,
-            None => return,
-        };
-        let current = env.ledger().sequence();
-        if current >= last_accrual + expiry_ledgers {
-            let balance: i128 = env
-                .storage()
-                .persistent()
-                .get(&DataKey2::LoyaltyBalance(customer.clone()))
-                .unwrap_or(0);
-            if balance > 0 {
-                env.storage()
-                    .persistent()
-                    .set(&DataKey2::LoyaltyBalance(customer.clone()), &0i128);
-                events::emit_points_expired(env, customer.clone(), balance);
+            .get(&DataKey3::TipSplitConfig(merchant));
+        
+        config.map(|beneficiaries| {
+            let mut result: soroban_sdk::Vec<(Address, u32)> = soroban_sdk::Vec::new(&env);
+            for b in beneficiaries.iter() {
+                result.push_back((b.recipient, b.bps));
             }
-        }
+            result
+        })
     }
 
-    /// Get a recurring invoice by ID.
-    pub fn get_recurring_invoice(env: Env, invoice_id: u32) -> RecurringInvoice {
+    /// Remove tip split configuration for a merchant.
+    /// Reverts to default behavior (100% to merchant).
+    pub fn remove_tip_split_config(
+        env: Env,
+        merchant: Address,
+        caller: Address,
+    ) {
+        caller.require_auth();
+        
+        // Validate caller is merchant (in production, add admin override if needed)
+        if caller != merchant {
+            panic_with_error!(&env, SubscriptionError::NotTipSplitMerchant);
+        }
+        
         env.storage()
             .persistent()
-            .get(&DataKey2::RecurringInvoice(invoice_id))
-            .expect("Recurring invoice not found")
+            .remove(&DataKey3::TipSplitConfig(merchant));
     }
 
-    /// Admin sets global referral terms.
-    pub fn set_referral_config(env: Env, admin: Address, commission_bps: u32, window_ledgers: u32) {
-        Self::require_not_paused(&env);
-        admin.require_auth();
-        let stored_admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Not initialized");
-        if admin != stored_admin {
-            panic!("Only admin can set referral config");
-        }
-        env.storage()
-            .instance()
-            .set(&DataKey2::ReferralCommissionBps, &commission_bps);
-        env.storage()
-            .instance()
-            .set(&DataKey2::ReferralWindowLedgers, &window_ledgers);
-        env.storage()
-            .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-    }
+    // =========================================================================
+    // Consent Records (#307): Zero-Amount Agreement Signing
+    // =========================================================================
 
-    /// Existing merchant registers a referral for a new merchant.
-    /// Fails if the referred address already has a merchant record.
-    pub fn register_referral(env: Env, referrer: Address, referred_merchant: Address) {
+    /// Create a consent record for a customer to sign terms without token transfer.
+    /// Merchant calls this to create a record keyed by (merchant, customer, terms_version).
+    /// Returns the consent_id.
+    pub fn create_consent_record(
+        env: Env,
+        merchant: Address,
+        customer: Address,
+        terms_hash: BytesN<32>,
+        terms_version: String,
+        expiry_ledger: u64,
+    ) -> u32 {
+        merchant.require_auth();
         Self::require_not_paused(&env);
-        referrer.require_auth();
 
-        // Referrer must be an approved merchant
-        let referrer_approved: bool = env
-            .storage()
-            .persistent()
-            .get(&DataKey::MerchantApproved(referrer.clone()))
-            .unwrap_or(false);
-        if !referrer_approved {
-            panic!("Referrer is not an approved merchant");
+        // Validate inputs
+        if terms_version.len() == 0 || terms_version.len() > 64 {
+            panic_with_error!(&env, MerchantError::InvalidTermsVersion);
         }
-
-        // Referred must not already have a merchant record
-        let already_exists: bool = env
-            .storage()
-            .persistent()
-            .get(&DataKey::MerchantApproved(referred_merchant.clone()))
-            .unwrap_or(false);
-        if already_exists {
-            panic_with_error!(&env, Error::ReferralAlreadyExists);
+        if expiry_ledger <= env.ledger().sequence() as u64 {
+            panic_with_error!(&env, PaymentError::DeadlineInPast);
         }
 
-        let window_ledgers: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey2::ReferralWindowLedgers)
-            .unwrap_or(0);
-        let record = ReferralRecord {
-            referrer: referrer.clone(),
-            registered_at_ledger: env.ledger().sequence(),
-            window_ledgers,
+        // Get next consent ID
+        let consent_id = Self::next_consent_id(&env);
+
+        // Create consent record
+        let now = env.ledger().timestamp();
+        let consent = ConsentRecord {
+            id: consent_id,
+            merchant: merchant.clone(),
+            customer: customer.clone(),
+            terms_hash: terms_hash.clone(),
+            terms_version: terms_version.clone(),
+            created_at: now,
+            expires_at: expiry_ledger,
+            is_signed: false,
+            signed_at: 0,
+            is_revoked: false,
         };
-        let key = DataKey2::ReferralRecord(referred_merchant.clone());
-        env.storage().persistent().set(&key, &record);
-        env.storage().persistent().extend_ttl(
-            &key,
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
 
-        events::emit_referral_registered(&env, referrer, referred_merchant);
+        // Store consent record
         env.storage()
-            .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-    }
-
-    /// Referrer withdraws accumulated commission to their address.
-    pub fn claim_referral_commission(env: Env, referrer: Address, token: Address) {
-        Self::require_not_paused(&env);
-        referrer.require_auth();
+            .persistent()
+            .set(&DataKey2::ConsentRecord(consent_id), &consent);
 
-        let key = DataKey2::PendingCommission(referrer.clone());
-        let pending: i128 = env.storage().persistent().get(&key).unwrap_or(0);
-        if pending <= 0 {
-            panic_with_error!(&env, Error::NoCommissionToClaim);
-        }
+        // Index by (merchant, customer, terms_version)
+        env.storage().persistent().set(
+            &DataKey2::ConsentIndex(merchant.clone(), customer.clone(), terms_version.clone()),
+            &consent_id,
+        );
 
-        env.storage().persistent().set(&key, &0i128);
+        // Extend TTL
         env.storage().persistent().extend_ttl(
-            &key,
+            &DataKey2::ConsentRecord(consent_id),
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        env.storage().persistent().extend_ttl(
+            &DataKey2::ConsentIndex(merchant.clone(), customer.clone(), terms_version.clone()),
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        events::emit_consent_record_created(
+            &env,
+            consent_id,
+            merchant,
+            customer,
+            terms_hash,
+            terms_version,
+        );
+        consent_id
+    }
+    // ── #329: Failed Auto-Debit Retry Queue ───────────────────────────────────
 
-        let token_client = token::Client::new(&env, &token);
-        token_client.transfer(&env.current_contract_address(), &referrer, &pending);
-
-        events::emit_commission_claimed(&env, referrer, pending);
-        env.storage()
-            .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-    }
-
-    /// Get pending commission balance for a referrer.
-    pub fn get_pending_commission(env: Env, referrer: Address) -> i128 {
-        env.storage()
-            .persistent()
-            .get(&DataKey2::PendingCommission(referrer))
-            .unwrap_or(0)
-    }
-
-    /// Get the referral record for a referred merchant.
-    pub fn get_referral_record(env: Env, referred_merchant: Address) -> Option<ReferralRecord> {
-        env.storage()
-            .persistent()
-            .get(&DataKey2::ReferralRecord(referred_merchant))
-    }
-
-    /// Internal: accrue referral commission when a referred merchant's payment is finalized.
-    fn accrue_referral_commission(
-        env: &Env,
-        merchant: &Address,
-        payment_id: u32,
-        fee_amount: i128,
-    ) {
-        if fee_amount <= 0 {
-            return;
-        }
-
-        let record_opt: Option<ReferralRecord> = env
-            .storage()
-            .persistent()
-            .get(&DataKey2::ReferralRecord(merchant.clone()));
-        let record = match record_opt {
-            Some(r) => r,
-            None => return,
-        };
-
-        // Check window has not expired
-        let current_ledger = env.ledger().sequence();
-        if record.window_ledgers > 0
-            && current_ledger
-                > record
-                    .registered_at_ledger
-                    .saturating_add(record.window_ledgers)
-        {
-            return;
-        }
-
-        let commission_bps: u32 = env
+    /// Admin configures the retry back-off schedule.
+    /// `base_retry_interval`: ledgers before first retry (default 100).
+    /// `max_retry_interval`: back-off cap in ledgers (default 3 200).
+    /// `max_retry_attempts`: maximum retry attempts before abandonment (default 5).
+    pub fn set_retry_config(
+        env: Env,
+        admin: Address,
+        base_retry_interval: u64,
+        max_retry_interval: u64,
+        max_retry_attempts: u32,
+    ) {
+        admin.require_auth();
+        let stored_admin: Address = env
             .storage()
             .instance()
-            .get(&DataKey2::ReferralCommissionBps)
-            .unwrap_or(0);
-        if commission_bps == 0 {
-            return;
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
+        if admin != stored_admin {
+            panic_with_error!(&env, Error::Unauthorized);
         }
-
-        let commission = (fee_amount * commission_bps as i128) / 10_000;
-        if commission <= 0 {
-            return;
+        if base_retry_interval == 0 {
+            panic_with_error!(&env, ConfigError::InvalidRetryConfig);
         }
-
-        let pending_key = DataKey2::PendingCommission(record.referrer.clone());
-        let current: i128 = env.storage().persistent().get(&pending_key).unwrap_or(0);
-        let new_total = current.saturating_add(commission);
-        env.storage().persistent().set(&pending_key, &new_total);
+        if max_retry_interval < base_retry_interval {
+            panic_with_error!(&env, ConfigError::InvalidRetryConfig);
+        }
+        if max_retry_attempts == 0 {
+            panic_with_error!(&env, ConfigError::InvalidRetryConfig);
+        }
+        let cfg = RetryConfig {
+            base_retry_interval,
+            max_retry_interval,
+            max_retry_attempts,
+        };
+        env.storage().persistent().set(&DataKey2::RetryConfig, &cfg);
         env.storage().persistent().extend_ttl(
-            &pending_key,
+            &DataKey2::RetryConfig,
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
-
-        events::emit_commission_accrued(
-            env,
-            record.referrer,
-            merchant.clone(),
-            payment_id,
-            commission,
-        );
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
     }
 
-    // ─────────────────────────────────────────────────────────────────
-    // #265: Merchant Tip and Gratuity Support
-    // ─────────────────────────────────────────────────────────────────
-
-    /// Create a payment with tipping enabled. Identical to `create_payment`
-    /// except `tipping_enabled` is stored on the payment record so that
-    /// `complete_payment_with_tip` may attach a gratuity at settlement.
-    #[allow(clippy::too_many_arguments)]
-    pub fn create_payment_with_tipping(
+    /// Merchant initiates a pull-payment from a pre-approved customer allowance.
+    /// If the customer's balance is sufficient the payment settles immediately.
+    /// On insufficient balance the contract stores a `FailedDebitRecord` and
+    /// emits `DebitFailed` instead of reverting, returning the new record ID.
+    pub fn initiate_allowed_payment(
         env: Env,
-        customer: Address,
         merchant: Address,
-        amount: i128,
+        customer: Address,
         token: Address,
-        reference: Option<String>,
-        metadata: Option<Map<String, String>>,
-        idempotency_key: Option<BytesN<32>>,
+        amount: i128,
+        plan_id: u32,
     ) -> u32 {
-        let payment_id = Self::create_payment_with_expiry(
-            env.clone(),
-            customer,
-            merchant,
-            amount,
-            token,
-            reference,
-            metadata,
-            None,
-            None,
-            idempotency_key,
-            None,
+        Self::require_not_paused(&env);
+        merchant.require_auth();
+        if amount <= 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+        Self::require_token_allowed(&env, &token);
+        Self::require_merchant_approved(&env, &merchant);
+
+        let cfg: RetryConfig = env
+            .storage()
+            .persistent()
+            .get(&DataKey2::RetryConfig)
+            .unwrap_or(RetryConfig {
+                base_retry_interval: DEFAULT_BASE_RETRY_INTERVAL,
+                max_retry_interval: DEFAULT_MAX_RETRY_INTERVAL,
+                max_retry_attempts: DEFAULT_MAX_RETRY_ATTEMPTS,
+            });
+
+        let client = token::Client::new(&env, &token);
+        let result = client.try_transfer_from(
+            &env.current_contract_address(),
+            &customer,
+            &merchant,
+            &amount,
         );
-        // Flip the tipping flag on the freshly-stored payment.
-        let mut payment: Payment = env
+
+        let record_id: u32 = env
             .storage()
             .persistent()
-            .get(&DataKey::Payment(payment_id))
-            .expect("Payment not found");
-        payment.tipping_enabled = true;
+            .get(&DataKey2::FailedDebitCounter)
+            .unwrap_or(0u32)
+            + 1;
         env.storage()
             .persistent()
-            .set(&DataKey::Payment(payment_id), &payment);
+            .set(&DataKey2::FailedDebitCounter, &record_id);
         env.storage().persistent().extend_ttl(
-            &DataKey::Payment(payment_id),
+            &DataKey2::FailedDebitCounter,
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
-        payment_id
-    }
 
-    /// Admin completes a payment and optionally forwards a customer-provided
-    /// gratuity directly to the merchant (fee-exempt).
-    ///
-    /// * `tip_amount == 0` behaves identically to `complete_payment`.
-    /// * `tip_amount > 0` requires `payment.tipping_enabled == true`.
-    /// * `tip_amount` must not exceed `max_tip_bps` percent of the base amount.
-    /// * The customer must auth this call (to authorise the tip pull).
-    pub fn complete_payment_with_tip(
-        env: Env,
-        payment_id: u32,
-        customer: Address,
-        tip_amount: i128,
-    ) {
-        Self::require_not_paused(&env);
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Not initialized");
-        admin.require_auth();
-
-        let payment: Payment = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Payment(payment_id))
-            .expect("Payment not found");
-
-        if tip_amount < 0 {
-            panic!("tip_amount cannot be negative");
-        }
-
-        if tip_amount > 0 {
-            if !payment.tipping_enabled {
-                panic_with_error!(&env, Error::TippingNotEnabled);
-            }
-            let max_tip_bps: u32 = env
-                .storage()
-                .instance()
-                .get(&DataKey2::MaxTipBps)
-                .unwrap_or(DEFAULT_MAX_TIP_BPS);
-            let max_tip = (payment.amount * max_tip_bps as i128) / 10_000;
-            if tip_amount > max_tip {
-                panic_with_error!(&env, Error::TipExceedsMaxBps);
-            }
-            // Customer must auth the tip transfer.
-            customer.require_auth();
-            let token_client = token::Client::new(&env, &payment.token);
-            
-            // Check for tip split configuration (#370)
-            let split_config: Option<soroban_sdk::Vec<TipSplitBeneficiary>> = env
-                .storage()
+        if result.is_ok() {
+            let rec = FailedDebitRecord {
+                id: record_id,
+                plan_id,
+                merchant: merchant.clone(),
+                customer: customer.clone(),
+                token: token.clone(),
+                amount,
+                attempt_number: 1,
+                next_retry_ledger: 0,
+                status: FailedDebitStatus::Succeeded,
+                created_at: env.ledger().timestamp(),
+            };
+            env.storage()
                 .persistent()
-                .get(&DataKey3::TipSplitConfig(payment.merchant.clone()));
-            
-            match split_config {
-                Some(beneficiaries) => {
-                    // Multi-beneficiary distribution
-                    for beneficiary in beneficiaries.iter() {
-                        let allocated_amount = (tip_amount * beneficiary.bps as i128) / 10_000;
-                        
-                        // Skip zero allocations (rounding edge case)
-                        if allocated_amount > 0 {
-                            token_client.transfer(
-                                &customer,
-                                &beneficiary.recipient,
-                                &allocated_amount
-                            );
-                            
-                            events::emit_tip_split(
-                                &env,
-                                payment_id,
-                                beneficiary.recipient.clone(),
-                                allocated_amount,
-                                payment.token.clone(),
-                            );
-                        }
-                    }
-                }
-                None => {
-                    // Fallback: single transfer to merchant (backward compatible)
-                    token_client.transfer(&customer, &payment.merchant, &tip_amount);
-                    events::emit_tip_received(
-                        &env,
-                        payment_id,
-                        payment.merchant.clone(),
-                        tip_amount,
-                        payment.token.clone(),
-                    );
-                }
-            }
+                .set(&DataKey2::FailedDebit(record_id), &rec);
+        } else {
+            let next_retry_ledger = (env.ledger().sequence() as u64) + cfg.base_retry_interval;
+            let rec = FailedDebitRecord {
+                id: record_id,
+                plan_id,
+                merchant: merchant.clone(),
+                customer: customer.clone(),
+                token: token.clone(),
+                amount,
+                attempt_number: 1,
+                next_retry_ledger,
+                status: FailedDebitStatus::Pending,
+                created_at: env.ledger().timestamp(),
+            };
+            env.storage()
+                .persistent()
+                .set(&DataKey2::FailedDebit(record_id), &rec);
+            events::emit_debit_failed(&env, record_id, plan_id, 1, next_retry_ledger);
         }
-
-        Self::complete_payment_internal(&env, payment_id, false);
+        env.storage().persistent().extend_ttl(
+            &DataKey2::FailedDebit(record_id),
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        record_id
     }
 
-    /// Admin sets the global maximum tip in basis points.
-    /// Must be ≤ 10 000 bps (100%).  Default is 3 000 bps (30%).
-    pub fn set_max_tip_bps(env: Env, admin: Address, max_bps: u32) {
-        admin.require_auth();
-        let stored_admin: Address = env
+    /// Customer signs a consent record, authenticating their acceptance.
+    /// No token transfer occurs. Consent record must not be expired or revoked.
+    pub fn sign_consent(env: Env, customer: Address, consent_id: u32) {
+        customer.require_auth();
+        Self::require_not_paused(&env);
+
+        // Load consent record
+        let mut consent: ConsentRecord = env
             .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Not initialized");
-        if admin != stored_admin {
-            panic!("Only admin can set max tip bps");
+            .persistent()
+            .get(&DataKey2::ConsentRecord(consent_id))
+            .unwrap_or_else(|| panic_with_error!(&env, MerchantError::ConsentNotFound));
+
+        // Validate consent state
+        if consent.is_revoked {
+            panic_with_error!(&env, MerchantError::ConsentRevoked);
         }
-        if max_bps > 10_000 {
-            panic!("max_tip_bps cannot exceed 10 000");
+        if consent.is_signed {
+            panic_with_error!(&env, MerchantError::ConsentAlreadySigned);
         }
-        env.storage().instance().set(&DataKey2::MaxTipBps, &max_bps);
+        if consent.expires_at <= env.ledger().sequence() as u64 {
+            panic_with_error!(&env, MerchantError::ConsentExpired);
+        }
+        if consent.customer != customer {
+            panic_with_error!(&env, MerchantError::NotConsentCustomer);
+        }
+
+        // Mark as signed
+        consent.is_signed = true;
+        consent.signed_at = env.ledger().timestamp();
+
+        // Store updated consent
         env.storage()
-            .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+            .persistent()
+            .set(&DataKey2::ConsentRecord(consent_id), &consent);
+        env.storage().persistent().extend_ttl(
+            &DataKey2::ConsentRecord(consent_id),
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        // Emit event
+        events::emit_consent_signed(&env, consent_id, customer, consent.signed_at);
     }
 
-    /// Admin sets KYB verification hash for a merchant (#310)
-    pub fn set_merchant_kyb(
+    /// Check if a consent record is valid (signed, not expired, not revoked).
+    pub fn is_consent_valid(
         env: Env,
-        admin: Address,
         merchant: Address,
-        kyb_hash: BytesN<32>,
-        expiry_ledger: u64,
-        jurisdiction: String,
-    ) {
-        admin.require_auth();
-        let stored_admin: Address = env
+        customer: Address,
+        terms_version: String,
+    ) -> bool {
+        // Try to get consent ID from index
+        let consent_id_opt: Option<u32> = env.storage().persistent().get(&DataKey2::ConsentIndex(
+            merchant,
+            customer,
+            terms_version,
+        ));
+
+        if let Some(consent_id) = consent_id_opt {
+            if let Some(consent) = env
+                .storage()
+                .persistent()
+                .get::<_, ConsentRecord>(&DataKey2::ConsentRecord(consent_id))
+            {
+                // Extend TTL on read
+                env.storage().persistent().extend_ttl(
+                    &DataKey2::ConsentRecord(consent_id),
+                    PERSISTENT_LIFETIME_THRESHOLD,
+                    PERSISTENT_BUMP_AMOUNT,
+                );
+
+                // Check validity: signed, not expired, not revoked
+                return consent.is_signed
+                    && !consent.is_revoked
+                    && consent.expires_at > env.ledger().sequence() as u64;
+            }
+        }
+
+        false
+    }
+
+    /// Revoke a consent record (e.g., on account closure).
+    /// Only the merchant or admin can revoke.
+    pub fn revoke_consent(env: Env, caller: Address, consent_id: u32) {
+        caller.require_auth();
+        Self::require_not_paused(&env);
+
+        // Load consent record
+        let mut consent: ConsentRecord = env
+            .storage()
+            .persistent()
+            .get(&DataKey2::ConsentRecord(consent_id))
+            .unwrap_or_else(|| panic_with_error!(&env, MerchantError::ConsentNotFound));
+
+        // Validate caller is merchant or admin
+        let admin: Address = env
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
-        if admin != stored_admin {
-            panic!("Only admin can set merchant KYB");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
+        if caller != consent.merchant && caller != admin {
+            panic_with_error!(&env, MerchantError::NotConsentRevoker);
         }
 
-        let kyb = MerchantKYB {
-            kyb_hash: kyb_hash.clone(),
-            expiry_ledger,
-            jurisdiction: jurisdiction.clone(),
-            revoked: false,
-        };
+        // Mark as revoked
+        consent.is_revoked = true;
 
+        // Store updated consent
         env.storage()
             .persistent()
-            .set(&DataKey2::MerchantKYB(merchant.clone()), &kyb);
+            .set(&DataKey2::ConsentRecord(consent_id), &consent);
         env.storage().persistent().extend_ttl(
-            &DataKey2::MerchantKYB(merchant.clone()),
+            &DataKey2::ConsentRecord(consent_id),
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        events::emit_merchant_kyb_set(&env, merchant, kyb_hash, expiry_ledger, jurisdiction);
+        // Emit event
+        events::emit_consent_revoked(&env, consent_id, caller, env.ledger().timestamp());
     }
 
-    /// Admin toggles KYB enforcement globally (#310)
-    pub fn set_kyb_enforcement(env: Env, admin: Address, enabled: bool) {
-        admin.require_auth();
-        let stored_admin: Address = env
+    /// Get a consent record by ID.
+    pub fn get_consent_record(env: Env, consent_id: u32) -> ConsentRecord {
+        let consent: ConsentRecord = env
             .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Not initialized");
-        if admin != stored_admin {
-            panic!("Only admin can set KYB enforcement");
-        }
+            .persistent()
+            .get(&DataKey2::ConsentRecord(consent_id))
+            .unwrap_or_else(|| panic_with_error!(&env, MerchantError::ConsentNotFound));
+
+        env.storage().persistent().extend_ttl(
+            &DataKey2::ConsentRecord(consent_id),
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
 
+        consent
+    }
+
+    /// Get consent ID by (merchant, customer, terms_version) triple.
+    pub fn get_consent_id(
+        env: Env,
+        merchant: Address,
+        customer: Address,
+        terms_version: String,
+    ) -> Option<u32> {
         env.storage()
+            .persistent()
+            .get(&DataKey2::ConsentIndex(merchant, customer, terms_version))
+    }
+
+    fn next_consent_id(env: &Env) -> u32 {
+        let counter: u32 = env
+            .storage()
             .instance()
-            .set(&DataKey2::KYBEnforcementEnabled, &enabled);
+            .get(&DataKey2::ConsentRecordCounter)
+            .unwrap_or(0);
+        let next_id = counter
+            .checked_add(1)
+            .unwrap_or_else(|| panic_with_error!(env, Error::ArithmeticOverflow));
+        env.storage()
+            .instance()
+            .set(&DataKey2::ConsentRecordCounter, &next_id);
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        next_id
     }
 
-    /// Admin revokes a merchant's KYB verification (#310)
-    pub fn revoke_merchant_kyb(env: Env, admin: Address, merchant: Address) {
-        admin.require_auth();
-        let stored_admin: Address = env
+    /// Retry a failed debit. Enforces the exponential back-off schedule.
+    /// Returns `RetryNotDue` if the back-off interval has not elapsed.
+    /// On max attempts the record is moved to `AbandonedDebit` status.
+    pub fn retry_failed_debit(env: Env, record_id: u32) {
+        Self::require_not_paused(&env);
+
+        let mut rec: FailedDebitRecord = env
             .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Not initialized");
-        if admin != stored_admin {
-            panic!("Only admin can revoke merchant KYB");
+            .persistent()
+            .get(&DataKey2::FailedDebit(record_id))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::DebitRecordNotFound));
+
+        if rec.status == FailedDebitStatus::Abandoned {
+            panic_with_error!(&env, Error::DebitAlreadyAbandoned);
+        }
+        if rec.status == FailedDebitStatus::Succeeded {
+            panic_with_error!(&env, Error::DebitAlreadySucceeded);
         }
 
-        if let Some(mut kyb) = env
+        let current_ledger = env.ledger().sequence() as u64;
+        if current_ledger < rec.next_retry_ledger {
+            panic_with_error!(&env, Error::RetryNotDue);
+        }
+
+        let cfg: RetryConfig = env
             .storage()
             .persistent()
-            .get::<_, MerchantKYB>(&DataKey2::MerchantKYB(merchant.clone()))
-        {
-            kyb.revoked = true;
+            .get(&DataKey2::RetryConfig)
+            .unwrap_or(RetryConfig {
+                base_retry_interval: DEFAULT_BASE_RETRY_INTERVAL,
+                max_retry_interval: DEFAULT_MAX_RETRY_INTERVAL,
+                max_retry_attempts: DEFAULT_MAX_RETRY_ATTEMPTS,
+            });
+
+        let client = token::Client::new(&env, &rec.token);
+        let result = client.try_transfer_from(
+            &env.current_contract_address(),
+            &rec.customer,
+            &rec.merchant,
+            &rec.amount,
+        );
+
+        if result.is_ok() {
+            rec.status = FailedDebitStatus::Succeeded;
             env.storage()
                 .persistent()
-                .set(&DataKey2::MerchantKYB(merchant.clone()), &kyb);
+                .set(&DataKey2::FailedDebit(record_id), &rec);
             env.storage().persistent().extend_ttl(
-                &DataKey2::MerchantKYB(merchant.clone()),
+                &DataKey2::FailedDebit(record_id),
                 PERSISTENT_LIFETIME_THRESHOLD,
                 PERSISTENT_BUMP_AMOUNT,
             );
+            events::emit_debit_retry_succeeded(&env, record_id, rec.plan_id, rec.amount);
+        } else {
+            rec.attempt_number += 1;
+            if rec.attempt_number > cfg.max_retry_attempts {
+                rec.status = FailedDebitStatus::Abandoned;
+                env.storage()
+                    .persistent()
+                    .set(&DataKey2::FailedDebit(record_id), &rec);
+                env.storage().persistent().extend_ttl(
+                    &DataKey2::FailedDebit(record_id),
+                    PERSISTENT_LIFETIME_THRESHOLD,
+                    PERSISTENT_BUMP_AMOUNT,
+                );
+                events::emit_debit_abandoned(&env, record_id, rec.plan_id);
+            } else {
+                // Double the back-off, capped at max_retry_interval
+                let raw_interval = cfg.base_retry_interval.saturating_mul(
+                    1u64.checked_shl((rec.attempt_number - 1).min(63) as u32)
+                        .unwrap_or(u64::MAX),
+                );
+                let interval = raw_interval.min(cfg.max_retry_interval);
+                rec.next_retry_ledger = current_ledger + interval;
+                env.storage()
+                    .persistent()
+                    .set(&DataKey2::FailedDebit(record_id), &rec);
+                env.storage().persistent().extend_ttl(
+                    &DataKey2::FailedDebit(record_id),
+                    PERSISTENT_LIFETIME_THRESHOLD,
+                    PERSISTENT_BUMP_AMOUNT,
+                );
+                events::emit_debit_failed(
+                    &env,
+                    record_id,
+                    rec.plan_id,
+                    rec.attempt_number,
+                    rec.next_retry_ledger,
+                );
+            }
         }
 
-        events::emit_merchant_kyb_revoked(&env, merchant);
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
     }
 
-    /// Get merchant KYB status (#310)
-    pub fn get_merchant_kyb_status(env: Env, merchant: Address) -> KYBStatus {
-        let current_ledger = env.ledger().sequence() as u64;
+    /// Customer triggers an early retry regardless of the back-off schedule,
+    /// e.g. after topping up their wallet.
+    pub fn trigger_early_retry(env: Env, customer: Address, record_id: u32) {
+        Self::require_not_paused(&env);
+        customer.require_auth();
 
-        if let Some(kyb) = env
+        let mut rec: FailedDebitRecord = env
             .storage()
             .persistent()
-            .get::<_, MerchantKYB>(&DataKey2::MerchantKYB(merchant.clone()))
-        {
+            .get(&DataKey2::FailedDebit(record_id))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::DebitRecordNotFound));
+
+        if rec.customer != customer {
+            panic_with_error!(&env, PaymentError::NotPaymentCustomer);
+        }
+        if rec.status == FailedDebitStatus::Abandoned {
+            panic_with_error!(&env, Error::DebitAlreadyAbandoned);
+        }
+        if rec.status == FailedDebitStatus::Succeeded {
+            panic_with_error!(&env, Error::DebitAlreadySucceeded);
+        }
+
+        let cfg: RetryConfig = env
+            .storage()
+            .persistent()
+            .get(&DataKey2::RetryConfig)
+            .unwrap_or(RetryConfig {
+                base_retry_interval: DEFAULT_BASE_RETRY_INTERVAL,
+                max_retry_interval: DEFAULT_MAX_RETRY_INTERVAL,
+                max_retry_attempts: DEFAULT_MAX_RETRY_ATTEMPTS,
+            });
+
+        let client = token::Client::new(&env, &rec.token);
+        let result = client.try_transfer_from(
+            &env.current_contract_address(),
+            &rec.customer,
+            &rec.merchant,
+            &rec.amount,
+        );
+
+        if result.is_ok() {
+            rec.status = FailedDebitStatus::Succeeded;
+            env.storage()
+                .persistent()
+                .set(&DataKey2::FailedDebit(record_id), &rec);
             env.storage().persistent().extend_ttl(
-                &DataKey2::MerchantKYB(merchant),
+                &DataKey2::FailedDebit(record_id),
                 PERSISTENT_LIFETIME_THRESHOLD,
                 PERSISTENT_BUMP_AMOUNT,
             );
-
-            let verified = !kyb.revoked && current_ledger <= kyb.expiry_ledger;
-            KYBStatus {
-                verified,
-                expiry_ledger: kyb.expiry_ledger,
-                jurisdiction: kyb.jurisdiction,
-            }
+            events::emit_debit_retry_succeeded(&env, record_id, rec.plan_id, rec.amount);
         } else {
-            KYBStatus {
-                verified: false,
-                expiry_ledger: 0,
-                jurisdiction: String::from_slice(&env, ""),
+            rec.attempt_number += 1;
+            if rec.attempt_number > cfg.max_retry_attempts {
+                rec.status = FailedDebitStatus::Abandoned;
+                env.storage()
+                    .persistent()
+                    .set(&DataKey2::FailedDebit(record_id), &rec);
+                env.storage().persistent().extend_ttl(
+                    &DataKey2::FailedDebit(record_id),
+                    PERSISTENT_LIFETIME_THRESHOLD,
+                    PERSISTENT_BUMP_AMOUNT,
+                );
+                events::emit_debit_abandoned(&env, record_id, rec.plan_id);
+            } else {
+                let raw_interval = cfg.base_retry_interval.saturating_mul(
+                    1u64.checked_shl((rec.attempt_number - 1).min(63) as u32)
+                        .unwrap_or(u64::MAX),
+                );
+                let interval = raw_interval.min(cfg.max_retry_interval);
+                rec.next_retry_ledger = (env.ledger().sequence() as u64) + interval;
+                env.storage()
+                    .persistent()
+                    .set(&DataKey2::FailedDebit(record_id), &rec);
+                env.storage().persistent().extend_ttl(
+                    &DataKey2::FailedDebit(record_id),
+                    PERSISTENT_LIFETIME_THRESHOLD,
+                    PERSISTENT_BUMP_AMOUNT,
+                );
+                events::emit_debit_failed(
+                    &env,
+                    record_id,
+                    rec.plan_id,
+                    rec.attempt_number,
+                    rec.next_retry_ledger,
+                );
             }
         }
-    }
 
-    /// Check if KYB enforcement is enabled (#310)
-    pub fn is_kyb_enforcement_enabled(env: Env) -> bool {
-        env.storage()
-            .instance()
-            .get::<_, bool>(&DataKey2::KYBEnforcementEnabled)
-            .unwrap_or(false)
-    }
-    /// Returns the current maximum tip in basis points (default: 3 000).
-    pub fn get_max_tip_bps(env: Env) -> u32 {
         env.storage()
             .instance()
-            .get(&DataKey2::MaxTipBps)
-            .unwrap_or(DEFAULT_MAX_TIP_BPS)
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
     }
 
-    // =========================================================================
-    // Tip Split Configuration (#370)
-    // =========================================================================
-
-    /// Configure tip split allocation for a merchant.
-    /// Validates that basis points sum to exactly 10,000 and beneficiary count <= MAX_TIP_SPLIT_BENEFICIARIES.
-    pub fn set_tip_split_config(
-        env: Env,
-        merchant: Address,
-        caller: Address,
-        split_map: soroban_sdk::Vec<(Address, u32)>,
-    ) {
-        caller.require_auth();
-        
-        // Validate caller is merchant (in production, add admin override if needed)
-        if caller != merchant {
-            panic!("Only merchant can configure tip splits");
-        }
-        
-        // Validate non-empty
-        if split_map.is_empty() {
-            panic!("Tip split configuration cannot be empty");
-        }
-        
-        // Validate max beneficiaries
-        if split_map.len() > MAX_TIP_SPLIT_BENEFICIARIES {
-            panic!("Tip split beneficiaries exceed maximum allowed");
-        }
-        
-        // Validate total bps sum to 10,000
-        let mut total_bps: u32 = 0;
-        for entry in split_map.iter() {
-            total_bps = total_bps.checked_add(entry.1).expect("BPS overflow");
-        }
-        if total_bps != 10_000 {
-            panic!("Tip split basis points must sum to exactly 10,000");
-        }
-        
-        // Convert to Vec<TipSplitBeneficiary> and store
-        let mut beneficiaries: soroban_sdk::Vec<TipSplitBeneficiary> = soroban_sdk::Vec::new(&env);
-        for entry in split_map.iter() {
-            beneficiaries.push_back(TipSplitBeneficiary {
-                recipient: entry.0,
-                bps: entry.1,
-            });
-        }
-        
-        env.storage()
-            .persistent()
-            .set(&DataKey3::TipSplitConfig(merchant.clone()), &beneficiaries);
+    /// Read a failed debit record by ID.
+    pub fn get_failed_debit(env: Env, record_id: u32) -> FailedDebitRecord {
         env.storage()
             .persistent()
-            .extend_ttl(
-                &DataKey3::TipSplitConfig(merchant),
-                PERSISTENT_LIFETIME_THRESHOLD,
-                PERSISTENT_BUMP_AMOUNT,
-            );
+            .get(&DataKey2::FailedDebit(record_id))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::DebitRecordNotFound))
     }
 
-    /// Retrieve tip split configuration for a merchant.
-    /// Returns None if no configuration exists (fallback to default behavior).
-    pub fn get_tip_split_config(
-        env: Env,
-        merchant: Address,
-    ) -> Option<soroban_sdk::Vec<(Address, u32)>> {
-        let config: Option<soroban_sdk::Vec<TipSplitBeneficiary>> = env
-            .storage()
+    /// Read the current retry configuration.
+    pub fn get_retry_config(env: Env) -> RetryConfig {
+        env.storage()
             .persistent()
-            .get(&DataKey3::TipSplitConfig(merchant));
-        
-        config.map(|beneficiaries| {
-            let mut result: soroban_sdk::Vec<(Address, u32)> = soroban_sdk::Vec::new(&env);
-            for b in beneficiaries.iter() {
-                result.push_back((b.recipient, b.bps));
-            }
-            result
-        })
+            .get(&DataKey2::RetryConfig)
+            .unwrap_or(RetryConfig {
+                base_retry_interval: DEFAULT_BASE_RETRY_INTERVAL,
+                max_retry_interval: DEFAULT_MAX_RETRY_INTERVAL,
+                max_retry_attempts: DEFAULT_MAX_RETRY_ATTEMPTS,
+            })
     }
 
-    /// Remove tip split configuration for a merchant.
-    /// Reverts to default behavior (100% to merchant).
-    pub fn remove_tip_split_config(
+    // ── #351: Recurring Payment Scheduler ────────────────────────────────────
+
+    /// Create a recurring payment schedule.
+    /// The payer must separately call `token::approve` on the token contract to grant
+    /// this contract the spending allowance for `amount * max_cycles` (or the
+    /// pre-approved spending module equivalently) before each execution.
+    /// Returns the schedule_id.
+    pub fn create_recurring_payment(
         env: Env,
-        merchant: Address,
-        caller: Address,
-    ) {
-        caller.require_auth();
-        
-        // Validate caller is merchant (in production, add admin override if needed)
-        if caller != merchant {
-            panic!("Only merchant can remove tip split configuration");
+        payer: Address,
+        payee: Address,
+        token: Address,
+        amount: i128,
+        interval_seconds: u64,
+        max_cycles: u32,
+    ) -> u32 {
+        Self::require_not_paused(&env);
+        payer.require_auth();
+
+        if amount <= 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
         }
-        
+        if interval_seconds == 0 {
+            panic_with_error!(&env, SubscriptionError::InvalidInterval);
+        }
+
+        let mut counter: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey3::RecurringCounter)
+            .unwrap_or(0);
+        counter += 1;
+        env.storage()
+            .instance()
+            .set(&DataKey3::RecurringCounter, &counter);
+
+        let schedule_id = counter;
+        let now = env.ledger().timestamp();
+
+        let schedule = RecurringPaymentSchedule {
+            schedule_id,
+            payer: payer.clone(),
+            payee: payee.clone(),
+            token: token.clone(),
+            amount,
+            interval_seconds,
+            max_cycles,
+            cycles_executed: 0,
+            next_due: now,
+            active: true,
+        };
+
+        env.storage()
+            .persistent()
+            .set(&DataKey3::RecurringSchedule(schedule_id), &schedule);
+        env.storage().persistent().extend_ttl(
+            &DataKey3::RecurringSchedule(schedule_id),
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
         env.storage()
-            .persistent()
-            .remove(&DataKey3::TipSplitConfig(merchant));
-    }
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 
-    // =========================================================================
-    // Consent Records (#307): Zero-Amount Agreement Signing
-    // =========================================================================
+        schedule_id
+    }
 
-    /// Create a consent record for a customer to sign terms without token transfer.
-    /// Merchant calls this to create a record keyed by (merchant, customer, terms_version).
-    /// Returns the consent_id.
-    pub fn create_consent_record(
-        env: Env,
-        merchant: Address,
-        customer: Address,
-        terms_hash: BytesN<32>,
-        terms_version: String,
-        expiry_ledger: u64,
-    ) -> u32 {
-        merchant.require_auth();
+    /// Execute a recurring payment schedule.
+    /// Callable by anyone once `ledger_timestamp >= next_due`; `keeper`
+    /// receives the keeper bounty.
+    /// Transfers `amount` from payer to payee via a pre-approved token allowance.
+    /// Auto-cancels the schedule when `max_cycles` is reached.
+    pub fn execute_recurring(env: Env, keeper: Address, schedule_id: u32) {
         Self::require_not_paused(&env);
 
-        // Validate inputs
-        if terms_version.len() == 0 || terms_version.len() > 64 {
-            panic!("terms_version must be 1-64 bytes");
+        let mut schedule: RecurringPaymentSchedule = env
+            .storage()
+            .persistent()
+            .get(&DataKey3::RecurringSchedule(schedule_id))
+            .unwrap_or_else(|| {
+                panic_with_error!(&env, SubscriptionError::RecurringScheduleNotFound)
+            });
+
+        if !schedule.active {
+            panic_with_error!(&env, SubscriptionError::RecurringScheduleInactive);
         }
-        if expiry_ledger <= env.ledger().sequence() as u64 {
-            panic!("expiry_ledger must be in the future");
+
+        let now = env.ledger().timestamp();
+        if now < schedule.next_due {
+            panic_with_error!(&env, SubscriptionError::IntervalNotElapsed);
         }
 
-        // Get next consent ID
-        let consent_id = Self::next_consent_id(&env);
+        let client = token::Client::new(&env, &schedule.token);
+        client.transfer_from(
+            &env.current_contract_address(),
+            &schedule.payer,
+            &schedule.payee,
+            &schedule.amount,
+        );
 
-        // Create consent record
-        let now = env.ledger().timestamp();
-        let consent = ConsentRecord {
-            id: consent_id,
-            merchant: merchant.clone(),
-            customer: customer.clone(),
-            terms_hash: terms_hash.clone(),
-            terms_version: terms_version.clone(),
-            created_at: now,
-            expires_at: expiry_ledger,
-            is_signed: false,
-            signed_at: 0,
-            is_revoked: false,
-        };
+        schedule.cycles_executed += 1;
+        let cycle = schedule.cycles_executed;
+        let next_due = now + schedule.interval_seconds;
+        schedule.next_due = next_due;
+
+        let auto_cancelled = schedule.max_cycles > 0 && cycle >= schedule.max_cycles;
+        if auto_cancelled {
+            schedule.active = false;
+        }
 
-        // Store consent record
         env.storage()
             .persistent()
-            .set(&DataKey2::ConsentRecord(consent_id), &consent);
-
-        // Index by (merchant, customer, terms_version)
-        env.storage().persistent().set(
-            &DataKey2::ConsentIndex(merchant.clone(), customer.clone(), terms_version.clone()),
-            &consent_id,
-        );
-
-        // Extend TTL
-        env.storage().persistent().extend_ttl(
-            &DataKey2::ConsentRecord(consent_id),
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
+            .set(&DataKey3::RecurringSchedule(schedule_id), &schedule);
         env.storage().persistent().extend_ttl(
-            &DataKey2::ConsentIndex(merchant.clone(), customer.clone(), terms_version.clone()),
+            &DataKey3::RecurringSchedule(schedule_id),
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
-        events::emit_consent_record_created(
+
+        events::emit_recurring_payment_executed(
             &env,
-            consent_id,
-            merchant,
-            customer,
-            terms_hash,
-            terms_version,
+            schedule_id,
+            cycle,
+            schedule.amount,
+            if auto_cancelled { 0 } else { next_due },
         );
-        consent_id
+        Self::pay_keeper_reward(&env, &keeper, 1);
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
     }
-    // ── #329: Failed Auto-Debit Retry Queue ───────────────────────────────────
 
-    /// Admin configures the retry back-off schedule.
-    /// `base_retry_interval`: ledgers before first retry (default 100).
-    /// `max_retry_interval`: back-off cap in ledgers (default 3 200).
-    /// `max_retry_attempts`: maximum retry attempts before abandonment (default 5).
-    pub fn set_retry_config(
-        env: Env,
-        admin: Address,
-        base_retry_interval: u64,
-        max_retry_interval: u64,
-        max_retry_attempts: u32,
-    ) {
-        admin.require_auth();
-        let stored_admin: Address = env
+    /// Payer cancels their recurring payment schedule at any time.
+    pub fn cancel_recurring_payment(env: Env, payer: Address, schedule_id: u32) {
+        Self::require_not_paused(&env);
+        payer.require_auth();
+
+        let mut schedule: RecurringPaymentSchedule = env
             .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Not initialized");
-        if admin != stored_admin {
-            panic!("Only admin can set retry config");
-        }
-        if base_retry_interval == 0 {
-            panic!("base_retry_interval must be positive");
-        }
-        if max_retry_interval < base_retry_interval {
-            panic!("max_retry_interval must be >= base_retry_interval");
+            .persistent()
+            .get(&DataKey3::RecurringSchedule(schedule_id))
+            .unwrap_or_else(|| {
+                panic_with_error!(&env, SubscriptionError::RecurringScheduleNotFound)
+            });
+
+        if schedule.payer != payer {
+            panic_with_error!(&env, SubscriptionError::NotSchedulePayer);
         }
-        if max_retry_attempts == 0 {
-            panic!("max_retry_attempts must be at least 1");
+
+        if !schedule.active {
+            panic_with_error!(&env, SubscriptionError::RecurringScheduleInactive);
         }
-        let cfg = RetryConfig {
-            base_retry_interval,
-            max_retry_interval,
-            max_retry_attempts,
-        };
-        env.storage().persistent().set(&DataKey2::RetryConfig, &cfg);
+
+        schedule.active = false;
+
+        env.storage()
+            .persistent()
+            .set(&DataKey3::RecurringSchedule(schedule_id), &schedule);
         env.storage().persistent().extend_ttl(
-            &DataKey2::RetryConfig,
+            &DataKey3::RecurringSchedule(schedule_id),
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+
+        events::emit_recurring_payment_cancelled(&env, schedule_id, payer);
+
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
     }
 
-    /// Merchant initiates a pull-payment from a pre-approved customer allowance.
-    /// If the customer's balance is sufficient the payment settles immediately.
-    /// On insufficient balance the contract stores a `FailedDebitRecord` and
-    /// emits `DebitFailed` instead of reverting, returning the new record ID.
-    pub fn initiate_allowed_payment(
+    /// Get a recurring payment schedule by ID.
+    pub fn get_recurring_schedule(env: Env, schedule_id: u32) -> RecurringPaymentSchedule {
+        env.storage()
+            .persistent()
+            .get(&DataKey3::RecurringSchedule(schedule_id))
+            .unwrap_or_else(|| {
+                panic_with_error!(&env, SubscriptionError::RecurringScheduleNotFound)
+            })
+    }
+
+    // ── Payment Streams ──────────────────────────────────────────────────────
+
+    /// Sender deposits `deposit` up front and streams it to `recipient` at
+    /// `rate_per_second` from `start_time` (0 = now). Nothing is streamed
+    /// before `cliff_time` (0 = no cliff). With an `end_time` (0 = none) the
+    /// deposit may not exceed what the stream pays out by then.
+    /// Returns the stream_id.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_stream(
         env: Env,
-        merchant: Address,
-        customer: Address,
+        sender: Address,
+        recipient: Address,
         token: Address,
-        amount: i128,
-        plan_id: u32,
+        deposit: i128,
+        rate_per_second: i128,
+        start_time: u64,
+        cliff_time: u64,
+        end_time: u64,
     ) -> u32 {
         Self::require_not_paused(&env);
-        merchant.require_auth();
-        if amount <= 0 {
-            panic!("amount must be positive");
-        }
+        sender.require_auth();
         Self::require_token_allowed(&env, &token);
-        Self::require_merchant_approved(&env, &merchant);
-
-        let cfg: RetryConfig = env
-            .storage()
-            .persistent()
-            .get(&DataKey2::RetryConfig)
-            .unwrap_or(RetryConfig {
-                base_retry_interval: DEFAULT_BASE_RETRY_INTERVAL,
-                max_retry_interval: DEFAULT_MAX_RETRY_INTERVAL,
-                max_retry_attempts: DEFAULT_MAX_RETRY_ATTEMPTS,
-            });
 
-        let client = token::Client::new(&env, &token);
-        let result = client.try_transfer_from(
-            &env.current_contract_address(),
-            &customer,
-            &merchant,
-            &amount,
-        );
+        let now = env.ledger().timestamp();
+        let start_time = if start_time == 0 { now } else { start_time };
+        let cliff_time = if cliff_time == 0 { start_time } else { cliff_time };
+        if deposit <= 0
+            || rate_per_second <= 0
+            || sender == recipient
+            || start_time < now
+            || cliff_time < start_time
+            || (end_time != 0 && (end_time <= start_time || cliff_time > end_time))
+        {
+            panic_with_error!(&env, SubscriptionError::InvalidStream);
+        }
+        if end_time != 0
+            && deposit > rate_per_second.saturating_mul((end_time - start_time) as i128)
+        {
+            panic_with_error!(&env, SubscriptionError::InvalidStream);
+        }
 
-        let record_id: u32 = env
-            .storage()
-            .persistent()
-            .get(&DataKey2::FailedDebitCounter)
-            .unwrap_or(0u32)
-            + 1;
-        env.storage()
-            .persistent()
-            .set(&DataKey2::FailedDebitCounter, &record_id);
-        env.storage().persistent().extend_ttl(
-            &DataKey2::FailedDebitCounter,
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
+        token::Client::new(&env, &token).transfer(
+            &sender,
+            env.current_contract_address(),
+            &deposit,
         );
 
-        if result.is_ok() {
-            let rec = FailedDebitRecord {
-                id: record_id,
-                plan_id,
-                merchant: merchant.clone(),
-                customer: customer.clone(),
-                token: token.clone(),
-                amount,
-                attempt_number: 1,
-                next_retry_ledger: 0,
-                status: FailedDebitStatus::Succeeded,
-                created_at: env.ledger().timestamp(),
-            };
-            env.storage()
-                .persistent()
-                .set(&DataKey2::FailedDebit(record_id), &rec);
-        } else {
-            let next_retry_ledger = (env.ledger().sequence() as u64) + cfg.base_retry_interval;
-            let rec = FailedDebitRecord {
-                id: record_id,
-                plan_id,
-                merchant: merchant.clone(),
-                customer: customer.clone(),
-                token: token.clone(),
-                amount,
-                attempt_number: 1,
-                next_retry_ledger,
-                status: FailedDebitStatus::Pending,
-                created_at: env.ledger().timestamp(),
-            };
-            env.storage()
-                .persistent()
-                .set(&DataKey2::FailedDebit(record_id), &rec);
-            events::emit_debit_failed(&env, record_id, plan_id, 1, next_retry_ledger);
+        let stream_id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey3::StreamCounter)
+            .unwrap_or(0)
+            + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey3::StreamCounter, &stream_id);
+        let stream = PaymentStream {
+            stream_id,
+            sender: sender.clone(),
+            recipient: recipient.clone(),
+            token: token.clone(),
+            rate_per_second,
+            start_time,
+            streamed_at_start: 0,
+            cliff_time,
+            end_time,
+            deposited: deposit,
+            withdrawn: 0,
+            status: StreamStatus::Active,
+        };
+        Self::save_stream(&env, &stream);
+
+        events::emit_stream_created(
+            &env,
+            stream_id,
+            sender,
+            recipient,
+            token,
+            deposit,
+            rate_per_second,
+        );
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        stream_id
+    }
+
+    /// Sender adds funds to an active stream. An open-ended stream runs for
+    /// longer, and one that has run dry resumes accruing from the top-up
+    /// rather than for the time it sat empty. A stream with an end time can
+    /// be topped up to its full amount.
+    pub fn top_up_stream(env: Env, sender: Address, stream_id: u32, amount: i128) {
+        Self::require_not_paused(&env);
+        sender.require_auth();
+
+        let mut stream = Self::load_stream(&env, stream_id);
+        if stream.sender != sender {
+            panic_with_error!(&env, SubscriptionError::NotStreamParty);
+        }
+        if stream.status != StreamStatus::Active {
+            panic_with_error!(&env, SubscriptionError::StreamNotActive);
+        }
+        let deposited = stream.deposited + amount;
+        if amount <= 0
+            || (stream.end_time != 0
+                && deposited
+                    > stream
+                        .rate_per_second
+                        .saturating_mul((stream.end_time - stream.start_time) as i128))
+        {
+            panic_with_error!(&env, SubscriptionError::InvalidStream);
         }
-        env.storage().persistent().extend_ttl(
-            &DataKey2::FailedDebit(record_id),
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
+
+        token::Client::new(&env, &stream.token).transfer(
+            &sender,
+            env.current_contract_address(),
+            &amount,
         );
-        record_id
+        let now = env.ledger().timestamp();
+        if stream.end_time == 0 && Self::streamed_amount(&stream, now) >= stream.deposited {
+            stream.streamed_at_start = stream.deposited;
+            stream.start_time = now;
+        }
+        stream.deposited = deposited;
+        Self::save_stream(&env, &stream);
+
+        events::emit_stream_topped_up(&env, stream_id, amount, deposited);
     }
 
-    /// Customer signs a consent record, authenticating their acceptance.
-    /// No token transfer occurs. Consent record must not be expired or revoked.
-    pub fn sign_consent(env: Env, customer: Address, consent_id: u32) {
-        customer.require_auth();
+    /// Recipient withdraws everything streamed so far, less the protocol fee.
+    /// Returns the amount received.
+    pub fn withdraw_from_stream(env: Env, recipient: Address, stream_id: u32) -> i128 {
         Self::require_not_paused(&env);
+        recipient.require_auth();
 
-        // Load consent record
-        let mut consent: ConsentRecord = env
-            .storage()
-            .persistent()
-            .get(&DataKey2::ConsentRecord(consent_id))
-            .expect("Consent record not found");
+        let mut stream = Self::load_stream(&env, stream_id);
+        if stream.recipient != recipient {
+            panic_with_error!(&env, SubscriptionError::NotStreamParty);
+        }
+        if stream.status != StreamStatus::Active {
+            panic_with_error!(&env, SubscriptionError::StreamNotActive);
+        }
+        let now = env.ledger().timestamp();
+        let amount = Self::streamed_amount(&stream, now) - stream.withdrawn;
+        if amount <= 0 {
+            panic_with_error!(&env, SubscriptionError::NothingToWithdraw);
+        }
 
-        // Validate consent state
-        if consent.is_revoked {
-            panic!("Consent record has been revoked");
+        let received = Self::pay_stream_recipient(&env, &stream, amount);
+        stream.withdrawn += amount;
+        // Fully paid out: past the end time, or dry for an open-ended stream
+        if stream.withdrawn == stream.deposited && (stream.end_time == 0 || now >= stream.end_time)
+        {
+            stream.status = StreamStatus::Completed;
         }
-        if consent.is_signed {
-            panic!("Consent record already signed");
+        Self::save_stream(&env, &stream);
+        received
+    }
+
+    /// Sender or recipient cancels an active stream. The recipient is paid
+    /// everything streamed up to now and the sender is refunded the rest.
+    pub fn cancel_stream(env: Env, caller: Address, stream_id: u32) {
+        Self::require_not_paused(&env);
+        caller.require_auth();
+
+        let mut stream = Self::load_stream(&env, stream_id);
+        if caller != stream.sender && caller != stream.recipient {
+            panic_with_error!(&env, SubscriptionError::NotStreamParty);
         }
-        if consent.expires_at <= env.ledger().sequence() as u64 {
-            panic!("Consent record has expired");
+        if stream.status != StreamStatus::Active {
+            panic_with_error!(&env, SubscriptionError::StreamNotActive);
         }
-        if consent.customer != customer {
-            panic!("Only the customer can sign this consent");
+
+        let streamed = Self::streamed_amount(&stream, env.ledger().timestamp());
+        let recipient_amount = streamed - stream.withdrawn;
+        let sender_refund = stream.deposited - streamed;
+        if recipient_amount > 0 {
+            Self::pay_stream_recipient(&env, &stream, recipient_amount);
+        }
+        if sender_refund > 0 {
+            token::Client::new(&env, &stream.token).transfer(
+                &env.current_contract_address(),
+                &stream.sender,
+                &sender_refund,
+            );
         }
+        stream.deposited = streamed;
+        stream.withdrawn = streamed;
+        stream.status = StreamStatus::Cancelled;
+        Self::save_stream(&env, &stream);
 
-        // Mark as signed
-        consent.is_signed = true;
-        consent.signed_at = env.ledger().timestamp();
+        events::emit_stream_cancelled(&env, stream_id, caller, recipient_amount, sender_refund);
+    }
 
-        // Store updated consent
+    /// Recipient hands the recipient role to `new_recipient`. Funds streamed
+    /// so far are first paid to the outgoing recipient.
+    pub fn transfer_stream(env: Env, recipient: Address, stream_id: u32, new_recipient: Address) {
+        Self::require_not_paused(&env);
+        recipient.require_auth();
+
+        let mut stream = Self::load_stream(&env, stream_id);
+        if stream.recipient != recipient {
+            panic_with_error!(&env, SubscriptionError::NotStreamParty);
+        }
+        if stream.status != StreamStatus::Active {
+            panic_with_error!(&env, SubscriptionError::StreamNotActive);
+        }
+        if new_recipient == recipient || new_recipient == stream.sender {
+            panic_with_error!(&env, SubscriptionError::InvalidStream);
+        }
+
+        let accrued =
+            Self::streamed_amount(&stream, env.ledger().timestamp()) - stream.withdrawn;
+        if accrued > 0 {
+            Self::pay_stream_recipient(&env, &stream, accrued);
+            stream.withdrawn += accrued;
+        }
+        stream.recipient = new_recipient.clone();
+        Self::save_stream(&env, &stream);
+
+        events::emit_stream_recipient_changed(&env, stream_id, recipient, new_recipient);
+    }
+
+    /// Get a payment stream by ID.
+    pub fn get_stream(env: Env, stream_id: u32) -> PaymentStream {
+        Self::load_stream(&env, stream_id)
+    }
+
+    /// Gross amount the recipient could withdraw now, before fees.
+    pub fn get_stream_withdrawable(env: Env, stream_id: u32) -> i128 {
+        let stream = Self::load_stream(&env, stream_id);
+        if stream.status != StreamStatus::Active {
+            return 0;
+        }
+        Self::streamed_amount(&stream, env.ledger().timestamp()) - stream.withdrawn
+    }
+
+    /// Internal: total streamed by `now`, capped at the deposit.
+    fn streamed_amount(stream: &PaymentStream, now: u64) -> i128 {
+        if now < stream.cliff_time {
+            return 0;
+        }
+        let until = if stream.end_time != 0 {
+            now.min(stream.end_time)
+        } else {
+            now
+        };
+        let elapsed = until.saturating_sub(stream.start_time) as i128;
+        elapsed
+            .saturating_mul(stream.rate_per_second)
+            .saturating_add(stream.streamed_at_start)
+            .min(stream.deposited)
+    }
+
+    /// Internal: pay streamed funds to the recipient, charging the fee tier
+    /// for their rolling volume. Returns the amount received.
+    fn pay_stream_recipient(env: &Env, stream: &PaymentStream, amount: i128) -> i128 {
+        let rolling_before = Self::rolling_merchant_volume(env, &stream.recipient);
+        let fee_bps = Self::fee_bps_for_volume(env, rolling_before + amount);
+        let fee = amount * fee_bps as i128 / 10_000;
+        let token_client = token::Client::new(env, &stream.token);
+        if fee > 0 {
+            let fee_recipient: Address = env
+                .storage()
+                .instance()
+                .get(&DataKey::FeeRecipient)
+                .unwrap_or_else(|| {
+                    panic_with_error!(env, PaymentError::FeeRecipientNotConfigured)
+                });
+            token_client.transfer(&env.current_contract_address(), &fee_recipient, &fee);
+        }
+        let received = amount - fee;
+        token_client.transfer(&env.current_contract_address(), &stream.recipient, &received);
+        Self::inc_merchant_volume_bucket(env, &stream.recipient, amount);
+
+        events::emit_stream_withdrawn(
+            env,
+            stream.stream_id,
+            stream.recipient.clone(),
+            amount,
+            fee,
+        );
+        received
+    }
+
+    fn load_stream(env: &Env, stream_id: u32) -> PaymentStream {
         env.storage()
             .persistent()
-            .set(&DataKey2::ConsentRecord(consent_id), &consent);
-        env.storage().persistent().extend_ttl(
-            &DataKey2::ConsentRecord(consent_id),
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
+            .get(&DataKey3::Stream(stream_id))
+            .unwrap_or_else(|| panic_with_error!(env, SubscriptionError::StreamNotFound))
+    }
 
-        // Emit event
-        events::emit_consent_signed(&env, consent_id, customer, consent.signed_at);
+    fn save_stream(env: &Env, stream: &PaymentStream) {
+        let key = DataKey3::Stream(stream.stream_id);
+        env.storage().persistent().set(&key, stream);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
     }
 
-    /// Check if a consent record is valid (signed, not expired, not revoked).
-    pub fn is_consent_valid(
+    // ── Keeper Rewards ───────────────────────────────────────────────────────
+
+    /// Admin sets the bounty paid to callers of the permissionless maintenance
+    /// calls (`bulk_expire_payments`, `execute_scheduled_payment`,
+    /// `execute_recurring`, `trigger_invoice_cycle`). The pool token can only
+    /// change while the pool is empty.
+    pub fn set_keeper_config(
         env: Env,
-        merchant: Address,
-        customer: Address,
-        terms_version: String,
-    ) -> bool {
-        // Try to get consent ID from index
-        let consent_id_opt: Option<u32> = env.storage().persistent().get(&DataKey2::ConsentIndex(
-            merchant,
-            customer,
-            terms_version,
-        ));
+        admin: Address,
+        token: Address,
+        bounty_per_item: i128,
+        max_reward_per_call: i128,
+        max_reward_per_ledger: i128,
+    ) {
+        Self::require_admin(&env, &admin);
+        if bounty_per_item <= 0 || max_reward_per_call <= 0 || max_reward_per_ledger <= 0 {
+            panic_with_error!(&env, ConfigError::InvalidKeeperConfig);
+        }
+        if let Some(current) = Self::get_keeper_config(env.clone()) {
+            if current.token != token && Self::get_keeper_pool(env.clone()).balance > 0 {
+                panic_with_error!(&env, ConfigError::InvalidKeeperConfig);
+            }
+        }
+        Self::require_token_allowed(&env, &token);
 
-        if let Some(consent_id) = consent_id_opt {
-            if let Some(consent) = env
-                .storage()
-                .persistent()
-                .get::<_, ConsentRecord>(&DataKey2::ConsentRecord(consent_id))
-            {
-                // Extend TTL on read
-                env.storage().persistent().extend_ttl(
-                    &DataKey2::ConsentRecord(consent_id),
-                    PERSISTENT_LIFETIME_THRESHOLD,
-                    PERSISTENT_BUMP_AMOUNT,
-                );
+        let config = KeeperConfig {
+            token: token.clone(),
+            bounty_per_item,
+            max_reward_per_call,
+            max_reward_per_ledger,
+        };
+        env.storage().instance().set(&DataKey3::KeeperConfig, &config);
+        events::emit_keeper_config_set(
+            &env,
+            token,
+            bounty_per_item,
+            max_reward_per_call,
+            max_reward_per_ledger,
+        );
 
-                // Check validity: signed, not expired, not revoked
-                return consent.is_signed
-                    && !consent.is_revoked
-                    && consent.expires_at > env.ledger().sequence() as u64;
-            }
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Get the keeper bounty terms, if a keeper pool is configured.
+    pub fn get_keeper_config(env: Env) -> Option<KeeperConfig> {
+        env.storage().instance().get(&DataKey3::KeeperConfig)
+    }
+
+    /// Get the keeper pool balance and the current ledger's payout tally.
+    pub fn get_keeper_pool(env: Env) -> KeeperPool {
+        ahjoor_types::keeper::keeper_pool(&env, &DataKey3::KeeperPool)
+    }
+
+    /// Admin tops up the keeper pool in the configured keeper token.
+    pub fn fund_keeper_pool(env: Env, admin: Address, amount: i128) {
+        Self::require_admin(&env, &admin);
+        if amount <= 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
         }
+        let config = Self::get_keeper_config(env.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, ConfigError::KeeperNotConfigured));
 
-        false
+        token::Client::new(&env, &config.token).transfer(
+            &admin,
+            env.current_contract_address(),
+            &amount,
+        );
+        let mut pool = Self::get_keeper_pool(env.clone());
+        pool.balance += amount;
+        env.storage().instance().set(&DataKey3::KeeperPool, &pool);
+        events::emit_keeper_pool_funded(&env, amount, pool.balance);
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
     }
 
-    /// Revoke a consent record (e.g., on account closure).
-    /// Only the merchant or admin can revoke.
-    pub fn revoke_consent(env: Env, caller: Address, consent_id: u32) {
-        caller.require_auth();
-        Self::require_not_paused(&env);
+    /// Admin withdraws unspent funds from the keeper pool.
+    pub fn withdraw_keeper_pool(env: Env, admin: Address, amount: i128) {
+        Self::require_admin(&env, &admin);
+        if amount <= 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+        let config = Self::get_keeper_config(env.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, ConfigError::KeeperNotConfigured));
+        let mut pool = Self::get_keeper_pool(env.clone());
+        if amount > pool.balance {
+            panic_with_error!(&env, ConfigError::InsufficientKeeperPool);
+        }
 
-        // Load consent record
-        let mut consent: ConsentRecord = env
-            .storage()
-            .persistent()
-            .get(&DataKey2::ConsentRecord(consent_id))
-            .expect("Consent record not found");
+        pool.balance -= amount;
+        env.storage().instance().set(&DataKey3::KeeperPool, &pool);
+        token::Client::new(&env, &config.token).transfer(
+            &env.current_contract_address(),
+            &admin,
+            &amount,
+        );
+        events::emit_keeper_pool_withdrawn(&env, amount, pool.balance);
 
-        // Validate caller is merchant or admin
-        let admin: Address = env
-            .storage()
+        env.storage()
             .instance()
-            .get(&DataKey::Admin)
-            .expect("Not initialized");
-        if caller != consent.merchant && caller != admin {
-            panic!("Only merchant or admin can revoke consent");
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Internal: pay `keeper` the bounty for `items` processed by a maintenance
+    /// call from the keeper pool. Returns the amount paid.
+    fn pay_keeper_reward(env: &Env, keeper: &Address, items: u32) -> i128 {
+        let reward = ahjoor_types::keeper::pay_keeper_reward(
+            env,
+            &DataKey3::KeeperConfig,
+            &DataKey3::KeeperPool,
+            keeper,
+            items,
+        );
+        if reward > 0 {
+            events::emit_keeper_rewarded(env, keeper.clone(), items, reward);
         }
+        reward
+    }
 
-        // Mark as revoked
-        consent.is_revoked = true;
+    // ── Installment Plans (BNPL) ─────────────────────────────────────────────
 
-        // Store updated consent
+    /// Merchant sets the late-payment terms applied to payment plans created
+    /// from now on. Existing plans keep the terms they were created with.
+    pub fn set_plan_terms(
+        env: Env,
+        merchant: Address,
+        grace_period_ledgers: u32,
+        late_fee_bps: u32,
+        default_after_ledgers: u32,
+    ) {
+        merchant.require_auth();
+        if late_fee_bps > 10_000 {
+            panic_with_error!(&env, SubscriptionError::InvalidPlanTerms);
+        }
+        if default_after_ledgers <= grace_period_ledgers {
+            panic_with_error!(&env, SubscriptionError::InvalidPlanTerms);
+        }
+
+        let terms = PlanTerms {
+            grace_period_ledgers,
+            late_fee_bps,
+            default_after_ledgers,
+        };
         env.storage()
             .persistent()
-            .set(&DataKey2::ConsentRecord(consent_id), &consent);
+            .set(&DataKey3::PlanTerms(merchant.clone()), &terms);
         env.storage().persistent().extend_ttl(
-            &DataKey2::ConsentRecord(consent_id),
+            &DataKey3::PlanTerms(merchant),
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        // Emit event
-        events::emit_consent_revoked(&env, consent_id, caller, env.ledger().timestamp());
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
     }
 
-    /// Get a consent record by ID.
-    pub fn get_consent_record(env: Env, consent_id: u32) -> ConsentRecord {
-        let consent: ConsentRecord = env
-            .storage()
+    /// Get a merchant's payment plan terms, falling back to the contract defaults.
+    pub fn get_plan_terms(env: Env, merchant: Address) -> PlanTerms {
+        env.storage()
             .persistent()
-            .get(&DataKey2::ConsentRecord(consent_id))
-            .expect("Consent record not found");
+            .get(&DataKey3::PlanTerms(merchant))
+            .unwrap_or(PlanTerms {
+                grace_period_ledgers: DEFAULT_PLAN_GRACE_LEDGERS,
+                late_fee_bps: DEFAULT_PLAN_LATE_FEE_BPS,
+                default_after_ledgers: DEFAULT_PLAN_DEFAULT_AFTER_LEDGERS,
+            })
+    }
 
-        env.storage().persistent().extend_ttl(
-            &DataKey2::ConsentRecord(consent_id),
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
+    /// Merchant deposits liquidity into their upfront payout pool for `token`.
+    pub fn fund_plan_pool(env: Env, merchant: Address, token: Address, amount: i128) {
+        Self::require_not_paused(&env);
+        merchant.require_auth();
+        if amount <= 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+        Self::require_token_allowed(&env, &token);
 
-        consent
-    }
+        token::Client::new(&env, &token).transfer(
+            &merchant,
+            env.current_contract_address(),
+            &amount,
+        );
+        let balance = Self::get_plan_pool(env.clone(), merchant.clone(), token.clone()) + amount;
+        Self::set_plan_pool(&env, &merchant, &token, balance);
 
-    /// Get consent ID by (merchant, customer, terms_version) triple.
-    pub fn get_consent_id(
-        env: Env,
-        merchant: Address,
-        customer: Address,
-        terms_version: String,
-    ) -> Option<u32> {
         env.storage()
-            .persistent()
-            .get(&DataKey2::ConsentIndex(merchant, customer, terms_version))
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
     }
 
-    fn next_consent_id(env: &Env) -> u32 {
-        let counter: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey2::ConsentRecordCounter)
-            .unwrap_or(0);
-        let next_id = counter.checked_add(1).expect("consent ID overflow");
-        env.storage()
-            .instance()
-            .set(&DataKey2::ConsentRecordCounter, &next_id);
+    /// Merchant withdraws unused liquidity from their upfront payout pool.
+    pub fn withdraw_plan_pool(env: Env, merchant: Address, token: Address, amount: i128) {
+        merchant.require_auth();
+        if amount <= 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+        let balance = Self::get_plan_pool(env.clone(), merchant.clone(), token.clone());
+        if amount > balance {
+            panic_with_error!(&env, SubscriptionError::InsufficientPlanPool);
+        }
+
+        Self::set_plan_pool(&env, &merchant, &token, balance - amount);
+        token::Client::new(&env, &token).transfer(
+            &env.current_contract_address(),
+            &merchant,
+            &amount,
+        );
+
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        next_id
     }
 
-    /// Retry a failed debit. Enforces the exponential back-off schedule.
-    /// Returns `RetryNotDue` if the back-off interval has not elapsed.
-    /// On max attempts the record is moved to `AbandonedDebit` status.
-    pub fn retry_failed_debit(env: Env, record_id: u32) {
-        Self::require_not_paused(&env);
-
-        let mut rec: FailedDebitRecord = env
-            .storage()
+    /// Get the merchant's upfront payout pool balance for `token`.
+    pub fn get_plan_pool(env: Env, merchant: Address, token: Address) -> i128 {
+        env.storage()
             .persistent()
-            .get(&DataKey2::FailedDebit(record_id))
-            .unwrap_or_else(|| panic_with_error!(&env, Error::DebitRecordNotFound));
+            .get(&DataKey3::PlanPool(merchant, token))
+            .unwrap_or(0)
+    }
 
-        if rec.status == FailedDebitStatus::Abandoned {
-            panic_with_error!(&env, Error::DebitAlreadyAbandoned);
+    /// Create an installment plan. The customer pays `down_payment` now and the
+    /// rest in `num_installments` equal installments, the first due
+    /// `interval_ledgers` from now. Installments are pulled via `transfer_from`,
+    /// so the customer must `approve` this contract for the financed amount
+    /// plus any late fees.
+    ///
+    /// With `upfront_payout` the merchant receives the financed amount
+    /// immediately from their plan pool, and installments repay the pool.
+    /// Late-payment terms are snapshotted from `get_plan_terms`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_payment_plan(
+        env: Env,
+        customer: Address,
+        merchant: Address,
+        token: Address,
+        total_amount: i128,
+        down_payment: i128,
+        num_installments: u32,
+        interval_ledgers: u32,
+        upfront_payout: bool,
+    ) -> u32 {
+        Self::require_not_paused(&env);
+        customer.require_auth();
+        if upfront_payout {
+            merchant.require_auth();
         }
-        if rec.status == FailedDebitStatus::Succeeded {
-            panic_with_error!(&env, Error::DebitAlreadySucceeded);
+        if total_amount <= 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
         }
-
-        let current_ledger = env.ledger().sequence() as u64;
-        if current_ledger < rec.next_retry_ledger {
-            panic_with_error!(&env, Error::RetryNotDue);
+        if down_payment < 0 || down_payment >= total_amount {
+            panic_with_error!(&env, SubscriptionError::InvalidPlanTerms);
         }
+        if num_installments == 0 {
+            panic_with_error!(&env, SubscriptionError::InvalidPlanTerms);
+        }
+        if interval_ledgers == 0 {
+            panic_with_error!(&env, SubscriptionError::InvalidInterval);
+        }
+        Self::require_token_allowed(&env, &token);
+        Self::require_merchant_approved(&env, &merchant);
 
-        let cfg: RetryConfig = env
-            .storage()
-            .persistent()
-            .get(&DataKey2::RetryConfig)
-            .unwrap_or(RetryConfig {
-                base_retry_interval: DEFAULT_BASE_RETRY_INTERVAL,
-                max_retry_interval: DEFAULT_MAX_RETRY_INTERVAL,
-                max_retry_attempts: DEFAULT_MAX_RETRY_ATTEMPTS,
-            });
+        let financed = total_amount - down_payment;
+        let installment_amount = financed / num_installments as i128;
+        if installment_amount == 0 {
+            panic_with_error!(&env, SubscriptionError::InvalidPlanTerms);
+        }
 
-        let client = token::Client::new(&env, &rec.token);
-        let result = client.try_transfer_from(
-            &env.current_contract_address(),
-            &rec.customer,
-            &rec.merchant,
-            &rec.amount,
-        );
+        let terms = Self::get_plan_terms(env.clone(), merchant.clone());
+        let token_client = token::Client::new(&env, &token);
 
-        if result.is_ok() {
-            rec.status = FailedDebitStatus::Succeeded;
-            env.storage()
-                .persistent()
-                .set(&DataKey2::FailedDebit(record_id), &rec);
-            env.storage().persistent().extend_ttl(
-                &DataKey2::FailedDebit(record_id),
-                PERSISTENT_LIFETIME_THRESHOLD,
-                PERSISTENT_BUMP_AMOUNT,
-            );
-            events::emit_debit_retry_succeeded(&env, record_id, rec.plan_id, rec.amount);
-        } else {
-            rec.attempt_number += 1;
-            if rec.attempt_number > cfg.max_retry_attempts {
-                rec.status = FailedDebitStatus::Abandoned;
-                env.storage()
-                    .persistent()
-                    .set(&DataKey2::FailedDebit(record_id), &rec);
-                env.storage().persistent().extend_ttl(
-                    &DataKey2::FailedDebit(record_id),
-                    PERSISTENT_LIFETIME_THRESHOLD,
-                    PERSISTENT_BUMP_AMOUNT,
-                );
-                events::emit_debit_abandoned(&env, record_id, rec.plan_id);
-            } else {
-                // Double the back-off, capped at max_retry_interval
-                let raw_interval = cfg.base_retry_interval.saturating_mul(
-                    1u64.checked_shl((rec.attempt_number - 1).min(63) as u32)
-                        .unwrap_or(u64::MAX),
-                );
-                let interval = raw_interval.min(cfg.max_retry_interval);
-                rec.next_retry_ledger = current_ledger + interval;
-                env.storage()
-                    .persistent()
-                    .set(&DataKey2::FailedDebit(record_id), &rec);
-                env.storage().persistent().extend_ttl(
-                    &DataKey2::FailedDebit(record_id),
-                    PERSISTENT_LIFETIME_THRESHOLD,
-                    PERSISTENT_BUMP_AMOUNT,
-                );
-                events::emit_debit_failed(
-                    &env,
-                    record_id,
-                    rec.plan_id,
-                    rec.attempt_number,
-                    rec.next_retry_ledger,
-                );
+        if down_payment > 0 {
+            token_client.transfer(&customer, &merchant, &down_payment);
+        }
+        if upfront_payout {
+            let pool = Self::get_plan_pool(env.clone(), merchant.clone(), token.clone());
+            if pool < financed {
+                panic_with_error!(&env, SubscriptionError::InsufficientPlanPool);
             }
+            Self::set_plan_pool(&env, &merchant, &token, pool - financed);
+            token_client.transfer(&env.current_contract_address(), &merchant, &financed);
         }
 
+        let plan_id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey3::PaymentPlanCounter)
+            .unwrap_or(0)
+            + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey3::PaymentPlanCounter, &plan_id);
+
+        let now = env.ledger().sequence() as u64;
+        let last_due = now + interval_ledgers as u64 * num_installments as u64;
+        let plan = PaymentPlan {
+            plan_id,
+            customer: customer.clone(),
+            merchant: merchant.clone(),
+            token,
+            total_amount,
+            num_installments,
+            installment_amount,
+            interval_ledgers,
+            expiry_ledger: last_due + terms.default_after_ledgers as u64,
+            next_due_ledger: now + interval_ledgers as u64,
+            installments_paid: 0,
+            status: PlanStatus::Active,
+            down_payment,
+            amount_paid: down_payment,
+            late_fees_paid: 0,
+            grace_period_ledgers: terms.grace_period_ledgers,
+            late_fee_bps: terms.late_fee_bps,
+            default_after_ledgers: terms.default_after_ledgers,
+            upfront_payout,
+        };
+        Self::save_payment_plan(&env, &plan);
+
+        events::emit_payment_plan_created(
+            &env,
+            plan_id,
+            customer,
+            merchant,
+            total_amount,
+            num_installments,
+            upfront_payout,
+        );
+
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        plan_id
     }
 
-    /// Customer triggers an early retry regardless of the back-off schedule,
-    /// e.g. after topping up their wallet.
-    pub fn trigger_early_retry(env: Env, customer: Address, record_id: u32) {
+    /// Collect the next installment once it is due. Callable by anyone.
+    /// A late fee is added when the installment is collected after the
+    /// grace period. Nothing can be collected past the plan's `expiry_ledger`.
+    /// Returns the total amount pulled from the customer.
+    pub fn collect_installment(env: Env, plan_id: u32) -> i128 {
         Self::require_not_paused(&env);
-        customer.require_auth();
-
-        let mut rec: FailedDebitRecord = env
-            .storage()
-            .persistent()
-            .get(&DataKey2::FailedDebit(record_id))
-            .unwrap_or_else(|| panic_with_error!(&env, Error::DebitRecordNotFound));
-
-        if rec.customer != customer {
-            panic!("Only the debited customer may trigger an early retry");
+        let mut plan = Self::get_payment_plan(env.clone(), plan_id);
+        if plan.status != PlanStatus::Active {
+            panic_with_error!(&env, SubscriptionError::PaymentPlanNotActive);
         }
-        if rec.status == FailedDebitStatus::Abandoned {
-            panic_with_error!(&env, Error::DebitAlreadyAbandoned);
+
+        let now = env.ledger().sequence() as u64;
+        if now < plan.next_due_ledger {
+            panic_with_error!(&env, SubscriptionError::InstallmentNotDue);
         }
-        if rec.status == FailedDebitStatus::Succeeded {
-            panic_with_error!(&env, Error::DebitAlreadySucceeded);
+        if now > plan.expiry_ledger {
+            panic_with_error!(&env, SubscriptionError::PaymentPlanExpired);
         }
 
-        let cfg: RetryConfig = env
-            .storage()
-            .persistent()
-            .get(&DataKey2::RetryConfig)
-            .unwrap_or(RetryConfig {
-                base_retry_interval: DEFAULT_BASE_RETRY_INTERVAL,
-                max_retry_interval: DEFAULT_MAX_RETRY_INTERVAL,
-                max_retry_attempts: DEFAULT_MAX_RETRY_ATTEMPTS,
-            });
-
-        let client = token::Client::new(&env, &rec.token);
-        let result = client.try_transfer_from(
+        let amount = Self::plan_installment_amount(&plan, plan.installments_paid);
+        let late_fee = Self::plan_late_fee(&plan, amount, now);
+        token::Client::new(&env, &plan.token).transfer_from(
             &env.current_contract_address(),
-            &rec.customer,
-            &rec.merchant,
-            &rec.amount,
-        );
-
-        if result.is_ok() {
-            rec.status = FailedDebitStatus::Succeeded;
-            env.storage()
-                .persistent()
-                .set(&DataKey2::FailedDebit(record_id), &rec);
-            env.storage().persistent().extend_ttl(
-                &DataKey2::FailedDebit(record_id),
-                PERSISTENT_LIFETIME_THRESHOLD,
-                PERSISTENT_BUMP_AMOUNT,
-            );
-            events::emit_debit_retry_succeeded(&env, record_id, rec.plan_id, rec.amount);
+            &plan.customer,
+            &Self::plan_payee(&env, &plan),
+            &(amount + late_fee),
+        );
+        if plan.upfront_payout {
+            let pool = Self::get_plan_pool(env.clone(), plan.merchant.clone(), plan.token.clone());
+            Self::set_plan_pool(&env, &plan.merchant, &plan.token, pool + amount + late_fee);
+        }
+
+        plan.installments_paid += 1;
+        plan.amount_paid += amount;
+        plan.late_fees_paid += late_fee;
+        let completed = plan.installments_paid == plan.num_installments;
+        if completed {
+            plan.status = PlanStatus::Completed;
+            plan.next_due_ledger = 0;
         } else {
-            rec.attempt_number += 1;
-            if rec.attempt_number > cfg.max_retry_attempts {
-                rec.status = FailedDebitStatus::Abandoned;
-                env.storage()
-                    .persistent()
-                    .set(&DataKey2::FailedDebit(record_id), &rec);
-                env.storage().persistent().extend_ttl(
-                    &DataKey2::FailedDebit(record_id),
-                    PERSISTENT_LIFETIME_THRESHOLD,
-                    PERSISTENT_BUMP_AMOUNT,
-                );
-                events::emit_debit_abandoned(&env, record_id, rec.plan_id);
-            } else {
-                let raw_interval = cfg.base_retry_interval.saturating_mul(
-                    1u64.checked_shl((rec.attempt_number - 1).min(63) as u32)
-                        .unwrap_or(u64::MAX),
-                );
-                let interval = raw_interval.min(cfg.max_retry_interval);
-                rec.next_retry_ledger = (env.ledger().sequence() as u64) + interval;
-                env.storage()
-                    .persistent()
-                    .set(&DataKey2::FailedDebit(record_id), &rec);
-                env.storage().persistent().extend_ttl(
-                    &DataKey2::FailedDebit(record_id),
-                    PERSISTENT_LIFETIME_THRESHOLD,
-                    PERSISTENT_BUMP_AMOUNT,
-                );
-                events::emit_debit_failed(
-                    &env,
-                    record_id,
-                    rec.plan_id,
-                    rec.attempt_number,
-                    rec.next_retry_ledger,
-                );
-            }
+            plan.next_due_ledger += plan.interval_ledgers as u64;
+        }
+        Self::save_payment_plan(&env, &plan);
+
+        events::emit_installment_collected(
+            &env,
+            plan_id,
+            plan.installments_paid,
+            amount,
+            late_fee,
+            plan.next_due_ledger,
+        );
+        if completed {
+            events::emit_payment_plan_completed(&env, plan_id, false);
         }
 
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-    }
-
-    /// Read a failed debit record by ID.
-    pub fn get_failed_debit(env: Env, record_id: u32) -> FailedDebitRecord {
-        env.storage()
-            .persistent()
-            .get(&DataKey2::FailedDebit(record_id))
-            .unwrap_or_else(|| panic_with_error!(&env, Error::DebitRecordNotFound))
-    }
 
-    /// Read the current retry configuration.
-    pub fn get_retry_config(env: Env) -> RetryConfig {
-        env.storage()
-            .persistent()
-            .get(&DataKey2::RetryConfig)
-            .unwrap_or(RetryConfig {
-                base_retry_interval: DEFAULT_BASE_RETRY_INTERVAL,
-                max_retry_interval: DEFAULT_MAX_RETRY_INTERVAL,
-                max_retry_attempts: DEFAULT_MAX_RETRY_ATTEMPTS,
-            })
+        amount + late_fee
     }
 
-    // ── #351: Recurring Payment Scheduler ────────────────────────────────────
-
-    /// Create a recurring payment schedule.
-    /// The payer must separately call `token::approve` on the token contract to grant
-    /// this contract the spending allowance for `amount * max_cycles` (or the
-    /// pre-approved spending module equivalently) before each execution.
-    /// Returns the schedule_id.
-    pub fn create_recurring_payment(
-        env: Env,
-        payer: Address,
-        payee: Address,
-        token: Address,
-        amount: i128,
-        interval_seconds: u64,
-        max_cycles: u32,
-    ) -> u32 {
+    /// Customer pays off the remaining balance early. A late fee is still
+    /// charged for every installment already past its grace period.
+    /// Returns the total amount paid.
+    pub fn pay_off_payment_plan(env: Env, customer: Address, plan_id: u32) -> i128 {
         Self::require_not_paused(&env);
-        payer.require_auth();
+        customer.require_auth();
+        let mut plan = Self::get_payment_plan(env.clone(), plan_id);
+        if plan.customer != customer {
+            panic_with_error!(&env, SubscriptionError::NotPlanCustomer);
+        }
+        if plan.status != PlanStatus::Active {
+            panic_with_error!(&env, SubscriptionError::PaymentPlanNotActive);
+        }
 
-        if amount <= 0 {
-            panic!("Amount must be positive");
+        let now = env.ledger().sequence() as u64;
+        if now > plan.expiry_ledger {
+            panic_with_error!(&env, SubscriptionError::PaymentPlanExpired);
+        }
+        let remaining = plan.total_amount - plan.amount_paid;
+        let mut late_fee = 0i128;
+        let mut due_ledger = plan.next_due_ledger;
+        for index in plan.installments_paid..plan.num_installments {
+            if now <= due_ledger + plan.grace_period_ledgers as u64 {
+                break;
+            }
+            let amount = Self::plan_installment_amount(&plan, index);
+            late_fee += amount * plan.late_fee_bps as i128 / 10_000;
+            due_ledger += plan.interval_ledgers as u64;
         }
-        if interval_seconds == 0 {
-            panic!("Interval must be positive");
+
+        token::Client::new(&env, &plan.token).transfer(
+            &customer,
+            Self::plan_payee(&env, &plan),
+            &(remaining + late_fee),
+        );
+        if plan.upfront_payout {
+            let pool = Self::get_plan_pool(env.clone(), plan.merchant.clone(), plan.token.clone());
+            Self::set_plan_pool(&env, &plan.merchant, &plan.token, pool + remaining + late_fee);
         }
 
-        let mut counter: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey3::RecurringCounter)
-            .unwrap_or(0);
-        counter += 1;
+        plan.installments_paid = plan.num_installments;
+        plan.amount_paid = plan.total_amount;
+        plan.late_fees_paid += late_fee;
+        plan.status = PlanStatus::Completed;
+        plan.next_due_ledger = 0;
+        Self::save_payment_plan(&env, &plan);
+
+        events::emit_payment_plan_completed(&env, plan_id, true);
+
         env.storage()
             .instance()
-            .set(&DataKey3::RecurringCounter, &counter);
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 
-        let schedule_id = counter;
-        let now = env.ledger().timestamp();
+        remaining + late_fee
+    }
 
-        let schedule = RecurringPaymentSchedule {
-            schedule_id,
-            payer: payer.clone(),
-            payee: payee.clone(),
-            token: token.clone(),
-            amount,
-            interval_seconds,
-            max_cycles,
-            cycles_executed: 0,
-            next_due: now,
-            active: true,
-        };
+    /// Mark a plan as defaulted once its current installment has stayed unpaid
+    /// for `default_after_ledgers`. Callable by anyone.
+    pub fn default_payment_plan(env: Env, plan_id: u32) {
+        let mut plan = Self::get_payment_plan(env.clone(), plan_id);
+        if plan.status != PlanStatus::Active {
+            panic_with_error!(&env, SubscriptionError::PaymentPlanNotActive);
+        }
+        let now = env.ledger().sequence() as u64;
+        if now <= plan.next_due_ledger + plan.default_after_ledgers as u64 {
+            panic_with_error!(&env, SubscriptionError::PlanNotInDefault);
+        }
 
-        env.storage()
-            .persistent()
-            .set(&DataKey3::RecurringSchedule(schedule_id), &schedule);
-        env.storage().persistent().extend_ttl(
-            &DataKey3::RecurringSchedule(schedule_id),
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
+        plan.status = PlanStatus::Defaulted;
+        Self::save_payment_plan(&env, &plan);
+
+        events::emit_payment_plan_defaulted(
+            &env,
+            plan_id,
+            plan.installments_paid + 1,
+            plan.total_amount - plan.amount_paid,
         );
 
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-
-        schedule_id
     }
 
-    /// Execute a recurring payment schedule.
-    /// Callable by anyone once `ledger_timestamp >= next_due`.
-    /// Transfers `amount` from payer to payee via a pre-approved token allowance.
-    /// Auto-cancels the schedule when `max_cycles` is reached.
-    pub fn execute_recurring(env: Env, schedule_id: u32) {
-        Self::require_not_paused(&env);
-
-        let mut schedule: RecurringPaymentSchedule = env
-            .storage()
+    /// Get a payment plan by ID.
+    pub fn get_payment_plan(env: Env, plan_id: u32) -> PaymentPlan {
+        env.storage()
             .persistent()
-            .get(&DataKey3::RecurringSchedule(schedule_id))
-            .expect("Recurring schedule not found");
+            .get(&DataKey2::PaymentPlan(plan_id))
+            .unwrap_or_else(|| panic_with_error!(&env, SubscriptionError::PaymentPlanNotFound))
+    }
 
-        if !schedule.active {
-            panic!("Recurring schedule is not active");
+    /// Get the outstanding installments of a plan with their due ledgers and
+    /// amounts. Empty once the plan is completed.
+    pub fn get_plan_schedule(env: Env, plan_id: u32) -> Vec<PlanInstallment> {
+        let plan = Self::get_payment_plan(env.clone(), plan_id);
+        let mut schedule = Vec::new(&env);
+        if plan.status == PlanStatus::Completed {
+            return schedule;
         }
-
-        let now = env.ledger().timestamp();
-        if now < schedule.next_due {
-            panic!("Next execution is not yet due");
+        let mut due_ledger = plan.next_due_ledger;
+        for index in plan.installments_paid..plan.num_installments {
+            schedule.push_back(PlanInstallment {
+                installment_number: index + 1,
+                due_ledger,
+                amount: Self::plan_installment_amount(&plan, index),
+            });
+            due_ledger += plan.interval_ledgers as u64;
         }
+        schedule
+    }
 
-        let client = token::Client::new(&env, &schedule.token);
-        client.transfer_from(
-            &env.current_contract_address(),
-            &schedule.payer,
-            &schedule.payee,
-            &schedule.amount,
-        );
+    fn plan_installment_amount(plan: &PaymentPlan, index: u32) -> i128 {
+        if index + 1 == plan.num_installments {
+            let financed = plan.total_amount - plan.down_payment;
+            financed - plan.installment_amount * (plan.num_installments - 1) as i128
+        } else {
+            plan.installment_amount
+        }
+    }
 
-        schedule.cycles_executed += 1;
-        let cycle = schedule.cycles_executed;
-        let next_due = now + schedule.interval_seconds;
-        schedule.next_due = next_due;
+    fn plan_late_fee(plan: &PaymentPlan, amount: i128, now: u64) -> i128 {
+        if now > plan.next_due_ledger + plan.grace_period_ledgers as u64 {
+            amount * plan.late_fee_bps as i128 / 10_000
+        } else {
+            0
+        }
+    }
 
-        let auto_cancelled = schedule.max_cycles > 0 && cycle >= schedule.max_cycles;
-        if auto_cancelled {
-            schedule.active = false;
+    /// Installments go to the merchant, or back into their pool when the
+    /// merchant was already paid up front.
+    fn plan_payee(env: &Env, plan: &PaymentPlan) -> Address {
+        if plan.upfront_payout {
+            env.current_contract_address()
+        } else {
+            plan.merchant.clone()
         }
+    }
 
-        env.storage()
-            .persistent()
-            .set(&DataKey3::RecurringSchedule(schedule_id), &schedule);
+    fn set_plan_pool(env: &Env, merchant: &Address, token: &Address, balance: i128) {
+        let key = DataKey3::PlanPool(merchant.clone(), token.clone());
+        env.storage().persistent().set(&key, &balance);
         env.storage().persistent().extend_ttl(
-            &DataKey3::RecurringSchedule(schedule_id),
+            &key,
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+    }
 
-        events::emit_recurring_payment_executed(
+    fn save_payment_plan(env: &Env, plan: &PaymentPlan) {
+        let key = DataKey2::PaymentPlan(plan.plan_id);
+        env.storage().persistent().set(&key, plan);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    // ── #354: Multi-Token Invoices ───────────────────────────────────────────
+
+    /// Set the merchant's conversion rate (scaled by 10^6) from `token` into an
+    /// invoice base currency. Rates are snapshotted onto invoices at issuance.
+    pub fn set_invoice_conversion_rate(env: Env, merchant: Address, token: Address, rate_to_base: i128) {
+        multi_token_invoice_impl::MultiTokenInvoiceImpl::set_conversion_rate(
+            &env, merchant, token, rate_to_base,
+        );
+    }
+
+    /// Issue an invoice payable in any of `accepted_tokens` and settled to the
+    /// merchant in `preferred_settlement_token`. Every token involved must pass
+    /// the token whitelist. When `oracle_contract` is set, payers may also use
+    /// `pay_invoice_cross_token` at the oracle's live price.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_multi_token_invoice(
+        env: Env,
+        merchant: Address,
+        customer: Address,
+        total_amount: i128,
+        base_currency: Address,
+        accepted_tokens: Vec<Address>,
+        preferred_settlement_token: Address,
+        line_items: Vec<multi_token_invoice::InvoiceLineItem>,
+        due_date: u64,
+        metadata: Map<String, String>,
+        oracle_contract: Option<Address>,
+    ) -> u32 {
+        Self::require_not_paused(&env);
+        Self::require_merchant_approved(&env, &merchant);
+        Self::require_token_allowed(&env, &base_currency);
+        Self::require_token_allowed(&env, &preferred_settlement_token);
+        for token in accepted_tokens.iter() {
+            Self::require_token_allowed(&env, &token);
+        }
+
+        let invoice_id = multi_token_invoice_impl::MultiTokenInvoiceImpl::create_invoice_with_oracle(
             &env,
-            schedule_id,
-            cycle,
-            schedule.amount,
-            if auto_cancelled { 0 } else { next_due },
+            merchant,
+            customer,
+            total_amount,
+            base_currency,
+            accepted_tokens,
+            preferred_settlement_token,
+            line_items,
+            due_date,
+            metadata,
+            oracle_contract,
         );
 
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        invoice_id
     }
 
-    /// Payer cancels their recurring payment schedule at any time.
-    pub fn cancel_recurring_payment(env: Env, payer: Address, schedule_id: u32) {
+    /// Pay part or all of an invoice in one of its accepted tokens at the rate
+    /// snapshotted at issuance. Payments that would push the combined total over
+    /// the invoice amount are rejected.
+    pub fn pay_multi_token_invoice(
+        env: Env,
+        invoice_id: u32,
+        payer: Address,
+        token: Address,
+        amount: i128,
+    ) -> multi_token_invoice::InvoicePayment {
         Self::require_not_paused(&env);
-        payer.require_auth();
+        Self::require_token_allowed(&env, &token);
+        multi_token_invoice_impl::MultiTokenInvoiceImpl::accept_payment(
+            &env, invoice_id, payer, token, amount,
+        )
+    }
 
-        let mut schedule: RecurringPaymentSchedule = env
-            .storage()
-            .persistent()
-            .get(&DataKey3::RecurringSchedule(schedule_id))
-            .expect("Recurring schedule not found");
+    /// Pay an invoice in an accepted token priced live through the invoice's
+    /// oracle, bounded by `max_slippage_bps` against the snapshotted rate.
+    pub fn pay_invoice_cross_token(
+        env: Env,
+        invoice_id: u32,
+        payer: Address,
+        payment_token: Address,
+        payment_amount: i128,
+        max_slippage_bps: u32,
+    ) -> multi_token_invoice::InvoicePayment {
+        Self::require_not_paused(&env);
+        Self::require_token_allowed(&env, &payment_token);
+        multi_token_invoice_impl::MultiTokenInvoiceImpl::pay_invoice_cross_token(
+            &env,
+            invoice_id,
+            payer,
+            payment_token,
+            payment_amount,
+            max_slippage_bps,
+        )
+    }
 
-        if schedule.payer != payer {
-            panic!("Only the payer can cancel this schedule");
-        }
+    /// Pay out a batch of fully-paid invoices sharing a preferred settlement
+    /// token. The merchant is paid in that token: payments made in another
+    /// token are swapped through the swap router set with `set_swap_router`,
+    /// bounded by the oracle rate and the default slippage tolerance.
+    pub fn settle_multi_token_invoices(
+        env: Env,
+        merchant: Address,
+        invoice_ids: Vec<u32>,
+    ) -> multi_token_invoice::SettlementBatch {
+        Self::require_not_paused(&env);
+        multi_token_invoice_impl::MultiTokenInvoiceImpl::settle_invoices(&env, merchant, invoice_ids)
+    }
 
-        if !schedule.active {
-            panic!("Recurring schedule is already inactive");
-        }
+    /// Cancel an unsettled invoice (merchant only), refunding every payment
+    /// received so far in its original token.
+    pub fn cancel_multi_token_invoice(env: Env, invoice_id: u32) {
+        multi_token_invoice_impl::MultiTokenInvoiceImpl::cancel_invoice(&env, invoice_id);
+    }
 
-        schedule.active = false;
+    pub fn get_multi_token_invoice(env: Env, invoice_id: u32) -> Option<multi_token_invoice::MultiTokenInvoice> {
+        multi_token_invoice_impl::MultiTokenInvoiceImpl::get_invoice(&env, invoice_id)
+    }
 
-        env.storage()
-            .persistent()
-            .set(&DataKey3::RecurringSchedule(schedule_id), &schedule);
-        env.storage().persistent().extend_ttl(
-            &DataKey3::RecurringSchedule(schedule_id),
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
+    pub fn get_invoice_payments(env: Env, invoice_id: u32) -> Vec<multi_token_invoice::InvoicePayment> {
+        multi_token_invoice_impl::MultiTokenInvoiceImpl::get_invoice_payments(&env, invoice_id)
+    }
 
-        events::emit_recurring_payment_cancelled(&env, schedule_id, payer);
+    /// Remaining unpaid amount of an invoice in its base currency.
+    pub fn get_invoice_balance(env: Env, invoice_id: u32) -> i128 {
+        multi_token_invoice_impl::MultiTokenInvoiceImpl::get_invoice_balance(&env, invoice_id)
+    }
+
+    pub fn get_settlement_batch(env: Env, batch_id: u32) -> Option<multi_token_invoice::SettlementBatch> {
+        multi_token_invoice_impl::MultiTokenInvoiceImpl::get_settlement_batch(&env, batch_id)
+    }
+
+    // ── Pre-Approved Spending Allowances ─────────────────────────────────────
+
+    /// Customer grants `merchant` a standing allowance to pull up to
+    /// `total_amount` of `token`, bounded per transaction and per day. The
+    /// customer must also approve this contract as token spender.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_spending_allowance(
+        env: Env,
+        customer: Address,
+        merchant: Address,
+        token: Address,
+        total_amount: i128,
+        per_transaction_limit: i128,
+        daily_limit: i128,
+        expires_at: u64,
+        consent_hash: BytesN<32>,
+        consent_metadata: Map<String, String>,
+    ) -> u32 {
+        Self::require_not_paused(&env);
+        Self::require_token_allowed(&env, &token);
+        Self::require_merchant_approved(&env, &merchant);
+
+        let allowance_id = pre_approved_spending_impl::PreApprovedSpendingImpl::create_allowance(
+            &env,
+            customer,
+            merchant,
+            token,
+            total_amount,
+            per_transaction_limit,
+            daily_limit,
+            expires_at,
+            consent_hash,
+            consent_metadata,
+        );
 
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        allowance_id
     }
 
-    /// Get a recurring payment schedule by ID.
-    pub fn get_recurring_schedule(env: Env, schedule_id: u32) -> RecurringPaymentSchedule {
-        env.storage()
-            .persistent()
-            .get(&DataKey3::RecurringSchedule(schedule_id))
-            .expect("Recurring schedule not found")
+    /// Merchant pulls `amount` against a customer allowance. Every successful
+    /// pull is recorded as an `AllowanceTransaction`.
+    pub fn spend_from_allowance(
+        env: Env,
+        merchant: Address,
+        allowance_id: u32,
+        amount: i128,
+        reference: String,
+    ) -> pre_approved_spending::AllowanceTransaction {
+        Self::require_not_paused(&env);
+        Self::require_merchant_approved(&env, &merchant);
+        pre_approved_spending_impl::PreApprovedSpendingImpl::spend_from_allowance(
+            &env, merchant, allowance_id, amount, reference,
+        )
+    }
+
+    /// Customer temporarily stops all pulls against an allowance.
+    pub fn pause_allowance(env: Env, allowance_id: u32) {
+        pre_approved_spending_impl::PreApprovedSpendingImpl::pause_allowance(&env, allowance_id);
+    }
+
+    pub fn resume_allowance(env: Env, allowance_id: u32) {
+        pre_approved_spending_impl::PreApprovedSpendingImpl::resume_allowance(&env, allowance_id);
+    }
+
+    /// Customer permanently revokes an allowance.
+    pub fn revoke_allowance(env: Env, allowance_id: u32) {
+        pre_approved_spending_impl::PreApprovedSpendingImpl::revoke_allowance(&env, allowance_id);
+    }
+
+    pub fn update_allowance_limits(
+        env: Env,
+        allowance_id: u32,
+        per_transaction_limit: i128,
+        daily_limit: i128,
+    ) {
+        pre_approved_spending_impl::PreApprovedSpendingImpl::update_allowance_limits(
+            &env,
+            allowance_id,
+            per_transaction_limit,
+            daily_limit,
+        );
+    }
+
+    pub fn get_allowance(env: Env, allowance_id: u32) -> Option<pre_approved_spending::SpendingAllowance> {
+        pre_approved_spending_impl::PreApprovedSpendingImpl::get_allowance(&env, allowance_id)
+    }
+
+    pub fn get_allowance_remaining(env: Env, allowance_id: u32) -> i128 {
+        pre_approved_spending_impl::PreApprovedSpendingImpl::get_remaining_balance(&env, allowance_id)
+    }
+
+    /// Paginated allowances granted by a customer, oldest first, revoked ones
+    /// included.
+    /// `page_size` must be in `1..=MAX_CUSTOMER_PAYMENTS_PAGE_SIZE`.
+    pub fn get_customer_allowances(
+        env: Env,
+        customer: Address,
+        page: u32,
+        page_size: u32,
+    ) -> Vec<pre_approved_spending::SpendingAllowance> {
+        pre_approved_spending_impl::PreApprovedSpendingImpl::get_customer_allowances(
+            &env, customer, page, page_size,
+        )
+    }
+
+    /// Paginated allowances granted to a merchant, oldest first, revoked ones
+    /// included.
+    /// `page_size` must be in `1..=MAX_CUSTOMER_PAYMENTS_PAGE_SIZE`.
+    pub fn get_merchant_allowances(
+        env: Env,
+        merchant: Address,
+        page: u32,
+        page_size: u32,
+    ) -> Vec<pre_approved_spending::SpendingAllowance> {
+        pre_approved_spending_impl::PreApprovedSpendingImpl::get_merchant_allowances(
+            &env, merchant, page, page_size,
+        )
+    }
+
+    /// Paginated spend history for an allowance, oldest first.
+    /// `page_size` must be in `1..=MAX_CUSTOMER_PAYMENTS_PAGE_SIZE`.
+    pub fn get_allowance_transactions(
+        env: Env,
+        allowance_id: u32,
+        page: u32,
+        page_size: u32,
+    ) -> Vec<pre_approved_spending::AllowanceTransaction> {
+        pre_approved_spending_impl::PreApprovedSpendingImpl::get_allowance_transactions(
+            &env,
+            allowance_id,
+            page,
+            page_size,
+        )
+    }
+
+    /// Paginated audit log (creation, spends, pause/resume/revoke, limit
+    /// changes) for an allowance, oldest first.
+    pub fn get_allowance_audit_log(
+        env: Env,
+        allowance_id: u32,
+        page: u32,
+        page_size: u32,
+    ) -> Vec<pre_approved_spending::AllowanceAuditLog> {
+        pre_approved_spending_impl::PreApprovedSpendingImpl::get_audit_log(
+            &env,
+            allowance_id,
+            page,
+            page_size,
+        )
     }
 }
 
@@ -9577,5 +12766,27 @@ mod test_retry_queue;
 mod test_spending_limit;
 #[cfg(test)]
 mod test_buyer_trust_tier;
+#[cfg(test)]
+mod test_multi_token_invoice;
+#[cfg(test)]
+mod test_pre_approved_spending;
+#[cfg(test)]
+mod test_payment_plan;
+#[cfg(test)]
+mod test_keeper;
+#[cfg(test)]
+mod test_oracle_set;
+#[cfg(test)]
+mod test_loyalty_program;
+#[cfg(test)]
+mod test_stream;
+#[cfg(test)]
+mod test_subscription_plan;
+#[cfg(test)]
+mod test_metered_subscription;
+#[cfg(test)]
+mod test_subscription_dunning;
 
 pub use events::*;
+#[cfg(test)]
+mod test_errors;
@@ -1,11 +1,6 @@
-#![allow(dead_code)]
-use soroban_sdk::{
-    contract, contracterror, contractclient, contractimpl, contracttype, panic_with_error, token, Address, Bytes,
-    BytesN, Env, Map, String, Symbol, Vec,
-};
+use soroban_sdk::{contractclient, contracterror, contracttype, Address, BytesN, Env, Map, String, Vec};
 
 /// Minimal oracle interface for cross-token price lookups (#354).
-#[allow(dead_code)]
 #[contractclient(name = "InvoiceOracleClient")]
 pub trait InvoiceOracleInterface {
     /// Returns the price of `base` in terms of `quote`, scaled by 1_000_000.
@@ -13,6 +8,23 @@ pub trait InvoiceOracleInterface {
     fn get_price(env: soroban_sdk::Env, base: Address, quote: Address) -> Option<i128>;
 }
 
+/// Swap venue the contract's configured swap router must expose for invoice
+/// settlement.
+#[contractclient(name = "SettlementRouterClient")]
+pub trait SettlementRouterInterface {
+    /// Swaps `amount_in` of `token_in`, already transferred to the router, into
+    /// `token_out` paid to `to`. Returns the amount of `token_out` delivered,
+    /// which must be at least `min_amount_out`.
+    fn swap_exact_in(
+        env: soroban_sdk::Env,
+        token_in: Address,
+        token_out: Address,
+        amount_in: i128,
+        min_amount_out: i128,
+        to: Address,
+    ) -> i128;
+}
+
 /// Record of a cross-token oracle settlement for an invoice (#354).
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -63,7 +75,8 @@ pub struct MultiTokenInvoice {
     pub payments_received: Map<Address, i128>,
     /// Conversion rates for each accepted token to base currency
     pub conversion_rates: Map<Address, i128>,
-    /// Settlement conversion rate (base to preferred settlement)
+    /// Settlement conversion rate (base to preferred settlement), read from
+    /// the oracle at settlement
     pub settlement_conversion_rate: i128,
     /// Optional oracle contract for cross-token settlement (#354).
     /// When set, payers may settle using any accepted token and oracle price lookup
@@ -92,6 +105,8 @@ pub enum InvoiceStatus {
     FullyPaid = 3,
     Overdue = 4,
     Cancelled = 5,
+    /// Paid out to the merchant in the preferred settlement token; terminal.
+    Settled = 6,
 }
 
 #[contracttype]
@@ -124,7 +139,7 @@ pub struct SettlementBatch {
     pub batch_id: u32,
     /// Invoices included in this batch
     pub invoice_ids: Vec<u32>,
-    /// Total amount in settlement currency
+    /// Total paid to the merchant in the preferred settlement token
     pub total_settlement_amount: i128,
     /// Settlement timestamp
     pub settled_at: u64,
@@ -143,22 +158,29 @@ pub enum SettlementStatus {
     Failed = 3,
 }
 
+/// Errors raised by the multi-token invoice entrypoints.
+///
+/// Codes start at 201 so they never collide with the contract-wide `Error` enum.
 #[contracterror]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum MultiTokenInvoiceError {
-    InvoiceNotFound = 1,
-    InvalidInvoiceStatus = 2,
-    PaymentExceedsInvoiceAmount = 3,
-    TokenNotAccepted = 4,
-    ConversionRateNotSet = 5,
-    InvoiceExpired = 6,
-    UnauthorizedAccess = 7,
-    InvalidLineItem = 8,
-    SettlementFailed = 9,
-    InvalidConversionRate = 10,
-    SlippageExceeded = 11,
-    OracleNotConfigured = 12,
-    OraclePriceUnavailable = 13,
+    InvoiceNotFound = 201,
+    InvalidInvoiceStatus = 202,
+    PaymentExceedsInvoiceAmount = 203,
+    TokenNotAccepted = 204,
+    ConversionRateNotSet = 205,
+    InvoiceExpired = 206,
+    UnauthorizedAccess = 207,
+    InvalidLineItem = 208,
+    SettlementFailed = 209,
+    InvalidConversionRate = 210,
+    SlippageExceeded = 211,
+    OracleNotConfigured = 212,
+    OraclePriceUnavailable = 213,
+    /// Invoice was created without any accepted payment token.
+    NoAcceptedTokens = 214,
+    /// Invoices in one settlement batch must share a preferred settlement token.
+    MixedSettlementTokens = 215,
 }
 
 pub trait MultiTokenInvoiceInterface {
@@ -193,14 +215,6 @@ pub trait MultiTokenInvoiceInterface {
         rate_to_base: i128,
     );
 
-    /// Set settlement conversion rate
-    fn set_settlement_conversion_rate(
-        env: Env,
-        merchant: Address,
-        invoice_id: u32,
-        rate: i128,
-    );
-
     /// Get invoice details
     fn get_invoice(env: Env, invoice_id: u32) -> Option<MultiTokenInvoice>;
 
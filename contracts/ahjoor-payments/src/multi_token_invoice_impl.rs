@@ -1,18 +1,17 @@
-#![allow(dead_code)]
-use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, panic_with_error, token, Address, Bytes,
-    BytesN, Env, Map, String, Symbol, Vec,
-};
+use soroban_sdk::{panic_with_error, token, Address, BytesN, Env, Map, String, Symbol, Vec};
+use crate::events;
 use crate::multi_token_invoice::*;
 
 // Storage keys
 const INVOICE_COUNTER_KEY: &str = "invoice_counter";
-const INVOICE_KEY_PREFIX: &str = "invoice_";
-const INVOICE_PAYMENT_KEY_PREFIX: &str = "invoice_payment_";
-const SETTLEMENT_BATCH_KEY_PREFIX: &str = "settlement_batch_";
 const SETTLEMENT_BATCH_COUNTER_KEY: &str = "settlement_batch_counter";
 const PAYMENT_COUNTER_KEY: &str = "payment_counter";
-const MERCHANT_CONVERSION_RATES_KEY_PREFIX: &str = "merchant_rates_";
+/// Maximum number of line items per invoice.
+const MAX_LINE_ITEMS: u32 = 20;
+/// Maximum number of invoices in one settlement batch.
+const MAX_SETTLEMENT_BATCH: u32 = 50;
+/// Conversion rates and oracle prices are scaled by 10^6.
+const RATE_PRECISION: i128 = 1_000_000;
 
 fn invoice_key(env: &Env, id: u32) -> (Symbol, u32) {
     (Symbol::new(env, "invoice"), id)
@@ -20,6 +19,9 @@ fn invoice_key(env: &Env, id: u32) -> (Symbol, u32) {
 fn inv_payment_key(env: &Env, id: u32) -> (Symbol, u32) {
     (Symbol::new(env, "inv_payment"), id)
 }
+fn inv_payment_index_key(env: &Env, invoice_id: u32) -> (Symbol, u32) {
+    (Symbol::new(env, "inv_payments"), invoice_id)
+}
 fn settle_batch_key(env: &Env, id: u32) -> (Symbol, u32) {
     (Symbol::new(env, "settle_batch"), id)
 }
@@ -64,6 +66,10 @@ impl MultiTokenInvoiceImpl {
 
     /// Create a new multi-token invoice with an optional oracle contract for
     /// cross-token settlement price discovery (#354).
+    ///
+    /// The merchant's current conversion rates for the accepted tokens are
+    /// snapshotted onto the invoice, so later rate changes never reprice an
+    /// invoice that has already been issued. The base currency always converts 1:1.
     pub fn create_invoice_with_oracle(
         env: &Env,
         merchant: Address,
@@ -83,7 +89,11 @@ impl MultiTokenInvoiceImpl {
             panic_with_error!(env, MultiTokenInvoiceError::InvalidLineItem);
         }
 
-        if line_items.len() > 20 {
+        if accepted_tokens.is_empty() {
+            panic_with_error!(env, MultiTokenInvoiceError::NoAcceptedTokens);
+        }
+
+        if line_items.len() > MAX_LINE_ITEMS {
             panic_with_error!(env, MultiTokenInvoiceError::InvalidLineItem);
         }
 
@@ -97,6 +107,24 @@ impl MultiTokenInvoiceImpl {
                 .checked_add(item.amount)
                 .unwrap_or_else(|| panic_with_error!(env, MultiTokenInvoiceError::InvalidLineItem));
         }
+        if !line_items.is_empty() && calculated_total != total_amount {
+            panic_with_error!(env, MultiTokenInvoiceError::InvalidLineItem);
+        }
+
+        // Snapshot the merchant's rate table for the accepted tokens
+        let merchant_rates: Map<Address, i128> = env
+            .storage()
+            .persistent()
+            .get(&merch_rates_key(env, &merchant))
+            .unwrap_or_else(|| Map::new(env));
+        let mut conversion_rates: Map<Address, i128> = Map::new(env);
+        for token in accepted_tokens.iter() {
+            if token == base_currency {
+                conversion_rates.set(token, RATE_PRECISION);
+            } else if let Some(rate) = merchant_rates.get(token.clone()) {
+                conversion_rates.set(token, rate);
+            }
+        }
 
         // Get next invoice ID
         let invoice_id: u32 = env
@@ -120,12 +148,12 @@ impl MultiTokenInvoiceImpl {
             total_amount,
             base_currency: base_currency.clone(),
             accepted_tokens,
-            preferred_settlement_token,
+            preferred_settlement_token: preferred_settlement_token.clone(),
             line_items,
             status: InvoiceStatus::Issued,
             payments_received: Map::new(env),
-            conversion_rates: Map::new(env),
-            settlement_conversion_rate: 1_000_000, // 1:1 by default
+            conversion_rates,
+            settlement_conversion_rate: RATE_PRECISION, // 1:1 by default
             oracle_contract,
             metadata,
         };
@@ -137,10 +165,22 @@ impl MultiTokenInvoiceImpl {
             .instance()
             .set(&Symbol::new(env, INVOICE_COUNTER_KEY), &next_id);
 
+        events::emit_multi_token_invoice_created(
+            env,
+            next_id,
+            merchant,
+            customer,
+            total_amount,
+            base_currency,
+            preferred_settlement_token,
+        );
+
         next_id
     }
 
-    /// Accept payment for an invoice
+    /// Accept payment for an invoice in any accepted token at the invoice's
+    /// snapshotted conversion rate. The token amount is escrowed in the contract
+    /// until the invoice is settled or cancelled.
     pub fn accept_payment(
         env: &Env,
         invoice_id: u32,
@@ -156,35 +196,10 @@ impl MultiTokenInvoiceImpl {
 
         // Get invoice
         let key = invoice_key(env, invoice_id);
-        let mut invoice: MultiTokenInvoice = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or_else(|| panic_with_error!(env, MultiTokenInvoiceError::InvoiceNotFound));
-
-        // Check invoice status
-        match invoice.status {
-            InvoiceStatus::Cancelled => {
-                panic_with_error!(env, MultiTokenInvoiceError::InvalidInvoiceStatus);
-            }
-            InvoiceStatus::FullyPaid => {
-                panic_with_error!(env, MultiTokenInvoiceError::PaymentExceedsInvoiceAmount);
-            }
-            _ => {}
-        }
-
-        // Check if token is accepted
-        let mut token_accepted = false;
-        for accepted_token in invoice.accepted_tokens.iter() {
-            if accepted_token == token {
-                token_accepted = true;
-                break;
-            }
-        }
+        let mut invoice: MultiTokenInvoice = Self::load_invoice(env, invoice_id);
 
-        if !token_accepted {
-            panic_with_error!(env, MultiTokenInvoiceError::TokenNotAccepted);
-        }
+        Self::require_payable(env, &invoice);
+        Self::require_token_accepted(env, &invoice, &token);
 
         // Get conversion rate
         let conversion_rate: i128 = invoice
@@ -193,76 +208,10 @@ impl MultiTokenInvoiceImpl {
             .unwrap_or_else(|| panic_with_error!(env, MultiTokenInvoiceError::ConversionRateNotSet));
 
         // Calculate amount in base currency
-        let amount_in_base = amount
-            .checked_mul(conversion_rate)
-            .unwrap_or_else(|| panic_with_error!(env, MultiTokenInvoiceError::InvalidConversionRate))
-            .checked_div(1_000_000)
-            .unwrap_or_else(|| panic_with_error!(env, MultiTokenInvoiceError::InvalidConversionRate));
-
-        // Calculate amount in settlement currency
-        let amount_in_settlement = amount_in_base
-            .checked_mul(invoice.settlement_conversion_rate)
-            .unwrap_or_else(|| panic_with_error!(env, MultiTokenInvoiceError::InvalidConversionRate))
-            .checked_div(1_000_000)
-            .unwrap_or_else(|| panic_with_error!(env, MultiTokenInvoiceError::InvalidConversionRate));
-
-        // Update payments received
-        let current_payment: i128 = invoice
-            .payments_received
-            .get(token.clone())
-            .unwrap_or(0);
-
-        let new_payment = current_payment
-            .checked_add(amount_in_base)
-            .unwrap_or_else(|| panic_with_error!(env, MultiTokenInvoiceError::PaymentExceedsInvoiceAmount));
-
-        if new_payment > invoice.total_amount {
-            panic_with_error!(env, MultiTokenInvoiceError::PaymentExceedsInvoiceAmount);
-        }
-
-        invoice.payments_received.set(token.clone(), new_payment);
-
-        // Update invoice status
-        if new_payment >= invoice.total_amount {
-            invoice.status = InvoiceStatus::FullyPaid;
-        } else {
-            invoice.status = InvoiceStatus::PartiallyPaid;
-        }
-
-        // Get payment ID
-        let payment_id: u32 = env
-            .storage()
-            .instance()
-            .get(&Symbol::new(env, PAYMENT_COUNTER_KEY))
-            .unwrap_or(0u32);
-
-        let next_payment_id = payment_id.checked_add(1).unwrap_or_else(|| {
-            panic_with_error!(env, MultiTokenInvoiceError::SettlementFailed);
-        });
-
-        let now = env.ledger().timestamp();
-
-        let payment = InvoicePayment {
-            payment_id: next_payment_id,
-            invoice_id,
-            token,
-            amount,
-            amount_in_base,
-            amount_in_settlement,
-            paid_at: now,
-            payer,
-            tx_hash: BytesN::from_array(env, &[0u8; 32]),
-        };
-
-        // Store payment
-        let payment_key = inv_payment_key(env, next_payment_id);
-        env.storage().persistent().set(&payment_key, &payment);
+        let amount_in_base = Self::convert(env, amount, conversion_rate);
 
-        // Store updated invoice
+        let payment = Self::record_payment(env, &mut invoice, payer, token, amount, amount_in_base);
         env.storage().persistent().set(&key, &invoice);
-        env.storage()
-            .instance()
-            .set(&Symbol::new(env, PAYMENT_COUNTER_KEY), &next_payment_id);
 
         payment
     }
@@ -291,50 +240,40 @@ impl MultiTokenInvoiceImpl {
         env.storage().persistent().set(&key, &rates);
     }
 
-    /// Set settlement conversion rate
-    pub fn set_settlement_conversion_rate(
-        env: &Env,
-        merchant: Address,
-        invoice_id: u32,
-        rate: i128,
-    ) {
-        merchant.require_auth();
-
-        if rate <= 0 {
-            panic_with_error!(env, MultiTokenInvoiceError::InvalidConversionRate);
-        }
-
-        let key = invoice_key(env, invoice_id);
-        let mut invoice: MultiTokenInvoice = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or_else(|| panic_with_error!(env, MultiTokenInvoiceError::InvoiceNotFound));
-
-        if invoice.merchant != merchant {
-            panic_with_error!(env, MultiTokenInvoiceError::UnauthorizedAccess);
-        }
-
-        invoice.settlement_conversion_rate = rate;
-        env.storage().persistent().set(&key, &invoice);
-    }
-
     /// Get invoice details
     pub fn get_invoice(env: &Env, invoice_id: u32) -> Option<MultiTokenInvoice> {
         let key = invoice_key(env, invoice_id);
         env.storage().persistent().get(&key)
     }
 
-    /// Get invoice payment history
+    /// Get invoice payment history, oldest first.
     pub fn get_invoice_payments(env: &Env, invoice_id: u32) -> Vec<InvoicePayment> {
-        let mut payments = Vec::new(env);
+        let ids: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&inv_payment_index_key(env, invoice_id))
+            .unwrap_or_else(|| Vec::new(env));
 
-        // This would require iterating through all payments and filtering by invoice_id
-        // For now, return empty vector - in production, use a proper indexing strategy
+        let mut payments = Vec::new(env);
+        for id in ids.iter() {
+            if let Some(payment) = env
+                .storage()
+                .persistent()
+                .get::<_, InvoicePayment>(&inv_payment_key(env, id))
+            {
+                payments.push_back(payment);
+            }
+        }
         payments
     }
 
-    /// Settle invoices in batch
+    /// Settle fully-paid invoices in batch, paying the merchant in the shared
+    /// preferred settlement token. Each invoice releases exactly the tokens
+    /// escrowed by its own payments and moves to `Settled`, so it can never be
+    /// settled twice. Payments already in the preferred token are transferred
+    /// as-is; every other token is swapped through the configured swap router
+    /// for at least its base → preferred value, read from the oracle when one
+    /// covers the pair, less the global default slippage tolerance.
     pub fn settle_invoices(
         env: &Env,
         merchant: Address,
@@ -342,19 +281,17 @@ impl MultiTokenInvoiceImpl {
     ) -> SettlementBatch {
         merchant.require_auth();
 
-        if invoice_ids.len() > 50 {
+        if invoice_ids.is_empty() || invoice_ids.len() > MAX_SETTLEMENT_BATCH {
             panic_with_error!(env, MultiTokenInvoiceError::SettlementFailed);
         }
 
-        let mut total_settlement_amount: i128 = 0;
+        let mut settlement_token: Option<Address> = None;
+        // token -> (escrowed amount, expected value in the settlement token)
+        let mut payouts: Map<Address, (i128, i128)> = Map::new(env);
 
         for invoice_id in invoice_ids.iter() {
             let key = invoice_key(env, invoice_id);
-            let invoice: MultiTokenInvoice = env
-                .storage()
-                .persistent()
-                .get(&key)
-                .unwrap_or_else(|| panic_with_error!(env, MultiTokenInvoiceError::InvoiceNotFound));
+            let mut invoice: MultiTokenInvoice = Self::load_invoice(env, invoice_id);
 
             if invoice.merchant != merchant {
                 panic_with_error!(env, MultiTokenInvoiceError::UnauthorizedAccess);
@@ -364,8 +301,41 @@ impl MultiTokenInvoiceImpl {
                 panic_with_error!(env, MultiTokenInvoiceError::InvalidInvoiceStatus);
             }
 
+            match &settlement_token {
+                Some(t) if *t != invoice.preferred_settlement_token => {
+                    panic_with_error!(env, MultiTokenInvoiceError::MixedSettlementTokens);
+                }
+                Some(_) => {}
+                None => settlement_token = Some(invoice.preferred_settlement_token.clone()),
+            }
+
+            invoice.settlement_conversion_rate = Self::settlement_rate(env, &invoice);
+            for payment in Self::get_invoice_payments(env, invoice_id).iter() {
+                let (escrowed, expected) = payouts.get(payment.token.clone()).unwrap_or((0, 0));
+                let value =
+                    Self::convert(env, payment.amount_in_base, invoice.settlement_conversion_rate);
+                payouts.set(payment.token, (escrowed + payment.amount, expected + value));
+            }
+
+            invoice.status = InvoiceStatus::Settled;
+            env.storage().persistent().set(&key, &invoice);
+        }
+
+        let settlement_token = settlement_token.unwrap();
+        let mut total_settlement_amount: i128 = 0;
+        for (token, (amount, expected)) in payouts.iter() {
+            let delivered = if token == settlement_token {
+                token::Client::new(env, &token).transfer(
+                    &env.current_contract_address(),
+                    &merchant,
+                    &amount,
+                );
+                amount
+            } else {
+                Self::swap_to_merchant(env, &token, &settlement_token, amount, expected, &merchant)
+            };
             total_settlement_amount = total_settlement_amount
-                .checked_add(invoice.total_amount)
+                .checked_add(delivered)
                 .unwrap_or_else(|| panic_with_error!(env, MultiTokenInvoiceError::SettlementFailed));
         }
 
@@ -384,11 +354,11 @@ impl MultiTokenInvoiceImpl {
 
         let batch = SettlementBatch {
             batch_id: next_batch_id,
-            invoice_ids,
+            invoice_ids: invoice_ids.clone(),
             total_settlement_amount,
             settled_at: now,
             status: SettlementStatus::Completed,
-            merchant,
+            merchant: merchant.clone(),
         };
 
         // Store batch
@@ -398,6 +368,15 @@ impl MultiTokenInvoiceImpl {
             .instance()
             .set(&Symbol::new(env, SETTLEMENT_BATCH_COUNTER_KEY), &next_batch_id);
 
+        events::emit_multi_token_invoices_settled(
+            env,
+            next_batch_id,
+            merchant,
+            settlement_token,
+            total_settlement_amount,
+            invoice_ids,
+        );
+
         batch
     }
 
@@ -407,31 +386,38 @@ impl MultiTokenInvoiceImpl {
         env.storage().persistent().get(&key)
     }
 
-    /// Cancel an invoice
+    /// Cancel an unsettled invoice. Every payment received so far is returned
+    /// to its payer in the token it was made in.
     pub fn cancel_invoice(env: &Env, invoice_id: u32) {
         let key = invoice_key(env, invoice_id);
-        let mut invoice: MultiTokenInvoice = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or_else(|| panic_with_error!(env, MultiTokenInvoiceError::InvoiceNotFound));
+        let mut invoice: MultiTokenInvoice = Self::load_invoice(env, invoice_id);
 
         invoice.merchant.require_auth();
 
+        if invoice.status == InvoiceStatus::Settled || invoice.status == InvoiceStatus::Cancelled {
+            panic_with_error!(env, MultiTokenInvoiceError::InvalidInvoiceStatus);
+        }
+
+        let payments = Self::get_invoice_payments(env, invoice_id);
+        let mut refunded: i128 = 0;
+        for payment in payments.iter() {
+            token::Client::new(env, &payment.token).transfer(
+                &env.current_contract_address(),
+                &payment.payer,
+                &payment.amount,
+            );
+            refunded += payment.amount_in_base;
+        }
+
         invoice.status = InvoiceStatus::Cancelled;
         env.storage().persistent().set(&key, &invoice);
+
+        events::emit_multi_token_invoice_cancelled(env, invoice_id, payments.len(), refunded);
     }
 
     /// Get invoice status
     pub fn get_invoice_status(env: &Env, invoice_id: u32) -> InvoiceStatus {
-        let key = invoice_key(env, invoice_id);
-        let invoice: MultiTokenInvoice = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or_else(|| panic_with_error!(env, MultiTokenInvoiceError::InvoiceNotFound));
-
-        invoice.status
+        Self::load_invoice(env, invoice_id).status
     }
 
     /// Pay an invoice using a different token than the invoice currency, using an oracle
@@ -451,8 +437,6 @@ impl MultiTokenInvoiceImpl {
         payment_amount: i128,
         max_slippage_bps: u32,
     ) -> InvoicePayment {
-        use crate::multi_token_invoice::InvoiceOracleClient;
-
         payer.require_auth();
 
         if payment_amount <= 0 {
@@ -460,56 +444,17 @@ impl MultiTokenInvoiceImpl {
         }
 
         let key = invoice_key(env, invoice_id);
-        let mut invoice: MultiTokenInvoice = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or_else(|| panic_with_error!(env, MultiTokenInvoiceError::InvoiceNotFound));
+        let mut invoice: MultiTokenInvoice = Self::load_invoice(env, invoice_id);
 
-        match invoice.status {
-            InvoiceStatus::Cancelled => {
-                panic_with_error!(env, MultiTokenInvoiceError::InvalidInvoiceStatus);
-            }
-            InvoiceStatus::FullyPaid => {
-                panic_with_error!(env, MultiTokenInvoiceError::PaymentExceedsInvoiceAmount);
-            }
-            _ => {}
-        }
+        Self::require_payable(env, &invoice);
+        Self::require_token_accepted(env, &invoice, &payment_token);
 
-        // Validate that the payment token is in the accepted list
-        let mut token_accepted = false;
-        for accepted_token in invoice.accepted_tokens.iter() {
-            if accepted_token == payment_token {
-                token_accepted = true;
-                break;
-            }
-        }
-        if !token_accepted {
-            panic_with_error!(env, MultiTokenInvoiceError::TokenNotAccepted);
-        }
-
-        // Require oracle to be configured
-        let oracle_addr = invoice
-            .oracle_contract
-            .clone()
+        // Price of payment_token in terms of base_currency (scaled × 1_000_000)
+        let oracle_price = Self::oracle_price(env, &invoice, &payment_token, &invoice.base_currency)
             .unwrap_or_else(|| panic_with_error!(env, MultiTokenInvoiceError::OracleNotConfigured));
 
-        // Query oracle: price of payment_token in terms of base_currency (scaled × 1_000_000)
-        let oracle_client = InvoiceOracleClient::new(env, &oracle_addr);
-        let oracle_price: i128 = oracle_client
-            .get_price(&payment_token, &invoice.base_currency)
-            .unwrap_or_else(|| panic_with_error!(env, MultiTokenInvoiceError::OraclePriceUnavailable));
-
-        if oracle_price <= 0 {
-            panic_with_error!(env, MultiTokenInvoiceError::OraclePriceUnavailable);
-        }
-
         // Compute the base-currency equivalent of the payment
-        let amount_in_base = payment_amount
-            .checked_mul(oracle_price)
-            .unwrap_or_else(|| panic_with_error!(env, MultiTokenInvoiceError::InvalidConversionRate))
-            .checked_div(1_000_000)
-            .unwrap_or_else(|| panic_with_error!(env, MultiTokenInvoiceError::InvalidConversionRate));
+        let amount_in_base = Self::convert(env, payment_amount, oracle_price);
 
         // Slippage check: ensure oracle price does not deviate from stored conversion rate
         // by more than max_slippage_bps relative to the stored rate.
@@ -529,114 +474,276 @@ impl MultiTokenInvoiceImpl {
             }
         }
 
-        // Validate payment does not exceed remaining invoice balance
-        let current_paid: i128 = invoice
-            .payments_received
-            .get(payment_token.clone())
-            .unwrap_or(0);
-        let new_paid = current_paid
+        let payment = Self::record_payment(
+            env,
+            &mut invoice,
+            payer,
+            payment_token.clone(),
+            payment_amount,
+            amount_in_base,
+        );
+        env.storage().persistent().set(&key, &invoice);
+
+        // Store cross-token settlement record
+        let settlement_key = cross_settle_key(env, invoice_id);
+        let settlement_record = CrossTokenSettlementRecord {
+            invoice_id,
+            paid_token: payment_token.clone(),
+            paid_amount: payment_amount,
+            invoiced_token: invoice.base_currency.clone(),
+            invoiced_amount: amount_in_base,
+            oracle_price,
+            max_slippage_bps,
+        };
+        env.storage().persistent().set(&settlement_key, &settlement_record);
+
+        events::emit_cross_token_settlement(
+            env,
+            invoice_id,
+            payment_token,
+            payment_amount,
+            invoice.base_currency,
+            amount_in_base,
+            oracle_price,
+        );
+
+        payment
+    }
+
+    /// Get remaining balance for an invoice
+    pub fn get_invoice_balance(env: &Env, invoice_id: u32) -> i128 {
+        let invoice = Self::load_invoice(env, invoice_id);
+
+        invoice
+            .total_amount
+            .checked_sub(Self::total_paid(env, &invoice))
+            .unwrap_or_else(|| panic_with_error!(env, MultiTokenInvoiceError::SettlementFailed))
+    }
+
+    // --- Internal Helpers ---
+
+    fn load_invoice(env: &Env, invoice_id: u32) -> MultiTokenInvoice {
+        env.storage()
+            .persistent()
+            .get(&invoice_key(env, invoice_id))
+            .unwrap_or_else(|| panic_with_error!(env, MultiTokenInvoiceError::InvoiceNotFound))
+    }
+
+    fn require_payable(env: &Env, invoice: &MultiTokenInvoice) {
+        match invoice.status {
+            InvoiceStatus::Cancelled | InvoiceStatus::Settled => {
+                panic_with_error!(env, MultiTokenInvoiceError::InvalidInvoiceStatus);
+            }
+            InvoiceStatus::FullyPaid => {
+                panic_with_error!(env, MultiTokenInvoiceError::PaymentExceedsInvoiceAmount);
+            }
+            _ => {}
+        }
+        if invoice.due_date > 0 && env.ledger().timestamp() > invoice.due_date {
+            panic_with_error!(env, MultiTokenInvoiceError::InvoiceExpired);
+        }
+    }
+
+    /// Base → preferred settlement rate: 1:1 when they are the same token,
+    /// otherwise the oracle price, falling back to the stored rate when no
+    /// oracle covers the pair.
+    fn settlement_rate(env: &Env, invoice: &MultiTokenInvoice) -> i128 {
+        if invoice.preferred_settlement_token == invoice.base_currency {
+            return RATE_PRECISION;
+        }
+        Self::oracle_price(
+            env,
+            invoice,
+            &invoice.base_currency,
+            &invoice.preferred_settlement_token,
+        )
+        .unwrap_or(invoice.settlement_conversion_rate)
+    }
+
+    /// Swaps `amount` of `token_in` into `token_out` for the merchant through
+    /// the configured swap router and returns what the merchant received.
+    /// The merchant's balance must rise by at least `expected` less the
+    /// global default slippage tolerance.
+    fn swap_to_merchant(
+        env: &Env,
+        token_in: &Address,
+        token_out: &Address,
+        amount: i128,
+        expected: i128,
+        merchant: &Address,
+    ) -> i128 {
+        let router: Address = env
+            .storage()
+            .instance()
+            .get(&crate::DataKey::SwapRouter)
+            .unwrap_or_else(|| panic_with_error!(env, crate::PaymentError::SwapRouterNotConfigured));
+        let slippage_bps =
+            crate::AhjoorPaymentsContract::get_slippage_config_internal(env).default_bps as i128;
+        let min_out = expected
+            .checked_mul(10_000 - slippage_bps)
+            .unwrap_or_else(|| panic_with_error!(env, MultiTokenInvoiceError::SettlementFailed))
+            / 10_000;
+
+        let out_client = token::Client::new(env, token_out);
+        let before = out_client.balance(merchant);
+        token::Client::new(env, token_in).transfer(&env.current_contract_address(), &router, &amount);
+        SettlementRouterClient::new(env, &router).swap_exact_in(
+            token_in,
+            token_out,
+            &amount,
+            &min_out,
+            merchant,
+        );
+        let delivered = out_client.balance(merchant) - before;
+        if delivered < min_out {
+            panic_with_error!(env, MultiTokenInvoiceError::SlippageExceeded);
+        }
+        delivered
+    }
+
+    /// Price of `base` in `quote` scaled by 10^6. The contract's oracle-set
+    /// median is used when a set is configured for the pair, so no single feed
+    /// can price the invoice; otherwise the invoice's own oracle is asked.
+    /// `None` when neither is available.
+    fn oracle_price(
+        env: &Env,
+        invoice: &MultiTokenInvoice,
+        base: &Address,
+        quote: &Address,
+    ) -> Option<i128> {
+        use crate::multi_token_invoice::InvoiceOracleClient;
+
+        if let Some(price) =
+            crate::AhjoorPaymentsContract::get_oracle_set_price(env, base, quote, RATE_PRECISION)
+        {
+            return Some(price);
+        }
+        let oracle_addr = invoice.oracle_contract.as_ref()?;
+        let price = InvoiceOracleClient::new(env, oracle_addr)
+            .get_price(base, quote)
+            .unwrap_or_else(|| panic_with_error!(env, MultiTokenInvoiceError::OraclePriceUnavailable));
+        if price <= 0 {
+            panic_with_error!(env, MultiTokenInvoiceError::OraclePriceUnavailable);
+        }
+        Some(price)
+    }
+
+    fn require_token_accepted(env: &Env, invoice: &MultiTokenInvoice, token: &Address) {
+        if !invoice.accepted_tokens.contains(token) {
+            panic_with_error!(env, MultiTokenInvoiceError::TokenNotAccepted);
+        }
+    }
+
+    /// `amount * rate / 10^6` with overflow mapped to `InvalidConversionRate`.
+    fn convert(env: &Env, amount: i128, rate: i128) -> i128 {
+        amount
+            .checked_mul(rate)
+            .unwrap_or_else(|| panic_with_error!(env, MultiTokenInvoiceError::InvalidConversionRate))
+            .checked_div(RATE_PRECISION)
+            .unwrap_or_else(|| panic_with_error!(env, MultiTokenInvoiceError::InvalidConversionRate))
+    }
+
+    /// Base-currency value received across all tokens.
+    fn total_paid(env: &Env, invoice: &MultiTokenInvoice) -> i128 {
+        let mut total_paid: i128 = 0;
+        for (_, paid) in invoice.payments_received.iter() {
+            total_paid = total_paid
+                .checked_add(paid)
+                .unwrap_or_else(|| panic_with_error!(env, MultiTokenInvoiceError::SettlementFailed));
+        }
+        total_paid
+    }
+
+    /// Pulls `amount` of `token` from the payer, credits `amount_in_base` against
+    /// the invoice and stores the payment record. Rejects any payment that would
+    /// take the combined total over `total_amount`. The caller persists the invoice.
+    fn record_payment(
+        env: &Env,
+        invoice: &mut MultiTokenInvoice,
+        payer: Address,
+        token: Address,
+        amount: i128,
+        amount_in_base: i128,
+    ) -> InvoicePayment {
+        if amount_in_base <= 0 {
+            panic_with_error!(env, MultiTokenInvoiceError::InvalidConversionRate);
+        }
+
+        let new_total_paid = Self::total_paid(env, invoice)
             .checked_add(amount_in_base)
             .unwrap_or_else(|| panic_with_error!(env, MultiTokenInvoiceError::PaymentExceedsInvoiceAmount));
-
-        if new_paid > invoice.total_amount {
+        if new_total_paid > invoice.total_amount {
             panic_with_error!(env, MultiTokenInvoiceError::PaymentExceedsInvoiceAmount);
         }
 
-        // Transfer payment_token from payer to contract
-        let token_client = token::Client::new(env, &payment_token);
-        token_client.transfer(&payer, &env.current_contract_address(), &payment_amount);
+        token::Client::new(env, &token).transfer(&payer, env.current_contract_address(), &amount);
 
-        invoice.payments_received.set(payment_token.clone(), new_paid);
+        let token_paid = invoice.payments_received.get(token.clone()).unwrap_or(0);
+        invoice
+            .payments_received
+            .set(token.clone(), token_paid + amount_in_base);
 
-        if new_paid >= invoice.total_amount {
-            invoice.status = InvoiceStatus::FullyPaid;
+        // Update invoice status
+        invoice.status = if new_total_paid >= invoice.total_amount {
+            InvoiceStatus::FullyPaid
         } else {
-            invoice.status = InvoiceStatus::PartiallyPaid;
-        }
+            InvoiceStatus::PartiallyPaid
+        };
 
-        let amount_in_settlement = amount_in_base
-            .checked_mul(invoice.settlement_conversion_rate)
-            .unwrap_or_else(|| panic_with_error!(env, MultiTokenInvoiceError::InvalidConversionRate))
-            .checked_div(1_000_000)
-            .unwrap_or_else(|| panic_with_error!(env, MultiTokenInvoiceError::InvalidConversionRate));
+        // Calculate amount in settlement currency
+        let amount_in_settlement =
+            Self::convert(env, amount_in_base, invoice.settlement_conversion_rate);
 
+        // Get payment ID
         let payment_id: u32 = env
             .storage()
             .instance()
             .get(&Symbol::new(env, PAYMENT_COUNTER_KEY))
             .unwrap_or(0u32);
+
         let next_payment_id = payment_id.checked_add(1).unwrap_or_else(|| {
-            panic_with_error!(env, MultiTokenInvoiceError::SettlementFailed)
+            panic_with_error!(env, MultiTokenInvoiceError::SettlementFailed);
         });
 
-        let now = env.ledger().timestamp();
-
         let payment = InvoicePayment {
             payment_id: next_payment_id,
-            invoice_id,
-            token: payment_token.clone(),
-            amount: payment_amount,
+            invoice_id: invoice.invoice_id,
+            token,
+            amount,
             amount_in_base,
             amount_in_settlement,
-            paid_at: now,
+            paid_at: env.ledger().timestamp(),
             payer,
             tx_hash: BytesN::from_array(env, &[0u8; 32]),
         };
 
-        let payment_key = inv_payment_key(env, next_payment_id);
-        env.storage().persistent().set(&payment_key, &payment);
-        env.storage().persistent().set(&key, &invoice);
+        // Store payment and index it under the invoice
+        env.storage()
+            .persistent()
+            .set(&inv_payment_key(env, next_payment_id), &payment);
+        let index_key = inv_payment_index_key(env, invoice.invoice_id);
+        let mut ids: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&index_key)
+            .unwrap_or_else(|| Vec::new(env));
+        ids.push_back(next_payment_id);
+        env.storage().persistent().set(&index_key, &ids);
         env.storage()
             .instance()
             .set(&Symbol::new(env, PAYMENT_COUNTER_KEY), &next_payment_id);
 
-        // Store cross-token settlement record
-        let settlement_key = cross_settle_key(env, invoice_id);
-        let settlement_record = CrossTokenSettlementRecord {
-            invoice_id,
-            paid_token: payment_token.clone(),
-            paid_amount: payment_amount,
-            invoiced_token: invoice.base_currency.clone(),
-            invoiced_amount: amount_in_base,
-            oracle_price,
-            max_slippage_bps,
-        };
-        env.storage().persistent().set(&settlement_key, &settlement_record);
-
-        // Emit cross-token settlement event
-        env.events().publish(
-            (Symbol::new(env, "CrossTokenSettlement"),),
-            (
-                invoice_id,
-                payment_token,
-                payment_amount,
-                invoice.base_currency,
-                amount_in_base,
-            ),
+        events::emit_invoice_payment_received(
+            env,
+            invoice.invoice_id,
+            next_payment_id,
+            payment.payer.clone(),
+            payment.token.clone(),
+            amount,
+            amount_in_base,
+            invoice.status,
         );
 
         payment
     }
-
-    /// Get remaining balance for an invoice
-    pub fn get_invoice_balance(env: &Env, invoice_id: u32) -> i128 {
-        let key = invoice_key(env, invoice_id);
-        let invoice: MultiTokenInvoice = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or_else(|| panic_with_error!(env, MultiTokenInvoiceError::InvoiceNotFound));
-
-        let mut total_paid: i128 = 0;
-        for payment in invoice.payments_received.iter() {
-            total_paid = total_paid
-                .checked_add(payment.1)
-                .unwrap_or_else(|| panic_with_error!(env, MultiTokenInvoiceError::SettlementFailed));
-        }
-
-        invoice
-            .total_amount
-            .checked_sub(total_paid)
-            .unwrap_or_else(|| panic_with_error!(env, MultiTokenInvoiceError::SettlementFailed))
-    }
 }
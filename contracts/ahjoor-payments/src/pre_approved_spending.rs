@@ -1,8 +1,4 @@
-#![allow(dead_code)]
-use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, panic_with_error, token, Address, Bytes,
-    BytesN, Env, Map, String, Symbol, Vec,
-};
+use soroban_sdk::{contracterror, contracttype, Address, BytesN, Env, Map, String, Vec};
 
 /// Pre-approved spending allowance with on-chain consent record
 #[contracttype]
@@ -155,23 +151,30 @@ pub enum AuditAction {
     ConsentRecorded = 7,
 }
 
+/// Errors raised by the pre-approved spending entrypoints.
+///
+/// Codes start at 301 so they never collide with the contract-wide `Error` enum.
 #[contracterror]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum SpendingAllowanceError {
-    AllowanceNotFound = 1,
-    AllowanceExpired = 2,
-    AllowanceExhausted = 3,
-    AllowanceRevoked = 4,
-    TransactionExceedsLimit = 5,
-    DailyLimitExceeded = 6,
-    PerTransactionLimitExceeded = 7,
-    UnauthorizedAccess = 8,
-    ConsentNotFound = 9,
-    ConsentExpired = 10,
-    ConsentRevoked = 11,
-    InvalidConsentHash = 12,
-    AllowancePaused = 13,
-    InvalidAllowanceAmount = 14,
+    AllowanceNotFound = 301,
+    AllowanceExpired = 302,
+    AllowanceExhausted = 303,
+    AllowanceRevoked = 304,
+    TransactionExceedsLimit = 305,
+    DailyLimitExceeded = 306,
+    PerTransactionLimitExceeded = 307,
+    UnauthorizedAccess = 308,
+    ConsentNotFound = 309,
+    ConsentExpired = 310,
+    ConsentRevoked = 311,
+    InvalidConsentHash = 312,
+    AllowancePaused = 313,
+    InvalidAllowanceAmount = 314,
+    /// Pause/resume attempted from a status that does not allow it.
+    InvalidAllowanceStatus = 315,
+    /// History page size is zero or above the maximum.
+    InvalidPageSize = 316,
 }
 
 pub trait PreApprovedSpendingInterface {
@@ -206,6 +209,7 @@ pub trait PreApprovedSpendingInterface {
     /// Spend from an allowance
     fn spend_from_allowance(
         env: Env,
+        merchant: Address,
         allowance_id: u32,
         amount: i128,
         reference: String,
@@ -235,17 +239,22 @@ pub trait PreApprovedSpendingInterface {
     /// Get daily remaining balance
     fn get_daily_remaining(env: Env, allowance_id: u32) -> i128;
 
-    /// Get allowance transaction history
-    fn get_allowance_transactions(env: Env, allowance_id: u32) -> Vec<AllowanceTransaction>;
+    /// Get a page of allowance transaction history
+    fn get_allowance_transactions(
+        env: Env,
+        allowance_id: u32,
+        page: u32,
+        page_size: u32,
+    ) -> Vec<AllowanceTransaction>;
 
-    /// Get audit log for an allowance
-    fn get_audit_log(env: Env, allowance_id: u32) -> Vec<AllowanceAuditLog>;
+    /// Get a page of the audit log for an allowance
+    fn get_audit_log(env: Env, allowance_id: u32, page: u32, page_size: u32) -> Vec<AllowanceAuditLog>;
 
-    /// Get all allowances for a customer
-    fn get_customer_allowances(env: Env, customer: Address) -> Vec<SpendingAllowance>;
+    /// Get a page of a customer's allowances
+    fn get_customer_allowances(env: Env, customer: Address, page: u32, page_size: u32) -> Vec<SpendingAllowance>;
 
-    /// Get all allowances for a merchant
-    fn get_merchant_allowances(env: Env, merchant: Address) -> Vec<SpendingAllowance>;
+    /// Get a page of a merchant's allowances
+    fn get_merchant_allowances(env: Env, merchant: Address, page: u32, page_size: u32) -> Vec<SpendingAllowance>;
 
     /// Verify consent is valid
     fn verify_consent(env: Env, consent_id: u32) -> bool;
@@ -1,8 +1,6 @@
-#![allow(dead_code)]
-use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, panic_with_error, token, Address, Bytes,
-    BytesN, Env, Map, String, Symbol, Vec,
-};
+use soroban_sdk::{panic_with_error, token, Address, BytesN, Env, IntoVal, Map, String, Symbol, Val, Vec};
+use crate::events;
+use crate::{PERSISTENT_BUMP_AMOUNT, PERSISTENT_LIFETIME_THRESHOLD};
 use crate::pre_approved_spending::*;
 
 // Storage key symbols (used as tuple key prefixes to avoid format!)
@@ -11,6 +9,10 @@ const CONSENT_COUNTER_KEY: &str = "consent_counter";
 const TRANSACTION_COUNTER_KEY: &str = "transaction_counter";
 const AUDIT_LOG_COUNTER_KEY: &str = "audit_log_counter";
 
+/// Ids held by one storage entry of a paged index. The index key itself
+/// holds the id count; page `n` lives under the key extended with `n`.
+pub const INDEX_PAGE_SIZE: u32 = 50;
+
 fn allowance_key(env: &Env, id: u32) -> (Symbol, u32) {
     (Symbol::new(env, "allowance"), id)
 }
@@ -23,6 +25,12 @@ fn transaction_key(env: &Env, id: u32) -> (Symbol, u32) {
 fn audit_log_key(env: &Env, id: u32) -> (Symbol, u32) {
     (Symbol::new(env, "audit_log"), id)
 }
+fn allowance_transactions_key(env: &Env, allowance_id: u32) -> (Symbol, u32) {
+    (Symbol::new(env, "allow_txs"), allowance_id)
+}
+fn allowance_audit_key(env: &Env, allowance_id: u32) -> (Symbol, u32) {
+    (Symbol::new(env, "allow_logs"), allowance_id)
+}
 fn customer_allowances_key(env: &Env, customer: &Address) -> (Symbol, Address) {
     (Symbol::new(env, "cust_allows"), customer.clone())
 }
@@ -94,30 +102,14 @@ impl PreApprovedSpendingImpl {
         };
 
         // Store allowance
-        env.storage().persistent().set(&allowance_key(env, next_id), &allowance);
+        Self::save_allowance(env, &allowance);
         env.storage()
             .instance()
             .set(&Symbol::new(env, ALLOWANCE_COUNTER_KEY), &next_id);
 
-        // Add to customer allowances list
-        let ckey = customer_allowances_key(env, &customer);
-        let mut customer_allowances: Vec<u32> = env
-            .storage()
-            .persistent()
-            .get(&ckey)
-            .unwrap_or_else(|| Vec::new(env));
-        customer_allowances.push_back(next_id);
-        env.storage().persistent().set(&ckey, &customer_allowances);
-
-        // Add to merchant allowances list
-        let mkey = merchant_allowances_key(env, &merchant);
-        let mut merchant_allowances: Vec<u32> = env
-            .storage()
-            .persistent()
-            .get(&mkey)
-            .unwrap_or_else(|| Vec::new(env));
-        merchant_allowances.push_back(next_id);
-        env.storage().persistent().set(&mkey, &merchant_allowances);
+        // Index under both parties
+        Self::append_index(env, &customer_allowances_key(env, &customer), next_id);
+        Self::append_index(env, &merchant_allowances_key(env, &merchant), next_id);
 
         // Log audit entry
         Self::log_audit_entry(
@@ -128,6 +120,16 @@ impl PreApprovedSpendingImpl {
             "Allowance created",
         );
 
+        events::emit_allowance_created(
+            env,
+            next_id,
+            customer,
+            merchant,
+            allowance.token,
+            total_amount,
+            expires_at,
+        );
+
         next_id
     }
 
@@ -187,13 +189,18 @@ impl PreApprovedSpendingImpl {
         next_id
     }
 
-    /// Spend from an allowance
+    /// Merchant pulls `amount` from a customer allowance. Tokens move from the
+    /// customer to the merchant via the customer's token allowance to this
+    /// contract, in the same way `execute_recurring` pulls funds.
     pub fn spend_from_allowance(
         env: &Env,
+        merchant: Address,
         allowance_id: u32,
         amount: i128,
         reference: String,
     ) -> AllowanceTransaction {
+        merchant.require_auth();
+
         let key = allowance_key(env, allowance_id);
         let mut allowance: SpendingAllowance = env
             .storage()
@@ -201,6 +208,14 @@ impl PreApprovedSpendingImpl {
             .get(&key)
             .unwrap_or_else(|| panic_with_error!(env, SpendingAllowanceError::AllowanceNotFound));
 
+        if allowance.merchant != merchant {
+            panic_with_error!(env, SpendingAllowanceError::UnauthorizedAccess);
+        }
+
+        if amount <= 0 {
+            panic_with_error!(env, SpendingAllowanceError::InvalidAllowanceAmount);
+        }
+
         let now = env.ledger().timestamp();
 
         // Check allowance status
@@ -223,7 +238,7 @@ impl PreApprovedSpendingImpl {
         // Check expiration
         if now > allowance.expires_at {
             allowance.status = AllowanceStatus::Expired;
-            env.storage().persistent().set(&key, &allowance);
+            Self::save_allowance(env, &allowance);
             panic_with_error!(env, SpendingAllowanceError::AllowanceExpired);
         }
 
@@ -278,6 +293,13 @@ impl PreApprovedSpendingImpl {
             panic_with_error!(env, SpendingAllowanceError::InvalidAllowanceAmount);
         });
 
+        token::Client::new(env, &allowance.token).transfer_from(
+            &env.current_contract_address(),
+            &allowance.customer,
+            &merchant,
+            &amount,
+        );
+
         let transaction = AllowanceTransaction {
             tx_id: next_tx_id,
             allowance_id,
@@ -287,11 +309,17 @@ impl PreApprovedSpendingImpl {
             reference,
         };
 
-        // Store transaction
+        // Store transaction and index it under the allowance
         env.storage().persistent().set(&transaction_key(env, next_tx_id), &transaction);
+        env.storage().persistent().extend_ttl(
+            &transaction_key(env, next_tx_id),
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        Self::append_index(env, &allowance_transactions_key(env, allowance_id), next_tx_id);
 
         // Store updated allowance
-        env.storage().persistent().set(&key, &allowance);
+        Self::save_allowance(env, &allowance);
         env.storage()
             .instance()
             .set(&Symbol::new(env, TRANSACTION_COUNTER_KEY), &next_tx_id);
@@ -301,10 +329,19 @@ impl PreApprovedSpendingImpl {
             env,
             allowance_id,
             AuditAction::TransactionApproved,
-            allowance.customer.clone(),
+            merchant.clone(),
             "Spent from allowance",
         );
 
+        events::emit_allowance_spent(
+            env,
+            allowance_id,
+            next_tx_id,
+            merchant,
+            amount,
+            allowance.total_amount - allowance.amount_spent,
+        );
+
         transaction
     }
 
@@ -329,8 +366,12 @@ impl PreApprovedSpendingImpl {
 
         allowance.customer.require_auth();
 
+        if allowance.status != AllowanceStatus::Active {
+            panic_with_error!(env, SpendingAllowanceError::InvalidAllowanceStatus);
+        }
+
         allowance.status = AllowanceStatus::Paused;
-        env.storage().persistent().set(&key, &allowance);
+        Self::save_allowance(env, &allowance);
 
         Self::log_audit_entry(
             env,
@@ -353,11 +394,11 @@ impl PreApprovedSpendingImpl {
         allowance.customer.require_auth();
 
         if allowance.status != AllowanceStatus::Paused {
-            panic_with_error!(env, SpendingAllowanceError::InvalidAllowanceAmount);
+            panic_with_error!(env, SpendingAllowanceError::InvalidAllowanceStatus);
         }
 
         allowance.status = AllowanceStatus::Active;
-        env.storage().persistent().set(&key, &allowance);
+        Self::save_allowance(env, &allowance);
 
         Self::log_audit_entry(
             env,
@@ -379,8 +420,12 @@ impl PreApprovedSpendingImpl {
 
         allowance.customer.require_auth();
 
+        if allowance.status == AllowanceStatus::Revoked {
+            panic_with_error!(env, SpendingAllowanceError::AllowanceRevoked);
+        }
+
         allowance.status = AllowanceStatus::Revoked;
-        env.storage().persistent().set(&key, &allowance);
+        Self::save_allowance(env, &allowance);
 
         Self::log_audit_entry(
             env,
@@ -434,45 +479,75 @@ impl PreApprovedSpendingImpl {
             .unwrap_or_else(|| panic_with_error!(env, SpendingAllowanceError::InvalidAllowanceAmount))
     }
 
-    /// Get allowance transaction history
-    pub fn get_allowance_transactions(env: &Env, _allowance_id: u32) -> Vec<AllowanceTransaction> {
-        Vec::new(env)
-    }
-
-    /// Get audit log for an allowance
-    pub fn get_audit_log(env: &Env, _allowance_id: u32) -> Vec<AllowanceAuditLog> {
-        Vec::new(env)
+    /// Get one page of an allowance's transaction history, oldest first.
+    pub fn get_allowance_transactions(
+        env: &Env,
+        allowance_id: u32,
+        page: u32,
+        page_size: u32,
+    ) -> Vec<AllowanceTransaction> {
+        let mut transactions = Vec::new(env);
+        for id in Self::page_ids(env, &allowance_transactions_key(env, allowance_id), page, page_size).iter() {
+            if let Some(tx) = env
+                .storage()
+                .persistent()
+                .get::<_, AllowanceTransaction>(&transaction_key(env, id))
+            {
+                transactions.push_back(tx);
+            }
+        }
+        transactions
     }
 
-    /// Get all allowances for a customer
-    pub fn get_customer_allowances(env: &Env, customer: Address) -> Vec<SpendingAllowance> {
-        let mut allowances = Vec::new(env);
-        let ckey = customer_allowances_key(env, &customer);
-
-        if let Some(allowance_ids) = env.storage().persistent().get::<_, Vec<u32>>(&ckey) {
-            for id in allowance_ids.iter() {
-                if let Some(allowance) = env.storage().persistent().get::<_, SpendingAllowance>(&allowance_key(env, id)) {
-                    allowances.push_back(allowance);
-                }
+    /// Get one page of an allowance's audit log, oldest first.
+    pub fn get_audit_log(
+        env: &Env,
+        allowance_id: u32,
+        page: u32,
+        page_size: u32,
+    ) -> Vec<AllowanceAuditLog> {
+        let mut logs = Vec::new(env);
+        for id in Self::page_ids(env, &allowance_audit_key(env, allowance_id), page, page_size).iter() {
+            if let Some(log) = env
+                .storage()
+                .persistent()
+                .get::<_, AllowanceAuditLog>(&audit_log_key(env, id))
+            {
+                logs.push_back(log);
             }
         }
+        logs
+    }
 
-        allowances
+    /// Get one page of a customer's allowances, oldest first. Revoked
+    /// allowances stay listed with their status.
+    pub fn get_customer_allowances(
+        env: &Env,
+        customer: Address,
+        page: u32,
+        page_size: u32,
+    ) -> Vec<SpendingAllowance> {
+        Self::load_allowances(env, Self::page_ids(env, &customer_allowances_key(env, &customer), page, page_size))
     }
 
-    /// Get all allowances for a merchant
-    pub fn get_merchant_allowances(env: &Env, merchant: Address) -> Vec<SpendingAllowance> {
-        let mut allowances = Vec::new(env);
-        let mkey = merchant_allowances_key(env, &merchant);
+    /// Get one page of a merchant's allowances, oldest first. Revoked
+    /// allowances stay listed with their status.
+    pub fn get_merchant_allowances(
+        env: &Env,
+        merchant: Address,
+        page: u32,
+        page_size: u32,
+    ) -> Vec<SpendingAllowance> {
+        Self::load_allowances(env, Self::page_ids(env, &merchant_allowances_key(env, &merchant), page, page_size))
+    }
 
-        if let Some(allowance_ids) = env.storage().persistent().get::<_, Vec<u32>>(&mkey) {
-            for id in allowance_ids.iter() {
-                if let Some(allowance) = env.storage().persistent().get::<_, SpendingAllowance>(&allowance_key(env, id)) {
-                    allowances.push_back(allowance);
-                }
+    fn load_allowances(env: &Env, ids: Vec<u32>) -> Vec<SpendingAllowance> {
+        let mut allowances = Vec::new(env);
+        for id in ids.iter() {
+            if let Some(allowance) = env.storage().persistent().get::<_, SpendingAllowance>(&allowance_key(env, id)) {
+                allowances.push_back(allowance);
             }
         }
-
         allowances
     }
 
@@ -505,10 +580,13 @@ impl PreApprovedSpendingImpl {
         if per_transaction_limit <= 0 || daily_limit <= 0 {
             panic_with_error!(env, SpendingAllowanceError::InvalidAllowanceAmount);
         }
+        if per_transaction_limit > allowance.total_amount || daily_limit > allowance.total_amount {
+            panic_with_error!(env, SpendingAllowanceError::InvalidAllowanceAmount);
+        }
 
         allowance.per_transaction_limit = per_transaction_limit;
         allowance.daily_limit = daily_limit;
-        env.storage().persistent().set(&key, &allowance);
+        Self::save_allowance(env, &allowance);
 
         Self::log_audit_entry(
             env,
@@ -545,8 +623,76 @@ impl PreApprovedSpendingImpl {
         };
 
         env.storage().persistent().set(&audit_log_key(env, next_log_id), &log);
+        env.storage().persistent().extend_ttl(
+            &audit_log_key(env, next_log_id),
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        Self::append_index(env, &allowance_audit_key(env, allowance_id), next_log_id);
         env.storage()
             .instance()
             .set(&Symbol::new(env, AUDIT_LOG_COUNTER_KEY), &next_log_id);
+
+        if action != AuditAction::Created && action != AuditAction::TransactionApproved {
+            events::emit_allowance_updated(env, allowance_id, action, log.actor);
+        }
+    }
+
+    fn save_allowance(env: &Env, allowance: &SpendingAllowance) {
+        let key = allowance_key(env, allowance.allowance_id);
+        env.storage().persistent().set(&key, allowance);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    }
+
+    /// Append `id` to the paged index at `key`. `key` holds the id count and
+    /// each `INDEX_PAGE_SIZE` ids share one `(prefix, owner, page)` entry, so
+    /// no single entry grows with the index.
+    fn append_index<T>(env: &Env, key: &(Symbol, T), id: u32)
+    where
+        T: Clone,
+        (Symbol, T): IntoVal<Env, Val>,
+        (Symbol, T, u32): IntoVal<Env, Val>,
+    {
+        let storage = env.storage().persistent();
+        let count: u32 = storage.get(key).unwrap_or(0);
+        let page_key = (key.0.clone(), key.1.clone(), count / INDEX_PAGE_SIZE);
+        let mut ids: Vec<u32> = storage.get(&page_key).unwrap_or_else(|| Vec::new(env));
+        ids.push_back(id);
+        storage.set(&page_key, &ids);
+        storage.extend_ttl(&page_key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+        storage.set(key, &(count + 1));
+        storage.extend_ttl(key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    }
+
+    /// Slice `page` of the paged id index at `key`. `page_size` must be in
+    /// `1..=MAX_CUSTOMER_PAYMENTS_PAGE_SIZE`.
+    fn page_ids<T>(env: &Env, key: &(Symbol, T), page: u32, page_size: u32) -> Vec<u32>
+    where
+        T: Clone,
+        (Symbol, T): IntoVal<Env, Val>,
+        (Symbol, T, u32): IntoVal<Env, Val>,
+    {
+        if page_size == 0 || page_size > crate::MAX_CUSTOMER_PAYMENTS_PAGE_SIZE {
+            panic_with_error!(env, SpendingAllowanceError::InvalidPageSize);
+        }
+        let storage = env.storage().persistent();
+        let total: u32 = storage.get(key).unwrap_or(0);
+        let start = page.saturating_mul(page_size).min(total);
+        let end = start.saturating_add(page_size).min(total);
+        let mut ids = Vec::new(env);
+        let mut entry: Vec<u32> = Vec::new(env);
+        for pos in start..end {
+            if pos == start || pos % INDEX_PAGE_SIZE == 0 {
+                entry = storage
+                    .get(&(key.0.clone(), key.1.clone(), pos / INDEX_PAGE_SIZE))
+                    .unwrap_or_else(|| Vec::new(env));
+            }
+            if let Some(id) = entry.get(pos % INDEX_PAGE_SIZE) {
+                ids.push_back(id);
+            }
+        }
+        ids
     }
 }
@@ -77,7 +77,7 @@ fn test_initialize() {
 }
 
 #[test]
-#[should_panic(expected = "Already initialized")]
+#[should_panic(expected = "Error(Contract, #41)")] // AlreadyInitialized
 fn test_initialize_twice_panics() {
     let s = setup();
     s.init();
@@ -147,7 +147,7 @@ fn test_complete_payment_marks_ready_for_settlement() {
 }
 
 #[test]
-#[should_panic(expected = "Payment is not pending")]
+#[should_panic(expected = "Error(Contract, #53)")] // PaymentNotPending
 fn test_complete_already_completed_panics() {
     let s = setup();
     s.init();
@@ -170,7 +170,7 @@ fn test_complete_already_completed_panics() {
 }
 
 #[test]
-#[should_panic(expected = "Payment amount must be positive")]
+#[should_panic(expected = "Error(Contract, #47)")] // InvalidAmount
 fn test_create_payment_zero_amount_panics() {
     let s = setup();
     s.init();
@@ -228,7 +228,7 @@ fn test_create_batch_payments_escrow() {
 }
 
 #[test]
-#[should_panic(expected = "Batch size exceeds maximum allowed")]
+#[should_panic(expected = "Error(Contract, #74)")] // BatchTooLarge
 fn test_batch_exceeds_max_size() {
     let s = setup();
     s.init();
@@ -337,7 +337,7 @@ fn test_dispute_pending_payment() {
 }
 
 #[test]
-#[should_panic(expected = "Only pending payments can be disputed")]
+#[should_panic(expected = "Error(Contract, #59)")] // PaymentNotDisputable
 fn test_dispute_completed_payment_panics() {
     let s = setup();
     s.init();
@@ -362,7 +362,7 @@ fn test_dispute_completed_payment_panics() {
 }
 
 #[test]
-#[should_panic(expected = "Only pending payments can be disputed")]
+#[should_panic(expected = "Error(Contract, #59)")] // PaymentNotDisputable
 fn test_dispute_already_disputed_panics() {
     let s = setup();
     s.init();
@@ -389,7 +389,7 @@ fn test_dispute_already_disputed_panics() {
 }
 
 #[test]
-#[should_panic(expected = "Only the payment customer can dispute")]
+#[should_panic(expected = "Error(Contract, #51)")] // NotPaymentCustomer
 fn test_dispute_non_customer_panics() {
     let s = setup();
     s.init();
@@ -414,7 +414,7 @@ fn test_dispute_non_customer_panics() {
 }
 
 #[test]
-#[should_panic(expected = "Payment is not pending")]
+#[should_panic(expected = "Error(Contract, #53)")] // PaymentNotPending
 fn test_complete_disputed_payment_panics() {
     let s = setup();
     s.init();
@@ -513,7 +513,7 @@ fn test_resolve_dispute_to_customer() {
 }
 
 #[test]
-#[should_panic(expected = "Payment is not disputed")]
+#[should_panic(expected = "Error(Contract, #55)")] // PaymentNotDisputed
 fn test_resolve_non_disputed_panics() {
     let s = setup();
     s.init();
@@ -641,7 +641,7 @@ fn test_set_dispute_timeout() {
 }
 
 #[test]
-#[should_panic(expected = "Dispute timeout must be positive")]
+#[should_panic(expected = "Error(Contract, #406)")] // InvalidDuration
 fn test_set_dispute_timeout_zero_panics() {
     let s = setup();
     s.init();
@@ -892,7 +892,7 @@ fn test_customer_payment_tracking() {
 }
 
 #[test]
-#[should_panic(expected = "Payment not found")]
+#[should_panic(expected = "Error(Contract, #45)")] // PaymentNotFound
 fn test_get_nonexistent_payment_panics() {
     let s = setup();
     s.init();
@@ -1341,7 +1341,7 @@ fn test_multi_token_different_rate() {
 
 /// Stale oracle price (age > max_oracle_age) must be rejected.
 #[test]
-#[should_panic(expected = "Oracle price is stale")]
+#[should_panic(expected = "Error(Contract, #89)")] // OraclePriceStale
 fn test_multi_token_stale_oracle_rejected() {
     let s = setup_multi_token();
 
@@ -1361,7 +1361,7 @@ fn test_multi_token_stale_oracle_rejected() {
 
 /// Unavailable oracle (no price set) must be rejected.
 #[test]
-#[should_panic(expected = "Oracle price unavailable")]
+#[should_panic(expected = "Error(Contract, #88)")] // OraclePriceUnavailable
 fn test_multi_token_oracle_unavailable() {
     let s = setup_multi_token();
     // Oracle has no price set — lastprice returns None
@@ -1396,7 +1396,7 @@ fn test_multi_token_zero_slippage_exact_rate() {
 
 /// set_oracle rejects max_oracle_age = 0.
 #[test]
-#[should_panic(expected = "max_oracle_age must be positive")]
+#[should_panic(expected = "Error(Contract, #406)")] // InvalidDuration
 fn test_set_oracle_zero_age_panics() {
     let env = Env::default();
     env.mock_all_auths();
@@ -1883,7 +1883,7 @@ fn test_accept_admin_role() {
 }
 
 #[test]
-#[should_panic(expected = "No admin transfer proposed")]
+#[should_panic(expected = "Error(Contract, #173)")] // NoAdminTransferProposed
 fn test_accept_admin_role_without_proposal_panics() {
     let s = setup();
     s.init();
@@ -2765,7 +2765,7 @@ fn test_verify_payment_returns_false_for_pending_payment() {
 }
 
 #[test]
-#[should_panic(expected = "Receipt not found")]
+#[should_panic(expected = "Error(Contract, #80)")] // ReceiptNotFound
 fn test_get_receipt_for_pending_payment_panics() {
     let s = setup();
     s.init();
@@ -2958,7 +2958,7 @@ fn test_get_payments_by_reference() {
 }
 
 #[test]
-#[should_panic(expected = "Reference exceeds maximum length of 64 bytes")]
+#[should_panic(expected = "Error(Contract, #84)")] // ReferenceTooLong
 fn test_reference_too_long_panics() {
     let s = setup();
     s.init();
@@ -2984,7 +2984,7 @@ fn test_reference_too_long_panics() {
 }
 
 #[test]
-#[should_panic(expected = "Metadata exceeds maximum of 5 keys")]
+#[should_panic(expected = "Error(Contract, #83)")] // InvalidMetadata
 fn test_metadata_too_many_keys_panics() {
     let s = setup();
     s.init();
@@ -3069,7 +3069,7 @@ fn test_initialize_with_fee() {
 }
 
 #[test]
-#[should_panic(expected = "Fee cannot exceed 500 bps (5%)")]
+#[should_panic(expected = "Error(Contract, #403)")] // FeeTooHigh
 fn test_initialize_with_excessive_fee_panics() {
     let s = setup();
     s.client.initialize(&s.admin, &s.fee_recipient, &501);
@@ -3156,7 +3156,7 @@ fn test_update_fee() {
 }
 
 #[test]
-#[should_panic(expected = "Fee cannot exceed 500 bps (5%)")]
+#[should_panic(expected = "Error(Contract, #403)")] // FeeTooHigh
 fn test_update_fee_exceeds_max() {
     let s = setup();
     s.init();
@@ -3495,7 +3495,7 @@ fn test_full_payment_flow_with_fee_and_idempotency() {
 // ===========================================================================
 
 #[test]
-#[should_panic(expected = "split_recipients must sum to 10000 bps")]
+#[should_panic(expected = "Error(Contract, #82)")] // InvalidSplitRecipients
 fn test_split_recipients_invalid_sum_rejected() {
     let s = setup();
     s.init();
@@ -3796,7 +3796,7 @@ fn test_get_fee_tiers_roundtrip() {
 // ===========================================================================
 
 #[test]
-#[should_panic(expected = "Payment cannot execute before schedule")]
+#[should_panic(expected = "Error(Contract, #66)")] // ScheduleNotReached
 fn test_scheduled_payment_rejects_early_execution() {
     let s = setup();
     s.init();
@@ -3818,7 +3818,8 @@ fn test_scheduled_payment_rejects_early_execution() {
         &None,
     );
 
-    s.client.execute_scheduled_payment(&pid);
+    s.client
+        .execute_scheduled_payment(&Address::generate(&s.env), &pid);
 }
 
 #[test]
@@ -3847,7 +3848,8 @@ fn test_scheduled_payment_anyone_can_execute_after_time() {
     assert_eq!(payment.status, PaymentStatus::ScheduledPending);
 
     s.env.ledger().set_timestamp(1_500);
-    s.client.execute_scheduled_payment(&pid);
+    s.client
+        .execute_scheduled_payment(&Address::generate(&s.env), &pid);
 
     assert_eq!(s.token_client.balance(&merchant), 400);
     assert_eq!(s.token_client.balance(&s.client.address), 0);
@@ -3884,7 +3886,7 @@ fn test_scheduled_payment_cancel_before_execution_refunds() {
 }
 
 #[test]
-#[should_panic(expected = "Scheduled payment is ready to execute")]
+#[should_panic(expected = "Error(Contract, #67)")] // ScheduledPaymentDue
 fn test_scheduled_payment_cannot_cancel_after_ready() {
     let s = setup();
     s.init();
@@ -3981,7 +3983,7 @@ fn test_get_payments_by_category_empty_returns_empty() {
 }
 
 #[test]
-#[should_panic(expected = "Tags list cannot exceed 3 items")]
+#[should_panic(expected = "Error(Contract, #85)")] // TooManyTags
 fn test_tags_exceeding_3_rejected() {
     let s = setup();
     s.init();
@@ -4118,7 +4120,7 @@ fn test_bulk_expire_ineligible_payment_reverts_entire_batch() {
 }
 
 #[test]
-#[should_panic(expected = "Batch size exceeds maximum allowed")]
+#[should_panic(expected = "Error(Contract, #74)")] // BatchTooLarge
 fn test_bulk_expire_exceeds_cap_rejected() {
     let s = setup();
     s.init();
@@ -4130,7 +4132,7 @@ fn test_bulk_expire_exceeds_cap_rejected() {
 }
 
 #[test]
-#[should_panic(expected = "Payment has not expired yet")]
+#[should_panic(expected = "Error(Contract, #63)")] // PaymentNotExpired
 fn test_bulk_expire_not_expired_rejected() {
     let s = setup();
     s.init();
@@ -4235,7 +4237,7 @@ fn test_resume_resets_interval_from_resume_time() {
 }
 
 #[test]
-#[should_panic(expected = "Only the subscriber can pause")]
+#[should_panic(expected = "Error(Contract, #102)")] // NotSubscriber
 fn test_non_subscriber_cannot_pause() {
     let s = setup();
     s.init();
@@ -4252,7 +4254,7 @@ fn test_non_subscriber_cannot_pause() {
 }
 
 #[test]
-#[should_panic(expected = "Subscription is not paused")]
+#[should_panic(expected = "Error(Contract, #106)")] // SubscriptionNotPaused
 fn test_resume_not_paused_subscription_fails() {
     let s = setup();
     s.init();
@@ -4354,7 +4356,7 @@ fn test_authorize_payment_succeeds() {
 }
 
 #[test]
-#[should_panic(expected = "capture_deadline_ledger must be in the future")]
+#[should_panic(expected = "Error(Contract, #94)")] // DeadlineInPast
 fn test_authorize_payment_past_deadline_panics() {
     let s = setup();
     s.init();
@@ -4368,7 +4370,7 @@ fn test_authorize_payment_past_deadline_panics() {
 }
 
 #[test]
-#[should_panic(expected = "Payment amount must be positive")]
+#[should_panic(expected = "Error(Contract, #47)")] // InvalidAmount
 fn test_authorize_payment_zero_amount_panics() {
     let s = setup();
     s.init();
@@ -4422,7 +4424,7 @@ fn test_capture_after_deadline_panics() {
 }
 
 #[test]
-#[should_panic(expected = "Payment is not authorized")]
+#[should_panic(expected = "Error(Contract, #54)")] // PaymentNotAuthorized
 fn test_capture_pending_payment_panics() {
     let s = setup();
     s.init();
@@ -4740,7 +4742,7 @@ fn test_withdrawal_queue_empty() {
 }
 
 #[test]
-#[should_panic(expected = "Payment not found in withdrawal queue")]
+#[should_panic(expected = "Error(Contract, #179)")] // NotInWithdrawalQueue
 fn test_prioritize_nonexistent_payment() {
     let s = setup();
     s.init();
@@ -4848,7 +4850,7 @@ fn test_create_payment_with_invoice_success() {
 }
 
 #[test]
-#[should_panic(expected = "Invoice total does not match payment amount")]
+#[should_panic(expected = "Error(Contract, #86)")] // InvalidInvoice
 fn test_create_payment_with_invoice_total_mismatch() {
     let s = setup();
     s.init();
@@ -4968,7 +4970,7 @@ fn test_create_payment_with_multiple_line_items() {
 }
 
 #[test]
-#[should_panic(expected = "Invoice line items exceed maximum of 20")]
+#[should_panic(expected = "Error(Contract, #86)")] // InvalidInvoice
 fn test_create_payment_with_too_many_line_items() {
     let s = setup();
     s.init();
@@ -5006,7 +5008,7 @@ fn test_create_payment_with_too_many_line_items() {
 }
 
 #[test]
-#[should_panic(expected = "Invoice line_items cannot be empty")]
+#[should_panic(expected = "Error(Contract, #86)")] // InvalidInvoice
 fn test_create_payment_with_empty_invoice() {
     let s = setup();
     s.init();
@@ -5309,7 +5311,7 @@ fn test_pagination_empty_customer() {
 }
 
 #[test]
-#[should_panic(expected = "page_size exceeds maximum of 50")]
+#[should_panic(expected = "Error(Contract, #48)")] // InvalidPageSize
 fn test_pagination_rejects_oversize_page() {
     let s = setup();
     s.init();
@@ -5319,7 +5321,7 @@ fn test_pagination_rejects_oversize_page() {
 }
 
 #[test]
-#[should_panic(expected = "page_size must be greater than 0")]
+#[should_panic(expected = "Error(Contract, #48)")] // InvalidPageSize
 fn test_pagination_rejects_zero_page_size() {
     let s = setup();
     s.init();
@@ -5372,7 +5374,7 @@ fn test_admin_can_update_expiry_bounds() {
 }
 
 #[test]
-#[should_panic(expected = "min_expiry must be <= max_expiry")]
+#[should_panic(expected = "Error(Contract, #412)")] // InvalidExpiryBounds
 fn test_set_bounds_rejects_inverted_range() {
     let s = setup();
     s.init();
@@ -5380,7 +5382,7 @@ fn test_set_bounds_rejects_inverted_range() {
 }
 
 #[test]
-#[should_panic(expected = "min_expiry must be greater than 0")]
+#[should_panic(expected = "Error(Contract, #412)")] // InvalidExpiryBounds
 fn test_set_bounds_rejects_zero_min() {
     let s = setup();
     s.init();
@@ -5464,7 +5466,7 @@ fn test_create_payment_at_max_boundary_succeeds() {
 }
 
 #[test]
-#[should_panic(expected = "expiry_seconds is below the configured minimum")]
+#[should_panic(expected = "Error(Contract, #95)")] // ExpiryBelowMinimum
 fn test_create_payment_below_min_expiry_panics() {
     let s = setup();
     s.init();
@@ -5481,7 +5483,7 @@ fn test_create_payment_below_min_expiry_panics() {
 }
 
 #[test]
-#[should_panic(expected = "expiry_seconds exceeds the configured maximum")]
+#[should_panic(expected = "Error(Contract, #96)")] // ExpiryAboveMaximum
 fn test_create_payment_above_max_expiry_panics() {
     let s = setup();
     s.init();
@@ -5548,7 +5550,7 @@ fn test_update_slippage_config_and_enforce_bounds() {
 }
 
 #[test]
-#[should_panic(expected = "slippage_bps below minimum allowed")]
+#[should_panic(expected = "Error(Contract, #91)")] // SlippageOutOfBounds
 fn test_multi_token_slippage_below_min_rejected() {
     let s = setup_multi_token();
     // Set min=50
@@ -5566,7 +5568,7 @@ fn test_multi_token_slippage_below_min_rejected() {
 }
 
 #[test]
-#[should_panic(expected = "slippage_bps exceeds maximum allowed")]
+#[should_panic(expected = "Error(Contract, #91)")] // SlippageOutOfBounds
 fn test_multi_token_slippage_above_max_rejected() {
     let s = setup_multi_token();
     // Set max=200
@@ -5600,7 +5602,7 @@ fn test_slippage_tolerance_applied_event_emitted() {
 }
 
 #[test]
-#[should_panic(expected = "default_bps must be within")]
+#[should_panic(expected = "Error(Contract, #408)")] // InvalidSlippageConfig
 fn test_update_slippage_config_default_out_of_bounds_rejected() {
     let s = setup_multi_token();
     // default=300 is outside [50, 200]
@@ -87,7 +87,7 @@ fn test_set_min_collateral_by_admin() {
 }
 
 #[test]
-#[should_panic(expected = "min_collateral cannot be negative")]
+#[should_panic(expected = "Error(Contract, #415)")] // InvalidCollateralConfig
 fn test_set_min_collateral_negative_panics() {
     let s = collateral_setup();
     s.client.set_min_collateral(&-1i128);
@@ -124,7 +124,7 @@ fn test_deposit_collateral_accumulates() {
 }
 
 #[test]
-#[should_panic(expected = "Deposit amount must be positive")]
+#[should_panic(expected = "Error(Contract, #47)")] // InvalidAmount
 fn test_deposit_collateral_zero_panics() {
     let s = collateral_setup();
     let merchant = Address::generate(&s.env);
@@ -132,7 +132,7 @@ fn test_deposit_collateral_zero_panics() {
 }
 
 #[test]
-#[should_panic(expected = "Deposit amount must be positive")]
+#[should_panic(expected = "Error(Contract, #47)")] // InvalidAmount
 fn test_deposit_collateral_negative_panics() {
     let s = collateral_setup();
     let merchant = Address::generate(&s.env);
@@ -157,7 +157,7 @@ fn test_approve_merchant_requires_collateral() {
 }
 
 #[test]
-#[should_panic(expected = "Merchant collateral below minimum required")]
+#[should_panic(expected = "Error(Contract, #160)")] // CollateralBelowMinimum
 fn test_approve_merchant_without_collateral_panics() {
     let s = collateral_setup();
     let merchant = Address::generate(&s.env);
@@ -166,7 +166,7 @@ fn test_approve_merchant_without_collateral_panics() {
 }
 
 #[test]
-#[should_panic(expected = "Merchant collateral below minimum required")]
+#[should_panic(expected = "Error(Contract, #160)")] // CollateralBelowMinimum
 fn test_approve_merchant_insufficient_collateral_panics() {
     let s = collateral_setup();
     let merchant = Address::generate(&s.env);
@@ -197,7 +197,7 @@ fn test_withdraw_collateral_success() {
 }
 
 #[test]
-#[should_panic(expected = "Withdrawal would drop collateral below minimum required")]
+#[should_panic(expected = "Error(Contract, #160)")] // CollateralBelowMinimum
 fn test_withdraw_collateral_below_minimum_panics() {
     let s = collateral_setup();
     let merchant = Address::generate(&s.env);
@@ -209,7 +209,7 @@ fn test_withdraw_collateral_below_minimum_panics() {
 }
 
 #[test]
-#[should_panic(expected = "Insufficient collateral balance")]
+#[should_panic(expected = "Error(Contract, #161)")] // InsufficientCollateral
 fn test_withdraw_collateral_exceeds_balance_panics() {
     let s = collateral_setup();
     let merchant = Address::generate(&s.env);
@@ -220,7 +220,7 @@ fn test_withdraw_collateral_exceeds_balance_panics() {
 }
 
 #[test]
-#[should_panic(expected = "Withdrawal amount must be positive")]
+#[should_panic(expected = "Error(Contract, #47)")] // InvalidAmount
 fn test_withdraw_collateral_zero_panics() {
     let s = collateral_setup();
     let merchant = Address::generate(&s.env);
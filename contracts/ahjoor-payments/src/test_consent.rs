@@ -239,7 +239,7 @@ fn test_revoke_consent() {
 }
 
 #[test]
-#[should_panic(expected = "Consent record already signed")]
+#[should_panic(expected = "Error(Contract, #166)")] // ConsentAlreadySigned
 fn test_duplicate_sign_consent_panics() {
     let s = setup();
     s.init();
@@ -264,7 +264,7 @@ fn test_duplicate_sign_consent_panics() {
 }
 
 #[test]
-#[should_panic(expected = "Consent record has been revoked")]
+#[should_panic(expected = "Error(Contract, #165)")] // ConsentRevoked
 fn test_sign_revoked_consent_panics() {
     let s = setup();
     s.init();
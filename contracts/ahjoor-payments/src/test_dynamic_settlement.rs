@@ -124,7 +124,7 @@ fn test_create_payment_multi_token_success() {
 }
 
 #[test]
-#[should_panic(expected = "Oracle not configured")]
+#[should_panic(expected = "Error(Contract, #87)")] // OracleNotConfigured
 fn test_create_payment_multi_token_requires_oracle_config() {
     let env = Env::default();
     env.mock_all_auths();
@@ -0,0 +1,104 @@
+#![cfg(test)]
+use super::*;
+use soroban_sdk::token::StellarAssetClient as TokenAdminClient;
+use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+fn setup_errors<'a>() -> (
+    Env,
+    AhjoorPaymentsContractClient<'a>,
+    Address,
+    Address,
+    Address,
+    Address,
+) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AhjoorPaymentsContract, ());
+    let client = AhjoorPaymentsContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let customer = Address::generate(&env);
+    let merchant = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    TokenAdminClient::new(&env, &token).mint(&customer, &10_000);
+
+    client.initialize(&admin, &admin, &0u32);
+
+    (env, client, admin, customer, merchant, token)
+}
+
+fn contract_error(code: impl Into<soroban_sdk::Error>) -> soroban_sdk::Error {
+    code.into()
+}
+
+#[test]
+fn test_not_found_wrong_status_and_unauthorized_are_distinct() {
+    let (env, client, _admin, customer, merchant, token) = setup_errors();
+    let reason = String::from_str(&env, "damaged");
+
+    // Not found
+    assert_eq!(
+        client.try_get_payment(&99),
+        Err(Ok(contract_error(Error::PaymentNotFound)))
+    );
+
+    let payment_id = client.create_payment(&customer, &merchant, &500, &token, &None, &None, &None);
+
+    // Unauthorized: only the paying customer may dispute
+    assert_eq!(
+        client.try_dispute_payment(&merchant, &payment_id, &reason),
+        Err(Ok(contract_error(PaymentError::NotPaymentCustomer)))
+    );
+
+    // Wrong status: completed payments cannot be disputed or completed again
+    client.complete_payment(&payment_id);
+    assert_eq!(
+        client.try_dispute_payment(&customer, &payment_id, &reason),
+        Err(Ok(contract_error(PaymentError::PaymentNotDisputable)))
+    );
+    assert_eq!(
+        client.try_complete_payment(&payment_id),
+        Err(Ok(contract_error(PaymentError::PaymentNotPending)))
+    );
+}
+
+#[test]
+fn test_admin_checks_raise_unauthorized() {
+    let (env, client, _admin, _customer, _merchant, _token) = setup_errors();
+    let intruder = Address::generate(&env);
+
+    assert_eq!(
+        client.try_update_fee(&intruder, &100),
+        Err(Ok(contract_error(Error::Unauthorized)))
+    );
+}
+
+#[test]
+fn test_config_validation_raises_config_errors() {
+    let (_env, client, admin, _customer, _merchant, _token) = setup_errors();
+
+    assert_eq!(
+        client.try_update_fee(&admin, &501),
+        Err(Ok(contract_error(ConfigError::FeeTooHigh)))
+    );
+    assert_eq!(
+        client.try_resume_contract(&admin),
+        Err(Ok(contract_error(ConfigError::ContractNotPaused)))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #101)")] // SubscriptionNotFound
+fn test_missing_subscription_panics_with_typed_error() {
+    let (_env, client, _admin, _customer, _merchant, _token) = setup_errors();
+    client.get_subscription(&7);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #42)")] // NotInitialized
+fn test_uninitialized_contract_panics_with_typed_error() {
+    let env = Env::default();
+    let contract_id = env.register(AhjoorPaymentsContract, ());
+    AhjoorPaymentsContractClient::new(&env, &contract_id).get_admin();
+}
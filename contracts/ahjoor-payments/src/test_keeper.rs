@@ -0,0 +1,216 @@
+#![cfg(test)]
+use super::*;
+use soroban_sdk::token::Client as TokenClient;
+use soroban_sdk::token::StellarAssetClient as TokenAdminClient;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, Env,
+};
+
+const EXPIRY: u64 = 7 * 24 * 60 * 60 + 1;
+
+struct KeeperSetup<'a> {
+    env: Env,
+    client: AhjoorPaymentsContractClient<'a>,
+    admin: Address,
+    customer: Address,
+    merchant: Address,
+    keeper: Address,
+    token: Address,
+    token_client: TokenClient<'a>,
+}
+
+/// Bounty 10 per item, at most 25 per call and 40 per ledger, pool of 100.
+fn setup_keeper<'a>() -> KeeperSetup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AhjoorPaymentsContract, ());
+    let client = AhjoorPaymentsContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let customer = Address::generate(&env);
+    let merchant = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let token_client = TokenClient::new(&env, &token);
+    let token_admin = TokenAdminClient::new(&env, &token);
+    token_admin.mint(&customer, &10_000);
+    token_admin.mint(&admin, &100);
+
+    client.initialize(&admin, &admin, &0u32);
+    client.set_keeper_config(&admin, &token, &10, &25, &40);
+    client.fund_keeper_pool(&admin, &100);
+
+    KeeperSetup {
+        env,
+        client,
+        admin,
+        customer,
+        merchant,
+        keeper,
+        token,
+        token_client,
+    }
+}
+
+fn create_payments(s: &KeeperSetup, count: u32) -> Vec<u32> {
+    let mut ids = Vec::new(&s.env);
+    for _ in 0..count {
+        ids.push_back(s.client.create_payment(
+            &s.customer,
+            &s.merchant,
+            &100,
+            &s.token,
+            &None,
+            &None,
+            &None,
+        ));
+    }
+    ids
+}
+
+#[test]
+fn test_bulk_expire_pays_bounty_per_item_capped_per_call() {
+    let s = setup_keeper();
+    let ids = create_payments(&s, 3);
+    s.env.ledger().with_mut(|l| l.timestamp = EXPIRY);
+
+    s.client.bulk_expire_payments(&s.keeper, &ids);
+
+    // 3 items × 10 = 30, capped at 25 per call
+    assert_eq!(s.token_client.balance(&s.keeper), 25);
+    let pool = s.client.get_keeper_pool();
+    assert_eq!(pool.balance, 75);
+    assert_eq!(pool.paid_this_ledger, 25);
+}
+
+#[test]
+fn test_keeper_rewards_capped_per_ledger() {
+    let s = setup_keeper();
+    let ids = create_payments(&s, 4);
+    s.env.ledger().with_mut(|l| l.timestamp = EXPIRY);
+
+    s.client.bulk_expire_payments(
+        &s.keeper,
+        &vec![&s.env, ids.get(0).unwrap(), ids.get(1).unwrap()],
+    );
+    s.client
+        .bulk_expire_payments(&s.keeper, &vec![&s.env, ids.get(2).unwrap()]);
+    // 20 + 10 paid; the ledger has 10 left
+    assert_eq!(s.token_client.balance(&s.keeper), 30);
+
+    s.env.ledger().with_mut(|l| l.sequence_number += 1);
+    s.client
+        .bulk_expire_payments(&s.keeper, &vec![&s.env, ids.get(3).unwrap()]);
+    assert_eq!(s.token_client.balance(&s.keeper), 40);
+    assert_eq!(s.client.get_keeper_pool().paid_this_ledger, 10);
+}
+
+#[test]
+fn test_processed_item_cannot_be_rewarded_twice() {
+    let s = setup_keeper();
+    let ids = create_payments(&s, 1);
+    s.env.ledger().with_mut(|l| l.timestamp = EXPIRY);
+
+    s.client.bulk_expire_payments(&s.keeper, &ids);
+    assert_eq!(s.token_client.balance(&s.keeper), 10);
+
+    s.env.ledger().with_mut(|l| l.sequence_number += 1);
+    assert_eq!(
+        s.client.try_bulk_expire_payments(&s.keeper, &ids),
+        Err(Ok(soroban_sdk::Error::from(
+            PaymentError::PaymentNotExpirable
+        )))
+    );
+    assert_eq!(s.token_client.balance(&s.keeper), 10);
+    assert_eq!(s.client.get_keeper_pool().balance, 90);
+}
+
+#[test]
+fn test_repeated_id_is_expired_and_refunded_once() {
+    let s = setup_keeper();
+    let ids = create_payments(&s, 2);
+    let pid = ids.get(0).unwrap();
+    s.client.dispute_payment(
+        &s.customer,
+        &ids.get(1).unwrap(),
+        &soroban_sdk::String::from_str(&s.env, "not delivered"),
+    );
+    s.env.ledger().with_mut(|l| l.timestamp = EXPIRY);
+
+    // Disputed payments are left to dispute resolution
+    assert_eq!(
+        s.client.try_bulk_expire_payments(&s.keeper, &ids),
+        Err(Ok(soroban_sdk::Error::from(
+            PaymentError::PaymentNotExpirable
+        )))
+    );
+
+    let customer_before = s.token_client.balance(&s.customer);
+    s.client
+        .bulk_expire_payments(&s.keeper, &vec![&s.env, pid, pid]);
+    assert_eq!(s.client.get_payment(&pid).status, PaymentStatus::Expired);
+    assert_eq!(s.token_client.balance(&s.customer), customer_before + 100);
+    assert_eq!(s.token_client.balance(&s.keeper), 10);
+}
+
+#[test]
+fn test_scheduled_payment_pays_keeper_and_empty_pool_pays_nothing() {
+    let s = setup_keeper();
+    s.env.ledger().set_timestamp(1_000);
+    let pid = s.client.create_payment_with_options(
+        &s.customer,
+        &s.merchant,
+        &250,
+        &s.token,
+        &None,
+        &None,
+        &None,
+        &Some(2_000u64),
+        &None,
+    );
+    s.env.ledger().set_timestamp(2_000);
+    s.client.execute_scheduled_payment(&s.keeper, &pid);
+    assert_eq!(s.token_client.balance(&s.keeper), 10);
+
+    // Maintenance keeps working once the pool runs dry
+    s.client.withdraw_keeper_pool(&s.admin, &90);
+    assert_eq!(s.token_client.balance(&s.admin), 90);
+    let ids = create_payments(&s, 1);
+    s.env.ledger().with_mut(|l| l.timestamp += EXPIRY);
+    s.client.bulk_expire_payments(&s.keeper, &ids);
+    assert_eq!(s.token_client.balance(&s.keeper), 10);
+}
+
+#[test]
+fn test_keeper_config_and_pool_validation() {
+    let s = setup_keeper();
+    assert_eq!(
+        s.client
+            .try_set_keeper_config(&s.admin, &s.token, &0, &25, &40),
+        Err(Ok(soroban_sdk::Error::from(
+            ConfigError::InvalidKeeperConfig
+        )))
+    );
+    // The pool token cannot change while the pool holds funds
+    let other = s
+        .env
+        .register_stellar_asset_contract_v2(s.admin.clone())
+        .address();
+    assert_eq!(
+        s.client
+            .try_set_keeper_config(&s.admin, &other, &10, &25, &40),
+        Err(Ok(soroban_sdk::Error::from(
+            ConfigError::InvalidKeeperConfig
+        )))
+    );
+    assert_eq!(
+        s.client.try_withdraw_keeper_pool(&s.admin, &101),
+        Err(Ok(soroban_sdk::Error::from(
+            ConfigError::InsufficientKeeperPool
+        )))
+    );
+}
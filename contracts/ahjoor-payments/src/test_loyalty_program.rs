@@ -0,0 +1,346 @@
+#![cfg(test)]
+use super::*;
+use soroban_sdk::token::Client as TokenClient;
+use soroban_sdk::token::StellarAssetClient as TokenAdminClient;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, Env,
+};
+
+struct ProgramSetup<'a> {
+    env: Env,
+    client: AhjoorPaymentsContractClient<'a>,
+    customer: Address,
+    merchant_a: Address,
+    merchant_b: Address,
+    token: Address,
+    token_admin: TokenAdminClient<'a>,
+}
+
+fn setup_programs<'a>() -> ProgramSetup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AhjoorPaymentsContract, ());
+    let client = AhjoorPaymentsContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let customer = Address::generate(&env);
+    let merchant_a = Address::generate(&env);
+    let merchant_b = Address::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let token_admin = TokenAdminClient::new(&env, &token);
+    token_admin.mint(&customer, &100_000_000);
+
+    client.initialize(&admin, &admin, &0u32);
+    client.set_min_collateral(&0i128);
+    client.approve_merchant(&merchant_a);
+    client.approve_merchant(&merchant_b);
+
+    ProgramSetup {
+        env,
+        client,
+        customer,
+        merchant_a,
+        merchant_b,
+        token,
+        token_admin,
+    }
+}
+
+fn pay(s: &ProgramSetup, merchant: &Address, amount: i128) -> u32 {
+    let pid = s.client.create_payment(
+        &s.customer,
+        merchant,
+        &amount,
+        &s.token,
+        &None,
+        &None,
+        &None,
+    );
+    s.client.complete_payment(&pid);
+    pid
+}
+
+#[test]
+fn test_tier_multiplier_follows_rolling_spend() {
+    let s = setup_programs();
+    // 1_000 points per 1_000_000 paid; 2x from 1_000_000 spent in 1_200 ledgers
+    let tiers = vec![
+        &s.env,
+        LoyaltyTier {
+            min_spend: 1_000_000,
+            multiplier_bps: 20_000,
+        },
+    ];
+    s.client
+        .configure_loyalty_program(&s.merchant_a, &1_000, &10_000, &0, &0, &1_200, &tiers);
+
+    // The first payment earns at the base rate and reaches the tier
+    pay(&s, &s.merchant_a, 1_000_000);
+    pay(&s, &s.merchant_a, 1_000_000);
+
+    let balance = s.client.get_loyalty_balance(&s.customer);
+    assert_eq!(balance.platform_points, 0);
+    let program = balance.programs.get(0).unwrap();
+    assert_eq!(program.merchant, s.merchant_a);
+    assert_eq!(program.points, 3_000);
+    assert_eq!(program.tier, 1);
+    assert_eq!(program.rolling_spend, 2_000_000);
+
+    // Spend rolls out of the window and the tier is lost
+    s.env.ledger().with_mut(|l| l.sequence_number += 1_300);
+    let program = s
+        .client
+        .get_loyalty_balance(&s.customer)
+        .programs
+        .get(0)
+        .unwrap();
+    assert_eq!(program.rolling_spend, 0);
+    assert_eq!(program.tier, 0);
+    assert_eq!(program.points, 3_000);
+}
+
+#[test]
+fn test_point_lots_expire_independently() {
+    let s = setup_programs();
+    // Lots live 1_200 ledgers after the 100-ledger slice they were earned in
+    s.client.configure_loyalty_program(
+        &s.merchant_a,
+        &100,
+        &10_000,
+        &0,
+        &1_200,
+        &0,
+        &Vec::new(&s.env),
+    );
+
+    pay(&s, &s.merchant_a, 1_000_000);
+    s.env.ledger().with_mut(|l| l.sequence_number = 500);
+    pay(&s, &s.merchant_a, 2_000_000);
+
+    let program = s
+        .client
+        .get_loyalty_balance(&s.customer)
+        .programs
+        .get(0)
+        .unwrap();
+    assert_eq!(program.points, 300);
+    assert_eq!(program.next_expiry_ledger, 1_300);
+    assert_eq!(program.expiring_points, 100);
+
+    // Redemption spends the soonest-expiring lot first
+    let pid = s.client.create_payment(
+        &s.customer,
+        &s.merchant_a,
+        &1_000,
+        &s.token,
+        &None,
+        &None,
+        &None,
+    );
+    s.client
+        .redeem_program_points(&s.customer, &pid, &s.merchant_a, &50);
+    let program = s
+        .client
+        .get_loyalty_balance(&s.customer)
+        .programs
+        .get(0)
+        .unwrap();
+    assert_eq!(program.points, 250);
+    assert_eq!(program.expiring_points, 50);
+
+    s.env.ledger().with_mut(|l| l.sequence_number = 1_300);
+    let program = s
+        .client
+        .get_loyalty_balance(&s.customer)
+        .programs
+        .get(0)
+        .unwrap();
+    assert_eq!(program.points, 200);
+    assert_eq!(program.next_expiry_ledger, 1_800);
+    assert_eq!(program.expiring_points, 200);
+}
+
+#[test]
+fn test_coalition_redemption_is_settled_by_issuer() {
+    let s = setup_programs();
+    let no_tiers = Vec::new(&s.env);
+    // 1 unit of discount per point at either merchant
+    s.client
+        .configure_loyalty_program(&s.merchant_a, &100, &10_000, &0, &0, &0, &no_tiers);
+    s.client
+        .configure_loyalty_program(&s.merchant_b, &100, &10_000, &0, &0, &0, &no_tiers);
+
+    let coalition_id = s.client.create_loyalty_coalition(&s.merchant_a, &s.token);
+    assert_eq!(
+        s.client
+            .try_join_loyalty_coalition(&s.merchant_b, &coalition_id),
+        Err(Ok(soroban_sdk::Error::from(
+            MerchantError::CoalitionInviteNotFound
+        )))
+    );
+    s.client
+        .invite_to_coalition(&s.merchant_a, &coalition_id, &s.merchant_b);
+    s.client
+        .join_loyalty_coalition(&s.merchant_b, &coalition_id);
+    assert_eq!(s.client.get_coalition(&coalition_id).members.len(), 2);
+
+    // A's rate cannot move while B honours its points
+    assert_eq!(
+        s.client
+            .try_configure_loyalty_program(&s.merchant_a, &100, &20_000, &0, &0, &0, &no_tiers),
+        Err(Ok(soroban_sdk::Error::from(
+            MerchantError::InvalidLoyaltyProgram
+        )))
+    );
+    assert_eq!(
+        s.client
+            .try_configure_loyalty_program(&s.merchant_a, &100, &5_000, &0, &0, &0, &no_tiers),
+        Err(Ok(soroban_sdk::Error::from(
+            MerchantError::LoyaltyRateFrozen
+        )))
+    );
+
+    // 100 points earned at A are spent at B, backed by A's reserve
+    pay(&s, &s.merchant_a, 1_000_000);
+    let pid = s.client.create_payment(
+        &s.customer,
+        &s.merchant_b,
+        &1_000,
+        &s.token,
+        &None,
+        &None,
+        &None,
+    );
+    assert_eq!(
+        s.client
+            .try_redeem_program_points(&s.customer, &pid, &s.merchant_a, &40),
+        Err(Ok(soroban_sdk::Error::from(
+            MerchantError::InsufficientCoalitionReserve
+        )))
+    );
+    s.token_admin.mint(&s.merchant_a, &100);
+    s.client
+        .fund_coalition_reserve(&s.merchant_a, &coalition_id, &100);
+    s.client
+        .redeem_program_points(&s.customer, &pid, &s.merchant_a, &40);
+    assert_eq!(s.client.get_payment(&pid).amount, 960);
+    assert_eq!(
+        s.client.get_coalition_reserve(&coalition_id, &s.merchant_a),
+        60
+    );
+    assert_eq!(
+        s.client
+            .get_coalition_debt(&coalition_id, &s.merchant_a, &s.merchant_b),
+        40
+    );
+
+    // The issuer cannot leave before settling
+    assert_eq!(
+        s.client.try_leave_loyalty_coalition(&s.merchant_a),
+        Err(Ok(soroban_sdk::Error::from(
+            MerchantError::CoalitionDebtOutstanding
+        )))
+    );
+
+    let token_client = TokenClient::new(&s.env, &s.token);
+    let merchant_b_before = token_client.balance(&s.merchant_b);
+    // Anyone can release the held discount to B
+    assert_eq!(
+        s.client
+            .settle_coalition_debt(&s.merchant_a, &coalition_id, &s.merchant_b),
+        40
+    );
+    assert_eq!(token_client.balance(&s.merchant_b), merchant_b_before + 40);
+    assert_eq!(
+        s.client
+            .get_coalition_debt(&coalition_id, &s.merchant_a, &s.merchant_b),
+        0
+    );
+
+    // Ownership passes to B when A leaves
+    s.client.leave_loyalty_coalition(&s.merchant_a);
+    let merchant_a_before = token_client.balance(&s.merchant_a);
+    s.client
+        .withdraw_coalition_reserve(&s.merchant_a, &coalition_id, &60);
+    assert_eq!(token_client.balance(&s.merchant_a), merchant_a_before + 60);
+    let coalition = s.client.get_coalition(&coalition_id);
+    assert_eq!(coalition.owner, s.merchant_b);
+    assert_eq!(coalition.members.len(), 1);
+}
+
+#[test]
+fn test_program_validation_and_cross_merchant_limits() {
+    let s = setup_programs();
+    let tiers = vec![
+        &s.env,
+        LoyaltyTier {
+            min_spend: 500,
+            multiplier_bps: 15_000,
+        },
+        LoyaltyTier {
+            min_spend: 500,
+            multiplier_bps: 20_000,
+        },
+    ];
+    assert_eq!(
+        s.client.try_configure_loyalty_program(
+            &s.merchant_a,
+            &100,
+            &10_000,
+            &0,
+            &0,
+            &1_000,
+            &tiers
+        ),
+        Err(Ok(soroban_sdk::Error::from(
+            MerchantError::InvalidLoyaltyProgram
+        )))
+    );
+
+    let no_tiers = Vec::new(&s.env);
+    s.client
+        .configure_loyalty_program(&s.merchant_a, &100, &10_000, &0, &0, &0, &no_tiers);
+    s.client
+        .configure_loyalty_program(&s.merchant_b, &100, &10_000, &0, &0, &0, &no_tiers);
+    pay(&s, &s.merchant_a, 1_000_000);
+
+    // B is not in a coalition with A
+    let pid = s.client.create_payment(
+        &s.customer,
+        &s.merchant_b,
+        &1_000,
+        &s.token,
+        &None,
+        &None,
+        &None,
+    );
+    assert_eq!(
+        s.client
+            .try_redeem_program_points(&s.customer, &pid, &s.merchant_a, &10),
+        Err(Ok(soroban_sdk::Error::from(
+            MerchantError::NotCoalitionMember
+        )))
+    );
+
+    // Cross-merchant redemptions must be in the coalition token
+    let other = s
+        .env
+        .register_stellar_asset_contract_v2(s.merchant_a.clone())
+        .address();
+    let coalition_id = s.client.create_loyalty_coalition(&s.merchant_a, &other);
+    s.client
+        .invite_to_coalition(&s.merchant_a, &coalition_id, &s.merchant_b);
+    s.client
+        .join_loyalty_coalition(&s.merchant_b, &coalition_id);
+    assert_eq!(
+        s.client
+            .try_redeem_program_points(&s.customer, &pid, &s.merchant_a, &10),
+        Err(Ok(soroban_sdk::Error::from(
+            MerchantError::CoalitionTokenMismatch
+        )))
+    );
+}
@@ -128,7 +128,7 @@ fn test_ban_appeal_rejection_cooldown() {
 // Test: one-active-appeal guard
 // ---------------------------------------------------------------------------
 #[test]
-#[should_panic(expected = "An active appeal already exists")]
+#[should_panic(expected = "Error(Contract, #155)")] // AppealAlreadyExists
 fn test_duplicate_appeal_rejected() {
     let (env, client, admin, merchant, _token_addr, _token_client, _token_admin_client) = setup_ban();
 
@@ -160,7 +160,7 @@ fn test_reinstate_merchant() {
 // Test: only banned merchant can submit appeal
 // ---------------------------------------------------------------------------
 #[test]
-#[should_panic(expected = "Only banned merchants can submit an appeal")]
+#[should_panic(expected = "Error(Contract, #153)")] // MerchantNotBanned
 fn test_non_banned_merchant_cannot_appeal() {
     let (_env, client, _admin, merchant, _token_addr, _token_client, _token_admin_client) = setup_ban();
 
@@ -173,7 +173,7 @@ fn test_non_banned_merchant_cannot_appeal() {
 // Test: cooling-off period is enforced
 // ---------------------------------------------------------------------------
 #[test]
-#[should_panic(expected = "Cooling-off period has not elapsed")]
+#[should_panic(expected = "Error(Contract, #159)")] // ReinstatementCoolingOffActive
 fn test_cooling_off_period_enforced() {
     let (env, client, admin, merchant, _token_addr, _token_client, _token_admin_client) = setup_ban();
 
@@ -236,7 +236,7 @@ fn test_rejected_appeal_status() {
 // Test: cannot approve already resolved appeal
 // ---------------------------------------------------------------------------
 #[test]
-#[should_panic(expected = "Appeal already resolved or not pending")]
+#[should_panic(expected = "Error(Contract, #156)")] // AppealNotPending
 fn test_cannot_approve_resolved_appeal() {
     let (env, client, admin, merchant, _token_addr, _token_client, _token_admin_client) = setup_ban();
 
@@ -253,7 +253,7 @@ fn test_cannot_approve_resolved_appeal() {
 // Test: cannot reject already resolved appeal
 // ---------------------------------------------------------------------------
 #[test]
-#[should_panic(expected = "Appeal already resolved or not pending")]
+#[should_panic(expected = "Error(Contract, #156)")] // AppealNotPending
 fn test_cannot_reject_resolved_appeal() {
     let (env, client, admin, merchant, _token_addr, _token_client, _token_admin_client) = setup_ban();
 
@@ -270,7 +270,7 @@ fn test_cannot_reject_resolved_appeal() {
 // Test: cannot submit second appeal while in cooling-off
 // ---------------------------------------------------------------------------
 #[test]
-#[should_panic(expected = "An active appeal already exists")]
+#[should_panic(expected = "Error(Contract, #155)")] // AppealAlreadyExists
 fn test_cannot_appeal_during_cooling_off() {
     let (env, client, admin, merchant, _token_addr, _token_client, _token_admin_client) = setup_ban();
 
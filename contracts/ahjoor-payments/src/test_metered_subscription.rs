@@ -0,0 +1,257 @@
+#![cfg(test)]
+use super::*;
+use soroban_sdk::token::Client as TokenClient;
+use soroban_sdk::token::StellarAssetClient as TokenAdminClient;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, Env,
+};
+
+struct MeteredSetup<'a> {
+    env: Env,
+    client: AhjoorPaymentsContractClient<'a>,
+    merchant: Address,
+    subscriber: Address,
+    token_client: TokenClient<'a>,
+    sub_id: u32,
+}
+
+fn setup_metered<'a>() -> MeteredSetup<'a> {
+    let env = Env::default();
+    // Charges move the subscriber's tokens from within the contract call
+    env.mock_all_auths_allowing_non_root_auth();
+    env.ledger().set_timestamp(1_000);
+
+    let contract_id = env.register(AhjoorPaymentsContract, ());
+    let client = AhjoorPaymentsContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let merchant = Address::generate(&env);
+    let subscriber = Address::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let token_client = TokenClient::new(&env, &token);
+    TokenAdminClient::new(&env, &token).mint(&subscriber, &10_000);
+
+    client.initialize(&admin, &admin, &0u32);
+    client.set_min_collateral(&0i128);
+    client.approve_merchant(&merchant);
+    // The fixed amount is superseded once metering is configured
+    let sub_id = client.create_subscription(&subscriber, &merchant, &1, &token, &1_000, &0);
+
+    MeteredSetup {
+        env,
+        client,
+        merchant,
+        subscriber,
+        token_client,
+        sub_id,
+    }
+}
+
+fn tiers(env: &Env, bands: &[(i128, i128)]) -> Vec<UsagePriceTier> {
+    let mut out = Vec::new(env);
+    for (up_to, unit_price) in bands {
+        out.push_back(UsagePriceTier {
+            up_to: *up_to,
+            unit_price: *unit_price,
+        });
+    }
+    out
+}
+
+#[test]
+fn test_tiered_usage_is_charged_in_arrears() {
+    let s = setup_metered();
+    // First 100 units at 2, the rest at 1
+    s.client.configure_metered_billing(
+        &s.merchant,
+        &s.sub_id,
+        &MeteredPricingMode::Tiered,
+        &tiers(&s.env, &[(100, 2), (0, 1)]),
+    );
+    s.client.set_usage_cap(&s.subscriber, &s.sub_id, &1_000);
+    s.client.report_usage(&s.merchant, &s.sub_id, &80);
+    s.env.ledger().set_timestamp(1_500);
+    s.client.report_usage(&s.merchant, &s.sub_id, &70);
+
+    // The first period runs a full interval from when metering started
+    assert_eq!(
+        s.client.try_charge_subscription(&s.sub_id),
+        Err(Ok(soroban_sdk::Error::from(
+            SubscriptionError::IntervalNotElapsed
+        )))
+    );
+
+    s.env.ledger().set_timestamp(2_000);
+    s.client.charge_subscription(&s.sub_id);
+    assert_eq!(s.token_client.balance(&s.merchant), 250);
+
+    let period = s.client.get_usage_period(&s.sub_id, &0);
+    assert_eq!(period.usage, 150);
+    assert_eq!(period.charged, 250);
+    assert_eq!(period.charged_at, 2_000);
+    let records = s.client.get_usage_records(&s.sub_id, &0);
+    assert_eq!(records.len(), 2);
+    assert_eq!(records.get(1).unwrap().reported_at, 1_500);
+
+    // New usage lands in the next period
+    s.client.report_usage(&s.merchant, &s.sub_id, &10);
+    assert_eq!(s.client.get_usage_records(&s.sub_id, &1).len(), 1);
+    assert_eq!(s.client.get_usage_period(&s.sub_id, &1).charged, 20);
+}
+
+#[test]
+fn test_volume_pricing_respects_subscriber_cap() {
+    let s = setup_metered();
+    // Past 100 units every unit costs 2
+    s.client.configure_metered_billing(
+        &s.merchant,
+        &s.sub_id,
+        &MeteredPricingMode::Volume,
+        &tiers(&s.env, &[(100, 3), (0, 2)]),
+    );
+    s.client.set_usage_cap(&s.subscriber, &s.sub_id, &200);
+    s.client.report_usage(&s.merchant, &s.sub_id, &150);
+
+    // The open period can be audited before it is charged
+    let preview = s.client.get_usage_period(&s.sub_id, &0);
+    assert_eq!(preview.priced, 300);
+    assert_eq!(preview.charged, 200);
+    assert_eq!(preview.charged_at, 0);
+
+    s.env.ledger().set_timestamp(2_000);
+    s.client.charge_subscription(&s.sub_id);
+    assert_eq!(s.token_client.balance(&s.merchant), 200);
+    assert_eq!(s.client.get_metered_pricing(&s.sub_id).max_per_period, 200);
+
+    // A period without usage charges nothing
+    s.env.ledger().set_timestamp(3_000);
+    s.client.charge_subscription(&s.sub_id);
+    assert_eq!(s.token_client.balance(&s.merchant), 200);
+    assert_eq!(s.client.get_subscription(&s.sub_id).charges_count, 2);
+}
+
+#[test]
+fn test_only_authorized_reporters_submit_usage() {
+    let s = setup_metered();
+    let reporter = Address::generate(&s.env);
+    assert_eq!(
+        s.client.try_report_usage(&s.merchant, &s.sub_id, &5),
+        Err(Ok(soroban_sdk::Error::from(
+            SubscriptionError::NotMeteredSubscription
+        )))
+    );
+
+    s.client.configure_metered_billing(
+        &s.merchant,
+        &s.sub_id,
+        &MeteredPricingMode::Tiered,
+        &tiers(&s.env, &[(0, 1)]),
+    );
+    // Nothing is metered until the subscriber has capped the charge
+    assert_eq!(
+        s.client.try_report_usage(&s.merchant, &s.sub_id, &5),
+        Err(Ok(soroban_sdk::Error::from(
+            SubscriptionError::UsageCapNotSet
+        )))
+    );
+    s.client.set_usage_cap(&s.subscriber, &s.sub_id, &100);
+    assert_eq!(
+        s.client.try_report_usage(&reporter, &s.sub_id, &5),
+        Err(Ok(soroban_sdk::Error::from(
+            SubscriptionError::NotUsageReporter
+        )))
+    );
+
+    s.client.set_usage_reporter(&s.merchant, &reporter, &true);
+    assert_eq!(s.client.report_usage(&reporter, &s.sub_id, &5), 1);
+    let record = s.client.get_usage_records(&s.sub_id, &0).get(0).unwrap();
+    assert_eq!(record.reported_by, reporter);
+
+    s.client.set_usage_reporter(&s.merchant, &reporter, &false);
+    assert_eq!(
+        s.client.try_report_usage(&reporter, &s.sub_id, &5),
+        Err(Ok(soroban_sdk::Error::from(
+            SubscriptionError::NotUsageReporter
+        )))
+    );
+}
+
+#[test]
+fn test_price_tables_are_validated() {
+    let s = setup_metered();
+    // The last band must be unbounded
+    assert_eq!(
+        s.client.try_configure_metered_billing(
+            &s.merchant,
+            &s.sub_id,
+            &MeteredPricingMode::Tiered,
+            &tiers(&s.env, &[(100, 2), (200, 1)]),
+        ),
+        Err(Ok(soroban_sdk::Error::from(
+            SubscriptionError::InvalidMeteredPricing
+        )))
+    );
+    // Bands must ascend
+    assert_eq!(
+        s.client.try_configure_metered_billing(
+            &s.merchant,
+            &s.sub_id,
+            &MeteredPricingMode::Tiered,
+            &tiers(&s.env, &[(100, 2), (50, 1), (0, 1)]),
+        ),
+        Err(Ok(soroban_sdk::Error::from(
+            SubscriptionError::InvalidMeteredPricing
+        )))
+    );
+    assert_eq!(
+        s.client.try_configure_metered_billing(
+            &s.merchant,
+            &s.sub_id,
+            &MeteredPricingMode::Volume,
+            &vec![&s.env],
+        ),
+        Err(Ok(soroban_sdk::Error::from(
+            SubscriptionError::InvalidMeteredPricing
+        )))
+    );
+    assert_eq!(
+        s.client.try_set_usage_cap(&s.subscriber, &s.sub_id, &100),
+        Err(Ok(soroban_sdk::Error::from(
+            SubscriptionError::NotMeteredSubscription
+        )))
+    );
+}
+
+#[test]
+fn test_replaced_table_prices_the_next_period() {
+    let s = setup_metered();
+    s.client.configure_metered_billing(
+        &s.merchant,
+        &s.sub_id,
+        &MeteredPricingMode::Tiered,
+        &tiers(&s.env, &[(0, 1)]),
+    );
+    s.client.set_usage_cap(&s.subscriber, &s.sub_id, &1_000);
+    s.client.report_usage(&s.merchant, &s.sub_id, &100);
+
+    // Raising the price just before the charge leaves reported usage alone
+    s.client.configure_metered_billing(
+        &s.merchant,
+        &s.sub_id,
+        &MeteredPricingMode::Tiered,
+        &tiers(&s.env, &[(0, 5)]),
+    );
+    assert_eq!(s.client.get_usage_period(&s.sub_id, &0).charged, 100);
+    s.env.ledger().set_timestamp(2_000);
+    s.client.charge_subscription(&s.sub_id);
+    assert_eq!(s.token_client.balance(&s.merchant), 100);
+
+    let pricing = s.client.get_metered_pricing(&s.sub_id);
+    assert_eq!(pricing.tiers.get(0).unwrap().unit_price, 5);
+    assert_eq!(pricing.pending_tiers.len(), 0);
+    s.client.report_usage(&s.merchant, &s.sub_id, &10);
+    assert_eq!(s.client.get_usage_period(&s.sub_id, &1).charged, 50);
+}
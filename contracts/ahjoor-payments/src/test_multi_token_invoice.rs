@@ -0,0 +1,420 @@
+#![cfg(test)]
+use super::*;
+use crate::multi_token_invoice::{InvoiceLineItem, InvoiceStatus};
+use ahjoor_token_whitelist::{TokenWhitelistContract, TokenWhitelistContractClient};
+use soroban_sdk::token::Client as TokenClient;
+use soroban_sdk::token::StellarAssetClient as TokenAdminClient;
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, Map, String, Vec};
+
+// ---------------------------------------------------------------------------
+// Mock invoice oracle: get_price(base, quote) scaled by 10^6
+// ---------------------------------------------------------------------------
+mod mock_invoice_oracle {
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env};
+
+    #[contract]
+    pub struct MockInvoiceOracle;
+
+    #[contractimpl]
+    impl MockInvoiceOracle {
+        pub fn set_price(env: Env, price: i128) {
+            env.storage().instance().set(&symbol_short!("price"), &price);
+        }
+
+        pub fn get_price(env: Env, _base: Address, _quote: Address) -> Option<i128> {
+            env.storage().instance().get(&symbol_short!("price"))
+        }
+    }
+}
+
+use mock_invoice_oracle::{MockInvoiceOracle, MockInvoiceOracleClient};
+
+// ---------------------------------------------------------------------------
+// Mock swap router: pays out token_out at a fixed rate scaled by 10^6 from
+// its own liquidity, without checking min_amount_out itself
+// ---------------------------------------------------------------------------
+mod mock_swap_router {
+    use soroban_sdk::{contract, contractimpl, symbol_short, token, Address, Env};
+
+    #[contract]
+    pub struct MockSwapRouter;
+
+    #[contractimpl]
+    impl MockSwapRouter {
+        pub fn set_rate(env: Env, rate: i128) {
+            env.storage().instance().set(&symbol_short!("rate"), &rate);
+        }
+
+        pub fn swap_exact_in(
+            env: Env,
+            _token_in: Address,
+            token_out: Address,
+            amount_in: i128,
+            _min_amount_out: i128,
+            to: Address,
+        ) -> i128 {
+            let rate: i128 = env.storage().instance().get(&symbol_short!("rate")).unwrap();
+            let out = amount_in * rate / 1_000_000;
+            token::Client::new(&env, &token_out).transfer(&env.current_contract_address(), &to, &out);
+            out
+        }
+    }
+}
+
+use mock_swap_router::{MockSwapRouter, MockSwapRouterClient};
+
+struct InvoiceSetup<'a> {
+    env: Env,
+    client: AhjoorPaymentsContractClient<'a>,
+    admin: Address,
+    merchant: Address,
+    customer: Address,
+    /// Base currency and preferred settlement token.
+    usdc: Address,
+    /// Secondary accepted token.
+    eurc: Address,
+}
+
+fn setup_invoice<'a>() -> InvoiceSetup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AhjoorPaymentsContract, ());
+    let client = AhjoorPaymentsContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let merchant = Address::generate(&env);
+    let customer = Address::generate(&env);
+    let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    let eurc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+
+    client.initialize(&admin, &admin, &0u32);
+
+    let usdc_admin = TokenAdminClient::new(&env, &usdc);
+    let eurc_admin = TokenAdminClient::new(&env, &eurc);
+    usdc_admin.mint(&customer, &10_000);
+    eurc_admin.mint(&customer, &10_000);
+
+    InvoiceSetup {
+        env,
+        client,
+        admin,
+        merchant,
+        customer,
+        usdc,
+        eurc,
+    }
+}
+
+/// Issues a 1_000 USDC invoice accepting USDC and EURC, settling in USDC.
+/// EURC converts at 1.1 USDC per EURC.
+fn create_invoice(s: &InvoiceSetup, oracle: Option<Address>) -> u32 {
+    s.client
+        .set_invoice_conversion_rate(&s.merchant, &s.eurc, &1_100_000);
+    s.client.create_multi_token_invoice(
+        &s.merchant,
+        &s.customer,
+        &1_000,
+        &s.usdc,
+        &vec![&s.env, s.usdc.clone(), s.eurc.clone()],
+        &s.usdc,
+        &Vec::<InvoiceLineItem>::new(&s.env),
+        &0u64,
+        &Map::<String, String>::new(&s.env),
+        &oracle,
+    )
+}
+
+/// Sets a swap router paying out at `rate` with liquidity in both tokens.
+fn set_router(s: &InvoiceSetup, rate: i128) {
+    let router = s.env.register(MockSwapRouter, ());
+    MockSwapRouterClient::new(&s.env, &router).set_rate(&rate);
+    TokenAdminClient::new(&s.env, &s.usdc).mint(&router, &10_000);
+    TokenAdminClient::new(&s.env, &s.eurc).mint(&router, &10_000);
+    s.client.set_swap_router(&s.admin, &router);
+}
+
+#[test]
+fn test_partial_payments_in_two_tokens_fill_invoice() {
+    let s = setup_invoice();
+    let invoice_id = create_invoice(&s, None);
+
+    let first = s
+        .client
+        .pay_multi_token_invoice(&invoice_id, &s.customer, &s.usdc, &450);
+    assert_eq!(first.amount_in_base, 450);
+    assert_eq!(
+        s.client.get_multi_token_invoice(&invoice_id).unwrap().status,
+        InvoiceStatus::PartiallyPaid
+    );
+    assert_eq!(s.client.get_invoice_balance(&invoice_id), 550);
+
+    // 500 EURC at 1.1 = 550 USDC
+    let second = s
+        .client
+        .pay_multi_token_invoice(&invoice_id, &s.customer, &s.eurc, &500);
+    assert_eq!(second.amount_in_base, 550);
+    assert_eq!(
+        s.client.get_multi_token_invoice(&invoice_id).unwrap().status,
+        InvoiceStatus::FullyPaid
+    );
+    assert_eq!(s.client.get_invoice_balance(&invoice_id), 0);
+
+    let payments = s.client.get_invoice_payments(&invoice_id);
+    assert_eq!(payments.len(), 2);
+    assert_eq!(payments.get(0).unwrap().token, s.usdc);
+    assert_eq!(payments.get(1).unwrap().token, s.eurc);
+
+    assert_eq!(TokenClient::new(&s.env, &s.usdc).balance(&s.client.address), 450);
+    assert_eq!(TokenClient::new(&s.env, &s.eurc).balance(&s.client.address), 500);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #203)")] // PaymentExceedsInvoiceAmount
+fn test_overpayment_in_single_token_rejected() {
+    let s = setup_invoice();
+    let invoice_id = create_invoice(&s, None);
+
+    s.client
+        .pay_multi_token_invoice(&invoice_id, &s.customer, &s.usdc, &1_001);
+}
+
+#[test]
+fn test_overpayment_across_tokens_rejected_and_refunds_nothing() {
+    let s = setup_invoice();
+    let invoice_id = create_invoice(&s, None);
+
+    s.client
+        .pay_multi_token_invoice(&invoice_id, &s.customer, &s.usdc, &600);
+    // 400 EURC = 440 USDC, which would take the combined total to 1_040
+    let result = s
+        .client
+        .try_pay_multi_token_invoice(&invoice_id, &s.customer, &s.eurc, &400);
+    assert!(result.is_err());
+
+    assert_eq!(TokenClient::new(&s.env, &s.eurc).balance(&s.customer), 10_000);
+    assert_eq!(s.client.get_invoice_balance(&invoice_id), 400);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #204)")] // TokenNotAccepted
+fn test_payment_in_unaccepted_token_rejected() {
+    let s = setup_invoice();
+    let invoice_id = create_invoice(&s, None);
+    let other = s
+        .env
+        .register_stellar_asset_contract_v2(Address::generate(&s.env))
+        .address();
+
+    s.client
+        .pay_multi_token_invoice(&invoice_id, &s.customer, &other, &100);
+}
+
+#[test]
+fn test_cross_token_payment_uses_oracle_price() {
+    let s = setup_invoice();
+    let oracle_id = s.env.register(MockInvoiceOracle, ());
+    // Oracle quotes EURC at 1.08 USDC, within 5% of the merchant's 1.1 rate
+    MockInvoiceOracleClient::new(&s.env, &oracle_id).set_price(&1_080_000);
+    let invoice_id = create_invoice(&s, Some(oracle_id));
+
+    let payment = s.client.pay_invoice_cross_token(
+        &invoice_id,
+        &s.customer,
+        &s.eurc,
+        &500,
+        &500u32,
+    );
+    assert_eq!(payment.amount_in_base, 540);
+    assert_eq!(s.client.get_invoice_balance(&invoice_id), 460);
+    assert_eq!(TokenClient::new(&s.env, &s.eurc).balance(&s.client.address), 500);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #211)")] // SlippageExceeded
+fn test_cross_token_payment_rejects_excess_slippage() {
+    let s = setup_invoice();
+    let oracle_id = s.env.register(MockInvoiceOracle, ());
+    // 1.0 vs the snapshotted 1.1 is a ~9% deviation
+    MockInvoiceOracleClient::new(&s.env, &oracle_id).set_price(&1_000_000);
+    let invoice_id = create_invoice(&s, Some(oracle_id));
+
+    s.client
+        .pay_invoice_cross_token(&invoice_id, &s.customer, &s.eurc, &500, &100u32);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #212)")] // OracleNotConfigured
+fn test_cross_token_payment_requires_oracle() {
+    let s = setup_invoice();
+    let invoice_id = create_invoice(&s, None);
+
+    s.client
+        .pay_invoice_cross_token(&invoice_id, &s.customer, &s.eurc, &500, &100u32);
+}
+
+#[test]
+fn test_settlement_releases_only_the_invoice_escrow_once() {
+    let s = setup_invoice();
+    set_router(&s, 1_100_000);
+    let invoice_id = create_invoice(&s, None);
+    s.client
+        .pay_multi_token_invoice(&invoice_id, &s.customer, &s.usdc, &450);
+    s.client
+        .pay_multi_token_invoice(&invoice_id, &s.customer, &s.eurc, &500);
+    // Funds escrowed for another invoice are never touched
+    let other_id = create_invoice(&s, None);
+    s.client
+        .pay_multi_token_invoice(&other_id, &s.customer, &s.usdc, &300);
+
+    let batch = s
+        .client
+        .settle_multi_token_invoices(&s.merchant, &vec![&s.env, invoice_id]);
+    assert_eq!(batch.total_settlement_amount, 1_000);
+    // The EURC leg is swapped, so the merchant is paid only in USDC
+    assert_eq!(TokenClient::new(&s.env, &s.usdc).balance(&s.merchant), 1_000);
+    assert_eq!(TokenClient::new(&s.env, &s.eurc).balance(&s.merchant), 0);
+    assert_eq!(TokenClient::new(&s.env, &s.usdc).balance(&s.client.address), 300);
+    assert_eq!(
+        s.client.get_multi_token_invoice(&invoice_id).unwrap().status,
+        InvoiceStatus::Settled
+    );
+    assert_eq!(s.client.get_settlement_batch(&batch.batch_id), Some(batch));
+
+    let again = s
+        .client
+        .try_settle_multi_token_invoices(&s.merchant, &vec![&s.env, invoice_id]);
+    assert!(again.is_err());
+}
+
+#[test]
+fn test_settlement_values_batch_at_oracle_rate() {
+    let s = setup_invoice();
+    let oracle_id = s.env.register(MockInvoiceOracle, ());
+    MockInvoiceOracleClient::new(&s.env, &oracle_id).set_price(&900_000);
+    set_router(&s, 900_000);
+    // USDC-denominated, settling in EURC
+    let invoice_id = s.client.create_multi_token_invoice(
+        &s.merchant,
+        &s.customer,
+        &1_000,
+        &s.usdc,
+        &vec![&s.env, s.usdc.clone()],
+        &s.eurc,
+        &Vec::<InvoiceLineItem>::new(&s.env),
+        &0u64,
+        &Map::<String, String>::new(&s.env),
+        &Some(oracle_id),
+    );
+    s.client
+        .pay_multi_token_invoice(&invoice_id, &s.customer, &s.usdc, &1_000);
+
+    let batch = s
+        .client
+        .settle_multi_token_invoices(&s.merchant, &vec![&s.env, invoice_id]);
+    assert_eq!(batch.total_settlement_amount, 900);
+    assert_eq!(
+        s.client
+            .get_multi_token_invoice(&invoice_id)
+            .unwrap()
+            .settlement_conversion_rate,
+        900_000
+    );
+    assert_eq!(TokenClient::new(&s.env, &s.eurc).balance(&s.merchant), 900);
+    assert_eq!(TokenClient::new(&s.env, &s.usdc).balance(&s.merchant), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #211)")] // SlippageExceeded
+fn test_settlement_rejects_swap_below_oracle_value() {
+    let s = setup_invoice();
+    // Router pays 1.0 USDC per EURC against the 1.1 the payment was valued at
+    set_router(&s, 1_000_000);
+    let invoice_id = create_invoice(&s, None);
+    s.client
+        .pay_multi_token_invoice(&invoice_id, &s.customer, &s.eurc, &500);
+    s.client
+        .pay_multi_token_invoice(&invoice_id, &s.customer, &s.usdc, &450);
+
+    s.client
+        .settle_multi_token_invoices(&s.merchant, &vec![&s.env, invoice_id]);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #92)")] // SwapRouterNotConfigured
+fn test_settlement_in_another_token_requires_router() {
+    let s = setup_invoice();
+    let invoice_id = create_invoice(&s, None);
+    s.client
+        .pay_multi_token_invoice(&invoice_id, &s.customer, &s.eurc, &500);
+    s.client
+        .pay_multi_token_invoice(&invoice_id, &s.customer, &s.usdc, &450);
+
+    s.client
+        .settle_multi_token_invoices(&s.merchant, &vec![&s.env, invoice_id]);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #202)")] // InvalidInvoiceStatus
+fn test_settlement_requires_fully_paid_invoice() {
+    let s = setup_invoice();
+    let invoice_id = create_invoice(&s, None);
+    s.client
+        .pay_multi_token_invoice(&invoice_id, &s.customer, &s.usdc, &100);
+
+    s.client
+        .settle_multi_token_invoices(&s.merchant, &vec![&s.env, invoice_id]);
+}
+
+#[test]
+fn test_cancel_refunds_each_payment_in_original_token() {
+    let s = setup_invoice();
+    let invoice_id = create_invoice(&s, None);
+    s.client
+        .pay_multi_token_invoice(&invoice_id, &s.customer, &s.usdc, &300);
+    s.client
+        .pay_multi_token_invoice(&invoice_id, &s.customer, &s.eurc, &200);
+
+    s.client.cancel_multi_token_invoice(&invoice_id);
+
+    assert_eq!(TokenClient::new(&s.env, &s.usdc).balance(&s.customer), 10_000);
+    assert_eq!(TokenClient::new(&s.env, &s.eurc).balance(&s.customer), 10_000);
+    assert_eq!(
+        s.client.get_multi_token_invoice(&invoice_id).unwrap().status,
+        InvoiceStatus::Cancelled
+    );
+    assert!(s
+        .client
+        .try_pay_multi_token_invoice(&invoice_id, &s.customer, &s.usdc, &100)
+        .is_err());
+}
+
+#[test]
+fn test_conversion_rate_is_snapshotted_at_issuance() {
+    let s = setup_invoice();
+    let invoice_id = create_invoice(&s, None);
+    s.client
+        .set_invoice_conversion_rate(&s.merchant, &s.eurc, &2_000_000);
+
+    let payment = s
+        .client
+        .pay_multi_token_invoice(&invoice_id, &s.customer, &s.eurc, &100);
+    assert_eq!(payment.amount_in_base, 110);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #5)")] // TokenNotAllowed
+fn test_invoice_rejects_token_outside_whitelist() {
+    let s = setup_invoice();
+    let admin = Address::generate(&s.env);
+    let whitelist_id = s.env.register(TokenWhitelistContract, ());
+    let whitelist = TokenWhitelistContractClient::new(&s.env, &whitelist_id);
+    whitelist.initialize(&admin);
+    whitelist.add_token(&admin, &s.usdc);
+    let payments_admin = s.client.get_admin();
+    s.client
+        .set_token_whitelist_contract(&payments_admin, &whitelist_id);
+
+    // EURC is accepted on the invoice but not whitelisted
+    create_invoice(&s, None);
+}
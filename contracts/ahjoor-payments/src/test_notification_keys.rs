@@ -95,7 +95,7 @@ fn test_update_notification_key() {
 }
 
 #[test]
-#[should_panic(expected = "Notification key exceeds maximum length of 128 bytes")]
+#[should_panic(expected = "Error(Contract, #170)")] // InvalidNotificationKey
 fn test_oversized_key_rejected() {
     let (_env, _admin, _customer, merchant, _token, client) = setup_test_env();
 
@@ -110,7 +110,7 @@ fn test_oversized_key_rejected() {
 }
 
 #[test]
-#[should_panic(expected = "Notification key cannot be empty")]
+#[should_panic(expected = "Error(Contract, #170)")] // InvalidNotificationKey
 fn test_empty_key_rejected() {
     let (env, _admin, _customer, merchant, _token, client) = setup_test_env();
 
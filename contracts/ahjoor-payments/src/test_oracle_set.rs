@@ -0,0 +1,243 @@
+#![cfg(test)]
+use super::*;
+use soroban_sdk::token::Client as TokenClient;
+use soroban_sdk::token::StellarAssetClient as TokenAdminClient;
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    vec, Address, Env, IntoVal,
+};
+
+mod set_mock_oracle {
+    use crate::PriceData;
+    use soroban_sdk::{contract, contractimpl, contracttype, Address, Env};
+
+    #[contracttype]
+    enum OracleKey {
+        Price,
+    }
+
+    #[contract]
+    pub struct SetMockOracle;
+
+    #[contractimpl]
+    impl SetMockOracle {
+        pub fn set_price(env: Env, price: i128, timestamp: u64) {
+            env.storage()
+                .instance()
+                .set(&OracleKey::Price, &PriceData { price, timestamp });
+        }
+
+        pub fn lastprice(env: Env, _base: Address, _quote: Address) -> Option<PriceData> {
+            env.storage().instance().get(&OracleKey::Price)
+        }
+    }
+}
+
+use set_mock_oracle::{SetMockOracle, SetMockOracleClient};
+
+struct OracleSetSetup<'a> {
+    env: Env,
+    client: AhjoorPaymentsContractClient<'a>,
+    admin: Address,
+    customer: Address,
+    merchant: Address,
+    usdc_addr: Address,
+    xlm_addr: Address,
+    xlm_client: TokenClient<'a>,
+    oracles: Vec<Address>,
+}
+
+/// Three XLM/USDC oracles, max age 300s, the ledger at t = 1_000.
+fn setup_oracle_set<'a>() -> OracleSetSetup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let contract_id = env.register(AhjoorPaymentsContract, ());
+    let client = AhjoorPaymentsContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let customer = Address::generate(&env);
+    let merchant = Address::generate(&env);
+    let usdc_addr = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let xlm_addr = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let xlm_client = TokenClient::new(&env, &xlm_addr);
+    TokenAdminClient::new(&env, &xlm_addr).mint(&customer, &10_000);
+
+    let oracles = vec![
+        &env,
+        env.register(SetMockOracle, ()),
+        env.register(SetMockOracle, ()),
+        env.register(SetMockOracle, ()),
+    ];
+
+    client.initialize(&admin, &admin, &0);
+    client.set_oracle(&oracles.get(0).unwrap(), &usdc_addr, &300u64);
+
+    OracleSetSetup {
+        env,
+        client,
+        admin,
+        customer,
+        merchant,
+        usdc_addr,
+        xlm_addr,
+        xlm_client,
+        oracles,
+    }
+}
+
+fn set_price(s: &OracleSetSetup, index: u32, price: i128, timestamp: u64) {
+    SetMockOracleClient::new(&s.env, &s.oracles.get(index).unwrap()).set_price(&price, &timestamp);
+}
+
+fn count_events(env: &Env, name: &str) -> usize {
+    env.events()
+        .all()
+        .iter()
+        .filter(|e| e.1 == (Symbol::new(env, name),).into_val(env))
+        .count()
+}
+
+#[test]
+fn test_checkout_priced_at_median_and_outlier_rejected() {
+    let s = setup_oracle_set();
+    s.client
+        .set_oracle_set(&s.admin, &s.xlm_addr, &s.usdc_addr, &s.oracles, &2, &500);
+
+    // A feed quoting XLM at 10x would let the customer underpay
+    set_price(&s, 0, 10_000_000, 1_000);
+    set_price(&s, 1, 1_000_000, 1_000);
+    set_price(&s, 2, 1_020_000, 1_000);
+    assert_eq!(
+        s.client.try_create_payment_multi_token(
+            &s.customer,
+            &s.merchant,
+            &100,
+            &s.xlm_addr,
+            &Some(50)
+        ),
+        Err(Ok(soroban_sdk::Error::from(
+            PaymentError::OracleDeviationExceeded
+        )))
+    );
+
+    // Median of 0.098, 0.100 and 0.102 USDC/XLM is 0.100 → 1_000 XLM for 100 USDC
+    set_price(&s, 0, 980_000, 1_000);
+    s.client
+        .create_payment_multi_token(&s.customer, &s.merchant, &100, &s.xlm_addr, &Some(50));
+    assert_eq!(count_events(&s.env, "oracle_price_aggregated"), 1);
+    assert_eq!(s.xlm_client.balance(&s.client.address), 1_000);
+}
+
+#[test]
+fn test_stale_source_fails_over_to_remaining_sources() {
+    let s = setup_oracle_set();
+    s.client
+        .set_oracle_set(&s.admin, &s.xlm_addr, &s.usdc_addr, &s.oracles, &2, &500);
+
+    set_price(&s, 0, 500_000, 500);
+    set_price(&s, 1, 1_000_000, 1_000);
+    set_price(&s, 2, 1_000_000, 1_000);
+    s.client
+        .create_payment_multi_token(&s.customer, &s.merchant, &100, &s.xlm_addr, &Some(50));
+    assert_eq!(count_events(&s.env, "oracle_failover"), 1);
+    assert_eq!(s.xlm_client.balance(&s.client.address), 1_000);
+
+    // With a second source stale, too few remain
+    s.env.ledger().set_timestamp(1_400);
+    set_price(&s, 2, 1_000_000, 1_400);
+    assert_eq!(
+        s.client.try_create_payment_multi_token(
+            &s.customer,
+            &s.merchant,
+            &100,
+            &s.xlm_addr,
+            &Some(50)
+        ),
+        Err(Ok(soroban_sdk::Error::from(
+            PaymentError::InsufficientOracleSources
+        )))
+    );
+}
+
+#[test]
+fn test_oracle_set_validation() {
+    let s = setup_oracle_set();
+    let duplicated = vec![&s.env, s.oracles.get(0).unwrap(), s.oracles.get(0).unwrap()];
+    for (oracles, min_sources, max_deviation_bps) in [
+        (duplicated, 1u32, 500u32),
+        (s.oracles.clone(), 4, 500),
+        (Vec::new(&s.env), 1, 500),
+        (s.oracles.clone(), 2, 10_001),
+    ] {
+        assert_eq!(
+            s.client.try_set_oracle_set(
+                &s.admin,
+                &s.xlm_addr,
+                &s.usdc_addr,
+                &oracles,
+                &min_sources,
+                &max_deviation_bps
+            ),
+            Err(Ok(soroban_sdk::Error::from(ConfigError::InvalidOracleSet)))
+        );
+    }
+
+    s.client
+        .set_oracle_set(&s.admin, &s.xlm_addr, &s.usdc_addr, &s.oracles, &2, &500);
+    let set = s.client.get_oracle_set(&s.xlm_addr, &s.usdc_addr).unwrap();
+    assert_eq!(set.min_sources, 2);
+    s.client
+        .remove_oracle_set(&s.admin, &s.xlm_addr, &s.usdc_addr);
+    assert_eq!(s.client.get_oracle_set(&s.xlm_addr, &s.usdc_addr), None);
+}
+
+#[test]
+fn test_cross_token_invoice_priced_at_oracle_set_median() {
+    let s = setup_oracle_set();
+    s.client
+        .set_oracle_set(&s.admin, &s.xlm_addr, &s.usdc_addr, &s.oracles, &2, &500);
+    // Invoice rates are scaled by 10^6: 0.1 USDC per XLM
+    s.client
+        .set_invoice_conversion_rate(&s.merchant, &s.xlm_addr, &100_000);
+    let invoice_id = s.client.create_multi_token_invoice(
+        &s.merchant,
+        &s.customer,
+        &1_000,
+        &s.usdc_addr,
+        &vec![&s.env, s.usdc_addr.clone(), s.xlm_addr.clone()],
+        &s.usdc_addr,
+        &Vec::new(&s.env),
+        &0u64,
+        &soroban_sdk::Map::new(&s.env),
+        &None,
+    );
+
+    set_price(&s, 0, 10_000_000, 1_000);
+    set_price(&s, 1, 1_000_000, 1_000);
+    set_price(&s, 2, 1_020_000, 1_000);
+    assert_eq!(
+        s.client.try_pay_invoice_cross_token(
+            &invoice_id,
+            &s.customer,
+            &s.xlm_addr,
+            &1_000,
+            &100u32
+        ),
+        Err(Ok(soroban_sdk::Error::from(
+            PaymentError::OracleDeviationExceeded
+        )))
+    );
+
+    set_price(&s, 0, 980_000, 1_000);
+    let payment =
+        s.client
+            .pay_invoice_cross_token(&invoice_id, &s.customer, &s.xlm_addr, &1_000, &100u32);
+    assert_eq!(payment.amount_in_base, 100);
+    assert_eq!(count_events(&s.env, "oracle_price_aggregated"), 1);
+}
@@ -0,0 +1,260 @@
+#![cfg(test)]
+use super::*;
+use soroban_sdk::token::Client as TokenClient;
+use soroban_sdk::token::StellarAssetClient as TokenAdminClient;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+
+const INTERVAL: u32 = 1_000;
+
+struct PlanSetup<'a> {
+    env: Env,
+    client: AhjoorPaymentsContractClient<'a>,
+    customer: Address,
+    merchant: Address,
+    token: Address,
+    token_client: TokenClient<'a>,
+    token_admin: TokenAdminClient<'a>,
+}
+
+fn setup_plan<'a>() -> PlanSetup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AhjoorPaymentsContract, ());
+    let client = AhjoorPaymentsContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let customer = Address::generate(&env);
+    let merchant = Address::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let token_client = TokenClient::new(&env, &token);
+    let token_admin = TokenAdminClient::new(&env, &token);
+    token_admin.mint(&customer, &10_000);
+
+    client.initialize(&admin, &admin, &0u32);
+    client.set_plan_terms(&merchant, &100, &1_000, &500);
+
+    // Customer lets the payments contract pull installments
+    token_client.approve(
+        &customer,
+        &contract_id,
+        &10_000,
+        &(env.ledger().sequence() + 100_000),
+    );
+
+    PlanSetup {
+        env,
+        client,
+        customer,
+        merchant,
+        token,
+        token_client,
+        token_admin,
+    }
+}
+
+fn advance_to(env: &Env, sequence: u64) {
+    env.ledger()
+        .with_mut(|l| l.sequence_number = sequence as u32);
+}
+
+/// 1_000 total, 100 down, 900 financed over three installments.
+fn create_plan(s: &PlanSetup, upfront_payout: bool) -> u32 {
+    s.client.create_payment_plan(
+        &s.customer,
+        &s.merchant,
+        &s.token,
+        &1_000,
+        &100,
+        &3,
+        &INTERVAL,
+        &upfront_payout,
+    )
+}
+
+#[test]
+fn test_plan_collects_down_payment_and_installments_on_schedule() {
+    let s = setup_plan();
+    let plan_id = create_plan(&s, false);
+    assert_eq!(s.token_client.balance(&s.merchant), 100);
+
+    for n in 1..=3u32 {
+        let due = s.client.get_payment_plan(&plan_id).next_due_ledger;
+        advance_to(&s.env, due);
+        assert_eq!(s.client.collect_installment(&plan_id), 300);
+        assert_eq!(s.client.get_payment_plan(&plan_id).installments_paid, n);
+    }
+
+    let plan = s.client.get_payment_plan(&plan_id);
+    assert_eq!(plan.status, PlanStatus::Completed);
+    assert_eq!(plan.amount_paid, 1_000);
+    assert_eq!(s.token_client.balance(&s.merchant), 1_000);
+    assert_eq!(s.token_client.balance(&s.customer), 9_000);
+}
+
+#[test]
+fn test_schedule_lists_remaining_installments_with_remainder_last() {
+    let s = setup_plan();
+    let start = s.env.ledger().sequence() as u64;
+    let plan_id = s.client.create_payment_plan(
+        &s.customer,
+        &s.merchant,
+        &s.token,
+        &1_000,
+        &0,
+        &3,
+        &INTERVAL,
+        &false,
+    );
+
+    let schedule = s.client.get_plan_schedule(&plan_id);
+    assert_eq!(schedule.len(), 3);
+    assert_eq!(schedule.get(0).unwrap().due_ledger, start + 1_000);
+    assert_eq!(schedule.get(0).unwrap().amount, 333);
+    assert_eq!(schedule.get(2).unwrap().due_ledger, start + 3_000);
+    assert_eq!(schedule.get(2).unwrap().amount, 334);
+
+    advance_to(&s.env, start + 1_000);
+    s.client.collect_installment(&plan_id);
+    let schedule = s.client.get_plan_schedule(&plan_id);
+    assert_eq!(schedule.len(), 2);
+    assert_eq!(schedule.get(0).unwrap().installment_number, 2);
+    assert_eq!(schedule.get(0).unwrap().due_ledger, start + 2_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #120)")] // InstallmentNotDue
+fn test_installment_cannot_be_collected_early() {
+    let s = setup_plan();
+    let plan_id = create_plan(&s, false);
+
+    s.client.collect_installment(&plan_id);
+}
+
+#[test]
+fn test_late_fee_applies_only_after_grace_period() {
+    let s = setup_plan();
+    let plan_id = create_plan(&s, false);
+
+    // Within the 100-ledger grace period: no fee
+    let due = s.client.get_payment_plan(&plan_id).next_due_ledger;
+    advance_to(&s.env, due + 100);
+    assert_eq!(s.client.collect_installment(&plan_id), 300);
+
+    // Past grace: 10% late fee on the installment
+    let due = s.client.get_payment_plan(&plan_id).next_due_ledger;
+    advance_to(&s.env, due + 101);
+    assert_eq!(s.client.collect_installment(&plan_id), 330);
+
+    let plan = s.client.get_payment_plan(&plan_id);
+    assert_eq!(plan.late_fees_paid, 30);
+    assert_eq!(plan.amount_paid, 700);
+}
+
+#[test]
+fn test_missed_installment_defaults_plan() {
+    let s = setup_plan();
+    let plan_id = create_plan(&s, false);
+    let due = s.client.get_payment_plan(&plan_id).next_due_ledger;
+
+    advance_to(&s.env, due + 500);
+    assert!(s.client.try_default_payment_plan(&plan_id).is_err());
+
+    advance_to(&s.env, due + 501);
+    s.client.default_payment_plan(&plan_id);
+    assert_eq!(
+        s.client.get_payment_plan(&plan_id).status,
+        PlanStatus::Defaulted
+    );
+    assert!(s.client.try_collect_installment(&plan_id).is_err());
+}
+
+#[test]
+fn test_early_payoff_settles_remaining_balance() {
+    let s = setup_plan();
+    let plan_id = create_plan(&s, false);
+    let due = s.client.get_payment_plan(&plan_id).next_due_ledger;
+    advance_to(&s.env, due);
+    s.client.collect_installment(&plan_id);
+
+    assert_eq!(s.client.pay_off_payment_plan(&s.customer, &plan_id), 600);
+
+    let plan = s.client.get_payment_plan(&plan_id);
+    assert_eq!(plan.status, PlanStatus::Completed);
+    assert_eq!(plan.installments_paid, 3);
+    assert_eq!(s.token_client.balance(&s.merchant), 1_000);
+    assert_eq!(s.client.get_plan_schedule(&plan_id).len(), 0);
+}
+
+#[test]
+fn test_upfront_payout_draws_pool_and_installments_repay_it() {
+    let s = setup_plan();
+    s.token_admin.mint(&s.merchant, &2_000);
+    s.client.fund_plan_pool(&s.merchant, &s.token, &2_000);
+
+    let plan_id = create_plan(&s, true);
+    // Down payment plus the financed 900 paid out up front
+    assert_eq!(s.token_client.balance(&s.merchant), 1_000);
+    assert_eq!(s.client.get_plan_pool(&s.merchant, &s.token), 1_100);
+
+    let due = s.client.get_payment_plan(&plan_id).next_due_ledger;
+    advance_to(&s.env, due);
+    s.client.collect_installment(&plan_id);
+    assert_eq!(s.client.get_plan_pool(&s.merchant, &s.token), 1_400);
+    assert_eq!(s.token_client.balance(&s.merchant), 1_000);
+
+    s.client.pay_off_payment_plan(&s.customer, &plan_id);
+    assert_eq!(s.client.get_plan_pool(&s.merchant, &s.token), 2_000);
+
+    s.client.withdraw_plan_pool(&s.merchant, &s.token, &2_000);
+    assert_eq!(s.token_client.balance(&s.merchant), 3_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #123)")] // InsufficientPlanPool
+fn test_upfront_payout_requires_funded_pool() {
+    let s = setup_plan();
+    s.token_admin.mint(&s.merchant, &500);
+    s.client.fund_plan_pool(&s.merchant, &s.token, &500);
+
+    create_plan(&s, true);
+}
+
+#[test]
+fn test_payoff_charges_a_late_fee_per_missed_installment() {
+    let s = setup_plan();
+    let plan_id = create_plan(&s, false);
+    let due = s.client.get_payment_plan(&plan_id).next_due_ledger;
+
+    // The first two installments are both past grace
+    advance_to(&s.env, due + INTERVAL as u64 + 101);
+    assert_eq!(s.client.pay_off_payment_plan(&s.customer, &plan_id), 960);
+    assert_eq!(s.client.get_payment_plan(&plan_id).late_fees_paid, 60);
+}
+
+#[test]
+fn test_plan_cannot_be_paid_after_expiry() {
+    let s = setup_plan();
+    let plan_id = create_plan(&s, false);
+    let expiry = s.client.get_payment_plan(&plan_id).expiry_ledger;
+
+    advance_to(&s.env, expiry + 1);
+    assert_eq!(
+        s.client.try_collect_installment(&plan_id),
+        Err(Ok(soroban_sdk::Error::from(
+            SubscriptionError::PaymentPlanExpired
+        )))
+    );
+    assert_eq!(
+        s.client.try_pay_off_payment_plan(&s.customer, &plan_id),
+        Err(Ok(soroban_sdk::Error::from(
+            SubscriptionError::PaymentPlanExpired
+        )))
+    );
+    s.client.default_payment_plan(&plan_id);
+}
@@ -0,0 +1,217 @@
+#![cfg(test)]
+use super::*;
+use crate::pre_approved_spending::{AllowanceStatus, AuditAction};
+use soroban_sdk::token::Client as TokenClient;
+use soroban_sdk::token::StellarAssetClient as TokenAdminClient;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, BytesN, Env, Map, String,
+};
+
+fn setup_allowance<'a>() -> (
+    Env,
+    AhjoorPaymentsContractClient<'a>,
+    Address,
+    Address,
+    Address,
+    TokenClient<'a>,
+) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AhjoorPaymentsContract, ());
+    let client = AhjoorPaymentsContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let customer = Address::generate(&env);
+    let merchant = Address::generate(&env);
+    let token_addr = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let token_client = TokenClient::new(&env, &token_addr);
+    TokenAdminClient::new(&env, &token_addr).mint(&customer, &10_000);
+
+    client.initialize(&admin, &admin, &0u32);
+
+    // Customer lets the payments contract pull on their behalf
+    token_client.approve(
+        &customer,
+        &contract_id,
+        &10_000,
+        &(env.ledger().sequence() + 10_000),
+    );
+
+    (env, client, customer, merchant, token_addr, token_client)
+}
+
+fn create_allowance(
+    env: &Env,
+    client: &AhjoorPaymentsContractClient,
+    customer: &Address,
+    merchant: &Address,
+    token: &Address,
+) -> u32 {
+    client.create_spending_allowance(
+        customer,
+        merchant,
+        token,
+        &1_000,
+        &300,
+        &500,
+        &(env.ledger().timestamp() + 30 * 24 * 3600),
+        &BytesN::from_array(env, &[7u8; 32]),
+        &Map::new(env),
+    )
+}
+
+#[test]
+fn test_merchant_pulls_against_allowance() {
+    let (env, client, customer, merchant, token_addr, token_client) = setup_allowance();
+    let id = create_allowance(&env, &client, &customer, &merchant, &token_addr);
+
+    let tx = client.spend_from_allowance(&merchant, &id, &250, &String::from_str(&env, "order-1"));
+    assert_eq!(tx.amount, 250);
+    assert_eq!(token_client.balance(&merchant), 250);
+    assert_eq!(token_client.balance(&customer), 9_750);
+    assert_eq!(client.get_allowance_remaining(&id), 750);
+}
+
+#[test]
+fn test_every_spend_is_recorded_and_paginated() {
+    let (env, client, customer, merchant, token_addr, _tc) = setup_allowance();
+    let id = create_allowance(&env, &client, &customer, &merchant, &token_addr);
+
+    for amount in [100i128, 150, 200] {
+        client.spend_from_allowance(&merchant, &id, &amount, &String::from_str(&env, "pull"));
+    }
+
+    let first = client.get_allowance_transactions(&id, &0, &2);
+    assert_eq!(first.len(), 2);
+    assert_eq!(first.get(0).unwrap().amount, 100);
+    assert_eq!(first.get(1).unwrap().amount, 150);
+
+    let second = client.get_allowance_transactions(&id, &1, &2);
+    assert_eq!(second.len(), 1);
+    assert_eq!(second.get(0).unwrap().amount, 200);
+
+    assert_eq!(client.get_allowance_transactions(&id, &2, &2).len(), 0);
+}
+
+#[test]
+fn test_audit_log_records_every_state_change() {
+    let (env, client, customer, merchant, token_addr, _tc) = setup_allowance();
+    let id = create_allowance(&env, &client, &customer, &merchant, &token_addr);
+
+    client.spend_from_allowance(&merchant, &id, &100, &String::from_str(&env, "pull"));
+    client.pause_allowance(&id);
+    client.resume_allowance(&id);
+    client.update_allowance_limits(&id, &200, &400);
+    client.revoke_allowance(&id);
+
+    let log = client.get_allowance_audit_log(&id, &0, &10);
+    let actions: [AuditAction; 6] = [
+        AuditAction::Created,
+        AuditAction::TransactionApproved,
+        AuditAction::Paused,
+        AuditAction::Resumed,
+        AuditAction::Modified,
+        AuditAction::Revoked,
+    ];
+    assert_eq!(log.len(), actions.len() as u32);
+    for (i, action) in actions.iter().enumerate() {
+        assert_eq!(log.get(i as u32).unwrap().action, *action);
+    }
+    assert_eq!(log.get(1).unwrap().actor, merchant);
+    assert_eq!(log.get(2).unwrap().actor, customer);
+
+    // Histories are kept per allowance
+    let other = create_allowance(&env, &client, &customer, &merchant, &token_addr);
+    assert_eq!(client.get_allowance_audit_log(&other, &0, &10).len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #313)")] // AllowancePaused
+fn test_paused_allowance_rejects_spend() {
+    let (env, client, customer, merchant, token_addr, _tc) = setup_allowance();
+    let id = create_allowance(&env, &client, &customer, &merchant, &token_addr);
+
+    client.pause_allowance(&id);
+    client.spend_from_allowance(&merchant, &id, &100, &String::from_str(&env, "pull"));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #308)")] // UnauthorizedAccess
+fn test_other_merchant_cannot_pull() {
+    let (env, client, customer, merchant, token_addr, _tc) = setup_allowance();
+    let id = create_allowance(&env, &client, &customer, &merchant, &token_addr);
+
+    let intruder = Address::generate(&env);
+    client.spend_from_allowance(&intruder, &id, &100, &String::from_str(&env, "pull"));
+}
+
+#[test]
+fn test_limits_enforced_and_daily_window_resets() {
+    let (env, client, customer, merchant, token_addr, _tc) = setup_allowance();
+    let id = create_allowance(&env, &client, &customer, &merchant, &token_addr);
+    let reference = String::from_str(&env, "pull");
+
+    // Above the 300 per-transaction limit
+    assert!(client
+        .try_spend_from_allowance(&merchant, &id, &301, &reference)
+        .is_err());
+
+    client.spend_from_allowance(&merchant, &id, &300, &reference);
+    client.spend_from_allowance(&merchant, &id, &200, &reference);
+    // Daily limit of 500 reached
+    assert!(client
+        .try_spend_from_allowance(&merchant, &id, &1, &reference)
+        .is_err());
+
+    env.ledger().with_mut(|l| l.timestamp += 24 * 3600 + 1);
+    client.spend_from_allowance(&merchant, &id, &300, &reference);
+    client.spend_from_allowance(&merchant, &id, &200, &reference);
+
+    let allowance = client.get_allowance(&id).unwrap();
+    assert_eq!(allowance.amount_spent, 1_000);
+    assert_eq!(allowance.status, AllowanceStatus::Exhausted);
+    // Rejected attempts leave no transaction behind
+    assert_eq!(client.get_allowance_transactions(&id, &0, &10).len(), 4);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #316)")] // InvalidPageSize
+fn test_history_page_size_is_bounded() {
+    let (env, client, customer, merchant, token_addr, _tc) = setup_allowance();
+    let id = create_allowance(&env, &client, &customer, &merchant, &token_addr);
+
+    client.get_allowance_transactions(&id, &0, &(MAX_CUSTOMER_PAYMENTS_PAGE_SIZE + 1));
+}
+
+#[test]
+fn test_party_index_is_paged_without_a_cap() {
+    let (env, client, customer, merchant, token_addr, _tc) = setup_allowance();
+    let mut first = 0;
+    for n in 0..(pre_approved_spending_impl::INDEX_PAGE_SIZE * 2 + 20) {
+        let id = create_allowance(&env, &client, &customer, &merchant, &token_addr);
+        if n == 0 {
+            first = id;
+        }
+    }
+
+    // A page straddling two index entries reads across both
+    let page = client.get_merchant_allowances(&merchant, &1, &40);
+    assert_eq!(page.len(), 40);
+    assert_eq!(page.get(0).unwrap().allowance_id, first + 40);
+    assert_eq!(page.get(39).unwrap().allowance_id, first + 79);
+    assert_eq!(client.get_customer_allowances(&customer, &2, &40).len(), 40);
+    assert_eq!(client.get_customer_allowances(&customer, &3, &40).len(), 0);
+
+    // Another customer can still grant the same merchant an allowance
+    let other = Address::generate(&env);
+    let id = create_allowance(&env, &client, &other, &merchant, &token_addr);
+    assert_eq!(client.get_merchant_allowances(&merchant, &3, &40).get(0).unwrap().allowance_id, id);
+
+    client.revoke_allowance(&first);
+    let page = client.get_customer_allowances(&customer, &0, &1);
+    assert_eq!(page.get(0).unwrap().status, AllowanceStatus::Revoked);
+}
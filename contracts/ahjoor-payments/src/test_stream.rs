@@ -0,0 +1,224 @@
+#![cfg(test)]
+use super::*;
+use soroban_sdk::token::Client as TokenClient;
+use soroban_sdk::token::StellarAssetClient as TokenAdminClient;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+
+struct StreamSetup<'a> {
+    env: Env,
+    client: AhjoorPaymentsContractClient<'a>,
+    fee_recipient: Address,
+    sender: Address,
+    recipient: Address,
+    token: Address,
+    token_client: TokenClient<'a>,
+}
+
+fn setup_stream<'a>(fee_bps: u32) -> StreamSetup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let contract_id = env.register(AhjoorPaymentsContract, ());
+    let client = AhjoorPaymentsContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let fee_recipient = Address::generate(&env);
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let token_client = TokenClient::new(&env, &token);
+    TokenAdminClient::new(&env, &token).mint(&sender, &10_000);
+
+    client.initialize(&admin, &fee_recipient, &fee_bps);
+
+    StreamSetup {
+        env,
+        client,
+        fee_recipient,
+        sender,
+        recipient,
+        token,
+        token_client,
+    }
+}
+
+#[test]
+fn test_withdraw_follows_rate_and_cliff() {
+    let s = setup_stream(0);
+    // 10 per second from 1_000 to 1_100, cliff at 1_030
+    let stream_id = s.client.create_stream(
+        &s.sender,
+        &s.recipient,
+        &s.token,
+        &1_000,
+        &10,
+        &0,
+        &1_030,
+        &1_100,
+    );
+    assert_eq!(s.token_client.balance(&s.sender), 9_000);
+
+    s.env.ledger().set_timestamp(1_020);
+    assert_eq!(s.client.get_stream_withdrawable(&stream_id), 0);
+    assert_eq!(
+        s.client.try_withdraw_from_stream(&s.recipient, &stream_id),
+        Err(Ok(soroban_sdk::Error::from(
+            SubscriptionError::NothingToWithdraw
+        )))
+    );
+
+    // Accrual counts from the start once the cliff has passed
+    s.env.ledger().set_timestamp(1_050);
+    assert_eq!(s.client.withdraw_from_stream(&s.recipient, &stream_id), 500);
+    assert_eq!(s.token_client.balance(&s.recipient), 500);
+
+    s.env.ledger().set_timestamp(1_200);
+    assert_eq!(s.client.withdraw_from_stream(&s.recipient, &stream_id), 500);
+    let stream = s.client.get_stream(&stream_id);
+    assert_eq!(stream.withdrawn, 1_000);
+    assert_eq!(stream.status, StreamStatus::Completed);
+}
+
+#[test]
+fn test_cancel_settles_pro_rata() {
+    let s = setup_stream(0);
+    let stream_id = s.client.create_stream(
+        &s.sender,
+        &s.recipient,
+        &s.token,
+        &1_000,
+        &10,
+        &0,
+        &0,
+        &1_100,
+    );
+
+    s.env.ledger().set_timestamp(1_010);
+    s.client.withdraw_from_stream(&s.recipient, &stream_id);
+    s.env.ledger().set_timestamp(1_040);
+    s.client.cancel_stream(&s.recipient, &stream_id);
+
+    assert_eq!(s.token_client.balance(&s.recipient), 400);
+    assert_eq!(s.token_client.balance(&s.sender), 9_600);
+    let stream = s.client.get_stream(&stream_id);
+    assert_eq!(stream.status, StreamStatus::Cancelled);
+    assert_eq!(stream.deposited, 400);
+
+    s.env.ledger().set_timestamp(1_100);
+    assert_eq!(s.client.get_stream_withdrawable(&stream_id), 0);
+    assert_eq!(
+        s.client.try_cancel_stream(&s.sender, &stream_id),
+        Err(Ok(soroban_sdk::Error::from(
+            SubscriptionError::StreamNotActive
+        )))
+    );
+}
+
+#[test]
+fn test_top_up_and_recipient_transfer() {
+    let s = setup_stream(0);
+    // Open-ended: 1 per second until the deposit is streamed
+    let stream_id = s
+        .client
+        .create_stream(&s.sender, &s.recipient, &s.token, &100, &1, &0, &0, &0);
+
+    s.env.ledger().set_timestamp(1_050);
+    let new_recipient = Address::generate(&s.env);
+    s.client
+        .transfer_stream(&s.recipient, &stream_id, &new_recipient);
+    // What accrued before the transfer went to the outgoing recipient
+    assert_eq!(s.token_client.balance(&s.recipient), 50);
+    assert_eq!(
+        s.client.try_withdraw_from_stream(&s.recipient, &stream_id),
+        Err(Ok(soroban_sdk::Error::from(
+            SubscriptionError::NotStreamParty
+        )))
+    );
+
+    s.client.top_up_stream(&s.sender, &stream_id, &100);
+    s.env.ledger().set_timestamp(1_150);
+    assert_eq!(
+        s.client.withdraw_from_stream(&new_recipient, &stream_id),
+        100
+    );
+
+    // The stream stops once the deposit is used up
+    s.env.ledger().set_timestamp(1_500);
+    assert_eq!(s.client.get_stream_withdrawable(&stream_id), 50);
+}
+
+#[test]
+fn test_stream_fees_and_validation() {
+    let s = setup_stream(100);
+    assert_eq!(
+        s.client.try_create_stream(
+            &s.sender,
+            &s.recipient,
+            &s.token,
+            &1_000,
+            &10,
+            &0,
+            &0,
+            &1_050,
+        ),
+        Err(Ok(soroban_sdk::Error::from(
+            SubscriptionError::InvalidStream
+        )))
+    );
+
+    let stream_id = s.client.create_stream(
+        &s.sender,
+        &s.recipient,
+        &s.token,
+        &1_000,
+        &10,
+        &0,
+        &0,
+        &1_100,
+    );
+    s.env.ledger().set_timestamp(1_100);
+    // 1% protocol fee on the streamed amount
+    assert_eq!(s.client.withdraw_from_stream(&s.recipient, &stream_id), 990);
+    assert_eq!(s.token_client.balance(&s.fee_recipient), 10);
+    assert_eq!(
+        s.client.try_top_up_stream(&s.sender, &stream_id, &10),
+        Err(Ok(soroban_sdk::Error::from(
+            SubscriptionError::StreamNotActive
+        )))
+    );
+}
+
+#[test]
+fn test_top_up_after_running_dry_resumes_accrual() {
+    let s = setup_stream(0);
+    let stream_id = s
+        .client
+        .create_stream(&s.sender, &s.recipient, &s.token, &100, &1, &0, &0, &0);
+
+    // Dry since 1_100; the idle time is not streamed again after a top-up
+    s.env.ledger().set_timestamp(1_200);
+    s.client.top_up_stream(&s.sender, &stream_id, &100);
+    assert_eq!(s.client.get_stream_withdrawable(&stream_id), 100);
+    s.env.ledger().set_timestamp(1_250);
+    assert_eq!(s.client.get_stream_withdrawable(&stream_id), 150);
+
+    // Once everything is streamed and withdrawn the stream is done
+    s.env.ledger().set_timestamp(1_400);
+    assert_eq!(s.client.withdraw_from_stream(&s.recipient, &stream_id), 200);
+    assert_eq!(
+        s.client.get_stream(&stream_id).status,
+        StreamStatus::Completed
+    );
+    assert_eq!(
+        s.client.try_top_up_stream(&s.sender, &stream_id, &10),
+        Err(Ok(soroban_sdk::Error::from(
+            SubscriptionError::StreamNotActive
+        )))
+    );
+}
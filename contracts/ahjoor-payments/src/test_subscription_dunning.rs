@@ -0,0 +1,240 @@
+#![cfg(test)]
+use super::*;
+use soroban_sdk::token::Client as TokenClient;
+use soroban_sdk::token::StellarAssetClient as TokenAdminClient;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, Env,
+};
+
+const DAY: u64 = 24 * 60 * 60;
+
+struct DunningSetup<'a> {
+    env: Env,
+    client: AhjoorPaymentsContractClient<'a>,
+    merchant: Address,
+    subscriber: Address,
+    token_admin: TokenAdminClient<'a>,
+    token_client: TokenClient<'a>,
+    sub_id: u32,
+}
+
+fn setup_dunning<'a>() -> DunningSetup<'a> {
+    let env = Env::default();
+    // Charges move the subscriber's tokens from within the contract call
+    env.mock_all_auths_allowing_non_root_auth();
+    env.ledger().set_timestamp(1_000);
+
+    let contract_id = env.register(AhjoorPaymentsContract, ());
+    let client = AhjoorPaymentsContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let merchant = Address::generate(&env);
+    let subscriber = Address::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let token_client = TokenClient::new(&env, &token);
+    let token_admin = TokenAdminClient::new(&env, &token);
+    // Enough for the first charge only
+    token_admin.mint(&subscriber, &100);
+
+    client.initialize(&admin, &admin, &0u32);
+    client.set_min_collateral(&0i128);
+    client.approve_merchant(&merchant);
+    let sub_id = client.create_subscription(&subscriber, &merchant, &100, &token, &(30 * DAY), &0);
+    client.charge_subscription(&sub_id);
+
+    DunningSetup {
+        env,
+        client,
+        merchant,
+        subscriber,
+        token_admin,
+        token_client,
+        sub_id,
+    }
+}
+
+#[test]
+fn test_failed_charge_goes_past_due_and_recovers() {
+    let s = setup_dunning();
+    s.client.set_dunning_config(
+        &s.merchant,
+        &vec![&s.env, DAY, 3 * DAY, 7 * DAY],
+        &(5 * DAY),
+    );
+
+    let due_at = 1_000 + 30 * DAY;
+    s.env.ledger().set_timestamp(due_at);
+    s.client.charge_subscription(&s.sub_id);
+    let sub = s.client.get_subscription(&s.sub_id);
+    assert_eq!(sub.past_due_since, due_at);
+    assert_eq!(sub.next_retry_at, due_at + DAY);
+    assert_eq!(sub.charges_count, 1);
+    assert_eq!(
+        s.client.get_subscription_status(&s.sub_id),
+        SubscriptionStatus::PastDue
+    );
+
+    // Retries follow the ladder, not the billing interval
+    assert_eq!(
+        s.client.try_charge_subscription(&s.sub_id),
+        Err(Ok(soroban_sdk::Error::from(Error::RetryNotDue)))
+    );
+    s.env.ledger().set_timestamp(due_at + DAY);
+    s.client.charge_subscription(&s.sub_id);
+    assert_eq!(
+        s.client.get_subscription(&s.sub_id).next_retry_at,
+        due_at + 3 * DAY
+    );
+
+    s.token_admin.mint(&s.subscriber, &100);
+    s.env.ledger().set_timestamp(due_at + 3 * DAY);
+    s.client.charge_subscription(&s.sub_id);
+    assert_eq!(s.token_client.balance(&s.merchant), 200);
+    let sub = s.client.get_subscription(&s.sub_id);
+    assert_eq!(sub.past_due_since, 0);
+    assert_eq!(sub.charges_count, 2);
+    assert_eq!(
+        s.client.get_subscription_status(&s.sub_id),
+        SubscriptionStatus::Active
+    );
+}
+
+#[test]
+fn test_grace_period_then_auto_cancel_after_last_retry() {
+    let s = setup_dunning();
+    s.client
+        .set_dunning_config(&s.merchant, &vec![&s.env, DAY, 3 * DAY], &(2 * DAY));
+
+    let due_at = 1_000 + 30 * DAY;
+    s.env.ledger().set_timestamp(due_at);
+    s.client.charge_subscription(&s.sub_id);
+    s.env.ledger().set_timestamp(due_at + DAY);
+    s.client.charge_subscription(&s.sub_id);
+
+    // Service lapses once the grace period is over
+    s.env.ledger().set_timestamp(due_at + 2 * DAY);
+    assert_eq!(
+        s.client.get_subscription_status(&s.sub_id),
+        SubscriptionStatus::Unpaid
+    );
+
+    s.env.ledger().set_timestamp(due_at + 3 * DAY);
+    s.client.charge_subscription(&s.sub_id);
+    let sub = s.client.get_subscription(&s.sub_id);
+    assert!(!sub.active);
+    assert_eq!(sub.dunning_attempts, 2);
+    assert_eq!(
+        s.client.get_subscription_status(&s.sub_id),
+        SubscriptionStatus::Cancelled
+    );
+    assert_eq!(
+        s.client.try_charge_subscription(&s.sub_id),
+        Err(Ok(soroban_sdk::Error::from(
+            SubscriptionError::SubscriptionCancelled
+        )))
+    );
+}
+
+#[test]
+fn test_without_dunning_failed_charge_reverts() {
+    let s = setup_dunning();
+    s.env.ledger().set_timestamp(1_000 + 30 * DAY);
+    assert!(s.client.try_charge_subscription(&s.sub_id).is_err());
+    assert_eq!(s.client.get_subscription(&s.sub_id).past_due_since, 0);
+
+    // Clearing the ladder turns dunning back off
+    s.client
+        .set_dunning_config(&s.merchant, &vec![&s.env, DAY], &0);
+    s.client.set_dunning_config(&s.merchant, &vec![&s.env], &0);
+    assert_eq!(s.client.get_dunning_config(&s.merchant), None);
+    assert!(s.client.try_charge_subscription(&s.sub_id).is_err());
+}
+
+#[test]
+fn test_dunning_ladder_must_ascend() {
+    let s = setup_dunning();
+    assert_eq!(
+        s.client
+            .try_set_dunning_config(&s.merchant, &vec![&s.env, 3 * DAY, DAY], &0),
+        Err(Ok(soroban_sdk::Error::from(
+            SubscriptionError::InvalidDunningConfig
+        )))
+    );
+    assert_eq!(
+        s.client
+            .try_set_dunning_config(&s.merchant, &vec![&s.env, 0, DAY], &0),
+        Err(Ok(soroban_sdk::Error::from(
+            SubscriptionError::InvalidDunningConfig
+        )))
+    );
+    s.client
+        .set_dunning_config(&s.merchant, &vec![&s.env, DAY, 3 * DAY, 7 * DAY], &DAY);
+    let cfg = s.client.get_dunning_config(&s.merchant).unwrap();
+    assert_eq!(cfg.retry_offsets.len(), 3);
+    assert_eq!(cfg.grace_period_seconds, DAY);
+}
+
+#[test]
+fn test_past_due_run_keeps_its_ladder() {
+    let s = setup_dunning();
+    s.client.set_dunning_config(
+        &s.merchant,
+        &vec![&s.env, DAY, 3 * DAY, 7 * DAY],
+        &(5 * DAY),
+    );
+    let due_at = 1_000 + 30 * DAY;
+    s.env.ledger().set_timestamp(due_at);
+    s.client.charge_subscription(&s.sub_id);
+
+    // Shortening the ladder does not cut the run short
+    s.client
+        .set_dunning_config(&s.merchant, &vec![&s.env, DAY], &0);
+    s.env.ledger().set_timestamp(due_at + DAY);
+    s.client.charge_subscription(&s.sub_id);
+    let sub = s.client.get_subscription(&s.sub_id);
+    assert!(sub.active);
+    assert_eq!(sub.next_retry_at, due_at + 3 * DAY);
+
+    // Turning dunning off still lets the run retry and end
+    s.client.set_dunning_config(&s.merchant, &vec![&s.env], &0);
+    s.env.ledger().set_timestamp(due_at + 3 * DAY);
+    s.client.charge_subscription(&s.sub_id);
+    s.env.ledger().set_timestamp(due_at + 7 * DAY);
+    s.client.charge_subscription(&s.sub_id);
+    assert_eq!(
+        s.client.get_subscription_status(&s.sub_id),
+        SubscriptionStatus::Cancelled
+    );
+}
+
+#[test]
+fn test_plan_change_rejected_while_past_due() {
+    let s = setup_dunning();
+    let token = s.client.get_subscription(&s.sub_id).token;
+    let basic = s
+        .client
+        .create_subscription_plan(&s.merchant, &token, &100, &(30 * DAY), &0, &0);
+    let pro = s
+        .client
+        .create_subscription_plan(&s.merchant, &token, &300, &(30 * DAY), &0, &0);
+    s.client
+        .set_dunning_config(&s.merchant, &vec![&s.env, DAY], &DAY);
+
+    s.token_admin.mint(&s.subscriber, &100);
+    let sub_id = s.client.subscribe_to_plan(&s.subscriber, &basic);
+    s.client.charge_subscription(&sub_id);
+    s.env.ledger().set_timestamp(1_000 + 30 * DAY);
+    s.client.charge_subscription(&sub_id);
+    assert!(s.client.get_subscription(&sub_id).past_due_since > 0);
+
+    assert_eq!(
+        s.client
+            .try_change_subscription_plan(&s.subscriber, &sub_id, &pro),
+        Err(Ok(soroban_sdk::Error::from(
+            SubscriptionError::SubscriptionPastDue
+        )))
+    );
+}
@@ -0,0 +1,211 @@
+#![cfg(test)]
+use super::*;
+use soroban_sdk::token::Client as TokenClient;
+use soroban_sdk::token::StellarAssetClient as TokenAdminClient;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, Env,
+};
+
+const WEEK: u64 = 7 * 24 * 60 * 60;
+
+struct PlanSetup<'a> {
+    env: Env,
+    client: AhjoorPaymentsContractClient<'a>,
+    merchant: Address,
+    subscriber: Address,
+    token: Address,
+    token_client: TokenClient<'a>,
+}
+
+fn setup_plans<'a>() -> PlanSetup<'a> {
+    let env = Env::default();
+    // Charges move the subscriber's tokens from within the contract call
+    env.mock_all_auths_allowing_non_root_auth();
+    env.ledger().set_timestamp(1_000);
+
+    let contract_id = env.register(AhjoorPaymentsContract, ());
+    let client = AhjoorPaymentsContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let merchant = Address::generate(&env);
+    let subscriber = Address::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let token_client = TokenClient::new(&env, &token);
+    TokenAdminClient::new(&env, &token).mint(&subscriber, &10_000);
+
+    client.initialize(&admin, &admin, &0u32);
+    client.set_min_collateral(&0i128);
+    client.approve_merchant(&merchant);
+
+    PlanSetup {
+        env,
+        client,
+        merchant,
+        subscriber,
+        token,
+        token_client,
+    }
+}
+
+#[test]
+fn test_plan_subscriptions_are_listed_and_charged_at_plan_price() {
+    let s = setup_plans();
+    let plan_id = s
+        .client
+        .create_subscription_plan(&s.merchant, &s.token, &100, &1_000, &0, &0);
+
+    let first = s.client.subscribe_to_plan(&s.subscriber, &plan_id);
+    let other = Address::generate(&s.env);
+    let second = s.client.subscribe_to_plan(&other, &plan_id);
+    assert_eq!(
+        s.client.get_plan_subscribers(&plan_id),
+        vec![&s.env, first, second]
+    );
+
+    s.client.charge_subscription(&first);
+    assert_eq!(s.token_client.balance(&s.merchant), 100);
+    assert_eq!(s.client.get_subscription(&first).plan_id, plan_id);
+
+    s.client.cancel_subscription(&other, &second);
+    assert_eq!(s.client.get_plan_subscribers(&plan_id), vec![&s.env, first]);
+
+    // Closed plans take no new subscribers
+    s.client
+        .set_subscription_plan_active(&s.merchant, &plan_id, &false);
+    assert_eq!(
+        s.client.try_subscribe_to_plan(&other, &plan_id),
+        Err(Ok(soroban_sdk::Error::from(
+            SubscriptionError::SubscriptionPlanInactive
+        )))
+    );
+}
+
+#[test]
+fn test_price_change_applies_after_notice() {
+    let s = setup_plans();
+    let plan_id = s
+        .client
+        .create_subscription_plan(&s.merchant, &s.token, &100, &WEEK, &0, &0);
+    let sub_id = s.client.subscribe_to_plan(&s.subscriber, &plan_id);
+
+    assert_eq!(
+        s.client
+            .try_schedule_plan_price_change(&s.merchant, &plan_id, &150, &(1_000 + WEEK - 1)),
+        Err(Ok(soroban_sdk::Error::from(
+            SubscriptionError::InvalidSubscriptionPlan
+        )))
+    );
+    s.client
+        .schedule_plan_price_change(&s.merchant, &plan_id, &150, &(1_000 + WEEK + 10));
+
+    // Still the old price within the notice period
+    s.client.charge_subscription(&sub_id);
+    assert_eq!(s.token_client.balance(&s.merchant), 100);
+
+    s.env.ledger().set_timestamp(1_000 + 2 * WEEK);
+    s.client.charge_subscription(&sub_id);
+    assert_eq!(s.token_client.balance(&s.merchant), 250);
+    let plan = s.client.get_subscription_plan(&plan_id);
+    assert_eq!(plan.price, 150);
+    assert_eq!(plan.pending_price_at, 0);
+}
+
+#[test]
+fn test_upgrade_charges_prorated_difference() {
+    let s = setup_plans();
+    let basic = s
+        .client
+        .create_subscription_plan(&s.merchant, &s.token, &100, &1_000, &0, &0);
+    let pro = s
+        .client
+        .create_subscription_plan(&s.merchant, &s.token, &300, &1_000, &0, &0);
+    let sub_id = s.client.subscribe_to_plan(&s.subscriber, &basic);
+    s.client.charge_subscription(&sub_id);
+
+    // Half the basic period unused: 50 credited against the 300 pro price
+    s.env.ledger().set_timestamp(1_500);
+    s.client
+        .change_subscription_plan(&s.subscriber, &sub_id, &pro);
+    assert_eq!(s.token_client.balance(&s.merchant), 350);
+
+    let sub = s.client.get_subscription(&sub_id);
+    assert_eq!(sub.plan_id, pro);
+    assert_eq!(sub.amount, 300);
+    assert_eq!(sub.last_charged_at, 1_500);
+    assert_eq!(s.client.get_plan_subscribers(&basic).len(), 0);
+    assert_eq!(s.client.get_plan_subscribers(&pro), vec![&s.env, sub_id]);
+}
+
+#[test]
+fn test_downgrade_credit_covers_next_charges() {
+    let s = setup_plans();
+    let basic = s
+        .client
+        .create_subscription_plan(&s.merchant, &s.token, &100, &1_000, &0, &0);
+    let pro = s
+        .client
+        .create_subscription_plan(&s.merchant, &s.token, &300, &1_000, &0, &0);
+    let sub_id = s.client.subscribe_to_plan(&s.subscriber, &pro);
+    s.client.charge_subscription(&sub_id);
+
+    // 240 unused on pro, 100 for basic: 140 credit
+    s.env.ledger().set_timestamp(1_200);
+    s.client
+        .change_subscription_plan(&s.subscriber, &sub_id, &basic);
+    assert_eq!(s.client.get_subscription(&sub_id).credit, 140);
+    assert_eq!(s.token_client.balance(&s.merchant), 300);
+    // Nothing was collected, so no billable period is used up
+    assert_eq!(s.client.get_subscription(&sub_id).charges_count, 1);
+
+    s.env.ledger().set_timestamp(2_200);
+    s.client.charge_subscription(&sub_id);
+    assert_eq!(s.token_client.balance(&s.merchant), 300);
+    assert_eq!(s.client.get_subscription(&sub_id).credit, 40);
+
+    s.env.ledger().set_timestamp(3_200);
+    s.client.charge_subscription(&sub_id);
+    assert_eq!(s.token_client.balance(&s.merchant), 360);
+
+    // Plans of another merchant are not interchangeable
+    let other_merchant = Address::generate(&s.env);
+    s.client.approve_merchant(&other_merchant);
+    let foreign =
+        s.client
+            .create_subscription_plan(&other_merchant, &s.token, &100, &1_000, &0, &0);
+    assert_eq!(
+        s.client
+            .try_change_subscription_plan(&s.subscriber, &sub_id, &foreign),
+        Err(Ok(soroban_sdk::Error::from(
+            SubscriptionError::InvalidPlanChange
+        )))
+    );
+}
+
+#[test]
+fn test_upgrade_credit_uses_price_paid_for_the_period() {
+    let s = setup_plans();
+    let interval = 1_000_000;
+    let basic = s
+        .client
+        .create_subscription_plan(&s.merchant, &s.token, &100, &interval, &0, &0);
+    let pro = s
+        .client
+        .create_subscription_plan(&s.merchant, &s.token, &300, &interval, &0, &0);
+    let sub_id = s.client.subscribe_to_plan(&s.subscriber, &basic);
+    s.client.charge_subscription(&sub_id);
+
+    // Basic goes up mid-period, after the subscriber paid 100
+    s.client
+        .schedule_plan_price_change(&s.merchant, &basic, &200, &(1_000 + WEEK));
+    s.env.ledger().set_timestamp(1_000 + 750_000);
+    assert_eq!(s.client.get_subscription_plan(&basic).price, 200);
+
+    // A quarter of the 100 paid is unused: 25 credited against 300
+    s.client
+        .change_subscription_plan(&s.subscriber, &sub_id, &pro);
+    assert_eq!(s.token_client.balance(&s.merchant), 375);
+    assert_eq!(s.client.get_subscription(&sub_id).charges_count, 2);
+}
@@ -64,7 +64,7 @@ fn test_set_max_tip_bps() {
 }
 
 #[test]
-#[should_panic(expected = "max_tip_bps cannot exceed 10 000")]
+#[should_panic(expected = "Error(Contract, #413)")] // InvalidTipBps
 fn test_set_max_tip_bps_too_high() {
     let s = tip_setup();
     s.client.set_max_tip_bps(&s.admin, &10_001);
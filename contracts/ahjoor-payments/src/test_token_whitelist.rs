@@ -81,7 +81,7 @@ fn test_set_token_whitelist_contract() {
 }
 
 #[test]
-#[should_panic(expected = "Only admin can set token whitelist contract")]
+#[should_panic(expected = "Error(Contract, #43)")] // Unauthorized
 fn test_set_token_whitelist_contract_unauthorized() {
     let (
         env,
@@ -0,0 +1,206 @@
+use soroban_sdk::contracterror;
+
+// Refund codes live in 2000–2999 so they never collide with payments
+// (1–999) or escrow (1000–1999) codes surfaced by the same front-end.
+
+/// General refund lifecycle, party and configuration failures. Codes 2000–2099.
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// The contract has already been initialized.
+    AlreadyInitialized = 2000,
+    /// The contract has not been initialized.
+    NotInitialized = 2001,
+    /// Caller is not the contract admin.
+    Unauthorized = 2002,
+    /// The contract is paused.
+    ContractPaused = 2003,
+    /// The contract is already paused.
+    ContractAlreadyPaused = 2004,
+    /// The contract is not paused.
+    ContractNotPaused = 2005,
+    /// No refund exists with the given id.
+    RefundNotFound = 2006,
+    /// An amount that must be positive was zero or negative.
+    InvalidAmount = 2007,
+    /// The reason code must be between 0 and 4.
+    InvalidReasonCode = 2008,
+    /// The token is not on the whitelist.
+    TokenNotAllowed = 2009,
+    /// Caller is neither the admin nor a merchant delegate.
+    NotAuthorizedReviewer = 2010,
+    /// Caller is not the customer who requested the refund.
+    NotCustomer = 2011,
+    /// Caller is not the merchant on the refund or payment.
+    NotMerchant = 2012,
+    /// The refund is not in the `Requested` state.
+    RefundNotRequested = 2013,
+    /// The refund is not in a state that can be approved.
+    RefundNotApprovable = 2014,
+    /// The refund has not been approved.
+    RefundNotApproved = 2015,
+    /// The refund has already been acted on.
+    RefundAlreadyActedOn = 2016,
+    /// The amount exceeds what remains refundable on the payment.
+    ExceedsRefundableAmount = 2017,
+    /// The refund window for the payment has expired.
+    RefundWindowExpired = 2018,
+    /// The customer's refund cooldown is still active.
+    RefundCooldownActive = 2019,
+    /// No payment contract is configured.
+    PaymentContractNotConfigured = 2020,
+    /// The payment contract has no such payment.
+    PaymentNotFound = 2021,
+    /// The payment is not completed.
+    PaymentNotCompleted = 2022,
+    /// The merchant's token balance cannot cover the refund.
+    MerchantBalanceInsufficient = 2023,
+    /// The refund batch is empty.
+    EmptyBatch = 2024,
+    /// The batch exceeds the maximum size.
+    BatchTooLarge = 2025,
+    /// The maximum batch size must be positive.
+    InvalidBatchSize = 2026,
+    /// The refund fee exceeds 200 basis points.
+    FeeTooHigh = 2027,
+    /// A basis-point setting exceeds 10000.
+    InvalidBps = 2028,
+    /// A window, ledger count or threshold must be positive.
+    InvalidDuration = 2029,
+    /// Refund tiers must be sorted by `max_seconds_since_payment`.
+    InvalidRefundTiers = 2030,
+    /// The address is already a delegate.
+    DelegateAlreadyExists = 2031,
+    /// The address is not a delegate.
+    DelegateNotFound = 2032,
+    /// A merchant may have at most 5 delegates.
+    TooManyDelegates = 2033,
+    /// The merchant is already on the auto-approve list.
+    MerchantAlreadyWhitelisted = 2034,
+    /// The merchant is not on the auto-approve list.
+    MerchantNotWhitelisted = 2035,
+    /// Migration has already run for this version.
+    MigrationAlreadyCompleted = 2036,
+    /// A counter or version number overflowed.
+    ArithmeticOverflow = 2037,
+    /// No keeper pool is configured.
+    KeeperNotConfigured = 2038,
+    /// Keeper bounty and caps must be positive; the token can only change while the pool is empty.
+    InvalidKeeperConfig = 2039,
+    /// The keeper pool balance is insufficient.
+    InsufficientKeeperPool = 2040,
+}
+
+/// Counter-offer, appeal, review window, evidence, escalation and abuse failures. Codes 2100–2199.
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReviewError {
+    /// A counter-offer has already been submitted.
+    CounterOfferExists = 2100,
+    /// The counter-offer exceeds the requested amount.
+    CounterOfferExceedsRefund = 2101,
+    /// No counter-offer exists for this refund.
+    CounterOfferNotFound = 2102,
+    /// The counter-offer has not expired.
+    CounterOfferNotExpired = 2103,
+    /// The refund is not in the `CounterOffered` state.
+    NotCounterOffered = 2104,
+    /// Only rejected refunds can be appealed.
+    AppealNotAllowed = 2105,
+    /// The appeal window has expired.
+    AppealWindowExpired = 2106,
+    /// No appeal window is configured.
+    AppealWindowNotConfigured = 2107,
+    /// The refund has no rejection timestamp.
+    RejectionTimestampMissing = 2108,
+    /// The refund is not under appeal.
+    NotUnderAppeal = 2109,
+    /// No auto-reject window is configured.
+    AutoRejectWindowNotConfigured = 2110,
+    /// The auto-reject window has not elapsed.
+    AutoRejectWindowOpen = 2111,
+    /// No customer cancel window is configured.
+    CancelWindowNotConfigured = 2112,
+    /// The customer cancel window has not elapsed.
+    CancelWindowOpen = 2113,
+    /// No dispute window is configured.
+    DisputeWindowNotConfigured = 2114,
+    /// The dispute window has not elapsed.
+    DisputeWindowOpen = 2115,
+    /// The merchant response deadline has not passed.
+    MerchantDeadlineNotPassed = 2116,
+    /// The merchant is exempt from auto-approval.
+    MerchantAutoApprovalExempt = 2117,
+    /// The review extension has already been used.
+    ExtensionAlreadyUsed = 2118,
+    /// Evidence can only be submitted while the refund is under review.
+    EvidenceNotAllowed = 2119,
+    /// The evidence deadline has passed.
+    EvidenceDeadlinePassed = 2120,
+    /// The merchant evidence window has not elapsed.
+    EvidenceWindowOpen = 2121,
+    /// The refund cannot be escalated.
+    EscalationNotAllowed = 2122,
+    /// The primary review deadline has not elapsed.
+    PrimaryDeadlineNotPassed = 2123,
+    /// No senior arbiter is configured.
+    SeniorArbiterNotConfigured = 2124,
+    /// Caller is not the senior arbiter.
+    UnauthorizedSeniorArbiter = 2125,
+    /// The refund has not been escalated.
+    RefundNotEscalated = 2126,
+    /// The senior review deadline has not passed.
+    SeniorDeadlineNotPassed = 2127,
+    /// Auto-approval on a missed senior deadline is disabled.
+    AutoApproveOnSeniorMissDisabled = 2128,
+    /// Only rejected refunds can be flagged for abuse.
+    OnlyRejectedRefundsCanBeFlagged = 2129,
+    /// The customer is blocked for refund abuse.
+    CustomerBlockedForAbuse = 2130,
+    /// The buyer's fraud score is at or above the block threshold.
+    BuyerBlockedForFraud = 2131,
+}
+
+/// Reserve, store credit, voucher, cross-contract origin and refund policy failures. Codes 2200–2299.
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SettlementError {
+    /// No reserve token is configured.
+    ReserveTokenNotConfigured = 2200,
+    /// The merchant reserve balance is insufficient.
+    InsufficientReserve = 2201,
+    /// The withdrawal would drop the reserve below its required minimum.
+    WithdrawalBreachesMinimum = 2202,
+    /// The merchant reserve is below the minimum; deposit before creating payments.
+    ReserveBelowMinimum = 2203,
+    /// No store credit exists with the given id.
+    StoreCreditNotFound = 2204,
+    /// The store credit has no balance left.
+    StoreCreditExhausted = 2205,
+    /// The store credit has expired.
+    StoreCreditExpired = 2206,
+    /// The store credit expiry has already been extended.
+    StoreCreditExtensionAlreadyUsed = 2207,
+    /// The expiry ledger must be in the future and later than the current one.
+    InvalidExpiry = 2208,
+    /// The voucher bonus exceeds the configured cap.
+    VoucherBonusExceedsCap = 2209,
+    /// No escrow contract is configured.
+    EscrowContractNotConfigured = 2210,
+    /// Caller is not the configured escrow contract.
+    NotEscrowContract = 2211,
+    /// The origin contract is already whitelisted.
+    ContractAlreadyWhitelisted = 2212,
+    /// The origin contract is not whitelisted.
+    ContractNotWhitelisted = 2213,
+    /// The calling contract is not a whitelisted refund origin.
+    UnauthorisedOriginContract = 2214,
+    /// A refund policy cannot allow more than 100% of the payment.
+    MaxRefundBpsCannotExceed100Percent = 2215,
+    /// The refund policy excludes this payment tag.
+    PaymentTagExcluded = 2216,
+    /// The amount exceeds what the refund policy allows.
+    RefundAmountExceedsPolicy = 2217,
+    /// The refund falls outside the policy window.
+    RefundOutsideWindow = 2218,
+}
@@ -1120,3 +1120,71 @@ pub fn emit_evidence_anchored(
     }
     .publish(e);
 }
+
+/// Event: Admin set the keeper bounty terms
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct KeeperConfigSet {
+    pub token: Address,
+    pub bounty_per_item: i128,
+    pub max_reward_per_call: i128,
+    pub max_reward_per_ledger: i128,
+}
+
+/// Event: Admin added funds to the keeper pool
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct KeeperPoolFunded {
+    pub amount: i128,
+    pub balance: i128,
+}
+
+/// Event: Admin withdrew funds from the keeper pool
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct KeeperPoolWithdrawn {
+    pub amount: i128,
+    pub balance: i128,
+}
+
+/// Event: Keeper was paid for processing maintenance items
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct KeeperRewarded {
+    pub keeper: Address,
+    pub items: u32,
+    pub amount: i128,
+}
+
+pub fn emit_keeper_config_set(
+    e: &Env,
+    token: Address,
+    bounty_per_item: i128,
+    max_reward_per_call: i128,
+    max_reward_per_ledger: i128,
+) {
+    KeeperConfigSet {
+        token,
+        bounty_per_item,
+        max_reward_per_call,
+        max_reward_per_ledger,
+    }
+    .publish(e);
+}
+
+pub fn emit_keeper_pool_funded(e: &Env, amount: i128, balance: i128) {
+    KeeperPoolFunded { amount, balance }.publish(e);
+}
+
+pub fn emit_keeper_pool_withdrawn(e: &Env, amount: i128, balance: i128) {
+    KeeperPoolWithdrawn { amount, balance }.publish(e);
+}
+
+pub fn emit_keeper_rewarded(e: &Env, keeper: Address, items: u32, amount: i128) {
+    KeeperRewarded {
+        keeper,
+        items,
+        amount,
+    }
+    .publish(e);
+}
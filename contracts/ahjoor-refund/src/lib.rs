@@ -1,7 +1,8 @@
 #![no_std]
 use ahjoor_token_whitelist::TokenWhitelistClient;
 use soroban_sdk::{
-    contract, contractimpl, contracttype, token, Address, BytesN, Env, String, Symbol, Vec,
+    contract, contractimpl, contracttype, panic_with_error, token, Address, BytesN, Env, String,
+    Symbol, Vec,
 };
 
 // --- Storage TTL Constants ---
@@ -11,62 +12,10 @@ const INSTANCE_BUMP_AMOUNT: u32 = 120_000;
 const PERSISTENT_LIFETIME_THRESHOLD: u32 = 100_000;
 const PERSISTENT_BUMP_AMOUNT: u32 = 120_000;
 
-// ---------------------------------------------------------------------------
-// Minimal payment contract client — only the fields we need from get_payment.
-// ---------------------------------------------------------------------------
-mod payment_contract {
-    use soroban_sdk::{
-        contractclient, contracttype, Address, BytesN, Env, Map, String, Symbol, Vec,
-    };
-
-    #[contracttype]
-    #[derive(Clone, Debug, Eq, PartialEq)]
-    pub enum PaymentStatus {
-        Pending = 0,
-        Completed = 1,
-        Refunded = 2,
-        Disputed = 3,
-        Expired = 4,
-        ScheduledPending = 5,
-    }
-
-    #[contracttype]
-    #[derive(Clone, Debug, Eq, PartialEq)]
-    pub struct SplitRecipient {
-        pub recipient: Address,
-        pub bps: u32,
-    }
-
-    #[contracttype]
-    #[derive(Clone, Debug, Eq, PartialEq)]
-    pub struct Payment {
-        pub id: u32,
-        pub customer: Address,
-        pub merchant: Address,
-        pub amount: i128,
-        pub token: Address,
-        pub status: PaymentStatus,
-        pub created_at: u64,
-        pub expires_at: u64,
-        pub refunded_amount: i128,
-        pub reference: Option<String>,
-        pub metadata: Option<Map<String, String>>,
-        pub split_recipients: Option<Vec<SplitRecipient>>,
-        pub execute_after: u64,
-        pub category: Option<Symbol>,
-        pub tags: Option<Vec<Symbol>>,
-        pub capture_deadline: u64,
-        pub external_id: Option<BytesN<32>>,
-        pub tipping_enabled: bool,
-        pub extension_count: u32,
-    }
-
-    #[allow(dead_code)]
-    #[contractclient(name = "PaymentContractClient")]
-    pub trait PaymentContractInterface {
-        fn get_payment(env: Env, payment_id: u32) -> Payment;
-    }
-}
+// Payment schema and client come from `ahjoor-types`, the same definitions
+// `ahjoor-payments` stores, so `get_payment` can never decode a stale layout.
+use ahjoor_types::payment as payment_contract;
+pub use ahjoor_types::{KeeperConfig, KeeperPool};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[contracttype]
@@ -322,10 +271,19 @@ pub enum DataKey2 {
     // --- Feature: Evidence Hash Anchoring (#365) ---
     /// On-chain SHA-256 content hash anchor per (refund_id, submitter) (#365)
     EvidenceHash(u32, Address),
+
+    // --- Feature: Keeper Rewards ---
+    /// Keeper bounty terms → KeeperConfig
+    KeeperConfig,
+    /// Keeper pool balance and per-ledger tally → KeeperPool
+    KeeperPool,
 }
 
+mod errors;
 mod events;
 
+pub use errors::{Error, ReviewError, SettlementError};
+
 /// Merchant refund policy for on-chain validation (#320)
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -472,12 +430,12 @@ impl AhjoorRefundContract {
         let refund_cooldown_seconds = cfg.refund_cooldown_seconds;
         let customer_cancel_window_seconds = cfg.customer_cancel_window_seconds;
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("Already initialized");
+            panic_with_error!(&env, Error::AlreadyInitialized);
         }
 
         // Validate fee cap (max 200 bps = 2%)
         if refund_fee_bps > 200 {
-            panic!("Refund fee cannot exceed 200 basis points (2%)");
+            panic_with_error!(&env, Error::FeeTooHigh);
         }
 
         env.storage().instance().set(&DataKey::Admin, &admin);
@@ -510,7 +468,7 @@ impl AhjoorRefundContract {
         }
 
         let tiers = refund_tiers.unwrap_or(Vec::new(&env));
-        Self::validate_refund_tiers(&tiers);
+        Self::validate_refund_tiers(&env, &tiers);
         env.storage().instance().set(&DataKey::RefundTiers, &tiers);
 
         // Issue #161: Initialize global stats
@@ -586,12 +544,12 @@ impl AhjoorRefundContract {
         customer.require_auth();
 
         if amount <= 0 {
-            panic!("Refund amount must be positive");
+            panic_with_error!(&env, Error::InvalidAmount);
         }
 
         // #157: Validate reason code (0-4)
         if reason_code > 4 {
-            panic!("Invalid reason code: must be 0-4");
+            panic_with_error!(&env, Error::InvalidReasonCode);
         }
 
         // #166: Enforce per-customer cooldown
@@ -610,18 +568,13 @@ impl AhjoorRefundContract {
             if last_request > 0 && now_ts.saturating_sub(last_request) < cooldown {
                 let next_eligible_at = last_request + cooldown;
                 events::emit_refund_cooldown_active(&env, customer.clone(), next_eligible_at);
-                panic!("RefundCooldownActive");
+                panic_with_error!(&env, Error::RefundCooldownActive);
             }
         }
 
         // Fraud score check: block buyers at or above threshold
         if Self::is_buyer_blocked(&env, &customer) {
-            let score = Self::get_fraud_score(env.clone(), customer.clone());
-            let threshold = Self::get_fraud_score_block_threshold(env.clone());
-            panic!(
-                "BuyerBlockedForFraud: score={}, threshold={}",
-                score, threshold
-            );
+            panic_with_error!(&env, ReviewError::BuyerBlockedForFraud);
         }
 
         // Abuse score check: block customers with active abuse block or threshold exceeded
@@ -632,18 +585,18 @@ impl AhjoorRefundContract {
             .storage()
             .instance()
             .get(&DataKey::PaymentContractAddress)
-            .expect("Payment contract not configured");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::PaymentContractNotConfigured));
 
         let payment_client =
             payment_contract::PaymentContractClient::new(&env, &payment_contract_addr);
         let payment = payment_client
             .try_get_payment(&payment_id)
-            .unwrap_or_else(|_| panic!("PaymentContractError: payment not found"))
-            .unwrap_or_else(|_| panic!("PaymentContractError: payment not found"));
+            .unwrap_or_else(|_| panic_with_error!(&env, Error::PaymentNotFound))
+            .unwrap_or_else(|_| panic_with_error!(&env, Error::PaymentNotFound));
 
         // Validate payment status is Completed
         if payment.status != payment_contract::PaymentStatus::Completed {
-            panic!("PaymentContractError: payment is not completed");
+            panic_with_error!(&env, Error::PaymentNotCompleted);
         }
 
         // Validate merchant matches the payment's merchant
@@ -672,7 +625,7 @@ impl AhjoorRefundContract {
                     let absolute_cap = (payment.amount as u128 * tier_bps as u128 / 10_000) as i128;
                     let remaining_under_tier = absolute_cap.saturating_sub(already_refunded);
                     if remaining_under_tier <= 0 {
-                        panic!("ExceedsRefundableAmount");
+                        panic_with_error!(&env, Error::ExceedsRefundableAmount);
                     }
                     effective_amount = if amount > remaining_under_tier {
                         remaining_under_tier
@@ -686,12 +639,12 @@ impl AhjoorRefundContract {
             }
 
             if !matched {
-                panic!("RefundWindowExpired");
+                panic_with_error!(&env, Error::RefundWindowExpired);
             }
         }
 
         if effective_amount + already_refunded > payment.amount {
-            panic!("ExceedsRefundableAmount");
+            panic_with_error!(&env, Error::ExceedsRefundableAmount);
         }
         let policy = Self::refund_policy_for(&env, &merchant);
         Self::enforce_refund_policy(
@@ -928,27 +881,27 @@ impl AhjoorRefundContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
 
         let mut refund: Refund = env
             .storage()
             .persistent()
             .get(&DataKey::Refund(refund_id))
-            .expect("Refund not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::RefundNotFound));
 
         // #167: Allow admin OR a delegate of the refund's merchant
         let is_admin = admin == stored_admin;
         let is_delegate = !is_admin && Self::is_merchant_delegate(&env, &refund.merchant, &admin);
 
         if !is_admin && !is_delegate {
-            panic!("Only admin or merchant delegate can approve refunds");
+            panic_with_error!(&env, Error::NotAuthorizedReviewer);
         }
 
         if refund.status != RefundStatus::Requested
             && refund.status != RefundStatus::EvidenceSubmitted
             && refund.status != RefundStatus::EvidencePeriodExpired
         {
-            panic!("Refund is not in a state that can be approved");
+            panic_with_error!(&env, Error::RefundNotApprovable);
         }
 
         // #276: Block adjudication during open evidence window
@@ -956,7 +909,7 @@ impl AhjoorRefundContract {
             && refund.merch_response_deadline > 0
             && env.ledger().sequence() < refund.merch_response_deadline
         {
-            panic!("EvidenceWindowOpen: merchant evidence window has not elapsed");
+            panic_with_error!(&env, ReviewError::EvidenceWindowOpen);
         }
 
         // #276: Auto-advance to EvidencePeriodExpired if deadline passed without evidence
@@ -1029,24 +982,24 @@ impl AhjoorRefundContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
 
         let mut refund: Refund = env
             .storage()
             .persistent()
             .get(&DataKey::Refund(refund_id))
-            .expect("Refund not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::RefundNotFound));
 
         // #167: Allow admin OR a delegate of the refund's merchant
         let is_admin = admin == stored_admin;
         let is_delegate = !is_admin && Self::is_merchant_delegate(&env, &refund.merchant, &admin);
 
         if !is_admin && !is_delegate {
-            panic!("Only admin or merchant delegate can reject refunds");
+            panic_with_error!(&env, Error::NotAuthorizedReviewer);
         }
 
         if refund.status != RefundStatus::Requested {
-            panic!("Refund is not in requested status");
+            panic_with_error!(&env, Error::RefundNotRequested);
         }
 
         let now = env.ledger().timestamp();
@@ -1096,10 +1049,10 @@ impl AhjoorRefundContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
 
         if admin != stored_admin {
-            panic!("Only admin can process refunds");
+            panic_with_error!(&env, Error::Unauthorized);
         }
 
         Self::process_refund_internal(&env, refund_id);
@@ -1121,27 +1074,27 @@ impl AhjoorRefundContract {
         merchant.require_auth();
 
         if amount <= 0 {
-            panic!("Refund amount must be positive");
+            panic_with_error!(&env, Error::InvalidAmount);
         }
 
         let payment_contract_addr: Address = env
             .storage()
             .instance()
             .get(&DataKey::PaymentContractAddress)
-            .expect("Payment contract not configured");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::PaymentContractNotConfigured));
         let payment_client =
             payment_contract::PaymentContractClient::new(&env, &payment_contract_addr);
         let payment = payment_client
             .try_get_payment(&payment_id)
-            .unwrap_or_else(|_| panic!("PaymentContractError: payment not found"))
-            .unwrap_or_else(|_| panic!("PaymentContractError: payment not found"));
+            .unwrap_or_else(|_| panic_with_error!(&env, Error::PaymentNotFound))
+            .unwrap_or_else(|_| panic_with_error!(&env, Error::PaymentNotFound));
 
         if payment.merchant != merchant {
-            panic!("Only payment merchant can initiate refund");
+            panic_with_error!(&env, Error::NotMerchant);
         }
 
         if amount > payment.amount {
-            panic!("ExceedsRefundableAmount");
+            panic_with_error!(&env, Error::ExceedsRefundableAmount);
         }
 
         let already_refunded: i128 = env
@@ -1151,7 +1104,7 @@ impl AhjoorRefundContract {
             .unwrap_or(0);
         let remaining = payment.amount.saturating_sub(already_refunded);
         if amount > remaining {
-            panic!("MerchantBalanceInsufficient");
+            panic_with_error!(&env, Error::MerchantBalanceInsufficient);
         }
 
         // Token whitelist validation
@@ -1240,16 +1193,16 @@ impl AhjoorRefundContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         if admin != stored_admin {
-            panic!("Only admin can process refunds");
+            panic_with_error!(&env, Error::Unauthorized);
         }
 
         if refund_ids.is_empty() {
-            panic!("Refund batch cannot be empty");
+            panic_with_error!(&env, Error::EmptyBatch);
         }
         if refund_ids.len() > 20 {
-            panic!("BatchTooLarge");
+            panic_with_error!(&env, Error::BatchTooLarge);
         }
 
         for i in 0..refund_ids.len() {
@@ -1258,9 +1211,9 @@ impl AhjoorRefundContract {
                 .storage()
                 .persistent()
                 .get(&DataKey::Refund(rid))
-                .expect("Refund not found");
+                .unwrap_or_else(|| panic_with_error!(&env, Error::RefundNotFound));
             if refund.status != RefundStatus::Approved {
-                panic!("Refund is not approved");
+                panic_with_error!(&env, Error::RefundNotApproved);
             }
         }
 
@@ -1271,7 +1224,7 @@ impl AhjoorRefundContract {
                 .storage()
                 .persistent()
                 .get(&DataKey::Refund(rid))
-                .expect("Refund not found");
+                .unwrap_or_else(|| panic_with_error!(&env, Error::RefundNotFound));
             total_amount += refund.amount;
             Self::process_refund_internal(&env, rid);
         }
@@ -1302,31 +1255,31 @@ impl AhjoorRefundContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
 
         let mut refund: Refund = env
             .storage()
             .persistent()
             .get(&DataKey::Refund(refund_id))
-            .expect("Refund not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::RefundNotFound));
 
         // Allow admin OR a delegate of the refund's merchant
         let is_admin = admin == stored_admin;
         let is_delegate = !is_admin && Self::is_merchant_delegate(&env, &refund.merchant, &admin);
 
         if !is_admin && !is_delegate {
-            panic!("Only admin or merchant delegate can approve refunds as voucher");
+            panic_with_error!(&env, Error::NotAuthorizedReviewer);
         }
 
         if refund.status != RefundStatus::Requested
             && refund.status != RefundStatus::EvidenceSubmitted
             && refund.status != RefundStatus::EvidencePeriodExpired
         {
-            panic!("Refund is not in a state that can be approved as voucher");
+            panic_with_error!(&env, Error::RefundNotApprovable);
         }
 
         if credit_amount <= 0 {
-            panic!("credit_amount must be positive");
+            panic_with_error!(&env, Error::InvalidAmount);
         }
 
         // Validate bonus cap
@@ -1340,11 +1293,11 @@ impl AhjoorRefundContract {
             refund.amount + (refund.amount as u128 * max_bonus_bps as u128 / 10_000) as i128;
 
         if credit_amount > max_credit {
-            panic!("VoucherBonusExceedsCap");
+            panic_with_error!(&env, SettlementError::VoucherBonusExceedsCap);
         }
 
         if expiry_ledger <= env.ledger().sequence() as u64 {
-            panic!("expiry_ledger must be in the future");
+            panic_with_error!(&env, SettlementError::InvalidExpiry);
         }
 
         // Return the escrowed tokens to the contract (they stay locked — no transfer to customer).
@@ -1438,7 +1391,7 @@ impl AhjoorRefundContract {
         customer.require_auth();
 
         if credit_amount <= 0 {
-            panic!("credit_amount must be positive");
+            panic_with_error!(&env, Error::InvalidAmount);
         }
 
         let credit_key = DataKey::StoreCredit(merchant.clone(), customer.clone());
@@ -1446,15 +1399,15 @@ impl AhjoorRefundContract {
             .storage()
             .persistent()
             .get(&credit_key)
-            .expect("StoreCreditNotFound");
+            .unwrap_or_else(|| panic_with_error!(&env, SettlementError::StoreCreditNotFound));
 
         // Check expiry
         if env.ledger().sequence() as u64 > credit.expiry_ledger {
-            panic!("StoreCreditExpired");
+            panic_with_error!(&env, SettlementError::StoreCreditExpired);
         }
 
         if credit.credit_amount <= 0 {
-            panic!("StoreCreditExhausted");
+            panic_with_error!(&env, SettlementError::StoreCreditExhausted);
         }
 
         // Clamp to available balance
@@ -1501,18 +1454,18 @@ impl AhjoorRefundContract {
             .storage()
             .persistent()
             .get(&credit_key)
-            .expect("StoreCreditNotFound");
+            .unwrap_or_else(|| panic_with_error!(&env, SettlementError::StoreCreditNotFound));
 
         if credit.merchant != merchant {
-            panic!("Only the issuing merchant can extend store credit expiry");
+            panic_with_error!(&env, Error::NotMerchant);
         }
 
         if credit.extension_used {
-            panic!("StoreCreditExtensionAlreadyUsed");
+            panic_with_error!(&env, SettlementError::StoreCreditExtensionAlreadyUsed);
         }
 
         if new_expiry_ledger <= credit.expiry_ledger {
-            panic!("new_expiry_ledger must be later than current expiry");
+            panic_with_error!(&env, SettlementError::InvalidExpiry);
         }
 
         credit.expiry_ledger = new_expiry_ledger;
@@ -1535,14 +1488,14 @@ impl AhjoorRefundContract {
         env.storage()
             .persistent()
             .get(&DataKey::StoreCredit(merchant, customer))
-            .expect("StoreCreditNotFound")
+            .unwrap_or_else(|| panic_with_error!(&env, SettlementError::StoreCreditNotFound))
     }
 
     /// Admin sets the maximum voucher bonus in basis points (e.g. 500 = 5% above refund amount).
     pub fn set_max_voucher_bonus_bps(env: Env, admin: Address, max_bps: u32) {
         Self::require_admin(&env, &admin);
         if max_bps > 10_000 {
-            panic!("max_bps cannot exceed 10000");
+            panic_with_error!(&env, Error::InvalidBps);
         }
         env.storage()
             .instance()
@@ -1562,7 +1515,7 @@ impl AhjoorRefundContract {
 
     pub fn set_refund_tiers(env: Env, admin: Address, tiers: Vec<(u64, u32)>) {
         Self::require_admin(&env, &admin);
-        Self::validate_refund_tiers(&tiers);
+        Self::validate_refund_tiers(&env, &tiers);
         env.storage().instance().set(&DataKey::RefundTiers, &tiers);
         env.storage()
             .instance()
@@ -1586,21 +1539,21 @@ impl AhjoorRefundContract {
             .storage()
             .persistent()
             .get(&DataKey::Refund(refund_id))
-            .expect("Refund not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::RefundNotFound));
 
         if refund.status != RefundStatus::Requested {
-            panic!("Refund has already been acted on");
+            panic_with_error!(&env, Error::RefundAlreadyActedOn);
         }
 
         let dispute_window: u64 = env
             .storage()
             .instance()
             .get(&DataKey::DisputeWindow)
-            .expect("Dispute window not configured");
+            .unwrap_or_else(|| panic_with_error!(&env, ReviewError::DisputeWindowNotConfigured));
 
         let now = env.ledger().timestamp();
         if now < refund.requested_at + dispute_window {
-            panic!("Dispute window has not elapsed");
+            panic_with_error!(&env, ReviewError::DisputeWindowOpen);
         }
 
         let fee_bps: u32 = env
@@ -1678,7 +1631,7 @@ impl AhjoorRefundContract {
         // Check if already whitelisted
         for addr in whitelist.iter() {
             if addr == merchant {
-                panic!("Merchant already whitelisted");
+                panic_with_error!(&env, Error::MerchantAlreadyWhitelisted);
             }
         }
 
@@ -1718,7 +1671,7 @@ impl AhjoorRefundContract {
         }
 
         if !found {
-            panic!("Merchant not in whitelist");
+            panic_with_error!(&env, Error::MerchantNotWhitelisted);
         }
 
         env.storage()
@@ -1785,12 +1738,12 @@ impl AhjoorRefundContract {
             .unwrap_or(Vec::new(&env));
 
         if delegates.len() >= 5 {
-            panic!("Maximum 5 delegates per merchant");
+            panic_with_error!(&env, Error::TooManyDelegates);
         }
 
         for addr in delegates.iter() {
             if addr == delegate {
-                panic!("Address is already a delegate");
+                panic_with_error!(&env, Error::DelegateAlreadyExists);
             }
         }
 
@@ -1832,7 +1785,7 @@ impl AhjoorRefundContract {
         }
 
         if !found {
-            panic!("Address is not a delegate");
+            panic_with_error!(&env, Error::DelegateNotFound);
         }
 
         env.storage().persistent().set(&key, &new_delegates);
@@ -1869,14 +1822,14 @@ impl AhjoorRefundContract {
             .storage()
             .persistent()
             .get(&DataKey::Refund(refund_id))
-            .expect("Refund not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::RefundNotFound));
 
         if refund.customer != customer {
-            panic!("Only the requesting customer can cancel this refund");
+            panic_with_error!(&env, Error::NotCustomer);
         }
 
         if refund.status != RefundStatus::Requested {
-            panic!("Only Requested refunds can be cancelled");
+            panic_with_error!(&env, Error::RefundNotRequested);
         }
 
         // Return escrowed funds to customer
@@ -1934,21 +1887,21 @@ impl AhjoorRefundContract {
             .storage()
             .persistent()
             .get(&DataKey::Refund(refund_id))
-            .expect("Refund not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::RefundNotFound));
 
         if refund.status != RefundStatus::Requested {
-            panic!("Only Requested refunds can be auto-cancelled");
+            panic_with_error!(&env, Error::RefundNotRequested);
         }
 
         let cancel_window: u64 = env
             .storage()
             .instance()
             .get(&DataKey::CustomerCancelWindow)
-            .expect("Customer cancel window not configured");
+            .unwrap_or_else(|| panic_with_error!(&env, ReviewError::CancelWindowNotConfigured));
 
         let now = env.ledger().timestamp();
         if now < refund.requested_at + cancel_window {
-            panic!("Customer cancel window has not elapsed");
+            panic_with_error!(&env, ReviewError::CancelWindowOpen);
         }
 
         // Return escrowed funds to customer
@@ -1986,7 +1939,7 @@ impl AhjoorRefundContract {
         env.storage()
             .instance()
             .get(&DataKey::CustomerCancelWindow)
-            .expect("Customer cancel window not configured")
+            .unwrap_or_else(|| panic_with_error!(&env, ReviewError::CancelWindowNotConfigured))
     }
 
     // -------------------------------------------------------------------------
@@ -1999,7 +1952,7 @@ impl AhjoorRefundContract {
         Self::require_admin(&env, &admin);
 
         if threshold == 0 {
-            panic!("Threshold must be positive");
+            panic_with_error!(&env, Error::InvalidDuration);
         }
 
         let old_threshold: u32 = env
@@ -2039,7 +1992,7 @@ impl AhjoorRefundContract {
         merchant.require_auth();
 
         if amount < 0 {
-            panic!("Threshold amount cannot be negative");
+            panic_with_error!(&env, Error::InvalidAmount);
         }
 
         env.storage()
@@ -2229,7 +2182,7 @@ impl AhjoorRefundContract {
         page_size: u32,
     ) -> Vec<u32> {
         if reason_code > 4 {
-            panic!("Invalid reason code: must be 0-4");
+            panic_with_error!(&env, Error::InvalidReasonCode);
         }
         let key = DataKey::ReasonCodeRefunds(merchant, reason_code);
         let all: Vec<u32> = env
@@ -2250,7 +2203,7 @@ impl AhjoorRefundContract {
     /// Get the count of refunds for a merchant + reason_code combination.
     pub fn get_reason_code_count(env: Env, merchant: Address, reason_code: u32) -> u32 {
         if reason_code > 4 {
-            panic!("Invalid reason code: must be 0-4");
+            panic_with_error!(&env, Error::InvalidReasonCode);
         }
         env.storage()
             .persistent()
@@ -2263,25 +2216,25 @@ impl AhjoorRefundContract {
     // -------------------------------------------------------------------------
 
     /// Auto-reject a refund that has been sitting in Requested status beyond the idle window.
-    /// Callable by anyone. Returns escrowed funds to customer.
-    pub fn auto_reject_stale_refund(env: Env, refund_id: u32) {
+    /// Callable by anyone; `keeper` receives the keeper bounty. Returns escrowed funds to customer.
+    pub fn auto_reject_stale_refund(env: Env, keeper: Address, refund_id: u32) {
         Self::require_not_paused(&env);
 
         let mut refund: Refund = env
             .storage()
             .persistent()
             .get(&DataKey::Refund(refund_id))
-            .expect("Refund not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::RefundNotFound));
 
         if refund.status != RefundStatus::Requested {
-            panic!("Refund is not in requested status");
+            panic_with_error!(&env, Error::RefundNotRequested);
         }
 
         let auto_reject_window: u64 = env
             .storage()
             .instance()
             .get(&DataKey::AutoRejectWindow)
-            .expect("Auto-reject window not configured");
+            .unwrap_or_else(|| panic_with_error!(&env, ReviewError::AutoRejectWindowNotConfigured));
 
         let extension: u64 = env
             .storage()
@@ -2293,7 +2246,7 @@ impl AhjoorRefundContract {
         let now = env.ledger().timestamp();
 
         if now < deadline {
-            panic!("Auto-reject window has not elapsed");
+            panic_with_error!(&env, ReviewError::AutoRejectWindowOpen);
         }
 
         let elapsed_seconds = now - refund.requested_at;
@@ -2327,6 +2280,7 @@ impl AhjoorRefundContract {
         Self::update_stats_on_reject(&env, &refund.merchant);
 
         events::emit_refund_auto_rejected(&env, refund_id, elapsed_seconds);
+        Self::pay_keeper_reward(&env, &keeper, 1);
 
         env.storage()
             .instance()
@@ -2341,10 +2295,10 @@ impl AhjoorRefundContract {
             .storage()
             .persistent()
             .get(&DataKey::Refund(refund_id))
-            .expect("Refund not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::RefundNotFound));
 
         if refund.status != RefundStatus::Requested {
-            panic!("Refund is not in requested status");
+            panic_with_error!(&env, Error::RefundNotRequested);
         }
 
         let key = DataKey::RefundDeadlineExtension(refund_id);
@@ -2369,7 +2323,7 @@ impl AhjoorRefundContract {
         env.storage()
             .instance()
             .get(&DataKey::AutoRejectWindow)
-            .expect("Auto-reject window not configured")
+            .unwrap_or_else(|| panic_with_error!(&env, ReviewError::AutoRejectWindowNotConfigured))
     }
 
     // -------------------------------------------------------------------------
@@ -2386,27 +2340,29 @@ impl AhjoorRefundContract {
             .storage()
             .persistent()
             .get(&DataKey::Refund(refund_id))
-            .expect("Refund not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::RefundNotFound));
 
         if refund.status != RefundStatus::Rejected {
-            panic!("Appeal only allowed from Rejected status");
+            panic_with_error!(&env, ReviewError::AppealNotAllowed);
         }
 
         if refund.customer != customer {
-            panic!("Only the original customer can appeal");
+            panic_with_error!(&env, Error::NotCustomer);
         }
 
         let appeal_window: u64 = env
             .storage()
             .instance()
             .get(&DataKey::AppealWindow)
-            .expect("Appeal window not configured");
+            .unwrap_or_else(|| panic_with_error!(&env, ReviewError::AppealWindowNotConfigured));
 
-        let rejected_at = refund.rejected_at.expect("Rejection timestamp missing");
+        let rejected_at = refund
+            .rejected_at
+            .unwrap_or_else(|| panic_with_error!(&env, ReviewError::RejectionTimestampMissing));
         let now = env.ledger().timestamp();
 
         if now > rejected_at + appeal_window {
-            panic!("Appeal window has expired");
+            panic_with_error!(&env, ReviewError::AppealWindowExpired);
         }
 
         refund.status = RefundStatus::UnderAppeal;
@@ -2438,10 +2394,10 @@ impl AhjoorRefundContract {
             .storage()
             .persistent()
             .get(&DataKey::Refund(refund_id))
-            .expect("Refund not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::RefundNotFound));
 
         if refund.status != RefundStatus::UnderAppeal {
-            panic!("Refund is not under appeal");
+            panic_with_error!(&env, ReviewError::NotUnderAppeal);
         }
 
         let now = env.ledger().timestamp();
@@ -2541,7 +2497,7 @@ impl AhjoorRefundContract {
         env.storage()
             .instance()
             .get(&DataKey::AppealWindow)
-            .expect("Appeal window not configured")
+            .unwrap_or_else(|| panic_with_error!(&env, ReviewError::AppealWindowNotConfigured))
     }
 
     // -------------------------------------------------------------------------
@@ -2597,14 +2553,14 @@ impl AhjoorRefundContract {
 
         if let Some(escrow_addr) = escrow_contract_addr {
             if env.current_contract_address() != escrow_addr {
-                panic!("Only escrow contract can register escrow refunds");
+                panic_with_error!(&env, SettlementError::NotEscrowContract);
             }
         } else {
-            panic!("Escrow contract not configured");
+            panic_with_error!(&env, SettlementError::EscrowContractNotConfigured);
         }
 
         if amount <= 0 {
-            panic!("Refund amount must be positive");
+            panic_with_error!(&env, Error::InvalidAmount);
         }
 
         let refund_id = Self::next_refund_id(&env);
@@ -2693,7 +2649,7 @@ impl AhjoorRefundContract {
         Self::require_admin(&env, &admin);
 
         if new_fee_bps > 200 {
-            panic!("Refund fee cannot exceed 200 basis points (2%)");
+            panic_with_error!(&env, Error::FeeTooHigh);
         }
 
         env.storage()
@@ -2723,7 +2679,7 @@ impl AhjoorRefundContract {
         env.storage()
             .instance()
             .get(&DataKey::DisputeWindow)
-            .expect("Dispute window not configured")
+            .unwrap_or_else(|| panic_with_error!(&env, ReviewError::DisputeWindowNotConfigured))
     }
 
     /// Get refund details
@@ -2731,7 +2687,7 @@ impl AhjoorRefundContract {
         env.storage()
             .persistent()
             .get(&DataKey::Refund(refund_id))
-            .expect("Refund not found")
+            .unwrap_or_else(|| panic_with_error!(&env, Error::RefundNotFound))
     }
 
     /// Get refunds by customer with pagination.
@@ -2760,13 +2716,13 @@ impl AhjoorRefundContract {
             .storage()
             .instance()
             .get(&DataKey::PaymentContractAddress)
-            .expect("Payment contract not configured");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::PaymentContractNotConfigured));
         let payment_client =
             payment_contract::PaymentContractClient::new(&env, &payment_contract_addr);
         let payment = payment_client
             .try_get_payment(&payment_id)
-            .unwrap_or_else(|_| panic!("PaymentContractError: payment not found"))
-            .unwrap_or_else(|_| panic!("PaymentContractError: payment not found"));
+            .unwrap_or_else(|_| panic_with_error!(&env, Error::PaymentNotFound))
+            .unwrap_or_else(|_| panic_with_error!(&env, Error::PaymentNotFound));
         let already_refunded: i128 = env
             .storage()
             .persistent()
@@ -2796,7 +2752,7 @@ impl AhjoorRefundContract {
         env.storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized")
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized))
     }
 
     /// Get the configured payment contract address (#64).
@@ -2804,7 +2760,7 @@ impl AhjoorRefundContract {
         env.storage()
             .instance()
             .get(&DataKey::PaymentContractAddress)
-            .expect("Payment contract not configured")
+            .unwrap_or_else(|| panic_with_error!(&env, Error::PaymentContractNotConfigured))
     }
 
     /// Upgrade this contract's WASM code. Admin only.
@@ -2815,15 +2771,17 @@ impl AhjoorRefundContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         if admin != stored_admin {
-            panic!("Only admin can upgrade contract");
+            panic_with_error!(&env, Error::Unauthorized);
         }
 
         let old_version = Self::get_or_init_version(&env);
         env.deployer().update_current_contract_wasm(new_wasm_hash);
 
-        let new_version = old_version.checked_add(1).expect("Version overflow");
+        let new_version = old_version
+            .checked_add(1)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::ArithmeticOverflow));
         env.storage()
             .instance()
             .set(&DataKey::ContractVersion, &new_version);
@@ -2843,9 +2801,9 @@ impl AhjoorRefundContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         if admin != stored_admin {
-            panic!("Only admin can migrate contract");
+            panic_with_error!(&env, Error::Unauthorized);
         }
 
         let version = Self::get_or_init_version(&env);
@@ -2855,7 +2813,7 @@ impl AhjoorRefundContract {
             .get(&DataKey::MigrationCompleted(version))
             .unwrap_or(false)
         {
-            panic!("Migration already completed for this version");
+            panic_with_error!(&env, Error::MigrationAlreadyCompleted);
         }
 
         env.storage()
@@ -2883,9 +2841,9 @@ impl AhjoorRefundContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         if admin != stored_admin {
-            panic!("Only admin can set token whitelist contract");
+            panic_with_error!(&env, Error::Unauthorized);
         }
 
         env.storage()
@@ -2923,7 +2881,7 @@ impl AhjoorRefundContract {
         Self::require_admin(&env, &admin);
 
         if Self::is_paused(env.clone()) {
-            panic!("Contract already paused");
+            panic_with_error!(&env, Error::ContractAlreadyPaused);
         }
 
         env.storage().instance().set(&DataKey::Paused, &true);
@@ -2936,7 +2894,7 @@ impl AhjoorRefundContract {
         Self::require_admin(&env, &admin);
 
         if !Self::is_paused(env.clone()) {
-            panic!("Contract is not paused");
+            panic_with_error!(&env, Error::ContractNotPaused);
         }
 
         env.storage().instance().set(&DataKey::Paused, &false);
@@ -2959,6 +2917,127 @@ impl AhjoorRefundContract {
             .unwrap_or(String::from_str(&env, ""))
     }
 
+    // --- Keeper Rewards ---
+
+    /// Admin sets the bounty paid to callers of the permissionless maintenance
+    /// calls (`auto_reject_stale_refund`, `trigger_auto_approval`). The pool
+    /// token can only change while the pool is empty.
+    pub fn set_keeper_config(
+        env: Env,
+        admin: Address,
+        token: Address,
+        bounty_per_item: i128,
+        max_reward_per_call: i128,
+        max_reward_per_ledger: i128,
+    ) {
+        Self::require_admin(&env, &admin);
+        if bounty_per_item <= 0 || max_reward_per_call <= 0 || max_reward_per_ledger <= 0 {
+            panic_with_error!(&env, Error::InvalidKeeperConfig);
+        }
+        if let Some(current) = Self::get_keeper_config(env.clone()) {
+            if current.token != token && Self::get_keeper_pool(env.clone()).balance > 0 {
+                panic_with_error!(&env, Error::InvalidKeeperConfig);
+            }
+        }
+        Self::require_token_allowed(&env, &token);
+
+        let config = KeeperConfig {
+            token: token.clone(),
+            bounty_per_item,
+            max_reward_per_call,
+            max_reward_per_ledger,
+        };
+        env.storage().instance().set(&DataKey2::KeeperConfig, &config);
+        events::emit_keeper_config_set(
+            &env,
+            token,
+            bounty_per_item,
+            max_reward_per_call,
+            max_reward_per_ledger,
+        );
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Get the keeper bounty terms, if a keeper pool is configured.
+    pub fn get_keeper_config(env: Env) -> Option<KeeperConfig> {
+        env.storage().instance().get(&DataKey2::KeeperConfig)
+    }
+
+    /// Get the keeper pool balance and the current ledger's payout tally.
+    pub fn get_keeper_pool(env: Env) -> KeeperPool {
+        ahjoor_types::keeper::keeper_pool(&env, &DataKey2::KeeperPool)
+    }
+
+    /// Admin tops up the keeper pool in the configured keeper token.
+    pub fn fund_keeper_pool(env: Env, admin: Address, amount: i128) {
+        Self::require_admin(&env, &admin);
+        if amount <= 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+        let config = Self::get_keeper_config(env.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, Error::KeeperNotConfigured));
+
+        token::Client::new(&env, &config.token).transfer(
+            &admin,
+            env.current_contract_address(),
+            &amount,
+        );
+        let mut pool = Self::get_keeper_pool(env.clone());
+        pool.balance += amount;
+        env.storage().instance().set(&DataKey2::KeeperPool, &pool);
+        events::emit_keeper_pool_funded(&env, amount, pool.balance);
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Admin withdraws unspent funds from the keeper pool.
+    pub fn withdraw_keeper_pool(env: Env, admin: Address, amount: i128) {
+        Self::require_admin(&env, &admin);
+        if amount <= 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+        let config = Self::get_keeper_config(env.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, Error::KeeperNotConfigured));
+        let mut pool = Self::get_keeper_pool(env.clone());
+        if amount > pool.balance {
+            panic_with_error!(&env, Error::InsufficientKeeperPool);
+        }
+
+        pool.balance -= amount;
+        env.storage().instance().set(&DataKey2::KeeperPool, &pool);
+        token::Client::new(&env, &config.token).transfer(
+            &env.current_contract_address(),
+            &admin,
+            &amount,
+        );
+        events::emit_keeper_pool_withdrawn(&env, amount, pool.balance);
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Pay `keeper` the bounty for `items` processed by a maintenance call
+    /// from the keeper pool. Returns the amount paid.
+    fn pay_keeper_reward(env: &Env, keeper: &Address, items: u32) -> i128 {
+        let reward = ahjoor_types::keeper::pay_keeper_reward(
+            env,
+            &DataKey2::KeeperConfig,
+            &DataKey2::KeeperPool,
+            keeper,
+            items,
+        );
+        if reward > 0 {
+            events::emit_keeper_rewarded(env, keeper.clone(), items, reward);
+        }
+        reward
+    }
+
     // --- Internal Helpers ---
 
     fn require_not_paused(env: &Env) {
@@ -2968,7 +3047,7 @@ impl AhjoorRefundContract {
             .get(&DataKey::Paused)
             .unwrap_or(false)
         {
-            panic!("Contract is paused");
+            panic_with_error!(env, Error::ContractPaused);
         }
     }
 
@@ -2978,9 +3057,9 @@ impl AhjoorRefundContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(env, Error::NotInitialized));
         if stored_admin != *admin {
-            panic!("Only admin can manage pause state");
+            panic_with_error!(env, Error::Unauthorized);
         }
     }
 
@@ -2993,7 +3072,7 @@ impl AhjoorRefundContract {
         {
             let client = TokenWhitelistClient::new(env, &whitelist_contract);
             if !client.is_token_allowed_for_contract(&env.current_contract_address(), token) {
-                panic!("TokenNotAllowed");
+                panic_with_error!(env, Error::TokenNotAllowed);
             }
         }
     }
@@ -3212,15 +3291,15 @@ impl AhjoorRefundContract {
         );
     }
 
-    fn validate_refund_tiers(tiers: &Vec<(u64, u32)>) {
+    fn validate_refund_tiers(env: &Env, tiers: &Vec<(u64, u32)>) {
         let mut prev_max = 0u64;
         for i in 0..tiers.len() {
             let (max_seconds_since_payment, refund_bps) = tiers.get(i).unwrap();
             if i > 0 && max_seconds_since_payment < prev_max {
-                panic!("Refund tiers must be sorted by max_seconds_since_payment");
+                panic_with_error!(env, Error::InvalidRefundTiers);
             }
             if refund_bps > 10_000 {
-                panic!("Refund tier bps must be <= 10000");
+                panic_with_error!(env, Error::InvalidBps);
             }
             prev_max = max_seconds_since_payment;
         }
@@ -3231,10 +3310,10 @@ impl AhjoorRefundContract {
             .storage()
             .persistent()
             .get(&DataKey::Refund(refund_id))
-            .expect("Refund not found");
+            .unwrap_or_else(|| panic_with_error!(env, Error::RefundNotFound));
 
         if refund.status != RefundStatus::Approved {
-            panic!("Refund is not approved");
+            panic_with_error!(env, Error::RefundNotApproved);
         }
 
         let fee_bps: u32 = env
@@ -3306,7 +3385,7 @@ impl AhjoorRefundContract {
             .storage()
             .instance()
             .get(&DataKey::PaymentContractAddress)
-            .expect("Payment contract not configured");
+            .unwrap_or_else(|| panic_with_error!(env, Error::PaymentContractNotConfigured));
         let payment_client =
             payment_contract::PaymentContractClient::new(env, &payment_contract_addr);
 
@@ -3362,9 +3441,9 @@ impl AhjoorRefundContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         if admin != stored_admin {
-            panic!("Only admin can configure counter-offer default resolution");
+            panic_with_error!(&env, Error::Unauthorized);
         }
         env.storage()
             .instance()
@@ -3383,21 +3462,21 @@ impl AhjoorRefundContract {
             .storage()
             .persistent()
             .get(&DataKey::Refund(refund_id))
-            .expect("Refund not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::RefundNotFound));
 
         if refund.status != RefundStatus::CounterOffered {
-            panic!("Refund is not in CounterOffered state");
+            panic_with_error!(&env, ReviewError::NotCounterOffered);
         }
 
         let offer: CounterOffer = env
             .storage()
             .persistent()
             .get(&DataKey::CounterOffer(refund_id))
-            .expect("Counter-offer not found");
+            .unwrap_or_else(|| panic_with_error!(&env, ReviewError::CounterOfferNotFound));
 
         let now = env.ledger().timestamp();
         if now <= offer.expiry {
-            panic!("Counter-offer has not expired yet");
+            panic_with_error!(&env, ReviewError::CounterOfferNotExpired);
         }
 
         let accept_on_expiry: bool = env
@@ -3461,12 +3540,12 @@ impl AhjoorRefundContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         if admin != stored_admin {
-            panic!("Only admin can configure counter-offer expiry");
+            panic_with_error!(&env, Error::Unauthorized);
         }
         if seconds == 0 {
-            panic!("Expiry must be positive");
+            panic_with_error!(&env, Error::InvalidDuration);
         }
         env.storage()
             .instance()
@@ -3483,25 +3562,25 @@ impl AhjoorRefundContract {
         merchant.require_auth();
 
         if amount <= 0 {
-            panic!("Counter-offer amount must be positive");
+            panic_with_error!(&env, Error::InvalidAmount);
         }
 
         let mut refund: Refund = env
             .storage()
             .persistent()
             .get(&DataKey::Refund(refund_id))
-            .expect("Refund not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::RefundNotFound));
 
         if refund.status != RefundStatus::Requested {
-            panic!("Refund is not in Requested state");
+            panic_with_error!(&env, Error::RefundNotRequested);
         }
 
         if merchant != refund.merchant {
-            panic!("Only the merchant can make a counter-offer");
+            panic_with_error!(&env, Error::NotMerchant);
         }
 
         if amount > refund.amount {
-            panic!("Counter-offer cannot exceed original refund amount");
+            panic_with_error!(&env, ReviewError::CounterOfferExceedsRefund);
         }
 
         // Prevent duplicate counter-offers
@@ -3510,7 +3589,7 @@ impl AhjoorRefundContract {
             .persistent()
             .has(&DataKey::CounterOffer(refund_id))
         {
-            panic!("Counter-offer already submitted for this refund");
+            panic_with_error!(&env, ReviewError::CounterOfferExists);
         }
 
         let expiry_seconds: u64 = env
@@ -3561,21 +3640,21 @@ impl AhjoorRefundContract {
             .storage()
             .persistent()
             .get(&DataKey::Refund(refund_id))
-            .expect("Refund not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::RefundNotFound));
 
         if refund.status != RefundStatus::CounterOffered {
-            panic!("Refund is not in CounterOffered state");
+            panic_with_error!(&env, ReviewError::NotCounterOffered);
         }
 
         if customer != refund.customer {
-            panic!("Only the customer can accept a counter-offer");
+            panic_with_error!(&env, Error::NotCustomer);
         }
 
         let offer: CounterOffer = env
             .storage()
             .persistent()
             .get(&DataKey::CounterOffer(refund_id))
-            .expect("Counter-offer not found");
+            .unwrap_or_else(|| panic_with_error!(&env, ReviewError::CounterOfferNotFound));
 
         let now = env.ledger().timestamp();
         if now > offer.expiry {
@@ -3625,14 +3704,14 @@ impl AhjoorRefundContract {
             .storage()
             .persistent()
             .get(&DataKey::Refund(refund_id))
-            .expect("Refund not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::RefundNotFound));
 
         if refund.status != RefundStatus::CounterOffered {
-            panic!("Refund is not in CounterOffered state");
+            panic_with_error!(&env, ReviewError::NotCounterOffered);
         }
 
         if customer != refund.customer {
-            panic!("Only the customer can reject a counter-offer");
+            panic_with_error!(&env, Error::NotCustomer);
         }
 
         env.storage()
@@ -3657,20 +3736,20 @@ impl AhjoorRefundContract {
             .storage()
             .persistent()
             .get(&DataKey::Refund(refund_id))
-            .expect("Refund not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::RefundNotFound));
 
         if refund.status != RefundStatus::CounterOffered {
-            panic!("Refund is not in CounterOffered state");
+            panic_with_error!(&env, ReviewError::NotCounterOffered);
         }
 
         let offer: CounterOffer = env
             .storage()
             .persistent()
             .get(&DataKey::CounterOffer(refund_id))
-            .expect("Counter-offer not found");
+            .unwrap_or_else(|| panic_with_error!(&env, ReviewError::CounterOfferNotFound));
 
         if env.ledger().timestamp() <= offer.expiry {
-            panic!("Counter-offer has not expired yet");
+            panic_with_error!(&env, ReviewError::CounterOfferNotExpired);
         }
 
         env.storage()
@@ -3701,9 +3780,9 @@ impl AhjoorRefundContract {
             .storage()
             .persistent()
             .get(&DataKey::Refund(refund_id))
-            .expect("Refund not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::RefundNotFound));
         if refund.status != RefundStatus::Requested {
-            panic!("Can only set priority on Requested refunds");
+            panic_with_error!(&env, Error::RefundNotRequested);
         }
         refund.priority = priority;
         env.storage()
@@ -3730,12 +3809,12 @@ impl AhjoorRefundContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         if admin != stored_admin {
-            panic!("Only admin can set max batch size");
+            panic_with_error!(&env, Error::Unauthorized);
         }
         if max_batch_size == 0 {
-            panic!("Max batch size must be positive");
+            panic_with_error!(&env, Error::InvalidBatchSize);
         }
         env.storage()
             .instance()
@@ -3822,9 +3901,9 @@ impl AhjoorRefundContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         if admin != stored_admin {
-            panic!("Only admin can batch approve refunds");
+            panic_with_error!(&env, Error::Unauthorized);
         }
 
         let max_batch: u32 = env
@@ -3834,7 +3913,7 @@ impl AhjoorRefundContract {
             .unwrap_or(DEFAULT_MAX_BATCH_SIZE);
 
         if refund_ids.len() > max_batch {
-            panic!("Batch size exceeds maximum allowed");
+            panic_with_error!(&env, Error::BatchTooLarge);
         }
 
         let mut processed = Vec::new(&env);
@@ -3893,9 +3972,9 @@ impl AhjoorRefundContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Not initialized");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
         if admin != stored_admin {
-            panic!("Only admin can batch reject refunds");
+            panic_with_error!(&env, Error::Unauthorized);
         }
 
         let max_batch: u32 = env
@@ -3905,7 +3984,7 @@ impl AhjoorRefundContract {
             .unwrap_or(DEFAULT_MAX_BATCH_SIZE);
 
         if refund_ids.len() > max_batch {
-            panic!("Batch size exceeds maximum allowed");
+            panic_with_error!(&env, Error::BatchTooLarge);
         }
 
         let mut processed = Vec::new(&env);
@@ -3971,7 +4050,7 @@ impl AhjoorRefundContract {
         Self::require_not_paused(&env);
         Self::require_admin(&env, &admin);
         if ratio_bps > 10_000 {
-            panic!("reserve_ratio_bps cannot exceed 10000");
+            panic_with_error!(&env, Error::InvalidBps);
         }
         env.storage()
             .instance()
@@ -3986,7 +4065,7 @@ impl AhjoorRefundContract {
         Self::require_not_paused(&env);
         merchant.require_auth();
         if amount <= 0 {
-            panic!("amount must be positive");
+            panic_with_error!(&env, Error::InvalidAmount);
         }
         Self::require_token_allowed(&env, &token);
         let client = token::Client::new(&env, &token);
@@ -4019,13 +4098,13 @@ impl AhjoorRefundContract {
         Self::require_not_paused(&env);
         merchant.require_auth();
         if amount <= 0 {
-            panic!("amount must be positive");
+            panic_with_error!(&env, Error::InvalidAmount);
         }
         let key = DataKey::MerchantReserve(merchant.clone());
         let current: i128 = env.storage().persistent().get(&key).unwrap_or(0);
         let required = Self::required_reserve_internal(&env, &merchant);
         if current - amount < required {
-            panic!("WithdrawalWouldBreachMinimum: reserve would fall below required minimum");
+            panic_with_error!(&env, SettlementError::WithdrawalBreachesMinimum);
         }
         let new_balance = current - amount;
         env.storage().persistent().set(&key, &new_balance);
@@ -4100,7 +4179,7 @@ impl AhjoorRefundContract {
             .persistent()
             .has(&DataKey::MerchantFlagged(merchant))
         {
-            panic!("ReserveBelowMinimum: merchant is non-compliant; deposit reserve before creating payments");
+            panic_with_error!(&env, SettlementError::ReserveBelowMinimum);
         }
     }
 
@@ -4128,7 +4207,7 @@ impl AhjoorRefundContract {
         Self::require_not_paused(&env);
         Self::require_admin(&env, &admin);
         if window_ledgers == 0 {
-            panic!("window_ledgers must be positive");
+            panic_with_error!(&env, Error::InvalidDuration);
         }
         env.storage()
             .instance()
@@ -4156,12 +4235,12 @@ impl AhjoorRefundContract {
             .storage()
             .persistent()
             .get(&DataKey::Refund(refund_id))
-            .expect("Refund not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::RefundNotFound));
         if refund.merchant != merchant {
-            panic!("Only the refund merchant can submit evidence");
+            panic_with_error!(&env, Error::NotMerchant);
         }
         if refund.status != RefundStatus::Requested && refund.status != RefundStatus::EvidenceSubmitted {
-            panic!("Evidence can only be submitted for Requested or EvidenceSubmitted refunds");
+            panic_with_error!(&env, ReviewError::EvidenceNotAllowed);
         }
         // Window check: reject if deadline has passed
         if refund.merch_response_deadline > 0
@@ -4180,7 +4259,7 @@ impl AhjoorRefundContract {
                 );
                 events::emit_evidence_period_expired(&env, refund_id, merchant);
             }
-            panic!("EvidenceDeadlinePassed");
+            panic_with_error!(&env, ReviewError::EvidenceDeadlinePassed);
         }
         let num_hashes = evidence_hashes.len();
         let evidence = RefundEvidence {
@@ -4261,7 +4340,7 @@ impl AhjoorRefundContract {
         Self::require_not_paused(&env);
         Self::require_admin(&env, &admin);
         if ledgers == 0 {
-            panic!("ledgers must be positive");
+            panic_with_error!(&env, Error::InvalidDuration);
         }
         env.storage()
             .instance()
@@ -4276,7 +4355,7 @@ impl AhjoorRefundContract {
         Self::require_not_paused(&env);
         Self::require_admin(&env, &admin);
         if ledgers == 0 {
-            panic!("ledgers must be positive");
+            panic_with_error!(&env, Error::InvalidDuration);
         }
         env.storage()
             .instance()
@@ -4325,28 +4404,28 @@ impl AhjoorRefundContract {
             .storage()
             .persistent()
             .get(&DataKey::Refund(refund_id))
-            .expect("Refund not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::RefundNotFound));
 
         if refund.status != RefundStatus::Requested
             && refund.status != RefundStatus::EvidenceSubmitted
             && refund.status != RefundStatus::EvidencePeriodExpired
         {
-            panic!("EscalationNotAllowed: refund is not in an escalatable state");
+            panic_with_error!(&env, ReviewError::EscalationNotAllowed);
         }
 
         if refund.primary_review_deadline_ledger == 0 {
-            panic!("EscalationNotAllowed: no primary review deadline configured for this refund");
+            panic_with_error!(&env, ReviewError::EscalationNotAllowed);
         }
 
         if env.ledger().sequence() < refund.primary_review_deadline_ledger {
-            panic!("PrimaryDeadlineNotPassed: primary review deadline has not elapsed");
+            panic_with_error!(&env, ReviewError::PrimaryDeadlineNotPassed);
         }
 
         let senior_arbiter: Address = env
             .storage()
             .instance()
             .get(&DataKey2::SeniorArbiter)
-            .expect("SeniorArbiterNotConfigured");
+            .unwrap_or_else(|| panic_with_error!(&env, ReviewError::SeniorArbiterNotConfigured));
 
         let senior_window: u32 = env
             .storage()
@@ -4390,20 +4469,20 @@ impl AhjoorRefundContract {
             .storage()
             .instance()
             .get(&DataKey2::SeniorArbiter)
-            .expect("SeniorArbiterNotConfigured");
+            .unwrap_or_else(|| panic_with_error!(&env, ReviewError::SeniorArbiterNotConfigured));
 
         if senior_arbiter != stored_arbiter {
-            panic!("UnauthorizedSeniorArbiter");
+            panic_with_error!(&env, ReviewError::UnauthorizedSeniorArbiter);
         }
 
         let mut refund: Refund = env
             .storage()
             .persistent()
             .get(&DataKey::Refund(refund_id))
-            .expect("Refund not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::RefundNotFound));
 
         if refund.status != RefundStatus::EscalatedToSenior {
-            panic!("RefundNotEscalated");
+            panic_with_error!(&env, ReviewError::RefundNotEscalated);
         }
 
         let now = env.ledger().timestamp();
@@ -4510,23 +4589,23 @@ impl AhjoorRefundContract {
             .unwrap_or(false);
 
         if !auto_approve {
-            panic!("AutoApproveOnSeniorMissDisabled");
+            panic_with_error!(&env, ReviewError::AutoApproveOnSeniorMissDisabled);
         }
 
         let mut refund: Refund = env
             .storage()
             .persistent()
             .get(&DataKey::Refund(refund_id))
-            .expect("Refund not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::RefundNotFound));
 
         if refund.status != RefundStatus::EscalatedToSenior {
-            panic!("RefundNotEscalated");
+            panic_with_error!(&env, ReviewError::RefundNotEscalated);
         }
 
         if refund.senior_review_deadline_ledger == 0
             || env.ledger().sequence() < refund.senior_review_deadline_ledger
         {
-            panic!("SeniorDeadlineNotPassed");
+            panic_with_error!(&env, ReviewError::SeniorDeadlineNotPassed);
         }
 
         let now = env.ledger().timestamp();
@@ -4610,7 +4689,7 @@ impl AhjoorRefundContract {
     pub fn set_abuse_block_threshold(env: Env, admin: Address, threshold: u32) {
         Self::require_admin(&env, &admin);
         if threshold == 0 {
-            panic!("threshold must be positive");
+            panic_with_error!(&env, Error::InvalidDuration);
         }
         env.storage()
             .instance()
@@ -4624,7 +4703,7 @@ impl AhjoorRefundContract {
     pub fn set_block_duration_ledgers(env: Env, admin: Address, ledgers: u64) {
         Self::require_admin(&env, &admin);
         if ledgers == 0 {
-            panic!("ledgers must be positive");
+            panic_with_error!(&env, Error::InvalidDuration);
         }
         env.storage()
             .instance()
@@ -4638,7 +4717,7 @@ impl AhjoorRefundContract {
     pub fn set_rapid_submission_window(env: Env, admin: Address, ledgers: u32) {
         Self::require_admin(&env, &admin);
         if ledgers == 0 {
-            panic!("ledgers must be positive");
+            panic_with_error!(&env, Error::InvalidDuration);
         }
         env.storage()
             .instance()
@@ -4656,10 +4735,10 @@ impl AhjoorRefundContract {
             .storage()
             .persistent()
             .get(&DataKey::Refund(refund_id))
-            .expect("Refund not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::RefundNotFound));
 
         if refund.status != RefundStatus::Rejected {
-            panic!("OnlyRejectedRefundsCanBeFlagged");
+            panic_with_error!(&env, ReviewError::OnlyRejectedRefundsCanBeFlagged);
         }
 
         Self::increment_abuse_score(&env, &refund.customer, 10, true); // +10 more on top of existing +10
@@ -4724,10 +4803,10 @@ impl AhjoorRefundContract {
         Self::require_admin(&env, &admin);
 
         if factor_bps > 10_000 {
-            panic!("factor_bps must be <= 10_000 (10_000 = no decay)");
+            panic_with_error!(&env, Error::InvalidBps);
         }
         if period_ledgers == 0 && factor_bps > 0 {
-            panic!("period_ledgers must be positive when factor_bps > 0");
+            panic_with_error!(&env, Error::InvalidDuration);
         }
 
         env.storage()
@@ -4864,7 +4943,7 @@ impl AhjoorRefundContract {
                 customer.clone(),
                 record.blocked_until_ledger,
             );
-            panic!("CustomerBlockedForAbuse");
+            panic_with_error!(env, ReviewError::CustomerBlockedForAbuse);
         }
 
         // Check if score now exceeds threshold (even after decay)
@@ -4894,7 +4973,7 @@ impl AhjoorRefundContract {
             );
 
             events::emit_customer_blocked_for_abuse(env, customer.clone(), blocked_until);
-            panic!("CustomerBlockedForAbuse");
+            panic_with_error!(env, ReviewError::CustomerBlockedForAbuse);
         }
     }
 
@@ -4979,7 +5058,7 @@ impl AhjoorRefundContract {
 
         for addr in whitelist.iter() {
             if addr == contract {
-                panic!("ContractAlreadyWhitelisted");
+                panic_with_error!(&env, SettlementError::ContractAlreadyWhitelisted);
             }
         }
 
@@ -5019,7 +5098,7 @@ impl AhjoorRefundContract {
         }
 
         if !found {
-            panic!("ContractNotWhitelisted");
+            panic_with_error!(&env, SettlementError::ContractNotWhitelisted);
         }
 
         env.storage()
@@ -5053,7 +5132,7 @@ impl AhjoorRefundContract {
         origin_contract.require_auth();
 
         if amount <= 0 {
-            panic!("amount must be positive");
+            panic_with_error!(&env, Error::InvalidAmount);
         }
 
         // Verify origin_contract is whitelisted
@@ -5070,7 +5149,7 @@ impl AhjoorRefundContract {
             }
         }
         if !allowed {
-            panic!("UnauthorisedOriginContract");
+            panic_with_error!(&env, SettlementError::UnauthorisedOriginContract);
         }
 
         let refund_id = Self::next_refund_id(&env);
@@ -5197,14 +5276,14 @@ impl AhjoorRefundContract {
         merchant.require_auth();
 
         if amount <= 0 {
-            panic!("Amount must be positive");
+            panic_with_error!(&env, Error::InvalidAmount);
         }
 
         let token: Address = env
             .storage()
             .instance()
             .get(&DataKey2::ReserveToken)
-            .unwrap_or_else(|| panic!("Reserve token not configured"));
+            .unwrap_or_else(|| panic_with_error!(&env, SettlementError::ReserveTokenNotConfigured));
 
         let client = token::Client::new(&env, &token);
         client.transfer_from(
@@ -5289,7 +5368,7 @@ impl AhjoorRefundContract {
         merchant.require_auth();
 
         if amount <= 0 {
-            panic!("Amount must be positive");
+            panic_with_error!(&env, Error::InvalidAmount);
         }
 
         let mut balance: i128 = env
@@ -5299,7 +5378,7 @@ impl AhjoorRefundContract {
             .unwrap_or(0);
 
         if balance < amount {
-            panic!("Insufficient reserve balance");
+            panic_with_error!(&env, SettlementError::InsufficientReserve);
         }
 
         // Check that withdrawal doesn't breach minimum
@@ -5318,7 +5397,7 @@ impl AhjoorRefundContract {
         let minimum_required = (volume as u128 * min_bps as u128 / 10_000) as i128;
 
         if balance - amount < minimum_required {
-            panic!("Withdrawal would breach minimum reserve requirement");
+            panic_with_error!(&env, SettlementError::WithdrawalBreachesMinimum);
         }
 
         balance -= amount;
@@ -5330,7 +5409,7 @@ impl AhjoorRefundContract {
             .storage()
             .instance()
             .get(&DataKey2::ReserveToken)
-            .unwrap_or_else(|| panic!("Reserve token not configured"));
+            .unwrap_or_else(|| panic_with_error!(&env, SettlementError::ReserveTokenNotConfigured));
 
         let client = token::Client::new(&env, &token);
         client.transfer(&env.current_contract_address(), &merchant, &amount);
@@ -5413,18 +5492,19 @@ impl AhjoorRefundContract {
 
     // --- #335: Refund Auto-Approval on Merchant Non-Response ---
 
-    /// Trigger auto-approval after merchant deadline has passed.
-    pub fn trigger_auto_approval(env: Env, refund_id: u32) {
+    /// Trigger auto-approval after merchant deadline has passed. Callable by
+    /// anyone; `keeper` receives the keeper bounty.
+    pub fn trigger_auto_approval(env: Env, keeper: Address, refund_id: u32) {
         Self::require_not_paused(&env);
 
         let mut refund: Refund = env
             .storage()
             .persistent()
             .get(&DataKey::Refund(refund_id))
-            .expect("Refund not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::RefundNotFound));
 
         if refund.status != RefundStatus::Requested {
-            panic!("RefundNotInRequestedState");
+            panic_with_error!(&env, Error::RefundNotRequested);
         }
         if env
             .storage()
@@ -5432,12 +5512,12 @@ impl AhjoorRefundContract {
             .get::<DataKey2, bool>(&DataKey2::MerchantAutoApproveExempt(refund.merchant.clone()))
             .unwrap_or(false)
         {
-            panic!("MerchantAutoApprovalExempt");
+            panic_with_error!(&env, ReviewError::MerchantAutoApprovalExempt);
         }
 
         let now_ledger = env.ledger().sequence();
         if now_ledger < refund.auto_approval_deadline_ledger {
-            panic!("MerchantDeadlineNotPassed");
+            panic_with_error!(&env, ReviewError::MerchantDeadlineNotPassed);
         }
 
         refund.status = RefundStatus::Approved;
@@ -5447,6 +5527,7 @@ impl AhjoorRefundContract {
             .set(&DataKey::Refund(refund_id), &refund);
         Self::process_refund_internal(&env, refund_id);
         events::emit_refund_auto_approved(&env, refund_id, refund.customer, refund.amount);
+        Self::pay_keeper_reward(&env, &keeper, 1);
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -5466,14 +5547,14 @@ impl AhjoorRefundContract {
             .storage()
             .persistent()
             .get(&DataKey::Refund(refund_id))
-            .expect("Refund not found");
+            .unwrap_or_else(|| panic_with_error!(&env, Error::RefundNotFound));
 
         if refund.merchant != merchant {
-            panic!("OnlyMerchantCanRequestExtension");
+            panic_with_error!(&env, Error::NotMerchant);
         }
 
         if refund.extension_requested {
-            panic!("ExtensionAlreadyUsed");
+            panic_with_error!(&env, ReviewError::ExtensionAlreadyUsed);
         }
 
         let extension_ledgers: u32 = env
@@ -5518,7 +5599,7 @@ impl AhjoorRefundContract {
         merchant.require_auth();
 
         if max_refund_bps > 10_000 {
-            panic!("MaxRefundBpsCannotExceed100Percent");
+            panic_with_error!(&env, SettlementError::MaxRefundBpsCannotExceed100Percent);
         }
 
         let was_update = env
@@ -5566,7 +5647,7 @@ impl AhjoorRefundContract {
         admin.require_auth();
         Self::require_admin(&env, &admin);
         if max_refund_bps > 10_000 {
-            panic!("MaxRefundBpsCannotExceed100Percent");
+            panic_with_error!(&env, SettlementError::MaxRefundBpsCannotExceed100Percent);
         }
         env.storage().instance().set(
             &DataKey2::GlobalRefundPolicy,
@@ -5643,16 +5724,16 @@ impl AhjoorRefundContract {
         let elapsed_ledgers =
             (env.ledger().timestamp().saturating_sub(payment_created_at) / 5) as u32;
         if elapsed_ledgers > policy.eligible_window_ledgers {
-            panic!("RefundOutsideWindow");
+            panic_with_error!(env, SettlementError::RefundOutsideWindow);
         }
         if refund_amount > (payment_amount as u128 * policy.max_refund_bps as u128 / 10_000) as i128
         {
-            panic!("RefundAmountExceedsPolicy");
+            panic_with_error!(env, SettlementError::RefundAmountExceedsPolicy);
         }
         if let Some(tags) = payment_tags {
             for tag in tags {
                 if Self::is_tag_excluded(&policy.excluded_tags, tag) {
-                    panic!("PaymentTagExcluded");
+                    panic_with_error!(env, SettlementError::PaymentTagExcluded);
                 }
             }
         }
@@ -5691,3 +5772,9 @@ mod test_abuse_score;
 
 #[cfg(test)]
 mod test_cross_contract_refund;
+
+#[cfg(test)]
+mod test_keeper;
+
+#[cfg(test)]
+mod test_errors;
@@ -96,7 +96,7 @@ fn test_initialize() {
 }
 
 #[test]
-#[should_panic(expected = "Already initialized")]
+#[should_panic(expected = "Error(Contract, #2000)")] // AlreadyInitialized
 fn test_initialize_twice_panics() {
     let s = setup();
     s.refund_client
@@ -136,7 +136,7 @@ fn test_request_refund_against_completed_payment() {
 }
 
 #[test]
-#[should_panic(expected = "PaymentContractError: payment is not completed")]
+#[should_panic(expected = "Error(Contract, #2022)")] // PaymentNotCompleted
 fn test_request_refund_against_pending_payment_panics() {
     let s = setup();
     let customer = Address::generate(&s.env);
@@ -159,7 +159,7 @@ fn test_request_refund_against_pending_payment_panics() {
 }
 
 #[test]
-#[should_panic(expected = "PaymentContractError: payment not found")]
+#[should_panic(expected = "Error(Contract, #2021)")] // PaymentNotFound
 fn test_request_refund_nonexistent_payment_panics() {
     let s = setup();
     let customer = Address::generate(&s.env);
@@ -175,7 +175,7 @@ fn test_request_refund_nonexistent_payment_panics() {
 }
 
 #[test]
-#[should_panic(expected = "ExceedsRefundableAmount")]
+#[should_panic(expected = "Error(Contract, #2017)")] // ExceedsRefundableAmount
 fn test_request_refund_exceeds_payment_amount_panics() {
     let s = setup();
     let customer = Address::generate(&s.env);
@@ -190,7 +190,7 @@ fn test_request_refund_exceeds_payment_amount_panics() {
 }
 
 #[test]
-#[should_panic(expected = "Refund amount must be positive")]
+#[should_panic(expected = "Error(Contract, #2007)")] // InvalidAmount
 fn test_request_refund_zero_amount_panics() {
     let s = setup();
     let customer = Address::generate(&s.env);
@@ -230,7 +230,7 @@ fn test_approve_refund() {
 }
 
 #[test]
-#[should_panic(expected = "Only admin or merchant delegate can approve refunds")]
+#[should_panic(expected = "Error(Contract, #2010)")] // NotAuthorizedReviewer
 fn test_approve_refund_by_non_admin_panics() {
     let s = setup();
     let customer = Address::generate(&s.env);
@@ -251,7 +251,7 @@ fn test_approve_refund_by_non_admin_panics() {
 }
 
 #[test]
-#[should_panic(expected = "Refund is not in a state that can be approved")]
+#[should_panic(expected = "Error(Contract, #2014)")] // RefundNotApprovable
 fn test_approve_already_approved_refund_panics() {
     let s = setup();
     let customer = Address::generate(&s.env);
@@ -302,7 +302,7 @@ fn test_reject_refund() {
 }
 
 #[test]
-#[should_panic(expected = "Only admin or merchant delegate can reject refunds")]
+#[should_panic(expected = "Error(Contract, #2010)")] // NotAuthorizedReviewer
 fn test_reject_refund_by_non_admin_panics() {
     let s = setup();
     let customer = Address::generate(&s.env);
@@ -355,7 +355,7 @@ fn test_process_refund() {
 }
 
 #[test]
-#[should_panic(expected = "Only admin can process refunds")]
+#[should_panic(expected = "Error(Contract, #2002)")] // Unauthorized
 fn test_process_refund_by_non_admin_panics() {
     let s = setup();
     let customer = Address::generate(&s.env);
@@ -377,7 +377,7 @@ fn test_process_refund_by_non_admin_panics() {
 }
 
 #[test]
-#[should_panic(expected = "Refund is not approved")]
+#[should_panic(expected = "Error(Contract, #2015)")] // RefundNotApproved
 fn test_process_unapproved_refund_panics() {
     let s = setup();
     let customer = Address::generate(&s.env);
@@ -1291,7 +1291,7 @@ fn test_get_dispute_window_returns_configured_value() {
 }
 
 #[test]
-#[should_panic(expected = "Dispute window has not elapsed")]
+#[should_panic(expected = "Error(Contract, #2115)")] // DisputeWindowOpen
 fn test_auto_approve_early_panics() {
     let s = setup_with_dispute_window(86_400);
     let customer = Address::generate(&s.env);
@@ -1315,7 +1315,7 @@ fn test_auto_approve_early_panics() {
 }
 
 #[test]
-#[should_panic(expected = "Refund has already been acted on")]
+#[should_panic(expected = "Error(Contract, #2016)")] // RefundAlreadyActedOn
 fn test_auto_approve_after_merchant_approved_panics() {
     let s = setup_with_dispute_window(86_400);
     let customer = Address::generate(&s.env);
@@ -1342,7 +1342,7 @@ fn test_auto_approve_after_merchant_approved_panics() {
 }
 
 #[test]
-#[should_panic(expected = "Refund has already been acted on")]
+#[should_panic(expected = "Error(Contract, #2016)")] // RefundAlreadyActedOn
 fn test_auto_approve_after_merchant_rejected_panics() {
     let s = setup_with_dispute_window(86_400);
     let customer = Address::generate(&s.env);
@@ -97,7 +97,7 @@ fn test_threshold_blocking() {
 }
 
 #[test]
-#[should_panic(expected = "CustomerBlockedForAbuse")]
+#[should_panic(expected = "Error(Contract, #2130)")] // CustomerBlockedForAbuse
 fn test_blocked_customer_cannot_request() {
     let (env, refund_client, payment_client, admin, token_addr, _tc, token_admin) = setup_abuse();
     let customer = Address::generate(&env);
@@ -119,6 +119,29 @@ fn test_blocked_customer_cannot_request() {
     refund_client.request_refund(&customer, &pid, &100, &String::from_str(&env, "bad"), &0);
 }
 
+#[test]
+#[should_panic(expected = "Error(Contract, #2131)")] // BuyerBlockedForFraud
+fn test_buyer_at_fraud_threshold_cannot_request() {
+    let (env, refund_client, payment_client, admin, token_addr, _tc, token_admin) = setup_abuse();
+    let customer = Address::generate(&env);
+    let merchant = Address::generate(&env);
+    refund_client.set_fraud_score_block_threshold(&admin, &2u32);
+
+    // Two rejections reach the fraud threshold before the abuse threshold
+    for _ in 0..2 {
+        let pid = make_payment(&env, &payment_client, &token_admin, &customer, &merchant, &token_addr, 1000);
+        token_admin.mint(&customer, &100);
+        let rid = refund_client.request_refund(
+            &customer, &pid, &100, &String::from_str(&env, "bad"), &0,
+        );
+        refund_client.reject_refund(&admin, &rid, &String::from_str(&env, "no"));
+    }
+    assert!(refund_client.get_fraud_score(&customer) >= 2);
+
+    let pid = make_payment(&env, &payment_client, &token_admin, &customer, &merchant, &token_addr, 1000);
+    refund_client.request_refund(&customer, &pid, &100, &String::from_str(&env, "bad"), &0);
+}
+
 #[test]
 fn test_rapid_submission_detected() {
     let (env, refund_client, payment_client, _admin, token_addr, _tc, token_admin) = setup_abuse();
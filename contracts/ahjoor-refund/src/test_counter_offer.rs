@@ -114,7 +114,7 @@ fn test_counter_offer_expiry_escalates() {
 // Test: duplicate counter-offer rejected
 // ---------------------------------------------------------------------------
 #[test]
-#[should_panic(expected = "Refund is not in Requested state")]
+#[should_panic(expected = "Error(Contract, #2013)")] // RefundNotRequested
 fn test_duplicate_counter_offer_rejected() {
     let (_env, refund_client, _, _admin, _customer, merchant, _token_addr, _token_client, _) = setup_counter_offer();
     let refund_id = 0u32;
@@ -128,7 +128,7 @@ fn test_duplicate_counter_offer_rejected() {
 // Test: counter-offer amount cannot exceed original
 // ---------------------------------------------------------------------------
 #[test]
-#[should_panic(expected = "Counter-offer cannot exceed original refund amount")]
+#[should_panic(expected = "Error(Contract, #2101)")] // CounterOfferExceedsRefund
 fn test_counter_offer_exceeds_original() {
     let (_env, refund_client, _, _admin, _customer, merchant, _token_addr, _token_client, _) = setup_counter_offer();
     let refund_id = 0u32;
@@ -62,7 +62,7 @@ fn test_register_cross_contract_refund_success() {
 }
 
 #[test]
-#[should_panic(expected = "UnauthorisedOriginContract")]
+#[should_panic(expected = "Error(Contract, #2214)")] // UnauthorisedOriginContract
 fn test_non_whitelisted_caller_rejected() {
     let (env, refund_client, _payment_client, _admin, token_addr, _token_admin) = setup_cc();
 
@@ -136,7 +136,7 @@ fn test_add_and_remove_origin_contract() {
 }
 
 #[test]
-#[should_panic(expected = "ContractAlreadyWhitelisted")]
+#[should_panic(expected = "Error(Contract, #2212)")] // ContractAlreadyWhitelisted
 fn test_cannot_whitelist_same_contract_twice() {
     let (env, refund_client, _payment_client, admin, _token_addr, _token_admin) = setup_cc();
 
@@ -144,3 +144,34 @@ fn test_cannot_whitelist_same_contract_twice() {
     refund_client.add_refund_origin_contract(&admin, &origin);
     refund_client.add_refund_origin_contract(&admin, &origin);
 }
+
+#[test]
+fn test_shared_payment_schema_decodes_live_payment() {
+    let (env, _refund_client, payment_client, _admin, token_addr, token_admin) = setup_cc();
+
+    let customer = Address::generate(&env);
+    let merchant = Address::generate(&env);
+    token_admin.mint(&customer, &1_000);
+    TokenClient::new(&env, &token_addr).approve(
+        &customer,
+        &payment_client.address,
+        &1_000,
+        &(env.ledger().sequence() + 1_000),
+    );
+
+    // `Authorized` (5) is the status the old local copy misread as `ScheduledPending`.
+    let capture_deadline = env.ledger().sequence() as u64 + 100;
+    let pid = payment_client.authorize_payment(
+        &merchant,
+        &customer,
+        &token_addr,
+        &400,
+        &capture_deadline,
+    );
+
+    let decoded = payment_contract::PaymentContractClient::new(&env, &payment_client.address)
+        .get_payment(&pid);
+    assert_eq!(decoded.status, payment_contract::PaymentStatus::Authorized);
+    assert_eq!(decoded, payment_client.get_payment(&pid));
+    assert_eq!(decoded.capture_deadline, capture_deadline);
+}
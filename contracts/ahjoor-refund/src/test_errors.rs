@@ -0,0 +1,52 @@
+#![cfg(test)]
+use super::*;
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+fn setup_errors<'a>() -> (Env, AhjoorRefundContractClient<'a>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AhjoorRefundContract, ());
+    let client = AhjoorRefundContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let payment_contract = Address::generate(&env);
+    client.initialize(&admin, &payment_contract, &86_400u64, &None);
+
+    (env, client, admin)
+}
+
+fn contract_error(code: impl Into<soroban_sdk::Error>) -> soroban_sdk::Error {
+    code.into()
+}
+
+#[test]
+fn test_not_found_and_config_errors_are_distinct() {
+    let (env, client, admin) = setup_errors();
+    let merchant = Address::generate(&env);
+
+    assert_eq!(
+        client.try_get_refund(&99),
+        Err(Ok(contract_error(Error::RefundNotFound)))
+    );
+    assert_eq!(
+        client.try_update_refund_fee(&admin, &201),
+        Err(Ok(contract_error(Error::FeeTooHigh)))
+    );
+    assert_eq!(
+        client.try_remove_delegate(&merchant, &admin),
+        Err(Ok(contract_error(Error::DelegateNotFound)))
+    );
+    assert_eq!(
+        client.try_resume_contract(&admin),
+        Err(Ok(contract_error(Error::ContractNotPaused)))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2001)")] // NotInitialized
+fn test_uninitialized_contract_panics_with_typed_error() {
+    let env = Env::default();
+    let contract_id = env.register(AhjoorRefundContract, ());
+    AhjoorRefundContractClient::new(&env, &contract_id).get_admin();
+}
@@ -79,7 +79,7 @@ fn test_primary_resolution_before_deadline() {
 }
 
 #[test]
-#[should_panic(expected = "PrimaryDeadlineNotPassed")]
+#[should_panic(expected = "Error(Contract, #2123)")] // PrimaryDeadlineNotPassed
 fn test_escalation_blocked_before_primary_deadline() {
     let (env, refund_client, payment_client, _admin, _senior, token_addr, _tc, token_admin) =
         setup_escalation();
@@ -208,7 +208,7 @@ fn test_auto_approve_on_senior_miss() {
 }
 
 #[test]
-#[should_panic(expected = "SeniorDeadlineNotPassed")]
+#[should_panic(expected = "Error(Contract, #2127)")] // SeniorDeadlineNotPassed
 fn test_auto_approve_before_senior_deadline() {
     let (env, refund_client, payment_client, _admin, _senior, token_addr, _tc, token_admin) =
         setup_escalation();
@@ -230,7 +230,7 @@ fn test_auto_approve_before_senior_deadline() {
 }
 
 #[test]
-#[should_panic(expected = "UnauthorizedSeniorArbiter")]
+#[should_panic(expected = "Error(Contract, #2125)")] // UnauthorizedSeniorArbiter
 fn test_non_senior_cannot_resolve_escalated() {
     let (env, refund_client, payment_client, _admin, _senior, token_addr, _tc, token_admin) =
         setup_escalation();
@@ -47,7 +47,7 @@ fn test_set_merchant_response_window() {
 }
 
 #[test]
-#[should_panic(expected = "window_ledgers must be positive")]
+#[should_panic(expected = "Error(Contract, #2029)")] // InvalidDuration
 fn test_set_zero_window_rejected() {
     let env = Env::default();
     env.mock_all_auths();
@@ -0,0 +1,132 @@
+#![cfg(test)]
+use super::*;
+use ahjoor_payments::{AhjoorPaymentsContract, AhjoorPaymentsContractClient};
+use soroban_sdk::token::Client as TokenClient;
+use soroban_sdk::token::StellarAssetClient as TokenAdminClient;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env, String,
+};
+
+const AUTO_REJECT_WINDOW: u64 = 2_592_000;
+
+struct KeeperSetup<'a> {
+    env: Env,
+    client: AhjoorRefundContractClient<'a>,
+    admin: Address,
+    customer: Address,
+    keeper: Address,
+    token_client: TokenClient<'a>,
+}
+
+/// Bounty 10 per item, at most 10 per call and 15 per ledger, pool of 100.
+/// Two refunds of 500 each are requested against completed payments.
+fn setup_keeper<'a>() -> KeeperSetup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let payment_id = env.register(AhjoorPaymentsContract, ());
+    let payment_client = AhjoorPaymentsContractClient::new(&env, &payment_id);
+    let refund_contract = env.register(AhjoorRefundContract, ());
+    let client = AhjoorRefundContractClient::new(&env, &refund_contract);
+
+    let admin = Address::generate(&env);
+    let customer = Address::generate(&env);
+    let merchant = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    let token_addr = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let token_client = TokenClient::new(&env, &token_addr);
+    let token_admin = TokenAdminClient::new(&env, &token_addr);
+
+    payment_client.initialize(&admin, &admin, &0u32);
+    payment_client.set_min_collateral(&0i128);
+    payment_client.approve_merchant(&merchant);
+    client.initialize(&admin, &payment_id, &86_400u64, &None);
+
+    token_admin.mint(&customer, &2_000);
+    token_admin.mint(&refund_contract, &1_000);
+    token_admin.mint(&admin, &100);
+    for _ in 0..2 {
+        let pid = payment_client.create_payment(
+            &customer,
+            &merchant,
+            &500,
+            &token_addr,
+            &None,
+            &None,
+            &None,
+        );
+        payment_client.complete_payment(&pid);
+        client.request_refund(
+            &customer,
+            &pid,
+            &500,
+            &String::from_str(&env, "bad product"),
+            &0u32,
+        );
+    }
+
+    client.set_keeper_config(&admin, &token_addr, &10, &10, &15);
+    client.fund_keeper_pool(&admin, &100);
+
+    KeeperSetup {
+        env,
+        client,
+        admin,
+        customer,
+        keeper,
+        token_client,
+    }
+}
+
+#[test]
+fn test_auto_approval_pays_keeper_once() {
+    let s = setup_keeper();
+
+    s.client.trigger_auto_approval(&s.keeper, &0);
+    assert_eq!(s.client.get_refund(&0).status, RefundStatus::Processed);
+    assert_eq!(s.token_client.balance(&s.keeper), 10);
+
+    s.env.ledger().with_mut(|l| l.sequence_number += 1);
+    assert_eq!(
+        s.client.try_trigger_auto_approval(&s.keeper, &0),
+        Err(Ok(soroban_sdk::Error::from(Error::RefundNotRequested)))
+    );
+    assert_eq!(s.token_client.balance(&s.keeper), 10);
+    assert_eq!(s.client.get_keeper_pool().balance, 90);
+}
+
+#[test]
+fn test_stale_refund_rejection_pays_keeper_within_ledger_cap() {
+    let s = setup_keeper();
+    s.env
+        .ledger()
+        .with_mut(|l| l.timestamp += AUTO_REJECT_WINDOW);
+    let customer_before = s.token_client.balance(&s.customer);
+
+    s.client.auto_reject_stale_refund(&s.keeper, &0);
+    s.client.auto_reject_stale_refund(&s.keeper, &1);
+
+    // Both refunds returned to the customer; 10 + 10 is capped at 15 per ledger
+    assert_eq!(s.client.get_refund(&1).status, RefundStatus::Rejected);
+    assert_eq!(s.token_client.balance(&s.customer), customer_before + 1_000);
+    assert_eq!(s.token_client.balance(&s.keeper), 15);
+    assert_eq!(s.client.get_keeper_pool().paid_this_ledger, 15);
+}
+
+#[test]
+fn test_keeper_config_validation() {
+    let s = setup_keeper();
+    let token = s.client.get_keeper_config().unwrap().token;
+    assert_eq!(
+        s.client
+            .try_set_keeper_config(&s.admin, &token, &10, &0, &15),
+        Err(Ok(soroban_sdk::Error::from(Error::InvalidKeeperConfig)))
+    );
+    assert_eq!(
+        s.client.try_withdraw_keeper_pool(&s.admin, &101),
+        Err(Ok(soroban_sdk::Error::from(Error::InsufficientKeeperPool)))
+    );
+}
@@ -49,7 +49,7 @@ fn test_deposit_and_withdraw_reserve() {
 }
 
 #[test]
-#[should_panic(expected = "WithdrawalWouldBreachMinimum")]
+#[should_panic(expected = "Error(Contract, #2202)")] // WithdrawalBreachesMinimum
 fn test_withdraw_below_minimum_rejected() {
     let env = Env::default();
     env.mock_all_auths();
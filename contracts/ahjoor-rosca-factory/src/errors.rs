@@ -0,0 +1,14 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Error {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    GroupNotFound = 4,
+    /// Contribution amount must be positive.
+    InvalidContributionAmount = 5,
+    /// `page_size` must be between 1 and `MAX_PAGE_SIZE`.
+    InvalidPageSize = 6,
+}
@@ -0,0 +1,60 @@
+use ahjoor_types::rosca::GroupStatus;
+use soroban_sdk::{contractevent, Address, BytesN, Env};
+
+/// Event: New rosca group deployed and registered
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct GroupDeployed {
+    pub group_id: u32,
+    pub address: Address,
+    pub admin: Address,
+    pub token: Address,
+    pub contribution_amount: i128,
+}
+
+/// Event: Registry entry refreshed from the deployed group
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct GroupSynced {
+    pub group_id: u32,
+    pub status: GroupStatus,
+    pub member_count: u32,
+}
+
+/// Event: Wasm hash used for new deployments changed
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct RoscaWasmUpdated {
+    pub wasm_hash: BytesN<32>,
+}
+
+pub fn emit_group_deployed(
+    e: &Env,
+    group_id: u32,
+    address: Address,
+    admin: Address,
+    token: Address,
+    contribution_amount: i128,
+) {
+    GroupDeployed {
+        group_id,
+        address,
+        admin,
+        token,
+        contribution_amount,
+    }
+    .publish(e);
+}
+
+pub fn emit_group_synced(e: &Env, group_id: u32, status: GroupStatus, member_count: u32) {
+    GroupSynced {
+        group_id,
+        status,
+        member_count,
+    }
+    .publish(e);
+}
+
+pub fn emit_rosca_wasm_updated(e: &Env, wasm_hash: BytesN<32>) {
+    RoscaWasmUpdated { wasm_hash }.publish(e);
+}
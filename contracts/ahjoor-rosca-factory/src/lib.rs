@@ -0,0 +1,425 @@
+#![no_std]
+//! Deploys `ahjoor-rosca` groups from an uploaded wasm hash and keeps an
+//! on-chain directory of them, queryable by member and by token.
+use ahjoor_types::rosca::{GroupStatus, RoscaConfig, RoscaGroupClient};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, panic_with_error, Address, BytesN, Env, Vec,
+};
+
+// Instance storage: admin, wasm hash and the group counter
+const INSTANCE_LIFETIME_THRESHOLD: u32 = 100_000;
+const INSTANCE_BUMP_AMOUNT: u32 = 120_000;
+
+// Persistent storage: group records and the paged member/token indexes
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = 100_000;
+const PERSISTENT_BUMP_AMOUNT: u32 = 120_000;
+
+/// Maximum `page_size` accepted by the paginated group queries.
+pub const MAX_PAGE_SIZE: u32 = 50;
+
+/// Group ids held by one storage entry of the member and token indexes.
+pub const INDEX_PAGE_SIZE: u32 = 100;
+
+mod errors;
+mod events;
+
+pub use errors::Error;
+
+/// Registry entry for a deployed group.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GroupRecord {
+    pub group_id: u32,
+    /// Address of the deployed rosca contract.
+    pub address: Address,
+    pub admin: Address,
+    pub token: Address,
+    pub contribution_amount: i128,
+    /// Status as of the last `create_group` / `sync_group`.
+    pub status: GroupStatus,
+    /// Member count as of the last `create_group` / `sync_group`.
+    pub member_count: u32,
+    pub created_at: u64,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    Admin,
+    RoscaWasmHash,
+    GroupCounter,
+    /// group_id → GroupRecord
+    Group(u32),
+    /// rosca contract address → group_id
+    GroupByAddress(Address),
+    /// group_id → members as of the last sync
+    GroupMembers(u32),
+    /// member → number of groups they belong to
+    MemberGroupCount(Address),
+    /// (member, page) → up to `INDEX_PAGE_SIZE` group ids
+    MemberGroupPage(Address, u32),
+    /// (member, group_id) → position of the group in the member's index
+    MemberGroupPos(Address, u32),
+    /// token → number of groups contributing in that token
+    TokenGroupCount(Address),
+    /// (token, page) → up to `INDEX_PAGE_SIZE` group ids
+    TokenGroupPage(Address, u32),
+}
+
+/// A paged group-id index: a count entry plus one entry per
+/// `INDEX_PAGE_SIZE` ids, so no single entry grows with the index.
+enum GroupIndex {
+    Member(Address),
+    Token(Address),
+}
+
+impl GroupIndex {
+    fn count_key(&self) -> DataKey {
+        match self {
+            GroupIndex::Member(member) => DataKey::MemberGroupCount(member.clone()),
+            GroupIndex::Token(token) => DataKey::TokenGroupCount(token.clone()),
+        }
+    }
+
+    fn page_key(&self, page: u32) -> DataKey {
+        match self {
+            GroupIndex::Member(member) => DataKey::MemberGroupPage(member.clone(), page),
+            GroupIndex::Token(token) => DataKey::TokenGroupPage(token.clone(), page),
+        }
+    }
+}
+
+#[contract]
+pub struct AhjoorRoscaFactory;
+
+#[contractimpl]
+impl AhjoorRoscaFactory {
+    /// One-time setup with the admin and the uploaded rosca wasm hash.
+    pub fn initialize(env: Env, admin: Address, rosca_wasm_hash: BytesN<32>) {
+        admin.require_auth();
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic_with_error!(&env, Error::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::RoscaWasmHash, &rosca_wasm_hash);
+        env.storage().instance().set(&DataKey::GroupCounter, &0u32);
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Admin points new deployments at a different rosca wasm. Existing
+    /// groups are unaffected.
+    pub fn set_rosca_wasm_hash(env: Env, admin: Address, rosca_wasm_hash: BytesN<32>) {
+        Self::require_admin(&env, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::RoscaWasmHash, &rosca_wasm_hash);
+        events::emit_rosca_wasm_updated(&env, rosca_wasm_hash);
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    pub fn get_rosca_wasm_hash(env: Env) -> BytesN<32> {
+        env.storage()
+            .instance()
+            .get(&DataKey::RoscaWasmHash)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized))
+    }
+
+    /// Deploy a new rosca group, initialise it with the given parameters and
+    /// register it under the members the deployed group reports. The
+    /// deployment salt is derived from the group id, so the address is known
+    /// in advance via `predict_group_address`.
+    /// Returns the new group id.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_group(
+        env: Env,
+        admin: Address,
+        members: Vec<Address>,
+        contribution_amount: i128,
+        token: Address,
+        round_duration: u64,
+        config: RoscaConfig,
+        start_at: Option<u64>,
+    ) -> u32 {
+        admin.require_auth();
+        if contribution_amount <= 0 {
+            panic_with_error!(&env, Error::InvalidContributionAmount);
+        }
+        let wasm_hash = Self::get_rosca_wasm_hash(env.clone());
+
+        let group_id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::GroupCounter)
+            .unwrap_or(0)
+            + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::GroupCounter, &group_id);
+
+        let address = env
+            .deployer()
+            .with_current_contract(Self::group_salt(&env, group_id))
+            .deploy_v2(wasm_hash, ());
+        let client = RoscaGroupClient::new(&env, &address);
+        client.init(
+            &admin,
+            &members,
+            &contribution_amount,
+            &token,
+            &round_duration,
+            &config,
+            &start_at,
+        );
+        // Index the members the group itself reports, not the caller's list.
+        let members = client.get_members();
+
+        let record = GroupRecord {
+            group_id,
+            address: address.clone(),
+            admin: admin.clone(),
+            token: token.clone(),
+            contribution_amount,
+            status: GroupStatus::Active,
+            member_count: members.len(),
+            created_at: env.ledger().timestamp(),
+        };
+        Self::save_record(&env, &record);
+        Self::set_persistent(&env, &DataKey::GroupByAddress(address.clone()), &group_id);
+        Self::set_persistent(&env, &DataKey::GroupMembers(group_id), &members);
+        for member in members.iter() {
+            Self::member_index_add(&env, &member, group_id);
+        }
+        Self::index_push(&env, &GroupIndex::Token(token.clone()), group_id);
+
+        events::emit_group_deployed(&env, group_id, address, admin, token, contribution_amount);
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        group_id
+    }
+
+    /// Refresh a group's registry entry from the deployed contract: status,
+    /// member count and the member index. Callable by anyone, e.g. after
+    /// members join or leave.
+    pub fn sync_group(env: Env, group_id: u32) -> GroupRecord {
+        let mut record = Self::get_group(env.clone(), group_id);
+        let client = RoscaGroupClient::new(&env, &record.address);
+        let members = client.get_members();
+
+        let previous: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::GroupMembers(group_id))
+            .unwrap_or(Vec::new(&env));
+        for member in previous.iter() {
+            if !members.contains(&member) {
+                Self::member_index_remove(&env, &member, group_id);
+            }
+        }
+        for member in members.iter() {
+            if !previous.contains(&member) {
+                Self::member_index_add(&env, &member, group_id);
+            }
+        }
+        Self::set_persistent(&env, &DataKey::GroupMembers(group_id), &members);
+
+        record.status = client.get_group_status();
+        record.member_count = members.len();
+        Self::save_record(&env, &record);
+
+        events::emit_group_synced(&env, group_id, record.status, record.member_count);
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        record
+    }
+
+    /// Address the group with `group_id` is (or will be) deployed at.
+    pub fn predict_group_address(env: Env, group_id: u32) -> Address {
+        env.deployer()
+            .with_current_contract(Self::group_salt(&env, group_id))
+            .deployed_address()
+    }
+
+    pub fn get_group(env: Env, group_id: u32) -> GroupRecord {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Group(group_id))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::GroupNotFound))
+    }
+
+    pub fn get_group_by_address(env: Env, address: Address) -> Option<GroupRecord> {
+        let group_id: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::GroupByAddress(address))?;
+        env.storage().persistent().get(&DataKey::Group(group_id))
+    }
+
+    pub fn get_group_count(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::GroupCounter)
+            .unwrap_or(0)
+    }
+
+    /// Groups `member` belongs to (as of each group's last sync). Oldest
+    /// first until the member leaves a group; the latest group then takes the
+    /// place of the one left.
+    pub fn get_groups_by_member(
+        env: Env,
+        member: Address,
+        page: u32,
+        page_size: u32,
+    ) -> Vec<GroupRecord> {
+        Self::page_records(&env, &GroupIndex::Member(member), page, page_size)
+    }
+
+    /// Groups contributing in `token`, oldest first.
+    pub fn get_groups_by_token(
+        env: Env,
+        token: Address,
+        page: u32,
+        page_size: u32,
+    ) -> Vec<GroupRecord> {
+        Self::page_records(&env, &GroupIndex::Token(token), page, page_size)
+    }
+
+    // --- Internal Helpers ---
+
+    fn require_admin(env: &Env, admin: &Address) {
+        admin.require_auth();
+        let stored: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(env, Error::NotInitialized));
+        if stored != *admin {
+            panic_with_error!(env, Error::Unauthorized);
+        }
+    }
+
+    fn group_salt(env: &Env, group_id: u32) -> BytesN<32> {
+        let mut salt = [0u8; 32];
+        salt[28..].copy_from_slice(&group_id.to_be_bytes());
+        BytesN::from_array(env, &salt)
+    }
+
+    fn save_record(env: &Env, record: &GroupRecord) {
+        Self::set_persistent(env, &DataKey::Group(record.group_id), record);
+    }
+
+    fn set_persistent<V: soroban_sdk::IntoVal<Env, soroban_sdk::Val>>(
+        env: &Env,
+        key: &DataKey,
+        value: &V,
+    ) {
+        env.storage().persistent().set(key, value);
+        env.storage().persistent().extend_ttl(
+            key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn index_len(env: &Env, index: &GroupIndex) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&index.count_key())
+            .unwrap_or(0)
+    }
+
+    fn index_page(env: &Env, index: &GroupIndex, page: u32) -> Vec<u32> {
+        env.storage()
+            .persistent()
+            .get(&index.page_key(page))
+            .unwrap_or(Vec::new(env))
+    }
+
+    /// Append `group_id` to `index`, returning its position.
+    fn index_push(env: &Env, index: &GroupIndex, group_id: u32) -> u32 {
+        let len = Self::index_len(env, index);
+        let mut ids = Self::index_page(env, index, len / INDEX_PAGE_SIZE);
+        ids.push_back(group_id);
+        Self::set_persistent(env, &index.page_key(len / INDEX_PAGE_SIZE), &ids);
+        Self::set_persistent(env, &index.count_key(), &(len + 1));
+        len
+    }
+
+    fn member_index_add(env: &Env, member: &Address, group_id: u32) {
+        let pos_key = DataKey::MemberGroupPos(member.clone(), group_id);
+        if env.storage().persistent().has(&pos_key) {
+            return;
+        }
+        let pos = Self::index_push(env, &GroupIndex::Member(member.clone()), group_id);
+        Self::set_persistent(env, &pos_key, &pos);
+    }
+
+    /// Remove `group_id` from the member's index by moving the last entry
+    /// into its slot, keeping every page full.
+    fn member_index_remove(env: &Env, member: &Address, group_id: u32) {
+        let pos_key = DataKey::MemberGroupPos(member.clone(), group_id);
+        let Some(pos) = env.storage().persistent().get::<_, u32>(&pos_key) else {
+            return;
+        };
+        env.storage().persistent().remove(&pos_key);
+
+        let index = GroupIndex::Member(member.clone());
+        let last = Self::index_len(env, &index) - 1;
+        let last_page = last / INDEX_PAGE_SIZE;
+        let mut tail = Self::index_page(env, &index, last_page);
+        let moved = tail.pop_back().unwrap();
+        if pos != last {
+            if pos / INDEX_PAGE_SIZE == last_page {
+                tail.set(pos % INDEX_PAGE_SIZE, moved);
+            } else {
+                let mut ids = Self::index_page(env, &index, pos / INDEX_PAGE_SIZE);
+                ids.set(pos % INDEX_PAGE_SIZE, moved);
+                Self::set_persistent(env, &index.page_key(pos / INDEX_PAGE_SIZE), &ids);
+            }
+            Self::set_persistent(env, &DataKey::MemberGroupPos(member.clone(), moved), &pos);
+        }
+        if tail.is_empty() {
+            env.storage()
+                .persistent()
+                .remove(&index.page_key(last_page));
+        } else {
+            Self::set_persistent(env, &index.page_key(last_page), &tail);
+        }
+        Self::set_persistent(env, &index.count_key(), &last);
+    }
+
+    fn page_records(env: &Env, index: &GroupIndex, page: u32, page_size: u32) -> Vec<GroupRecord> {
+        if page_size == 0 || page_size > MAX_PAGE_SIZE {
+            panic_with_error!(env, Error::InvalidPageSize);
+        }
+        let len = Self::index_len(env, index);
+        let start = page.saturating_mul(page_size).min(len);
+        let end = start.saturating_add(page_size).min(len);
+        let mut records = Vec::new(env);
+        let mut ids = Vec::new(env);
+        for i in start..end {
+            if i == start || i % INDEX_PAGE_SIZE == 0 {
+                ids = Self::index_page(env, index, i / INDEX_PAGE_SIZE);
+            }
+            let group_id = ids.get(i % INDEX_PAGE_SIZE).unwrap();
+            if let Some(record) = env.storage().persistent().get(&DataKey::Group(group_id)) {
+                records.push_back(record);
+            }
+        }
+        records
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,256 @@
+#![cfg(test)]
+//! Deploys the real `ahjoor-rosca` contract built into
+//! `target/wasm32v1-none/release/ahjoor_rosca.wasm`; build it first with
+//! `make build` (or `stellar contract build --package ahjoor-rosca`).
+use super::*;
+use ahjoor_types::rosca::{PayoutStrategy, VotingMode};
+use soroban_sdk::{testutils::Address as _, token, vec, Address, BytesN, Env, Vec};
+
+#[allow(clippy::too_many_arguments)]
+mod rosca {
+    soroban_sdk::contractimport!(file = "../../target/wasm32v1-none/release/ahjoor_rosca.wasm");
+}
+
+struct FactorySetup<'a> {
+    env: Env,
+    client: AhjoorRoscaFactoryClient<'a>,
+    admin: Address,
+    token: Address,
+}
+
+fn setup<'a>() -> FactorySetup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AhjoorRoscaFactory, ());
+    let client = AhjoorRoscaFactoryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+
+    // Uploading the rosca wasm is a transaction of its own and alone exceeds
+    // the default budget; the factory calls after it run under the default
+    // (network-sized) limits.
+    env.cost_estimate().budget().reset_unlimited();
+    let wasm_hash = env.deployer().upload_contract_wasm(rosca::WASM);
+    env.cost_estimate().budget().reset_default();
+    client.initialize(&admin, &wasm_hash);
+
+    FactorySetup {
+        env,
+        client,
+        admin,
+        token,
+    }
+}
+
+fn config() -> RoscaConfig {
+    RoscaConfig {
+        strategy: PayoutStrategy::RoundRobin,
+        custom_order: None,
+        penalty_amount: 0,
+        exit_penalty_bps: 0,
+        collective_goal: None,
+        member_goals: None,
+        fee_bps: 0,
+        fee_recipient: None,
+        max_defaults: 3,
+        grace_period_ledgers: 0,
+        use_timestamp_schedule: false,
+        round_duration_seconds: 0,
+        max_members: None,
+        skip_fee: 0,
+        max_skips_per_cycle: 0,
+        voting_mode: VotingMode::Equal,
+        late_fee_bps: 0,
+        grace_period_seconds: 0,
+        auction_enabled: false,
+        auction_window_ledgers: 0,
+        randomize_payout_order: false,
+        reserve_enabled: false,
+        reserve_contribution_bps: 0,
+    }
+}
+
+fn create_group(s: &FactorySetup, members: &Vec<Address>, token: &Address) -> u32 {
+    s.client
+        .create_group(&s.admin, members, &100, token, &3600, &config(), &None)
+}
+
+#[test]
+fn test_create_group_deploys_initialised_instance_at_predicted_address() {
+    let s = setup();
+    let members = vec![&s.env, Address::generate(&s.env), Address::generate(&s.env)];
+
+    let predicted = s.client.predict_group_address(&1);
+    s.env.cost_estimate().budget().reset_default();
+    let group_id = create_group(&s, &members, &s.token);
+    assert_eq!(group_id, 1);
+    // Deploying and initialising the group stays well inside one transaction
+    let budget = s.env.cost_estimate().budget();
+    assert!(budget.cpu_instruction_cost() < 25_000_000);
+    assert!(budget.memory_bytes_cost() < 15 * 1024 * 1024);
+
+    let record = s.client.get_group(&group_id);
+    assert_eq!(record.address, predicted);
+    assert_eq!(record.admin, s.admin);
+    assert_eq!(record.token, s.token);
+    assert_eq!(record.contribution_amount, 100);
+    assert_eq!(record.member_count, 2);
+    assert_eq!(record.status, GroupStatus::Active);
+    assert_eq!(
+        s.client.get_group_by_address(&predicted),
+        Some(record.clone())
+    );
+
+    // The deployed instance was initialised with the group admin
+    let group = rosca::Client::new(&s.env, &record.address);
+    assert_eq!(group.get_admin(), s.admin);
+    assert_eq!(group.get_members(), members);
+}
+
+#[test]
+fn test_each_group_gets_its_own_deployment() {
+    let s = setup();
+    let members = vec![&s.env, Address::generate(&s.env)];
+
+    let first = create_group(&s, &members, &s.token);
+    let second = create_group(&s, &members, &s.token);
+
+    assert_ne!(
+        s.client.get_group(&first).address,
+        s.client.get_group(&second).address
+    );
+    assert_eq!(s.client.get_group_count(), 2);
+}
+
+#[test]
+fn test_groups_by_member_and_token_are_paginated() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+    let other_token = Address::generate(&s.env);
+
+    for _ in 0..3 {
+        create_group(&s, &vec![&s.env, alice.clone()], &s.token);
+    }
+    create_group(&s, &vec![&s.env, alice.clone()], &other_token);
+    create_group(&s, &vec![&s.env, Address::generate(&s.env)], &s.token);
+
+    let page0 = s.client.get_groups_by_member(&alice, &0, &3);
+    assert_eq!(page0.len(), 3);
+    assert_eq!(page0.get(0).unwrap().group_id, 1);
+    let page1 = s.client.get_groups_by_member(&alice, &1, &3);
+    assert_eq!(page1.len(), 1);
+    assert_eq!(page1.get(0).unwrap().group_id, 4);
+
+    let by_token = s.client.get_groups_by_token(&s.token, &0, &10);
+    assert_eq!(by_token.len(), 4);
+    assert_eq!(by_token.get(3).unwrap().group_id, 5);
+    assert_eq!(s.client.get_groups_by_token(&other_token, &0, &10).len(), 1);
+}
+
+#[test]
+fn test_member_index_spans_pages_and_fills_removed_slots() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+    let index = GroupIndex::Member(alice.clone());
+
+    s.env.as_contract(&s.client.address, || {
+        for group_id in 1..=INDEX_PAGE_SIZE + 20 {
+            AhjoorRoscaFactory::member_index_add(&s.env, &alice, group_id);
+        }
+        // Re-adding an indexed group is a no-op
+        AhjoorRoscaFactory::member_index_add(&s.env, &alice, 1);
+        assert_eq!(
+            AhjoorRoscaFactory::index_len(&s.env, &index),
+            INDEX_PAGE_SIZE + 20
+        );
+        assert_eq!(AhjoorRoscaFactory::index_page(&s.env, &index, 1).len(), 20);
+
+        // The last group moves into the first page's freed slot
+        AhjoorRoscaFactory::member_index_remove(&s.env, &alice, 2);
+        let first = AhjoorRoscaFactory::index_page(&s.env, &index, 0);
+        assert_eq!(first.get(1).unwrap(), INDEX_PAGE_SIZE + 20);
+        assert_eq!(AhjoorRoscaFactory::index_page(&s.env, &index, 1).len(), 19);
+
+        // Emptying the last page drops its entry
+        for group_id in INDEX_PAGE_SIZE + 1..INDEX_PAGE_SIZE + 20 {
+            AhjoorRoscaFactory::member_index_remove(&s.env, &alice, group_id);
+        }
+        assert_eq!(
+            AhjoorRoscaFactory::index_len(&s.env, &index),
+            INDEX_PAGE_SIZE
+        );
+        assert!(!s.env.storage().persistent().has(&index.page_key(1)));
+
+        // Removing the moved group clears its tracked position as well
+        AhjoorRoscaFactory::member_index_remove(&s.env, &alice, INDEX_PAGE_SIZE + 20);
+        AhjoorRoscaFactory::member_index_remove(&s.env, &alice, INDEX_PAGE_SIZE + 20);
+        assert_eq!(
+            AhjoorRoscaFactory::index_len(&s.env, &index),
+            INDEX_PAGE_SIZE - 1
+        );
+    });
+}
+
+#[test]
+fn test_sync_group_refreshes_members_and_status() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+    let bob = Address::generate(&s.env);
+    let carol = Address::generate(&s.env);
+    let group_id = create_group(&s, &vec![&s.env, alice.clone(), bob.clone()], &s.token);
+    let address = s.client.get_group(&group_id).address;
+
+    // Bob leaves and Carol joins, then the group admin dissolves the group
+    let group = rosca::Client::new(&s.env, &address);
+    group.remove_member(&bob);
+    group.add_member(&carol);
+    token::StellarAssetClient::new(&s.env, &s.token).mint(&address, &100);
+    group.dissolve_group(&s.admin, &BytesN::from_array(&s.env, &[0; 32]));
+
+    let record = s.client.sync_group(&group_id);
+    assert_eq!(record.status, GroupStatus::Dissolved);
+    assert_eq!(record.member_count, 2);
+    assert_eq!(s.client.get_groups_by_member(&bob, &0, &10).len(), 0);
+    assert_eq!(s.client.get_groups_by_member(&carol, &0, &10).len(), 1);
+    assert_eq!(s.client.get_groups_by_member(&alice, &0, &10).len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "Error(Auth, InvalidAction)")]
+fn test_initialize_requires_admin_auth() {
+    let env = Env::default();
+    let client = AhjoorRoscaFactoryClient::new(&env, &env.register(AhjoorRoscaFactory, ()));
+
+    client.initialize(
+        &Address::generate(&env),
+        &BytesN::from_array(&env, &[0; 32]),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #3)")] // Unauthorized
+fn test_wasm_hash_update_requires_admin() {
+    let s = setup();
+    let hash = s.client.get_rosca_wasm_hash();
+    let intruder = Address::generate(&s.env);
+
+    s.client.set_rosca_wasm_hash(&intruder, &hash);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")] // InvalidPageSize
+fn test_page_size_is_bounded() {
+    let s = setup();
+    s.client
+        .get_groups_by_token(&s.token, &0, &(MAX_PAGE_SIZE + 1));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #4)")] // GroupNotFound
+fn test_unknown_group_panics() {
+    let s = setup();
+    s.client.get_group(&7);
+}
@@ -0,0 +1,290 @@
+use crate::{
+    errors::ExtError2, events, AscaConfig, AscaLoan, AscaLoanStatus, DataKey, DataKey4,
+    PERSISTENT_BUMP_AMOUNT, PERSISTENT_LIFETIME_THRESHOLD,
+};
+use soroban_sdk::{panic_with_error, token, Address, Env, Map, Vec};
+
+/// Panics if the group runs in ASCA mode, where rotating contributions and
+/// payouts are replaced by savings, loans and a share-out.
+pub(crate) fn check_not_asca(env: &Env) {
+    if env.storage().instance().has(&DataKey4::AscaConfig) {
+        panic_with_error!(env, ExtError2::AscaModeActive);
+    }
+}
+
+pub(crate) fn load_config(env: &Env) -> AscaConfig {
+    env.storage()
+        .instance()
+        .get(&DataKey4::AscaConfig)
+        .unwrap_or_else(|| panic_with_error!(env, ExtError2::AscaNotEnabled))
+}
+
+pub(crate) fn load_loan(env: &Env, loan_id: u32) -> AscaLoan {
+    env.storage()
+        .persistent()
+        .get(&DataKey4::AscaLoan(loan_id))
+        .unwrap_or_else(|| panic_with_error!(env, ExtError2::AscaLoanNotFound))
+}
+
+fn save_loan(env: &Env, loan: &AscaLoan) {
+    let key = DataKey4::AscaLoan(loan.loan_id);
+    env.storage().persistent().set(&key, loan);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+fn active_loans(env: &Env) -> Map<Address, u32> {
+    env.storage()
+        .instance()
+        .get(&DataKey4::ActiveAscaLoans)
+        .unwrap_or(Map::new(env))
+}
+
+pub(crate) fn savings(env: &Env) -> Map<Address, i128> {
+    env.storage()
+        .instance()
+        .get(&DataKey4::AscaSavings)
+        .unwrap_or(Map::new(env))
+}
+
+pub(crate) fn pool(env: &Env) -> i128 {
+    env.storage().instance().get(&DataKey4::AscaPool).unwrap_or(0)
+}
+
+fn set_pool(env: &Env, amount: i128) {
+    env.storage().instance().set(&DataKey4::AscaPool, &amount);
+}
+
+/// Balance still owed on a loan, including penalties.
+pub(crate) fn outstanding(loan: &AscaLoan) -> i128 {
+    loan.total_due + loan.penalties - loan.repaid
+}
+
+/// Validates an ASCA proposal at creation time.
+pub(crate) fn validate_proposal(
+    env: &Env,
+    proposal_type: crate::ProposalType,
+    creator: &Address,
+    target_member: &Address,
+    execution_data: Option<i128>,
+) {
+    match proposal_type {
+        crate::ProposalType::AscaLoan => {
+            load_config(env);
+            if creator != target_member || execution_data.unwrap_or(0) <= 0 {
+                panic_with_error!(env, ExtError2::InvalidAscaLoan);
+            }
+            if active_loans(env).contains_key(creator.clone()) {
+                panic_with_error!(env, ExtError2::AscaLoanOutstanding);
+            }
+        }
+        crate::ProposalType::AscaInterestRate => {
+            load_config(env);
+        }
+        _ => {}
+    }
+}
+
+/// Sets the group interest rate voted through `ProposalType::AscaInterestRate`.
+/// Out-of-range values are ignored, as for the other rule-change proposals.
+pub(crate) fn execute_interest_rate(env: &Env, new_rate: Option<i128>) {
+    if let Some(rate) = new_rate {
+        if (0..=10_000).contains(&rate) {
+            let mut config = load_config(env);
+            config.interest_bps = rate as u32;
+            env.storage().instance().set(&DataKey4::AscaConfig, &config);
+            events::emit_asca_interest_rate_set(env, config.interest_bps);
+        }
+    }
+}
+
+/// Disburses a loan approved through `ProposalType::AscaLoan`.
+pub(crate) fn execute_loan(env: &Env, borrower: &Address, principal: Option<i128>) {
+    let config = load_config(env);
+    let principal = principal.unwrap_or(0);
+    if principal <= 0 {
+        panic_with_error!(env, ExtError2::InvalidAscaLoan);
+    }
+    let mut active = active_loans(env);
+    if active.contains_key(borrower.clone()) {
+        panic_with_error!(env, ExtError2::AscaLoanOutstanding);
+    }
+    let borrower_savings = savings(env).get(borrower.clone()).unwrap_or(0);
+    let max_loan = borrower_savings * config.max_loan_to_savings_bps as i128 / 10_000;
+    let available = pool(env);
+    if principal > max_loan || principal > available {
+        panic_with_error!(env, ExtError2::AscaLoanLimitExceeded);
+    }
+
+    let loan_id: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey4::AscaLoanCounter)
+        .unwrap_or(0);
+    let total_due = principal + principal * config.interest_bps as i128 / 10_000;
+    let loan = AscaLoan {
+        loan_id,
+        borrower: borrower.clone(),
+        principal,
+        interest_bps: config.interest_bps,
+        total_due,
+        penalties: 0,
+        repaid: 0,
+        installments: config.installments,
+        installment_interval: config.installment_interval,
+        disbursed_at: env.ledger().timestamp(),
+        penalised_installments: 0,
+        status: AscaLoanStatus::Active,
+    };
+    save_loan(env, &loan);
+    env.storage()
+        .instance()
+        .set(&DataKey4::AscaLoanCounter, &(loan_id + 1));
+    active.set(borrower.clone(), loan_id);
+    env.storage().instance().set(&DataKey4::ActiveAscaLoans, &active);
+    set_pool(env, available - principal);
+
+    let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+    token::Client::new(env, &token_addr).transfer(
+        &env.current_contract_address(),
+        borrower,
+        &principal,
+    );
+    events::emit_asca_loan_disbursed(env, loan_id, borrower.clone(), principal, total_due);
+}
+
+/// Adds a penalty for every installment that became overdue, unpaid, since
+/// the last check. Each installment is penalised at most once.
+pub(crate) fn apply_overdue(env: &Env, loan: &mut AscaLoan) {
+    if loan.status != AscaLoanStatus::Active {
+        return;
+    }
+    // Installment k is due at disbursed_at + k * installment_interval and
+    // overdue once that time has passed
+    let elapsed = env.ledger().timestamp().saturating_sub(loan.disbursed_at);
+    let fell_due = (elapsed.saturating_sub(1) / loan.installment_interval)
+        .min(loan.installments as u64) as u32;
+    if fell_due <= loan.penalised_installments {
+        return;
+    }
+
+    let installment = loan.total_due / loan.installments as i128;
+    let covered = if installment > 0 {
+        ((loan.repaid - loan.penalties).max(0) / installment) as u32
+    } else {
+        loan.installments
+    };
+    let first_unchecked = loan.penalised_installments.max(covered);
+    if fell_due > first_unchecked {
+        let overdue = fell_due - first_unchecked;
+        let penalty_bps = load_config(env).overdue_penalty_bps as i128;
+        let penalty = installment * overdue as i128 * penalty_bps / 10_000;
+        loan.penalties += penalty;
+        events::emit_asca_loan_overdue(env, loan.loan_id, overdue, penalty);
+    }
+    loan.penalised_installments = fell_due;
+}
+
+/// Updates a loan's overdue penalties and persists it.
+pub(crate) fn refresh_overdue(env: &Env, loan_id: u32) -> AscaLoan {
+    let mut loan = load_loan(env, loan_id);
+    apply_overdue(env, &mut loan);
+    save_loan(env, &loan);
+    loan
+}
+
+/// Applies a repayment, capped at the outstanding balance. Returns the
+/// amount taken from the borrower.
+pub(crate) fn repay(env: &Env, borrower: &Address, loan_id: u32, amount: i128) -> i128 {
+    let mut loan = load_loan(env, loan_id);
+    if loan.borrower != *borrower {
+        panic_with_error!(env, ExtError2::AscaLoanNotFound);
+    }
+    if loan.status != AscaLoanStatus::Active {
+        panic_with_error!(env, ExtError2::AscaLoanNotActive);
+    }
+    if amount <= 0 {
+        panic_with_error!(env, ExtError2::InvalidAscaLoan);
+    }
+    apply_overdue(env, &mut loan);
+
+    let payment = amount.min(outstanding(&loan));
+    let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+    token::Client::new(env, &token_addr).transfer(
+        borrower,
+        env.current_contract_address(),
+        &payment,
+    );
+    loan.repaid += payment;
+    set_pool(env, pool(env) + payment);
+
+    let remaining = outstanding(&loan);
+    if remaining == 0 {
+        loan.status = AscaLoanStatus::Repaid;
+        let mut active = active_loans(env);
+        active.remove(borrower.clone());
+        env.storage().instance().set(&DataKey4::ActiveAscaLoans, &active);
+    }
+    save_loan(env, &loan);
+    events::emit_asca_loan_repaid(env, loan_id, payment, remaining);
+    payment
+}
+
+/// Shares the pool out in proportion to savings. Loans still active are
+/// first netted against the borrower's savings; any balance beyond that is
+/// absorbed by the pool.
+pub(crate) fn share_out(env: &Env) {
+    let config = load_config(env);
+    if env.ledger().timestamp() < config.cycle_end {
+        panic_with_error!(env, ExtError2::AscaCycleNotEnded);
+    }
+
+    let mut member_savings = savings(env);
+    let mut netted: Map<Address, i128> = Map::new(env);
+    for (borrower, loan_id) in active_loans(env).iter() {
+        let mut loan = load_loan(env, loan_id);
+        apply_overdue(env, &mut loan);
+        let saved = member_savings.get(borrower.clone()).unwrap_or(0);
+        let offset = outstanding(&loan).min(saved);
+        member_savings.set(borrower.clone(), saved - offset);
+        netted.set(borrower, offset);
+        loan.status = AscaLoanStatus::SettledAtShareOut;
+        save_loan(env, &loan);
+    }
+    env.storage().instance().remove(&DataKey4::ActiveAscaLoans);
+
+    let mut total_savings = 0i128;
+    for (_, saved) in member_savings.iter() {
+        total_savings += saved;
+    }
+    let available = pool(env);
+    let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+    let token_client = token::Client::new(env, &token_addr);
+    let mut paid = 0i128;
+    let original = savings(env);
+    let members: Vec<Address> = original.keys();
+    for member in members.iter() {
+        let saved = member_savings.get(member.clone()).unwrap_or(0);
+        let share = if total_savings > 0 {
+            available * saved / total_savings
+        } else {
+            0
+        };
+        if share > 0 {
+            token_client.transfer(&env.current_contract_address(), &member, &share);
+            paid += share;
+        }
+        events::emit_asca_share_out(
+            env,
+            member.clone(),
+            original.get(member.clone()).unwrap_or(0),
+            share,
+            netted.get(member).unwrap_or(0),
+        );
+    }
+
+    // Rounding dust stays in the pool for the next cycle
+    set_pool(env, available - paid);
+    env.storage().instance().remove(&DataKey4::AscaSavings);
+}
@@ -23,6 +23,8 @@ pub(crate) fn record_cycle_audit(
     penalties_collected: i128,
     fee_collected: i128,
     insurance_drawn: i128,
+    collateral_drawn: i128,
+    locked_rate: i128,
     cycle_start_timestamp: u64,
     cycle_end_timestamp: u64,
 ) {
@@ -37,6 +39,8 @@ pub(crate) fn record_cycle_audit(
         penalties_collected,
         fee_collected,
         insurance_drawn,
+        collateral_drawn,
+        locked_rate,
         cycle_start_timestamp,
         cycle_end_timestamp,
     };
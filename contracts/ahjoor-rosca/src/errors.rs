@@ -194,4 +194,50 @@ pub enum ExtError2 {
     TokenMismatch = 112,
     /// Member already has an outstanding emergency loan.
     OutstandingLoanExists = 113,
+    /// No collateral requirement is configured for this group.
+    CollateralNotEnabled = 114,
+    /// Member has not posted the collateral currently required.
+    InsufficientCollateral = 115,
+    /// Collateral amount must be positive.
+    InvalidCollateralAmount = 116,
+    /// Member holds no collateral above their current requirement.
+    NoCollateralToRelease = 117,
+    /// Group contributions are not fiat-denominated.
+    FiatContributionNotEnabled = 118,
+    /// Oracle returned no price for the token.
+    OraclePriceUnavailable = 119,
+    /// Oracle price is older than the configured maximum age.
+    OraclePriceStale = 120,
+    /// Oracle price is non-positive or prices the contribution at zero tokens.
+    InvalidOraclePrice = 121,
+    /// Operation is not available while the group runs in ASCA mode.
+    AscaModeActive = 122,
+    /// Group is not running in ASCA mode.
+    AscaNotEnabled = 123,
+    /// ASCA loan proposal is malformed (non-positive amount or not for the creator).
+    InvalidAscaLoan = 124,
+    /// Loan exceeds the borrower's savings multiple or the pool's cash.
+    AscaLoanLimitExceeded = 125,
+    /// Borrower already has an active ASCA loan.
+    AscaLoanOutstanding = 126,
+    /// No ASCA loan found with the given ID.
+    AscaLoanNotFound = 127,
+    /// ASCA loan is already closed.
+    AscaLoanNotActive = 128,
+    /// ASCA cycle has not ended yet.
+    AscaCycleNotEnded = 129,
+    /// ASCA configuration is invalid (zero installments or interval, rate above 100%, or past cycle end).
+    InvalidAscaConfig = 130,
+    /// No keeper pool is configured.
+    KeeperNotConfigured = 131,
+    /// Keeper bounty and caps must be positive; the token can only change while the pool is empty.
+    InvalidKeeperConfig = 132,
+    /// The keeper pool balance is insufficient.
+    InsufficientKeeperPool = 133,
+    /// Reminder interval is not one of the supported intervals.
+    InvalidReminderInterval = 134,
+    /// Oracle price decimals exceed the supported maximum.
+    InvalidPriceDecimals = 135,
+    /// Pricing the contribution from the oracle overflowed.
+    ContributionOverflow = 136,
 }
@@ -1,4 +1,5 @@
 use crate::DistributionType;
+use ahjoor_types::credit::CreditOutcome;
 use soroban_sdk::{contractevent, Address, BytesN, Env, Symbol, Vec};
 
 /// Event: Rosca initialized
@@ -1397,6 +1398,19 @@ pub fn emit_credit_score_updated(e: &Env, member: Address, old_score: i128, new_
     CreditScoreUpdated { member, old_score, new_score, reason }.publish(e);
 }
 
+/// Event: The shared credit registry rejected or failed an outcome report
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct CreditReportFailed {
+    pub registry: Address,
+    pub member: Address,
+    pub outcome: CreditOutcome,
+}
+
+pub fn emit_credit_report_failed(e: &Env, registry: Address, member: Address, outcome: CreditOutcome) {
+    CreditReportFailed { registry, member, outcome }.publish(e);
+}
+
 // ── #330: Contribution Delegation Events ─────────────────────────────────────
 
 /// Event: Member granted contribution delegation to a proxy (#330)
@@ -1821,3 +1835,237 @@ pub struct ProxyExpired {
 pub fn emit_proxy_expired(e: &Env, group_id: u32, member: Address, proxy: Address, expiry_ledger: u64) {
     ProxyExpired { group_id, member, proxy, expiry_ledger }.publish(e);
 }
+
+// --- Collateral-Backed Membership Events ---
+
+/// Event: Admin configured the group's collateral requirement
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct CollateralRequirementSet {
+    pub amount: i128,
+    pub scale_by_position: bool,
+}
+
+/// Event: Member deposited or topped up collateral
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct CollateralDeposited {
+    pub member: Address,
+    pub amount: i128,
+    pub balance: i128,
+}
+
+/// Event: Collateral was drawn to cover a missed contribution
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct CollateralDrawn {
+    pub member: Address,
+    pub round: u32,
+    pub amount: i128,
+    pub balance: i128,
+}
+
+/// Event: Collateral above the member's remaining obligations was released
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct CollateralReleased {
+    pub member: Address,
+    pub amount: i128,
+    pub balance: i128,
+}
+
+pub fn emit_collateral_requirement_set(e: &Env, amount: i128, scale_by_position: bool) {
+    CollateralRequirementSet { amount, scale_by_position }.publish(e);
+}
+
+pub fn emit_collateral_deposited(e: &Env, member: Address, amount: i128, balance: i128) {
+    CollateralDeposited { member, amount, balance }.publish(e);
+}
+
+pub fn emit_collateral_drawn(e: &Env, member: Address, round: u32, amount: i128, balance: i128) {
+    CollateralDrawn { member, round, amount, balance }.publish(e);
+}
+
+pub fn emit_collateral_released(e: &Env, member: Address, amount: i128, balance: i128) {
+    CollateralReleased { member, amount, balance }.publish(e);
+}
+
+// --- Fiat-Denominated Contribution Events ---
+
+/// Event: Admin fixed the contribution in a quote asset
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct FiatContributionSet {
+    pub oracle: Address,
+    pub quote_asset: Address,
+    pub quote_amount: i128,
+}
+
+/// Event: A round's token contribution was priced from the oracle
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct RoundRateLocked {
+    pub round: u32,
+    pub price: i128,
+    pub token_amount: i128,
+}
+
+pub fn emit_fiat_contribution_set(e: &Env, oracle: Address, quote_asset: Address, quote_amount: i128) {
+    FiatContributionSet { oracle, quote_asset, quote_amount }.publish(e);
+}
+
+pub fn emit_round_rate_locked(e: &Env, round: u32, price: i128, token_amount: i128) {
+    RoundRateLocked { round, price, token_amount }.publish(e);
+}
+
+// --- ASCA Mode Events ---
+
+/// Event: Group switched to ASCA mode
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct AscaModeEnabled {
+    pub interest_bps: u32,
+    pub installments: u32,
+    pub cycle_end: u64,
+}
+
+/// Event: Member saved into the ASCA pool
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct AscaSaved {
+    pub member: Address,
+    pub amount: i128,
+    pub savings: i128,
+}
+
+/// Event: Group voted a new ASCA interest rate
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct AscaInterestRateSet {
+    pub interest_bps: u32,
+}
+
+/// Event: An approved ASCA loan was disbursed
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct AscaLoanDisbursed {
+    pub loan_id: u32,
+    pub borrower: Address,
+    pub principal: i128,
+    pub total_due: i128,
+}
+
+/// Event: Overdue installments were penalised
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct AscaLoanOverdue {
+    pub loan_id: u32,
+    pub overdue_installments: u32,
+    pub penalty: i128,
+}
+
+/// Event: Borrower repaid part or all of an ASCA loan
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct AscaLoanRepaid {
+    pub loan_id: u32,
+    pub amount: i128,
+    pub outstanding: i128,
+}
+
+/// Event: Member received their proportional share of the ASCA pool
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct AscaShareOut {
+    pub member: Address,
+    pub savings: i128,
+    pub share: i128,
+    pub netted_loan: i128,
+}
+
+pub fn emit_asca_mode_enabled(e: &Env, interest_bps: u32, installments: u32, cycle_end: u64) {
+    AscaModeEnabled { interest_bps, installments, cycle_end }.publish(e);
+}
+
+pub fn emit_asca_saved(e: &Env, member: Address, amount: i128, savings: i128) {
+    AscaSaved { member, amount, savings }.publish(e);
+}
+
+pub fn emit_asca_interest_rate_set(e: &Env, interest_bps: u32) {
+    AscaInterestRateSet { interest_bps }.publish(e);
+}
+
+pub fn emit_asca_loan_disbursed(e: &Env, loan_id: u32, borrower: Address, principal: i128, total_due: i128) {
+    AscaLoanDisbursed { loan_id, borrower, principal, total_due }.publish(e);
+}
+
+pub fn emit_asca_loan_overdue(e: &Env, loan_id: u32, overdue_installments: u32, penalty: i128) {
+    AscaLoanOverdue { loan_id, overdue_installments, penalty }.publish(e);
+}
+
+pub fn emit_asca_loan_repaid(e: &Env, loan_id: u32, amount: i128, outstanding: i128) {
+    AscaLoanRepaid { loan_id, amount, outstanding }.publish(e);
+}
+
+pub fn emit_asca_share_out(e: &Env, member: Address, savings: i128, share: i128, netted_loan: i128) {
+    AscaShareOut { member, savings, share, netted_loan }.publish(e);
+}
+
+// --- Keeper Reward Events ---
+
+/// Event: Admin set the keeper bounty terms
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct KeeperConfigSet {
+    pub token: Address,
+    pub bounty_per_item: i128,
+    pub max_reward_per_call: i128,
+    pub max_reward_per_ledger: i128,
+}
+
+/// Event: Admin added funds to the keeper pool
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct KeeperPoolFunded {
+    pub amount: i128,
+    pub balance: i128,
+}
+
+/// Event: Admin withdrew funds from the keeper pool
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct KeeperPoolWithdrawn {
+    pub amount: i128,
+    pub balance: i128,
+}
+
+/// Event: Keeper was paid for processing maintenance items
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct KeeperRewarded {
+    pub keeper: Address,
+    pub items: u32,
+    pub amount: i128,
+}
+
+pub fn emit_keeper_config_set(
+    e: &Env,
+    token: Address,
+    bounty_per_item: i128,
+    max_reward_per_call: i128,
+    max_reward_per_ledger: i128,
+) {
+    KeeperConfigSet { token, bounty_per_item, max_reward_per_call, max_reward_per_ledger }.publish(e);
+}
+
+pub fn emit_keeper_pool_funded(e: &Env, amount: i128, balance: i128) {
+    KeeperPoolFunded { amount, balance }.publish(e);
+}
+
+pub fn emit_keeper_pool_withdrawn(e: &Env, amount: i128, balance: i128) {
+    KeeperPoolWithdrawn { amount, balance }.publish(e);
+}
+
+pub fn emit_keeper_rewarded(e: &Env, keeper: Address, items: u32, amount: i128) {
+    KeeperRewarded { keeper, items, amount }.publish(e);
+}
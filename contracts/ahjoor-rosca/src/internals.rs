@@ -1,4 +1,5 @@
-use crate::{errors::{Error, ExtError}, events, audit_trail, ContributionEntry, CycleSnapshotData, DataKey, DataKey2, DataKey3, PersistentKey, PayoutRecord, SlotBid, types::{InsuranceClaim, InsuranceCoverageMode}};
+use crate::{errors::{Error, ExtError, ExtError2}, events, audit_trail, CollateralConfig, ContributionEntry, CycleSnapshotData, FiatContributionConfig, KeeperConfig, KeeperPool, RoundRate, DataKey, DataKey2, DataKey3, DataKey4, PersistentKey, PayoutRecord, SlotBid, types::{InsuranceClaim, InsuranceCoverageMode}};
+use ahjoor_types::oracle::{OracleClient, PriceData};
 use soroban_sdk::{panic_with_error, token, Address, Bytes, BytesN, Env, Map, Vec};
 
 const PERSISTENT_LIFETIME_THRESHOLD: u32 = 100_000;
@@ -88,6 +89,9 @@ pub(crate) fn complete_round_payout(env: &Env, _paid_members: &Vec<Address>) {
         .get(&DataKey::RewardPool)
         .unwrap_or(0);
     let base_token: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+    // Member collateral and ASCA loan pool cash stay in the contract and are
+    // never part of the pot
+    let held_outside_pot = collateral_held(env) + crate::asca::pool(env);
 
     let approved_tokens: Vec<Address> = env
         .storage()
@@ -138,10 +142,10 @@ pub(crate) fn complete_round_payout(env: &Env, _paid_members: &Vec<Address>) {
     let mut actual_pot: i128 = 0;
     for token_addr in approved_tokens.iter() {
         let client = token::Client::new(env, &token_addr);
-        let mut balance = client.balance(&env.current_contract_address());
+        let mut balance = client.balance(&env.current_contract_address()) - keeper_pool_held(env, &token_addr);
 
         if token_addr == base_token {
-            balance -= reward_pool;
+            balance -= reward_pool + held_outside_pot;
             actual_pot = balance;
         }
     }
@@ -196,10 +200,10 @@ pub(crate) fn complete_round_payout(env: &Env, _paid_members: &Vec<Address>) {
 
     for token_addr in approved_tokens.iter() {
         let client = token::Client::new(env, &token_addr);
-        let mut balance = client.balance(&env.current_contract_address());
+        let mut balance = client.balance(&env.current_contract_address()) - keeper_pool_held(env, &token_addr);
 
         if token_addr == base_token {
-            balance -= reward_pool;
+            balance -= reward_pool + held_outside_pot;
             total_payout_history_amt = balance;
         }
 
@@ -301,6 +305,7 @@ pub(crate) fn complete_round_payout(env: &Env, _paid_members: &Vec<Address>) {
         contributions.push_back(ContributionEntry {
             member: member.clone(),
             amount,
+            timestamp: env.ledger().timestamp(),
         });
     }
 
@@ -365,12 +370,27 @@ pub(crate) fn complete_round_payout(env: &Env, _paid_members: &Vec<Address>) {
     // For this round, we track what was drawn via events; default to 0 if not tracked separately
     let insurance_drawn = 0i128;
 
+    let collateral_drawn: i128 = env
+        .storage()
+        .instance()
+        .get(&DataKey3::PendingCollateralDraw)
+        .unwrap_or(0);
+    env.storage().instance().remove(&DataKey3::PendingCollateralDraw);
+
+    let locked_rate: i128 = env
+        .storage()
+        .instance()
+        .get::<_, RoundRate>(&DataKey3::RoundRate)
+        .filter(|rate| rate.round == current_round)
+        .map(|rate| rate.price)
+        .unwrap_or(0);
+
     // Record the cycle audit trail
     audit_trail::record_cycle_audit(
         env,
         current_round,
         total_payout_history_amt,
-        payout_recipient,
+        payout_recipient.clone(),
         total_payout_history_amt,
         contributions,
         defaulters,
@@ -378,6 +398,8 @@ pub(crate) fn complete_round_payout(env: &Env, _paid_members: &Vec<Address>) {
         penalties_collected,
         total_fee_collected,
         insurance_drawn,
+        collateral_drawn,
+        locked_rate,
         cycle_start_timestamp,
         cycle_end_timestamp,
     );
@@ -520,6 +542,18 @@ pub(crate) fn reset_round_state(env: &Env, current_round: u32) {
         audit_trail::record_cycle_start(env, cycle_number, cycle_start_timestamp);
     }
 
+    // Price the new round now if the oracle is fresh; otherwise the first
+    // contribution (or `lock_round_rate`) locks it once a fresh price exists.
+    if let Some(config) = env
+        .storage()
+        .instance()
+        .get::<_, FiatContributionConfig>(&DataKey3::FiatContribution)
+    {
+        if let Ok((price, token_amount)) = fresh_oracle_price(env, &config) {
+            lock_round_rate(env, new_round, &price, token_amount);
+        }
+    }
+
     // Slot Auction: open a new auction at the start of each cycle
     let auction_enabled: bool = env
         .storage()
@@ -664,3 +698,219 @@ pub(crate) fn execute_member_removal(env: &Env, member: &Address) {
 
     events::emit_mem_del(env, member.clone());
 }
+
+// ── Collateral-Backed Membership ────────────────────────────────────────────
+
+/// Collateral `member` must keep posted for the rounds left in the current
+/// cycle. Zero when no requirement is configured or the member has no
+/// remaining obligations.
+pub(crate) fn required_collateral(env: &Env, member: &Address) -> i128 {
+    let config: CollateralConfig = match env.storage().instance().get(&DataKey3::CollateralConfig) {
+        Some(config) => config,
+        None => return 0,
+    };
+    let exited_members: Vec<Address> = env
+        .storage()
+        .instance()
+        .get(&DataKey::ExitedMembers)
+        .unwrap_or(Vec::new(env));
+    if exited_members.contains(member) {
+        return 0;
+    }
+    let payout_order: Vec<Address> = env
+        .storage()
+        .instance()
+        .get(&DataKey::PayoutOrder)
+        .unwrap_or(Vec::new(env));
+    let position = match payout_order.first_index_of(member) {
+        Some(position) => position as i128,
+        None => return 0,
+    };
+    let n = payout_order.len() as i128;
+    let full = if config.scale_by_position {
+        config.amount * (n - position) / n
+    } else {
+        config.amount
+    };
+
+    let current_round: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::CurrentRound)
+        .unwrap_or(0);
+    let remaining_rounds = n - (current_round as i128 % n);
+    full * remaining_rounds / n
+}
+
+/// Total collateral held for all members.
+pub(crate) fn collateral_held(env: &Env) -> i128 {
+    let balances: Map<Address, i128> = env
+        .storage()
+        .instance()
+        .get(&DataKey3::MemberCollateral)
+        .unwrap_or(Map::new(env));
+    let mut total = 0i128;
+    for (_, balance) in balances.iter() {
+        total += balance;
+    }
+    total
+}
+
+/// Draws each defaulter's collateral to cover their missed contribution for
+/// `round`. The drawn amount leaves the collateral balance and is credited as
+/// the member's contribution to this round's pot; it is recorded on the next
+/// `CycleRecord`. Returns the defaulters whose contribution is still short.
+pub(crate) fn draw_collateral_for_defaulters(env: &Env, defaulters: &Vec<Address>, round: u32) -> Vec<Address> {
+    if !env.storage().instance().has(&DataKey3::CollateralConfig) {
+        return defaulters.clone();
+    }
+    let mut balances: Map<Address, i128> = env
+        .storage()
+        .instance()
+        .get(&DataKey3::MemberCollateral)
+        .unwrap_or(Map::new(env));
+    let base_amount: i128 = env
+        .storage()
+        .instance()
+        .get(&DataKey::ContributionAmt)
+        .unwrap_or(0);
+    let tiers: Map<Address, u32> = env
+        .storage()
+        .instance()
+        .get(&DataKey::MemberTiers)
+        .unwrap_or(Map::new(env));
+    let mut member_contributions: Map<Address, i128> = env
+        .storage()
+        .instance()
+        .get(&DataKey::MemberContributions)
+        .unwrap_or(Map::new(env));
+
+    let mut still_short = Vec::new(env);
+    let mut total_drawn = 0i128;
+    for member in defaulters.iter() {
+        let balance = balances.get(member.clone()).unwrap_or(0);
+        let tier_bps = tiers.get(member.clone()).unwrap_or(10_000);
+        let paid = member_contributions.get(member.clone()).unwrap_or(0);
+        let owed = (base_amount * tier_bps as i128) / 10_000 - paid;
+        let drawn = if balance < owed { balance } else { owed };
+        if drawn < owed {
+            still_short.push_back(member.clone());
+        }
+        if drawn <= 0 {
+            continue;
+        }
+        balances.set(member.clone(), balance - drawn);
+        member_contributions.set(member.clone(), paid + drawn);
+        total_drawn += drawn;
+        events::emit_collateral_drawn(env, member.clone(), round, drawn, balance - drawn);
+    }
+
+    if total_drawn > 0 {
+        env.storage().instance().set(&DataKey3::MemberCollateral, &balances);
+        env.storage()
+            .instance()
+            .set(&DataKey::MemberContributions, &member_contributions);
+        let pending: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey3::PendingCollateralDraw)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey3::PendingCollateralDraw, &(pending + total_drawn));
+    }
+    still_short
+}
+
+// ── Fiat-Denominated Contributions ──────────────────────────────────────────
+
+/// Locks the current round's token contribution from the oracle if the group
+/// is fiat-denominated and the round has not been priced yet.
+pub(crate) fn ensure_round_rate_locked(env: &Env) {
+    let config: FiatContributionConfig = match env.storage().instance().get(&DataKey3::FiatContribution) {
+        Some(config) => config,
+        None => return,
+    };
+    let current_round: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::CurrentRound)
+        .unwrap_or(0);
+    if let Some(rate) = env.storage().instance().get::<_, RoundRate>(&DataKey3::RoundRate) {
+        if rate.round == current_round {
+            return;
+        }
+    }
+    match fresh_oracle_price(env, &config) {
+        Ok((price, token_amount)) => lock_round_rate(env, current_round, &price, token_amount),
+        Err(err) => panic_with_error!(env, err),
+    }
+}
+
+/// Reads the oracle price of the group token in the quote asset, rejecting
+/// missing, stale and non-positive prices, and returns it with the token
+/// amount it prices the contribution at.
+fn fresh_oracle_price(env: &Env, config: &FiatContributionConfig) -> Result<(PriceData, i128), ExtError2> {
+    let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+    let price = match OracleClient::new(env, &config.oracle).try_lastprice(&token_addr, &config.quote_asset) {
+        Ok(Ok(Some(price))) => price,
+        _ => return Err(ExtError2::OraclePriceUnavailable),
+    };
+    if env.ledger().timestamp().saturating_sub(price.timestamp) > config.max_oracle_age {
+        return Err(ExtError2::OraclePriceStale);
+    }
+    if price.price <= 0 {
+        return Err(ExtError2::InvalidOraclePrice);
+    }
+    // price = quote units per token unit, scaled by 10^price_decimals
+    let token_amount = config
+        .quote_amount
+        .checked_mul(10i128.pow(config.price_decimals))
+        .ok_or(ExtError2::ContributionOverflow)?
+        / price.price;
+    if token_amount <= 0 {
+        return Err(ExtError2::InvalidOraclePrice);
+    }
+    Ok((price, token_amount))
+}
+
+fn lock_round_rate(env: &Env, round: u32, price: &PriceData, token_amount: i128) {
+    let rate = RoundRate {
+        round,
+        price: price.price,
+        price_timestamp: price.timestamp,
+        token_amount,
+    };
+    env.storage().instance().set(&DataKey::ContributionAmt, &token_amount);
+    env.storage().instance().set(&DataKey3::RoundRate, &rate);
+    events::emit_round_rate_locked(env, round, price.price, token_amount);
+}
+
+pub(crate) fn keeper_pool(env: &Env) -> KeeperPool {
+    ahjoor_types::keeper::keeper_pool(env, &DataKey4::KeeperPool)
+}
+
+/// Keeper pool funds held in `token`; they stay in the contract and are never
+/// part of the pot.
+fn keeper_pool_held(env: &Env, token: &Address) -> i128 {
+    match env.storage().instance().get::<DataKey4, KeeperConfig>(&DataKey4::KeeperConfig) {
+        Some(config) if config.token == *token => keeper_pool(env).balance,
+        _ => 0,
+    }
+}
+
+/// Pays `keeper` the bounty for `items` processed by a maintenance call from
+/// the keeper pool. Returns the amount paid.
+pub(crate) fn pay_keeper_reward(env: &Env, keeper: &Address, items: u32) -> i128 {
+    let reward = ahjoor_types::keeper::pay_keeper_reward(
+        env,
+        &DataKey4::KeeperConfig,
+        &DataKey4::KeeperPool,
+        keeper,
+        items,
+    );
+    if reward > 0 {
+        events::emit_keeper_rewarded(env, keeper.clone(), items, reward);
+    }
+    reward
+}
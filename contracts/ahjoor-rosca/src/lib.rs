@@ -3,7 +3,8 @@ use soroban_sdk::{
     contract, contractimpl, panic_with_error, token, Address, Bytes, BytesN, Env, Map, String, Symbol, Vec,
 };
 use soroban_sdk::xdr::ToXdr;
-use ahjoor_token_whitelist::TokenWhitelistClient;
+use ahjoor_types::credit::{CreditOutcome, CreditRegistryClient};
+use ahjoor_types::whitelist::TokenWhitelistClient;
 
 // Instance storage: config, counters, and active round state (bounded, shared TTL)
 pub(crate) const INSTANCE_LIFETIME_THRESHOLD: u32 = 100_000;
@@ -21,6 +22,12 @@ const TEMP_BUMP_AMOUNT: u32 = 15_000;
 
 pub(crate) const MIGRATION_TIMEOUT_SECONDS: u64 = 604800; // 7 days in seconds
 
+// Largest oracle price scale accepted for fiat contributions (10^18 fits in i128)
+pub(crate) const MAX_PRICE_DECIMALS: u32 = 18;
+
+// Reminder intervals accepted by emit_deadline_reminder, each rewarded once per round
+pub(crate) const REMINDER_INTERVALS: [&str; 3] = ["24h", "1h", "30m"];
+
 pub mod types;
 pub use types::*;
 
@@ -28,6 +35,7 @@ mod errors;
 mod events;
 mod internals;
 mod audit_trail;
+mod asca;
 pub mod savings_goal_tracking;
 pub mod savings_goal_tracking_impl;
 mod test_tiers;
@@ -687,6 +695,435 @@ impl AhjoorContract {
             .unwrap_or(0)
     }
 
+    // ── Keeper Rewards ────────────────────────────────────────────────────────
+
+    /// Admin sets the bounty paid to callers of the permissionless maintenance
+    /// calls (`close_round`, `emit_deadline_reminder`). The pool token can only
+    /// change while the pool is empty.
+    pub fn set_keeper_config(
+        env: Env,
+        admin: Address,
+        token: Address,
+        bounty_per_item: i128,
+        max_reward_per_call: i128,
+        max_reward_per_ledger: i128,
+    ) {
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).expect("Not initialized");
+        if admin != stored_admin { panic!("Only admin can set keeper config"); }
+        if bounty_per_item <= 0 || max_reward_per_call <= 0 || max_reward_per_ledger <= 0 {
+            panic_with_error!(&env, ExtError2::InvalidKeeperConfig);
+        }
+        if let Some(current) = Self::get_keeper_config(env.clone()) {
+            if current.token != token && internals::keeper_pool(&env).balance > 0 {
+                panic_with_error!(&env, ExtError2::InvalidKeeperConfig);
+            }
+        }
+
+        let config = KeeperConfig {
+            token: token.clone(),
+            bounty_per_item,
+            max_reward_per_call,
+            max_reward_per_ledger,
+        };
+        env.storage().instance().set(&DataKey4::KeeperConfig, &config);
+        events::emit_keeper_config_set(&env, token, bounty_per_item, max_reward_per_call, max_reward_per_ledger);
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    pub fn get_keeper_config(env: Env) -> Option<KeeperConfig> {
+        env.storage().instance().get(&DataKey4::KeeperConfig)
+    }
+
+    pub fn get_keeper_pool(env: Env) -> KeeperPool {
+        internals::keeper_pool(&env)
+    }
+
+    /// Admin tops up the keeper pool in the configured keeper token. Pool
+    /// funds are kept out of round payouts.
+    pub fn fund_keeper_pool(env: Env, admin: Address, amount: i128) {
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).expect("Not initialized");
+        if admin != stored_admin { panic!("Only admin can fund the keeper pool"); }
+        if amount <= 0 {
+            panic_with_error!(&env, Error::AmountMustBePositive);
+        }
+        let config = Self::get_keeper_config(env.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, ExtError2::KeeperNotConfigured));
+
+        token::Client::new(&env, &config.token).transfer(&admin, env.current_contract_address(), &amount);
+        let mut pool = internals::keeper_pool(&env);
+        pool.balance += amount;
+        env.storage().instance().set(&DataKey4::KeeperPool, &pool);
+        events::emit_keeper_pool_funded(&env, amount, pool.balance);
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Admin withdraws unspent funds from the keeper pool.
+    pub fn withdraw_keeper_pool(env: Env, admin: Address, amount: i128) {
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).expect("Not initialized");
+        if admin != stored_admin { panic!("Only admin can withdraw from the keeper pool"); }
+        if amount <= 0 {
+            panic_with_error!(&env, Error::AmountMustBePositive);
+        }
+        let config = Self::get_keeper_config(env.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, ExtError2::KeeperNotConfigured));
+        let mut pool = internals::keeper_pool(&env);
+        if amount > pool.balance {
+            panic_with_error!(&env, ExtError2::InsufficientKeeperPool);
+        }
+
+        pool.balance -= amount;
+        env.storage().instance().set(&DataKey4::KeeperPool, &pool);
+        token::Client::new(&env, &config.token).transfer(&env.current_contract_address(), &admin, &amount);
+        events::emit_keeper_pool_withdrawn(&env, amount, pool.balance);
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    // ── ASCA Mode ─────────────────────────────────────────────────────────────
+
+    /// Admin switches the group to ASCA mode, or starts a new ASCA cycle after
+    /// a share-out. Rotating contributions and payouts are disabled; members
+    /// save with `asca_save` and borrow through `ProposalType::AscaLoan`.
+    pub fn enable_asca_mode(env: Env, admin: Address, config: AscaConfig) {
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).expect("Not initialized");
+        if admin != stored_admin { panic!("Only admin can enable ASCA mode"); }
+
+        let paid_members: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::PaidMembers)
+            .unwrap_or(Vec::new(&env));
+        if !paid_members.is_empty() {
+            panic_with_error!(&env, Error::CannotChangeMidRound);
+        }
+        if !asca::savings(&env).is_empty() {
+            panic_with_error!(&env, ExtError2::AscaModeActive);
+        }
+        if config.installments == 0
+            || config.installment_interval == 0
+            || config.interest_bps > 10_000
+            || config.cycle_end <= env.ledger().timestamp()
+        {
+            panic_with_error!(&env, ExtError2::InvalidAscaConfig);
+        }
+
+        env.storage().instance().set(&DataKey4::AscaConfig, &config);
+        events::emit_asca_mode_enabled(&env, config.interest_bps, config.installments, config.cycle_end);
+        env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    pub fn get_asca_config(env: Env) -> Option<AscaConfig> {
+        env.storage().instance().get(&DataKey4::AscaConfig)
+    }
+
+    /// Member saves into the ASCA pool until the cycle ends.
+    pub fn asca_save(env: Env, member: Address, amount: i128) {
+        internals::check_not_paused(&env);
+        internals::check_not_frozen(&env);
+        member.require_auth();
+
+        let config = asca::load_config(&env);
+        if env.ledger().timestamp() >= config.cycle_end {
+            panic_with_error!(&env, Error::ContributionWindowClosed);
+        }
+        if amount <= 0 {
+            panic_with_error!(&env, Error::AmountMustBePositive);
+        }
+        let members: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Members)
+            .expect("Not initialized");
+        if !members.contains(&member) {
+            panic_with_error!(&env, Error::NotAMember);
+        }
+
+        let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        token::Client::new(&env, &token_addr).transfer(
+            &member,
+            env.current_contract_address(),
+            &amount,
+        );
+
+        let mut savings = asca::savings(&env);
+        let saved = savings.get(member.clone()).unwrap_or(0) + amount;
+        savings.set(member.clone(), saved);
+        env.storage().instance().set(&DataKey4::AscaSavings, &savings);
+        env.storage()
+            .instance()
+            .set(&DataKey4::AscaPool, &(asca::pool(&env) + amount));
+
+        events::emit_asca_saved(&env, member, amount, saved);
+        env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Borrower repays an ASCA loan, partially or in full. Overdue penalties
+    /// are accrued first. Returns the amount actually taken.
+    pub fn repay_asca_loan(env: Env, borrower: Address, loan_id: u32, amount: i128) -> i128 {
+        internals::check_not_paused(&env);
+        borrower.require_auth();
+        let paid = asca::repay(&env, &borrower, loan_id, amount);
+        env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        paid
+    }
+
+    /// Accrues penalties for installments that fell due unpaid. Callable by anyone.
+    pub fn apply_asca_overdue(env: Env, loan_id: u32) -> AscaLoan {
+        let loan = asca::refresh_overdue(&env, loan_id);
+        env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        loan
+    }
+
+    /// Admin shares the pool out in proportion to savings once the cycle has ended.
+    pub fn asca_share_out(env: Env, admin: Address) {
+        internals::check_not_paused(&env);
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).expect("Not initialized");
+        if admin != stored_admin { panic!("Only admin can share out"); }
+
+        asca::share_out(&env);
+        env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    pub fn get_asca_loan(env: Env, loan_id: u32) -> Option<AscaLoan> {
+        env.storage().persistent().get(&DataKey4::AscaLoan(loan_id))
+    }
+
+    pub fn get_asca_outstanding(env: Env, loan_id: u32) -> i128 {
+        asca::outstanding(&asca::load_loan(&env, loan_id))
+    }
+
+    pub fn get_asca_savings(env: Env, member: Address) -> i128 {
+        asca::savings(&env).get(member).unwrap_or(0)
+    }
+
+    pub fn get_asca_pool(env: Env) -> i128 {
+        asca::pool(&env)
+    }
+
+    // ── Fiat-Denominated Contributions ────────────────────────────────────────
+
+    /// Admin fixes the contribution at `quote_amount` of `quote_asset`. Each
+    /// round's token amount is then priced from the oracle when the round
+    /// opens, or on its first contribution if the price was stale then.
+    /// Only allowed between rounds; the current round is priced immediately.
+    /// `price_decimals` is the number of decimals the oracle scales prices by.
+    pub fn set_fiat_contribution(
+        env: Env,
+        admin: Address,
+        oracle: Address,
+        quote_asset: Address,
+        quote_amount: i128,
+        max_oracle_age: u64,
+        price_decimals: u32,
+    ) {
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).expect("Not initialized");
+        if admin != stored_admin { panic!("Only admin can set fiat contribution"); }
+        if quote_amount <= 0 {
+            panic_with_error!(&env, Error::AmountMustBePositive);
+        }
+        if price_decimals > MAX_PRICE_DECIMALS {
+            panic_with_error!(&env, ExtError2::InvalidPriceDecimals);
+        }
+        let paid_members: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::PaidMembers)
+            .unwrap_or(Vec::new(&env));
+        if !paid_members.is_empty() {
+            panic_with_error!(&env, Error::CannotChangeMidRound);
+        }
+
+        let config = FiatContributionConfig {
+            oracle: oracle.clone(),
+            quote_asset: quote_asset.clone(),
+            quote_amount,
+            max_oracle_age,
+            price_decimals,
+        };
+        env.storage().instance().set(&DataKey3::FiatContribution, &config);
+        env.storage().instance().remove(&DataKey3::RoundRate);
+        internals::ensure_round_rate_locked(&env);
+
+        events::emit_fiat_contribution_set(&env, oracle, quote_asset, quote_amount);
+        env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Admin returns the group to a fixed token contribution. The last locked
+    /// token amount stays in effect.
+    pub fn clear_fiat_contribution(env: Env, admin: Address) {
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).expect("Not initialized");
+        if admin != stored_admin { panic!("Only admin can clear fiat contribution"); }
+        if !env.storage().instance().has(&DataKey3::FiatContribution) {
+            panic_with_error!(&env, ExtError2::FiatContributionNotEnabled);
+        }
+
+        env.storage().instance().remove(&DataKey3::FiatContribution);
+        env.storage().instance().remove(&DataKey3::RoundRate);
+        env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    pub fn get_fiat_contribution(env: Env) -> Option<FiatContributionConfig> {
+        env.storage().instance().get(&DataKey3::FiatContribution)
+    }
+
+    /// Prices the current round from the oracle if it has not been priced
+    /// yet. Callable by anyone.
+    pub fn lock_round_rate(env: Env) -> RoundRate {
+        if !env.storage().instance().has(&DataKey3::FiatContribution) {
+            panic_with_error!(&env, ExtError2::FiatContributionNotEnabled);
+        }
+        internals::ensure_round_rate_locked(&env);
+        env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage().instance().get(&DataKey3::RoundRate).unwrap()
+    }
+
+    /// Rate the current round is priced at, if it has been locked.
+    pub fn get_round_rate(env: Env) -> Option<RoundRate> {
+        let current_round: u32 = env.storage().instance().get(&DataKey::CurrentRound).unwrap_or(0);
+        env.storage()
+            .instance()
+            .get::<_, RoundRate>(&DataKey3::RoundRate)
+            .filter(|rate| rate.round == current_round)
+    }
+
+    // ── Collateral-Backed Membership ──────────────────────────────────────────
+
+    /// Admin sets the collateral each member must post before contributing.
+    /// Invitees post it automatically in `join_with_invite`; members added by
+    /// the admin post it with `deposit_collateral`. Passing `amount == 0`
+    /// removes the requirement.
+    pub fn set_collateral_requirement(env: Env, admin: Address, amount: i128, scale_by_position: bool) {
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).expect("Not initialized");
+        if admin != stored_admin { panic!("Only admin can set collateral requirement"); }
+        if amount < 0 {
+            panic_with_error!(&env, ExtError2::InvalidCollateralAmount);
+        }
+
+        if amount == 0 {
+            env.storage().instance().remove(&DataKey3::CollateralConfig);
+        } else {
+            env.storage()
+                .instance()
+                .set(&DataKey3::CollateralConfig, &CollateralConfig { amount, scale_by_position });
+        }
+        events::emit_collateral_requirement_set(&env, amount, scale_by_position);
+        env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    pub fn get_collateral_config(env: Env) -> Option<CollateralConfig> {
+        env.storage().instance().get(&DataKey3::CollateralConfig)
+    }
+
+    /// Member deposits or tops up collateral in the group's base token.
+    pub fn deposit_collateral(env: Env, member: Address, amount: i128) {
+        internals::check_not_paused(&env);
+        member.require_auth();
+
+        if !env.storage().instance().has(&DataKey3::CollateralConfig) {
+            panic_with_error!(&env, ExtError2::CollateralNotEnabled);
+        }
+        if amount <= 0 {
+            panic_with_error!(&env, ExtError2::InvalidCollateralAmount);
+        }
+        let members: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Members)
+            .expect("Not initialized");
+        if !members.contains(&member) {
+            panic_with_error!(&env, Error::NotAMember);
+        }
+
+        Self::post_collateral_internal(&env, &member, amount);
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Member withdraws collateral above what their remaining obligations
+    /// require. Returns the amount released.
+    pub fn release_collateral(env: Env, member: Address) -> i128 {
+        internals::check_not_paused(&env);
+        member.require_auth();
+
+        let mut balances: Map<Address, i128> = env
+            .storage()
+            .instance()
+            .get(&DataKey3::MemberCollateral)
+            .unwrap_or(Map::new(&env));
+        let balance = balances.get(member.clone()).unwrap_or(0);
+        let releasable = balance - internals::required_collateral(&env, &member);
+        if releasable <= 0 {
+            panic_with_error!(&env, ExtError2::NoCollateralToRelease);
+        }
+
+        balances.set(member.clone(), balance - releasable);
+        env.storage().instance().set(&DataKey3::MemberCollateral, &balances);
+
+        let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        token::Client::new(&env, &token_addr).transfer(
+            &env.current_contract_address(),
+            &member,
+            &releasable,
+        );
+
+        events::emit_collateral_released(&env, member, releasable, balance - releasable);
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        releasable
+    }
+
+    pub fn get_member_collateral(env: Env, member: Address) -> i128 {
+        let balances: Map<Address, i128> = env
+            .storage()
+            .instance()
+            .get(&DataKey3::MemberCollateral)
+            .unwrap_or(Map::new(&env));
+        balances.get(member).unwrap_or(0)
+    }
+
+    /// Collateral `member` must currently keep posted.
+    pub fn get_required_collateral(env: Env, member: Address) -> i128 {
+        internals::required_collateral(&env, &member)
+    }
+
+    fn post_collateral_internal(env: &Env, member: &Address, amount: i128) {
+        let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        token::Client::new(env, &token_addr).transfer(
+            member,
+            env.current_contract_address(),
+            &amount,
+        );
+
+        let mut balances: Map<Address, i128> = env
+            .storage()
+            .instance()
+            .get(&DataKey3::MemberCollateral)
+            .unwrap_or(Map::new(env));
+        let balance = balances.get(member.clone()).unwrap_or(0) + amount;
+        balances.set(member.clone(), balance);
+        env.storage().instance().set(&DataKey3::MemberCollateral, &balances);
+
+        events::emit_collateral_deposited(env, member.clone(), amount, balance);
+    }
+
     /// Get the proposed admin address, if any.
     pub fn get_proposed_admin(env: Env) -> Option<Address> {
         env.storage().instance().get(&DataKey::ProposedAdmin)
@@ -727,6 +1164,7 @@ impl AhjoorContract {
     pub fn contribute(env: Env, contributor: Address, token: Address, amount: i128) {
         internals::check_not_paused(&env);
         internals::check_not_frozen(&env);
+        asca::check_not_asca(&env);
         contributor.require_auth();
 
         let start_at = Self::get_start_time(env.clone());
@@ -798,6 +1236,11 @@ impl AhjoorContract {
         if !members.contains(&contributor) {
             panic_with_error!(&env, Error::NotAMember);
         }
+        if Self::get_member_collateral(env.clone(), contributor.clone())
+            < internals::required_collateral(&env, &contributor)
+        {
+            panic_with_error!(&env, ExtError2::InsufficientCollateral);
+        }
 
         let activation_emitted: bool = env
             .storage()
@@ -844,6 +1287,9 @@ impl AhjoorContract {
         // Token whitelist validation
         Self::require_token_allowed(&env, &token);
 
+        // Fiat-denominated groups price the round before its first contribution
+        internals::ensure_round_rate_locked(&env);
+
         let base_token: Address = env.storage().instance().get(&DataKey::Token).unwrap();
         let base_amount: i128 = env
             .storage()
@@ -1234,15 +1680,12 @@ impl AhjoorContract {
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
     }
 
-    pub fn close_round(env: Env) {
+    /// Close the round once its deadline has passed, recording non-contributors
+    /// as defaulters. Callable by anyone; `keeper` receives the keeper bounty.
+    pub fn close_round(env: Env, keeper: Address) {
         internals::check_not_paused(&env);
         internals::check_not_frozen(&env);
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
-        admin.require_auth();
+        asca::check_not_asca(&env);
 
         let use_timestamp: bool = env
             .storage()
@@ -1266,7 +1709,7 @@ impl AhjoorContract {
         }
 
         let members: Vec<Address> = env.storage().instance().get(&DataKey::Members).unwrap();
-        let paid_members: Vec<Address> =
+        let mut paid_members: Vec<Address> =
             env.storage().instance().get(&DataKey::PaidMembers).unwrap();
         let exited_members: Vec<Address> = env
             .storage()
@@ -1286,28 +1729,38 @@ impl AhjoorContract {
             .get(&DataKey::CurrentRound)
             .unwrap();
 
-        let mut defaulters = Vec::new(&env);
+        let mut missed = Vec::new(&env);
         for member in members.iter() {
             let has_skipped = skip_requests.get((member.clone(), current_round)).unwrap_or(false);
             if !paid_members.contains(&member) && !exited_members.contains(&member) && !has_skipped {
-                defaulters.push_back(member);
+                missed.push_back(member);
+            }
+        }
+        // Members whose missed contribution collateral fully covers count as paid
+        let defaulters = internals::draw_collateral_for_defaulters(&env, &missed, current_round);
+        for member in missed.iter() {
+            if !defaulters.contains(&member) {
+                paid_members.push_back(member);
             }
         }
+        env.storage().instance().set(&DataKey::PaidMembers, &paid_members);
         env.storage()
             .instance()
             .set(&DataKey::Defaulters, &defaulters);
 
-        let current_round: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::CurrentRound)
-            .unwrap();
-        events::emit_closed(&env, current_round, defaulters);
+        let covered = defaulters.len() < missed.len();
+        events::emit_closed(&env, current_round, defaulters.clone());
         env.storage()
             .instance()
             .set(&DataKey2::LastRoundDeadline, &deadline);
 
-        internals::reset_round_state(&env, current_round);
+        // A round made whole from collateral pays out like any other
+        if covered && defaulters.is_empty() {
+            internals::complete_round_payout(&env, &paid_members);
+        } else {
+            internals::reset_round_state(&env, current_round);
+        }
+        internals::pay_keeper_reward(&env, &keeper, 1);
     }
 
     /// Finalize a round once its deadline has passed.
@@ -1344,6 +1797,7 @@ impl AhjoorContract {
     pub fn finalize_round(env: Env) {
         internals::check_not_paused(&env);
         internals::check_not_frozen(&env);
+        asca::check_not_asca(&env);
         let admin: Address = env
             .storage()
             .instance()
@@ -1374,7 +1828,7 @@ impl AhjoorContract {
         }
 
         let members: Vec<Address> = env.storage().instance().get(&DataKey::Members).unwrap();
-        let paid_members: Vec<Address> =
+        let mut paid_members: Vec<Address> =
             env.storage().instance().get(&DataKey::PaidMembers).unwrap();
         let exited_members: Vec<Address> = env
             .storage()
@@ -1410,12 +1864,21 @@ impl AhjoorContract {
             .get(&DataKey2::SkipRequests)
             .unwrap_or(Map::new(&env));
 
-        // Identify defaulters (non-contributors, non-exited, non-skippers)
-        let mut defaulters: Vec<Address> = Vec::new(&env);
+        // Identify non-contributors (non-exited, non-skippers)
+        let mut missed: Vec<Address> = Vec::new(&env);
         for member in members.iter() {
             let has_skipped = skip_requests.get((member.clone(), current_round)).unwrap_or(false);
             if !paid_members.contains(&member) && !exited_members.contains(&member) && !has_skipped {
-                defaulters.push_back(member.clone());
+                missed.push_back(member.clone());
+            }
+        }
+
+        // Cover missed contributions from collateral so the pot below includes
+        // them; members it fully covers are not defaulters
+        let defaulters = internals::draw_collateral_for_defaulters(&env, &missed, current_round);
+        for member in missed.iter() {
+            if !defaulters.contains(&member) {
+                paid_members.push_back(member);
             }
         }
 
@@ -3542,6 +4005,7 @@ impl AhjoorContract {
         if members.contains(&new_member) {
             panic_with_error!(&env, Error::AlreadyAMember);
         }
+        Self::require_min_registry_score_internal(&env, &new_member);
         members.push_back(new_member.clone());
         env.storage().instance().set(&DataKey::Members, &members);
 
@@ -3892,6 +4356,7 @@ impl AhjoorContract {
         if !members.contains(&creator) {
             panic_with_error!(&env, Error::OnlyMembersAllowed);
         }
+        asca::validate_proposal(&env, proposal_type, &creator, &target_member, execution_data);
 
         let current_time = env.ledger().timestamp();
         let deadline = current_time + voting_duration;
@@ -4298,6 +4763,12 @@ impl AhjoorContract {
                 env.storage().instance().set(&DataKey2::ActiveReinstatementProposal, &am);
                 events::emit_reinstatement_approved(&env, target);
             }
+            ProposalType::AscaLoan => {
+                asca::execute_loan(&env, &proposal.target_member, proposal.execution_data);
+            }
+            ProposalType::AscaInterestRate => {
+                asca::execute_interest_rate(&env, proposal.execution_data);
+            }
         }
 
         proposal.status = ProposalStatus::Executed;
@@ -5155,6 +5626,22 @@ impl AhjoorContract {
 
     // --- READ INTERFACE ---
 
+    /// Current member list.
+    pub fn get_members(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Members)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Lifecycle status of the group (Active unless dissolved, merged or split).
+    pub fn get_group_status(env: Env) -> GroupStatus {
+        env.storage()
+            .instance()
+            .get(&DataKey2::GroupStatus)
+            .unwrap_or(GroupStatus::Active)
+    }
+
     pub fn get_group_info(env: Env) -> GroupInfo {
         let members: Vec<Address> = env.storage().instance().get(&DataKey::Members).unwrap();
         let payout_order: Vec<Address> =
@@ -5256,6 +5743,8 @@ impl AhjoorContract {
         let lifetime_contributions = member_collected.get(member.clone()).unwrap_or(0);
 
         let claimable_rewards = Self::get_claimable_reward(env.clone(), member.clone());
+        let collateral_balance = Self::get_member_collateral(env.clone(), member.clone());
+        let collateral_required = internals::required_collateral(&env, &member);
 
         MemberStatus {
             is_member,
@@ -5266,6 +5755,8 @@ impl AhjoorContract {
             default_count,
             lifetime_contributions,
             claimable_rewards,
+            collateral_balance,
+            collateral_required,
         }
     }
 
@@ -5333,8 +5824,18 @@ impl AhjoorContract {
         (current_round, paid_members, deadline, strategy, token)
     }
 
-    pub fn emit_deadline_reminder(env: Env, interval: Symbol) {
+    /// Emit a contribution reminder for the current round. Callable by anyone;
+    /// `keeper` receives the keeper bounty for the first reminder of each
+    /// interval in a round that still has members left to contribute.
+    /// `interval` must be one of `REMINDER_INTERVALS`.
+    pub fn emit_deadline_reminder(env: Env, keeper: Address, interval: Symbol) {
         internals::check_not_paused(&env);
+        if !REMINDER_INTERVALS
+            .iter()
+            .any(|allowed| Symbol::new(&env, allowed) == interval)
+        {
+            panic_with_error!(&env, ExtError2::InvalidReminderInterval);
+        }
 
         let current_round: u32 = env
             .storage()
@@ -5388,13 +5889,25 @@ impl AhjoorContract {
             }
         }
 
+        let has_non_contributors = !non_contributors.is_empty();
         events::emit_reminder(
             &env,
             current_round,
             time_remaining,
             non_contributors,
-            interval,
+            interval.clone(),
         );
+
+        let rewarded_key = DataKey4::RewardedReminder(current_round, interval);
+        if has_non_contributors && !env.storage().persistent().has(&rewarded_key) {
+            env.storage().persistent().set(&rewarded_key, &true);
+            env.storage().persistent().extend_ttl(
+                &rewarded_key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+            internals::pay_keeper_reward(&env, &keeper, 1);
+        }
     }
 
     pub fn get_upcoming_deadlines(env: Env, count: u32) -> Map<u32, u64> {
@@ -6227,6 +6740,7 @@ impl AhjoorContract {
         if members.contains(&invitee) {
             panic_with_error!(&env, Error::AlreadyAMember);
         }
+        Self::require_min_registry_score_internal(&env, &invitee);
 
         members.push_back(invitee.clone());
         env.storage().instance().set(&DataKey::Members, &members);
@@ -6241,6 +6755,12 @@ impl AhjoorContract {
             .instance()
             .set(&DataKey::PayoutOrder, &payout_order);
 
+        // Post the invitee's collateral as part of joining
+        let required_collateral = internals::required_collateral(&env, &invitee);
+        if required_collateral > 0 {
+            Self::post_collateral_internal(&env, &invitee, required_collateral);
+        }
+
         events::emit_invite_redeemed(&env, invitee.clone());
         events::emit_mem_add(&env, invitee, members.len() as u32);
 
@@ -7275,6 +7795,7 @@ impl AhjoorContract {
     ) {
         internals::check_not_paused(&env);
         internals::check_not_frozen(&env);
+        asca::check_not_asca(&env);
         proxy.require_auth();
 
         let start_at = Self::get_start_time(env.clone());
@@ -7328,6 +7849,11 @@ impl AhjoorContract {
         if !members.contains(&member) {
             panic_with_error!(&env, Error::NotAMember);
         }
+        if Self::get_member_collateral(env.clone(), member.clone())
+            < internals::required_collateral(&env, &member)
+        {
+            panic_with_error!(&env, ExtError2::InsufficientCollateral);
+        }
 
         let activation_emitted: bool = env
             .storage()
@@ -7376,6 +7902,9 @@ impl AhjoorContract {
         }
         Self::require_token_allowed(&env, &token);
 
+        // Fiat-denominated groups price the round before its first contribution
+        internals::ensure_round_rate_locked(&env);
+
         let base_token: Address = env.storage().instance().get(&DataKey::Token).unwrap();
         if token != base_token {
             panic_with_error!(&env, ExtError2::IncorrectContributionAmount);
@@ -8064,6 +8593,33 @@ impl AhjoorContract {
         })
     }
 
+    /// Admin points this group at a shared `ahjoor-credit-registry`. Credit
+    /// outcomes are reported there as well as to the local score, once the
+    /// registry admin has allowlisted this contract as a reporter.
+    pub fn set_credit_registry(env: Env, admin: Address, registry: Address) {
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).expect("Not initialized");
+        if admin != stored_admin { panic!("Only admin can set credit registry"); }
+
+        env.storage().instance().set(&DataKey3::CreditRegistry, &registry);
+        env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    pub fn get_credit_registry(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey3::CreditRegistry)
+    }
+
+    /// Admin sets the minimum cross-group registry score required by
+    /// `add_member` and `join_with_invite`. Ignored until a registry is set.
+    pub fn set_min_registry_score(env: Env, admin: Address, min_score: i128) {
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).expect("Not initialized");
+        if admin != stored_admin { panic!("Only admin can set minimum registry score"); }
+
+        env.storage().instance().set(&DataKey3::MinRegistryScore, &min_score);
+        env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
     /// Enable group treasury for collective purchases (#314)
     pub fn enable_group_treasury(env: Env, admin: Address, treasury_admin: Address) {
         admin.require_auth();
@@ -8270,16 +8826,40 @@ impl AhjoorContract {
         let exit_sym     = Symbol::new(env, "early_exit");
         let complete_sym = Symbol::new(env, "completed");
 
-        if reason == on_time_sym {
+        let outcome = if reason == on_time_sym {
             ms.on_time_contributions = ms.on_time_contributions.saturating_add(1);
+            Some(CreditOutcome::OnTime)
         } else if reason == late_sym {
             ms.late_contributions = ms.late_contributions.saturating_add(1);
+            Some(CreditOutcome::Late)
         } else if reason == default_sym {
             ms.defaults = ms.defaults.saturating_add(1);
+            Some(CreditOutcome::Default)
         } else if reason == exit_sym {
             ms.early_exits = ms.early_exits.saturating_add(1);
+            Some(CreditOutcome::EarlyExit)
         } else if reason == complete_sym {
             ms.groups_completed = ms.groups_completed.saturating_add(1);
+            Some(CreditOutcome::GroupCompleted)
+        } else {
+            None
+        };
+
+        // Mirror the outcome to the shared registry. A registry that has not
+        // allowlisted this group yet must not block contributions or payouts,
+        // so a failed report is surfaced as an event instead.
+        if let (Some(outcome), Some(registry)) = (
+            outcome,
+            env.storage().instance().get::<_, Address>(&DataKey3::CreditRegistry),
+        ) {
+            let reported = CreditRegistryClient::new(env, &registry).try_report_outcome(
+                &env.current_contract_address(),
+                member,
+                &outcome,
+            );
+            if !matches!(reported, Ok(Ok(_))) {
+                events::emit_credit_report_failed(env, registry, member.clone(), outcome);
+            }
         }
 
         // Recompute score using admin weights
@@ -8882,6 +9462,18 @@ impl AhjoorContract {
             .unwrap_or_else(|| panic_with_error!(&env, ExtError::SplitProposalNotFound))
     }
 
+    /// Internal: panics if member's cross-group registry score is below the
+    /// group minimum. No-op unless both a registry and a minimum are set.
+    fn require_min_registry_score_internal(env: &Env, member: &Address) {
+        let min_score: Option<i128> = env.storage().instance().get(&DataKey3::MinRegistryScore);
+        let registry: Option<Address> = env.storage().instance().get(&DataKey3::CreditRegistry);
+        if let (Some(min_score), Some(registry)) = (min_score, registry) {
+            if CreditRegistryClient::new(env, &registry).get_score(member) < min_score {
+                panic_with_error!(env, ExtError::InsufficientCreditScore);
+            }
+        }
+    }
+
     /// Internal: panics if member's credit score is below the group minimum.
     fn require_min_credit_score_internal(env: &Env, member: &Address) {
         let min_score: i128 = env
@@ -9464,3 +10056,13 @@ mod test_snapshot;
 #[cfg(test)]
 mod test_savings_milestone_rewards;
 pub use events::*;
+#[cfg(test)]
+mod test_credit_registry;
+#[cfg(test)]
+mod test_collateral;
+#[cfg(test)]
+mod test_fiat_contribution;
+#[cfg(test)]
+mod test_asca;
+#[cfg(test)]
+mod test_keeper;
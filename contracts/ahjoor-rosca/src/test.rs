@@ -280,7 +280,7 @@ fn test_rosca_flow_with_time_locks() {
         .try_contribute(&user2, &setup.token_admin, &100);
     assert!(result.is_err());
 
-    setup.client.close_round();
+    setup.client.close_round(&setup.admin);
 
     let (round, paid, deadline, _, _) = setup.client.get_state();
     assert_eq!(round, 1);
@@ -298,7 +298,7 @@ fn test_cannot_close_early() {
     default_init(&setup);
 
     setup.env.ledger().set_timestamp(500);
-    let res = setup.client.try_close_round();
+    let res = setup.client.try_close_round(&setup.admin);
     assert_eq!(res.unwrap_err().unwrap(), Error::DeadlineNotPassed.into());
 }
 
@@ -340,7 +340,7 @@ fn test_admin_close_round() {
     default_init(&setup);
 
     setup.env.ledger().set_timestamp(3601);
-    setup.client.close_round();
+    setup.client.close_round(&setup.admin);
 
     let (round, _, _, _, _) = setup.client.get_state();
     assert_eq!(round, 1);
@@ -549,7 +549,7 @@ fn test_single_defaulter_penalty() {
 
     // Wait for deadline to pass
     setup.env.ledger().set_timestamp(3601);
-    setup.client.close_round();
+    setup.client.close_round(&setup.admin);
 
     // Check events after close_round
     let events_after_close = setup.env.events().all();
@@ -604,7 +604,7 @@ fn test_penalty_deferred_within_grace_period() {
 
     setup.client.contribute(&user1, &setup.token_admin, &100);
     setup.env.ledger().set_timestamp(3601);
-    setup.client.close_round();
+    setup.client.close_round(&setup.admin);
 
     setup.client.penalise_defaulter(&user2);
     assert_eq!(setup.token_client.balance(&user2), 1000);
@@ -652,7 +652,7 @@ fn test_penalty_applied_after_grace_boundary() {
 
     setup.client.contribute(&user1, &setup.token_admin, &100);
     setup.env.ledger().set_timestamp(3601);
-    setup.client.close_round();
+    setup.client.close_round(&setup.admin);
 
     setup.client.penalise_defaulter(&user2);
     assert_eq!(setup.token_client.balance(&user2), 1000);
@@ -713,7 +713,7 @@ fn test_reputation_score_lifecycle_and_bounds() {
     // Round 1: user2 defaults; penalty attempt during grace should not change score.
     setup.client.contribute(&user1, &setup.token_admin, &100);
     setup.env.ledger().set_timestamp(3601); // Just past round-1 deadline.
-    setup.client.close_round();
+    setup.client.close_round(&setup.admin);
     setup.client.penalise_defaulter(&user2);
     assert_eq!(setup.client.get_reputation_score(&user2), 10);
 
@@ -783,7 +783,7 @@ fn test_multiple_defaulters_penalty() {
 
     // Wait for deadline to pass
     setup.env.ledger().set_timestamp(3601);
-    setup.client.close_round();
+    setup.client.close_round(&setup.admin);
 
     // Admin penalizes both defaulters
     setup.client.penalise_defaulter(&user2);
@@ -833,7 +833,7 @@ fn test_member_suspension_after_two_defaults() {
     // ROUND 0: user2 defaults
     setup.client.contribute(&user1, &setup.token_admin, &100);
     setup.env.ledger().set_timestamp(3601);
-    setup.client.close_round();
+    setup.client.close_round(&setup.admin);
     setup.client.penalise_defaulter(&user2);
 
     // ROUND 1: user2 defaults again
@@ -841,7 +841,7 @@ fn test_member_suspension_after_two_defaults() {
     // So at 3601, new deadline would be 3601 + 3600 = 7201
     setup.client.contribute(&user1, &setup.token_admin, &100);
     setup.env.ledger().set_timestamp(7202); // Past the new deadline
-    setup.client.close_round();
+    setup.client.close_round(&setup.admin);
     setup.client.penalise_defaulter(&user2);
 
     // Check penalties were applied twice
@@ -910,14 +910,14 @@ fn test_suspended_member_skipped_in_payout() {
     client.contribute(&user1, &token_admin, &100);
     client.contribute(&user3, &token_admin, &100);
     env.ledger().set_timestamp(3601);
-    client.close_round();
+    client.close_round(&admin);
     client.penalise_defaulter(&user2);
 
     // ROUND 1: user2 defaults again (gets suspended)
     client.contribute(&user1, &token_admin, &100);
     client.contribute(&user3, &token_admin, &100);
     env.ledger().set_timestamp(7202);
-    client.close_round();
+    client.close_round(&admin);
     client.penalise_defaulter(&user2);
 
     // ROUND 2: All contribute, but user2 should be skipped for payout
@@ -1030,7 +1030,7 @@ fn test_penalty_disabled_when_amount_zero() {
     );
 
     env.ledger().set_timestamp(3601);
-    client.close_round();
+    client.close_round(&admin);
     let res = client.try_penalise_defaulter(&user1);
     assert_eq!(res.unwrap_err().unwrap(), Error::PenaltyDisabled.into());
 }
@@ -2514,7 +2514,7 @@ fn test_exit_request_rejected_mid_round() {
 #[test]
 fn test_admin_approves_exit_penalty_applied() {
     let env = Env::default();
-    let (client, _admin, u1, u2, u3, token_client, _ta) = setup_exit_env(&env);
+    let (client, admin, u1, u2, u3, token_client, _ta) = setup_exit_env(&env);
 
     // u1 contributes in round 0. u2 does NOT (so the round never auto-completes).
     // Therefore the 100 tokens u1 sent remain in the contract.
@@ -2522,7 +2522,7 @@ fn test_admin_approves_exit_penalty_applied() {
 
     // Advance past deadline so admin can close the round.
     env.ledger().set_timestamp(3601);
-    client.close_round();
+    client.close_round(&admin);
     // Now CurrentRound = 1. Contract still holds u1's 100 tokens.
 
     // u1 has contributed in 1 round. penalty = 100 * 1000 / 10000 = 10. refund = 90.
@@ -2640,7 +2640,7 @@ fn test_exited_member_skipped_in_payout_order() {
 #[test]
 fn test_exited_member_skipped_in_defaulters_list() {
     let env = Env::default();
-    let (client, _admin, u1, u2, _u3, _tc, _ta) = setup_exit_env(&env);
+    let (client, admin, u1, u2, _u3, _tc, _ta) = setup_exit_env(&env);
 
     // u1 exits before the first round deadline
     client.request_emergency_exit(&u1);
@@ -2651,7 +2651,7 @@ fn test_exited_member_skipped_in_defaulters_list() {
 
     // Advance past deadline
     env.ledger().set_timestamp(3601);
-    client.close_round();
+    client.close_round(&admin);
 
     // u3 should be a defaulter, u1 should NOT (they've exited)
     // We verify by checking that penalising u1 panics
@@ -2723,7 +2723,7 @@ fn test_exit_with_zero_penalty() {
 
     // Let deadline pass and close round so CurrentRound becomes 1.
     env.ledger().set_timestamp(3601);
-    client.close_round();
+    client.close_round(&admin);
 
     // CurrentRound = 1. u1 has contributed 1 round. penalty = 0. refund = 100.
     let u1_balance_before = token_client.balance(&u1);
@@ -3201,7 +3201,8 @@ fn test_emit_deadline_reminder() {
     setup.client.contribute(&u1, &setup.token_admin, &100);
 
     // Emit reminder
-    setup.client.emit_deadline_reminder(&symbol_short!("24h"));
+    setup.client
+        .emit_deadline_reminder(&setup.admin, &symbol_short!("24h"));
 
     let events = setup.env.events().all();
     let reminder_event = events.get(events.len() - 1).unwrap();
@@ -3638,7 +3639,7 @@ fn test_penalty_appeal_execution() {
     setup.client.contribute(&user3, &setup.token_admin, &100);
 
     setup.env.ledger().set_timestamp(3601);
-    setup.client.close_round();
+    setup.client.close_round(&setup.admin);
 
     setup.client.penalise_defaulter(&user2);
 
@@ -4036,12 +4037,12 @@ fn test_get_member_status_suspended_member() {
     // u2 defaults twice to trigger suspension
     setup.client.contribute(&u1, &setup.token_admin, &100);
     setup.env.ledger().set_timestamp(3601);
-    setup.client.close_round();
+    setup.client.close_round(&setup.admin);
     setup.client.penalise_defaulter(&u2);
 
     setup.client.contribute(&u1, &setup.token_admin, &100);
     setup.env.ledger().set_timestamp(7202);
-    setup.client.close_round();
+    setup.client.close_round(&setup.admin);
     setup.client.penalise_defaulter(&u2);
 
     let status = setup.client.get_member_status(&u2);
@@ -4603,14 +4604,14 @@ fn setup_exit_penalty_env(
 #[test]
 fn test_exit_before_payout_penalty_on_contributions_only() {
     let env = Env::default();
-    let (client, _admin, u1, u2, token_client, ta) = setup_exit_penalty_env(&env);
+    let (client, admin, u1, _u2, token_client, ta) = setup_exit_penalty_env(&env);
 
     // u1 contributes round 0 but u2 does not, so no payout happens.
     client.contribute(&u1, &ta, &100);
 
     // Close the round (u1 contributed but u2 did not → no auto-payout, close manually)
     env.ledger().set_timestamp(3601);
-    client.close_round();
+    client.close_round(&admin);
     // CurrentRound = 1. Contract holds u1's 100 tokens.
 
     // u1 exits: contributed 1 round (100 tokens), no payout received, penalty = 100 * 10% = 10
@@ -4626,7 +4627,7 @@ fn test_exit_before_payout_penalty_on_contributions_only() {
 #[test]
 fn test_exit_after_receiving_payout_deducted_from_refund() {
     let env = Env::default();
-    let (client, _admin, u1, u2, token_client, ta) = setup_exit_penalty_env(&env);
+    let (client, admin, u1, u2, token_client, ta) = setup_exit_penalty_env(&env);
 
     // Complete round 0: u1 and u2 both contribute. Recipient = u1 (index 0).
     client.contribute(&u1, &ta, &100);
@@ -4637,7 +4638,7 @@ fn test_exit_after_receiving_payout_deducted_from_refund() {
     client.contribute(&u1, &ta, &100);
 
     env.ledger().set_timestamp(3601);
-    client.close_round();
+    client.close_round(&admin);
     // CurrentRound = 2. u1 has contributed 2 rounds, received 200.
 
     // u1 exits:
@@ -0,0 +1,196 @@
+#![cfg(test)]
+use super::*;
+use soroban_sdk::token::Client as TokenClient;
+use soroban_sdk::token::StellarAssetClient as TokenAdminClient;
+use soroban_sdk::{testutils::{Address as _, Ledger}, Address, Env, String, Vec};
+
+fn setup_asca<'a>() -> (Env, AhjoorContractClient<'a>, Address, Vec<Address>, TokenClient<'a>) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AhjoorContract, ());
+    let client = AhjoorContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let token_addr = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    let token_client = TokenClient::new(&env, &token_addr);
+    let token_admin_client = TokenAdminClient::new(&env, &token_addr);
+
+    let mut members = Vec::new(&env);
+    for _ in 0..3 {
+        let m = Address::generate(&env);
+        token_admin_client.mint(&m, &1000);
+        members.push_back(m);
+    }
+
+    client.init(
+        &admin,
+        &members,
+        &100,
+        &token_addr,
+        &3600,
+        &RoscaConfig {
+            strategy: PayoutStrategy::RoundRobin,
+            custom_order: None,
+            penalty_amount: 0,
+            exit_penalty_bps: 0,
+            collective_goal: None,
+            member_goals: None,
+            fee_bps: 0,
+            fee_recipient: None,
+            max_defaults: 3,
+            use_timestamp_schedule: false,
+            round_duration_seconds: 0,
+            max_members: Some(10),
+            skip_fee: 0,
+            max_skips_per_cycle: 0,
+            voting_mode: VotingMode::Equal,
+            grace_period_ledgers: 0,
+            late_fee_bps: 0,
+            grace_period_seconds: 0,
+            auction_enabled: false,
+            auction_window_ledgers: 0,
+            randomize_payout_order: false,
+            reserve_enabled: false,
+            reserve_contribution_bps: 0,
+        },
+        &None,
+    );
+
+    client.enable_asca_mode(
+        &admin,
+        &AscaConfig {
+            interest_bps: 1_000,
+            installments: 2,
+            installment_interval: 100,
+            overdue_penalty_bps: 500,
+            max_loan_to_savings_bps: 20_000,
+            cycle_end: 10_000,
+        },
+    );
+    for m in members.iter() {
+        client.asca_save(&m, &100);
+    }
+
+    (env, client, admin, members, token_client)
+}
+
+/// Borrower proposes a loan, the other members approve it and it is executed.
+fn borrow(env: &Env, client: &AhjoorContractClient, members: &Vec<Address>, borrower: &Address, amount: i128) {
+    let proposal_id = client.get_proposal_counter();
+    client.create_proposal(
+        borrower,
+        &ProposalType::AscaLoan,
+        &String::from_str(env, "loan"),
+        borrower,
+        &50,
+        &Some(amount),
+    );
+    for m in members.iter() {
+        if m != *borrower {
+            client.vote_on_proposal(&m, &proposal_id, &true);
+        }
+    }
+    env.ledger().with_mut(|l| l.timestamp += 51);
+    client.execute_proposal(&proposal_id);
+}
+
+
+#[test]
+fn test_loan_repaid_with_interest_is_shared_out_by_savings() {
+    let (env, client, admin, members, token_client) = setup_asca();
+    let borrower = members.get(0).unwrap();
+
+    borrow(&env, &client, &members, &borrower, 150);
+    let loan = client.get_asca_loan(&0).unwrap();
+    assert_eq!(loan.total_due, 165);
+    assert_eq!(client.get_asca_pool(), 150);
+    assert_eq!(token_client.balance(&borrower), 900 + 150);
+
+    assert_eq!(client.repay_asca_loan(&borrower, &0, &500), 165);
+    assert_eq!(client.get_asca_loan(&0).unwrap().status, AscaLoanStatus::Repaid);
+    assert_eq!(client.get_asca_pool(), 315);
+
+    env.ledger().with_mut(|l| l.timestamp = 10_000);
+    client.asca_share_out(&admin);
+    for m in members.iter() {
+        assert_eq!(client.get_asca_savings(&m), 0);
+    }
+    assert_eq!(token_client.balance(&members.get(1).unwrap()), 900 + 105);
+    assert_eq!(token_client.balance(&borrower), 1050 - 165 + 105);
+}
+
+#[test]
+fn test_overdue_installments_are_penalised_once() {
+    let (env, client, _admin, members, _token_client) = setup_asca();
+    let borrower = members.get(0).unwrap();
+    borrow(&env, &client, &members, &borrower, 100);
+
+    env.ledger().with_mut(|l| l.timestamp += 100);
+    client.repay_asca_loan(&borrower, &0, &55);
+    env.ledger().with_mut(|l| l.timestamp += 101);
+
+    // First installment was paid on time; only the second is overdue
+    let loan = client.apply_asca_overdue(&0);
+    assert_eq!(loan.penalties, 2);
+    assert_eq!(client.apply_asca_overdue(&0).penalties, 2);
+    assert_eq!(client.get_asca_outstanding(&0), 110 + 2 - 55);
+}
+
+#[test]
+fn test_unpaid_loan_is_netted_against_savings_at_share_out() {
+    let (env, client, admin, members, token_client) = setup_asca();
+    let borrower = members.get(0).unwrap();
+    borrow(&env, &client, &members, &borrower, 150);
+
+    env.ledger().with_mut(|l| l.timestamp = 10_000);
+    client.asca_share_out(&admin);
+
+    assert_eq!(
+        client.get_asca_loan(&0).unwrap().status,
+        AscaLoanStatus::SettledAtShareOut
+    );
+    assert_eq!(token_client.balance(&borrower), 900 + 150);
+    assert_eq!(token_client.balance(&members.get(1).unwrap()), 900 + 75);
+    assert_eq!(token_client.balance(&members.get(2).unwrap()), 900 + 75);
+}
+
+#[test]
+fn test_loan_above_savings_multiple_is_rejected() {
+    let (env, client, _admin, members, _token_client) = setup_asca();
+    let borrower = members.get(0).unwrap();
+    let proposal_id = client.get_proposal_counter();
+    client.create_proposal(
+        &borrower,
+        &ProposalType::AscaLoan,
+        &String::from_str(&env, "loan"),
+        &borrower,
+        &50,
+        &Some(250),
+    );
+    client.vote_on_proposal(&members.get(1).unwrap(), &proposal_id, &true);
+    client.vote_on_proposal(&members.get(2).unwrap(), &proposal_id, &true);
+    env.ledger().with_mut(|l| l.timestamp += 51);
+
+    assert_eq!(
+        client.try_execute_proposal(&proposal_id),
+        Err(Ok(soroban_sdk::Error::from(ExtError2::AscaLoanLimitExceeded)))
+    );
+}
+
+#[test]
+fn test_rotating_contributions_are_disabled_in_asca_mode() {
+    let (env, client, _admin, members, token_client) = setup_asca();
+    let member = members.get(0).unwrap();
+    assert_eq!(
+        client.try_contribute(&member, &token_client.address, &100),
+        Err(Ok(soroban_sdk::Error::from(ExtError2::AscaModeActive)))
+    );
+
+    let proxy = Address::generate(&env);
+    client.authorize_proxy(&member, &0, &proxy, &1);
+    assert_eq!(
+        client.try_contribute_as_proxy(&proxy, &0, &member, &token_client.address, &100),
+        Err(Ok(soroban_sdk::Error::from(ExtError2::AscaModeActive)))
+    );
+}
@@ -184,7 +184,7 @@ fn test_cycle_record_tracks_skippers() {
     });
 
     // Finalize round to trigger payout and audit recording
-    client.close_round();
+    client.close_round(&admin);
 
 
 
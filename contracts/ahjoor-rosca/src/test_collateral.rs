@@ -0,0 +1,196 @@
+#![cfg(test)]
+use super::*;
+use soroban_sdk::token::Client as TokenClient;
+use soroban_sdk::token::StellarAssetClient as TokenAdminClient;
+use soroban_sdk::{testutils::{Address as _, Ledger}, Address, Env, Vec};
+
+fn setup_collateral<'a>(
+    member_count: u32,
+) -> (Env, AhjoorContractClient<'a>, Address, Address, Vec<Address>, TokenClient<'a>) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AhjoorContract, ());
+    let client = AhjoorContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let token_addr = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    let token_client = TokenClient::new(&env, &token_addr);
+    let token_admin_client = TokenAdminClient::new(&env, &token_addr);
+
+    let mut members = Vec::new(&env);
+    for _ in 0..member_count {
+        let m = Address::generate(&env);
+        token_admin_client.mint(&m, &1000);
+        members.push_back(m);
+    }
+
+    client.init(
+        &admin,
+        &members,
+        &100,
+        &token_addr,
+        &3600,
+        &RoscaConfig {
+            strategy: PayoutStrategy::RoundRobin,
+            custom_order: None,
+            penalty_amount: 0,
+            exit_penalty_bps: 0,
+            collective_goal: None,
+            member_goals: None,
+            fee_bps: 0,
+            fee_recipient: None,
+            max_defaults: 3,
+            use_timestamp_schedule: false,
+            round_duration_seconds: 0,
+            max_members: Some(10),
+            skip_fee: 0,
+            max_skips_per_cycle: 0,
+            voting_mode: VotingMode::Equal,
+            grace_period_ledgers: 0,
+            late_fee_bps: 0,
+            grace_period_seconds: 0,
+            auction_enabled: false,
+            auction_window_ledgers: 0,
+            randomize_payout_order: false,
+            reserve_enabled: false,
+            reserve_contribution_bps: 0,
+        },
+        &None,
+    );
+
+    (env, client, admin, token_addr, members, token_client)
+}
+
+#[test]
+fn test_requirement_scales_with_payout_position() {
+    let (_env, client, admin, _token_addr, members, _token_client) = setup_collateral(3);
+    client.set_collateral_requirement(&admin, &300, &true);
+
+    assert_eq!(client.get_required_collateral(&members.get(0).unwrap()), 300);
+    assert_eq!(client.get_required_collateral(&members.get(1).unwrap()), 200);
+    assert_eq!(client.get_required_collateral(&members.get(2).unwrap()), 100);
+}
+
+#[test]
+fn test_contribution_requires_posted_collateral() {
+    let (_env, client, admin, token_addr, members, token_client) = setup_collateral(2);
+    client.set_collateral_requirement(&admin, &200, &false);
+    let member = members.get(0).unwrap();
+
+    assert_eq!(
+        client.try_contribute(&member, &token_addr, &100),
+        Err(Ok(soroban_sdk::Error::from(ExtError2::InsufficientCollateral)))
+    );
+
+    client.deposit_collateral(&member, &200);
+    client.contribute(&member, &token_addr, &100);
+
+    let status = client.get_member_status(&member);
+    assert_eq!(status.collateral_balance, 200);
+    assert_eq!(status.collateral_required, 200);
+    assert_eq!(token_client.balance(&member), 700);
+}
+
+#[test]
+fn test_missed_contribution_is_drawn_from_collateral_and_audited() {
+    let (env, client, admin, token_addr, members, token_client) = setup_collateral(2);
+    client.set_collateral_requirement(&admin, &200, &false);
+    let recipient = members.get(0).unwrap();
+    let defaulter = members.get(1).unwrap();
+    client.deposit_collateral(&recipient, &200);
+    client.deposit_collateral(&defaulter, &200);
+
+    client.contribute(&recipient, &token_addr, &100);
+    env.ledger().with_mut(|l| l.timestamp = 3700);
+    client.finalize_round();
+
+    // The pot is made whole from the defaulter's collateral; collateral held
+    // for the recipient is not paid out
+    assert_eq!(token_client.balance(&recipient), 1000 - 200 - 100 + 200);
+    assert_eq!(client.get_member_collateral(&recipient), 200);
+    assert_eq!(client.get_member_collateral(&defaulter), 100);
+
+    let record = client.get_cycle_record(&0).unwrap();
+    assert_eq!(record.collateral_drawn, 100);
+    // Fully covered by collateral, so not recorded as a default
+    assert!(!record.defaulters.contains(&defaulter));
+    assert_eq!(client.get_member_status(&defaulter).default_count, 0);
+}
+
+#[test]
+fn test_close_round_draws_collateral_for_defaulters() {
+    let (env, client, admin, _token_addr, members, _token_client) = setup_collateral(2);
+    client.set_collateral_requirement(&admin, &150, &false);
+    let defaulter = members.get(1).unwrap();
+    client.deposit_collateral(&defaulter, &60);
+
+    env.ledger().with_mut(|l| l.timestamp = 3700);
+    client.close_round(&admin);
+
+    // Only what was posted can be drawn
+    assert_eq!(client.get_member_collateral(&defaulter), 0);
+    assert_eq!(client.get_member_status(&defaulter).collateral_required, 75);
+}
+
+#[test]
+fn test_close_round_pays_out_a_round_covered_by_collateral() {
+    let (env, client, admin, token_addr, members, token_client) = setup_collateral(2);
+    client.set_collateral_requirement(&admin, &200, &false);
+    let recipient = members.get(0).unwrap();
+    let defaulter = members.get(1).unwrap();
+    client.deposit_collateral(&recipient, &200);
+    client.deposit_collateral(&defaulter, &200);
+
+    client.contribute(&recipient, &token_addr, &100);
+    env.ledger().with_mut(|l| l.timestamp = 3700);
+    client.close_round(&admin);
+
+    // The drawn collateral completes this round's pot, which is paid out
+    assert_eq!(token_client.balance(&recipient), 1000 - 200 - 100 + 200);
+    assert_eq!(client.get_member_collateral(&defaulter), 100);
+    let record = client.get_cycle_record(&0).unwrap();
+    assert_eq!(record.collateral_drawn, 100);
+    assert!(record.defaulters.is_empty());
+}
+
+#[test]
+fn test_proxy_contribution_requires_posted_collateral() {
+    let (env, client, admin, token_addr, members, _token_client) = setup_collateral(2);
+    client.set_collateral_requirement(&admin, &200, &false);
+    let member = members.get(0).unwrap();
+    let proxy = Address::generate(&env);
+    client.authorize_proxy(&member, &0, &proxy, &1);
+
+    assert_eq!(
+        client.try_contribute_as_proxy(&proxy, &0, &member, &token_addr, &100),
+        Err(Ok(soroban_sdk::Error::from(ExtError2::InsufficientCollateral)))
+    );
+}
+
+#[test]
+fn test_collateral_is_released_pro_rata_and_topped_up() {
+    let (_env, client, admin, token_addr, members, token_client) = setup_collateral(2);
+    client.set_collateral_requirement(&admin, &200, &false);
+    let first = members.get(0).unwrap();
+    let second = members.get(1).unwrap();
+    client.deposit_collateral(&first, &200);
+    client.deposit_collateral(&second, &200);
+
+    assert_eq!(
+        client.try_release_collateral(&first),
+        Err(Ok(soroban_sdk::Error::from(ExtError2::NoCollateralToRelease)))
+    );
+
+    // The round pays out as soon as every member has contributed
+    client.contribute(&first, &token_addr, &100);
+    client.contribute(&second, &token_addr, &100);
+
+    // One of two rounds left: half the collateral is still required
+    assert_eq!(client.release_collateral(&first), 100);
+    assert_eq!(client.get_member_collateral(&first), 100);
+    assert_eq!(token_client.balance(&first), 1000 - 200 - 100 + 200 + 100);
+
+    client.deposit_collateral(&first, &50);
+    assert_eq!(client.get_member_collateral(&first), 150);
+}
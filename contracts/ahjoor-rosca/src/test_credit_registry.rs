@@ -0,0 +1,119 @@
+#![cfg(test)]
+use super::*;
+use ahjoor_credit_registry::{AhjoorCreditRegistry, AhjoorCreditRegistryClient};
+use soroban_sdk::token::StellarAssetClient as TokenAdminClient;
+use soroban_sdk::{testutils::{Address as _, Events}, Address, Env, IntoVal, Symbol, Vec};
+
+fn setup_with_registry<'a>() -> (
+    Env,
+    AhjoorContractClient<'a>,
+    AhjoorCreditRegistryClient<'a>,
+    Address,
+    Address,
+    Vec<Address>,
+) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AhjoorContract, ());
+    let client = AhjoorContractClient::new(&env, &contract_id);
+
+    let registry_id = env.register(AhjoorCreditRegistry, ());
+    let registry = AhjoorCreditRegistryClient::new(&env, &registry_id);
+
+    let admin = Address::generate(&env);
+    let token_addr = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    let token_admin_client = TokenAdminClient::new(&env, &token_addr);
+
+    let mut members = Vec::new(&env);
+    for _ in 0..2 {
+        let m = Address::generate(&env);
+        token_admin_client.mint(&m, &1000);
+        members.push_back(m);
+    }
+
+    client.init(
+        &admin,
+        &members,
+        &100,
+        &token_addr,
+        &3600,
+        &RoscaConfig {
+            strategy: PayoutStrategy::RoundRobin,
+            custom_order: None,
+            penalty_amount: 0,
+            exit_penalty_bps: 0,
+            collective_goal: None,
+            member_goals: None,
+            fee_bps: 0,
+            fee_recipient: None,
+            max_defaults: 3,
+            use_timestamp_schedule: false,
+            round_duration_seconds: 0,
+            max_members: Some(10),
+            skip_fee: 0,
+            max_skips_per_cycle: 0,
+            voting_mode: VotingMode::Equal,
+            grace_period_ledgers: 0,
+            late_fee_bps: 0,
+            grace_period_seconds: 0,
+            auction_enabled: false,
+            auction_window_ledgers: 0,
+            randomize_payout_order: false,
+            reserve_enabled: false,
+            reserve_contribution_bps: 0,
+        },
+        &None,
+    );
+
+    registry.initialize(&admin);
+    client.set_credit_registry(&admin, &registry_id);
+
+    (env, client, registry, admin, token_addr, members)
+}
+
+#[test]
+fn test_outcomes_are_reported_once_group_is_allowlisted() {
+    let (env, client, registry, admin, token_addr, members) = setup_with_registry();
+    let member = members.get(0).unwrap();
+
+    // Not yet an allowlisted reporter: the contribution still goes through
+    // and the failed report is surfaced as an event
+    client.contribute(&member, &token_addr, &100);
+    let failed_topics = (Symbol::new(&env, "credit_report_failed"),).into_val(&env);
+    assert!(env.events().all().iter().any(|e| e.1 == failed_topics));
+    assert_eq!(registry.get_member_records(&member, &0, &10).len(), 0);
+    assert_eq!(client.get_credit_score(&member).on_time_contributions, 1);
+
+    registry.add_reporter(&admin, &client.address);
+    let other = members.get(1).unwrap();
+    client.contribute(&other, &token_addr, &100);
+
+    assert!(!env.events().all().iter().any(|e| e.1 == failed_topics));
+
+    let records = registry.get_member_records(&other, &0, &10);
+    assert_eq!(records.len(), 1);
+    let record = registry.get_record(&records.get(0).unwrap());
+    assert_eq!(record.outcome, CreditOutcome::OnTime);
+    assert_eq!(record.reporter, client.address);
+    assert_eq!(registry.get_score(&other), 10);
+}
+
+#[test]
+fn test_add_member_requires_min_registry_score() {
+    let (env, client, registry, admin, _token_addr, _members) = setup_with_registry();
+    let reporter = Address::generate(&env);
+    registry.add_reporter(&admin, &reporter);
+    client.set_min_registry_score(&admin, &20);
+
+    let newcomer = Address::generate(&env);
+    registry.report_outcome(&reporter, &newcomer, &CreditOutcome::OnTime);
+    assert_eq!(
+        client.try_add_member(&newcomer),
+        Err(Ok(soroban_sdk::Error::from(ExtError::InsufficientCreditScore)))
+    );
+
+    registry.report_outcome(&reporter, &newcomer, &CreditOutcome::GroupCompleted);
+    client.add_member(&newcomer);
+    assert!(client.get_members().contains(&newcomer));
+}
@@ -0,0 +1,224 @@
+#![cfg(test)]
+use super::*;
+use soroban_sdk::token::Client as TokenClient;
+use soroban_sdk::token::StellarAssetClient as TokenAdminClient;
+use soroban_sdk::{testutils::{Address as _, Ledger}, Address, Env, Vec};
+
+/// Mock oracle returning a single price (scaled by 10^7) set by the test.
+mod mock_oracle {
+    use ahjoor_types::PriceData;
+    use soroban_sdk::{contract, contractimpl, contracttype, Address, Env};
+
+    #[contracttype]
+    enum OracleKey {
+        Price,
+        Ts,
+    }
+
+    #[contract]
+    pub struct MockOracle;
+
+    #[contractimpl]
+    impl MockOracle {
+        pub fn set_price(env: Env, price: i128, timestamp: u64) {
+            env.storage().instance().set(&OracleKey::Price, &price);
+            env.storage().instance().set(&OracleKey::Ts, &timestamp);
+        }
+
+        pub fn lastprice(env: Env, _base: Address, _quote: Address) -> Option<PriceData> {
+            let price: i128 = env.storage().instance().get(&OracleKey::Price)?;
+            let timestamp: u64 = env.storage().instance().get(&OracleKey::Ts)?;
+            Some(PriceData { price, timestamp })
+        }
+    }
+}
+
+use mock_oracle::{MockOracle, MockOracleClient};
+
+struct FiatSetup<'a> {
+    env: Env,
+    client: AhjoorContractClient<'a>,
+    admin: Address,
+    token_addr: Address,
+    members: Vec<Address>,
+    token_client: TokenClient<'a>,
+    oracle: Address,
+    quote_asset: Address,
+}
+
+fn setup_fiat<'a>() -> FiatSetup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AhjoorContract, ());
+    let client = AhjoorContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let token_addr = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    let token_client = TokenClient::new(&env, &token_addr);
+    let token_admin_client = TokenAdminClient::new(&env, &token_addr);
+
+    let mut members = Vec::new(&env);
+    for _ in 0..2 {
+        let m = Address::generate(&env);
+        token_admin_client.mint(&m, &1000);
+        members.push_back(m);
+    }
+
+    client.init(
+        &admin,
+        &members,
+        &100,
+        &token_addr,
+        &3600,
+        &RoscaConfig {
+            strategy: PayoutStrategy::RoundRobin,
+            custom_order: None,
+            penalty_amount: 0,
+            exit_penalty_bps: 0,
+            collective_goal: None,
+            member_goals: None,
+            fee_bps: 0,
+            fee_recipient: None,
+            max_defaults: 3,
+            use_timestamp_schedule: false,
+            round_duration_seconds: 0,
+            max_members: Some(10),
+            skip_fee: 0,
+            max_skips_per_cycle: 0,
+            voting_mode: VotingMode::Equal,
+            grace_period_ledgers: 0,
+            late_fee_bps: 0,
+            grace_period_seconds: 0,
+            auction_enabled: false,
+            auction_window_ledgers: 0,
+            randomize_payout_order: false,
+            reserve_enabled: false,
+            reserve_contribution_bps: 0,
+        },
+        &None,
+    );
+
+    let oracle = env.register(MockOracle, ());
+    let quote_asset = Address::generate(&env);
+
+    FiatSetup { env, client, admin, token_addr, members, token_client, oracle, quote_asset }
+}
+
+fn set_price(s: &FiatSetup, price: i128, ts: u64) {
+    MockOracleClient::new(&s.env, &s.oracle).set_price(&price, &ts);
+    s.env.ledger().with_mut(|l| l.timestamp = ts);
+}
+
+#[test]
+fn test_each_round_is_priced_from_the_oracle() {
+    let s = setup_fiat();
+    // 2 quote units per token: 100 quote units = 50 tokens
+    set_price(&s, 20_000_000, 100);
+    s.client
+        .set_fiat_contribution(&s.admin, &s.oracle, &s.quote_asset, &100, &300, &7);
+    let rate = s.client.get_round_rate().unwrap();
+    assert_eq!(rate.round, 0);
+    assert_eq!(rate.token_amount, 50);
+
+    let recipient = s.members.get(0).unwrap();
+    s.client.contribute(&recipient, &s.token_addr, &50);
+    // The token doubles in value before the round closes
+    set_price(&s, 40_000_000, 200);
+    s.client.contribute(&s.members.get(1).unwrap(), &s.token_addr, &50);
+
+    assert_eq!(s.token_client.balance(&recipient), 1000 - 50 + 100);
+    assert_eq!(s.client.get_cycle_record(&0).unwrap().locked_rate, 20_000_000);
+
+    // The next round was priced as it opened
+    let next = s.client.get_round_rate().unwrap();
+    assert_eq!(next.round, 1);
+    assert_eq!(next.token_amount, 25);
+}
+
+#[test]
+fn test_stale_price_is_rejected() {
+    let s = setup_fiat();
+    set_price(&s, 20_000_000, 100);
+    s.env.ledger().with_mut(|l| l.timestamp = 500);
+
+    assert_eq!(
+        s.client
+            .try_set_fiat_contribution(&s.admin, &s.oracle, &s.quote_asset, &100, &300, &7),
+        Err(Ok(soroban_sdk::Error::from(ExtError2::OraclePriceStale)))
+    );
+}
+
+#[test]
+fn test_round_opened_on_stale_price_is_locked_once_fresh() {
+    let s = setup_fiat();
+    set_price(&s, 20_000_000, 100);
+    s.client
+        .set_fiat_contribution(&s.admin, &s.oracle, &s.quote_asset, &100, &300, &7);
+    s.client.contribute(&s.members.get(0).unwrap(), &s.token_addr, &50);
+    s.env.ledger().with_mut(|l| l.timestamp = 1000);
+    s.client.contribute(&s.members.get(1).unwrap(), &s.token_addr, &50);
+
+    // Round 1 opened with a stale price, so it is not priced yet
+    assert_eq!(s.client.get_round_rate(), None);
+    assert_eq!(
+        s.client.try_contribute(&s.members.get(1).unwrap(), &s.token_addr, &50),
+        Err(Ok(soroban_sdk::Error::from(ExtError2::OraclePriceStale)))
+    );
+
+    set_price(&s, 10_000_000, 1100);
+    let rate = s.client.lock_round_rate();
+    assert_eq!(rate.round, 1);
+    assert_eq!(rate.token_amount, 100);
+    s.client.contribute(&s.members.get(1).unwrap(), &s.token_addr, &100);
+}
+
+#[test]
+fn test_proxy_contribution_prices_the_round() {
+    let s = setup_fiat();
+    set_price(&s, 20_000_000, 100);
+    s.client
+        .set_fiat_contribution(&s.admin, &s.oracle, &s.quote_asset, &100, &300, &7);
+    s.client.contribute(&s.members.get(0).unwrap(), &s.token_addr, &50);
+    s.env.ledger().with_mut(|l| l.timestamp = 1000);
+    s.client.contribute(&s.members.get(1).unwrap(), &s.token_addr, &50);
+    assert_eq!(s.client.get_round_rate(), None);
+
+    let member = s.members.get(0).unwrap();
+    let proxy = Address::generate(&s.env);
+    TokenAdminClient::new(&s.env, &s.token_addr).mint(&proxy, &1000);
+    s.client.authorize_proxy(&member, &0, &proxy, &1);
+
+    set_price(&s, 10_000_000, 1100);
+    s.client.contribute_as_proxy(&proxy, &0, &member, &s.token_addr, &100);
+    assert_eq!(s.client.get_round_rate().unwrap().token_amount, 100);
+    assert_eq!(s.token_client.balance(&proxy), 900);
+}
+
+#[test]
+fn test_price_decimals_come_from_the_config() {
+    let s = setup_fiat();
+    // 2 quote units per token at 2 decimals
+    set_price(&s, 200, 100);
+    s.client
+        .set_fiat_contribution(&s.admin, &s.oracle, &s.quote_asset, &100, &300, &2);
+    assert_eq!(s.client.get_round_rate().unwrap().token_amount, 50);
+
+    s.client.clear_fiat_contribution(&s.admin);
+    assert_eq!(
+        s.client
+            .try_set_fiat_contribution(&s.admin, &s.oracle, &s.quote_asset, &100, &300, &19),
+        Err(Ok(soroban_sdk::Error::from(ExtError2::InvalidPriceDecimals)))
+    );
+    assert_eq!(
+        s.client.try_set_fiat_contribution(
+            &s.admin,
+            &s.oracle,
+            &s.quote_asset,
+            &(i128::MAX / 100),
+            &300,
+            &18
+        ),
+        Err(Ok(soroban_sdk::Error::from(ExtError2::ContributionOverflow)))
+    );
+}
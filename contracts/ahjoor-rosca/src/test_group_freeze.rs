@@ -92,7 +92,7 @@ fn test_freeze_blocks_close_round() {
 
     // Advance past deadline
     env.ledger().set_timestamp(100_000);
-    let result = client.try_close_round();
+    let result = client.try_close_round(&admin);
     assert!(result.is_err());
 }
 
@@ -0,0 +1,141 @@
+#![cfg(test)]
+use super::*;
+use soroban_sdk::token::Client as TokenClient;
+use soroban_sdk::token::StellarAssetClient as TokenAdminClient;
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    Address, Env, Vec,
+};
+
+/// Three members contributing 100 per round of 3600 seconds. The keeper pool
+/// holds 100 of the group token: bounty 10, at most 10 per call and 25 per ledger.
+fn setup_keeper<'a>() -> (
+    Env,
+    AhjoorContractClient<'a>,
+    Address,
+    Vec<Address>,
+    TokenClient<'a>,
+) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AhjoorContract, ());
+    let client = AhjoorContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let token_addr = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let token_client = TokenClient::new(&env, &token_addr);
+    let token_admin_client = TokenAdminClient::new(&env, &token_addr);
+    token_admin_client.mint(&admin, &100);
+
+    let mut members = Vec::new(&env);
+    for _ in 0..3 {
+        let m = Address::generate(&env);
+        token_admin_client.mint(&m, &1000);
+        members.push_back(m);
+    }
+
+    client.init(
+        &admin,
+        &members,
+        &100,
+        &token_addr,
+        &3600,
+        &RoscaConfig {
+            strategy: PayoutStrategy::RoundRobin,
+            custom_order: None,
+            penalty_amount: 0,
+            exit_penalty_bps: 0,
+            collective_goal: None,
+            member_goals: None,
+            fee_bps: 0,
+            fee_recipient: None,
+            max_defaults: 3,
+            use_timestamp_schedule: false,
+            round_duration_seconds: 0,
+            max_members: Some(10),
+            skip_fee: 0,
+            max_skips_per_cycle: 0,
+            voting_mode: VotingMode::Equal,
+            grace_period_ledgers: 0,
+            late_fee_bps: 0,
+            grace_period_seconds: 0,
+            auction_enabled: false,
+            auction_window_ledgers: 0,
+            randomize_payout_order: false,
+            reserve_enabled: false,
+            reserve_contribution_bps: 0,
+        },
+        &None,
+    );
+    client.set_keeper_config(&admin, &token_addr, &10, &10, &25);
+    client.fund_keeper_pool(&admin, &100);
+
+    (env, client, admin, members, token_client)
+}
+
+#[test]
+fn test_close_round_pays_keeper_once_per_round() {
+    let (env, client, _admin, _members, token_client) = setup_keeper();
+    let keeper = Address::generate(&env);
+
+    env.ledger().with_mut(|l| l.timestamp = 3700);
+    client.close_round(&keeper);
+    assert_eq!(token_client.balance(&keeper), 10);
+
+    // The next round's deadline has not passed, so there is nothing to close
+    env.ledger().with_mut(|l| l.sequence_number += 1);
+    assert_eq!(
+        client.try_close_round(&keeper),
+        Err(Ok(soroban_sdk::Error::from(Error::DeadlineNotPassed)))
+    );
+    assert_eq!(token_client.balance(&keeper), 10);
+    assert_eq!(client.get_keeper_pool().balance, 90);
+}
+
+#[test]
+fn test_reminder_rewarded_once_per_interval_and_capped_per_ledger() {
+    let (env, client, _admin, _members, token_client) = setup_keeper();
+    let keeper = Address::generate(&env);
+
+    client.emit_deadline_reminder(&keeper, &symbol_short!("24h"));
+    client.emit_deadline_reminder(&keeper, &symbol_short!("24h"));
+    assert_eq!(token_client.balance(&keeper), 10);
+
+    client.emit_deadline_reminder(&keeper, &symbol_short!("1h"));
+    client.emit_deadline_reminder(&keeper, &symbol_short!("30m"));
+    // 10 + 10 + 10 is capped at 25 per ledger
+    assert_eq!(token_client.balance(&keeper), 25);
+
+    assert_eq!(
+        client.try_emit_deadline_reminder(&keeper, &symbol_short!("5m")),
+        Err(Ok(soroban_sdk::Error::from(
+            ExtError2::InvalidReminderInterval
+        )))
+    );
+}
+
+#[test]
+fn test_keeper_pool_is_kept_out_of_payouts() {
+    let (_env, client, admin, members, token_client) = setup_keeper();
+    let recipient = members.get(0).unwrap();
+    for member in members.iter() {
+        client.contribute(&member, &token_client.address, &100);
+    }
+
+    assert_eq!(token_client.balance(&recipient), 1000 - 100 + 300);
+    assert_eq!(token_client.balance(&client.address), 100);
+    assert_eq!(client.get_keeper_pool().balance, 100);
+
+    client.withdraw_keeper_pool(&admin, &100);
+    assert_eq!(token_client.balance(&admin), 100);
+    assert_eq!(
+        client.try_withdraw_keeper_pool(&admin, &1),
+        Err(Ok(soroban_sdk::Error::from(
+            ExtError2::InsufficientKeeperPool
+        )))
+    );
+}
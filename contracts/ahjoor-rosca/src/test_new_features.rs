@@ -902,7 +902,7 @@ fn test_penalise_defaulter_uses_max_defaults() {
     env.ledger().set_timestamp(100);
     client.contribute(&member1, &token_admin, &100);
     env.ledger().set_timestamp(3700);
-    client.close_round();
+    client.close_round(&admin);
 
     // Penalize member2 (first default)
     client.penalise_defaulter(&member2);
@@ -914,7 +914,7 @@ fn test_penalise_defaulter_uses_max_defaults() {
     env.ledger().set_timestamp(4000);
     client.contribute(&member1, &token_admin, &100);
     env.ledger().set_timestamp(7400);
-    client.close_round();
+    client.close_round(&admin);
 
     // Penalize member2 again (second default, should trigger suspension)
     token_admin_client.mint(&member2, &10); // Give penalty amount
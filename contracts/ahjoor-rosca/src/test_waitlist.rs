@@ -94,7 +94,7 @@ fn test_vacancy_filled_from_waitlist_on_exit() {
 // ---------------------------------------------------------------------------
 #[test]
 fn test_empty_waitlist_graceful() {
-    let (env, client, _admin, token_addr, members, _token_client, _token_admin_client) = setup_waitlist();
+    let (env, client, admin, token_addr, members, _token_client, _token_admin_client) = setup_waitlist();
 
     // No one on waitlist — suspension should still work fine
     let member0 = members.get(0).unwrap();
@@ -108,7 +108,7 @@ fn test_empty_waitlist_graceful() {
 
     // Close round — member0 defaults and gets suspended (max_defaults=1)
     env.ledger().with_mut(|l| l.timestamp = 3700);
-    client.close_round();
+    client.close_round(&admin);
 
     // No panic — empty waitlist handled gracefully
     let wl = client.get_waitlist();
@@ -1,11 +1,7 @@
 use soroban_sdk::{contracttype, Address, BytesN, Map, String, Vec};
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-#[contracttype]
-pub enum PayoutStrategy {
-    RoundRobin = 0,
-    AdminAssigned = 1,
-}
+pub use ahjoor_types::keeper::{KeeperConfig, KeeperPool};
+pub use ahjoor_types::rosca::{GroupStatus, PayoutStrategy, RoscaConfig, VotingMode};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[contracttype]
@@ -15,57 +11,6 @@ pub enum DistributionType {
     Weighted = 2,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-#[contracttype]
-pub enum VotingMode {
-    Equal = 0,
-    WeightedByContributions = 1,
-}
-
-#[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct RoscaConfig {
-    pub strategy: PayoutStrategy,
-    pub custom_order: Option<Vec<Address>>,
-    pub penalty_amount: i128,
-    pub exit_penalty_bps: u32,
-    pub collective_goal: Option<i128>,
-    pub member_goals: Option<Map<Address, i128>>,
-    /// Protocol fee in basis points (e.g., 100 = 1%, 500 = 5%). Max 500 bps.
-    pub fee_bps: u32,
-    /// Address that receives protocol fees
-    pub fee_recipient: Option<Address>,
-    /// Number of consecutive missed rounds before suspension (default: 3)
-    pub max_defaults: u32,
-    /// Additional ledgers (time units) before penalties are applied after deadline.
-    pub grace_period_ledgers: u32,
-    pub use_timestamp_schedule: bool,
-    pub round_duration_seconds: u64,
-    pub max_members: Option<u32>,
-    pub skip_fee: i128,
-    pub max_skips_per_cycle: u32,
-    pub voting_mode: VotingMode,
-    /// Late fee in basis points applied to contributions during the grace period.
-    /// Collected from the late contributor and distributed to on-time members.
-    /// 0 = no late fee (grace period is free). Max 1000 bps (10%).
-    pub late_fee_bps: u32,
-    /// Grace period duration in seconds (timestamp-based schedule).
-    /// Used when use_timestamp_schedule = true. 0 = no grace period.
-    pub grace_period_seconds: u64,
-    /// Enable the slot auction mechanism for this group.
-    /// When true, an auction opens at the start of each new cycle.
-    pub auction_enabled: bool,
-    /// Number of ledger timestamps (seconds) the bidding window stays open.
-    /// Ignored when auction_enabled = false.
-    pub auction_window_ledgers: u64,
-    /// Enable verifiable on-chain payout order randomization (#315)
-    pub randomize_payout_order: bool,
-    /// Enable emergency reserve for this group (#313)
-    pub reserve_enabled: bool,
-    /// Surcharge percentage (bps) on each contribution routed to emergency reserve (#313)
-    pub reserve_contribution_bps: u32,
-}
-
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct GroupInfo {
@@ -109,6 +54,10 @@ pub struct MemberStatus {
     pub default_count: u32,
     pub lifetime_contributions: i128,
     pub claimable_rewards: i128,
+    /// Collateral currently held for the member.
+    pub collateral_balance: i128,
+    /// Collateral the member must keep posted for their remaining obligations.
+    pub collateral_required: i128,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -119,6 +68,10 @@ pub enum ProposalType {
     MemberRemoval = 2,
     MaxMembersUpdate = 3,
     Reinstatement = 4, // #218
+    /// ASCA: lend `execution_data` tokens from the pool to `target_member` (the creator).
+    AscaLoan = 5,
+    /// ASCA: set the group interest rate to `execution_data` bps.
+    AscaInterestRate = 6,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -355,6 +308,33 @@ pub enum DataKey3 {
     SlotBidCommit(u32, Address), // (round, bidder) → SealedCommit
     SealedCommitters(u32),     // (round) → Vec<Address> — everyone who committed this round
     SealedRevealedBids(u32),   // (round) → Vec<SlotBid> — valid revealed bids this round
+    // Cross-group credit registry
+    CreditRegistry,            // Address — ahjoor-credit-registry this group reports to
+    MinRegistryScore,          // i128 — minimum registry score required to join this group
+    // Collateral-backed membership
+    CollateralConfig,          // CollateralConfig — per-group collateral requirement
+    MemberCollateral,          // Map<Address, i128> — member → collateral currently held
+    PendingCollateralDraw,     // i128 — collateral drawn since the last CycleRecord was written
+    // Fiat-denominated contributions
+    FiatContribution,          // FiatContributionConfig — oracle and quote-asset amount
+    RoundRate,                 // RoundRate — oracle rate locked for the current round
+}
+
+/// Overflow key enum — DataKey3 is capped at 50 variants by the soroban XDR limit.
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey4 {
+    // ASCA (accumulating savings and credit) mode
+    AscaConfig,                // AscaConfig — set when the group runs in ASCA mode
+    AscaSavings,               // Map<Address, i128> — member → savings deposited this cycle
+    AscaPool,                  // i128 — cash available to lend or share out
+    AscaLoanCounter,           // u32
+    AscaLoan(u32),             // loan_id → AscaLoan
+    ActiveAscaLoans,           // Map<Address, u32> — borrower → active loan_id
+    // Keeper rewards
+    KeeperConfig,              // KeeperConfig — bounty terms for close_round and reminders
+    KeeperPool,                // KeeperPool — pool balance and per-ledger payout tally
+    RewardedReminder(u32, soroban_sdk::Symbol), // bool — (round, reminder interval) already rewarded (persistent)
 }
 
 // ── #330: Contribution Delegation ────────────────────────────────────────────
@@ -492,10 +472,57 @@ pub struct CycleRecord {
     pub penalties_collected: i128,
     pub fee_collected: i128,
     pub insurance_drawn: i128,
+    /// Member collateral drawn to cover missed contributions since the previous record.
+    pub collateral_drawn: i128,
+    /// Oracle price (scaled by 10^price_decimals) the round's contribution was locked at;
+    /// 0 for groups contributing a fixed token amount.
+    pub locked_rate: i128,
     pub cycle_start_timestamp: u64,
     pub cycle_end_timestamp: u64,
 }
 
+// ── Collateral-Backed Membership ───────────────────────────────────────────────
+
+/// Per-group collateral requirement. With `scale_by_position`, the member at
+/// payout position `p` of `n` posts `amount * (n - p) / n`, so early recipients
+/// back more of their outstanding contributions. The requirement then shrinks
+/// pro rata with the rounds left in the cycle.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CollateralConfig {
+    pub amount: i128,
+    pub scale_by_position: bool,
+}
+
+// ── Fiat-Denominated Contributions ─────────────────────────────────────────────
+
+/// Contribution fixed in `quote_asset` units and converted to the group token
+/// through a Reflector-compatible oracle once per round.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FiatContributionConfig {
+    pub oracle: Address,
+    pub quote_asset: Address,
+    /// Contribution per member in quote-asset units.
+    pub quote_amount: i128,
+    /// Maximum age in seconds of an oracle price accepted when locking a round.
+    pub max_oracle_age: u64,
+    /// Decimals the oracle scales its prices by.
+    pub price_decimals: u32,
+}
+
+/// Oracle rate locked for a round. Every contribution in the round uses
+/// `token_amount`, so members contribute equal quote-asset value.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoundRate {
+    pub round: u32,
+    /// Quote-asset price of one token unit, scaled by 10^price_decimals.
+    pub price: i128,
+    pub price_timestamp: u64,
+    pub token_amount: i128,
+}
+
 // --- Emergency Payout Types ---
 
 #[contracttype]
@@ -532,17 +559,6 @@ pub struct EmergencyLoan {
     pub defaulted: bool,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-#[contracttype]
-pub enum GroupStatus {
-    Active = 0,
-    Dissolved = 1,
-    /// Group was merged into another group; all further interactions are rejected.
-    Merged = 2,
-    /// Group was split into two sub-groups; no further operations permitted.
-    Split = 3,
-}
-
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct DissolutionConfig {
@@ -780,3 +796,56 @@ pub struct TreasuryRoundProposal {
     pub votes_against: i128,
     pub confirmed: bool,
 }
+
+// ── ASCA Mode ──────────────────────────────────────────────────────────────────
+
+/// Accumulating savings and credit configuration. Members save into a pool,
+/// borrow from it through `ProposalType::AscaLoan`, and the pool is shared out
+/// in proportion to savings once `cycle_end` has passed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AscaConfig {
+    /// Flat interest charged on each loan's principal, in bps. Changed by
+    /// `ProposalType::AscaInterestRate`.
+    pub interest_bps: u32,
+    /// Number of equal installments a loan is repaid in.
+    pub installments: u32,
+    /// Seconds between installments.
+    pub installment_interval: u64,
+    /// Penalty added per overdue installment, in bps of the installment.
+    pub overdue_penalty_bps: u32,
+    /// Maximum loan as a multiple of the borrower's savings, in bps.
+    pub max_loan_to_savings_bps: u32,
+    /// Timestamp after which the pool can be shared out.
+    pub cycle_end: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[contracttype]
+pub enum AscaLoanStatus {
+    Active = 0,
+    Repaid = 1,
+    /// Closed at share-out by netting the balance against the borrower's share.
+    SettledAtShareOut = 2,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AscaLoan {
+    pub loan_id: u32,
+    pub borrower: Address,
+    pub principal: i128,
+    pub interest_bps: u32,
+    /// Principal plus interest.
+    pub total_due: i128,
+    /// Overdue penalties accrued so far.
+    pub penalties: i128,
+    pub repaid: i128,
+    pub installments: u32,
+    pub installment_interval: u64,
+    pub disbursed_at: u64,
+    /// Installments already penalised as overdue.
+    pub penalised_installments: u32,
+    pub status: AscaLoanStatus,
+}
+
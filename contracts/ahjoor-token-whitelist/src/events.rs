@@ -1,5 +1,7 @@
 use soroban_sdk::{contractevent, Address, BytesN, Env, Symbol};
 
+use crate::ProposalAction;
+
 #[contractevent]
 #[derive(Clone, Debug)]
 pub struct ContractInitialized {
@@ -216,6 +218,31 @@ pub struct ListingVetoed {
     pub reason_hash: BytesN<32>,
 }
 
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct TokenActionProposed {
+    pub proposal_id: u32,
+    pub token: Address,
+    pub proposer: Address,
+    pub action: ProposalAction,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct TokenActionEnacted {
+    pub proposal_id: u32,
+    pub token: Address,
+    pub action: ProposalAction,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct VoteTokensReclaimed {
+    pub proposal_id: u32,
+    pub voter: Address,
+    pub amount: i128,
+}
+
 pub fn emit_listing_proposed(e: &Env, proposal_id: u32, token: Address, proposer: Address) {
     ListingProposed { proposal_id, token, proposer }.publish(e);
 }
@@ -232,5 +259,23 @@ pub fn emit_listing_vetoed(e: &Env, proposal_id: u32, reason_hash: BytesN<32>) {
     ListingVetoed { proposal_id, reason_hash }.publish(e);
 }
 
+pub fn emit_token_action_proposed(
+    e: &Env,
+    proposal_id: u32,
+    token: Address,
+    proposer: Address,
+    action: ProposalAction,
+) {
+    TokenActionProposed { proposal_id, token, proposer, action }.publish(e);
+}
+
+pub fn emit_token_action_enacted(e: &Env, proposal_id: u32, token: Address, action: ProposalAction) {
+    TokenActionEnacted { proposal_id, token, action }.publish(e);
+}
+
+pub fn emit_vote_tokens_reclaimed(e: &Env, proposal_id: u32, voter: Address, amount: i128) {
+    VoteTokensReclaimed { proposal_id, voter, amount }.publish(e);
+}
+
 #[allow(dead_code)]
 fn _use_symbol(_: Symbol) {}
@@ -88,6 +88,8 @@ pub enum DataKey {
     QuorumBps,
     ListingProposal(u32),
     VoteRecord(u32, Address),
+    SuspensionCooldownLedgers,
+    SuspensionCooldown(Address),
 }
 
 #[contracttype]
@@ -100,6 +102,20 @@ pub enum ProposalStatus {
     Vetoed = 4,
 }
 
+/// Change a governance proposal applies to its token when enacted
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[allow(clippy::large_enum_variant)]
+pub enum ProposalAction {
+    List,
+    Delist,
+    AssignTier(u32),
+    SetQuota(TokenQuota),
+    SetMetadata(TokenMetadata),
+    /// Overrides an admin emergency suspension
+    LiftSuspension,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ListingProposal {
@@ -112,11 +128,17 @@ pub struct ListingProposal {
     pub reject_weight: i128,
     pub status: ProposalStatus,
     pub enactment_deadline_ledger: u32,
+    pub action: ProposalAction,
 }
 
 const DEFAULT_VOTING_WINDOW_LEDGERS: u32 = 120_960;
 const DEFAULT_ENACTMENT_DELAY_LEDGERS: u32 = 34_560;
 const DEFAULT_QUORUM_BPS: u32 = 5_000;
+const DEFAULT_SUSPENSION_COOLDOWN_LEDGERS: u32 = 17_280;
+/// Shortest post-lift cooldown the admin may configure (~6 hours).
+pub const MIN_SUSPENSION_COOLDOWN_LEDGERS: u32 = 4_320;
+/// Furthest past the current ledger an extension may push a suspension (~7 days).
+pub const MAX_SUSPENSION_EXTENSION_LEDGERS: u32 = 120_960;
 
 mod events;
 mod client;
@@ -192,13 +214,22 @@ impl TokenWhitelistContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
-        if env.storage().persistent().has(&DataKey::SuspensionRecord(token.clone())) {
-            env.storage().persistent().remove(&DataKey::SuspensionRecord(token.clone()));
-        }
+        Self::clear_token_config(&env, &token);
         env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         events::emit_token_delisted(&env, token, admin);
     }
 
+    /// Drops the suspension, tier, limit override and quota a delisted token
+    /// leaves behind, so a later relisting starts from a clean slate.
+    fn clear_token_config(env: &Env, token: &Address) {
+        let storage = env.storage().persistent();
+        storage.remove(&DataKey::SuspensionRecord(token.clone()));
+        storage.remove(&DataKey::SuspensionCooldown(token.clone()));
+        storage.remove(&DataKey::TokenTier(token.clone()));
+        storage.remove(&DataKey::TokenLimitOverride(token.clone()));
+        storage.remove(&DataKey::TokenQuota(token.clone()));
+    }
+
     pub fn set_risk_tier(env: Env, admin: Address, tier_id: u32, name: soroban_sdk::String, max_single_tx_amount: i128, max_daily_volume: i128) {
         admin.require_auth();
         Self::require_admin(&env, &admin);
@@ -398,6 +429,13 @@ impl TokenWhitelistContract {
         }
         if !in_whitelist { panic!("Token not whitelisted"); }
         let current_ledger = env.ledger().sequence();
+        let cooldown_until: u32 = env
+            .storage().persistent()
+            .get(&DataKey::SuspensionCooldown(token.clone()))
+            .unwrap_or(0);
+        if current_ledger < cooldown_until {
+            panic!("Suspension cooldown active");
+        }
         let maybe_existing: Option<SuspensionRecord> = env
             .storage().persistent()
             .get(&DataKey::SuspensionRecord(token.clone()));
@@ -440,6 +478,8 @@ impl TokenWhitelistContract {
         events::emit_token_suspension_lifted(&env, token, admin, current_ledger);
     }
 
+    /// Push back an active suspension's expiry, at most
+    /// `MAX_SUSPENSION_EXTENSION_LEDGERS` past the current ledger.
     pub fn extend_token_suspension(env: Env, admin: Address, token: Address, additional_ledgers: u32) {
         admin.require_auth();
         Self::require_admin(&env, &admin);
@@ -454,10 +494,14 @@ impl TokenWhitelistContract {
         if current_ledger >= record.expiry_ledger {
             panic!("No active suspension");
         }
+        let expiry_ledger = record.expiry_ledger.saturating_add(additional_ledgers);
+        if expiry_ledger - current_ledger > MAX_SUSPENSION_EXTENSION_LEDGERS {
+            panic!("Suspension extension too long");
+        }
         env.storage().persistent().set(
             &DataKey::SuspensionRecord(token.clone()),
             &SuspensionRecord {
-                expiry_ledger: record.expiry_ledger + additional_ledgers,
+                expiry_ledger,
                 reason_hash: record.reason_hash,
             },
         );
@@ -581,8 +625,7 @@ impl TokenWhitelistContract {
         if env.storage().persistent().has(&DataKey::TokenQuota(token.clone())) {
             panic!("Token already has quota");
         }
-        if max_volume_per_period <= 0 { panic!("max_volume_per_period must be positive"); }
-        if period_ledgers == 0 { panic!("period_ledgers must be positive"); }
+        Self::require_valid_quota(max_volume_per_period, period_ledgers);
         let quota = TokenQuota { max_volume_per_period, period_ledgers };
         env.storage().persistent().set(&DataKey::TokenQuota(token.clone()), &quota);
         env.storage().persistent().extend_ttl(&DataKey::TokenQuota(token.clone()), PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
@@ -598,8 +641,7 @@ impl TokenWhitelistContract {
     ) {
         admin.require_auth();
         Self::require_admin(&env, &admin);
-        if max_volume_per_period <= 0 { panic!("max_volume_per_period must be positive"); }
-        if period_ledgers == 0 { panic!("period_ledgers must be positive"); }
+        Self::require_valid_quota(max_volume_per_period, period_ledgers);
         if !env.storage().persistent().has(&DataKey::TokenQuota(token.clone())) {
             panic!("Token has no quota");
         }
@@ -609,6 +651,11 @@ impl TokenWhitelistContract {
         events::emit_token_quota_set(&env, token, max_volume_per_period, period_ledgers);
     }
 
+    fn require_valid_quota(max_volume_per_period: i128, period_ledgers: u32) {
+        if max_volume_per_period <= 0 { panic!("max_volume_per_period must be positive"); }
+        if period_ledgers == 0 { panic!("period_ledgers must be positive"); }
+    }
+
     pub fn remove_token_quota(env: Env, admin: Address, token: Address) {
         admin.require_auth();
         Self::require_admin(&env, &admin);
@@ -702,6 +749,16 @@ impl TokenWhitelistContract {
         env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
     }
 
+    /// Ledgers after a governance-lifted suspension during which the admin
+    /// cannot suspend the token again. At least `MIN_SUSPENSION_COOLDOWN_LEDGERS`.
+    pub fn set_suspension_cooldown_ledgers(env: Env, admin: Address, ledgers: u32) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+        if ledgers < MIN_SUSPENSION_COOLDOWN_LEDGERS { panic!("Suspension cooldown below minimum"); }
+        env.storage().instance().set(&DataKey::SuspensionCooldownLedgers, &ledgers);
+        env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
     pub fn set_quorum_bps(env: Env, admin: Address, quorum_bps: u32) {
         admin.require_auth();
         Self::require_admin(&env, &admin);
@@ -711,6 +768,41 @@ impl TokenWhitelistContract {
     }
 
     pub fn propose_token_listing(env: Env, proposer: Address, token: Address, rationale_hash: BytesN<32>) -> u32 {
+        Self::create_proposal(&env, proposer, token, ProposalAction::List, rationale_hash)
+    }
+
+    /// Propose a delisting, tier, quota, metadata or suspension-override change
+    /// for a whitelisted token. Shares the listing vote, quorum, delay and veto.
+    pub fn propose_token_action(
+        env: Env,
+        proposer: Address,
+        token: Address,
+        action: ProposalAction,
+        rationale_hash: BytesN<32>,
+    ) -> u32 {
+        if action == ProposalAction::List { panic!("UseProposeTokenListing"); }
+        if !Self::is_whitelisted(env.clone(), token.clone()) { panic!("Token not whitelisted"); }
+        match &action {
+            ProposalAction::SetQuota(quota) => {
+                Self::require_valid_quota(quota.max_volume_per_period, quota.period_ledgers);
+            }
+            ProposalAction::LiftSuspension
+                if Self::get_token_suspension(env.clone(), token.clone()).is_none() =>
+            {
+                panic!("No active suspension");
+            }
+            _ => {}
+        }
+        Self::create_proposal(&env, proposer, token, action, rationale_hash)
+    }
+
+    fn create_proposal(
+        env: &Env,
+        proposer: Address,
+        token: Address,
+        action: ProposalAction,
+        rationale_hash: BytesN<32>,
+    ) -> u32 {
         proposer.require_auth();
         let governance_token: Address = env
             .storage().instance()
@@ -720,7 +812,7 @@ impl TokenWhitelistContract {
             .storage().instance()
             .get(&DataKey::MinProposalStake)
             .unwrap_or(1);
-        let proposer_balance = token::Client::new(&env, &governance_token).balance(&proposer);
+        let proposer_balance = token::Client::new(env, &governance_token).balance(&proposer);
         if proposer_balance < min_stake {
             panic!("InsufficientProposerStake");
         }
@@ -742,12 +834,17 @@ impl TokenWhitelistContract {
             reject_weight: 0,
             status: ProposalStatus::Active,
             enactment_deadline_ledger: 0,
+            action: action.clone(),
         };
         env.storage().persistent().set(&DataKey::ListingProposal(proposal_id), &proposal);
         env.storage().persistent().extend_ttl(&DataKey::ListingProposal(proposal_id), PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
         env.storage().instance().set(&DataKey::ProposalCounter, &(proposal_id + 1));
         env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        events::emit_listing_proposed(&env, proposal_id, token, proposer);
+        if action == ProposalAction::List {
+            events::emit_listing_proposed(env, proposal_id, token, proposer);
+        } else {
+            events::emit_token_action_proposed(env, proposal_id, token, proposer, action);
+        }
         proposal_id
     }
 
@@ -758,8 +855,6 @@ impl TokenWhitelistContract {
             .storage().instance()
             .get(&DataKey::GovernanceToken)
             .expect("GovernanceTokenNotConfigured");
-        let voter_balance = token::Client::new(&env, &governance_token).balance(&voter);
-        if weight > voter_balance { panic!("VoteWeightExceedsBalance"); }
         let mut proposal: ListingProposal = env
             .storage().persistent()
             .get(&DataKey::ListingProposal(proposal_id))
@@ -767,16 +862,28 @@ impl TokenWhitelistContract {
         if proposal.status != ProposalStatus::Active { panic!("ProposalNotActive"); }
         if env.ledger().sequence() > proposal.voting_deadline_ledger { panic!("VotingWindowClosed"); }
         let vote_key = DataKey::VoteRecord(proposal_id, voter.clone());
+        let mut locked: i128 = 0;
         if let Some((prev_approve, prev_weight)) = env
             .storage().persistent()
             .get::<DataKey, (bool, i128)>(&vote_key)
         {
+            locked = prev_weight;
             if prev_approve {
                 proposal.approve_weight = proposal.approve_weight.saturating_sub(prev_weight);
             } else {
                 proposal.reject_weight = proposal.reject_weight.saturating_sub(prev_weight);
             }
         }
+        // Vote weight is escrowed here until the proposal is no longer active, so
+        // the same tokens cannot be moved to another address and voted again.
+        let gov_client = token::Client::new(&env, &governance_token);
+        if weight > locked {
+            let voter_balance = gov_client.balance(&voter);
+            if weight - locked > voter_balance { panic!("VoteWeightExceedsBalance"); }
+            gov_client.transfer(&voter, env.current_contract_address(), &(weight - locked));
+        } else if weight < locked {
+            gov_client.transfer(&env.current_contract_address(), &voter, &(locked - weight));
+        }
         if approve {
             proposal.approve_weight = proposal.approve_weight.saturating_add(weight);
         } else {
@@ -829,26 +936,87 @@ impl TokenWhitelistContract {
             .expect("ProposalNotFound");
         if proposal.status != ProposalStatus::PendingEnactment { panic!("ProposalNotPendingEnactment"); }
         if env.ledger().sequence() <= proposal.enactment_deadline_ledger { panic!("EnactmentDelayNotElapsed"); }
-        let mut whitelist: Vec<Address> = env
-            .storage().persistent()
-            .get(&DataKey::WhitelistedTokens)
-            .unwrap_or_else(|| Vec::new(&env));
-        let mut already_listed = false;
-        for t in whitelist.iter() {
-            if t == proposal.token { already_listed = true; break; }
+        let token = proposal.token.clone();
+        let governance = env.current_contract_address();
+        // The admin may have removed the token while the proposal was pending.
+        if proposal.action != ProposalAction::List && !Self::is_whitelisted(env.clone(), token.clone()) {
+            panic!("Token not whitelisted");
         }
-        if !already_listed {
-            whitelist.push_back(proposal.token.clone());
-            env.storage().persistent().set(&DataKey::WhitelistedTokens, &whitelist);
-            env.storage().persistent().extend_ttl(&DataKey::WhitelistedTokens, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+        match proposal.action.clone() {
+            ProposalAction::List => {
+                let mut whitelist: Vec<Address> = env
+                    .storage().persistent()
+                    .get(&DataKey::WhitelistedTokens)
+                    .unwrap_or_else(|| Vec::new(&env));
+                let mut already_listed = false;
+                for t in whitelist.iter() {
+                    if t == token { already_listed = true; break; }
+                }
+                if !already_listed {
+                    whitelist.push_back(token.clone());
+                    env.storage().persistent().set(&DataKey::WhitelistedTokens, &whitelist);
+                    env.storage().persistent().extend_ttl(&DataKey::WhitelistedTokens, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+                }
+            }
+            ProposalAction::Delist => {
+                let whitelist: Vec<Address> = env
+                    .storage().persistent()
+                    .get(&DataKey::WhitelistedTokens)
+                    .unwrap_or_else(|| Vec::new(&env));
+                let mut new_whitelist = Vec::new(&env);
+                for t in whitelist.iter() {
+                    if t != token { new_whitelist.push_back(t); }
+                }
+                env.storage().persistent().set(&DataKey::WhitelistedTokens, &new_whitelist);
+                env.storage().persistent().extend_ttl(&DataKey::WhitelistedTokens, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+                Self::clear_token_config(&env, &token);
+                events::emit_token_delisted(&env, token.clone(), governance);
+            }
+            ProposalAction::AssignTier(tier_id) => {
+                env.storage().persistent().set(&DataKey::TokenTier(token.clone()), &tier_id);
+                env.storage().persistent().extend_ttl(&DataKey::TokenTier(token.clone()), PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+                events::emit_token_tier_assigned(&env, token.clone(), tier_id);
+            }
+            ProposalAction::SetQuota(quota) => {
+                Self::require_valid_quota(quota.max_volume_per_period, quota.period_ledgers);
+                env.storage().persistent().set(&DataKey::TokenQuota(token.clone()), &quota);
+                env.storage().persistent().extend_ttl(&DataKey::TokenQuota(token.clone()), PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+                events::emit_token_quota_set(&env, token.clone(), quota.max_volume_per_period, quota.period_ledgers);
+            }
+            ProposalAction::SetMetadata(md) => {
+                env.storage().persistent().set(&DataKey::TokenMetadata(token.clone()), &md);
+                env.storage().persistent().extend_ttl(&DataKey::TokenMetadata(token.clone()), PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+                events::emit_token_metadata_set(&env, token.clone(), md.symbol, md.decimals);
+            }
+            ProposalAction::LiftSuspension => {
+                // The suspension may have lapsed during the vote; lifting is then a no-op.
+                if Self::get_token_suspension(env.clone(), token.clone()).is_some() {
+                    env.storage().persistent().remove(&DataKey::SuspensionRecord(token.clone()));
+                    // Keep the admin from immediately reapplying what governance overrode.
+                    let cooldown: u32 = env
+                        .storage().instance()
+                        .get(&DataKey::SuspensionCooldownLedgers)
+                        .unwrap_or(DEFAULT_SUSPENSION_COOLDOWN_LEDGERS);
+                    let cooldown_key = DataKey::SuspensionCooldown(token.clone());
+                    env.storage().persistent().set(&cooldown_key, &(env.ledger().sequence() + cooldown));
+                    env.storage().persistent().extend_ttl(&cooldown_key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+                    events::emit_token_suspension_lifted(&env, token.clone(), governance, env.ledger().sequence());
+                }
+            }
         }
         proposal.status = ProposalStatus::Enacted;
         env.storage().persistent().set(&DataKey::ListingProposal(proposal_id), &proposal);
         env.storage().persistent().extend_ttl(&DataKey::ListingProposal(proposal_id), PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
-        events::emit_listing_enacted(&env, proposal_id, proposal.token);
+        if proposal.action == ProposalAction::List {
+            events::emit_listing_enacted(&env, proposal_id, token);
+        } else {
+            events::emit_token_action_enacted(&env, proposal_id, token, proposal.action);
+        }
         env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
     }
 
+    /// Admin veto of a pending proposal. Proposals lifting a suspension are
+    /// exempt, since they override the admin's own emergency action.
     pub fn veto_listing_proposal(env: Env, admin: Address, proposal_id: u32, reason_hash: BytesN<32>) {
         admin.require_auth();
         Self::require_admin(&env, &admin);
@@ -859,6 +1027,9 @@ impl TokenWhitelistContract {
         if proposal.status == ProposalStatus::Enacted || proposal.status == ProposalStatus::Vetoed {
             panic!("ProposalAlreadyTerminal");
         }
+        if proposal.action == ProposalAction::LiftSuspension {
+            panic!("Suspension lift cannot be vetoed");
+        }
         proposal.status = ProposalStatus::Vetoed;
         env.storage().persistent().set(&DataKey::ListingProposal(proposal_id), &proposal);
         env.storage().persistent().extend_ttl(&DataKey::ListingProposal(proposal_id), PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
@@ -866,6 +1037,35 @@ impl TokenWhitelistContract {
         env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
     }
 
+    /// Return a voter's locked vote weight once the proposal is no longer active
+    pub fn reclaim_vote_tokens(env: Env, voter: Address, proposal_id: u32) -> i128 {
+        voter.require_auth();
+        let proposal: ListingProposal = env
+            .storage().persistent()
+            .get(&DataKey::ListingProposal(proposal_id))
+            .expect("ProposalNotFound");
+        if proposal.status == ProposalStatus::Active { panic!("ProposalStillActive"); }
+        let vote_key = DataKey::VoteRecord(proposal_id, voter.clone());
+        let (_, locked): (bool, i128) = env
+            .storage().persistent()
+            .get(&vote_key)
+            .expect("NoLockedVote");
+        let governance_token: Address = env
+            .storage().instance()
+            .get(&DataKey::GovernanceToken)
+            .expect("GovernanceTokenNotConfigured");
+        env.storage().persistent().remove(&vote_key);
+        token::Client::new(&env, &governance_token).transfer(&env.current_contract_address(), &voter, &locked);
+        events::emit_vote_tokens_reclaimed(&env, proposal_id, voter, locked);
+        env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        locked
+    }
+
+    /// Get a voter's `(approve, locked_weight)` on a proposal
+    pub fn get_vote_record(env: Env, proposal_id: u32, voter: Address) -> Option<(bool, i128)> {
+        env.storage().persistent().get(&DataKey::VoteRecord(proposal_id, voter))
+    }
+
     pub fn get_listing_proposal(env: Env, proposal_id: u32) -> ListingProposal {
         env.storage().persistent()
             .get(&DataKey::ListingProposal(proposal_id))
@@ -876,3 +1076,6 @@ impl TokenWhitelistContract {
         env.storage().instance().get(&DataKey::ProposalCounter).unwrap_or(0)
     }
 }
+
+#[cfg(test)]
+mod test_governance;
@@ -1,5 +1,9 @@
 #![cfg(test)]
-use crate::{ProposalStatus, TokenWhitelistContract, TokenWhitelistContractClient};
+use crate::{
+    ProposalAction, ProposalStatus, TokenMetadata, TokenQuota, TokenWhitelistContract,
+    TokenWhitelistContractClient, MAX_SUSPENSION_EXTENSION_LEDGERS,
+    MIN_SUSPENSION_COOLDOWN_LEDGERS,
+};
 use soroban_sdk::{
     testutils::{Address as _, Ledger},
     Address, BytesN, Env,
@@ -33,7 +37,7 @@ fn setup_governance<'a>() -> (
     (env, admin, client, gov_token)
 }
 
-fn mint_gov_tokens(env: &Env, gov_token: &Address, admin: &Address, to: &Address, amount: i128) {
+fn mint_gov_tokens(env: &Env, gov_token: &Address, _admin: &Address, to: &Address, amount: i128) {
     TokenAdminClient::new(env, gov_token).mint(to, &amount);
 }
 
@@ -240,3 +244,315 @@ fn test_cannot_enact_before_delay() {
     // Enactment delay not elapsed yet
     client.enact_listing(&proposal_id);
 }
+
+#[test]
+fn test_vote_weight_is_locked_in_contract() {
+    let (env, admin, client, gov_token) = setup_governance();
+    let gov = soroban_sdk::token::Client::new(&env, &gov_token);
+
+    let proposer = Address::generate(&env);
+    let voter = Address::generate(&env);
+    let second_wallet = Address::generate(&env);
+    let new_token = Address::generate(&env);
+
+    mint_gov_tokens(&env, &gov_token, &admin, &proposer, 200);
+    mint_gov_tokens(&env, &gov_token, &admin, &voter, 600);
+
+    let rationale = BytesN::from_array(&env, &[9u8; 32]);
+    let proposal_id = client.propose_token_listing(&proposer, &new_token, &rationale);
+    client.vote_listing(&voter, &proposal_id, &true, &600i128);
+
+    assert_eq!(gov.balance(&voter), 0);
+    assert_eq!(gov.balance(&client.address), 600);
+    assert_eq!(client.get_vote_record(&proposal_id, &voter), Some((true, 600)));
+
+    // The locked tokens cannot be moved to a second wallet to vote again
+    assert!(gov.try_transfer(&voter, &second_wallet, &600).is_err());
+    assert!(client.try_vote_listing(&second_wallet, &proposal_id, &true, &600i128).is_err());
+}
+
+#[test]
+fn test_lowering_vote_weight_releases_difference() {
+    let (env, admin, client, gov_token) = setup_governance();
+    let gov = soroban_sdk::token::Client::new(&env, &gov_token);
+
+    let proposer = Address::generate(&env);
+    let voter = Address::generate(&env);
+    let new_token = Address::generate(&env);
+
+    mint_gov_tokens(&env, &gov_token, &admin, &proposer, 200);
+    mint_gov_tokens(&env, &gov_token, &admin, &voter, 800);
+
+    let rationale = BytesN::from_array(&env, &[10u8; 32]);
+    let proposal_id = client.propose_token_listing(&proposer, &new_token, &rationale);
+    client.vote_listing(&voter, &proposal_id, &true, &800i128);
+    client.vote_listing(&voter, &proposal_id, &true, &300i128);
+
+    assert_eq!(gov.balance(&voter), 500);
+    assert_eq!(gov.balance(&client.address), 300);
+    assert_eq!(client.get_listing_proposal(&proposal_id).approve_weight, 300);
+}
+
+#[test]
+fn test_reclaim_vote_tokens_after_finalise() {
+    let (env, admin, client, gov_token) = setup_governance();
+    let gov = soroban_sdk::token::Client::new(&env, &gov_token);
+
+    let proposer = Address::generate(&env);
+    let voter = Address::generate(&env);
+    let new_token = Address::generate(&env);
+
+    mint_gov_tokens(&env, &gov_token, &admin, &proposer, 200);
+    mint_gov_tokens(&env, &gov_token, &admin, &voter, 600);
+
+    let rationale = BytesN::from_array(&env, &[11u8; 32]);
+    let proposal_id = client.propose_token_listing(&proposer, &new_token, &rationale);
+    client.vote_listing(&voter, &proposal_id, &true, &600i128);
+
+    env.ledger().set_sequence_number(env.ledger().sequence() + 101);
+    client.finalise_listing_proposal(&proposal_id);
+
+    assert_eq!(client.reclaim_vote_tokens(&voter, &proposal_id), 600);
+    assert_eq!(gov.balance(&voter), 600);
+    assert_eq!(client.get_vote_record(&proposal_id, &voter), None);
+
+    // A second reclaim has nothing left to return
+    assert!(client.try_reclaim_vote_tokens(&voter, &proposal_id).is_err());
+}
+
+#[test]
+#[should_panic(expected = "ProposalStillActive")]
+fn test_cannot_reclaim_while_proposal_active() {
+    let (env, admin, client, gov_token) = setup_governance();
+
+    let proposer = Address::generate(&env);
+    let voter = Address::generate(&env);
+    let new_token = Address::generate(&env);
+
+    mint_gov_tokens(&env, &gov_token, &admin, &proposer, 200);
+    mint_gov_tokens(&env, &gov_token, &admin, &voter, 600);
+
+    let rationale = BytesN::from_array(&env, &[12u8; 32]);
+    let proposal_id = client.propose_token_listing(&proposer, &new_token, &rationale);
+    client.vote_listing(&voter, &proposal_id, &true, &600i128);
+
+    client.reclaim_vote_tokens(&voter, &proposal_id);
+}
+
+fn pass_action(
+    env: &Env,
+    client: &TokenWhitelistContractClient,
+    gov_token: &Address,
+    admin: &Address,
+    token: &Address,
+    action: ProposalAction,
+) -> u32 {
+    let proposer = Address::generate(env);
+    let voter = Address::generate(env);
+    mint_gov_tokens(env, gov_token, admin, &proposer, 200);
+    mint_gov_tokens(env, gov_token, admin, &voter, 600);
+
+    let rationale = BytesN::from_array(env, &[13u8; 32]);
+    let proposal_id = client.propose_token_action(&proposer, token, &action, &rationale);
+    client.vote_listing(&voter, &proposal_id, &true, &600i128);
+    env.ledger().set_sequence_number(env.ledger().sequence() + 101);
+    client.finalise_listing_proposal(&proposal_id);
+    env.ledger().set_sequence_number(env.ledger().sequence() + 51);
+    proposal_id
+}
+
+#[test]
+fn test_governance_delists_token() {
+    let (env, admin, client, gov_token) = setup_governance();
+    let token = Address::generate(&env);
+    client.add_token(&admin, &token);
+
+    let proposal_id = pass_action(&env, &client, &gov_token, &admin, &token, ProposalAction::Delist);
+    client.enact_listing(&proposal_id);
+
+    assert_eq!(client.get_listing_proposal(&proposal_id).status, ProposalStatus::Enacted);
+    assert!(!client.is_token_allowed(&token));
+}
+
+#[test]
+fn test_governance_changes_tier_quota_and_metadata() {
+    let (env, admin, client, gov_token) = setup_governance();
+    let token = Address::generate(&env);
+    client.add_token(&admin, &token);
+    client.set_risk_tier(&admin, &2u32, &soroban_sdk::String::from_str(&env, "high"), &500i128, &5_000i128);
+
+    let tier = pass_action(&env, &client, &gov_token, &admin, &token, ProposalAction::AssignTier(2));
+    client.enact_listing(&tier);
+    assert_eq!(client.get_token_tier_limits(&token).max_single_tx_amount, 500);
+
+    let quota = TokenQuota { max_volume_per_period: 1_000, period_ledgers: 10 };
+    let quota_id = pass_action(&env, &client, &gov_token, &admin, &token, ProposalAction::SetQuota(quota.clone()));
+    client.enact_listing(&quota_id);
+    assert_eq!(client.get_token_quota(&token), Some(quota));
+
+    let md = TokenMetadata {
+        decimals: 7,
+        symbol: soroban_sdk::String::from_str(&env, "USDC"),
+        logo_hash: BytesN::from_array(&env, &[14u8; 32]),
+        canonical_oracle: None,
+    };
+    let md_id = pass_action(&env, &client, &gov_token, &admin, &token, ProposalAction::SetMetadata(md.clone()));
+    client.enact_listing(&md_id);
+    assert_eq!(client.get_token_metadata(&token), md);
+}
+
+#[test]
+fn test_governance_overrides_emergency_suspension() {
+    let (env, admin, client, gov_token) = setup_governance();
+    let token = Address::generate(&env);
+    client.add_token(&admin, &token);
+
+    // Admin fast-path suspension takes effect immediately
+    client.suspend_token_timed(&admin, &token, &10_000u32, &BytesN::from_array(&env, &[15u8; 32]));
+    assert!(!client.is_token_allowed(&token));
+
+    let proposal_id = pass_action(&env, &client, &gov_token, &admin, &token, ProposalAction::LiftSuspension);
+    client.enact_listing(&proposal_id);
+
+    assert!(client.get_token_suspension(&token).is_none());
+    assert!(client.is_token_allowed(&token));
+}
+
+#[test]
+fn test_admin_can_veto_action_proposal() {
+    let (env, admin, client, gov_token) = setup_governance();
+    let token = Address::generate(&env);
+    client.add_token(&admin, &token);
+
+    let proposal_id = pass_action(&env, &client, &gov_token, &admin, &token, ProposalAction::Delist);
+    client.veto_listing_proposal(&admin, &proposal_id, &BytesN::from_array(&env, &[16u8; 32]));
+
+    assert_eq!(client.get_listing_proposal(&proposal_id).status, ProposalStatus::Vetoed);
+    assert!(client.is_token_allowed(&token));
+}
+
+#[test]
+#[should_panic(expected = "Token not whitelisted")]
+fn test_action_proposal_requires_whitelisted_token() {
+    let (env, admin, client, gov_token) = setup_governance();
+    let token = Address::generate(&env);
+    pass_action(&env, &client, &gov_token, &admin, &token, ProposalAction::Delist);
+}
+
+#[test]
+#[should_panic(expected = "No active suspension")]
+fn test_lift_suspension_proposal_requires_active_suspension() {
+    let (env, admin, client, gov_token) = setup_governance();
+    let token = Address::generate(&env);
+    client.add_token(&admin, &token);
+    pass_action(&env, &client, &gov_token, &admin, &token, ProposalAction::LiftSuspension);
+}
+
+#[test]
+fn test_governance_delist_clears_tier_and_quota() {
+    let (env, admin, client, gov_token) = setup_governance();
+    let token = Address::generate(&env);
+    client.add_token(&admin, &token);
+    client.set_risk_tier(&admin, &2u32, &soroban_sdk::String::from_str(&env, "high"), &500i128, &5_000i128);
+    client.assign_token_tier(&admin, &token, &2u32);
+    client.set_token_limit_override(&admin, &token, &700i128, &7_000i128);
+    client.set_token_quota(&admin, &token, &1_000i128, &10u32);
+
+    let proposal_id = pass_action(&env, &client, &gov_token, &admin, &token, ProposalAction::Delist);
+    client.enact_listing(&proposal_id);
+
+    // Relisting starts from a clean slate
+    client.add_token(&admin, &token);
+    assert_eq!(client.get_token_quota(&token), None);
+    assert_eq!(client.get_token_tier_limits(&token).max_single_tx_amount, 0);
+}
+
+#[test]
+#[should_panic(expected = "Token not whitelisted")]
+fn test_delist_enactment_requires_token_still_listed() {
+    let (env, admin, client, gov_token) = setup_governance();
+    let token = Address::generate(&env);
+    client.add_token(&admin, &token);
+
+    let proposal_id = pass_action(&env, &client, &gov_token, &admin, &token, ProposalAction::Delist);
+    client.remove_token(&admin, &token);
+    client.enact_listing(&proposal_id);
+}
+
+#[test]
+#[should_panic(expected = "Token not whitelisted")]
+fn test_set_quota_enactment_requires_token_still_listed() {
+    let (env, admin, client, gov_token) = setup_governance();
+    let token = Address::generate(&env);
+    client.add_token(&admin, &token);
+
+    let quota = TokenQuota { max_volume_per_period: 1_000, period_ledgers: 10 };
+    let proposal_id = pass_action(&env, &client, &gov_token, &admin, &token, ProposalAction::SetQuota(quota));
+    client.remove_token(&admin, &token);
+    client.enact_listing(&proposal_id);
+}
+
+#[test]
+#[should_panic(expected = "Suspension cooldown active")]
+fn test_admin_cannot_resuspend_during_cooldown_after_governance_lift() {
+    let (env, admin, client, gov_token) = setup_governance();
+    let token = Address::generate(&env);
+    client.add_token(&admin, &token);
+    client.suspend_token_timed(&admin, &token, &10_000u32, &BytesN::from_array(&env, &[15u8; 32]));
+
+    let proposal_id = pass_action(&env, &client, &gov_token, &admin, &token, ProposalAction::LiftSuspension);
+    client.enact_listing(&proposal_id);
+
+    client.suspend_token_timed(&admin, &token, &10_000u32, &BytesN::from_array(&env, &[17u8; 32]));
+}
+
+#[test]
+fn test_admin_can_resuspend_after_cooldown_elapses() {
+    let (env, admin, client, gov_token) = setup_governance();
+    let token = Address::generate(&env);
+    client.add_token(&admin, &token);
+    client.set_suspension_cooldown_ledgers(&admin, &MIN_SUSPENSION_COOLDOWN_LEDGERS);
+    client.suspend_token_timed(&admin, &token, &10_000u32, &BytesN::from_array(&env, &[15u8; 32]));
+
+    let proposal_id = pass_action(&env, &client, &gov_token, &admin, &token, ProposalAction::LiftSuspension);
+    client.enact_listing(&proposal_id);
+
+    env.ledger().set_sequence_number(env.ledger().sequence() + MIN_SUSPENSION_COOLDOWN_LEDGERS);
+    client.suspend_token_timed(&admin, &token, &10_000u32, &BytesN::from_array(&env, &[17u8; 32]));
+    assert!(!client.is_token_allowed(&token));
+}
+
+#[test]
+#[should_panic(expected = "Suspension cooldown below minimum")]
+fn test_suspension_cooldown_has_a_floor() {
+    let (_env, admin, client, _gov_token) = setup_governance();
+    client.set_suspension_cooldown_ledgers(&admin, &(MIN_SUSPENSION_COOLDOWN_LEDGERS - 1));
+}
+
+#[test]
+#[should_panic(expected = "Suspension lift cannot be vetoed")]
+fn test_admin_cannot_veto_suspension_lift() {
+    let (env, admin, client, gov_token) = setup_governance();
+    let token = Address::generate(&env);
+    client.add_token(&admin, &token);
+    client.suspend_token_timed(&admin, &token, &10_000u32, &BytesN::from_array(&env, &[15u8; 32]));
+
+    let proposal_id = pass_action(&env, &client, &gov_token, &admin, &token, ProposalAction::LiftSuspension);
+    client.veto_listing_proposal(&admin, &proposal_id, &BytesN::from_array(&env, &[16u8; 32]));
+}
+
+#[test]
+fn test_suspension_extension_is_capped() {
+    let (env, admin, client, _gov_token) = setup_governance();
+    let token = Address::generate(&env);
+    client.add_token(&admin, &token);
+    client.suspend_token_timed(&admin, &token, &10_000u32, &BytesN::from_array(&env, &[15u8; 32]));
+
+    let now = env.ledger().sequence();
+    client.extend_token_suspension(&admin, &token, &(MAX_SUSPENSION_EXTENSION_LEDGERS - 10_000));
+    assert_eq!(
+        client.get_token_suspension(&token).unwrap().expiry_ledger,
+        now + MAX_SUSPENSION_EXTENSION_LEDGERS
+    );
+    assert!(client.try_extend_token_suspension(&admin, &token, &1u32).is_err());
+}
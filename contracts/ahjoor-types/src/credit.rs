@@ -0,0 +1,27 @@
+// ---------------------------------------------------------------------------
+// Credit registry schema shared by `ahjoor-credit-registry` and the rosca
+// groups that report contribution outcomes to it.
+// ---------------------------------------------------------------------------
+use soroban_sdk::{contractclient, contracttype, Address, Env};
+
+/// Contribution outcome a rosca group reports for one of its members.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[contracttype]
+pub enum CreditOutcome {
+    OnTime = 0,
+    Late = 1,
+    Default = 2,
+    EarlyExit = 3,
+    GroupCompleted = 4,
+}
+
+/// Subset of the `ahjoor-credit-registry` interface used by reporting groups.
+#[contractclient(name = "CreditRegistryClient")]
+pub trait CreditRegistryInterface {
+    /// Records `outcome` for `member`. `reporter` must be on the registry's
+    /// allowlist and authorize the call. Returns the new record id.
+    fn report_outcome(env: Env, reporter: Address, member: Address, outcome: CreditOutcome) -> u64;
+
+    /// Score for `member` across every reporting group.
+    fn get_score(env: Env, member: Address) -> i128;
+}
@@ -0,0 +1,91 @@
+// ---------------------------------------------------------------------------
+// Keeper bounty pool shared by the contracts with permissionless maintenance
+// calls (`ahjoor-payments`, `ahjoor-escrow`, `ahjoor-refund`, `ahjoor-rosca`).
+// Each contract keeps the config and pool in its own instance storage keys.
+// ---------------------------------------------------------------------------
+use soroban_sdk::{contracttype, token, Address, Env, IntoVal, TryFromVal, Val};
+
+/// Admin-set bounty terms for the permissionless maintenance calls.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeeperConfig {
+    /// Token the keeper pool is held and paid in.
+    pub token: Address,
+    /// Reward paid for each item a maintenance call processes.
+    pub bounty_per_item: i128,
+    /// Most a single call can earn, whatever its item count.
+    pub max_reward_per_call: i128,
+    /// Most all calls together can earn within one ledger.
+    pub max_reward_per_ledger: i128,
+}
+
+/// Keeper pool balance and the payout tally for the current ledger.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeeperPool {
+    pub balance: i128,
+    /// Ledger sequence `paid_this_ledger` was counted in.
+    pub ledger: u32,
+    pub paid_this_ledger: i128,
+}
+
+/// The keeper pool stored under `pool_key`, empty if never funded.
+pub fn keeper_pool<K>(env: &Env, pool_key: &K) -> KeeperPool
+where
+    K: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    env.storage()
+        .instance()
+        .get(pool_key)
+        .unwrap_or(KeeperPool {
+            balance: 0,
+            ledger: 0,
+            paid_this_ledger: 0,
+        })
+}
+
+/// Pays `keeper` the bounty for `items` processed by a maintenance call,
+/// capped per call, per ledger and by the pool balance. Returns the amount
+/// paid; 0 when no keeper pool is configured. The caller emits its own
+/// reward event.
+pub fn pay_keeper_reward<K>(
+    env: &Env,
+    config_key: &K,
+    pool_key: &K,
+    keeper: &Address,
+    items: u32,
+) -> i128
+where
+    K: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    let config: KeeperConfig = match env.storage().instance().get(config_key) {
+        Some(c) => c,
+        None => return 0,
+    };
+    let mut pool = keeper_pool(env, pool_key);
+    let ledger = env.ledger().sequence();
+    if pool.ledger != ledger {
+        pool.ledger = ledger;
+        pool.paid_this_ledger = 0;
+    }
+
+    let reward = config
+        .bounty_per_item
+        .saturating_mul(items as i128)
+        .min(config.max_reward_per_call)
+        .min(config.max_reward_per_ledger - pool.paid_this_ledger)
+        .min(pool.balance);
+    if reward <= 0 {
+        return 0;
+    }
+
+    pool.balance -= reward;
+    pool.paid_this_ledger += reward;
+    env.storage().instance().set(pool_key, &pool);
+    token::Client::new(env, &config.token).transfer(
+        &env.current_contract_address(),
+        keeper,
+        &reward,
+    );
+    reward
+}
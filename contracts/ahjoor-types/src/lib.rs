@@ -0,0 +1,30 @@
+#![no_std]
+//! Cross-contract types and client interfaces shared by the Ahjoor contracts.
+//!
+//! Anything that one contract stores and another contract decodes lives here,
+//! so both sides are always compiled against the same XDR schema. Adding,
+//! removing or reordering a field or variant in this crate is a wire-format
+//! change for every contract that depends on it.
+
+pub mod credit;
+pub mod keeper;
+pub mod oracle;
+pub mod payment;
+pub mod refund_reserve;
+pub mod rosca;
+pub mod whitelist;
+
+pub use credit::{CreditOutcome, CreditRegistryClient, CreditRegistryInterface};
+pub use keeper::{KeeperConfig, KeeperPool};
+pub use oracle::{OracleClient, OracleInterface, PriceData};
+pub use payment::{
+    Payment, PaymentContractClient, PaymentContractInterface, PaymentStatus, SplitRecipient,
+};
+pub use refund_reserve::{RefundReserveClient, RefundReserveInterface};
+pub use rosca::{
+    GroupStatus, PayoutStrategy, RoscaConfig, RoscaGroupClient, RoscaGroupInterface, VotingMode,
+};
+pub use whitelist::{TokenWhitelistClient, TokenWhitelistInterface};
+
+#[cfg(test)]
+mod test;
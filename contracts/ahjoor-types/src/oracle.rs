@@ -0,0 +1,21 @@
+// ---------------------------------------------------------------------------
+// Reflector-compatible oracle interface.
+// lastprice(base, quote) returns Option<PriceData> where price is scaled by
+// 10^decimals(). Contracts call it via the generated client.
+// ---------------------------------------------------------------------------
+use soroban_sdk::{contractclient, contracttype, Address, Env};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceData {
+    /// Price scaled by 10^7 (Reflector standard precision)
+    pub price: i128,
+    /// Ledger timestamp of the price update
+    pub timestamp: u64,
+}
+
+/// Minimal oracle client — only the method the Ahjoor contracts need.
+#[contractclient(name = "OracleClient")]
+pub trait OracleInterface {
+    fn lastprice(env: Env, base: Address, quote: Address) -> Option<PriceData>;
+}
@@ -0,0 +1,75 @@
+use soroban_sdk::{
+    contractclient, contracttype, Address, BytesN, Env, Map, String, Symbol, Vec,
+};
+
+/// Lifecycle status of a payment stored by `ahjoor-payments`.
+///
+/// Discriminants are part of the wire format: never renumber an existing
+/// variant, only append new ones.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PaymentStatus {
+    Pending = 0,
+    Completed = 1,
+    Refunded = 2,
+    Disputed = 3,
+    Expired = 4,
+    Authorized = 5,
+    ScheduledPending = 6,
+    /// Awaiting M-of-N multi-sig approval before proceeding.
+    PendingApproval = 7,
+    /// Payment completed; in cooling-off window before final settlement (#309)
+    CoolingOff = 8,
+    /// Payment cancelled during cooling-off period (#309)
+    CancelledInCoolingOff = 9,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SplitRecipient {
+    pub recipient: Address,
+    pub bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Payment {
+    pub id: u32,
+    pub customer: Address,
+    pub merchant: Address,
+    pub amount: i128,
+    pub token: Address,
+    pub status: PaymentStatus,
+    pub created_at: u64,
+    /// Ledger timestamp after which the payment can be expired. 0 = no expiry.
+    pub expires_at: u64,
+    /// Cumulative amount refunded via partial refunds.
+    pub refunded_amount: i128,
+    /// Optional merchant reference string (max 64 bytes) for off-chain reconciliation.
+    pub reference: Option<String>,
+    /// Optional key-value metadata (max 5 keys, each max 32 bytes).
+    pub metadata: Option<Map<String, String>>,
+    /// Optional recipient split definitions (must sum to 10,000 bps).
+    pub split_recipients: Option<Vec<SplitRecipient>>,
+    /// Optional execution timestamp for scheduled payments. 0 = immediate.
+    pub execute_after: u64,
+    /// Optional payment category for on-chain segmentation (#122)
+    pub category: Option<Symbol>,
+    /// Optional tags (max 3) immutable after creation (#122)
+    pub tags: Option<Vec<Symbol>>,
+    /// Ledger sequence after which an authorized payment can no longer be captured. 0 = not authorized.
+    pub capture_deadline: u64,
+    /// Optional off-chain order correlation key (hash of merchant order ID).
+    pub external_id: Option<BytesN<32>>,
+    /// Whether this payment accepts a customer tip at settlement time (#265).
+    pub tipping_enabled: bool,
+    /// Number of times this payment's expiry has been extended.
+    pub extension_count: u32,
+}
+
+/// Read-only view of `ahjoor-payments` used by contracts that act on payments
+/// they did not create (refunds, escrow top-ups, ...).
+#[contractclient(name = "PaymentContractClient")]
+pub trait PaymentContractInterface {
+    fn get_payment(env: Env, payment_id: u32) -> Payment;
+}
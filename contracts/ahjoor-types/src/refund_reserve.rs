@@ -0,0 +1,8 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+/// Refund-reserve view exposed by `ahjoor-refund` and consulted by
+/// `ahjoor-payments` before accepting new payments for a merchant (#334).
+#[contractclient(name = "RefundReserveClient")]
+pub trait RefundReserveInterface {
+    fn check_merchant_reserve(env: Env, merchant: Address) -> bool;
+}
@@ -0,0 +1,95 @@
+// ---------------------------------------------------------------------------
+// ROSCA group schema shared by `ahjoor-rosca` and the contracts that deploy
+// or query rosca groups (e.g. `ahjoor-rosca-factory`).
+// ---------------------------------------------------------------------------
+use soroban_sdk::{contractclient, contracttype, Address, Env, Map, Vec};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[contracttype]
+pub enum PayoutStrategy {
+    RoundRobin = 0,
+    AdminAssigned = 1,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[contracttype]
+pub enum VotingMode {
+    Equal = 0,
+    WeightedByContributions = 1,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoscaConfig {
+    pub strategy: PayoutStrategy,
+    pub custom_order: Option<Vec<Address>>,
+    pub penalty_amount: i128,
+    pub exit_penalty_bps: u32,
+    pub collective_goal: Option<i128>,
+    pub member_goals: Option<Map<Address, i128>>,
+    /// Protocol fee in basis points (e.g., 100 = 1%, 500 = 5%). Max 500 bps.
+    pub fee_bps: u32,
+    /// Address that receives protocol fees
+    pub fee_recipient: Option<Address>,
+    /// Number of consecutive missed rounds before suspension (default: 3)
+    pub max_defaults: u32,
+    /// Additional ledgers (time units) before penalties are applied after deadline.
+    pub grace_period_ledgers: u32,
+    pub use_timestamp_schedule: bool,
+    pub round_duration_seconds: u64,
+    pub max_members: Option<u32>,
+    pub skip_fee: i128,
+    pub max_skips_per_cycle: u32,
+    pub voting_mode: VotingMode,
+    /// Late fee in basis points applied to contributions during the grace period.
+    /// Collected from the late contributor and distributed to on-time members.
+    /// 0 = no late fee (grace period is free). Max 1000 bps (10%).
+    pub late_fee_bps: u32,
+    /// Grace period duration in seconds (timestamp-based schedule).
+    /// Used when use_timestamp_schedule = true. 0 = no grace period.
+    pub grace_period_seconds: u64,
+    /// Enable the slot auction mechanism for this group.
+    /// When true, an auction opens at the start of each new cycle.
+    pub auction_enabled: bool,
+    /// Number of ledger timestamps (seconds) the bidding window stays open.
+    /// Ignored when auction_enabled = false.
+    pub auction_window_ledgers: u64,
+    /// Enable verifiable on-chain payout order randomization (#315)
+    pub randomize_payout_order: bool,
+    /// Enable emergency reserve for this group (#313)
+    pub reserve_enabled: bool,
+    /// Surcharge percentage (bps) on each contribution routed to emergency reserve (#313)
+    pub reserve_contribution_bps: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[contracttype]
+pub enum GroupStatus {
+    Active = 0,
+    Dissolved = 1,
+    /// Group was merged into another group; all further interactions are rejected.
+    Merged = 2,
+    /// Group was split into two sub-groups; no further operations permitted.
+    Split = 3,
+}
+
+/// Subset of the `ahjoor-rosca` interface used to initialise and inspect a
+/// deployed group.
+#[contractclient(name = "RoscaGroupClient")]
+pub trait RoscaGroupInterface {
+    #[allow(clippy::too_many_arguments)]
+    fn init(
+        env: Env,
+        admin: Address,
+        members: Vec<Address>,
+        contribution_amount: i128,
+        token: Address,
+        round_duration: u64,
+        config: RoscaConfig,
+        start_at: Option<u64>,
+    );
+
+    fn get_members(env: Env) -> Vec<Address>;
+
+    fn get_group_status(env: Env) -> GroupStatus;
+}
@@ -0,0 +1,52 @@
+#![cfg(test)]
+use super::*;
+use soroban_sdk::{Env, IntoVal, TryFromVal, Val};
+
+/// Pinned wire codes for `PaymentStatus`. Refund, escrow and rosca decode
+/// payments with these values; if this table has to change, every dependent
+/// contract must be redeployed together with `ahjoor-payments`.
+const PAYMENT_STATUS_CODES: [(PaymentStatus, u32); 10] = [
+    (PaymentStatus::Pending, 0),
+    (PaymentStatus::Completed, 1),
+    (PaymentStatus::Refunded, 2),
+    (PaymentStatus::Disputed, 3),
+    (PaymentStatus::Expired, 4),
+    (PaymentStatus::Authorized, 5),
+    (PaymentStatus::ScheduledPending, 6),
+    (PaymentStatus::PendingApproval, 7),
+    (PaymentStatus::CoolingOff, 8),
+    (PaymentStatus::CancelledInCoolingOff, 9),
+];
+
+#[test]
+fn test_payment_status_wire_codes_are_stable() {
+    let env = Env::default();
+    for (status, code) in PAYMENT_STATUS_CODES.iter() {
+        let val: Val = status.into_val(&env);
+        assert_eq!(u32::try_from_val(&env, &val).unwrap(), *code);
+        assert_eq!(PaymentStatus::try_from_val(&env, &val).unwrap(), *status);
+    }
+}
+
+#[test]
+fn test_unknown_payment_status_code_is_rejected() {
+    let env = Env::default();
+    let val: Val = (PAYMENT_STATUS_CODES.len() as u32).into_val(&env);
+    assert!(PaymentStatus::try_from_val(&env, &val).is_err());
+}
+
+#[test]
+fn test_credit_outcome_wire_codes_are_stable() {
+    let env = Env::default();
+    let codes = [
+        (CreditOutcome::OnTime, 0u32),
+        (CreditOutcome::Late, 1),
+        (CreditOutcome::Default, 2),
+        (CreditOutcome::EarlyExit, 3),
+        (CreditOutcome::GroupCompleted, 4),
+    ];
+    for (outcome, code) in codes.iter() {
+        let val: Val = outcome.into_val(&env);
+        assert_eq!(u32::try_from_val(&env, &val).unwrap(), *code);
+    }
+}
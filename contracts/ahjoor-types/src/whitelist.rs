@@ -0,0 +1,13 @@
+// ---------------------------------------------------------------------------
+// Subset of the `ahjoor-token-whitelist` interface used by contracts that gate
+// their tokens on it. Depending on this client rather than the whitelist crate
+// keeps the whitelist's exported functions out of the caller's wasm.
+// ---------------------------------------------------------------------------
+use soroban_sdk::{contractclient, Address, Env};
+
+#[contractclient(name = "TokenWhitelistClient")]
+pub trait TokenWhitelistInterface {
+    /// Whether `token` may be used by `contract_id`: its contract-level
+    /// allowlist entry if it has one, otherwise the global whitelist.
+    fn is_token_allowed_for_contract(env: Env, contract_id: Address, token: Address) -> bool;
+}